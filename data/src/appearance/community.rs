@@ -0,0 +1,120 @@
+//! Themes shared by the community on the theme website.
+
+use std::path::PathBuf;
+
+use thiserror::Error;
+use tokio::fs;
+
+use super::theme::{self, Styles};
+use crate::config::Config;
+use crate::url::Url;
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub styles: Styles,
+}
+
+impl Theme {
+    /// Parse a theme link copied from the theme website, i.e. a
+    /// `halloy:///theme?e=` url.  Falls back to a generic name when `name`
+    /// is blank.
+    pub fn from_link(name: &str, link: &str) -> Result<Self, Error> {
+        let Ok(Url::Theme { styles, .. }) = link.trim().parse::<Url>() else {
+            return Err(Error::Link);
+        };
+
+        let name = match name.trim() {
+            "" => DEFAULT_NAME.to_string(),
+            name => name.to_string(),
+        };
+
+        Ok(Self { name, styles })
+    }
+}
+
+const DEFAULT_NAME: &str = "Community Theme";
+
+/// Write `theme` to the themes directory, without overwriting an existing
+/// theme of the same name.  Returns the name it was installed under.
+pub async fn install(theme: Theme) -> Result<String, Error> {
+    let dir = Config::themes_dir();
+    let stem = file_stem(&theme.name);
+
+    let mut name = stem.clone();
+    let mut suffix = 1;
+
+    while fs::try_exists(path(&dir, &name)).await? {
+        suffix += 1;
+        name = format!("{stem}-{suffix}");
+    }
+
+    theme.styles.save(path(&dir, &name)).await?;
+
+    Ok(name)
+}
+
+fn path(dir: &std::path::Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.toml"))
+}
+
+/// Theme name made safe for use as a file name.
+fn file_stem(name: &str) -> String {
+    let stem = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+
+    if stem.is_empty() {
+        DEFAULT_NAME.to_string()
+    } else {
+        stem
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("not a theme link")]
+    Link,
+    #[error(transparent)]
+    Save(#[from] theme::Error),
+    #[error("failed to write theme file: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_stem_is_sanitized() {
+        assert_eq!(file_stem("Ferra"), "Ferra");
+        assert_eq!(file_stem(" Solarized Dark "), "Solarized Dark");
+        assert_eq!(file_stem("../evil/theme"), "---evil-theme");
+        assert_eq!(file_stem("/"), "-");
+        assert_eq!(file_stem("   "), "Community Theme");
+    }
+
+    #[test]
+    fn from_link() {
+        let styles = Styles::default();
+        let link = crate::url::theme(&styles);
+
+        let theme = Theme::from_link(" ", &format!(" {link} ")).unwrap();
+        assert_eq!(theme.name, "Community Theme");
+        assert_eq!(theme.styles.encode_base64(), styles.encode_base64());
+
+        let theme = Theme::from_link("Solarized Dark", &link).unwrap();
+        assert_eq!(theme.name, "Solarized Dark");
+
+        assert!(Theme::from_link("", "https://themes.halloy.chat").is_err());
+        assert!(Theme::from_link("", "halloy:///theme?e=invalid").is_err());
+    }
+}
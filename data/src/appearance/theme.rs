@@ -192,6 +192,7 @@ pub struct Buffer {
     pub topic: TextStyle,
     pub url: TextStyle,
     pub nickname_offline: OptionalTextStyle,
+    pub nickname_away: OptionalTextStyle,
     #[serde(with = "color_serde_maybe")]
     pub backlog_rule: Option<Color>,
     #[serde(with = "color_serde_maybe")]
@@ -220,6 +221,7 @@ impl Default for Buffer {
             topic: TextStyle::default(),
             url: TextStyle::default(),
             nickname_offline: OptionalTextStyle::default(),
+            nickname_away: OptionalTextStyle::default(),
             backlog_rule: None,
             backlog_rule_text: None,
             date_rule: None,
@@ -304,6 +306,30 @@ pub struct Formatting {
     pub lightgrey: Option<Color>,
 }
 
+impl Formatting {
+    /// The colors set by the theme which are suited to nicknames, leaving
+    /// out white, black and greys.
+    pub fn palette(&self) -> Vec<Color> {
+        [
+            self.blue,
+            self.green,
+            self.red,
+            self.brown,
+            self.magenta,
+            self.orange,
+            self.yellow,
+            self.lightgreen,
+            self.cyan,
+            self.lightcyan,
+            self.lightblue,
+            self.pink,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct TextStyle {
     pub color: Color,
@@ -566,24 +592,46 @@ pub fn randomize_color(original_color: Color, seed: &str) -> Color {
 }
 
 pub fn nickname_color(
-    original_color: Color,
-    kind: &crate::buffer::Color,
+    styles: &Styles,
+    config: &buffer::nickname::Nickname,
     seed: Option<&str>,
 ) -> Color {
-    match (kind, seed) {
-        (crate::buffer::Color::Solid, _) | (_, None) => original_color,
-        (crate::buffer::Color::Unique, Some(seed)) => {
-            randomize_color(original_color, seed)
+    let original_color = styles.buffer.nickname.color;
+
+    let Some(seed) = seed else {
+        return original_color;
+    };
+
+    let seed = match &config.color_seed {
+        Some(color_seed) => format!("{color_seed}{seed}"),
+        None => seed.to_string(),
+    };
+
+    let palette_color = |colors: &[Color]| {
+        let index =
+            (seahash::hash(seed.as_bytes()) % colors.len() as u64) as usize;
+
+        colors[index]
+    };
+
+    match &config.color {
+        crate::buffer::Color::Solid => original_color,
+        crate::buffer::Color::Unique => randomize_color(original_color, &seed),
+        crate::buffer::Color::Theme => {
+            let colors = styles.formatting.palette();
+
+            if colors.is_empty() {
+                randomize_color(original_color, &seed)
+            } else {
+                palette_color(&colors)
+            }
         }
-        (crate::buffer::Color::Palette(colors), Some(seed)) => {
+        crate::buffer::Color::Palette(colors) => {
             if colors.is_empty() {
                 return original_color;
             }
 
-            let index =
-                (seahash::hash(seed.as_bytes()) % colors.len() as u64) as usize;
-
-            colors[index]
+            palette_color(colors)
         }
     }
 }
@@ -631,6 +679,131 @@ pub fn adapt_nickname_color(
     }
 }
 
+/// Minimum contrast ratio between text and its background (WCAG 2, level
+/// AA for normal text).
+pub const MIN_CONTRAST_RATIO: f32 = 4.5;
+
+/// Number of hues sampled when checking randomized nickname colors.
+const NICKNAME_HUE_SAMPLES: u16 = 36;
+
+/// WCAG 2 contrast ratio between two colors, from 1.0 (identical luminance)
+/// to 21.0 (black on white).
+pub fn contrast_ratio(foreground: Color, background: Color) -> f32 {
+    let foreground = relative_luminance(foreground);
+    let background = relative_luminance(background);
+
+    let (lighter, darker) = if foreground > background {
+        (foreground, background)
+    } else {
+        (background, foreground)
+    };
+
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Lowest contrast ratio of the nickname colors generated from `color`,
+/// which share its saturation and lightness but vary in hue.
+pub fn nickname_contrast_ratio(color: Color, background: Color) -> f32 {
+    nickname_hues(color)
+        .map(|color| contrast_ratio(color, background))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// `color` with the smallest change in lightness needed to reach
+/// [`MIN_CONTRAST_RATIO`] against `background`.
+pub fn fix_contrast(color: Color, background: Color) -> Color {
+    fix_lightness(color, |color| contrast_ratio(color, background))
+}
+
+/// Like [`fix_contrast`], but such that every nickname color generated from
+/// `color` reaches [`MIN_CONTRAST_RATIO`].
+pub fn fix_nickname_contrast(color: Color, background: Color) -> Color {
+    fix_lightness(color, |color| nickname_contrast_ratio(color, background))
+}
+
+fn fix_lightness(color: Color, ratio: impl Fn(Color) -> f32) -> Color {
+    if ratio(color) >= MIN_CONTRAST_RATIO {
+        return color;
+    }
+
+    let hsl = to_hsl(color);
+    let with_lightness = |lightness: f32| {
+        alpha_color(
+            from_hsl(Okhsl::new(hsl.hue, hsl.saturation, lightness)),
+            color.a,
+        )
+    };
+
+    // Search towards white and towards black for the closest lightness that
+    // passes, assuming contrast grows steadily in either direction
+    let search = |target: f32| {
+        if ratio(with_lightness(target)) < MIN_CONTRAST_RATIO {
+            return None;
+        }
+
+        let (mut failing, mut passing) = (hsl.lightness, target);
+
+        for _ in 0..16 {
+            let middle = (failing + passing) / 2.0;
+
+            if ratio(with_lightness(middle)) >= MIN_CONTRAST_RATIO {
+                passing = middle;
+            } else {
+                failing = middle;
+            }
+        }
+
+        Some(passing)
+    };
+
+    let lightness = match (search(1.0), search(0.0)) {
+        (Some(lighter), Some(darker)) => {
+            if lighter - hsl.lightness <= hsl.lightness - darker {
+                lighter
+            } else {
+                darker
+            }
+        }
+        (Some(lightness), None) | (None, Some(lightness)) => lightness,
+        // Unreachable, settle for the most contrast possible
+        (None, None) => {
+            if ratio(with_lightness(1.0)) >= ratio(with_lightness(0.0)) {
+                1.0
+            } else {
+                0.0
+            }
+        }
+    };
+
+    with_lightness(lightness)
+}
+
+fn nickname_hues(color: Color) -> impl Iterator<Item = Color> {
+    let hsl = to_hsl(color);
+
+    (0..NICKNAME_HUE_SAMPLES).map(move |sample| {
+        from_hsl(Okhsl::new(
+            f32::from(sample) * 360.0 / f32::from(NICKNAME_HUE_SAMPLES),
+            hsl.saturation,
+            hsl.lightness,
+        ))
+    })
+}
+
+fn relative_luminance(color: Color) -> f32 {
+    let linear = |channel: f32| {
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+
+    0.2126 * linear(color.r)
+        + 0.7152 * linear(color.g)
+        + 0.0722 * linear(color.b)
+}
+
 pub fn to_hsl(color: Color) -> Okhsl {
     let mut hsl = Okhsl::from_color(to_rgb(color));
     if hsl.saturation.is_nan() {
@@ -847,6 +1020,7 @@ mod binary {
         BufferServerMessagesRequestTopic = 57,
         ButtonsPrimaryBorderActive = 58,
         ButtonsSecondaryBorderActive = 59,
+        BufferNicknameAway = 60,
     }
 
     impl Tag {
@@ -884,6 +1058,7 @@ mod binary {
                 Tag::BufferNicknameOffline => {
                     styles.buffer.nickname_offline.color?
                 }
+                Tag::BufferNicknameAway => styles.buffer.nickname_away.color?,
                 Tag::BufferSelection => styles.buffer.selection,
                 Tag::BufferTimestamp => styles.buffer.timestamp.color,
                 Tag::BufferTopic => styles.buffer.topic.color,
@@ -1021,6 +1196,9 @@ mod binary {
                 Tag::BufferNicknameOffline => {
                     styles.buffer.nickname_offline.color = Some(color);
                 }
+                Tag::BufferNicknameAway => {
+                    styles.buffer.nickname_away.color = Some(color);
+                }
                 Tag::BufferSelection => styles.buffer.selection = color,
                 Tag::BufferTimestamp => styles.buffer.timestamp.color = color,
                 Tag::BufferTopic => styles.buffer.topic.color = color,
@@ -1134,3 +1312,49 @@ mod binary {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contrast_ratio_bounds() {
+        assert!(
+            (contrast_ratio(Color::BLACK, Color::WHITE) - 21.0).abs() < 0.01
+        );
+        assert!(
+            (contrast_ratio(Color::WHITE, Color::WHITE) - 1.0).abs() < 0.01
+        );
+    }
+
+    #[test]
+    fn fix_contrast_reaches_minimum() {
+        let background = Color::from_rgb8(0x20, 0x20, 0x20);
+
+        for color in [
+            Color::from_rgb8(0x30, 0x30, 0x30),
+            Color::from_rgb8(0x40, 0x10, 0x60),
+            Color::from_rgb8(0x00, 0x00, 0x80),
+        ] {
+            let fixed = fix_contrast(color, background);
+
+            assert!(contrast_ratio(fixed, background) >= MIN_CONTRAST_RATIO);
+        }
+
+        let fixed = fix_nickname_contrast(
+            Color::from_rgb8(0x40, 0x40, 0xa0),
+            background,
+        );
+
+        assert!(
+            nickname_contrast_ratio(fixed, background) >= MIN_CONTRAST_RATIO
+        );
+    }
+
+    #[test]
+    fn fix_contrast_keeps_readable_colors() {
+        let color = Color::from_rgb8(0xe0, 0xe0, 0xe0);
+
+        assert_eq!(fix_contrast(color, Color::BLACK), color);
+    }
+}
@@ -36,11 +36,25 @@ impl FromStr for NetworkState {
     }
 }
 
+/// How a connection is bound to one of the bouncer's networks.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub enum Kind {
+    /// `BOUNCER BIND` with the network's id.
+    #[default]
+    Soju,
+    /// A `user/network` username.
+    Znc,
+}
+
 // https://codeberg.org/emersion/soju/src/branch/master/doc/ext/bouncer-networks.md
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BouncerNetwork {
     pub id: String,
     pub name: String,
+    #[serde(default)]
+    pub kind: Kind,
 }
 
 // for ordering, we try to order lexiographically by name, and then check ID
@@ -87,8 +101,26 @@ impl BouncerNetwork {
             name: parameter_map
                 .remove("name")
                 .ok_or(Error::MissingField("name"))?,
+            kind: Kind::Soju,
         })
     }
+
+    /// A ZNC network, which is identified by its name.
+    pub fn znc(name: &str) -> Self {
+        BouncerNetwork {
+            id: name.to_owned(),
+            name: name.to_owned(),
+            kind: Kind::Znc,
+        }
+    }
+}
+
+/// The network name in a row of ZNC's `ListNetworks` table, e.g.
+/// `| libera  | Yes    | irc.libera.chat | ... |`.
+pub fn parse_znc_network(line: &str) -> Option<&str> {
+    let name = line.strip_prefix('|')?.split('|').next()?.trim();
+
+    (!name.is_empty() && name != "Network").then_some(name)
 }
 
 #[cfg(test)]
@@ -101,6 +133,7 @@ mod tests {
             BouncerNetwork {
                 id: 44.to_string(),
                 name: "OFTC".to_owned(),
+                kind: Kind::Soju,
             },
             BouncerNetwork::parse(
                 "44",
@@ -109,4 +142,20 @@ mod tests {
             .unwrap()
         );
     }
+
+    #[test]
+    fn parse_znc_networks() {
+        let tests = [
+            ("+---------+--------+", None),
+            ("| Network | On IRC | IRC Server |", None),
+            ("| libera  | Yes    | irc.libera.chat |", Some("libera")),
+            ("| oftc | No |", Some("oftc")),
+            ("|  | No |", None),
+            ("You are currently disconnected from IRC.", None),
+        ];
+
+        for (line, expected) in tests {
+            assert_eq!(parse_znc_network(line), expected, "{line:?}");
+        }
+    }
 }
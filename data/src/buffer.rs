@@ -1,4 +1,5 @@
 use core::fmt;
+use std::collections::HashSet;
 use std::str::FromStr;
 
 use chrono::Locale;
@@ -9,6 +10,7 @@ pub mod timestamp;
 
 pub use self::timestamp::Timestamp;
 use crate::appearance::theme::hex_to_color;
+use crate::message::{self, Message, source::server};
 use crate::serde::deserialize_strftime_date;
 use crate::target::{self, Target};
 use crate::{Server, channel, config};
@@ -51,6 +53,9 @@ pub enum Internal {
     ChannelDiscovery(Option<Server>),
     #[strum(serialize = "Config Editor")]
     ConfigEditor,
+    Diagnostics,
+    Unread,
+    Ignores,
 }
 
 impl Buffer {
@@ -129,6 +134,9 @@ impl Internal {
         Self::Highlights,
         Self::ChannelDiscovery(None),
         Self::ConfigEditor,
+        Self::Diagnostics,
+        Self::Unread,
+        Self::Ignores,
     ];
 
     pub fn key(&self) -> String {
@@ -138,6 +146,9 @@ impl Internal {
             Internal::Highlights => "highlights",
             Internal::ChannelDiscovery(_) => "channel-discovery",
             Internal::ConfigEditor => "config-editor",
+            Internal::Diagnostics => "diagnostics",
+            Internal::Unread => "unread",
+            Internal::Ignores => "ignores",
         }
         .to_string()
     }
@@ -155,6 +166,9 @@ impl From<config::sidebar::InternalBuffer> for Internal {
             config::sidebar::InternalBuffer::ChannelDiscovery => {
                 Self::ChannelDiscovery(None)
             }
+            config::sidebar::InternalBuffer::Diagnostics => Self::Diagnostics,
+            config::sidebar::InternalBuffer::Unread => Self::Unread,
+            config::sidebar::InternalBuffer::Ignores => Self::Ignores,
         }
     }
 }
@@ -163,12 +177,97 @@ impl From<config::sidebar::InternalBuffer> for Internal {
 #[serde(default)]
 pub struct Settings {
     pub channel: channel::Settings,
+    pub hidden_messages: HiddenMessages,
 }
 
 impl From<config::Buffer> for Settings {
     fn from(config: config::Buffer) -> Self {
         Self {
             channel: channel::Settings::from(config.channel),
+            hidden_messages: HiddenMessages::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageClass {
+    Join,
+    Part,
+    Quit,
+    ChangeNick,
+    ChangeMode,
+    ChangeTopic,
+    Ctcp,
+}
+
+impl MessageClass {
+    pub const CHANNEL: &'static [Self] = &[
+        Self::Join,
+        Self::Part,
+        Self::Quit,
+        Self::ChangeNick,
+        Self::ChangeMode,
+        Self::ChangeTopic,
+    ];
+
+    pub const QUERY: &'static [Self] =
+        &[Self::Quit, Self::ChangeNick, Self::Ctcp];
+
+    fn from_kind(kind: server::Kind) -> Option<Self> {
+        match kind {
+            server::Kind::Join => Some(Self::Join),
+            server::Kind::Part => Some(Self::Part),
+            server::Kind::Quit => Some(Self::Quit),
+            server::Kind::ChangeNick => Some(Self::ChangeNick),
+            server::Kind::ChangeMode => Some(Self::ChangeMode),
+            server::Kind::ChangeTopic => Some(Self::ChangeTopic),
+            server::Kind::Ctcp => Some(Self::Ctcp),
+            server::Kind::JoinTopic
+            | server::Kind::RequestTopic
+            | server::Kind::ChangeHost
+            | server::Kind::MonitoredOnline
+            | server::Kind::MonitoredOffline
+            | server::Kind::StandardReply(_)
+            | server::Kind::WAllOps
+            | server::Kind::Kick
+            | server::Kind::Away
+            | server::Kind::Invite => None,
+        }
+    }
+}
+
+/// Message classes hidden in a single buffer, on top of what
+/// `buffer.server_messages` already hides.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct HiddenMessages(HashSet<MessageClass>);
+
+impl HiddenMessages {
+    pub fn contains(&self, class: MessageClass) -> bool {
+        self.0.contains(&class)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn toggle(&mut self, class: MessageClass) {
+        if !self.0.remove(&class) {
+            self.0.insert(class);
+        }
+    }
+
+    pub fn hides(&self, message: &Message) -> bool {
+        if self.0.is_empty() {
+            return false;
+        }
+
+        if let message::Source::Server(Some(source)) = message.target.source() {
+            MessageClass::from_kind(source.kind())
+                .is_some_and(|class| self.0.contains(&class))
+        } else {
+            false
         }
     }
 }
@@ -266,6 +365,8 @@ pub enum Color {
     Solid,
     #[default]
     Unique,
+    /// The formatting colors of the theme.
+    Theme,
     Palette(Vec<IcedColor>),
 }
 
@@ -285,6 +386,7 @@ impl<'de> Deserialize<'de> for Color {
             Repr::String(value) => match value.as_str() {
                 "solid" => Ok(Self::Solid),
                 "unique" => Ok(Self::Unique),
+                "theme" => Ok(Self::Theme),
                 _ => Err(serde::de::Error::custom(format!(
                     "unknown color: {value}",
                 ))),
@@ -362,7 +464,93 @@ impl Resize {
 
 #[cfg(test)]
 mod tests {
-    use super::Color;
+    use super::{Color, HiddenMessages, MessageClass};
+    use crate::config::buffer::ServerMessages;
+    use crate::message::{self, Message, source::server::Kind};
+    use crate::target;
+    use crate::user::Nick;
+
+    fn query_message(kind: Option<Kind>) -> Message {
+        let query = target::Query::from(Nick::from_str(
+            "dan",
+            crate::isupport::CaseMap::default(),
+        ));
+
+        Message {
+            target: message::Target::Query {
+                query: query.clone(),
+                source: message::Source::Server(kind.map(|kind| {
+                    message::source::Server::new(kind, None, None)
+                })),
+            },
+            ..Message::plugin(
+                Some(target::Target::Query(query)),
+                "← VERSION".to_string(),
+            )
+        }
+    }
+
+    #[test]
+    fn message_classes_from_server_message_kinds() {
+        for (kind, class) in [
+            (Kind::Join, Some(MessageClass::Join)),
+            (Kind::Part, Some(MessageClass::Part)),
+            (Kind::Quit, Some(MessageClass::Quit)),
+            (Kind::ChangeNick, Some(MessageClass::ChangeNick)),
+            (Kind::ChangeMode, Some(MessageClass::ChangeMode)),
+            (Kind::ChangeTopic, Some(MessageClass::ChangeTopic)),
+            (Kind::Ctcp, Some(MessageClass::Ctcp)),
+            (Kind::JoinTopic, None),
+            (Kind::Kick, None),
+            (Kind::Away, None),
+        ] {
+            assert_eq!(MessageClass::from_kind(kind), class);
+        }
+    }
+
+    #[test]
+    fn hidden_messages_hide_their_classes() {
+        let mut hidden = HiddenMessages::default();
+
+        assert!(!hidden.hides(&query_message(Some(Kind::Ctcp))));
+
+        hidden.toggle(MessageClass::Ctcp);
+
+        assert!(hidden.hides(&query_message(Some(Kind::Ctcp))));
+        assert!(!hidden.hides(&query_message(Some(Kind::Quit))));
+        // Server messages without a kind are never hidden
+        assert!(!hidden.hides(&query_message(None)));
+
+        hidden.toggle(MessageClass::Ctcp);
+
+        assert!(hidden.is_empty());
+        assert!(!hidden.hides(&query_message(Some(Kind::Ctcp))));
+    }
+
+    #[test]
+    fn ctcp_messages_default_to_server_messages_without_a_kind() {
+        let server_messages = ServerMessages::default();
+
+        assert_eq!(
+            server_messages.enabled(Some(Kind::Ctcp)),
+            server_messages.enabled(None)
+        );
+        assert_eq!(
+            server_messages.should_drop(Some(Kind::Ctcp)),
+            server_messages.should_drop(None)
+        );
+        assert_eq!(
+            server_messages.dimmed(Some(Kind::Ctcp)).is_some(),
+            server_messages.dimmed(None).is_some()
+        );
+        assert!(
+            !server_messages.condense.kind(&message::source::Server::new(
+                Kind::Ctcp,
+                None,
+                None
+            ))
+        );
+    }
 
     #[derive(Debug, serde::Deserialize)]
     struct Root {
@@ -382,6 +570,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn color_deserializes_theme() {
+        let root: Root =
+            toml::from_str(r#"color = "theme""#).expect("valid theme color");
+
+        assert!(matches!(root.color, Color::Theme));
+    }
+
     #[test]
     fn color_rejects_empty_palette() {
         let err = toml::from_str::<Root>(r#"color = { palette = [] }"#)
@@ -429,27 +625,23 @@ impl From<SkinTone> for emojis::SkinTone {
     }
 }
 
-pub fn deserialize_locale<'de, D>(deserializer: D) -> Result<Locale, D::Error>
+pub fn deserialize_locale<'de, D>(
+    deserializer: D,
+) -> Result<Option<Locale>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let locale_string_maybe: Option<String> =
         Deserialize::deserialize(deserializer)?;
 
-    if let Some(locale_string) = &locale_string_maybe {
-        if let Ok(locale) = Locale::from_str(&locale_string.replace("-", "_")) {
-            Ok(locale)
-        } else {
-            Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Str(locale_string),
-                &"IETF BCP 47 language tag",
-            ))
-        }
-    } else {
-        Ok(sys_locale::get_locale()
-            .and_then(|locale_string| {
-                Locale::from_str(&locale_string.replace("-", "_")).ok()
+    locale_string_maybe
+        .map(|locale_string| {
+            Locale::from_str(&locale_string.replace('-', "_")).map_err(|_| {
+                serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Str(&locale_string),
+                    &"IETF BCP 47 language tag",
+                )
             })
-            .unwrap_or_default())
-    }
+        })
+        .transpose()
 }
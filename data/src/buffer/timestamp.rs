@@ -19,7 +19,7 @@ pub struct Timestamp {
     #[serde(deserialize_with = "deserialize_strftime_date_maybe")]
     pub copy_format: Option<String>,
     #[serde(deserialize_with = "deserialize_locale")]
-    pub locale: Locale,
+    pub locale: Option<Locale>,
     pub hide_consecutive: HideConsecutive,
 }
 
@@ -30,7 +30,7 @@ impl Default for Timestamp {
             brackets: Brackets::default(),
             context_menu_format: "%x".to_string(),
             copy_format: None,
-            locale: Locale::default(),
+            locale: None,
             hide_consecutive: HideConsecutive::default(),
         }
     }
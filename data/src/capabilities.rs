@@ -296,6 +296,12 @@ impl Capabilities {
         self.acknowledged.contains(&cap)
     }
 
+    /// Whether the server is a ZNC bouncer, which lists `znc.in/*`
+    /// capabilities.
+    pub fn is_znc(&self) -> bool {
+        self.listed.keys().any(|cap| cap.starts_with("znc.in/"))
+    }
+
     fn create_request(
         &self,
         capability_str: &'static str,
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::config;
@@ -69,3 +71,108 @@ impl TopicBanner {
         self.enabled = !self.enabled;
     }
 }
+
+/// Channels sharing a prefix, as grouped in the sidebar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grouped<T> {
+    Single(T),
+    Group { prefix: String, channels: Vec<T> },
+}
+
+/// The name of a channel up to the first `-`, `_`, or `.` after its first
+/// character, e.g. `#project` for `#project-dev`.
+pub fn prefix(channel: &str) -> &str {
+    channel
+        .char_indices()
+        .skip(2)
+        .find(|(_, c)| matches!(c, '-' | '_' | '.'))
+        .map_or(channel, |(index, _)| &channel[..index])
+}
+
+/// Groups channels that share a [`prefix`] with at least one other channel,
+/// keeping the order in which each group and channel first appears. Items
+/// without a channel name are never grouped.
+pub fn group_by_prefix<T>(
+    channels: impl IntoIterator<Item = T>,
+    name: impl Fn(&T) -> Option<&str>,
+) -> Vec<Grouped<T>> {
+    let channels = channels.into_iter().collect::<Vec<_>>();
+
+    let key = |channel: &T| {
+        name(channel).map(|name| (prefix(name).to_lowercase(), prefix(name)))
+    };
+
+    let mut counts = HashMap::<String, usize>::new();
+    for (key, _) in channels.iter().filter_map(key) {
+        *counts.entry(key).or_default() += 1;
+    }
+
+    let mut grouped = vec![];
+    let mut groups = HashMap::<String, usize>::new();
+
+    for channel in channels {
+        let Some((key, prefix)) = key(&channel)
+            .filter(|(key, _)| counts.get(key).is_some_and(|count| *count > 1))
+        else {
+            grouped.push(Grouped::Single(channel));
+            continue;
+        };
+        let prefix = prefix.to_string();
+
+        let index = *groups.entry(key).or_insert_with(|| {
+            grouped.push(Grouped::Group {
+                prefix,
+                channels: vec![],
+            });
+            grouped.len() - 1
+        });
+
+        if let Some(Grouped::Group { channels, .. }) = grouped.get_mut(index) {
+            channels.push(channel);
+        }
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_prefix() {
+        assert_eq!(prefix("#project-dev"), "#project");
+        assert_eq!(prefix("#project_ops.old"), "#project");
+        assert_eq!(prefix("#project"), "#project");
+        assert_eq!(prefix("#-dev"), "#-dev");
+        assert_eq!(prefix("##rust-beginners"), "##rust");
+    }
+
+    #[test]
+    fn group_channels_by_prefix() {
+        let grouped = group_by_prefix(
+            [
+                "#halloy",
+                "#project-dev",
+                "#rust",
+                "#Project",
+                "#project-ops",
+                "halloy",
+            ],
+            |channel| channel.starts_with('#').then_some(*channel),
+        );
+
+        assert_eq!(
+            grouped,
+            vec![
+                Grouped::Single("#halloy"),
+                Grouped::Group {
+                    prefix: "#project".to_string(),
+                    channels: vec!["#project-dev", "#Project", "#project-ops"],
+                },
+                Grouped::Single("#rust"),
+                Grouped::Single("halloy"),
+            ]
+        );
+    }
+}
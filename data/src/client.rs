@@ -33,14 +33,15 @@ use crate::isupport::{
     find_target_limit, format_optional_message_reference,
 };
 use crate::message::source;
+use crate::outbox::{self, Outbox};
 use crate::rate_limit::{TokenBucket, TokenPriority};
 use crate::target::{self, Target};
 use crate::time::Posix;
-use crate::user::{ChannelUsers, Nick, NickRef};
+use crate::user::{AccessLevel, ChannelUsers, Nick, NickRef};
 use crate::{
     Server, User, buffer, channel_discovery, compression, config, ctcp, dcc,
     environment, file_transfer, fileupload, history, isupport, message,
-    metadata, mode, server,
+    metadata, mode, moderation_queue, nick_history, plugin, server,
 };
 
 pub mod on_connect;
@@ -52,6 +53,8 @@ const CHATHISTORY_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
 const MODE_REQUEST_DELAY: Duration = Duration::from_millis(600);
 const MODE_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
 const TYPING_TIMEOUT: Duration = Duration::from_secs(6);
+const LAG_SPIKE: Duration = Duration::from_secs(10);
+const NICK_HISTORY_SAVE_AFTER: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Clone, Copy)]
 pub enum Status {
@@ -68,7 +71,13 @@ impl Status {
 
 #[derive(Debug)]
 pub enum State {
-    Disconnected { autoconnect: bool, connecting: bool },
+    Disconnected {
+        autoconnect: bool,
+        connecting: bool,
+        /// Messages sent while disconnected or left in the flood control
+        /// queue when the connection was lost.
+        unsent: Vec<outbox::Message>,
+    },
     Ready(Client),
 }
 
@@ -109,7 +118,7 @@ pub enum Broadcast {
     },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Destination {
     Server,
     Target(Target),
@@ -169,8 +178,16 @@ pub enum Event {
         target: Destination,
         deduplicate: bool,
     },
+    /// Replies to an informational command, shown as a single block.
+    CommandOutput {
+        title: String,
+        messages: Vec<message::Encoded>,
+        our_nick: Nick,
+        target: Destination,
+    },
     Broadcast(Broadcast),
     FileTransferRequest(file_transfer::ReceiveRequest),
+    FileTransferResume(file_transfer::ResumeRequest),
     UpdateReadMarker(Target, ReadMarker),
     JoinedChannel(target::Channel, DateTime<Utc>),
     LoggedIn(DateTime<Utc>),
@@ -187,6 +204,19 @@ pub enum Event {
     UpdateIcon,
 }
 
+impl Event {
+    /// The message from the server which the event was raised for, if any.
+    pub fn encoded(&self) -> Option<&message::Encoded> {
+        match self {
+            Event::Single { message, .. }
+            | Event::PrivOrNotice { message, .. }
+            | Event::WithTarget { message, .. }
+            | Event::DirectMessage(message, ..) => Some(message),
+            _ => None,
+        }
+    }
+}
+
 struct ChatHistoryRequest {
     subcommand: ChatHistorySubcommand,
     requested_at: Instant,
@@ -209,12 +239,20 @@ pub struct Client {
     resolved_host: Option<String>,
     configured_nick: Nick,
     chanmap: IndexMap<target::Channel, Channel>,
+    /// Channels our joins were forwarded to (numeric 470), with the channel
+    /// we asked to join.
+    channel_forwards: HashMap<target::Channel, target::Channel>,
     querymap: IndexMap<target::Query, QueryState>,
     labels: HashMap<String, Context>,
     batches: HashMap<String, Batch>,
     reroute_responses_to: Option<buffer::Upstream>,
+    /// Rerouted replies to an informational command, gathered until its
+    /// final reply.
+    command_output: Vec<Event>,
     logged_in: bool,
     away: bool,
+    /// Messages composed while disconnected, sent once registered.
+    deferred: Vec<outbox::Message>,
     registration_step: RegistrationStep,
     capabilities: Capabilities,
     features: Features,
@@ -229,6 +267,9 @@ pub struct Client {
     isupport: HashMap<isupport::Kind, isupport::Parameter>,
     who_queue: who_queue::WhoQueue,
     resolved_netid: Option<String>,
+    /// Rows of ZNC's `ListNetworks` table read so far, while it's being
+    /// listed.
+    znc_networks_listed: Option<usize>,
     anti_flood: Option<TokenBucket<message::Encoded>>,
     mode_requests: Vec<ModeRequest>,
     metadata_sub_requests: HashSet<String>,
@@ -237,6 +278,10 @@ pub struct Client {
     http_client: Option<Arc<reqwest::Client>>, // Only Some if config.proxy.is_some()
     registry: metadata::ServerRegistry,
     monitored_users: HashMap<User, MonitoredUser>,
+    lag: Option<Duration>,
+    nick_history: nick_history::NickHistory,
+    // When the nick history last changed, until it's saved.
+    nick_history_changed_at: Option<Instant>,
 }
 
 impl fmt::Debug for Client {
@@ -276,12 +321,15 @@ impl Client {
             ),
             alt_nick: None,
             chanmap: IndexMap::default(),
+            channel_forwards: HashMap::new(),
             querymap: IndexMap::default(),
             labels: HashMap::new(),
             batches: HashMap::new(),
             reroute_responses_to: None,
+            command_output: vec![],
             logged_in: false,
             away: false,
+            deferred: vec![],
             registration_step: RegistrationStep::Start,
             capabilities: Capabilities::default(),
             features: Features::default(),
@@ -297,7 +345,11 @@ impl Client {
             isupport: HashMap::new(),
             who_queue: who_queue::WhoQueue::new(&config),
             resolved_netid: None,
-            anti_flood: Some(TokenBucket::new(config.anti_flood, 10)),
+            znc_networks_listed: None,
+            anti_flood: Some(TokenBucket::new(
+                config.anti_flood,
+                config.anti_flood_burst,
+            )),
             mode_requests: Vec::new(),
             metadata_sub_requests: HashSet::new(),
             metadata_syncs: BinaryHeap::new(),
@@ -306,6 +358,9 @@ impl Client {
             channel_discovery_manager: channel_discovery::Manager::new(),
             registry: metadata::ServerRegistry::new(),
             monitored_users: HashMap::new(),
+            lag: None,
+            nick_history: nick_history::NickHistory::default(),
+            nick_history_changed_at: None,
         }
     }
 
@@ -598,7 +653,16 @@ impl Client {
         if let MODE(target, _, _) = command {
             !self.is_channel(target)
         } else {
-            matches!(command, WHO(..) | WHOIS(..) | WHOWAS(..) | INVITE(..))
+            matches!(
+                command,
+                WHO(..)
+                    | WHOIS(..)
+                    | WHOWAS(..)
+                    | INVITE(..)
+                    | NAMES(..)
+                    | LINKS
+                    | ADMIN(..)
+            )
         }
     }
 
@@ -652,7 +716,10 @@ impl Client {
                         | ERR_NOSUCHCHANNEL
                         | ERR_NOTONCHANNEL
                         | ERR_CHANOPRIVSNEEDED
-                        | ERR_USERONCHANNEL,
+                        | ERR_USERONCHANNEL
+                        | RPL_ENDOFNAMES
+                        | RPL_ENDOFLINKS
+                        | RPL_ADMINEMAIL,
                     _
                 )
             ),
@@ -690,6 +757,26 @@ impl Client {
         })
     }
 
+    /// User messages still deferred or waiting on flood control.
+    fn unsent(&self) -> Vec<outbox::Message> {
+        self.deferred
+            .iter()
+            .cloned()
+            .chain(
+                self.anti_flood
+                    .iter()
+                    .flat_map(TokenBucket::user_tokens)
+                    .filter_map(outbox::Message::from_encoded),
+            )
+            .collect()
+    }
+
+    fn send_deferred(&mut self) {
+        for message in std::mem::take(&mut self.deferred) {
+            self.send(None, message.encoded(), TokenPriority::User);
+        }
+    }
+
     fn send(
         &mut self,
         buffer: Option<&buffer::Upstream>,
@@ -973,15 +1060,101 @@ impl Client {
 
         let stop_reroute = self.stop_reroute(&message);
 
-        let events = self.handle(message, None, config)?;
+        let nick_history_reply = self.whois_nick_history_reply(&message);
+
+        let mut events = self.handle(message, None, config)?;
+
+        // Follow the WHOIS reply with the user's previous nicknames, routed to
+        // the same buffer.
+        if let Some(reply) = nick_history_reply {
+            let reply_event = events.iter().find_map(|event| match event {
+                Event::Single {
+                    our_nick,
+                    deduplicate,
+                    ..
+                } => Some(Event::Single {
+                    message: reply.clone(),
+                    our_nick: our_nick.clone(),
+                    deduplicate: *deduplicate,
+                }),
+                Event::WithTarget {
+                    our_nick,
+                    target,
+                    deduplicate,
+                    ..
+                } => Some(Event::WithTarget {
+                    message: reply.clone(),
+                    our_nick: our_nick.clone(),
+                    target: target.clone(),
+                    deduplicate: *deduplicate,
+                }),
+                _ => None,
+            });
+
+            events.extend(reply_event);
+        }
+
+        if self.reroute_responses_to.is_some() {
+            let (output, rest) =
+                events.into_iter().partition(is_command_output_event);
+
+            self.command_output.extend(output);
+            events = rest;
+        }
 
         if stop_reroute {
             self.reroute_responses_to = None;
+
+            events
+                .extend(gather_command_output(take(&mut self.command_output)));
         }
 
         Ok(events)
     }
 
+    fn whois_nick_history_reply(
+        &self,
+        message: &message::Encoded,
+    ) -> Option<message::Encoded> {
+        use irc::proto::command::Numeric::*;
+
+        let Command::Numeric(RPL_WHOISUSER, params) = &message.command else {
+            return None;
+        };
+
+        let nick = Nick::from_str(params.get(1)?, self.casemapping());
+        let userhost = format!("{}@{}", params.get(2)?, params.get(3)?);
+
+        let account = self
+            .chanmap
+            .values()
+            .find_map(|channel| channel.users.get_by_nick(nick.as_nickref()))
+            .and_then(User::accountname);
+
+        let previous = self.nick_history.previous(
+            account,
+            Some(userhost),
+            nick.as_nickref(),
+        );
+
+        if previous.is_empty() {
+            return None;
+        }
+
+        Some(message::Encoded(proto::Message {
+            tags: message.tags.clone(),
+            source: message.source.clone(),
+            command: Command::Numeric(
+                RPL_WHOISSPECIAL,
+                vec![
+                    params.first()?.clone(),
+                    params.get(1)?.clone(),
+                    format!("was: {}", previous.join(", ")),
+                ],
+            ),
+        }))
+    }
+
     fn handle(
         &mut self,
         mut message: message::Encoded,
@@ -994,6 +1167,10 @@ impl Client {
 
         let batch_tag = message.tags.remove("batch");
 
+        if batch_tag.is_none() {
+            self.detect_users_drift(&message);
+        }
+
         let context = parent_context.or(label_tag
             .as_ref()
             // Remove context associated to label if we get resp for it
@@ -1230,6 +1407,14 @@ impl Client {
                                     | None => (),
                                 };
 
+                                if let Some(BatchKind::LabeledResponse) =
+                                    finished.kind
+                                {
+                                    return Ok(gather_command_output(
+                                        finished.events,
+                                    ));
+                                }
+
                                 return Ok(finished.events);
                             }
                         }
@@ -1395,6 +1580,24 @@ impl Client {
                 self.capabilities
                     .acknowledge(caps.split(' ').map(String::from));
 
+                // Users of channels joined before these were acknowledged
+                // (e.g. after `CAP NEW`) are missing hostmasks or accounts
+                if caps.split(' ').any(|cap| {
+                    matches!(
+                        cap.parse(),
+                        Ok(Capability::UserhostInNames
+                            | Capability::AccountNotify
+                            | Capability::ExtendedJoin)
+                    )
+                }) {
+                    for channel in self.chanmap.keys() {
+                        self.who_queue.queue_join_who_request(
+                            &self.capabilities,
+                            channel,
+                        );
+                    }
+                }
+
                 if self.registration_step == RegistrationStep::Req {
                     if let Some(sasl) = self.config.sasl.as_ref().filter(|_| {
                         self.capabilities.acknowledged(Capability::Sasl)
@@ -1553,6 +1756,14 @@ impl Client {
                     });
                 }
             }
+            Command::PRIVMSG(_, text) | Command::NOTICE(_, text)
+                if self.znc_networks_listed.is_some()
+                    && message.user(self.casemapping()).is_some_and(
+                        |user| user.nickname().as_str() == "*status",
+                    ) =>
+            {
+                return Ok(self.handle_znc_network_listing(text));
+            }
             Command::PRIVMSG(_, text) | Command::NOTICE(_, text) => {
                 if let Some(user) = message.user(self.casemapping()) {
                     let is_echo = user.nickname() == self.nickname();
@@ -1580,25 +1791,48 @@ impl Client {
                             return Ok(vec![]);
                         }
 
-                        match command {
-                            dcc::Command::Send(request) => {
+                        let accepted =
+                            matches!(command, dcc::Command::Accept(_));
+
+                        let (request, secure) = match command {
+                            dcc::Command::Send(request) => (request, false),
+                            dcc::Command::SecureSend(request) => {
+                                (request, true)
+                            }
+                            dcc::Command::Resume(resume)
+                            | dcc::Command::Accept(resume) => {
                                 log::trace!(
-                                    "[{}] DCC Send => {request:?}",
-                                    self.server
+                                    "[{}] DCC {} => {resume:?}",
+                                    self.server,
+                                    if accepted { "Accept" } else { "Resume" }
                                 );
-                                return Ok(vec![Event::FileTransferRequest(
-                                    file_transfer::ReceiveRequest {
+                                return Ok(vec![Event::FileTransferResume(
+                                    file_transfer::ResumeRequest {
                                         from: user,
-                                        dcc_send: request,
+                                        resume,
+                                        accepted,
                                         server: self.server.clone(),
-                                        server_handle: self.handle.clone(),
                                     },
                                 )]);
                             }
                             dcc::Command::Unsupported(command) => {
                                 bail!("Unsupported DCC command: {command}",);
                             }
-                        }
+                        };
+
+                        log::trace!(
+                            "[{}] DCC Send (secure: {secure}) => {request:?}",
+                            self.server
+                        );
+                        return Ok(vec![Event::FileTransferRequest(
+                            file_transfer::ReceiveRequest {
+                                from: user,
+                                dcc_send: request,
+                                secure,
+                                server: self.server.clone(),
+                                server_handle: self.handle.clone(),
+                            },
+                        )]);
                     };
 
                     // CTCP Handling
@@ -1624,10 +1858,12 @@ impl Client {
                             }
 
                             // Response to a client sending us a CTCP request
-                            if matches!(
-                                &message.command,
-                                Command::PRIVMSG(_, _)
-                            ) {
+                            if config.ctcp.enabled
+                                && matches!(
+                                    &message.command,
+                                    Command::PRIVMSG(_, _)
+                                )
+                            {
                                 match query.command {
                                     ctcp::Command::Action => (),
                                     ctcp::Command::ClientInfo => {
@@ -1636,7 +1872,9 @@ impl Client {
                                             ctcp::response_message(
                                                 &query.command,
                                                 user.nickname().to_string(),
-                                                Some(config.ctcp.client_info()),
+                                                Some(config.ctcp.client_info(
+                                                    &plugin::ctcp_commands(),
+                                                )),
                                             )
                                             .into(),
                                             TokenPriority::High,
@@ -1675,13 +1913,17 @@ impl Client {
                                         }
                                     }
                                     ctcp::Command::Source => {
-                                        if config.ctcp.source {
+                                        if let Some(response) = config
+                                            .ctcp
+                                            .source
+                                            .response(SOURCE_WEBSITE)
+                                        {
                                             self.send(
                                                 None,
                                                 ctcp::response_message(
                                                     &query.command,
                                                     user.nickname().to_string(),
-                                                    Some(SOURCE_WEBSITE),
+                                                    Some(response),
                                                 )
                                                 .into(),
                                                 TokenPriority::High,
@@ -1689,15 +1931,17 @@ impl Client {
                                         }
                                     }
                                     ctcp::Command::Version => {
-                                        if config.ctcp.version {
+                                        if let Some(response) =
+                                            config.ctcp.version.response(
+                                                format!("Halloy {VERSION}"),
+                                            )
+                                        {
                                             self.send(
                                                 None,
                                                 ctcp::response_message(
                                                     &query.command,
                                                     user.nickname().to_string(),
-                                                    Some(format!(
-                                                        "Halloy {VERSION}"
-                                                    )),
+                                                    Some(response),
                                                 )
                                                 .into(),
                                                 TokenPriority::High,
@@ -1726,10 +1970,30 @@ impl Client {
                                         }
                                     }
                                     ctcp::Command::Unknown(command) => {
-                                        log::debug!(
-                                            "[{}] Ignoring CTCP command {command}: Unknown command",
-                                            self.server
-                                        );
+                                        if let Some(response) = plugin::ctcp(
+                                            &self.server,
+                                            user.nickname().as_str(),
+                                            &command,
+                                            query.params,
+                                        ) {
+                                            self.send(
+                                                None,
+                                                ctcp::response_message(
+                                                    &ctcp::Command::Unknown(
+                                                        command,
+                                                    ),
+                                                    user.nickname().to_string(),
+                                                    Some(response),
+                                                )
+                                                .into(),
+                                                TokenPriority::High,
+                                            );
+                                        } else {
+                                            log::debug!(
+                                                "[{}] Ignoring CTCP command {command}: Unknown command",
+                                                self.server
+                                            );
+                                        }
                                     }
                                 }
                             }
@@ -1750,6 +2014,42 @@ impl Client {
                         );
                     }
 
+                    if !is_echo
+                        && config.buffer.channel.moderation_queue.enabled
+                        && let Some(args) = moderation_queue::parse(
+                            text,
+                            &config.buffer.channel.moderation_queue.patterns,
+                        )
+                    {
+                        self.queue_voice_request(
+                            &user,
+                            &message.command,
+                            &args,
+                            text,
+                            message.server_time_or_now().0,
+                        );
+                    }
+
+                    // Plugins only see PRIVMSG, so replies to a NOTICE
+                    // can't loop
+                    if !is_echo
+                        && matches!(message.command, Command::PRIVMSG(..))
+                    {
+                        let target = self
+                            .message_channel_target(&message.command)
+                            .map_or_else(
+                                || user.nickname().to_string(),
+                                |channel| channel.to_string(),
+                            );
+
+                        plugin::incoming(
+                            &self.server,
+                            &target,
+                            user.nickname().as_str(),
+                            text,
+                        );
+                    }
+
                     let user_query = target::Query::from(&user);
                     let direct_message = self
                         .message_query_target(&message.command)
@@ -1844,6 +2144,18 @@ impl Client {
                 let new_nick =
                     Nick::from_str(nick.as_str(), self.casemapping());
 
+                // Prefer the channel user, which knows the account name.
+                let known_user = self
+                    .chanmap
+                    .values()
+                    .find_map(|channel| channel.users.resolve(&old_user))
+                    .filter(|user| user.accountname().is_some())
+                    .unwrap_or(&old_user);
+
+                if self.nick_history.record(known_user, new_nick.as_nickref()) {
+                    self.nick_history_changed_at = Some(Instant::now());
+                }
+
                 self.chanmap.values_mut().for_each(|channel| {
                     if let Some(user) = channel.users.take(&old_user) {
                         channel
@@ -1864,6 +2176,24 @@ impl Client {
                     received_with_server_time,
                 })]);
             }
+            Command::Numeric(ERR_LINKCHANNEL, args) => {
+                let from = ok!(args.get(1));
+                let to = ok!(args.get(2));
+
+                if let Ok(from) = target::Channel::parse(
+                    from,
+                    self.chantypes(),
+                    self.statusmsg(),
+                    self.casemapping(),
+                ) && let Ok(to) = target::Channel::parse(
+                    to,
+                    self.chantypes(),
+                    self.statusmsg(),
+                    self.casemapping(),
+                ) {
+                    self.channel_forwards.insert(to, from);
+                }
+            }
             Command::Numeric(ERR_NICKNAMEINUSE | ERR_ERRONEUSNICKNAME, _)
                 if self.resolved_nick.is_none() =>
             {
@@ -1935,6 +2265,7 @@ impl Client {
                     ));
 
                     self.chanmap.shift_remove(&target_channel);
+                    self.channel_forwards.remove(&target_channel);
 
                     self.who_queue.parted_channel(&target_channel);
                 } else if let Some(channel) =
@@ -2032,6 +2363,7 @@ impl Client {
                         == self.nickname().as_normalized_str()
                     {
                         self.chanmap.shift_remove(&channel);
+                        self.channel_forwards.remove(&channel);
 
                         let (server_time, received_with_server_time) =
                             message.server_time_or_now();
@@ -2264,7 +2596,13 @@ impl Client {
                                 && let Some(mut user) =
                                     channel.users.take(&lookup)
                             {
-                                user.update_access_level(op, *mode.value());
+                                user.update_access_level(
+                                    op,
+                                    *mode.value(),
+                                    isupport::get_prefix_or_default(
+                                        &self.isupport,
+                                    ),
+                                );
                                 channel.users.insert(user);
                             } else {
                                 channel_mode_changed = true;
@@ -2375,7 +2713,11 @@ impl Client {
                                 }
                             }
 
-                            channel.users.insert(user);
+                            if let Some(users) = &mut channel.users_refresh {
+                                users.insert(user);
+                            } else {
+                                channel.users.insert(user);
+                            }
                         }
                     }
 
@@ -2387,7 +2729,8 @@ impl Client {
                     }
 
                     // Don't save to history if names list was triggered by JOIN
-                    if !channel.users_init {
+                    // or by a user list refresh
+                    if !channel.users_init || channel.users_refresh.is_some() {
                         return Ok(vec![]);
                     }
                 }
@@ -2411,6 +2754,22 @@ impl Client {
 
                     return Ok(vec![]);
                 }
+
+                if let Some(channel) = self.chanmap.get_mut(&target)
+                    && let Some(users) = channel.users_refresh.take()
+                {
+                    log::debug!(
+                        "[{}] Refreshed user list of {target}",
+                        self.server
+                    );
+
+                    channel.users.replace(users);
+                    channel.users_stale = false;
+
+                    self.who_queue.refresh_who_poll(&self.server, &target);
+
+                    return Ok(vec![]);
+                }
             }
             Command::TOPIC(channel, topic) => {
                 let casemapping = self.casemapping();
@@ -2574,6 +2933,80 @@ impl Client {
                     }
                 }
             }
+            Command::Numeric(numeric @ (RPL_BANLIST | RPL_INVEXLIST), args) => {
+                let channel = ok!(args.get(1));
+
+                if let Ok(target_channel) = target::Channel::parse(
+                    channel,
+                    self.chantypes(),
+                    self.statusmsg(),
+                    self.casemapping(),
+                ) && let Some(channel) =
+                    self.chanmap.get_mut(&target_channel)
+                {
+                    let entry = MaskEntry {
+                        mask: ok!(args.get(2)).clone(),
+                        set_by: args.get(3).cloned(),
+                        set_at: args
+                            .get(4)
+                            .and_then(|ts| ts.parse::<u64>().ok())
+                            .and_then(|ts| Posix::from_seconds(ts).datetime()),
+                    };
+
+                    if *numeric == RPL_BANLIST {
+                        channel.bans.receiving.push(entry);
+                    } else {
+                        channel.invite_exceptions.receiving.push(entry);
+                    }
+                }
+            }
+            Command::Numeric(
+                numeric @ (RPL_ENDOFBANLIST | RPL_ENDOFINVEXLIST),
+                args,
+            ) => {
+                let channel = ok!(args.get(1));
+
+                if let Ok(target_channel) = target::Channel::parse(
+                    channel,
+                    self.chantypes(),
+                    self.statusmsg(),
+                    self.casemapping(),
+                ) && let Some(channel) =
+                    self.chanmap.get_mut(&target_channel)
+                {
+                    if *numeric == RPL_ENDOFBANLIST {
+                        channel.bans.received();
+                    } else {
+                        channel.invite_exceptions.received();
+                    }
+                }
+            }
+            Command::Numeric(RPL_KNOCK, args) => {
+                let channel = ok!(args.get(1));
+                let mask = ok!(args.get(2));
+                let casemapping = self.casemapping();
+
+                if config.buffer.channel.moderation_queue.enabled
+                    && let Ok(target_channel) = target::Channel::parse(
+                        channel,
+                        self.chantypes(),
+                        self.statusmsg(),
+                        casemapping,
+                    )
+                    && let Some(channel) = self.chanmap.get_mut(&target_channel)
+                    && let Ok(user) = User::parse(mask, casemapping, None)
+                {
+                    channel.moderation_queue.push(
+                        &channel.users,
+                        moderation_queue::Request {
+                            user,
+                            kind: moderation_queue::Kind::Knock,
+                            text: args.get(3).cloned(),
+                            received_at: message.server_time_or_now().0,
+                        },
+                    );
+                }
+            }
             Command::Numeric(RPL_CREATIONTIME, args) => {
                 let channel = ok!(args.get(1));
 
@@ -3166,6 +3599,13 @@ impl Client {
                 {
                     self.handle
                         .try_send(command!("BOUNCER", "LISTNETWORKS"))?;
+                } else if self.is_primary() && self.capabilities.is_znc() {
+                    self.handle.try_send(command!(
+                        "PRIVMSG",
+                        "*status",
+                        "ListNetworks"
+                    ))?;
+                    self.znc_networks_listed = Some(0);
                 }
 
                 // request metadata
@@ -3224,6 +3664,10 @@ impl Client {
                     }
                 }
 
+                // Queued after any JOINs, so deferred channel messages
+                // arrive once we're back in the channel
+                self.send_deferred();
+
                 if !self.config.monitor.is_empty() {
                     if let Some(isupport::Parameter::MONITOR(monitor_limit)) =
                         self.isupport.get(&isupport::Kind::MONITOR)
@@ -3440,8 +3884,16 @@ impl Client {
             _ => {}
         }
 
-        if let Some(target) =
-            context.map(Context::buffer).as_ref().map(Destination::from)
+        // Replies to informational commands are rerouted to the buffer the
+        // command was sent from
+        if let Some(target) = context
+            .map(Context::buffer)
+            .as_ref()
+            .or(self
+                .reroute_responses_to
+                .as_ref()
+                .filter(|_| is_command_output(&message)))
+            .map(Destination::from)
         {
             Ok(vec![Event::WithTarget {
                 message,
@@ -3603,6 +4055,52 @@ impl Client {
         }
     }
 
+    /// Adds a network for each row of ZNC's `ListNetworks` table, hiding the
+    /// reply.  The network this connection is attached to (from a
+    /// `user/network` username, or else ZNC's default, the first) is
+    /// skipped.
+    fn handle_znc_network_listing(&mut self, text: &str) -> Vec<Event> {
+        let Some(listed) = self.znc_networks_listed.as_mut() else {
+            return vec![];
+        };
+
+        let Some(name) = bouncer::parse_znc_network(text) else {
+            // The closing border follows the rows, and anything else
+            // (e.g. no networks) ends the listing
+            if !text.starts_with('+') || *listed > 0 {
+                self.znc_networks_listed = None;
+            }
+
+            return vec![];
+        };
+
+        *listed += 1;
+
+        let attached = self
+            .config
+            .username
+            .as_deref()
+            .and_then(|username| username.split_once('/'))
+            .map(|(_, network)| network);
+
+        let is_attached = match attached {
+            Some(attached) => attached == name,
+            None => *listed == 1,
+        };
+
+        if is_attached {
+            return vec![];
+        }
+
+        let server = Server {
+            network: Some(BouncerNetwork::znc(name).into()),
+            ..self.server.clone()
+        };
+        let network_config = self.config.bouncer_network_config(&server);
+
+        vec![Event::BouncerNetwork(server, network_config)]
+    }
+
     fn handle_znc_playback(
         &mut self,
         message: message::Encoded,
@@ -4135,31 +4633,209 @@ impl Client {
         }
     }
 
+    /// Marks a channel's user list as stale when a message contradicts it,
+    /// e.g. a user not in it parting or a user in it joining again.
+    fn detect_users_drift(&mut self, message: &message::Encoded) {
+        let (channel, require_member) = match &message.command {
+            Command::JOIN(channel, _) => (channel, false),
+            Command::PART(channel, _) => (channel, true),
+            // Channels without +n accept messages from outside
+            Command::PRIVMSG(channel, _) => (channel, true),
+            _ => return,
+        };
+
+        let casemapping = self.casemapping();
+
+        let Some(user) = message.user(casemapping) else {
+            return;
+        };
+
+        if user.nickname() == self.nickname() {
+            return;
+        }
+
+        let Ok(channel) = target::Channel::parse(
+            channel,
+            self.chantypes(),
+            self.statusmsg(),
+            casemapping,
+        ) else {
+            return;
+        };
+
+        let Some(client_channel) = self.chanmap.get_mut(&channel) else {
+            return;
+        };
+
+        if !client_channel.users_init
+            || client_channel.users_refresh.is_some()
+            || (matches!(message.command, Command::PRIVMSG(..))
+                && !client_channel
+                    .mode
+                    .as_ref()
+                    .is_some_and(|mode| mode.contains('n')))
+        {
+            return;
+        }
+
+        if client_channel.users.contains(&user) != require_member
+            && !client_channel.users_stale
+        {
+            log::debug!(
+                "[{}] User list of {channel} is out of date ({} {})",
+                self.server,
+                user.nickname(),
+                if require_member {
+                    "is not in it"
+                } else {
+                    "joined again"
+                },
+            );
+
+            client_channel.users_stale = true;
+        }
+    }
+
+    /// Requests the user list of a channel again, replacing the current one
+    /// once received.
+    pub fn refresh_users(&mut self, channel: &target::Channel) {
+        let Some(client_channel) = self.chanmap.get_mut(channel) else {
+            return;
+        };
+
+        if !client_channel.users_init || client_channel.users_refresh.is_some()
+        {
+            return;
+        }
+
+        client_channel.users_refresh = Some(ChannelUsers::default());
+        client_channel.last_users_refresh = Some(Instant::now());
+
+        self.send(
+            None,
+            command!("NAMES", channel.to_string()).into(),
+            TokenPriority::Low,
+        );
+    }
+
     fn topic<'a>(&'a self, channel: &target::Channel) -> Option<&'a Topic> {
         self.chanmap.get(channel).map(|channel| &channel.topic)
     }
 
-    fn mode<'a>(&'a self, channel: &target::Channel) -> Option<&'a String> {
-        self.chanmap
-            .get(channel)
-            .and_then(|channel| channel.mode.as_ref())
+    fn bans<'a>(&'a self, channel: &target::Channel) -> Option<&'a MaskList> {
+        self.chanmap.get(channel).map(|channel| &channel.bans)
     }
 
-    fn resolve_user_attributes<'a>(
-        &'a self,
+    fn channel_forwarded_from(
+        &self,
         channel: &target::Channel,
-        user: &User,
-    ) -> Option<&'a User> {
-        self.chanmap
-            .get(channel)
-            .and_then(|channel| channel.users.resolve(user))
+    ) -> Option<&target::Channel> {
+        self.channel_forwards.get(channel)
     }
 
-    pub fn users<'a>(
+    fn moderation_queue<'a>(
         &'a self,
         channel: &target::Channel,
-    ) -> Option<&'a ChannelUsers> {
-        self.chanmap.get(channel).map(|chanimpl| &chanimpl.users)
+    ) -> Option<&'a moderation_queue::Queue> {
+        self.chanmap
+            .get(channel)
+            .map(|channel| &channel.moderation_queue)
+    }
+
+    /// Queues a voice request in the moderated channels named in `args`, or
+    /// else the channel it was sent to, or else any moderated channel shared
+    /// with the user when sent to us directly.
+    fn queue_voice_request(
+        &mut self,
+        user: &User,
+        command: &Command,
+        args: &[&str],
+        text: &str,
+        received_at: DateTime<Utc>,
+    ) {
+        let requested = args
+            .iter()
+            .filter_map(|arg| {
+                target::Channel::parse(
+                    arg,
+                    self.chantypes(),
+                    self.statusmsg(),
+                    self.casemapping(),
+                )
+                .ok()
+            })
+            .collect::<Vec<_>>();
+
+        let channels = if !requested.is_empty() {
+            requested
+        } else if let Some(channel) = self.message_channel_target(command) {
+            vec![channel]
+        } else {
+            self.user_channels(user.nickname())
+        };
+
+        let our_nick = self.nickname().to_owned();
+
+        for channel in channels {
+            let Some(channel) = self.chanmap.get_mut(&channel) else {
+                continue;
+            };
+
+            let is_moderated = channel
+                .mode
+                .as_deref()
+                .is_some_and(|mode| mode.contains('m'));
+            let is_op = channel
+                .users
+                .get_by_nick(our_nick.as_nickref())
+                .is_some_and(|our_user| {
+                    our_user.highest_access_level() >= AccessLevel::HalfOp
+                });
+
+            if is_moderated && is_op {
+                channel.moderation_queue.push(
+                    &channel.users,
+                    moderation_queue::Request {
+                        user: user.clone(),
+                        kind: moderation_queue::Kind::Message,
+                        text: Some(text.to_string()),
+                        received_at,
+                    },
+                );
+            }
+        }
+    }
+
+    fn invite_exceptions<'a>(
+        &'a self,
+        channel: &target::Channel,
+    ) -> Option<&'a MaskList> {
+        self.chanmap
+            .get(channel)
+            .map(|channel| &channel.invite_exceptions)
+    }
+
+    fn mode<'a>(&'a self, channel: &target::Channel) -> Option<&'a String> {
+        self.chanmap
+            .get(channel)
+            .and_then(|channel| channel.mode.as_ref())
+    }
+
+    fn resolve_user_attributes<'a>(
+        &'a self,
+        channel: &target::Channel,
+        user: &User,
+    ) -> Option<&'a User> {
+        self.chanmap
+            .get(channel)
+            .and_then(|channel| channel.users.resolve(user))
+    }
+
+    pub fn users<'a>(
+        &'a self,
+        channel: &target::Channel,
+    ) -> Option<&'a ChannelUsers> {
+        self.chanmap.get(channel).map(|chanimpl| &chanimpl.users)
     }
 
     pub fn channel_typing_users(
@@ -4416,6 +5092,28 @@ impl Client {
                 < CHATHISTORY_REQUEST_TIMEOUT
         });
 
+        // Hold off while lagging, the user list is likely to be out of date
+        // again by the time it arrives
+        if self.config.names_refresh_enabled
+            && self.lag.is_none_or(|lag| lag < LAG_SPIKE)
+        {
+            let stale = self
+                .chanmap
+                .iter()
+                .filter(|(_, channel)| {
+                    channel.needs_users_refresh(
+                        now,
+                        self.config.names_refresh_interval,
+                    )
+                })
+                .map(|(channel, _)| channel.clone())
+                .collect::<Vec<_>>();
+
+            for channel in stale {
+                self.refresh_users(&channel);
+            }
+        }
+
         if let Some(ref mut anti_flood) = self.anti_flood {
             anti_flood.add_permits(now.into());
 
@@ -4543,6 +5241,42 @@ impl Client {
         self.who_queue.deprioritize_who_poll(&self.server, channel);
     }
 
+    pub fn refresh_who_poll(&mut self, channel: &target::Channel) {
+        self.who_queue.refresh_who_poll(&self.server, channel);
+    }
+
+    pub fn set_nick_history(&mut self, history: nick_history::NickHistory) {
+        self.nick_history = history;
+    }
+
+    /// Saves the nick history once it has gone unchanged for a while, or
+    /// right away if there is no `now` (e.g. on exit or disconnect).
+    pub fn save_nick_history(
+        &mut self,
+        now: Option<Instant>,
+    ) -> Option<impl Future<Output = ()> + use<>> {
+        let changed_at = self.nick_history_changed_at?;
+
+        if now.is_some_and(|now| {
+            now.duration_since(changed_at) < NICK_HISTORY_SAVE_AFTER
+        }) {
+            return None;
+        }
+
+        self.nick_history_changed_at = None;
+
+        let server = self.server.clone();
+        let history = self.nick_history.clone();
+
+        Some(async move {
+            if let Err(error) =
+                nick_history::save(server.clone(), history).await
+            {
+                log::warn!("[{server}] failed to save nick history: {error}");
+            }
+        })
+    }
+
     pub fn has_isupport_monitor(&self) -> bool {
         self.isupport.contains_key(&isupport::Kind::MONITOR)
     }
@@ -4664,6 +5398,106 @@ fn compare_channels(
     }
 }
 
+/// Replies to informational commands, shown as a single block in the buffer
+/// the command was sent from.
+fn is_command_output(message: &message::Encoded) -> bool {
+    use command::Numeric::*;
+
+    matches!(
+        message.command,
+        Command::Numeric(
+            RPL_WHOISCERTFP
+                | RPL_WHOISREGNICK
+                | RPL_WHOISUSER
+                | RPL_WHOISSERVER
+                | RPL_WHOISOPERATOR
+                | RPL_WHOISIDLE
+                | RPL_WHOISCHANNELS
+                | RPL_WHOISSPECIAL
+                | RPL_WHOISACCOUNT
+                | RPL_WHOISACTUALLY
+                | RPL_WHOISHOST
+                | RPL_WHOISMODES
+                | RPL_WHOISSECURE
+                | RPL_WHOISBOT
+                | RPL_AWAY
+                | RPL_ENDOFWHOIS
+                | RPL_WHOWASUSER
+                | RPL_ENDOFWHOWAS
+                | RPL_NAMREPLY
+                | RPL_ENDOFNAMES
+                | RPL_LINKS
+                | RPL_ENDOFLINKS
+                | RPL_ADMINME
+                | RPL_ADMINLOC1
+                | RPL_ADMINLOC2
+                | RPL_ADMINEMAIL,
+            _
+        )
+    )
+}
+
+fn is_command_output_event(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::WithTarget { message, .. } if is_command_output(message)
+    )
+}
+
+/// Gathers replies to an informational command into a single event, after
+/// any other events.
+fn gather_command_output(events: Vec<Event>) -> Vec<Event> {
+    let (output, mut events): (Vec<_>, Vec<_>) =
+        events.into_iter().partition(is_command_output_event);
+
+    let mut output = output.into_iter().filter_map(|event| match event {
+        Event::WithTarget {
+            message,
+            our_nick,
+            target,
+            ..
+        } => Some((message, our_nick, target)),
+        _ => None,
+    });
+
+    if let Some((first, our_nick, target)) = output.next() {
+        events.push(Event::CommandOutput {
+            title: command_output_title(&first),
+            messages: iter::once(first)
+                .chain(output.map(|(message, _, _)| message))
+                .collect(),
+            our_nick,
+            target,
+        });
+    }
+
+    events
+}
+
+fn command_output_title(message: &message::Encoded) -> String {
+    use command::Numeric::*;
+
+    let Command::Numeric(numeric, args) = &message.command else {
+        return String::new();
+    };
+
+    let (command, subject) = match numeric {
+        RPL_WHOWASUSER | RPL_ENDOFWHOWAS => ("WHOWAS", args.get(1)),
+        RPL_NAMREPLY => ("NAMES", args.get(2)),
+        RPL_ENDOFNAMES => ("NAMES", args.get(1)),
+        RPL_LINKS | RPL_ENDOFLINKS => ("LINKS", None),
+        RPL_ADMINME | RPL_ADMINLOC1 | RPL_ADMINLOC2 | RPL_ADMINEMAIL => {
+            ("ADMIN", None)
+        }
+        _ => ("WHOIS", args.get(1)),
+    };
+
+    subject.map_or_else(
+        || command.to_string(),
+        |subject| format!("{command} {subject}"),
+    )
+}
+
 fn is_reaction(message: &message::Encoded) -> bool {
     matches!(message.command, Command::PRIVMSG(..) | Command::TAGMSG(..))
         && message.in_reply_to().is_some()
@@ -4718,6 +5552,7 @@ fn continue_chathistory_between(
             },
             Event::Broadcast(_)
             | Event::FileTransferRequest(_)
+            | Event::FileTransferResume(_)
             | Event::UpdateReadMarker(_, _)
             | Event::JoinedChannel(_, _)
             | Event::LoggedIn(_)
@@ -4768,6 +5603,7 @@ fn continue_chathistory_targets(
             | Event::Redaction(_, _)
             | Event::Broadcast(_)
             | Event::FileTransferRequest(_)
+            | Event::FileTransferResume(_)
             | Event::UpdateReadMarker(_, _)
             | Event::JoinedChannel(_, _)
             | Event::LoggedIn(_)
@@ -4850,11 +5686,18 @@ impl Map {
     }
 
     pub fn disconnected(&mut self, server: Server, autoconnect: bool) {
+        let unsent = match self.0.remove(&server) {
+            Some(State::Disconnected { unsent, .. }) => unsent,
+            Some(State::Ready(client)) => client.unsent(),
+            None => vec![],
+        };
+
         self.0.insert(
             server,
             State::Disconnected {
                 autoconnect,
                 connecting: false,
+                unsent,
             },
         );
     }
@@ -4863,6 +5706,7 @@ impl Map {
         if let Some(State::Disconnected {
             autoconnect: server_autoconnect,
             connecting,
+            ..
         }) = self.0.get_mut(server)
         {
             *server_autoconnect = autoconnect;
@@ -4880,6 +5724,31 @@ impl Map {
         }
     }
 
+    pub fn update_lag(&mut self, server: &Server, lag: Duration) {
+        if let Some(client) = self.client_mut(server) {
+            // Messages may have been missed or reordered during a lag spike
+            if lag >= LAG_SPIKE {
+                client
+                    .chanmap
+                    .values_mut()
+                    .filter(|channel| channel.users_init)
+                    .for_each(|channel| channel.users_stale = true);
+            }
+
+            client.lag = Some(lag);
+        }
+    }
+
+    pub fn refresh_users(
+        &mut self,
+        server: &Server,
+        channel: &target::Channel,
+    ) {
+        if let Some(client) = self.client_mut(server) {
+            client.refresh_users(channel);
+        }
+    }
+
     pub fn connecting(&mut self, server: &Server) {
         if let Some(State::Disconnected { connecting, .. }) =
             self.0.get_mut(server)
@@ -4888,10 +5757,70 @@ impl Map {
         }
     }
 
-    pub fn ready(&mut self, server: Server, client: Client) {
+    pub fn ready(&mut self, server: Server, mut client: Client) {
+        // Messages composed while disconnected are sent once registered
+        if let Some(State::Disconnected { unsent, .. }) =
+            self.0.get_mut(&server)
+        {
+            client.deferred = std::mem::take(unsent);
+        }
+
         self.0.insert(server, State::Ready(client));
     }
 
+    /// Messages for `server` waiting to be sent once it's reconnected.
+    pub fn get_server_deferred(&self, server: &Server) -> &[outbox::Message] {
+        match self.0.get(server) {
+            Some(State::Disconnected { unsent, .. }) => unsent,
+            Some(State::Ready(client)) => &client.deferred,
+            None => &[],
+        }
+    }
+
+    /// Cancels a message composed while disconnected.
+    pub fn cancel_deferred(
+        &mut self,
+        server: &Server,
+        command: &crate::command::Irc,
+    ) {
+        let deferred = match self.0.get_mut(server) {
+            Some(State::Disconnected { unsent, .. }) => unsent,
+            Some(State::Ready(client)) => &mut client.deferred,
+            None => return,
+        };
+
+        if let Some(index) = deferred
+            .iter()
+            .position(|message| message.is_command(command))
+        {
+            deferred.remove(index);
+        }
+    }
+
+    /// Messages which haven't reached their server yet.
+    pub fn outbox(&self) -> Outbox {
+        self.0
+            .iter()
+            .map(|(server, state)| {
+                let unsent = match state {
+                    State::Disconnected { unsent, .. } => unsent.clone(),
+                    State::Ready(client) => client.unsent(),
+                };
+
+                (server.clone(), unsent)
+            })
+            .filter(|(_, unsent)| !unsent.is_empty())
+            .collect()
+    }
+
+    pub fn resend(&mut self, server: &Server, messages: Vec<outbox::Message>) {
+        if let Some(client) = self.client_mut(server) {
+            for message in messages {
+                client.send(None, message.encoded(), TokenPriority::User);
+            }
+        }
+    }
+
     pub fn update_config(
         &mut self,
         server: &Server,
@@ -4955,8 +5884,18 @@ impl Map {
         message: message::Encoded,
         priority: TokenPriority,
     ) -> Option<LabeledResponseContext> {
-        self.client_mut(buffer.server())
-            .and_then(|client| client.send(Some(buffer), message, priority))
+        match self.0.get_mut(buffer.server())? {
+            State::Ready(client) => {
+                client.send(Some(buffer), message, priority)
+            }
+            State::Disconnected { unsent, .. } => {
+                if matches!(priority, TokenPriority::User) {
+                    unsent.extend(outbox::Message::from_encoded(&message));
+                }
+
+                None
+            }
+        }
     }
 
     pub fn send_multiline_batch(
@@ -5015,6 +5954,16 @@ impl Map {
         }
     }
 
+    pub fn refresh_who_poll(
+        &mut self,
+        server: &Server,
+        channel: &target::Channel,
+    ) {
+        if let Some(client) = self.client_mut(server) {
+            client.refresh_who_poll(channel);
+        }
+    }
+
     pub fn add_monitored_user_automated(
         &mut self,
         server: &Server,
@@ -5089,6 +6038,69 @@ impl Map {
             .unwrap_or_default()
     }
 
+    pub fn get_channel_bans<'a>(
+        &'a self,
+        server: &Server,
+        channel: &target::Channel,
+    ) -> Option<&'a MaskList> {
+        self.client(server)
+            .map(|client| client.bans(channel))
+            .unwrap_or_default()
+    }
+
+    pub fn get_channel_invite_exceptions<'a>(
+        &'a self,
+        server: &Server,
+        channel: &target::Channel,
+    ) -> Option<&'a MaskList> {
+        self.client(server)
+            .map(|client| client.invite_exceptions(channel))
+            .unwrap_or_default()
+    }
+
+    /// The channel we asked to join, if our join was forwarded to `channel`.
+    pub fn get_channel_forwarded_from<'a>(
+        &'a self,
+        server: &Server,
+        channel: &target::Channel,
+    ) -> Option<&'a target::Channel> {
+        self.client(server)
+            .and_then(|client| client.channel_forwarded_from(channel))
+    }
+
+    pub fn dismiss_channel_forward(
+        &mut self,
+        server: &Server,
+        channel: &target::Channel,
+    ) {
+        if let Some(client) = self.client_mut(server) {
+            client.channel_forwards.remove(channel);
+        }
+    }
+
+    pub fn get_channel_moderation_queue<'a>(
+        &'a self,
+        server: &Server,
+        channel: &target::Channel,
+    ) -> Option<&'a moderation_queue::Queue> {
+        self.client(server)
+            .map(|client| client.moderation_queue(channel))
+            .unwrap_or_default()
+    }
+
+    pub fn remove_moderation_request(
+        &mut self,
+        server: &Server,
+        channel: &target::Channel,
+        nick: NickRef,
+    ) -> Option<moderation_queue::Request> {
+        self.client_mut(server)?
+            .chanmap
+            .get_mut(channel)?
+            .moderation_queue
+            .remove(nick)
+    }
+
     pub fn get_channels<'a>(
         &'a self,
         server: &Server,
@@ -5197,6 +6209,20 @@ impl Map {
         }
     }
 
+    pub fn get_server_lag(&self, server: &Server) -> Option<Duration> {
+        self.client(server).and_then(|client| client.lag)
+    }
+
+    pub fn get_server_is_away(&self, server: &Server) -> bool {
+        self.client(server).is_some_and(|client| client.away)
+    }
+
+    pub fn get_server_queued_messages(&self, server: &Server) -> usize {
+        self.client(server)
+            .and_then(|client| client.anti_flood.as_ref())
+            .map_or(0, TokenBucket::queued)
+    }
+
     pub fn get_use_tls(&self, server: &Server) -> bool {
         self.client(server).is_none_or(|c| c.config.use_tls)
     }
@@ -5490,6 +6516,17 @@ impl Map {
             .is_some_and(|client| client.features.detach)
     }
 
+    /// Mode letter for invite exceptions, if supported by the server.
+    pub fn get_server_invite_exception_mode(
+        &self,
+        server: &Server,
+    ) -> Option<char> {
+        match self.get_isupport_ref(server).get(&isupport::Kind::INVEX) {
+            Some(isupport::Parameter::INVEX(mode)) => Some(*mode),
+            _ => None,
+        }
+    }
+
     pub fn get_server_supports_list(&self, server: &Server) -> bool {
         self.client(server).is_some_and(Client::safelist)
     }
@@ -5585,6 +6622,36 @@ impl Map {
         Ok(())
     }
 
+    pub fn get_nick_history(
+        &self,
+        server: &Server,
+    ) -> &nick_history::NickHistory {
+        self.client(server)
+            .map_or(&nick_history::EMPTY, |client| &client.nick_history)
+    }
+
+    pub fn save_nick_history(
+        &mut self,
+        server: &Server,
+        now: Option<Instant>,
+    ) -> Option<impl Future<Output = ()> + use<>> {
+        self.client_mut(server)
+            .and_then(|client| client.save_nick_history(now))
+    }
+
+    pub fn save_nick_histories(
+        &mut self,
+        now: Option<Instant>,
+    ) -> Vec<impl Future<Output = ()> + use<>> {
+        self.0
+            .values_mut()
+            .filter_map(|state| match state {
+                State::Ready(client) => client.save_nick_history(now),
+                State::Disconnected { .. } => None,
+            })
+            .collect()
+    }
+
     pub fn get_registry(&self, server: &Server) -> &dyn metadata::Registry {
         self.0
             .get(server)
@@ -5745,12 +6812,47 @@ enum RegistrationStep {
 pub struct Channel {
     pub users: ChannelUsers,
     pub users_init: bool,
+    /// Set when the user list appears to have drifted from the server's.
+    pub users_stale: bool,
+    users_refresh: Option<ChannelUsers>,
+    last_users_refresh: Option<Instant>,
     pub topic: Topic,
     pub mode: Option<String>,
     pub typing: HashMap<Nick, Instant>,
+    pub bans: MaskList,
+    pub invite_exceptions: MaskList,
+    pub moderation_queue: moderation_queue::Queue,
+}
+
+/// A channel list mode (e.g. bans), filled in once requested.
+#[derive(Debug, Default, Clone)]
+pub struct MaskList {
+    pub entries: Option<Vec<MaskEntry>>,
+    receiving: Vec<MaskEntry>,
+}
+
+impl MaskList {
+    fn received(&mut self) {
+        self.entries = Some(std::mem::take(&mut self.receiving));
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MaskEntry {
+    pub mask: String,
+    pub set_by: Option<String>,
+    pub set_at: Option<DateTime<Utc>>,
 }
 
 impl Channel {
+    /// Whether the user list is stale and wasn't refreshed within `interval`.
+    fn needs_users_refresh(&self, now: Instant, interval: Duration) -> bool {
+        self.users_stale
+            && self.last_users_refresh.is_none_or(|refreshed| {
+                now.duration_since(refreshed) >= interval
+            })
+    }
+
     pub fn update_user_status(
         &mut self,
         user: &str,
@@ -5786,6 +6888,23 @@ impl Channel {
             self.users.insert(user.with_accountname(accountname));
         }
     }
+
+    pub fn update_user_hostmask(
+        &mut self,
+        user: &str,
+        username: &str,
+        hostname: &str,
+        casemapping: isupport::CaseMap,
+    ) {
+        let user = User::from(Nick::from_str(user, casemapping));
+
+        if let Some(user) = self.users.take(&user) {
+            self.users.insert(user.with_username_and_hostname(
+                username.to_string(),
+                hostname.to_string(),
+            ));
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -6520,4 +7639,262 @@ mod tests {
 
         assert_eq!(channel_user_is_away(&client, "tester"), Some(false));
     }
+
+    fn privmsg(target: &str, text: &str) -> message::Encoded {
+        proto::Message::from(Command::PRIVMSG(
+            target.to_string(),
+            text.to_string(),
+        ))
+        .into()
+    }
+
+    fn texts(messages: &[outbox::Message]) -> Vec<(&str, &str)> {
+        messages
+            .iter()
+            .map(|message| (message.target.as_str(), message.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn messages_sent_while_disconnected_are_deferred() {
+        let server = Server::from(Arc::<str>::from("test"));
+        let buffer = buffer::Upstream::Server(server.clone());
+        let mut map = Map::default();
+
+        map.disconnected(server.clone(), true);
+
+        map.send(&buffer, privmsg("#halloy", "one"), TokenPriority::User);
+        map.send(&buffer, privmsg("#halloy", "two"), TokenPriority::High);
+        map.send(
+            &buffer,
+            proto::Message::from(Command::JOIN("#halloy".to_string(), None))
+                .into(),
+            TokenPriority::User,
+        );
+        map.send(&buffer, privmsg("casper", "three"), TokenPriority::User);
+
+        assert_eq!(
+            texts(map.get_server_deferred(&server)),
+            vec![("#halloy", "one"), ("casper", "three")]
+        );
+    }
+
+    #[test]
+    fn deferred_messages_flush_in_order_on_reconnect() {
+        let server = Server::from(Arc::<str>::from("test"));
+        let buffer = buffer::Upstream::Server(server.clone());
+        let mut map = Map::default();
+
+        map.disconnected(server.clone(), true);
+
+        for text in ["one", "two", "three"] {
+            map.send(&buffer, privmsg("#halloy", text), TokenPriority::User);
+        }
+
+        map.ready(server.clone(), test_client("tester"));
+
+        assert_eq!(
+            texts(map.get_server_deferred(&server)),
+            vec![("#halloy", "one"), ("#halloy", "two"), ("#halloy", "three")]
+        );
+
+        map.client_mut(&server).unwrap().send_deferred();
+
+        assert!(map.get_server_deferred(&server).is_empty());
+        assert_eq!(
+            texts(&map.outbox()[&server]),
+            vec![("#halloy", "one"), ("#halloy", "two"), ("#halloy", "three")]
+        );
+    }
+
+    #[test]
+    fn unsent_messages_survive_a_disconnect() {
+        let server = Server::from(Arc::<str>::from("test"));
+        let buffer = buffer::Upstream::Server(server.clone());
+        let mut map = Map::default();
+
+        map.ready(server.clone(), test_client("tester"));
+        map.send(&buffer, privmsg("#halloy", "one"), TokenPriority::User);
+        map.disconnected(server.clone(), true);
+        map.send(&buffer, privmsg("#halloy", "two"), TokenPriority::User);
+
+        assert_eq!(
+            texts(map.get_server_deferred(&server)),
+            vec![("#halloy", "one"), ("#halloy", "two")]
+        );
+    }
+
+    #[test]
+    fn cancel_deferred_removes_one_matching_message() {
+        let server = Server::from(Arc::<str>::from("test"));
+        let buffer = buffer::Upstream::Server(server.clone());
+        let mut map = Map::default();
+
+        map.disconnected(server.clone(), true);
+
+        for text in ["one", "two", "one"] {
+            map.send(&buffer, privmsg("#halloy", text), TokenPriority::User);
+        }
+
+        map.cancel_deferred(
+            &server,
+            &crate::command::Irc::Msg("#halloy".to_string(), "one".to_string()),
+        );
+
+        assert_eq!(
+            texts(map.get_server_deferred(&server)),
+            vec![("#halloy", "two"), ("#halloy", "one")]
+        );
+    }
+
+    fn join_test_channel(client: &mut Client, config: &config::Config) {
+        use irc::proto::command::Numeric::*;
+
+        let server_source = proto::Source::Server("irc.test".to_string());
+
+        deliver(
+            client,
+            config,
+            proto::Source::User(proto::User {
+                nickname: "tester".to_string(),
+                username: Some("tester".to_string()),
+                hostname: Some("example.test".to_string()),
+            }),
+            Command::JOIN("#test".to_string(), None),
+        );
+        deliver(
+            client,
+            config,
+            server_source.clone(),
+            Command::Numeric(
+                RPL_NAMREPLY,
+                vec![
+                    "tester".to_string(),
+                    "=".to_string(),
+                    "#test".to_string(),
+                    "tester alice".to_string(),
+                ],
+            ),
+        );
+        deliver(
+            client,
+            config,
+            server_source,
+            Command::Numeric(
+                RPL_ENDOFNAMES,
+                vec!["tester".to_string(), "#test".to_string()],
+            ),
+        );
+    }
+
+    fn test_channel_is_stale(client: &Client) -> bool {
+        client
+            .chanmap
+            .values()
+            .next()
+            .is_some_and(|channel| channel.users_stale)
+    }
+
+    fn user_source(nickname: &str) -> proto::Source {
+        proto::Source::User(proto::User {
+            nickname: nickname.to_string(),
+            username: Some(nickname.to_string()),
+            hostname: Some("example.test".to_string()),
+        })
+    }
+
+    #[test]
+    fn users_are_stale_when_a_non_member_parts() {
+        let mut client = test_client("tester");
+        let config = config::Config::default();
+
+        join_test_channel(&mut client, &config);
+
+        deliver(
+            &mut client,
+            &config,
+            user_source("alice"),
+            Command::PART("#test".to_string(), None),
+        );
+
+        assert!(!test_channel_is_stale(&client));
+
+        deliver(
+            &mut client,
+            &config,
+            user_source("bob"),
+            Command::PART("#test".to_string(), None),
+        );
+
+        assert!(test_channel_is_stale(&client));
+    }
+
+    #[test]
+    fn users_are_stale_when_a_member_joins_again() {
+        let mut client = test_client("tester");
+        let config = config::Config::default();
+
+        join_test_channel(&mut client, &config);
+
+        deliver(
+            &mut client,
+            &config,
+            user_source("bob"),
+            Command::JOIN("#test".to_string(), None),
+        );
+
+        assert!(!test_channel_is_stale(&client));
+
+        deliver(
+            &mut client,
+            &config,
+            user_source("alice"),
+            Command::JOIN("#test".to_string(), None),
+        );
+
+        assert!(test_channel_is_stale(&client));
+    }
+
+    #[test]
+    fn stale_users_are_refreshed_once_per_interval() {
+        let interval = Duration::from_secs(60);
+        let now = Instant::now();
+
+        let mut channel = Channel::default();
+
+        assert!(!channel.needs_users_refresh(now, interval));
+
+        channel.users_stale = true;
+
+        assert!(channel.needs_users_refresh(now, interval));
+
+        channel.last_users_refresh = Some(now);
+
+        assert!(!channel.needs_users_refresh(now, interval));
+        assert!(!channel.needs_users_refresh(
+            now + interval - Duration::from_secs(1),
+            interval
+        ));
+        assert!(channel.needs_users_refresh(now + interval, interval));
+    }
+
+    #[test]
+    fn lag_spikes_mark_users_stale() {
+        let server = Server::from(Arc::<str>::from("test"));
+        let config = config::Config::default();
+        let mut client = test_client("tester");
+
+        join_test_channel(&mut client, &config);
+
+        let mut map = Map::default();
+        map.ready(server.clone(), client);
+
+        map.update_lag(&server, LAG_SPIKE - Duration::from_millis(1));
+
+        assert!(!test_channel_is_stale(map.client(&server).unwrap()));
+
+        map.update_lag(&server, LAG_SPIKE);
+
+        assert!(test_channel_is_stale(map.client(&server).unwrap()));
+    }
 }
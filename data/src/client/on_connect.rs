@@ -106,8 +106,9 @@ pub fn on_connect(
                                 None
                             }
                             // We don't handle hop, clear-buffer, sysinfo,
-                            // channel_discovery, connect, or reconnect when
-                            // called on connection.
+                            // channel_discovery, connect, reconnect,
+                            // test-notification, ignore, unignore, or plugin
+                            // commands when called on connection.
                             command::Internal::ClearBuffer
                             | command::Internal::ChannelDiscovery
                             | command::Internal::Connect(_)
@@ -115,7 +116,11 @@ pub fn on_connect(
                             | command::Internal::Hop(_, _)
                             | command::Internal::SysInfo
                             | command::Internal::Reconnect
-                            | command::Internal::Upload(_) => None,
+                            | command::Internal::Upload(_)
+                            | command::Internal::TestNotification(_)
+                            | command::Internal::Ignore(_)
+                            | command::Internal::Unignore(_)
+                            | command::Internal::Plugin(_, _) => None,
                         },
                     }
                 }
@@ -375,6 +375,7 @@ impl WhoQueue {
 
         let casemapping = isupport::get_casemapping_or_default(isupport);
         let bot_mode_char = isupport::get_bot_mode_char(isupport);
+        let prefix = isupport::get_prefix_or_default(isupport);
 
         let nick = ok!(args.get(5));
         let flags = ok!(args.get(6));
@@ -389,6 +390,7 @@ impl WhoQueue {
             None,
             casemapping,
             bot_mode_char,
+            prefix,
         );
 
         if client_channel.users.contains(&user) {
@@ -398,6 +400,13 @@ impl WhoQueue {
                 casemapping,
                 bot_mode_char,
             );
+
+            client_channel.update_user_hostmask(
+                nick,
+                username,
+                hostname,
+                casemapping,
+            );
         } else {
             client_channel.users.insert(user);
         }
@@ -470,6 +479,7 @@ impl WhoQueue {
                 let casemapping =
                     isupport::get_casemapping_or_default(isupport);
                 let bot_mode_char = isupport::get_bot_mode_char(isupport);
+                let prefix = isupport::get_prefix_or_default(isupport);
 
                 // Check token to ~ensure reply is to poll request
                 if let Ok(token) = ok!(args.get(1)).parse::<WhoToken>() {
@@ -518,6 +528,7 @@ impl WhoQueue {
                             Some(accountname),
                             casemapping,
                             bot_mode_char,
+                            prefix,
                         );
 
                         if client_channel.users.contains(&user) {
@@ -533,6 +544,13 @@ impl WhoQueue {
                                 accountname,
                                 casemapping,
                             );
+
+                            client_channel.update_user_hostmask(
+                                nick,
+                                username,
+                                hostname,
+                                casemapping,
+                            );
                         } else {
                             client_channel.users.insert(user);
                         }
@@ -614,6 +632,34 @@ impl WhoQueue {
         }
     }
 
+    // Move the channel's WHO poll to the front of the queue, so that the user
+    // list is refreshed as soon as possible.
+    pub fn refresh_who_poll(&mut self, server: &Server, channel: &Channel) {
+        if self
+            .in_flight
+            .iter()
+            .any(|who_poll| who_poll.channel == *channel)
+        {
+            return;
+        }
+
+        if let Some(pos) = self
+            .queue
+            .iter()
+            .position(|who_poll| who_poll.channel == *channel)
+        {
+            self.queue.remove(pos);
+        }
+
+        log::trace!("[{server}] {channel} - refreshing WHO poll");
+
+        self.insert_towards_front(WhoPoll {
+            channel: channel.clone(),
+            source: WhoSource::Join { priority: true },
+            status: WhoStatus::Waiting { immediate: false },
+        });
+    }
+
     pub fn deprioritize_who_poll(
         &mut self,
         server: &Server,
@@ -639,16 +685,16 @@ impl WhoQueue {
         mut who_poll: WhoPoll,
     ) -> (message::Encoded, TokenPriority) {
         let message = if isupport.contains_key(&isupport::Kind::WHOX) {
-            let whox_params = if capabilities
-                .acknowledged(Capability::NoImplicitNames)
-                && matches!(who_poll.source, WhoSource::Join { .. })
-            {
-                WhoXPollParameters::InitialJoin
-            } else if capabilities.acknowledged(Capability::AccountNotify) {
-                WhoXPollParameters::WithAccountName
-            } else {
-                WhoXPollParameters::Default
-            };
+            // Requests on join also fill in hostmasks and accounts, which
+            // NAMES doesn't include without `userhost-in-names`
+            let whox_params =
+                if matches!(who_poll.source, WhoSource::Join { .. }) {
+                    WhoXPollParameters::InitialJoin
+                } else if capabilities.acknowledged(Capability::AccountNotify) {
+                    WhoXPollParameters::WithAccountName
+                } else {
+                    WhoXPollParameters::Default
+                };
 
             who_poll.status = WhoStatus::Requested {
                 at: Instant::now(),
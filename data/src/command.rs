@@ -18,7 +18,10 @@ use crate::isupport::{
 };
 use crate::message::{self, formatting};
 use crate::user::{ChannelUsers, NickRef};
-use crate::{Config, Message, Server, Target, Url, User, ctcp, target};
+use crate::{
+    Config, Message, Server, Target, Url, User, ctcp, ignore, notification,
+    plugin, target,
+};
 
 pub mod alias;
 
@@ -48,6 +51,15 @@ pub enum Internal {
     Reconnect,
     Upload(String),
     Exec(String),
+    TestNotification(notification::Test),
+    /// Ignore a hostmask, or open the list of ignores when none is given.
+    Ignore(Option<ignore::Mask>),
+    Unignore(String),
+    /// A command registered by a plugin.
+    ///
+    /// - Command name
+    /// - Arguments
+    Plugin(String, String),
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -288,6 +300,10 @@ pub enum Kind {
     Upload,
     MassMessage,
     Exec,
+    TestNotification,
+    Ignore,
+    SoftIgnore,
+    Unignore,
     Raw,
 }
 
@@ -335,6 +351,10 @@ impl FromStr for Kind {
             "upload" => Ok(Kind::Upload),
             "massmessage" | "mm" => Ok(Kind::MassMessage),
             "exec" => Ok(Kind::Exec),
+            "test-notification" => Ok(Kind::TestNotification),
+            "ignore" => Ok(Kind::Ignore),
+            "softignore" => Ok(Kind::SoftIgnore),
+            "unignore" => Ok(Kind::Unignore),
             _ => Err(()),
         }
     }
@@ -1755,12 +1775,79 @@ fn parse_command(
                     Ok(Command::Internal(Internal::Exec(command.to_string())))
                 }
             }
+            Kind::TestNotification => {
+                validated::<1, 0, false>(args, |[kind], _| {
+                    kind.parse::<notification::Test>()
+                        .map(|test| {
+                            Command::Internal(Internal::TestNotification(test))
+                        })
+                        .map_err(|()| Error::InvalidNotificationType {
+                            valid: notification::Test::ALL
+                                .iter()
+                                .map(|test| test.as_str())
+                                .join(", "),
+                        })
+                })
+            }
+            Kind::Ignore => {
+                validated::<0, 2, false>(args, |_, [mask, types]| {
+                    let Some(mask) = mask else {
+                        return Ok(Command::Internal(Internal::Ignore(None)));
+                    };
+
+                    Ok(Command::Internal(Internal::Ignore(Some(ignore_mask(
+                        &mask,
+                        types.as_deref(),
+                    )?))))
+                })
+            }
+            Kind::SoftIgnore => {
+                validated::<1, 1, false>(args, |[mask], [types]| {
+                    Ok(Command::Internal(Internal::Ignore(Some(
+                        ignore_mask(&mask, types.as_deref())?.with_soft(true),
+                    ))))
+                })
+            }
+            Kind::Unignore => validated::<1, 0, false>(args, |[mask], _| {
+                Ok(Command::Internal(Internal::Unignore(mask)))
+            }),
         },
-        Err(()) => Ok(unknown()),
+        Err(()) if plugin::has_command(&cmd.to_lowercase()) => Ok(
+            Command::Internal(Internal::Plugin(cmd.to_lowercase(), raw.into())),
+        ),
+        // Not a command Halloy knows, so sent to the server as-is
+        Err(()) => Ok(Command::Irc(
+            Irc::Unknown(
+                cmd.to_string(),
+                args.iter().map(ToString::to_string).collect(),
+            ),
+            Some(Warning::UnknownCommand {
+                command: cmd.to_uppercase(),
+            }),
+        )),
     }
 }
 
 // TODO: Expand `validated` so we can better indicate which parameters is optional.
+fn ignore_mask(mask: &str, types: Option<&str>) -> Result<ignore::Mask, Error> {
+    let types = types
+        .iter()
+        .flat_map(|types| types.split(','))
+        .map(|kind| {
+            kind.parse::<ignore::Kind>().map_err(|()| {
+                Error::InvalidIgnoreType {
+                    valid: ignore::Kind::ALL
+                        .iter()
+                        .map(|kind| kind.as_str())
+                        .join(", "),
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ignore::Mask::new(mask, types))
+}
+
 fn validated<const EXACT: usize, const OPT: usize, const TEXT: bool>(
     args: Vec<&str>,
     f: impl FnOnce([String; EXACT], [Option<String>; OPT]) -> Result<Command, Error>,
@@ -2148,6 +2235,12 @@ pub enum Error {
     },
     #[error("/{command} is not enabled in configuration")]
     CommandNotEnabled { command: &'static str },
+    #[error("invalid notification type (expected one of: {valid})")]
+    InvalidNotificationType { valid: String },
+    #[error("invalid ignore type (expected one or more of: {valid})")]
+    InvalidIgnoreType { valid: String },
+    #[error("multi-step aliases can only be sent from the text input")]
+    MultiStepAlias,
 }
 
 #[derive(Debug, Clone, PartialEq, thiserror::Error)]
@@ -2159,6 +2252,10 @@ pub enum Warning {
         command: &'static str,
         context: String,
     },
+    #[error(
+        "unknown command /{command}, press enter to send it raw to the server"
+    )]
+    UnknownCommand { command: String },
 }
 
 fn fmt_incorrect_arg_count(min: usize, max: usize, actual: usize) -> String {
@@ -2199,7 +2296,9 @@ fn fmt_channel_name_requirements(chantypes: &[char]) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{AutoFormat, Command, Error, Internal, isupport, parse};
+    use super::{
+        AutoFormat, Command, Error, Internal, isupport, notification, parse,
+    };
     use crate::Config;
     use crate::capabilities::Capabilities;
     use crate::features::Features;
@@ -2344,4 +2443,39 @@ mod tests {
 
         assert!(matches!(error, Error::ExecDisabled));
     }
+
+    #[test]
+    fn parse_test_notification() {
+        let parse_test_notification = |input| {
+            parse(
+                input,
+                None,
+                None,
+                AutoFormat::default(),
+                true,
+                &isupport::DEFAULT,
+                &Capabilities::default(),
+                &Features::default(),
+                None,
+                &Config::default(),
+            )
+        };
+
+        assert!(matches!(
+            parse_test_notification("/test-notification direct-message"),
+            Ok(Command::Internal(Internal::TestNotification(
+                notification::Test::DirectMessage
+            )))
+        ));
+        assert!(matches!(
+            parse_test_notification("/test-notification file_transfer_request"),
+            Ok(Command::Internal(Internal::TestNotification(
+                notification::Test::FileTransferRequest
+            )))
+        ));
+        assert!(matches!(
+            parse_test_notification("/test-notification pager"),
+            Err(Error::InvalidNotificationType { .. })
+        ));
+    }
 }
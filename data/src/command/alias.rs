@@ -63,6 +63,41 @@ pub(super) fn expand(
     context: &Context<'_>,
     config: &Config,
 ) -> Result<Option<String>, Error> {
+    let Some(alias) = find(command, raw_args, config)? else {
+        return Ok(None);
+    };
+
+    if is_multi_step(alias) {
+        return Err(Error::MultiStepAlias);
+    }
+
+    Ok(Some(expand_alias(alias, raw_args, context)))
+}
+
+/// Expands `input` into the commands to send one after the other, if it
+/// invokes an alias made up of multiple steps.
+pub fn steps(
+    input: &str,
+    context: &Context<'_>,
+    config: &Config,
+) -> Option<Result<Vec<String>, Error>> {
+    let (command, _, raw_args) = super::parse_input(input).ok()?;
+
+    match find(command, raw_args, config) {
+        Ok(Some(alias)) if is_multi_step(alias) => Some(Ok(alias
+            .lines()
+            .map(|step| expand_alias(step, raw_args, context))
+            .collect())),
+        Ok(_) => None,
+        Err(error) => Some(Err(error)),
+    }
+}
+
+fn find<'a>(
+    command: &str,
+    raw_args: &str,
+    config: &'a Config,
+) -> Result<Option<&'a str>, Error> {
     let Some(alias) = config
         .buffer
         .commands
@@ -83,7 +118,12 @@ pub(super) fn expand(
         });
     }
 
-    Ok(Some(expand_alias(alias, raw_args, context)))
+    Ok(Some(alias))
+}
+
+/// Steps of an alias are stored one per line.
+fn is_multi_step(alias: &str) -> bool {
+    alias.lines().nth(1).is_some()
 }
 
 pub fn required_args(alias: &str) -> usize {
@@ -112,25 +152,21 @@ fn positional_arg_optionality(alias: &str) -> Vec<bool> {
     let mut optionality = Vec::new();
     let mut rest = alias;
 
-    while let Some(index) = rest.find('$') {
-        rest = &rest[index + 1..];
-
-        if let Some(placeholder) = parse_placeholder(rest) {
-            if let Placeholder::Argument {
-                index,
-                optional: is_optional,
-                ..
-            } = placeholder
-            {
-                if optionality.len() <= index {
-                    optionality.resize(index + 1, false);
-                }
-
-                optionality[index] |= is_optional;
+    while let Some((_, placeholder, after)) = next_placeholder(rest) {
+        if let Placeholder::Argument {
+            index,
+            optional: is_optional,
+            ..
+        } = placeholder
+        {
+            if optionality.len() <= index {
+                optionality.resize(index + 1, false);
             }
 
-            rest = &rest[placeholder.consumed_len()..];
+            optionality[index] |= is_optional;
         }
+
+        rest = after;
     }
 
     optionality
@@ -156,23 +192,44 @@ fn substitute_args(
     let mut expanded = String::with_capacity(template.len());
     let mut rest = template;
 
-    while let Some(index) = rest.find('$') {
-        expanded.push_str(&rest[..index]);
-        rest = &rest[index + 1..];
-
-        let Some(placeholder) = parse_placeholder(rest) else {
-            expanded.push('$');
-            continue;
-        };
-
+    while let Some((before, placeholder, after)) = next_placeholder(rest) {
+        expanded.push_str(before);
         push_placeholder(&mut expanded, args, context, &placeholder);
-        rest = &rest[placeholder.consumed_len()..];
+        rest = after;
     }
 
     expanded.push_str(rest);
     expanded
 }
 
+/// Finds the first placeholder in `input`, either `$`-prefixed or enclosed in
+/// braces, returning it along with the text before and after it.
+fn next_placeholder(input: &str) -> Option<(&str, Placeholder, &str)> {
+    let mut offset = 0;
+
+    while let Some(index) = input[offset..].find(['$', '{']) {
+        let start = offset + index;
+        let after = &input[start + 1..];
+
+        let placeholder = if input[start..].starts_with('$') {
+            parse_placeholder(after).map(|placeholder| {
+                let len = placeholder.consumed_len();
+                (placeholder, len)
+            })
+        } else {
+            parse_braced_placeholder(after)
+        };
+
+        if let Some((placeholder, len)) = placeholder {
+            return Some((&input[..start], placeholder, &after[len..]));
+        }
+
+        offset = start + 1;
+    }
+
+    None
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Placeholder {
     Argument {
@@ -238,6 +295,31 @@ fn parse_placeholder(input: &str) -> Option<Placeholder> {
     None
 }
 
+/// Parses `{0}` through `{8}`, `{args}` and the named variables, returning
+/// the placeholder and the length consumed including the closing brace.
+fn parse_braced_placeholder(input: &str) -> Option<(Placeholder, usize)> {
+    let name = &input[..input.find('}')?];
+
+    let placeholder = match name.as_bytes() {
+        [digit @ b'0'..=b'8'] => Placeholder::Argument {
+            index: (digit - b'0') as usize,
+            optional: false,
+            take_rest: false,
+        },
+        b"args" => Placeholder::Argument {
+            index: 0,
+            optional: false,
+            take_rest: true,
+        },
+        _ => [Variable::Nick, Variable::Channel, Variable::Server]
+            .into_iter()
+            .find(|variable| variable.as_str() == name)
+            .map(Placeholder::Variable)?,
+    };
+
+    Some((placeholder, name.len() + 1))
+}
+
 fn push_placeholder(
     expanded: &mut String,
     args: &[&str],
@@ -415,6 +497,44 @@ mod tests {
         assert_eq!(parse_placeholder(""), None);
     }
 
+    #[test]
+    fn parse_braced_placeholder_valid() {
+        assert_eq!(
+            parse_braced_placeholder("0}"),
+            Some((
+                Placeholder::Argument {
+                    index: 0,
+                    optional: false,
+                    take_rest: false,
+                },
+                2
+            ))
+        );
+        assert_eq!(
+            parse_braced_placeholder("args} rest"),
+            Some((
+                Placeholder::Argument {
+                    index: 0,
+                    optional: false,
+                    take_rest: true,
+                },
+                5
+            ))
+        );
+        assert_eq!(
+            parse_braced_placeholder("channel}"),
+            Some((Placeholder::Variable(Variable::Channel), 8))
+        );
+    }
+
+    #[test]
+    fn parse_braced_placeholder_invalid() {
+        assert_eq!(parse_braced_placeholder("9}"), None);
+        assert_eq!(parse_braced_placeholder("10}"), None);
+        assert_eq!(parse_braced_placeholder("nick"), None);
+        assert_eq!(parse_braced_placeholder("}"), None);
+    }
+
     // --- substitute_args ---
 
     #[test]
@@ -539,6 +659,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn substitute_braced_placeholders() {
+        let context = Context {
+            nick: Some(Cow::Borrowed("casperstorm")),
+            channel: Some(Cow::Borrowed("#halloy")),
+            server: None,
+        };
+
+        assert_eq!(
+            substitute_args(
+                "/msg {0} {nick} says {args} in {channel}",
+                &["alice", "hi"],
+                &context
+            ),
+            "/msg alice casperstorm says alice hi in #halloy"
+        );
+    }
+
+    #[test]
+    fn substitute_preserves_literal_braces() {
+        assert_eq!(
+            substitute_args("/me {shrugs} {0}", &["ok"], &Context::default()),
+            "/me {shrugs} ok"
+        );
+    }
+
+    #[test]
+    fn required_args_braced_placeholders() {
+        assert_eq!(required_args("/me is now playing {args}"), 1);
+        assert_eq!(required_args("/kick {2} {0}"), 3);
+    }
+
     // --- expand_alias ---
 
     #[test]
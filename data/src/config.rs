@@ -21,16 +21,21 @@ pub use self::display::Display;
 pub use self::file_transfer::FileTransfer;
 pub use self::filehost::Filehost;
 pub use self::highlights::Highlights;
+pub use self::ignore::Ignore;
 pub use self::keys::Keyboard;
+pub use self::lock::Lock;
 pub use self::logs::Logs;
+pub use self::mouse::Mouse;
 pub use self::notification::Notifications;
 pub use self::pane::Pane;
 pub use self::platform_specific::PlatformSpecific;
+pub use self::plugins::Plugins;
 pub use self::preview::Preview;
 pub use self::proxy::Proxy;
 pub use self::runtime::Runtime;
 pub use self::server::Server;
 pub use self::sidebar::Sidebar;
+pub use self::status_bar::StatusBar;
 pub use self::window::Window;
 use crate::appearance::theme::Styles;
 use crate::appearance::{self, Appearance};
@@ -41,7 +46,7 @@ use crate::serde::{
 };
 use crate::server::{ConfigMap as ServerMap, ServerName};
 use crate::shortcut::{Commands, KeyBind};
-use crate::{Theme, environment};
+use crate::{Theme, demo, environment};
 
 pub mod actions;
 pub mod buffer;
@@ -51,18 +56,23 @@ pub mod display;
 pub mod file_transfer;
 pub mod filehost;
 pub mod highlights;
+pub mod ignore;
 pub mod inclusivities;
 pub mod keys;
+pub mod lock;
 pub mod logs;
 pub mod metadata;
+pub mod mouse;
 pub mod notification;
 pub mod pane;
 pub mod platform_specific;
+pub mod plugins;
 pub mod preview;
 pub mod proxy;
 pub mod runtime;
 pub mod server;
 pub mod sidebar;
+pub mod status_bar;
 pub mod window;
 
 const CONFIG_TEMPLATE: &str = include_str!("../../config.toml");
@@ -79,7 +89,9 @@ pub struct Config {
     pub buffer: Buffer,
     pub pane: Pane,
     pub sidebar: Sidebar,
+    pub status_bar: StatusBar,
     pub keyboard: Keyboard,
+    pub mouse: Mouse,
     pub notifications: Notifications,
     pub file_transfer: FileTransfer,
     pub filehost: Filehost,
@@ -89,11 +101,15 @@ pub struct Config {
     pub highlights: Highlights,
     pub actions: Actions,
     pub ctcp: Ctcp,
+    pub ignore: Ignore,
+    pub lock: Lock,
     pub display: Display,
     pub logs: Logs,
     pub platform_specific: PlatformSpecific,
+    pub plugins: Plugins,
     pub runtime: Runtime,
     pub check_for_update_on_launch: bool,
+    pub language: Option<String>,
     pub metadata: Metadata,
 }
 
@@ -109,7 +125,9 @@ impl Default for Config {
             buffer: Buffer::default(),
             pane: Pane::default(),
             sidebar: Sidebar::default(),
+            status_bar: StatusBar::default(),
             keyboard: Keyboard::default(),
+            mouse: Mouse::default(),
             notifications: Notifications::default(),
             file_transfer: FileTransfer::default(),
             filehost: Filehost::default(),
@@ -119,11 +137,15 @@ impl Default for Config {
             highlights: Highlights::default(),
             actions: Actions::default(),
             ctcp: Ctcp::default(),
+            ignore: Ignore::default(),
+            lock: Lock::default(),
             display: Display::default(),
             logs: Logs::default(),
             platform_specific: PlatformSpecific::default(),
+            plugins: Plugins::default(),
             runtime: Runtime::default(),
             check_for_update_on_launch: true,
+            language: None,
             metadata: Metadata::default(),
         }
     }
@@ -191,6 +213,7 @@ impl Tooltips {
 }
 
 #[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(from = "f32")]
 pub struct ScaleFactor(f32);
 
 impl Default for ScaleFactor {
@@ -373,10 +396,31 @@ impl Config {
         dir
     }
 
+    pub fn plugins_dir() -> PathBuf {
+        let dir = Self::config_dir().join("plugins");
+
+        if !dir.exists() {
+            create_owned_dir(dir.as_path())
+                .expect("expected permissions to create plugins folder");
+        }
+
+        dir
+    }
+
     pub fn path() -> PathBuf {
         Self::config_dir().join(environment::CONFIG_FILE_NAME)
     }
 
+    /// Path of the config file for saving settings to.  The config file is
+    /// never written in demo mode.
+    pub fn writable_path() -> Result<PathBuf, Error> {
+        if demo::is_enabled() {
+            Err(Error::DemoMode)
+        } else {
+            Ok(Self::path())
+        }
+    }
+
     pub async fn load() -> Result<Self, Error> {
         use tokio::fs;
 
@@ -470,7 +514,9 @@ impl Config {
             pub buffer: Buffer,
             pub pane: Pane,
             pub sidebar: Sidebar,
+            pub status_bar: StatusBar,
             pub keyboard: Keyboard,
+            pub mouse: Mouse,
             pub notifications: Notifications,
             pub file_transfer: FileTransfer,
             pub filehost: Filehost,
@@ -480,11 +526,15 @@ impl Config {
             pub highlights: Highlights,
             pub actions: Actions,
             pub ctcp: Ctcp,
+            pub ignore: Ignore,
+            pub lock: Lock,
             pub display: Display,
             pub logs: Logs,
             pub platform_specific: PlatformSpecific,
+            pub plugins: Plugins,
             pub runtime: Runtime,
             pub check_for_update_on_launch: bool,
+            pub language: Option<String>,
             pub metadata: Metadata,
         }
 
@@ -500,7 +550,9 @@ impl Config {
                     buffer: Buffer::default(),
                     pane: Pane::default(),
                     sidebar: Sidebar::default(),
+                    status_bar: StatusBar::default(),
                     keyboard: Keyboard::default(),
+                    mouse: Mouse::default(),
                     notifications: Notifications::default(),
                     file_transfer: FileTransfer::default(),
                     filehost: Filehost::default(),
@@ -510,23 +562,31 @@ impl Config {
                     highlights: Highlights::default(),
                     actions: Actions::default(),
                     ctcp: Ctcp::default(),
+                    ignore: Ignore::default(),
+                    lock: Lock::default(),
                     display: Display::default(),
                     logs: Logs::default(),
                     platform_specific: PlatformSpecific::default(),
+                    plugins: Plugins::default(),
                     runtime: Runtime::default(),
                     check_for_update_on_launch: true,
+                    language: None,
                     metadata: Metadata::default(),
                 }
             }
         }
 
         let path = Self::path();
-        if !path.try_exists()? {
+        let content = if path.try_exists()? {
+            fs::read_to_string(path)
+                .await
+                .map_err(|e| Error::LoadConfigFile(e.to_string()))?
+        } else if demo::is_enabled() {
+            // Demo mode works without a config file.
+            String::new()
+        } else {
             return Err(Error::ConfigMissing);
-        }
-        let content = fs::read_to_string(path)
-            .await
-            .map_err(|e| Error::LoadConfigFile(e.to_string()))?;
+        };
 
         let config = toml::Deserializer::parse(content.as_ref())
             .map_err(|e| Error::Parse(ParseError::new(&content, &e)))?;
@@ -540,7 +600,9 @@ impl Config {
             scale_factor,
             buffer,
             sidebar,
+            status_bar,
             keyboard,
+            mouse,
             notifications,
             file_transfer,
             filehost,
@@ -551,11 +613,15 @@ impl Config {
             highlights,
             actions,
             ctcp,
+            ignore,
+            mut lock,
             display,
             logs,
             platform_specific,
+            plugins,
             runtime,
             check_for_update_on_launch,
+            language,
             metadata,
         } = serde_ignored::deserialize(config, |ignored| {
             log::warn!("[config.toml] Ignoring unknown setting: {ignored}");
@@ -564,8 +630,10 @@ impl Config {
 
         keyboard.validate()?;
 
+        lock.set_password().await?;
+
         let servers = ServerMap::new(
-            servers,
+            demo::servers().unwrap_or_else(|| servers.into_iter().collect()),
             sidebar.order_channels_by,
             buffer.typing.clone(),
         )
@@ -584,7 +652,9 @@ impl Config {
             scale_factor,
             buffer,
             sidebar,
+            status_bar,
             keyboard,
+            mouse,
             notifications,
             file_transfer,
             filehost,
@@ -595,15 +665,150 @@ impl Config {
             highlights,
             actions,
             ctcp,
+            ignore,
+            lock,
             display,
             logs,
             platform_specific,
+            plugins,
             runtime,
             check_for_update_on_launch,
+            language,
             metadata,
         })
     }
 
+    /// Set `font.family` in the config file, leaving the rest of the file
+    /// (including comments and formatting) untouched.  `None` removes the
+    /// setting, restoring the default font.
+    pub async fn save_font_family(family: Option<String>) -> Result<(), Error> {
+        use tokio::fs;
+
+        let path = Self::writable_path()?;
+        let content = fs::read_to_string(&path)
+            .await
+            .map_err(|e| Error::LoadConfigFile(e.to_string()))?;
+
+        let mut document = content
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|e| Error::LoadConfigFile(e.to_string()))?;
+
+        if let Some(family) = family {
+            document["font"]["family"] = toml_edit::value(family);
+        } else if let Some(font) = document
+            .get_mut("font")
+            .and_then(toml_edit::Item::as_table_like_mut)
+        {
+            font.remove("family");
+        }
+
+        fs::write(path, document.to_string()).await?;
+
+        Ok(())
+    }
+
+    /// Set `theme` in the config file, leaving the rest of the file
+    /// untouched.  When themes follow the OS appearance, only the `light` or
+    /// `dark` theme is replaced.
+    pub async fn save_theme(name: String, dark: bool) -> Result<(), Error> {
+        use tokio::fs;
+
+        let path = Self::writable_path()?;
+        let content = fs::read_to_string(&path)
+            .await
+            .map_err(|e| Error::LoadConfigFile(e.to_string()))?;
+
+        let mut document = content
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|e| Error::LoadConfigFile(e.to_string()))?;
+
+        if let Some(theme) = document
+            .get_mut("theme")
+            .and_then(toml_edit::Item::as_table_like_mut)
+        {
+            theme.insert(
+                if dark { "dark" } else { "light" },
+                toml_edit::value(name),
+            );
+        } else {
+            document["theme"] = toml_edit::value(name);
+        }
+
+        fs::write(path, document.to_string()).await?;
+
+        Ok(())
+    }
+
+    /// Set `servers.<server>.tls.pinned_cert_sha256` in the config file,
+    /// leaving the rest of the file untouched.
+    pub async fn save_pinned_cert(
+        server: String,
+        fingerprint: String,
+    ) -> Result<(), Error> {
+        use tokio::fs;
+
+        let path = Self::writable_path()?;
+        let content = fs::read_to_string(&path)
+            .await
+            .map_err(|e| Error::LoadConfigFile(e.to_string()))?;
+
+        let mut document = content
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|e| Error::LoadConfigFile(e.to_string()))?;
+
+        pin_cert(&mut document, &server, fingerprint)?;
+
+        fs::write(path, document.to_string()).await?;
+
+        Ok(())
+    }
+
+    /// Replace `from` with `to` in `servers.<server>.channels` in the config
+    /// file, leaving the rest of the file untouched.
+    pub async fn save_autojoin_channel(
+        server: String,
+        from: String,
+        to: String,
+    ) -> Result<(), Error> {
+        use tokio::fs;
+
+        let path = Self::writable_path()?;
+        let content = fs::read_to_string(&path)
+            .await
+            .map_err(|e| Error::LoadConfigFile(e.to_string()))?;
+
+        let mut document = content
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|e| Error::LoadConfigFile(e.to_string()))?;
+
+        let Some(channels) = document
+            .get_mut("servers")
+            .and_then(|servers| servers.get_mut(&server))
+            .and_then(|server| server.get_mut("channels"))
+            .and_then(toml_edit::Item::as_array_mut)
+        else {
+            return Err(Error::LoadConfigFile(format!(
+                "channels of server {server} not found"
+            )));
+        };
+
+        let Some(index) = channels.iter().position(|channel| {
+            channel
+                .as_str()
+                .is_some_and(|channel| channel.eq_ignore_ascii_case(&from))
+        }) else {
+            return Err(Error::LoadConfigFile(format!(
+                "{from} is not in the channels of server {server}"
+            )));
+        };
+
+        channels.replace(index, to);
+
+        fs::write(path, document.to_string()).await?;
+
+        Ok(())
+    }
+
     async fn load_appearance(
         theme_keys: (String, Option<String>),
     ) -> Result<Appearance, Error> {
@@ -698,7 +903,7 @@ impl Config {
     pub fn create_initial_config() {
         // Checks if a config file is there
         let config_file = Self::path();
-        if config_file.exists() {
+        if config_file.exists() || demo::is_enabled() {
             return;
         }
 
@@ -816,6 +1021,55 @@ impl ParseError {
     }
 }
 
+/// Set `tls.pinned_cert_sha256` for `server` in `document`.  Servers written
+/// as inline tables get an inline `tls` table.
+fn pin_cert(
+    document: &mut toml_edit::DocumentMut,
+    server: &str,
+    fingerprint: String,
+) -> Result<(), Error> {
+    let Some(item) = document
+        .get_mut("servers")
+        .and_then(|servers| servers.get_mut(server))
+    else {
+        return Err(Error::LoadConfigFile(format!(
+            "server {server} not found"
+        )));
+    };
+
+    let inline = item.is_inline_table();
+
+    let Some(table) = item.as_table_like_mut() else {
+        return Err(Error::LoadConfigFile(format!(
+            "server {server} is not a table"
+        )));
+    };
+
+    if table.get("tls").is_none() {
+        table.insert(
+            "tls",
+            if inline {
+                toml_edit::Item::Value(toml_edit::InlineTable::new().into())
+            } else {
+                toml_edit::table()
+            },
+        );
+    }
+
+    let Some(tls) = table
+        .get_mut("tls")
+        .and_then(toml_edit::Item::as_table_like_mut)
+    else {
+        return Err(Error::LoadConfigFile(format!(
+            "servers.{server}.tls is not a table"
+        )));
+    };
+
+    tls.insert("pinned_cert_sha256", toml_edit::value(fingerprint));
+
+    Ok(())
+}
+
 #[derive(Debug, Error, Clone)]
 pub enum Error {
     #[error("config could not be read: {0}")]
@@ -844,10 +1098,16 @@ pub enum Error {
         "Exactly one of sasl.plain.password, sasl.plain.password_file or sasl.plain.password_command must be set."
     )]
     DuplicateSaslPassword,
+    #[error(
+        "Only one of lock.password, lock.password_file and lock.password_command can be set."
+    )]
+    DuplicateLockPassword,
     #[error("Keybind \"{}\" is assigned to multiple actions: {}", keybind.as_config_string(), actions.as_config_string())]
     KeyBindConflict { keybind: KeyBind, actions: Commands },
     #[error("Config does not exist")]
     ConfigMissing,
+    #[error("config is not saved in demo mode")]
+    DemoMode,
 }
 
 impl From<std::io::Error> for Error {
@@ -855,3 +1115,46 @@ impl From<std::io::Error> for Error {
         Self::Io(error.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pinned(content: &str, server: &str) -> String {
+        let mut document = content.parse::<toml_edit::DocumentMut>().unwrap();
+
+        pin_cert(&mut document, server, "ab:cd".to_string()).unwrap();
+
+        document.to_string()
+    }
+
+    #[test]
+    fn pin_cert_tables() {
+        assert_eq!(
+            pinned(
+                "[servers.libera]\nserver = \"irc.libera.chat\"\n",
+                "libera"
+            ),
+            "[servers.libera]\nserver = \"irc.libera.chat\"\n\n\
+             [servers.libera.tls]\npinned_cert_sha256 = \"ab:cd\"\n"
+        );
+        assert_eq!(
+            pinned("[servers.libera]\ntls = { verify = true }\n", "libera"),
+            "[servers.libera]\n\
+             tls = { verify = true , pinned_cert_sha256 = \"ab:cd\" }\n"
+        );
+        assert_eq!(
+            pinned("[servers]\nlibera = { server = \"irc\" }\n", "libera"),
+            "[servers]\n\
+             libera = { server = \"irc\" , tls = { pinned_cert_sha256 = \"ab:cd\" } }\n"
+        );
+        assert!(
+            pin_cert(
+                &mut "[servers.libera]\n".parse().unwrap(),
+                "oftc",
+                String::new()
+            )
+            .is_err()
+        );
+    }
+}
@@ -15,6 +15,7 @@ pub struct Actions {
 #[serde(default)]
 pub struct Buffer {
     pub click_channel_name: ChannelClickAction,
+    pub click_unjoined_channel: UnjoinedChannelAction,
     pub click_highlight: ChannelClickAction,
     pub click_channel_discovery: ChannelClickAction,
     #[serde(alias = "click_nickname")]
@@ -32,6 +33,7 @@ impl Default for Buffer {
     fn default() -> Self {
         Self {
             click_channel_name: ChannelClickAction::default(),
+            click_unjoined_channel: UnjoinedChannelAction::default(),
             click_highlight: ChannelClickAction::default(),
             click_channel_discovery: ChannelClickAction::default(),
             click_username: NicknameClickAction::default(),
@@ -52,6 +54,19 @@ pub struct Sidebar {
     pub channel: Option<BufferAction>,
     pub query: Option<BufferAction>,
     pub focused_buffer: Option<BufferFocusedAction>,
+    pub middle_click: Option<SidebarClickAction>,
+    pub double_click: Option<SidebarClickAction>,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SidebarClickAction {
+    NewPane,
+    ReplacePane,
+    NewWindow,
+    ClosePane,
+    Leave,
+    MarkAsRead,
 }
 
 #[derive(Debug, Default, Clone, Deserialize)]
@@ -169,6 +184,15 @@ impl ChannelClickAction {
     }
 }
 
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnjoinedChannelAction {
+    #[default]
+    Join,
+    Preview,
+    Prompt,
+}
+
 #[derive(Debug, Copy, Clone, Default, Deserialize)]
 #[serde(default)]
 pub struct Notification {
@@ -1,9 +1,10 @@
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 use chrono::format::SecondsFormat;
-use chrono::{DateTime, Local, NaiveDate, Utc};
+use chrono::{DateTime, Local, Locale, NaiveDate, Utc};
 use iced::Color;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 
 pub use self::channel::{Channel, ChannelNameCasing};
 pub use self::hide_consecutive::{HideConsecutive, HideConsecutiveEnabled};
@@ -21,7 +22,7 @@ use crate::config::inclusivities::{
 use crate::message::source;
 use crate::target::TargetRef;
 use crate::user::Nick;
-use crate::{Server, isupport};
+use crate::{Server, i18n, isupport};
 
 pub mod channel;
 pub mod hide_consecutive;
@@ -53,12 +54,30 @@ pub struct Buffer {
     pub redaction: Redaction,
     pub close: Close,
     pub reply: Reply,
+    pub selection: Selection,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct Selection {
+    pub double_click: bool,
+    pub triple_click: bool,
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self {
+            double_click: true,
+            triple_click: true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
 #[serde(default)]
 pub struct Close {
     pub query: CloseQuery,
+    pub confirm: CloseConfirm,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -75,6 +94,35 @@ impl CloseQuery {
     }
 }
 
+/// Which closes to ask about before going through with them.
+#[derive(Debug, Clone, Copy, Deserialize, Default)]
+#[serde(default)]
+pub struct CloseConfirm {
+    pub leave_channel: bool,
+    pub query_with_unread: bool,
+    pub pane_with_draft: bool,
+}
+
+impl CloseConfirm {
+    pub fn asks(&self, confirmation: CloseConfirmation) -> bool {
+        match confirmation {
+            CloseConfirmation::LeaveChannel => self.leave_channel,
+            CloseConfirmation::QueryWithUnread => self.query_with_unread,
+            CloseConfirmation::PaneWithDraft => self.pane_with_draft,
+        }
+    }
+}
+
+/// A rule from [`CloseConfirm`], remembered once the user asks not to be
+/// prompted for it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CloseConfirmation {
+    LeaveChannel,
+    QueryWithUnread,
+    PaneWithDraft,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct Reply {
@@ -128,6 +176,7 @@ pub struct Emojis {
     pub skin_tone: SkinTone,
     pub auto_replace: bool,
     pub characters_to_trigger_picker: usize,
+    pub picker_button: bool,
 }
 
 impl Default for Emojis {
@@ -137,6 +186,7 @@ impl Default for Emojis {
             skin_tone: SkinTone::default(),
             auto_replace: true,
             characters_to_trigger_picker: 2,
+            picker_button: true,
         }
     }
 }
@@ -163,6 +213,7 @@ pub struct MarkAsRead {
     pub on_scroll_to_bottom: bool,
     pub on_message_sent: bool,
     pub on_message: OnMessage,
+    pub on_focus: OnFocus,
 }
 
 impl Default for MarkAsRead {
@@ -173,6 +224,7 @@ impl Default for MarkAsRead {
             on_scroll_to_bottom: true,
             on_message_sent: true,
             on_message: OnMessage::default(),
+            on_focus: OnFocus::default(),
         }
     }
 }
@@ -252,6 +304,73 @@ impl<'de> Deserialize<'de> for OnMessage {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OnFocus {
+    #[default]
+    None,
+    Immediately,
+    ScrolledToBottom,
+    After(Duration),
+}
+
+impl OnFocus {
+    /// Whether a buffer that has been focused for `focused_for` should be
+    /// marked as read.
+    pub fn mark_as_read(
+        &self,
+        focused_for: Duration,
+        is_scrolled_to_bottom: Option<bool>,
+    ) -> bool {
+        match self {
+            OnFocus::None => false,
+            OnFocus::Immediately => true,
+            OnFocus::ScrolledToBottom => is_scrolled_to_bottom.unwrap_or(false),
+            OnFocus::After(delay) => focused_for >= *delay,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OnFocus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "kebab-case")]
+        pub enum Enum {
+            Immediately,
+            ScrolledToBottom,
+            None,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OnFocusCondition {
+            Enum(Enum),
+            Bool(bool),
+            Seconds(u64),
+        }
+
+        match OnFocusCondition::deserialize(deserializer)? {
+            OnFocusCondition::Enum(on_focus) => Ok(match on_focus {
+                Enum::Immediately => Self::Immediately,
+                Enum::ScrolledToBottom => Self::ScrolledToBottom,
+                Enum::None => Self::None,
+            }),
+            OnFocusCondition::Bool(boolean) => {
+                if boolean {
+                    Ok(Self::Immediately)
+                } else {
+                    Ok(Self::None)
+                }
+            }
+            OnFocusCondition::Seconds(seconds) => {
+                Ok(Self::After(Duration::from_secs(seconds)))
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Default)]
 #[serde(default)]
 pub struct Quit {
@@ -341,7 +460,14 @@ fn deserialize_aliases<'de, D>(
 where
     D: Deserializer<'de>,
 {
-    let aliases = HashMap::<String, String>::deserialize(deserializer)?;
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Command {
+        Single(String),
+        Steps(Vec<String>),
+    }
+
+    let aliases = HashMap::<String, Command>::deserialize(deserializer)?;
 
     aliases.into_iter().try_fold(
         HashMap::new(),
@@ -355,15 +481,26 @@ where
                 ));
             }
 
-            let command = command.trim().to_string();
+            let steps = match command {
+                Command::Single(command) => vec![command],
+                Command::Steps(steps) => steps,
+            };
 
-            if command.is_empty() {
+            // Steps are kept one per line
+            let steps = steps
+                .iter()
+                .flat_map(|step| step.lines())
+                .map(str::trim)
+                .filter(|step| !step.is_empty())
+                .collect::<Vec<_>>();
+
+            if steps.is_empty() {
                 return Err(serde::de::Error::custom(format!(
                     "alias '{alias}' cannot map to an empty command"
                 )));
             }
 
-            if !command.starts_with('/') {
+            if steps.iter().any(|step| !step.starts_with('/')) {
                 log::warn!(
                     "ignoring alias '{alias}' as it doesnt start with '/'"
                 );
@@ -371,6 +508,8 @@ where
                 return Ok(normalized);
             }
 
+            let command = steps.join("\n");
+
             if !crate::command::alias::has_trailing_optional_args(&command) {
                 return Err(serde::de::Error::custom(format!(
                     "alias '{alias}' has a required argument after an optional one"
@@ -467,6 +606,7 @@ pub struct ServerMessages {
     pub request_topic: ServerMessage,
     pub away: ServerMessage,
     pub invite: ServerMessage,
+    pub ctcp: ServerMessage,
     pub default: ServerMessageDefault,
 }
 
@@ -495,6 +635,7 @@ impl Default for ServerMessages {
             request_topic: ServerMessage::default(),
             away: ServerMessage::default(),
             invite: ServerMessage::default(),
+            ctcp: ServerMessage::default(),
             default: ServerMessageDefault::default(),
         }
     }
@@ -527,6 +668,7 @@ impl ServerMessages {
             source::server::Kind::RequestTopic => &self.request_topic,
             source::server::Kind::Away => &self.away,
             source::server::Kind::Invite => &self.invite,
+            source::server::Kind::Ctcp => &self.ctcp,
         }
     }
 
@@ -685,7 +827,8 @@ impl Condensation {
             | source::server::Kind::ChangeTopic
             | source::server::Kind::RequestTopic
             | source::server::Kind::Away
-            | source::server::Kind::Invite => false,
+            | source::server::Kind::Invite
+            | source::server::Kind::Ctcp => false,
         }
     }
 
@@ -883,12 +1026,15 @@ pub enum LevelFilter {
 #[serde(default)]
 pub struct ChatHistory {
     pub infinite_scroll: bool,
+    /// Fold messages replayed after reconnecting behind an expander
+    pub fold_replayed: bool,
 }
 
 impl Default for ChatHistory {
     fn default() -> Self {
         Self {
             infinite_scroll: true,
+            fold_replayed: true,
         }
     }
 }
@@ -949,6 +1095,11 @@ impl<'de> Deserialize<'de> for AccessLevelFormat {
 }
 
 impl Buffer {
+    /// Locale used to format timestamps and date separators.
+    pub fn locale(&self) -> Locale {
+        self.timestamp.locale.unwrap_or_else(i18n::locale)
+    }
+
     pub fn format_timestamp(
         &self,
         date_time: &DateTime<Utc>,
@@ -957,12 +1108,13 @@ impl Buffer {
             return None;
         }
 
-        Some(self.timestamp.brackets.format(
-            date_time.with_timezone(&Local).format_localized(
-                &self.timestamp.format,
-                self.timestamp.locale,
+        Some(
+            self.timestamp.brackets.format(
+                date_time
+                    .with_timezone(&Local)
+                    .format_localized(&self.timestamp.format, self.locale()),
             ),
-        ))
+        )
     }
 
     pub fn format_range_end_timestamp(
@@ -981,7 +1133,7 @@ impl Buffer {
                     "{}",
                     end_date_time.with_timezone(&Local).format_localized(
                         &self.timestamp.format,
-                        self.timestamp.locale
+                        self.locale()
                     )
                 ))
                 .to_string(),
@@ -996,7 +1148,7 @@ impl Buffer {
             .with_timezone(&Local)
             .format_localized(
                 &self.timestamp.context_menu_format,
-                self.timestamp.locale,
+                self.locale(),
             )
             .to_string()
     }
@@ -1005,7 +1157,7 @@ impl Buffer {
         if let Some(copy_format) = &self.timestamp.copy_format {
             date_time
                 .with_timezone(&Local)
-                .format_localized(copy_format, self.timestamp.locale)
+                .format_localized(copy_format, self.locale())
                 .to_string()
         } else {
             date_time
@@ -1022,12 +1174,12 @@ impl Buffer {
                 // revert to default format
                 date.format_localized(
                     &DateSeparators::default().format,
-                    self.timestamp.locale,
+                    self.locale(),
                 ),
                 |date_time| {
                     date_time.format_localized(
                         &self.date_separators.format,
-                        self.timestamp.locale,
+                        self.locale(),
                     )
                 },
             )
@@ -8,11 +8,15 @@ use crate::serde::deserialize_u32_positive_integer;
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(default)]
 pub struct Channel {
+    /// Keep the buffer of a channel we leave, read-only, instead of closing
+    /// it.
+    pub archive_on_leave: bool,
     pub nicklist: Nicklist,
     #[serde(alias = "topic")] // For backwards compatibility
     pub topic_banner: TopicBanner,
     pub message: Message,
     pub channel_name_casing: Option<ChannelNameCasing>,
+    pub moderation_queue: ModerationQueue,
 }
 
 #[derive(Debug, Copy, Clone, Deserialize)]
@@ -57,6 +61,8 @@ pub struct Nicklist {
     pub width: Option<f32>,
     pub alignment: Alignment,
     pub show_access_levels: AccessLevelFormat,
+    /// Show the services account a user is logged in as in a tooltip.
+    pub show_account: bool,
     pub show_bot_icon: bool,
     pub truncate: Option<u16>,
 }
@@ -69,6 +75,7 @@ impl Default for Nicklist {
             width: None,
             alignment: Alignment::default(),
             show_access_levels: AccessLevelFormat::default(),
+            show_account: false,
             show_bot_icon: true,
             truncate: None,
         }
@@ -83,6 +90,22 @@ pub enum Alignment {
     Right,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ModerationQueue {
+    pub enabled: bool,
+    pub patterns: Vec<String>,
+}
+
+impl Default for ModerationQueue {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            patterns: vec!["!voice".to_string()],
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize)]
 #[serde(default)]
 pub struct TopicBanner {
@@ -9,6 +9,10 @@ pub struct Nickname {
     pub away: Away,
     pub offline: Offline,
     pub color: Color,
+    /// Mixed into nicknames before picking their color.
+    pub color_seed: Option<String>,
+    /// Color message text with the sender's nickname color.
+    pub color_message: bool,
     pub brackets: Brackets,
     pub alignment: Alignment,
     pub show_access_levels: AccessLevelFormat,
@@ -24,6 +28,8 @@ impl Default for Nickname {
             away: Away::default(),
             offline: Offline::default(),
             color: Color::default(),
+            color_seed: None,
+            color_message: false,
             brackets: Brackets::default(),
             alignment: Alignment::default(),
             show_access_levels: AccessLevelFormat::default(),
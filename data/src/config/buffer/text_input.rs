@@ -1,7 +1,12 @@
+use fancy_regex::{Regex, RegexBuilder};
 use serde::Deserialize;
 
 use crate::config::buffer::AccessLevelFormat;
+use crate::config::inclusivities::{Inclusivities, is_target_included};
+use crate::isupport;
 use crate::serde::deserialize_usize_positive_integer;
+use crate::server::Server;
+use crate::target::TargetRef;
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
@@ -16,6 +21,9 @@ pub struct TextInput {
     pub max_lines: usize,
     pub send_line_delay: u64,
     pub persist: bool,
+    pub history_length: usize,
+    /// Applied in order to the text of outgoing messages before sending.
+    pub filters: Vec<Filter>,
 }
 
 impl Default for TextInput {
@@ -30,6 +38,8 @@ impl Default for TextInput {
             max_lines: 5,
             send_line_delay: 100,
             persist: true,
+            history_length: 100,
+            filters: vec![],
         }
     }
 }
@@ -93,6 +103,8 @@ pub struct Autocomplete {
     pub order_by: OrderBy,
     pub sort_direction: SortDirection,
     pub completion_suffixes: [String; 2],
+    /// Number of recent messages whose words and URLs are also completed.
+    pub scrollback: usize,
 }
 
 impl Default for Autocomplete {
@@ -101,6 +113,7 @@ impl Default for Autocomplete {
             order_by: OrderBy::default(),
             sort_direction: SortDirection::default(),
             completion_suffixes: [": ".to_string(), " ".to_string()],
+            scrollback: 100,
         }
     }
 }
@@ -120,3 +133,114 @@ pub enum SortDirection {
     Asc,
     Desc,
 }
+
+#[derive(Debug, Clone)]
+pub struct Filter {
+    pub regex: Regex,
+    pub action: FilterAction,
+    pub exclude: Option<Inclusivities>,
+    pub include: Option<Inclusivities>,
+}
+
+#[derive(Debug, Clone)]
+pub enum FilterAction {
+    /// Replace matches, expanding `$1`, `$name`, etc.
+    Replace(String),
+    /// Refuse to send a matching message.
+    Block,
+    /// Ask before sending a matching message.
+    Confirm,
+}
+
+impl Filter {
+    /// Applies `filters` in order to `text`, or returns the filter that
+    /// blocks it.
+    pub fn apply_all<'a>(
+        filters: impl IntoIterator<Item = &'a Filter>,
+        text: String,
+    ) -> Result<String, &'a Filter> {
+        filters.into_iter().try_fold(text, |text, filter| {
+            match &filter.action {
+                FilterAction::Replace(replacement) => Ok(filter
+                    .regex
+                    .replace_all(&text, replacement.as_str())
+                    .into_owned()),
+                FilterAction::Block if filter.is_match(&text) => Err(filter),
+                FilterAction::Block | FilterAction::Confirm => Ok(text),
+            }
+        })
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text).unwrap_or(false)
+    }
+
+    /// Messages sent to a server buffer are always filtered.
+    pub fn is_target_included(
+        &self,
+        target: Option<TargetRef>,
+        server: &Server,
+        casemapping: isupport::CaseMap,
+    ) -> bool {
+        target.is_none_or(|target| {
+            is_target_included(
+                self.include.as_ref(),
+                self.exclude.as_ref(),
+                None,
+                target,
+                server,
+                casemapping,
+            )
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Filter {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Data {
+            regex: String,
+            replace: Option<String>,
+            #[serde(default)]
+            block: bool,
+            #[serde(default)]
+            confirm: bool,
+            exclude: Option<Inclusivities>,
+            include: Option<Inclusivities>,
+        }
+
+        let Data {
+            regex,
+            replace,
+            block,
+            confirm,
+            exclude,
+            include,
+        } = Data::deserialize(deserializer)?;
+
+        let action = match (replace, block, confirm) {
+            (Some(replace), false, false) => FilterAction::Replace(replace),
+            (None, true, false) => FilterAction::Block,
+            (None, false, true) => FilterAction::Confirm,
+            _ => {
+                return Err(serde::de::Error::custom(
+                    "filter must either `replace`, `block` or `confirm` matches",
+                ));
+            }
+        };
+
+        let regex = RegexBuilder::new(&regex).build().map_err(|err| {
+            serde::de::Error::custom(format!("invalid regex '{regex}': {err}"))
+        })?;
+
+        Ok(Filter {
+            regex,
+            action,
+            exclude,
+            include,
+        })
+    }
+}
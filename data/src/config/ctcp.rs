@@ -3,34 +3,68 @@ use serde::Deserialize;
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct Ctcp {
+    /// Respond to CTCP queries at all.
+    pub enabled: bool,
     pub ping: bool,
-    pub source: bool,
+    pub source: Reply,
     pub time: bool,
-    pub version: bool,
+    pub version: Reply,
     pub userinfo: Option<String>,
 }
 
 impl Default for Ctcp {
     fn default() -> Self {
         Self {
+            enabled: true,
             ping: true,
-            source: true,
+            source: Reply::default(),
             time: true,
-            version: true,
+            version: Reply::default(),
             userinfo: Option::default(),
         }
     }
 }
 
+/// Whether to respond to a query, and optionally with what in place of the
+/// default response.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Reply {
+    Enabled(bool),
+    Custom(String),
+}
+
+impl Default for Reply {
+    fn default() -> Self {
+        Self::Enabled(true)
+    }
+}
+
+impl Reply {
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, Self::Enabled(false))
+    }
+
+    /// The response to send, if enabled.
+    pub fn response(&self, default: impl Into<String>) -> Option<String> {
+        match self {
+            Self::Enabled(true) => Some(default.into()),
+            Self::Enabled(false) => None,
+            Self::Custom(response) => Some(response.clone()),
+        }
+    }
+}
+
 impl Ctcp {
-    pub fn client_info(&self) -> String {
+    /// Supported commands, including those `extra` ones handled by plugins.
+    pub fn client_info(&self, extra: &[String]) -> String {
         let mut commands = vec!["ACTION", "CLIENTINFO", "DCC"];
 
         if self.ping {
             commands.push("PING");
         }
 
-        if self.source {
+        if self.source.is_enabled() {
             commands.push("SOURCE");
         }
 
@@ -38,7 +72,7 @@ impl Ctcp {
             commands.push("TIME");
         }
 
-        if self.version {
+        if self.version.is_enabled() {
             commands.push("VERSION");
         }
 
@@ -46,6 +80,8 @@ impl Ctcp {
             commands.push("USERINFO");
         }
 
+        commands.extend(extra.iter().map(String::as_str));
+
         commands.join(" ")
     }
 }
@@ -1,10 +1,11 @@
 use std::net::IpAddr;
-use std::num::NonZeroU16;
+use std::num::{NonZeroU16, NonZeroUsize};
 use std::ops::RangeInclusive;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
+use crate::User;
 use crate::serde::deserialize_path_buf_with_path_transformations_maybe;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -18,10 +19,20 @@ pub struct FileTransfer {
     pub save_directory: Option<PathBuf>,
     /// If true, act as the "client" for the transfer. Requires the remote user act as the server.
     pub passive: bool,
+    /// If true, offer files over TLS (DCC SSEND). Requires the remote user's client to support it.
+    pub secure: bool,
     /// Time in seconds to wait before timing out a transfer waiting to be accepted.
     pub timeout: u64,
+    /// What to do when a received file already exists in save_directory.
+    pub on_collision: OnCollision,
     /// Auto-accept configuration for incoming file transfers.
     pub auto_accept: AutoAccept,
+    /// Auto-reject configuration for incoming file transfers.
+    pub auto_reject: AutoReject,
+    /// Maximum number of files received at once. Further accepted transfers wait for one to finish.
+    pub max_concurrent: Option<NonZeroUsize>,
+    /// Send files dropped onto a query or channel pane. Uploading to the filehost takes precedence when `filehost.file_drop` is enabled.
+    pub file_drop: bool,
     pub server: Option<Server>,
 }
 
@@ -31,13 +42,29 @@ impl Default for FileTransfer {
             enabled: true,
             save_directory: None,
             passive: true,
+            secure: false,
             timeout: 60 * 5,
+            on_collision: OnCollision::default(),
             auto_accept: AutoAccept::default(),
+            auto_reject: AutoReject::default(),
+            max_concurrent: None,
+            file_drop: false,
             server: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OnCollision {
+    /// Save as "name (1).ext", "name (2).ext", etc.
+    #[default]
+    Rename,
+    Overwrite,
+    /// Ask where to save the file.
+    Prompt,
+}
+
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(default)]
 pub struct AutoAccept {
@@ -49,6 +76,46 @@ pub struct AutoAccept {
     pub masks: Option<Vec<String>>,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AutoReject {
+    /// If true, automatically reject incoming file transfers matching any of the rules below.
+    pub enabled: bool,
+    /// Reject incoming file transfers from these nicks.
+    pub nicks: Vec<String>,
+    /// Reject incoming file transfers from these masks (regex patterns).
+    pub masks: Vec<String>,
+    /// Reject incoming files with these extensions (e.g. "exe"), ignoring case.
+    pub extensions: Vec<String>,
+    /// Reject incoming files larger than this many bytes.
+    pub max_size: Option<u64>,
+    /// Notice sent to the sender of a rejected file transfer. Rejected silently if not set.
+    pub notice: Option<String>,
+}
+
+impl AutoReject {
+    pub fn rejects(&self, from: &User, filename: &str, size: u64) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        let extension = Path::new(filename)
+            .extension()
+            .and_then(|extension| extension.to_str());
+
+        self.nicks.contains(&from.nickname().to_string())
+            || (!self.masks.is_empty() && from.matches_masks(&self.masks))
+            || extension.is_some_and(|extension| {
+                self.extensions.iter().any(|rejected| {
+                    rejected
+                        .trim_start_matches('.')
+                        .eq_ignore_ascii_case(extension)
+                })
+            })
+            || self.max_size.is_some_and(|max_size| size > max_size)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Server {
     /// Address advertised to the remote user to connect to
@@ -57,6 +124,10 @@ pub struct Server {
     pub bind_address: IpAddr,
     /// Port range used to bind with
     pub bind_ports: RangeInclusive<u16>,
+    /// Certificate presented when accepting secure (TLS) transfers
+    pub tls_cert_path: Option<PathBuf>,
+    /// Private key of the certificate, if not included in `tls_cert_path`
+    pub tls_key_path: Option<PathBuf>,
 }
 
 impl<'de> Deserialize<'de> for Server {
@@ -70,6 +141,16 @@ impl<'de> Deserialize<'de> for Server {
             bind_address: IpAddr,
             bind_port_first: NonZeroU16,
             bind_port_last: NonZeroU16,
+            #[serde(
+                default,
+                deserialize_with = "deserialize_path_buf_with_path_transformations_maybe"
+            )]
+            tls_cert_path: Option<PathBuf>,
+            #[serde(
+                default,
+                deserialize_with = "deserialize_path_buf_with_path_transformations_maybe"
+            )]
+            tls_key_path: Option<PathBuf>,
         }
 
         let Data {
@@ -77,6 +158,8 @@ impl<'de> Deserialize<'de> for Server {
             bind_address,
             bind_port_first,
             bind_port_last,
+            tls_cert_path,
+            tls_key_path,
         } = Data::deserialize(deserializer)?;
 
         if bind_port_last < bind_port_first {
@@ -89,6 +172,50 @@ impl<'de> Deserialize<'de> for Server {
             public_address,
             bind_address,
             bind_ports: bind_port_first.get()..=bind_port_last.get(),
+            tls_cert_path,
+            tls_key_path,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::AutoReject;
+    use crate::User;
+    use crate::isupport::CaseMap;
+
+    #[test]
+    fn auto_reject_rules() {
+        let user =
+            User::parse("alice!alice@example.com", CaseMap::default(), None)
+                .unwrap();
+
+        let auto_reject = AutoReject {
+            enabled: true,
+            extensions: vec![".exe".to_string(), "scr".to_string()],
+            max_size: Some(1024),
+            ..AutoReject::default()
+        };
+
+        assert!(auto_reject.rejects(&user, "setup.EXE", 10));
+        assert!(auto_reject.rejects(&user, "screensaver.scr", 10));
+        assert!(auto_reject.rejects(&user, "photo.jpg", 2048));
+        assert!(!auto_reject.rejects(&user, "photo.jpg", 1024));
+        assert!(!auto_reject.rejects(&user, "exe", 10));
+
+        let auto_reject = AutoReject {
+            enabled: true,
+            masks: vec![".*@example\\.com".to_string()],
+            ..AutoReject::default()
+        };
+
+        assert!(auto_reject.rejects(&user, "photo.jpg", 10));
+
+        let auto_reject = AutoReject {
+            nicks: vec!["alice".to_string()],
+            ..AutoReject::default()
+        };
+
+        assert!(!auto_reject.rejects(&user, "photo.jpg", 10));
+    }
+}
@@ -11,6 +11,9 @@ pub struct Filehost {
     pub paste: bool,
     /// Handle drag-and-drop. Defaults to `true`.
     pub file_drop: bool,
+    /// Offer to upload pasted text with more lines than the input allows.
+    /// Defaults to `true`.
+    pub paste_text: bool,
 }
 
 impl Default for Filehost {
@@ -20,6 +23,7 @@ impl Default for Filehost {
             button: true,
             paste: true,
             file_drop: false,
+            paste_text: true,
         }
     }
 }
@@ -36,4 +40,8 @@ impl Filehost {
     pub fn file_drop(&self) -> bool {
         self.enabled && self.file_drop
     }
+
+    pub fn paste_text(&self) -> bool {
+        self.enabled && self.paste_text
+    }
 }
@@ -14,6 +14,44 @@ pub struct Highlights {
     pub nickname: Nickname,
     #[serde(rename = "match")]
     pub matches: Vec<Match>,
+    pub export: Export,
+    pub deduplicate: Deduplicate,
+}
+
+/// Collapse the same highlight relayed into several channels.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Deduplicate {
+    pub enabled: bool,
+    /// Seconds within which a repeated message counts as a duplicate
+    pub window: u64,
+}
+
+impl Default for Deduplicate {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Export {
+    /// Shell command highlights are sent to from their context menu
+    pub command: Option<String>,
+    /// Seconds to wait for the command to finish
+    pub timeout: u64,
+}
+
+impl Default for Export {
+    fn default() -> Self {
+        Self {
+            command: None,
+            timeout: 10,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
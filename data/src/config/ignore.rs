@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+use crate::ignore::Mask;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Ignore {
+    /// Hostmasks whose messages are hidden, alongside those added with
+    /// `/ignore`.  These are globs, not regexes like the `masks` of other
+    /// settings.
+    pub masks: Vec<Mask>,
+}
@@ -25,6 +25,7 @@ pub struct Keyboard {
     pub toggle_nick_list: KeyBinds,
     pub toggle_topic: KeyBinds,
     pub toggle_sidebar: KeyBinds,
+    pub toggle_sidebar_filter: KeyBinds,
     pub toggle_fullscreen: KeyBinds,
     pub command_bar: KeyBinds,
     pub reload_configuration: KeyBinds,
@@ -47,6 +48,14 @@ pub struct Keyboard {
     pub open_config_file: KeyBinds,
     pub show_muted_buffers: KeyBinds,
     pub hide_muted_buffers: KeyBinds,
+    pub toggle_audio_mute: KeyBinds,
+    pub equalize_buffers: KeyBinds,
+    pub rotate_buffers: KeyBinds,
+    pub swap_previous_buffer: KeyBinds,
+    pub move_buffer_top: KeyBinds,
+    pub move_buffer_bottom: KeyBinds,
+    pub move_buffer_left: KeyBinds,
+    pub move_buffer_right: KeyBinds,
 }
 
 impl Default for Keyboard {
@@ -66,6 +75,7 @@ impl Default for Keyboard {
             leave_buffer: KeyBind::leave_buffer().into(),
             toggle_nick_list: KeyBind::toggle_nick_list().into(),
             toggle_sidebar: KeyBind::toggle_sidebar().into(),
+            toggle_sidebar_filter: KeyBind::toggle_sidebar_filter().into(),
             toggle_topic: KeyBind::toggle_topic().into(),
             toggle_fullscreen: KeyBind::toggle_fullscreen().into(),
             command_bar: KeyBind::command_bar().into(),
@@ -89,6 +99,14 @@ impl Default for Keyboard {
             open_config_file: KeyBind::open_config_file().into(),
             show_muted_buffers: KeyBind::show_muted_buffers().into(),
             hide_muted_buffers: KeyBind::hide_muted_buffers().into(),
+            toggle_audio_mute: KeyBind::toggle_audio_mute().into(),
+            equalize_buffers: KeyBind::equalize_buffers().into(),
+            rotate_buffers: KeyBind::rotate_buffers().into(),
+            swap_previous_buffer: KeyBind::swap_previous_buffer().into(),
+            move_buffer_top: KeyBind::move_buffer_top().into(),
+            move_buffer_bottom: KeyBind::move_buffer_bottom().into(),
+            move_buffer_left: KeyBind::move_buffer_left().into(),
+            move_buffer_right: KeyBind::move_buffer_right().into(),
         }
     }
 }
@@ -112,6 +130,7 @@ impl Keyboard {
             (&self.toggle_nick_list, ToggleNicklist),
             (&self.toggle_topic, ToggleTopic),
             (&self.toggle_sidebar, ToggleSidebar),
+            (&self.toggle_sidebar_filter, ToggleSidebarFilter),
             (&self.toggle_fullscreen, ToggleFullscreen),
             (&self.command_bar, CommandBar),
             (&self.reload_configuration, ReloadConfiguration),
@@ -135,6 +154,14 @@ impl Keyboard {
             (&self.open_config_file, OpenConfigFile),
             (&self.show_muted_buffers, ShowMutedBuffers),
             (&self.hide_muted_buffers, HideMutedBuffers),
+            (&self.toggle_audio_mute, ToggleAudioMute),
+            (&self.equalize_buffers, EqualizeBuffers),
+            (&self.rotate_buffers, RotateBuffers),
+            (&self.swap_previous_buffer, SwapPreviousBuffer),
+            (&self.move_buffer_top, MoveBufferTop),
+            (&self.move_buffer_bottom, MoveBufferBottom),
+            (&self.move_buffer_left, MoveBufferLeft),
+            (&self.move_buffer_right, MoveBufferRight),
         ]
     }
 
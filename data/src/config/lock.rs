@@ -0,0 +1,166 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tokio::fs;
+
+use crate::config::{self, server::read_from_command};
+use crate::serde::deserialize_path_buf_with_path_transformations_maybe;
+
+/// Locks Halloy behind a passphrase after a period without input.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Lock {
+    pub enabled: bool,
+    /// Seconds without input before Halloy locks.
+    pub timeout: u64,
+    pub password: Option<String>,
+    /// The file with the passphrase.
+    #[serde(
+        deserialize_with = "deserialize_path_buf_with_path_transformations_maybe"
+    )]
+    pub password_file: Option<PathBuf>,
+    /// Truncate read from password file to first newline
+    pub password_file_first_line_only: bool,
+    /// The command which outputs the passphrase.
+    pub password_command: Option<String>,
+    /// Hex encoded SHA-256 digest of the passphrase, to keep it out of the
+    /// config file.
+    pub password_sha256: Option<String>,
+}
+
+impl Default for Lock {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout: 600,
+            password: None,
+            password_file: None,
+            password_file_first_line_only: true,
+            password_command: None,
+            password_sha256: None,
+        }
+    }
+}
+
+impl Lock {
+    /// Reads the passphrase from `password_file` or `password_command`.
+    pub async fn set_password(&mut self) -> Result<(), config::Error> {
+        match (&self.password, &self.password_file, &self.password_command) {
+            (_, None, None) => {}
+            (None, Some(pass_file), None) => {
+                config::check_sensitive_file_permissions(
+                    "lock",
+                    pass_file,
+                    "password file",
+                );
+
+                let mut pass = fs::read_to_string(pass_file).await?;
+
+                if self.password_file_first_line_only {
+                    pass = pass
+                        .lines()
+                        .next()
+                        .map(String::from)
+                        .unwrap_or_default();
+                }
+
+                self.password = Some(pass);
+            }
+            (None, None, Some(pass_command)) => {
+                self.password = Some(read_from_command(pass_command).await?);
+            }
+            _ => return Err(config::Error::DuplicateLockPassword),
+        }
+
+        Ok(())
+    }
+
+    /// Locking requires a passphrase to unlock with.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+            && (self.password.is_some() || self.password_sha256.is_some())
+    }
+
+    pub fn verify(&self, passphrase: &str) -> bool {
+        if let Some(password) = &self.password {
+            return password == passphrase;
+        }
+
+        self.password_sha256.as_ref().is_some_and(|digest| {
+            hex::encode(Sha256::digest(passphrase.as_bytes()))
+                .eq_ignore_ascii_case(digest.trim())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify() {
+        let lock = Lock {
+            enabled: true,
+            password_sha256: Some(
+                "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08"
+                    .to_string(),
+            ),
+            ..Lock::default()
+        };
+
+        assert!(lock.is_enabled());
+        assert!(lock.verify("test"));
+        assert!(!lock.verify("Test"));
+
+        let lock = Lock {
+            enabled: true,
+            password: Some("hunter2".to_string()),
+            ..Lock::default()
+        };
+
+        assert!(lock.verify("hunter2"));
+        assert!(!lock.verify("hunter3"));
+        assert!(!Lock::default().is_enabled());
+    }
+
+    #[tokio::test]
+    async fn set_password() {
+        let path = std::env::temp_dir()
+            .join(format!("halloy-lock-password-{}", std::process::id()));
+        std::fs::write(&path, "hunter2\nsecond line\n").unwrap();
+
+        let mut lock = Lock {
+            enabled: true,
+            password_file: Some(path.clone()),
+            ..Lock::default()
+        };
+        let result = lock.set_password().await;
+
+        let _ = std::fs::remove_file(&path);
+
+        assert!(result.is_ok());
+        assert!(lock.is_enabled());
+        assert!(lock.verify("hunter2"));
+
+        let mut lock = Lock {
+            enabled: true,
+            password_command: Some("echo hunter2".to_string()),
+            ..Lock::default()
+        };
+
+        assert!(lock.set_password().await.is_ok());
+        assert!(lock.verify("hunter2"));
+
+        let mut lock = Lock {
+            password: Some("hunter2".to_string()),
+            password_command: Some("echo hunter2".to_string()),
+            ..Lock::default()
+        };
+
+        assert!(matches!(
+            lock.set_password().await,
+            Err(config::Error::DuplicateLockPassword)
+        ));
+    }
+}
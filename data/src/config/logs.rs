@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -5,8 +7,14 @@ use serde::Deserialize;
 pub struct Logs {
     pub file_level: LevelFilter,
     pub file_timestamp: Timestamp,
+    pub file_format: FileFormat,
+    /// Log levels for specific modules (e.g. `data::client`), overriding
+    /// `file_level`.
+    pub modules: HashMap<String, LevelFilter>,
     pub pane_level: LevelFilter,
     pub max_file_count: usize,
+    /// Port of the local metrics endpoint, disabled when unset.
+    pub metrics_port: Option<u16>,
 }
 
 impl Default for Logs {
@@ -14,13 +22,16 @@ impl Default for Logs {
         Self {
             file_level: LevelFilter::Debug,
             file_timestamp: Timestamp::default(),
+            file_format: FileFormat::default(),
+            modules: HashMap::new(),
             pane_level: LevelFilter::Info,
             max_file_count: 4,
+            metrics_port: None,
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Copy, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum LevelFilter {
     Off,
@@ -44,6 +55,15 @@ impl From<LevelFilter> for log::LevelFilter {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileFormat {
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum Timestamp {
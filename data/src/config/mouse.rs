@@ -0,0 +1,48 @@
+use serde::{Deserialize, Deserializer};
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct Mouse {
+    /// Multiplier applied to the distance scrolled by the mouse wheel.
+    #[serde(deserialize_with = "deserialize_scroll_speed")]
+    pub scroll_speed: f32,
+    pub sidebar: Sidebar,
+}
+
+impl Default for Mouse {
+    fn default() -> Self {
+        Self {
+            scroll_speed: 1.0,
+            sidebar: Sidebar::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct Sidebar {
+    /// Scrolling over the sidebar cycles buffers in the focused pane instead
+    /// of scrolling the sidebar.
+    pub wheel_cycles_buffers: bool,
+    /// When the sidebar is horizontal, shift has to be held for the wheel to
+    /// scroll it.
+    pub scroll_requires_shift: bool,
+}
+
+impl Default for Sidebar {
+    fn default() -> Self {
+        Self {
+            wheel_cycles_buffers: false,
+            scroll_requires_shift: true,
+        }
+    }
+}
+
+fn deserialize_scroll_speed<'de, D>(deserializer: D) -> Result<f32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let speed = f32::deserialize(deserializer)?;
+
+    Ok(speed.clamp(0.1, 10.0))
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 
 use crate::audio::Sound;
 use crate::config::inclusivities::{
@@ -18,6 +18,7 @@ pub struct Notification {
     pub request_attention: bool,
     pub show_content: bool,
     pub sound: Option<String>,
+    pub volume: Option<f32>,
     pub delay: Option<u32>,
     pub exclude: Option<Inclusivities>,
     pub include: Option<Inclusivities>,
@@ -30,6 +31,7 @@ impl Default for Notification {
             request_attention: false,
             show_content: false,
             sound: None,
+            volume: None,
             delay: Some(500),
             exclude: None,
             include: None,
@@ -56,9 +58,10 @@ impl Notification {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct Notifications {
+    pub volume: f32,
     pub connected: Notification,
     pub disconnected: Notification,
     pub reconnected: Notification,
@@ -70,9 +73,105 @@ pub struct Notifications {
     #[serde(rename = "channel")]
     pub channels: HashMap<String, Notification>,
     pub reaction: Notification,
+    pub unban_reminder: Notification,
+    pub plugin: Notification,
+    pub idle: Idle,
+    pub backend: Backend,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            connected: Notification::default(),
+            disconnected: Notification::default(),
+            reconnected: Notification::default(),
+            direct_message: Notification::default(),
+            highlight: Notification::default(),
+            file_transfer_request: Notification::default(),
+            monitored_online: Notification::default(),
+            monitored_offline: Notification::default(),
+            channels: HashMap::new(),
+            reaction: Notification::default(),
+            // Timed bans are scheduled explicitly, so remind by default.
+            unban_reminder: Notification {
+                show_toast: true,
+                ..Notification::default()
+            },
+            // Plugins notify on purpose, so show them by default.
+            plugin: Notification {
+                show_toast: true,
+                ..Notification::default()
+            },
+            idle: Idle::default(),
+            backend: Backend::default(),
+        }
+    }
+}
+
+/// How toasts are shown.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// The platform's notification service (D-Bus on Linux and the BSDs).
+    #[default]
+    Native,
+    /// The `terminal-notifier` command on macOS.
+    TerminalNotifier,
+    /// A shell command, given the toast in environment variables.
+    Command(String),
+}
+
+impl<'de> Deserialize<'de> for Backend {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            String(String),
+            Command { command: String },
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::String(value) => match value.as_str() {
+                "native" => Ok(Self::Native),
+                "terminal-notifier" => Ok(Self::TerminalNotifier),
+                _ => Err(serde::de::Error::custom(format!(
+                    "unknown notification backend: {value}",
+                ))),
+            },
+            Repr::Command { command } => Ok(Self::Command(command)),
+        }
+    }
+}
+
+/// Holds notifications back while Halloy is in use.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Idle {
+    pub enabled: bool,
+    /// Seconds without input (or focus) before Halloy is considered idle.
+    pub timeout: u64,
+}
+
+impl Default for Idle {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout: 300,
+        }
+    }
 }
 
 impl Notifications {
+    /// Playback volume for a notification's sound, combining the master
+    /// volume with the notification's own volume (both clamped to 0.0–1.0).
+    pub fn volume(&self, notification: &Notification) -> f32 {
+        self.volume.clamp(0.0, 1.0)
+            * notification.volume.unwrap_or(1.0).clamp(0.0, 1.0)
+    }
+
     pub fn load_sounds<'a>(
         &self,
         highlight_matches_sounds: impl Iterator<Item = &'a str>,
@@ -121,6 +220,12 @@ impl Notifications {
         if let Some(sound_name) = self.reaction.sound.as_deref() {
             load_and_insert(sound_name);
         }
+        if let Some(sound_name) = self.unban_reminder.sound.as_deref() {
+            load_and_insert(sound_name);
+        }
+        if let Some(sound_name) = self.plugin.sound.as_deref() {
+            load_and_insert(sound_name);
+        }
         for notification in self.channels.values() {
             if let Some(sound_name) = notification.sound.as_deref() {
                 load_and_insert(sound_name);
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 
 use crate::config::Scrollbar;
 
@@ -9,8 +9,10 @@ pub struct Pane {
     pub split_axis: SplitAxis,
     pub scrollbar: Scrollbar,
     pub restore_on_launch: bool,
+    pub focus_on_launch: FocusOnLaunch,
     pub gap: Gap,
     pub always_show_title_bar_buttons: bool,
+    pub gestures: Gestures,
 }
 
 impl Default for Pane {
@@ -19,12 +21,62 @@ impl Default for Pane {
             split_axis: SplitAxis::default(),
             scrollbar: Scrollbar::default(),
             restore_on_launch: true,
+            focus_on_launch: FocusOnLaunch::default(),
             gap: Gap::default(),
             always_show_title_bar_buttons: false,
+            gestures: Gestures::default(),
         }
     }
 }
 
+/// Buffer focused when launching.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FocusOnLaunch {
+    /// The buffer focused when Halloy was last closed
+    #[default]
+    Last,
+    /// An empty pane
+    Empty,
+    /// A server buffer, or one of its channels
+    Buffer {
+        server: String,
+        channel: Option<String>,
+    },
+}
+
+impl<'de> Deserialize<'de> for FocusOnLaunch {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "kebab-case")]
+        enum Named {
+            Last,
+            Empty,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Inner {
+            Named(Named),
+            Buffer {
+                server: String,
+                #[serde(default)]
+                channel: Option<String>,
+            },
+        }
+
+        Ok(match Inner::deserialize(deserializer)? {
+            Inner::Named(Named::Last) => FocusOnLaunch::Last,
+            Inner::Named(Named::Empty) => FocusOnLaunch::Empty,
+            Inner::Buffer { server, channel } => {
+                FocusOnLaunch::Buffer { server, channel }
+            }
+        })
+    }
+}
+
 #[derive(Debug, Copy, Clone, Deserialize)]
 #[serde(default)]
 pub struct Gap {
@@ -38,6 +90,25 @@ impl Default for Gap {
     }
 }
 
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(default)]
+pub struct Gestures {
+    /// Swipe horizontally with two fingers to cycle buffers in the focused
+    /// pane.
+    pub swipe_buffers: bool,
+    /// Pinch with two fingers to zoom the interface.
+    pub pinch_to_zoom: bool,
+}
+
+impl Default for Gestures {
+    fn default() -> Self {
+        Self {
+            swipe_buffers: true,
+            pinch_to_zoom: true,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum SplitAxis {
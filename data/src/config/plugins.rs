@@ -0,0 +1,25 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Plugins {
+    pub enabled: bool,
+    /// Plugins (file names without the `.lua` extension) which are not
+    /// loaded.
+    pub exclude: Vec<String>,
+}
+
+impl Default for Plugins {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            exclude: vec![],
+        }
+    }
+}
+
+impl Plugins {
+    pub fn is_excluded(&self, name: &str) -> bool {
+        self.exclude.iter().any(|exclude| exclude == name)
+    }
+}
@@ -1,6 +1,7 @@
 use fancy_regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Deserializer};
 
+use crate::config::Proxy;
 use crate::config::inclusivities::{
     Inclusivities, is_source_included, is_target_ref_included,
 };
@@ -15,9 +16,12 @@ pub struct Preview {
     pub enabled: Enabled,
     pub exclude: Exclude,
     pub max_per_message: usize,
+    pub fetch: Fetch,
     pub request: Request,
     pub card: Card,
     pub image: Image,
+    pub video: Video,
+    pub title: Title,
 }
 
 impl Default for Preview {
@@ -26,9 +30,12 @@ impl Default for Preview {
             enabled: Enabled::default(),
             exclude: Exclude::default(),
             max_per_message: 1,
+            fetch: Fetch::default(),
             request: Request::default(),
             card: Card::default(),
             image: Image::default(),
+            video: Video::default(),
+            title: Title::default(),
         }
     }
 }
@@ -40,6 +47,16 @@ impl Preview {
             return false;
         }
 
+        if !self.request.is_allowed(url) {
+            return false;
+        }
+
+        self.is_media_enabled(url) || self.title.is_enabled(url)
+    }
+
+    /// Whether cards and images may be fetched for the URL, rather than
+    /// only its title
+    pub fn is_media_enabled(&self, url: &str) -> bool {
         match &self.enabled {
             Enabled::Boolean(b) => *b,
             Enabled::Regex(regexes) => regexes
@@ -52,10 +69,21 @@ impl Preview {
         match preview {
             crate::Preview::Card(_) => self.card.hide_url,
             crate::Preview::Image(_) => self.image.hide_url,
+            crate::Preview::Title(_) => HideUrlCondition::Never,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Fetch {
+    #[default]
+    Automatic,
+    /// Previews are only fetched once asked for, so hosts linked to aren't
+    /// contacted just because a link scrolled into view
+    OnDemand,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Exclude(Vec<Regex>);
 
@@ -171,6 +199,42 @@ pub struct Request {
     pub delay_ms: u64,
     /// Preview image cache controls.
     pub image_cache: ImageCache,
+    /// Proxy used for fetching previews, instead of the proxy of the
+    /// server or the global proxy
+    pub proxy: Option<Proxy>,
+    /// Max number of previews requested at once for a single buffer
+    #[serde(deserialize_with = "deserialize_u64_positive_integer_limit")]
+    pub max_per_buffer: Option<u64>,
+    /// Hosts previews may be fetched from, including their subdomains
+    ///
+    /// When not empty, every request is checked against it, including
+    /// images referenced by a page and redirects
+    pub allowlist: Vec<String>,
+}
+
+impl Request {
+    pub fn is_allowed(&self, url: &str) -> bool {
+        self.allowlist.is_empty() || is_host_listed(url, &self.allowlist)
+    }
+}
+
+/// Whether the host of the URL is one of the hosts, or a subdomain of one.
+fn is_host_listed(url: &str, hosts: &[String]) -> bool {
+    let Some(host) = url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+    else {
+        return false;
+    };
+
+    hosts.iter().any(|listed| {
+        let listed = listed.trim().trim_start_matches('.');
+
+        host.eq_ignore_ascii_case(listed)
+            || host
+                .strip_suffix(&listed.to_ascii_lowercase())
+                .is_some_and(|subdomain| subdomain.ends_with('.'))
+    })
 }
 
 impl Default for Request {
@@ -183,6 +247,9 @@ impl Default for Request {
             concurrency: 4,
             delay_ms: 500,
             image_cache: ImageCache::default(),
+            proxy: None,
+            max_per_buffer: None,
+            allowlist: vec![],
         }
     }
 }
@@ -377,6 +444,77 @@ impl Image {
     }
 }
 
+/// Links to video files, which are shown as a link instead of being fetched.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Video {
+    pub enabled: bool,
+}
+
+impl Default for Video {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// A dimmed line with the title of the linked page, shown for links without
+/// a card or image preview.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Title {
+    pub enabled: bool,
+    pub exclude: Option<Inclusivities>,
+    pub include: Option<Inclusivities>,
+    /// Hosts titles may be fetched from, including their subdomains
+    pub allowlist: Vec<String>,
+    /// Hosts titles are never fetched from, including their subdomains
+    pub denylist: Vec<String>,
+}
+
+impl Title {
+    pub fn is_enabled(&self, url: &str) -> bool {
+        self.enabled && self.is_allowed(url)
+    }
+
+    pub fn is_allowed(&self, url: &str) -> bool {
+        !is_host_listed(url, &self.denylist)
+            && (self.allowlist.is_empty()
+                || is_host_listed(url, &self.allowlist))
+    }
+
+    pub fn visible(
+        &self,
+        target_ref: TargetRef,
+        server: &Server,
+        casemapping: isupport::CaseMap,
+    ) -> Visibility {
+        Visibility::for_target_ref(
+            self.include.as_ref(),
+            self.exclude.as_ref(),
+            target_ref,
+            server,
+            casemapping,
+        )
+    }
+
+    pub fn visible_for_source(
+        &self,
+        source: &Source,
+        channel: Option<&target::Channel>,
+        server: Option<&Server>,
+        casemapping: isupport::CaseMap,
+    ) -> bool {
+        is_source_included(
+            self.include.as_ref(),
+            self.exclude.as_ref(),
+            source,
+            channel,
+            server,
+            casemapping,
+        )
+    }
+}
+
 pub fn deserialize_trim_interval<'de, D>(
     deserializer: D,
 ) -> Result<u64, D::Error>
@@ -470,3 +608,42 @@ impl Visibility {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Request, Title};
+
+    #[test]
+    fn empty_allowlist_allows_everything() {
+        assert!(Request::default().is_allowed("https://example.com/page"));
+    }
+
+    #[test]
+    fn allowlist_matches_hosts_and_subdomains() {
+        let request = Request {
+            allowlist: vec!["example.com".to_string()],
+            ..Request::default()
+        };
+
+        assert!(request.is_allowed("https://example.com/page"));
+        assert!(request.is_allowed("https://cdn.EXAMPLE.com/image.png"));
+        assert!(!request.is_allowed("https://badexample.com/page"));
+        assert!(!request.is_allowed("https://example.com.evil.net/page"));
+        assert!(!request.is_allowed("not a url"));
+    }
+
+    #[test]
+    fn title_denylist_wins_over_allowlist() {
+        let title = Title {
+            enabled: true,
+            allowlist: vec!["example.com".to_string()],
+            denylist: vec!["private.example.com".to_string()],
+            ..Title::default()
+        };
+
+        assert!(title.is_enabled("https://www.example.com/page"));
+        assert!(!title.is_enabled("https://private.example.com/page"));
+        assert!(!title.is_enabled("https://other.net/page"));
+        assert!(!Title::default().is_enabled("https://example.com/page"));
+    }
+}
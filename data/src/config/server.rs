@@ -10,6 +10,7 @@ use tokio::process::Command;
 
 use self::filehost::Filehost;
 use self::icon::Icon;
+use crate::bouncer::{self, BouncerNetwork};
 use crate::capabilities::Capability;
 use crate::config::inclusivities::{
     Inclusivities, is_target_channel_included, is_target_query_included,
@@ -127,6 +128,7 @@ pub struct Server {
         deserialize_with = "deserialize_path_buf_with_path_transformations_maybe"
     )]
     pub root_cert_path: Option<PathBuf>,
+    pub tls: Tls,
     /// Sasl authentication
     pub sasl: Option<Sasl>,
     /// Commands which are executed once connected.
@@ -136,6 +138,12 @@ pub struct Server {
     /// WHO poll interval for servers without away-notify.
     #[serde(deserialize_with = "deserialize_who_poll_interval")]
     pub who_poll_interval: Duration,
+    /// Re-request the user list of channels whose nicklist looks out of
+    /// date. Defaults to `true`.
+    pub names_refresh_enabled: bool,
+    /// Minimum time between automatic user list refreshes of a channel.
+    #[serde(deserialize_with = "deserialize_who_poll_interval")]
+    pub names_refresh_interval: Duration,
     /// A list of nicknames to monitor (if MONITOR is supported by the server).
     pub monitor: Vec<String>,
     /// Whether to automatically make chathistory requests on the user's behalf
@@ -144,12 +152,21 @@ pub struct Server {
     /// Flood protection settings, to help the user avoid sending too many messages too quickly.
     #[serde(deserialize_with = "deserialize_anti_flood")]
     pub anti_flood: Duration,
+    /// Number of messages that may be sent in a burst before flood protection
+    /// starts spacing them out.
+    #[serde(deserialize_with = "deserialize_anti_flood_burst")]
+    pub anti_flood_burst: usize,
     #[serde(skip)]
     pub order: u16,
     pub proxy: Option<config::Proxy>,
     pub confirm_message_delivery: ConfirmMessageDelivery,
     /// Whether to automatically connect to the server on launch.
     pub autoconnect: bool,
+    /// Whether to wait until the server is first focused before connecting.
+    pub connect_on_demand: bool,
+    /// Whether to pause reconnection attempts while the network is
+    /// unavailable.
+    pub wait_for_network: bool,
     pub typing: OptionalTyping,
     pub filehost: Filehost,
     /// The key-value pairs for the user's metadata.
@@ -185,6 +202,13 @@ impl Server {
         }
     }
 
+    /// Whether to connect to the server on launch, i.e. `autoconnect` is
+    /// enabled and connecting has not been deferred until the server is
+    /// focused.
+    pub fn autoconnect_on_launch(&self) -> bool {
+        self.autoconnect && !self.connect_on_demand
+    }
+
     pub fn connection(
         &self,
         proxy: Option<config::Proxy>,
@@ -201,6 +225,12 @@ impl Server {
                     .sasl
                     .as_ref()
                     .and_then(Sasl::external_key),
+                options: connection::tls::Options {
+                    pinned_cert_sha256: self.tls.pinned_cert_sha256.as_deref(),
+                    server_name: self.tls.sni.as_deref(),
+                    min_version: self.tls.min_version.map(TlsVersion::into),
+                    alpn: &self.tls.alpn,
+                },
             }
         } else {
             connection::Security::Unsecured
@@ -214,7 +244,7 @@ impl Server {
             }
             .get(),
             security,
-            proxy: proxy.map(From::from),
+            proxy: proxy.and_then(|proxy| proxy.connection_proxy(&self.server)),
             websocket: self.use_websocket.then_some(connection::WebSocket {
                 path: &self.websocket_path,
                 ping_interval: self.websocket_ping_interval,
@@ -240,6 +270,44 @@ impl Server {
         }
     }
 
+    /// The configuration for one of a bouncer's networks.
+    pub fn bouncer_network_config(
+        &self,
+        server: &crate::server::Server,
+    ) -> Self {
+        match server.network.as_deref() {
+            Some(BouncerNetwork {
+                kind: bouncer::Kind::Znc,
+                name,
+                ..
+            }) => self.znc_network_config(name),
+            _ => self.bouncer_config(),
+        }
+    }
+
+    /// ZNC selects the network from a `user/network` username, replacing
+    /// any network already in the configured one.
+    fn znc_network_config(&self, network: &str) -> Self {
+        let with_network = |user: &str| {
+            let user = user.split_once('/').map_or(user, |(user, _)| user);
+            format!("{user}/{network}")
+        };
+
+        let mut config = self.bouncer_config();
+
+        config.username = Some(with_network(
+            self.username.as_deref().unwrap_or(&self.nickname),
+        ));
+
+        if let Some(Sasl::Plain { username, .. }) = &mut config.sasl {
+            *username = Some(with_network(
+                username.as_deref().unwrap_or(&self.nickname),
+            ));
+        }
+
+        config
+    }
+
     pub fn has_same_connection_settings(
         &self,
         default_proxy: Option<&config::Proxy>,
@@ -254,6 +322,7 @@ impl Server {
             || self.dangerously_accept_invalid_certs
                 != other.dangerously_accept_invalid_certs
             || self.root_cert_path != other.root_cert_path
+            || self.tls != other.tls
             || self.proxy.as_ref().or(default_proxy)
                 != other.proxy.as_ref().or(other_default_proxy)
             || self.username != other.username
@@ -304,17 +373,23 @@ impl Default for Server {
             websocket_ping_interval: Duration::from_secs(60),
             dangerously_accept_invalid_certs: Default::default(),
             root_cert_path: Option::default(),
+            tls: Tls::default(),
             sasl: Option::default(),
             on_connect: Vec::default(),
             who_poll_enabled: true,
             who_poll_interval: Duration::from_secs(2),
+            names_refresh_enabled: true,
+            names_refresh_interval: Duration::from_secs(300),
             monitor: Vec::default(),
             automated_chathistory: true,
             anti_flood: Duration::from_millis(2000),
+            anti_flood_burst: 10,
             order: 0,
             proxy: None,
             confirm_message_delivery: ConfirmMessageDelivery::default(),
             autoconnect: true,
+            connect_on_demand: false,
+            wait_for_network: true,
             typing: OptionalTyping::default(),
             filehost: Filehost::default(),
             metadata: HashMap::default(),
@@ -612,6 +687,24 @@ where
     }
 }
 
+fn deserialize_anti_flood_burst<'de, D>(
+    deserializer: D,
+) -> Result<usize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let burst: usize = Deserialize::deserialize(deserializer)?;
+
+    if !(1..=100).contains(&burst) {
+        Err(serde::de::Error::invalid_value(
+            serde::de::Unexpected::Unsigned(burst as u64),
+            &"integer in the range 1 .. 100",
+        ))
+    } else {
+        Ok(burst)
+    }
+}
+
 fn deserialize_who_poll_interval<'de, D>(
     deserializer: D,
 ) -> Result<Duration, D::Error>
@@ -685,6 +778,37 @@ pub async fn read_from_command(
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Tls {
+    /// SHA-256 fingerprint of the only certificate to trust, bypassing CA
+    /// validation (e.g. for a bouncer with a self-signed certificate).
+    pub pinned_cert_sha256: Option<String>,
+    /// Server name to send with SNI and validate the certificate against,
+    /// instead of `server`.  An empty name disables SNI.
+    pub sni: Option<String>,
+    pub min_version: Option<TlsVersion>,
+    /// Protocols to offer with ALPN.
+    pub alpn: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum TlsVersion {
+    #[serde(rename = "1.2")]
+    Tls12,
+    #[serde(rename = "1.3")]
+    Tls13,
+}
+
+impl From<TlsVersion> for connection::tls::Version {
+    fn from(version: TlsVersion) -> Self {
+        match version {
+            TlsVersion::Tls12 => connection::tls::Version::Tls12,
+            TlsVersion::Tls13 => connection::tls::Version::Tls13,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
 #[serde(default)]
 pub struct IrcProtocolLog {
@@ -701,3 +825,43 @@ pub enum IrcProtocolLogFormat {
     Goguma,
     // TODO: ZNC format
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::de::IntoDeserializer;
+    use serde::de::value::{Error, UsizeDeserializer};
+
+    use super::*;
+    use crate::rate_limit::{TokenBucket, TokenPriority};
+
+    fn burst(value: usize) -> Result<usize, Error> {
+        let deserializer: UsizeDeserializer<Error> = value.into_deserializer();
+
+        deserialize_anti_flood_burst(deserializer)
+    }
+
+    #[test]
+    fn anti_flood_burst_range() {
+        assert_eq!(burst(1), Ok(1));
+        assert_eq!(burst(100), Ok(100));
+        assert!(burst(0).is_err());
+        assert!(burst(101).is_err());
+    }
+
+    #[test]
+    fn anti_flood_burst_is_sent_at_once() {
+        let config = Server {
+            anti_flood_burst: 3,
+            ..Server::default()
+        };
+        let mut bucket =
+            TokenBucket::new(config.anti_flood, config.anti_flood_burst);
+
+        for token in 0..5 {
+            bucket.add_token(token, TokenPriority::User);
+        }
+
+        assert_eq!(bucket.acquire_tokens().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(bucket.queued(), 2);
+    }
+}
@@ -36,8 +36,12 @@ pub struct Sidebar {
     pub padding: Padding,
     pub spacing: Spacing,
     pub order_channels_by: OrderChannelsBy,
+    pub grouping: Grouping,
     pub channel_name_casing: Option<ChannelNameCasing>,
     pub internal_buffers: InternalBuffers,
+    pub show_nickname: bool,
+    /// Collapse the sidebar to a thin strip, expanding it on hover.
+    pub auto_hide: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -157,6 +161,8 @@ impl Default for UserMenu {
 pub struct UnreadIndicator {
     pub title: bool,
     pub icon: Icon,
+    /// Show the number of unread messages (and highlights).
+    pub count: bool,
     #[serde(deserialize_with = "deserialize_u32_positive_integer")]
     pub icon_size: u32,
     pub show_on_open_buffers: bool,
@@ -170,6 +176,7 @@ impl Default for UnreadIndicator {
         UnreadIndicator {
             title: false,
             icon: Icon::Dot,
+            count: false,
             icon_size: 6,
             show_on_open_buffers: true,
             query_as_highlight: false,
@@ -233,9 +240,15 @@ where
                 icon: Icon::Dot,
                 ..UnreadIndicator::default()
             }),
+            "count" => Ok(UnreadIndicator {
+                title: false,
+                icon: Icon::None,
+                count: true,
+                ..UnreadIndicator::default()
+            }),
             _ => Err(serde::de::Error::invalid_value(
                 serde::de::Unexpected::Str(string),
-                &"one of: \"dot\", \"title\", or \"none\"",
+                &"one of: \"dot\", \"title\", \"count\", or \"none\"",
             )),
         })
         .map(|map| map.deserialize())
@@ -381,6 +394,16 @@ pub enum OrderChannelsBy {
     Config,
 }
 
+#[derive(Debug, Copy, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Grouping {
+    #[default]
+    None,
+    /// Group channels sharing a common prefix, e.g. `#project` and
+    /// `#project-dev`.
+    Prefix,
+}
+
 #[derive(Debug, Copy, Clone, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum InternalBuffer {
@@ -389,4 +412,7 @@ pub enum InternalBuffer {
     ChannelDiscovery,
     Highlights,
     Logs,
+    Diagnostics,
+    Unread,
+    Ignores,
 }
@@ -0,0 +1,43 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StatusBar {
+    pub enabled: bool,
+    pub position: Position,
+    pub segments: Vec<Segment>,
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            position: Position::default(),
+            segments: vec![
+                Segment::Server,
+                Segment::Lag,
+                Segment::Away,
+                Segment::Queue,
+                Segment::FileTransfers,
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Position {
+    Top,
+    #[default]
+    Bottom,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Segment {
+    Server,
+    Lag,
+    Away,
+    Queue,
+    FileTransfers,
+}
@@ -5,8 +5,10 @@ use std::path::PathBuf;
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::buffer::{self, Buffer};
+use crate::config::buffer::CloseConfirmation;
 use crate::pane::Pane;
-use crate::{compression, environment};
+use crate::timed_ban::TimedBan;
+use crate::{Server, compression, environment, ignore};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
@@ -15,6 +17,22 @@ pub struct Dashboard {
     pub popout_panes: Vec<Pane>,
     pub buffer_settings: BufferSettings,
     pub focus_buffer: Option<Buffer>,
+    pub audio_muted: bool,
+    pub timed_bans: Vec<TimedBan>,
+    pub trusted_file_transfer_nicks: Vec<String>,
+    pub collapsed_servers: Vec<Server>,
+    pub pinned_buffers: Vec<buffer::Upstream>,
+    pub muted_buffers: Vec<buffer::Upstream>,
+    /// Channels we've left, kept in the sidebar read-only.
+    pub archived_buffers: Vec<buffer::Upstream>,
+    pub collapsed_channel_groups: Vec<(Server, String)>,
+    pub sidebar_width: Option<f32>,
+    /// Emojis most recently inserted with the emoji picker, newest first.
+    pub recent_emojis: Vec<String>,
+    /// Hostmasks ignored with `/ignore`.
+    pub ignores: Vec<ignore::Mask>,
+    /// `buffer.close.confirm` rules the user asked not to be prompted for.
+    pub dismissed_close_confirmations: Vec<CloseConfirmation>,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
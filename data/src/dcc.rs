@@ -18,6 +18,9 @@ pub fn decode(content: &str) -> Option<Command> {
 
     match args.next()?.to_lowercase().as_str() {
         "send" => Send::decode(args).map(Command::Send),
+        "ssend" => Send::decode(args).map(Command::SecureSend),
+        "resume" => Resume::decode(args).map(Command::Resume),
+        "accept" => Resume::decode(args).map(Command::Accept),
         cmd => Some(Command::Unsupported(cmd.to_string())),
     }
 }
@@ -25,6 +28,12 @@ pub fn decode(content: &str) -> Option<Command> {
 #[derive(Debug, Clone)]
 pub enum Command {
     Send(Send),
+    /// SDCC send, transferred over TLS
+    SecureSend(Send),
+    /// Receiver asks to continue a send from an existing partial file
+    Resume(Resume),
+    /// Sender agrees to continue from the requested position
+    Accept(Resume),
     Unsupported(String),
 }
 
@@ -113,7 +122,9 @@ impl Send {
         }
     }
 
-    pub fn encode(self, target: &dyn ToString) -> proto::Message {
+    pub fn encode(self, target: &dyn ToString, secure: bool) -> proto::Message {
+        let command = if secure { "SSEND" } else { "SEND" };
+
         match self {
             Self::Reverse {
                 filename,
@@ -129,7 +140,7 @@ impl Send {
                     &ctcp::Command::DCC,
                     target.to_string(),
                     Some(format!(
-                        "SEND {filename} {host} {port} {size} {token}"
+                        "{command} {filename} {host} {port} {size} {token}"
                     )),
                 )
             }
@@ -144,13 +155,73 @@ impl Send {
                 ctcp::query_message(
                     &ctcp::Command::DCC,
                     target.to_string(),
-                    Some(format!("SEND {filename} {host} {port} {size}")),
+                    Some(format!("{command} {filename} {host} {port} {size}")),
                 )
             }
         }
     }
 }
 
+/// Arguments of `DCC RESUME` and its reply, `DCC ACCEPT`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resume {
+    pub filename: String,
+    /// Port of the send being resumed, 0 for a passive send
+    pub port: u16,
+    pub position: u64,
+    /// Token of the passive send being resumed
+    pub token: Option<String>,
+}
+
+impl Resume {
+    fn decode<'a>(args: impl Iterator<Item = &'a str>) -> Option<Self> {
+        let mut args = args.collect::<Vec<_>>();
+
+        if args.len() < 3 {
+            return None;
+        }
+
+        // Passive sends are resumed with port 0 and their token appended
+        let token = (args.len() > 3 && args[args.len() - 3] == "0")
+            .then(|| args.pop().map(ToString::to_string))
+            .flatten();
+
+        let position = args.pop()?.parse().ok()?;
+        let port = args.pop()?.parse().ok()?;
+
+        Some(Self {
+            filename: sanitize_filename(&args.join(" ")),
+            port,
+            position,
+            token,
+        })
+    }
+
+    pub fn encode(self, target: &dyn ToString, accept: bool) -> proto::Message {
+        let command = if accept { "ACCEPT" } else { "RESUME" };
+
+        let Self {
+            filename,
+            port,
+            position,
+            token,
+        } = self;
+
+        let params = match token {
+            Some(token) => {
+                format!("{command} {filename} {port} {position} {token}")
+            }
+            None => format!("{command} {filename} {port} {position}"),
+        };
+
+        ctcp::query_message(
+            &ctcp::Command::DCC,
+            target.to_string(),
+            Some(params),
+        )
+    }
+}
+
 fn decode_host(host: &str) -> Option<IpAddr> {
     match host.parse::<u32>() {
         Ok(n) => Some(IpAddr::V4(Ipv4Addr::from(n))),
@@ -247,6 +318,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn secure_send_decode() {
+        let command = decode(
+            "\u{1}DCC SSEND my_file_name 1402301083 12350 1453953495\u{1}",
+        );
+
+        assert!(matches!(
+            command,
+            Some(Command::SecureSend(Send::Direct {
+                size: 1453953495,
+                ..
+            }))
+        ));
+    }
+
     #[test]
     fn send_decode_token() {
         let args = "\"my file name\" 1402301083 12345 1453953495 token";
@@ -285,6 +371,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resume_decode() {
+        let command = decode("\u{1}DCC RESUME my_file_name 12350 1048576\u{1}");
+
+        assert_eq!(
+            command.and_then(|command| match command {
+                Command::Resume(resume) => Some(resume),
+                _ => None,
+            }),
+            Some(Resume {
+                filename: "my_file_name".to_string(),
+                port: 12350,
+                position: 1048576,
+                token: None,
+            })
+        );
+
+        let command =
+            decode("\u{1}DCC ACCEPT \"my file name\" 0 1048576 42\u{1}");
+
+        assert_eq!(
+            command.and_then(|command| match command {
+                Command::Accept(resume) => Some(resume),
+                _ => None,
+            }),
+            Some(Resume {
+                filename: "my file name".to_string(),
+                port: 0,
+                position: 1048576,
+                token: Some("42".to_string()),
+            })
+        );
+
+        let args = "my_file_name 1048576";
+        assert_eq!(Resume::decode(args.split_whitespace()), None);
+    }
+
     #[test]
     fn send_decode_numeric_filename() {
         // Succeeds because only 4 args so we know to only
@@ -0,0 +1,373 @@
+//! Demo mode, enabled with `--demo`.
+//!
+//! Spins up a scripted IRC server on localhost which Halloy connects to in
+//! place of the configured servers, so themes, layouts, and notification
+//! settings can be tried out without connecting to a real network.
+
+use std::collections::HashSet;
+use std::net::{Ipv4Addr, TcpListener};
+use std::num::NonZeroU16;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, io, thread};
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::time;
+
+use crate::config;
+use crate::server::ServerName;
+
+/// Name of the demo server in the sidebar.
+pub const SERVER_NAME: &str = "Halloy Demo";
+
+const HOST: &str = "demo.halloy";
+const NICKNAME: &str = "halloy-user";
+const CHANNELS: &[&str] = &["#halloy", "#themes"];
+const USERS: &[&str] = &["@alice", "bob", "carol", "+dave", "erin"];
+const TOPIC: &str = "Welcome to the Halloy demo! Nothing here leaves your \
+                     computer.";
+const STEP_INTERVAL: Duration = Duration::from_secs(4);
+
+/// Scripted traffic, played in a loop once registered. `{nick}` is replaced
+/// by the client's nickname.
+const SCRIPT: &[&str] = &[
+    ":alice!alice@demo.halloy PRIVMSG #halloy :Good morning, everyone!",
+    ":bob!bob@demo.halloy PRIVMSG #halloy :morning alice o/",
+    ":carol!carol@demo.halloy PRIVMSG #themes :Has anyone tried the new \
+     theme editor yet?",
+    ":frank!frank@demo.halloy JOIN #halloy",
+    ":dave!dave@demo.halloy PRIVMSG #themes :Yes! Ferra is my favorite so far",
+    ":frank!frank@demo.halloy PRIVMSG #halloy :hey {nick}, are you around?",
+    ":erin!erin@demo.halloy PRIVMSG #halloy :\u{1}ACTION waves at frank\u{1}",
+    ":alice!alice@demo.halloy PRIVMSG #halloy :Check out \
+     https://halloy.chat for the docs",
+    ":bob!bob@demo.halloy PRIVMSG {nick} :psst, this is a query. Replies are \
+     welcome!",
+    ":carol!carol@demo.halloy NICK :carol_",
+    ":carol_!carol@demo.halloy PRIVMSG #themes :\u{2}Bold\u{2}, \
+     \u{1d}italic\u{1d}, and \u{3}04c\u{3}08o\u{3}09l\u{3}12o\u{3}13r\u{3}",
+    ":frank!frank@demo.halloy PART #halloy :See you later",
+    ":alice!alice@demo.halloy TOPIC #themes :Share your themes at \
+     https://themes.halloy.chat",
+    ":demo.halloy NOTICE {nick} :This is a server notice",
+    ":erin!erin@demo.halloy PRIVMSG #halloy :{nick}: did you get my last \
+     message?",
+    ":carol_!carol@demo.halloy NICK :carol",
+    ":dave!dave@demo.halloy QUIT :Connection reset by peer",
+    ":dave!dave@demo.halloy JOIN #themes",
+];
+
+static PORT: OnceLock<u16> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Starts the demo server in the background. Must be called before any
+/// config or data is loaded.
+pub fn start() -> io::Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    listener.set_nonblocking(true)?;
+
+    let port = listener.local_addr()?.port();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    thread::Builder::new()
+        .name("demo-server".to_string())
+        .spawn(move || {
+            if let Err(error) = runtime.block_on(serve(listener)) {
+                log::error!("[demo] server stopped: {error}");
+            }
+        })?;
+
+    let _ = DATA_DIR.set(
+        env::temp_dir().join(format!("halloy-demo-{}", std::process::id())),
+    );
+    let _ = PORT.set(port);
+
+    Ok(())
+}
+
+pub fn is_enabled() -> bool {
+    PORT.get().is_some()
+}
+
+/// Separate data directory for this run, so demo mode leaves the regular
+/// history and dashboard state untouched, and concurrent demos don't share
+/// state.
+pub fn data_dir() -> Option<PathBuf> {
+    DATA_DIR.get().cloned()
+}
+
+/// Removes the data directory of this run.
+pub fn cleanup() {
+    if let Some(dir) = DATA_DIR.get() {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+/// Server configuration used in place of the configured servers.
+pub fn servers() -> Option<Vec<(ServerName, config::Server)>> {
+    let port = *PORT.get()?;
+
+    Some(vec![(
+        ServerName::from(SERVER_NAME),
+        config::Server {
+            nickname: NICKNAME.to_string(),
+            server: Ipv4Addr::LOCALHOST.to_string(),
+            port: NonZeroU16::new(port),
+            use_tls: false,
+            channels: CHANNELS.iter().map(ToString::to_string).collect(),
+            ..config::Server::default()
+        },
+    )])
+}
+
+async fn serve(listener: TcpListener) -> io::Result<()> {
+    let listener = tokio::net::TcpListener::from_std(listener)?;
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+
+        tokio::spawn(async move {
+            if let Err(error) = Session::default().run(stream).await {
+                log::debug!("[demo] session ended: {error}");
+            }
+        });
+    }
+}
+
+#[derive(Default)]
+struct Session {
+    nick: Option<String>,
+    user: bool,
+    negotiating: bool,
+    registered: bool,
+    channels: HashSet<String>,
+    step: usize,
+}
+
+impl Session {
+    async fn run(mut self, stream: TcpStream) -> io::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut interval = time::interval(STEP_INTERVAL);
+
+        loop {
+            let replies = tokio::select! {
+                line = lines.next_line() => match line? {
+                    Some(line) => self.handle(&line),
+                    None => return Ok(()),
+                },
+                _ = interval.tick() => self.next_step(),
+            };
+
+            for reply in replies {
+                writer.write_all(reply.as_bytes()).await?;
+                writer.write_all(b"\r\n").await?;
+            }
+        }
+    }
+
+    fn nick(&self) -> &str {
+        self.nick.as_deref().unwrap_or("*")
+    }
+
+    fn handle(&mut self, line: &str) -> Vec<String> {
+        let (command, params) = parse(line);
+
+        match command.to_ascii_uppercase().as_str() {
+            "CAP" => match params.first().map(String::as_str) {
+                Some("LS") => {
+                    self.negotiating = true;
+                    vec![format!(":{HOST} CAP * LS :")]
+                }
+                Some("END") => {
+                    self.negotiating = false;
+                    self.register()
+                }
+                _ => vec![],
+            },
+            "NICK" => {
+                let Some(nick) = params.first() else {
+                    return vec![];
+                };
+
+                let reply = self.registered.then(|| {
+                    format!(":{}!{NICKNAME}@{HOST} NICK :{nick}", self.nick())
+                });
+                self.nick = Some(nick.clone());
+
+                reply.into_iter().chain(self.register()).collect()
+            }
+            "USER" => {
+                self.user = true;
+                self.register()
+            }
+            "PING" => {
+                let token = params.first().map_or("", String::as_str);
+                vec![format!(":{HOST} PONG {HOST} :{token}")]
+            }
+            "JOIN" => {
+                let Some(channels) = params.first() else {
+                    return vec![];
+                };
+
+                channels
+                    .split(',')
+                    .filter(|channel| channel.starts_with('#'))
+                    .flat_map(|channel| self.join(channel))
+                    .collect()
+            }
+            "PART" => {
+                let Some(channels) = params.first() else {
+                    return vec![];
+                };
+                let nick = self.nick().to_string();
+
+                channels
+                    .split(',')
+                    .filter(|channel| self.channels.remove(*channel))
+                    .map(|channel| {
+                        format!(":{nick}!{NICKNAME}@{HOST} PART {channel}")
+                    })
+                    .collect()
+            }
+            "PRIVMSG" => match params.first() {
+                // Demo users answer queries.
+                Some(target) if !target.starts_with('#') => vec![format!(
+                    ":{target}!{target}@{HOST} PRIVMSG {} :I'm only part of \
+                     the demo, but thanks for the message!",
+                    self.nick()
+                )],
+                _ => vec![],
+            },
+            "MODE" => match params.first() {
+                Some(channel) if channel.starts_with('#') => {
+                    vec![format!(":{HOST} 324 {} {channel} +nt", self.nick())]
+                }
+                _ => vec![],
+            },
+            "WHO" => params.first().map_or(vec![], |mask| {
+                vec![format!(
+                    ":{HOST} 315 {} {mask} :End of /WHO list.",
+                    self.nick()
+                )]
+            }),
+            _ => vec![],
+        }
+    }
+
+    fn register(&mut self) -> Vec<String> {
+        if self.registered || self.negotiating || !self.user {
+            return vec![];
+        }
+        let Some(nick) = self.nick.clone() else {
+            return vec![];
+        };
+
+        self.registered = true;
+
+        vec![
+            format!(":{HOST} 001 {nick} :Welcome to the Halloy demo, {nick}"),
+            format!(":{HOST} 002 {nick} :Your host is {HOST}"),
+            format!(":{HOST} 003 {nick} :This server was created just now"),
+            format!(":{HOST} 004 {nick} {HOST} demo o nt"),
+            format!(
+                ":{HOST} 005 {nick} CHANTYPES=# PREFIX=(ov)@+ \
+                 CASEMAPPING=rfc1459 NETWORK=HalloyDemo :are supported by \
+                 this server"
+            ),
+            format!(":{HOST} 375 {nick} :- {HOST} Message of the day -"),
+            format!(
+                ":{HOST} 372 {nick} :- Everything on this server is \
+                 scripted, so feel free to experiment."
+            ),
+            format!(":{HOST} 376 {nick} :End of /MOTD command."),
+        ]
+    }
+
+    fn join(&mut self, channel: &str) -> Vec<String> {
+        if !self.channels.insert(channel.to_string()) {
+            return vec![];
+        }
+
+        let nick = self.nick().to_string();
+        let names = USERS.join(" ");
+
+        vec![
+            format!(":{nick}!{NICKNAME}@{HOST} JOIN {channel}"),
+            format!(":{HOST} 332 {nick} {channel} :{TOPIC}"),
+            format!(":{HOST} 353 {nick} = {channel} :{names} {nick}"),
+            format!(":{HOST} 366 {nick} {channel} :End of /NAMES list."),
+        ]
+    }
+
+    fn next_step(&mut self) -> Vec<String> {
+        if !self.registered {
+            return vec![];
+        }
+
+        let line =
+            SCRIPT[self.step % SCRIPT.len()].replace("{nick}", self.nick());
+        self.step += 1;
+
+        // Skip channel traffic for channels that have been left.
+        let target = line.split(' ').nth(2).unwrap_or_default();
+        if target.starts_with('#') && !self.channels.contains(target) {
+            return vec![];
+        }
+
+        vec![line]
+    }
+}
+
+/// Splits a line sent by the client into its command and parameters.
+fn parse(line: &str) -> (&str, Vec<String>) {
+    let line = line.trim_end_matches(['\r', '\n']);
+    let (line, trailing) = match line.split_once(" :") {
+        Some((line, trailing)) => (line, Some(trailing)),
+        None => (line, None),
+    };
+
+    let mut parts = line.split(' ').filter(|part| !part.is_empty());
+    let command = parts.next().unwrap_or_default();
+
+    let params = parts
+        .map(ToString::to_string)
+        .chain(trailing.map(ToString::to_string))
+        .collect();
+
+    (command, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_after_cap_negotiation() {
+        let mut session = Session::default();
+
+        assert_eq!(session.handle("CAP LS 302").len(), 1);
+        assert!(session.handle("NICK tester").is_empty());
+        assert!(session.handle("USER tester 0 * :Tester").is_empty());
+
+        let welcome = session.handle("CAP END");
+        assert!(welcome[0].starts_with(":demo.halloy 001 tester "));
+
+        let join = session.handle("JOIN #halloy,#themes");
+        assert_eq!(join.len(), 8);
+        assert!(session.handle("JOIN #halloy").is_empty());
+    }
+
+    #[test]
+    fn parse_trailing() {
+        let (command, params) = parse("PRIVMSG #halloy :hello there\r\n");
+
+        assert_eq!(command, "PRIVMSG");
+        assert_eq!(params, vec!["#halloy", "hello there"]);
+    }
+}
@@ -0,0 +1,371 @@
+//! Counters for diagnosing performance issues, shown in the Diagnostics
+//! buffer and optionally served at a local metrics endpoint.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write;
+use std::net::Ipv4Addr;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use std::{io, thread};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::{Server, history};
+
+/// Window over which messages per second are averaged.
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+static STATE: LazyLock<Mutex<State>> = LazyLock::new(Mutex::default);
+
+#[derive(Debug, Default)]
+struct State {
+    connections: BTreeMap<Server, Connection>,
+    history_writes: HistoryWrites,
+    buffers: Vec<Buffer>,
+}
+
+#[derive(Debug, Default)]
+struct Connection {
+    received: u64,
+    recent: VecDeque<(Instant, usize)>,
+    connects: u64,
+}
+
+impl Connection {
+    fn prune(&mut self, now: Instant) {
+        while self
+            .recent
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > RATE_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+    }
+}
+
+fn state() -> MutexGuard<'static, State> {
+    STATE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub fn messages_received(server: &Server, count: usize) {
+    let now = Instant::now();
+    let mut state = state();
+    let connection = state.connections.entry(server.clone()).or_default();
+
+    connection.received += count as u64;
+    connection.recent.push_back((now, count));
+    connection.prune(now);
+}
+
+pub fn connected(server: &Server) {
+    state()
+        .connections
+        .entry(server.clone())
+        .or_default()
+        .connects += 1;
+}
+
+pub fn removed(server: &Server) {
+    state().connections.remove(server);
+}
+
+pub fn history_written(elapsed: Duration) {
+    let mut state = state();
+    let writes = &mut state.history_writes;
+
+    writes.count += 1;
+    writes.last = elapsed;
+    writes.max = writes.max.max(elapsed);
+    writes.total += elapsed;
+}
+
+pub fn buffers_measured(buffers: Vec<Buffer>) {
+    state().buffers = buffers;
+}
+
+pub fn snapshot() -> Snapshot {
+    let now = Instant::now();
+    let mut state = state();
+
+    let connections = state
+        .connections
+        .iter_mut()
+        .map(|(server, connection)| {
+            connection.prune(now);
+
+            let recent = connection
+                .recent
+                .iter()
+                .map(|(_, count)| *count)
+                .sum::<usize>();
+
+            ConnectionStats {
+                server: server.clone(),
+                messages_received: connection.received,
+                messages_per_second: recent as f64 / RATE_WINDOW.as_secs_f64(),
+                reconnects: connection.connects.saturating_sub(1),
+            }
+        })
+        .collect();
+
+    Snapshot {
+        connections,
+        history_writes: state.history_writes,
+        buffers: state.buffers.clone(),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub connections: Vec<ConnectionStats>,
+    pub history_writes: HistoryWrites,
+    pub buffers: Vec<Buffer>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConnectionStats {
+    pub server: Server,
+    pub messages_received: u64,
+    pub messages_per_second: f64,
+    pub reconnects: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HistoryWrites {
+    pub count: u64,
+    pub last: Duration,
+    pub max: Duration,
+    total: Duration,
+}
+
+impl HistoryWrites {
+    pub fn average(&self) -> Duration {
+        u32::try_from(self.count)
+            .ok()
+            .filter(|count| *count > 0)
+            .map_or(Duration::ZERO, |count| self.total / count)
+    }
+}
+
+/// Approximate memory held by a buffer's loaded history.
+#[derive(Debug, Clone)]
+pub struct Buffer {
+    pub kind: history::Kind,
+    pub messages: usize,
+    pub bytes: usize,
+}
+
+impl Snapshot {
+    /// The snapshot in the Prometheus text format.
+    pub fn to_metrics(&self) -> String {
+        let mut metrics = String::new();
+
+        for connection in &self.connections {
+            let server = escape(&connection.server.to_string());
+
+            let _ = writeln!(
+                metrics,
+                "halloy_messages_received_total{{server=\"{server}\"}} {}",
+                connection.messages_received
+            );
+            let _ = writeln!(
+                metrics,
+                "halloy_messages_per_second{{server=\"{server}\"}} {:.2}",
+                connection.messages_per_second
+            );
+            let _ = writeln!(
+                metrics,
+                "halloy_reconnects_total{{server=\"{server}\"}} {}",
+                connection.reconnects
+            );
+        }
+
+        let writes = &self.history_writes;
+        let _ =
+            writeln!(metrics, "halloy_history_writes_total {}", writes.count);
+        let _ = writeln!(
+            metrics,
+            "halloy_history_write_seconds_last {}",
+            writes.last.as_secs_f64()
+        );
+        let _ = writeln!(
+            metrics,
+            "halloy_history_write_seconds_max {}",
+            writes.max.as_secs_f64()
+        );
+        let _ = writeln!(
+            metrics,
+            "halloy_history_write_seconds_average {}",
+            writes.average().as_secs_f64()
+        );
+
+        for buffer in &self.buffers {
+            let _ = writeln!(
+                metrics,
+                "halloy_buffer_bytes{{buffer=\"{}\"}} {}",
+                escape(&buffer.kind.to_string()),
+                buffer.bytes
+            );
+        }
+
+        metrics
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Serves the counters in the Prometheus text format at
+/// `http://127.0.0.1:{port}/metrics`.
+pub fn serve(port: u16) -> io::Result<()> {
+    let listener = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    listener.set_nonblocking(true)?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    thread::Builder::new()
+        .name("metrics".to_string())
+        .spawn(move || {
+            if let Err(error) = runtime.block_on(accept(listener)) {
+                log::error!("[metrics] endpoint stopped: {error}");
+            }
+        })?;
+
+    log::info!("[metrics] serving at http://127.0.0.1:{port}/metrics");
+
+    Ok(())
+}
+
+async fn accept(listener: std::net::TcpListener) -> io::Result<()> {
+    let port = listener.local_addr()?.port();
+    let listener = tokio::net::TcpListener::from_std(listener)?;
+
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+
+        tokio::spawn(async move {
+            let mut request = [0; 1024];
+            let Ok(read) = stream.read(&mut request).await else {
+                return;
+            };
+
+            let request = String::from_utf8_lossy(&request[..read]);
+
+            let response = match route(&request, port) {
+                Route::Metrics => {
+                    let body = snapshot().to_metrics();
+
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; \
+                         version=0.0.4\r\nContent-Length: {}\r\n\
+                         Connection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                }
+                Route::Forbidden => "HTTP/1.1 403 Forbidden\r\n\
+                                     Content-Length: 0\r\nConnection: \
+                                     close\r\n\r\n"
+                    .to_string(),
+                Route::NotFound => "HTTP/1.1 404 Not Found\r\n\
+                                    Content-Length: 0\r\nConnection: \
+                                    close\r\n\r\n"
+                    .to_string(),
+            };
+
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Route {
+    Metrics,
+    Forbidden,
+    NotFound,
+}
+
+fn route(request: &str, port: u16) -> Route {
+    // Requests from web pages through DNS rebinding name another host
+    let is_local = request
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("host"))
+        .is_some_and(|(_, host)| {
+            let host = host.trim();
+
+            host == format!("127.0.0.1:{port}")
+                || host.eq_ignore_ascii_case(&format!("localhost:{port}"))
+        });
+
+    if !is_local {
+        Route::Forbidden
+    } else if request.starts_with("GET /metrics ") {
+        Route::Metrics
+    } else {
+        Route::NotFound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_write_average() {
+        let writes = HistoryWrites {
+            count: 4,
+            last: Duration::from_millis(1),
+            max: Duration::from_millis(10),
+            total: Duration::from_millis(20),
+        };
+
+        assert_eq!(writes.average(), Duration::from_millis(5));
+        assert_eq!(HistoryWrites::default().average(), Duration::ZERO);
+    }
+
+    #[test]
+    fn routes_local_requests() {
+        let request = |path: &str, host: &str| {
+            format!(
+                "GET {path} HTTP/1.1\r\nUser-Agent: test\r\n\
+                 Host: {host}\r\nAccept: */*\r\n\r\n"
+            )
+        };
+
+        assert_eq!(
+            route(&request("/metrics", "127.0.0.1:9091"), 9091),
+            Route::Metrics
+        );
+        assert_eq!(
+            route(&request("/metrics", "LocalHost:9091"), 9091),
+            Route::Metrics
+        );
+        assert_eq!(
+            route(&request("/", "localhost:9091"), 9091),
+            Route::NotFound
+        );
+    }
+
+    #[test]
+    fn rejects_other_hosts() {
+        for host in ["attacker.example:9091", "127.0.0.1:9092", "localhost"] {
+            assert_eq!(
+                route(
+                    &format!("GET /metrics HTTP/1.1\r\nHost: {host}\r\n\r\n"),
+                    9091
+                ),
+                Route::Forbidden
+            );
+        }
+
+        assert_eq!(
+            route("GET /metrics HTTP/1.1\r\n\r\n", 9091),
+            Route::Forbidden
+        );
+    }
+}
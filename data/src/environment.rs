@@ -1,6 +1,8 @@
 use std::env;
 use std::path::PathBuf;
 
+use crate::demo;
+
 pub const VERSION: &str = env!("VERSION");
 pub const GIT_HASH: Option<&str> = option_env!("GIT_HASH");
 pub const CONFIG_FILE_NAME: &str = "config.toml";
@@ -25,6 +27,10 @@ pub fn config_dir() -> PathBuf {
 }
 
 pub fn data_dir() -> PathBuf {
+    if let Some(dir) = demo::data_dir() {
+        return dir;
+    }
+
     portable_dir().unwrap_or_else(|| {
         dirs_next::data_dir()
             .expect("expected valid data dir")
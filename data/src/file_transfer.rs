@@ -5,12 +5,22 @@ use chrono::{DateTime, Utc};
 
 pub use self::manager::Manager;
 pub use self::task::Task;
+use crate::config::file_transfer::OnCollision;
 use crate::{Server, User, dcc, server};
 
 pub mod manager;
 pub mod task;
 
 const FALLBACK_FILENAME: &str = "dcc_transfer";
+// Names Windows reserves for devices, with or without an extension.
+const RESERVED_FILENAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6",
+    "COM7", "COM8", "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6",
+    "LPT7", "LPT8", "LPT9",
+];
+// Keep well below the 255 byte limit of most filesystems, leaving room for a
+// collision suffix.
+const MAX_FILENAME_LEN: usize = 200;
 
 pub fn sanitize_filename(raw: &str) -> String {
     let trimmed = raw.trim().trim_matches('"');
@@ -20,21 +30,136 @@ pub fn sanitize_filename(raw: &str) -> String {
         .and_then(|n| n.to_str())
         .unwrap_or(FALLBACK_FILENAME);
 
-    replace_control_chars(name)
+    let name = replace_unsafe_chars(name);
+    // Leading dots would hide the file, trailing dots and spaces are dropped
+    // by Windows.
+    let name = name
+        .trim_start_matches('.')
+        .trim_end_matches(['.', ' '])
+        .to_string();
+
+    if name.is_empty() {
+        return FALLBACK_FILENAME.to_string();
+    }
+
+    let stem = name.split('.').next().unwrap_or_default();
+    let name = if RESERVED_FILENAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem.trim_end()))
+    {
+        format!("_{name}")
+    } else {
+        name
+    };
+
+    truncate_filename(name)
 }
 
-// Replace control characters to avoid problematic filenames.
-fn replace_control_chars(input: &str) -> String {
+// Replace control characters and characters which are invalid in filenames on
+// some platforms.
+fn replace_unsafe_chars(input: &str) -> String {
     input
         .chars()
-        .map(|c| if c.is_control() { '_' } else { c })
+        .map(|c| {
+            if c.is_control()
+                || matches!(
+                    c,
+                    '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'
+                )
+            {
+                '_'
+            } else {
+                c
+            }
+        })
         .collect()
 }
 
+// Truncate to MAX_FILENAME_LEN bytes, preserving the extension.
+fn truncate_filename(name: String) -> String {
+    if name.len() <= MAX_FILENAME_LEN {
+        return name;
+    }
+
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension))
+            if !stem.is_empty() && extension.len() < MAX_FILENAME_LEN / 2 =>
+        {
+            (stem, Some(extension))
+        }
+        _ => (name.as_str(), None),
+    };
+
+    let max_stem_len =
+        MAX_FILENAME_LEN - extension.map_or(0, |extension| extension.len() + 1);
+    let mut end = max_stem_len.min(stem.len());
+    while !stem.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    match extension {
+        Some(extension) => format!("{}.{extension}", &stem[..end]),
+        None => stem[..end].to_string(),
+    }
+}
+
 pub fn receive_save_path(save_directory: &Path, filename: &str) -> PathBuf {
     save_directory.join(sanitize_filename(filename))
 }
 
+/// An existing file in the save directory which is smaller than the offered
+/// file, and so can be resumed.
+pub fn partial_download(
+    save_directory: &Path,
+    filename: &str,
+    size: u64,
+) -> Option<(PathBuf, u64)> {
+    let path = receive_save_path(save_directory, filename);
+    let len = path
+        .metadata()
+        .ok()
+        .filter(|metadata| metadata.is_file())?
+        .len();
+
+    (len > 0 && len < size).then_some((path, len))
+}
+
+/// Resolves a save path which may already exist according to `on_collision`.
+/// Returns `None` if the user should be prompted for a save path instead.
+pub fn resolve_collision(
+    save_path: PathBuf,
+    on_collision: OnCollision,
+) -> Option<PathBuf> {
+    if !save_path.exists() {
+        return Some(save_path);
+    }
+
+    match on_collision {
+        OnCollision::Overwrite => Some(save_path),
+        OnCollision::Prompt => None,
+        OnCollision::Rename => (1..)
+            .map(|n| with_suffix(&save_path, n))
+            .find(|path| !path.exists()),
+    }
+}
+
+// "name.ext" -> "name (n).ext"
+fn with_suffix(path: &Path, n: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+
+    let filename = match path.extension() {
+        Some(extension) => {
+            format!("{stem} ({n}).{}", extension.to_string_lossy())
+        }
+        None => format!("{stem} ({n})"),
+    };
+
+    path.with_file_name(filename)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Id(u16);
 
@@ -60,6 +185,13 @@ pub struct FileTransfer {
     pub filename: String,
     pub size: u64,
     pub status: Status,
+    /// Transferred over TLS (SDCC)
+    pub secure: bool,
+    /// The local file, which is sent or (once approved) received into
+    pub path: Option<PathBuf>,
+    /// Size of an interrupted download of this file in the save directory,
+    /// which can be resumed rather than received from the start
+    pub partial: Option<(PathBuf, u64)>,
 }
 
 impl FileTransfer {
@@ -109,6 +241,8 @@ pub enum Status {
     PendingReverseConfirmation,
     /// Queued (needs an open port to begin)
     Queued,
+    /// Accepted, waiting for other transfers to finish
+    Waiting,
     /// Ready (waiting for remote user to connect)
     Ready,
     /// Transfer is actively sending / receiving
@@ -123,10 +257,22 @@ pub enum Status {
 pub struct ReceiveRequest {
     pub from: User,
     pub dcc_send: dcc::Send,
+    pub secure: bool,
     pub server: Server,
     pub server_handle: server::Handle,
 }
 
+/// A `DCC RESUME` for a file we are sending, or the `DCC ACCEPT` reply to
+/// one we sent.
+#[derive(Debug, Clone)]
+pub struct ResumeRequest {
+    pub from: User,
+    pub resume: dcc::Resume,
+    /// An `ACCEPT` rather than a `RESUME`
+    pub accepted: bool,
+    pub server: Server,
+}
+
 #[derive(Debug)]
 pub struct SendRequest {
     pub to: User,
@@ -135,11 +281,32 @@ pub struct SendRequest {
     pub server_handle: server::Handle,
 }
 
+/// Several files sent one after another to the same user.
+#[derive(Debug)]
+pub struct SendBatchRequest {
+    pub to: User,
+    pub paths: Vec<PathBuf>,
+    pub server: Server,
+    pub server_handle: server::Handle,
+}
+
+/// Aggregate progress of a [`SendBatchRequest`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchProgress {
+    pub to: User,
+    pub total: usize,
+    /// Files which completed or failed.
+    pub finished: usize,
+    pub failed: usize,
+    /// Fraction of all files sent, including the current transfer.
+    pub progress: f64,
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
 
-    use super::{receive_save_path, sanitize_filename};
+    use super::{receive_save_path, sanitize_filename, with_suffix};
 
     #[test]
     fn sanitize_filename_strips_traversal_components() {
@@ -161,6 +328,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sanitize_filename_replaces_unsafe_names() {
+        assert_eq!(sanitize_filename("what?<now>.txt"), "what__now_.txt");
+        assert_eq!(sanitize_filename(".hidden"), "hidden");
+        assert_eq!(sanitize_filename("trailing. . "), "trailing");
+        assert_eq!(sanitize_filename("con.txt"), "_con.txt");
+        assert_eq!(sanitize_filename("console.txt"), "console.txt");
+
+        let long = format!("{}.tar.gz", "a".repeat(300));
+        let sanitized = sanitize_filename(&long);
+        assert_eq!(sanitized.len(), 200);
+        assert!(sanitized.ends_with("a.gz"));
+    }
+
+    #[test]
+    fn with_suffix_keeps_extension() {
+        assert_eq!(
+            with_suffix(Path::new("/downloads/photo.jpg"), 2),
+            Path::new("/downloads/photo (2).jpg")
+        );
+        assert_eq!(
+            with_suffix(Path::new("/downloads/README"), 1),
+            Path::new("/downloads/README (1)")
+        );
+    }
+
     #[test]
     fn receive_save_path_stays_in_configured_directory() {
         let save_path = receive_save_path(
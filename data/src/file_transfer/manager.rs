@@ -1,24 +1,30 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::num::NonZeroU16;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use chrono::Utc;
 use futures::StreamExt;
 use futures::stream::BoxStream;
+use irc::proto::command;
 use itertools::Itertools;
 use rand::RngExt;
 
 use super::{
-    Direction, FileTransfer, Id, ReceiveRequest, SendRequest, Status, Task,
-    receive_save_path, sanitize_filename, task,
+    BatchProgress, Direction, FileTransfer, Id, ReceiveRequest, ResumeRequest,
+    SendBatchRequest, SendRequest, Status, Task, partial_download,
+    receive_save_path, resolve_collision, sanitize_filename, task,
 };
-use crate::{Config, dcc};
+use crate::config::file_transfer::OnCollision;
+use crate::user::NickRef;
+use crate::{Config, Server, User, dcc, server};
 
 enum Item {
     Working {
         file_transfer: FileTransfer,
         task: task::Handle,
+        /// Approved, but the task has yet to start receiving
+        approved: bool,
     },
     Finished(FileTransfer),
 }
@@ -43,12 +49,45 @@ pub enum Event {
     NewTransfer(FileTransfer, BoxStream<'static, task::Update>),
 }
 
+/// How an accepted transfer waiting for a free slot is started.
+enum Approval {
+    Save(PathBuf),
+    Resume(PathBuf, u64),
+}
+
+/// Files sent to a user one after another.
+struct Batch {
+    to: User,
+    server: Server,
+    server_handle: server::Handle,
+    /// Transfers started so far, the last one being the current transfer.
+    transfers: Vec<Id>,
+    /// Files which could not be sent at all
+    skipped: usize,
+    pending: VecDeque<PathBuf>,
+}
+
+impl Batch {
+    fn current(&self) -> Option<Id> {
+        self.transfers.last().copied()
+    }
+}
+
 #[derive(Default)]
 pub struct Manager {
     items: HashMap<Id, Item>,
     /// Queued = waiting for port assignment
     queued: VecDeque<Id>,
     used_ports: HashMap<Id, NonZeroU16>,
+    batches: Vec<Batch>,
+    /// Normalized nicknames whose transfers are always accepted
+    trusted: HashSet<String>,
+    /// Received transfers waiting for the sender to accept resuming from
+    /// a position
+    resuming: HashMap<Id, u64>,
+    /// Accepted transfers waiting for a slot, see
+    /// `file_transfer.max_concurrent`
+    waiting: VecDeque<(Id, Approval)>,
 }
 
 impl Manager {
@@ -72,6 +111,8 @@ impl Manager {
             .map(|server| task::Server {
                 public_address: server.public_address,
                 bind_address: server.bind_address,
+                tls_cert_path: server.tls_cert_path.clone(),
+                tls_key_path: server.tls_key_path.clone(),
             })
     }
 
@@ -87,7 +128,10 @@ impl Manager {
             server_handle,
         } = request;
 
-        let reverse = config.file_transfer.passive;
+        // Without a server to listen on, the receiver has to listen instead
+        let reverse = config.file_transfer.passive
+            || config.file_transfer.server.is_none();
+        let secure = config.file_transfer.secure;
 
         let filename = sanitize_filename(
             path.file_name()
@@ -119,9 +163,13 @@ impl Manager {
                 // Task will trigger queued update
                 Status::Queued
             },
+            secure,
+            path: Some(path.clone()),
+            partial: None,
         };
 
-        let task = Task::send(id, path, filename, to, reverse, server_handle);
+        let task =
+            Task::send(id, path, filename, to, reverse, secure, server_handle);
         let (handle, stream) = task.spawn(
             self.server(config),
             Duration::from_secs(config.file_transfer.timeout),
@@ -133,12 +181,147 @@ impl Manager {
             Item::Working {
                 file_transfer: file_transfer.clone(),
                 task: handle,
+                approved: true,
             },
         );
 
         Some(Event::NewTransfer(file_transfer, stream.boxed()))
     }
 
+    /// Sends the first file right away, and each following file once the
+    /// previous transfer has completed or failed.
+    pub fn send_batch(
+        &mut self,
+        request: SendBatchRequest,
+        config: &Config,
+    ) -> Option<Event> {
+        let SendBatchRequest {
+            to,
+            paths,
+            server,
+            server_handle,
+        } = request;
+
+        let mut batch = Batch {
+            to,
+            server,
+            server_handle,
+            transfers: vec![],
+            skipped: 0,
+            pending: paths.into(),
+        };
+
+        let event = self.send_next(&mut batch, config)?;
+
+        // A single file doesn't need aggregate progress
+        if batch.transfers.len() + batch.skipped + batch.pending.len() > 1 {
+            self.batches.push(batch);
+        }
+
+        Some(event)
+    }
+
+    /// Starts the next pending file of `batch`.  Files which can't be sent
+    /// are counted as failed, and the following file is tried instead.
+    fn send_next(
+        &mut self,
+        batch: &mut Batch,
+        config: &Config,
+    ) -> Option<Event> {
+        while let Some(path) = batch.pending.pop_front() {
+            let Some(event) = self.send(
+                SendRequest {
+                    to: batch.to.clone(),
+                    path: path.clone(),
+                    server: batch.server.clone(),
+                    server_handle: batch.server_handle.clone(),
+                },
+                config,
+            ) else {
+                log::error!(
+                    "File transfer to {} for {path:?} could not be started",
+                    batch.to.nickname()
+                );
+                batch.skipped += 1;
+                continue;
+            };
+
+            let Event::NewTransfer(file_transfer, _) = &event;
+            batch.transfers.push(file_transfer.id);
+
+            return Some(event);
+        }
+
+        None
+    }
+
+    /// Starts the next file of the batch whose current transfer is `id`.
+    fn advance_batch(&mut self, id: Id, config: &Config) -> Option<Event> {
+        let index = self
+            .batches
+            .iter()
+            .position(|batch| batch.current() == Some(id))?;
+
+        let mut batch = self.batches.remove(index);
+
+        let event = self.send_next(&mut batch, config);
+
+        // Keep the batch until its last transfer has finished
+        if event.is_some() {
+            self.batches.insert(index, batch);
+        }
+
+        event
+    }
+
+    pub fn batches(&self) -> impl Iterator<Item = BatchProgress> + '_ {
+        self.batches.iter().map(|batch| {
+            let total =
+                batch.transfers.len() + batch.skipped + batch.pending.len();
+
+            let transfers = batch
+                .transfers
+                .iter()
+                .filter_map(|id| self.get(id))
+                .collect::<Vec<_>>();
+
+            let finished = transfers
+                .iter()
+                .filter(|transfer| {
+                    matches!(
+                        transfer.status,
+                        Status::Completed { .. } | Status::Failed { .. }
+                    )
+                })
+                .count();
+            let failed = transfers
+                .iter()
+                .filter(|transfer| {
+                    matches!(transfer.status, Status::Failed { .. })
+                })
+                .count();
+
+            // Skipped, failed and cleared transfers count as done
+            let done = batch.skipped + batch.transfers.len() - transfers.len();
+            let progress = done as f64
+                + transfers
+                    .iter()
+                    .map(|transfer| match transfer.status {
+                        Status::Failed { .. } => 1.0,
+                        _ => transfer.progress(),
+                    })
+                    .sum::<f64>();
+
+            BatchProgress {
+                to: batch.to.clone(),
+                total,
+                finished: finished + done,
+                failed: failed + batch.skipped,
+                progress: progress / total as f64,
+            }
+        })
+    }
+
     pub fn receive(
         &mut self,
         request: ReceiveRequest,
@@ -147,6 +330,7 @@ impl Manager {
         let ReceiveRequest {
             from,
             dcc_send,
+            secure,
             server,
             server_handle,
         } = request;
@@ -162,6 +346,7 @@ impl Manager {
             && let Some(Item::Working {
                 file_transfer,
                 task,
+                ..
             }) = self.items.get_mut(&id)
             && file_transfer.filename == *filename
         {
@@ -180,10 +365,32 @@ impl Manager {
             dcc_send.filename()
         );
 
+        let auto_reject = &config.file_transfer.auto_reject;
+
+        if auto_reject.rejects(&from, dcc_send.filename(), dcc_send.size()) {
+            log::info!(
+                "Auto-rejecting file transfer from {} for {:?}",
+                from.nickname(),
+                dcc_send.filename()
+            );
+
+            if let Some(notice) = &auto_reject.notice {
+                let mut server_handle = server_handle;
+
+                let _ = server_handle.try_send(command!(
+                    "NOTICE",
+                    from.nickname().to_string(),
+                    notice.clone()
+                ));
+            }
+
+            return None;
+        }
+
         let id = self.get_random_id();
 
         // Otherwise this must be a new request
-        let file_transfer = FileTransfer {
+        let mut file_transfer = FileTransfer {
             id,
             server,
             created_at: Utc::now(),
@@ -192,17 +399,35 @@ impl Manager {
             filename: sanitize_filename(dcc_send.filename()),
             size: dcc_send.size(),
             status: Status::PendingApproval,
+            secure,
+            path: None,
+            partial: config.file_transfer.save_directory.as_ref().and_then(
+                |save_directory| {
+                    partial_download(
+                        save_directory,
+                        dcc_send.filename(),
+                        dcc_send.size(),
+                    )
+                },
+            ),
         };
 
-        let task = Task::receive(id, dcc_send, from.clone(), server_handle);
-        let (mut handle, stream) = task.spawn(
+        let task =
+            Task::receive(id, dcc_send, secure, from.clone(), server_handle);
+        let (handle, stream) = task.spawn(
             self.server(config),
             Duration::from_secs(config.file_transfer.timeout),
             config.proxy.as_ref().cloned(),
         );
 
-        // Auto-accept if enabled and save directory is set
-        if config.file_transfer.auto_accept.enabled {
+        let mut approval = None;
+
+        let trusted =
+            self.trusted.contains(from.nickname().as_normalized_str());
+
+        // Auto-accept if enabled (or the sender is trusted) and save directory
+        // is set
+        if config.file_transfer.auto_accept.enabled || trusted {
             // Check if sender matches nickname or mask filters
             let should_auto_accept = {
                 let nickname_match =
@@ -217,25 +442,34 @@ impl Manager {
                     .as_ref()
                     .is_none_or(|masks| from.matches_masks(masks));
 
-                nickname_match && mask_match
+                trusted || (nickname_match && mask_match)
             };
 
             if should_auto_accept {
                 if let Some(save_directory) =
                     &config.file_transfer.save_directory
                 {
-                    let save_path = receive_save_path(
-                        save_directory,
-                        &file_transfer.filename,
-                    );
+                    if let Some(save_path) = resolve_collision(
+                        receive_save_path(
+                            save_directory,
+                            &file_transfer.filename,
+                        ),
+                        config.file_transfer.on_collision,
+                    ) {
+                        log::debug!(
+                            "Auto-accepting file transfer from {} for {:?}",
+                            from.nickname(),
+                            file_transfer.filename
+                        );
 
-                    log::debug!(
-                        "Auto-accepting file transfer from {} for {:?}",
-                        from.nickname(),
-                        file_transfer.filename
-                    );
-
-                    handle.approve(save_path);
+                        file_transfer.path = Some(save_path.clone());
+                        approval = Some(Approval::Save(save_path));
+                    } else {
+                        log::debug!(
+                            "{:?} already exists. File transfer will require manual approval.",
+                            file_transfer.filename
+                        );
+                    }
                 } else {
                     log::warn!(
                         "Auto-accept is enabled but save_directory is not set. File transfer will require manual approval."
@@ -249,13 +483,26 @@ impl Manager {
             Item::Working {
                 file_transfer: file_transfer.clone(),
                 task: handle,
+                approved: false,
             },
         );
 
+        if let Some(approval) = approval {
+            self.start_or_wait(id, approval, config);
+        }
+
+        let file_transfer = self.get(&id).cloned().unwrap_or(file_transfer);
+
         Some(Event::NewTransfer(file_transfer, stream.boxed()))
     }
 
-    pub fn update(&mut self, update: task::Update, config: &Config) {
+    /// Returns the next transfer of a batch, once the previous one has
+    /// finished.
+    pub fn update(
+        &mut self,
+        update: task::Update,
+        config: &Config,
+    ) -> Option<Event> {
         match update {
             task::Update::Metadata(id, size) => {
                 if let Some(item) = self.items.get_mut(&id) {
@@ -268,6 +515,7 @@ impl Manager {
                 if let Some(Item::Working {
                     file_transfer,
                     task,
+                    ..
                 }) = self.items.get_mut(&id)
                 {
                     if let Some(port) = available_port {
@@ -337,6 +585,9 @@ impl Manager {
                     );
 
                     self.recycle_port(id);
+                    self.start_waiting(config);
+
+                    return self.advance_batch(id, config);
                 }
             }
             task::Update::Failed(id, error) => {
@@ -353,10 +604,19 @@ impl Manager {
                     );
                     file_transfer.status = Status::Failed { error };
 
+                    if let Item::Working { approved, .. } = item {
+                        *approved = false;
+                    }
+
                     self.recycle_port(id);
+                    self.start_waiting(config);
+
+                    return self.advance_batch(id, config);
                 }
             }
         }
+
+        None
     }
 
     fn get_available_port(&self, config: &Config) -> Option<NonZeroU16> {
@@ -376,6 +636,7 @@ impl Manager {
             && let Some(Item::Working {
                 task,
                 file_transfer,
+                ..
             }) = self
                 .queued
                 .pop_front()
@@ -386,18 +647,293 @@ impl Manager {
         }
     }
 
-    pub fn remove(&mut self, id: &Id) {
+    pub fn remove(&mut self, id: &Id, config: &Config) {
         let _ = self.items.remove(id);
         self.queued.retain(|i| i != id);
+        self.resuming.remove(id);
+        self.waiting.retain(|(i, _)| i != id);
         self.recycle_port(*id);
+        self.start_waiting(config);
+
+        // Cancelling the current transfer of a batch cancels the rest of it
+        self.batches.retain(|batch| batch.current() != Some(*id));
+    }
+
+    pub fn approve(&mut self, id: &Id, save_to: PathBuf, config: &Config) {
+        if let Some(Item::Working { file_transfer, .. }) =
+            self.items.get_mut(id)
+        {
+            file_transfer.path = Some(save_to.clone());
+            self.start_or_wait(*id, Approval::Save(save_to), config);
+        }
+    }
+
+    /// Approves the transfers among `ids` which are pending approval, saving
+    /// them into `folder`. Returns the approved transfers.
+    pub fn approve_into(
+        &mut self,
+        ids: &[Id],
+        folder: &Path,
+        config: &Config,
+    ) -> Vec<Id> {
+        // Several files can't be prompted for, so they're renamed instead
+        let on_collision = match config.file_transfer.on_collision {
+            OnCollision::Prompt => OnCollision::Rename,
+            on_collision => on_collision,
+        };
+
+        let mut approved = vec![];
+
+        for id in ids {
+            if !self.is_pending_approval(id) {
+                continue;
+            }
+
+            if let Some(save_path) = self.get(id).and_then(|transfer| {
+                resolve_collision(
+                    receive_save_path(folder, &transfer.filename),
+                    on_collision,
+                )
+            }) {
+                self.approve(id, save_path, config);
+                approved.push(*id);
+            }
+        }
+
+        approved
+    }
+
+    /// Approve, continuing the interrupted download of the transfer's file.
+    pub fn resume(&mut self, id: &Id, config: &Config) {
+        if let Some(Item::Working { file_transfer, .. }) =
+            self.items.get_mut(id)
+            && let Some((path, position)) = file_transfer.partial.clone()
+        {
+            log::debug!(
+                "Resuming file transfer from {} for {:?} at {position} bytes",
+                file_transfer.remote_user.nickname(),
+                file_transfer.filename
+            );
+
+            file_transfer.path = Some(path.clone());
+            self.start_or_wait(*id, Approval::Resume(path, position), config);
+        }
+    }
+
+    /// Received transfers which have been approved and not yet finished.
+    fn running(&self) -> usize {
+        self.items
+            .values()
+            .filter(|item| {
+                matches!(
+                    item,
+                    Item::Working {
+                        file_transfer: FileTransfer {
+                            direction: Direction::Received,
+                            ..
+                        },
+                        approved: true,
+                        ..
+                    }
+                )
+            })
+            .count()
+    }
+
+    fn has_free_slot(&self, config: &Config) -> bool {
+        config
+            .file_transfer
+            .max_concurrent
+            .is_none_or(|max_concurrent| self.running() < max_concurrent.get())
+    }
+
+    fn start_or_wait(&mut self, id: Id, approval: Approval, config: &Config) {
+        if self.has_free_slot(config) {
+            self.start(id, approval);
+        } else if let Some(item) = self.items.get_mut(&id) {
+            log::debug!(
+                "File transfer for {:?} waiting for other transfers to finish",
+                item.file_transfer().filename
+            );
+
+            item.file_transfer_mut().status = Status::Waiting;
+            self.waiting.push_back((id, approval));
+        }
+    }
+
+    fn start(&mut self, id: Id, approval: Approval) {
+        if let Some(Item::Working {
+            file_transfer,
+            task,
+            approved,
+        }) = self.items.get_mut(&id)
+        {
+            match approval {
+                Approval::Save(save_to) => task.approve(save_to),
+                Approval::Resume(save_to, position) => {
+                    task.approve_resume(save_to, position);
+                    self.resuming.insert(id, position);
+                }
+            }
+
+            file_transfer.status = Status::PendingApproval;
+            *approved = true;
+        }
     }
 
-    pub fn approve(&mut self, id: &Id, save_to: PathBuf) {
-        if let Some(Item::Working { task, .. }) = self.items.get_mut(id) {
-            task.approve(save_to);
+    /// Starts waiting transfers, in order, while there are free slots.
+    fn start_waiting(&mut self, config: &Config) {
+        while self.has_free_slot(config)
+            && let Some((id, approval)) = self.waiting.pop_front()
+        {
+            self.start(id, approval);
         }
     }
 
+    /// Position (starting at 1) of a transfer waiting for a free slot.
+    pub fn waiting_position(&self, id: &Id) -> Option<usize> {
+        self.waiting
+            .iter()
+            .position(|(waiting, _)| waiting == id)
+            .map(|index| index + 1)
+    }
+
+    /// Moves a waiting transfer `offset` places towards the front (negative)
+    /// or back (positive) of the queue.
+    pub fn move_waiting(&mut self, id: &Id, offset: isize) {
+        let Some(index) =
+            self.waiting.iter().position(|(waiting, _)| waiting == id)
+        else {
+            return;
+        };
+
+        let target = index
+            .saturating_add_signed(offset)
+            .min(self.waiting.len() - 1);
+
+        if let Some(waiting) = self.waiting.remove(index) {
+            self.waiting.insert(target, waiting);
+        }
+    }
+
+    /// Routes a `DCC RESUME` to the matching transfer we are sending, or a
+    /// `DCC ACCEPT` to the matching transfer we asked to resume.
+    pub fn handle_resume(&mut self, request: ResumeRequest) {
+        let ResumeRequest {
+            from,
+            resume,
+            accepted,
+            server,
+        } = request;
+
+        let is_from = |file_transfer: &FileTransfer, direction: Direction| {
+            file_transfer.direction == direction
+                && file_transfer.server == server
+                && file_transfer.remote_user.nickname() == from.nickname()
+        };
+
+        if accepted {
+            let Some(id) = self
+                .resuming
+                .iter()
+                .find(|(id, position)| {
+                    **position == resume.position
+                        && self.get(id).is_some_and(|file_transfer| {
+                            is_from(file_transfer, Direction::Received)
+                        })
+                })
+                .map(|(id, _)| *id)
+            else {
+                return;
+            };
+
+            self.resuming.remove(&id);
+
+            if let Some(Item::Working { task, .. }) = self.items.get_mut(&id) {
+                task.resume_accepted();
+            }
+        } else {
+            let token = resume
+                .token
+                .as_ref()
+                .and_then(|token| token.parse::<u16>().ok());
+
+            let Some((file_transfer, task)) =
+                self.items.iter_mut().find_map(|(id, item)| match item {
+                    Item::Working {
+                        file_transfer,
+                        task,
+                        ..
+                    } if is_from(file_transfer, Direction::Sent)
+                        && matches!(
+                            file_transfer.status,
+                            Status::Ready | Status::PendingReverseConfirmation
+                        )
+                        && match token {
+                            Some(token) => Id(token) == *id,
+                            None => self
+                                .used_ports
+                                .get(id)
+                                .is_some_and(|port| port.get() == resume.port),
+                        } =>
+                    {
+                        Some((file_transfer, task))
+                    }
+                    _ => None,
+                })
+            else {
+                log::debug!(
+                    "Ignoring DCC RESUME from {} for {:?}",
+                    from.nickname(),
+                    resume.filename
+                );
+                return;
+            };
+
+            if resume.position >= file_transfer.size {
+                return;
+            }
+
+            log::debug!(
+                "File transfer to {} for {:?} resuming at {} bytes",
+                from.nickname(),
+                file_transfer.filename,
+                resume.position
+            );
+
+            task.resume(resume.position);
+        }
+    }
+
+    /// A received transfer which is waiting for the user to accept it.
+    pub fn is_pending_approval(&self, id: &Id) -> bool {
+        matches!(
+            self.items.get(id),
+            Some(Item::Working {
+                file_transfer: FileTransfer {
+                    status: Status::PendingApproval,
+                    ..
+                },
+                approved: false,
+                ..
+            })
+        )
+    }
+
+    /// Always accept transfers from `nickname`.
+    pub fn trust(&mut self, nickname: NickRef) {
+        self.trusted
+            .insert(nickname.as_normalized_str().to_string());
+    }
+
+    pub fn trusted(&self) -> impl Iterator<Item = &str> {
+        self.trusted.iter().map(String::as_str)
+    }
+
+    pub fn set_trusted(&mut self, trusted: impl IntoIterator<Item = String>) {
+        self.trusted = trusted.into_iter().collect();
+    }
+
     pub fn get<'a>(&'a self, id: &Id) -> Option<&'a FileTransfer> {
         self.items.get(id).map(Item::file_transfer)
     }
@@ -410,3 +946,217 @@ impl Manager {
         self.items.values().len() == 0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::isupport;
+    use crate::server::ServerName;
+    use crate::user::Nick;
+
+    fn receive(manager: &mut Manager, filename: &str) -> Id {
+        let id = manager.get_random_id();
+
+        manager.items.insert(
+            id,
+            Item::Working {
+                file_transfer: FileTransfer {
+                    id,
+                    server: Server::from(ServerName::from("libera")),
+                    created_at: Utc::now(),
+                    direction: Direction::Received,
+                    remote_user: User::from(Nick::from_str(
+                        "sender",
+                        isupport::CaseMap::default(),
+                    )),
+                    filename: filename.to_string(),
+                    size: 1024,
+                    status: Status::PendingApproval,
+                    secure: false,
+                    path: None,
+                    partial: None,
+                },
+                task: task::Handle::idle(),
+                approved: false,
+            },
+        );
+
+        id
+    }
+
+    #[tokio::test]
+    async fn approve_into_approves_pending_transfers() {
+        let config = Config::default();
+        let folder = Path::new("downloads");
+
+        let mut manager = Manager::default();
+        let first = receive(&mut manager, "first.txt");
+        let second = receive(&mut manager, "second.txt");
+        let approved = receive(&mut manager, "approved.txt");
+
+        manager.approve(&approved, PathBuf::from("approved.txt"), &config);
+
+        assert_eq!(
+            manager.approve_into(&[first, second, approved], folder, &config),
+            vec![first, second]
+        );
+
+        for (id, filename) in [(first, "first.txt"), (second, "second.txt")] {
+            assert!(!manager.is_pending_approval(&id));
+            assert_eq!(
+                manager.get(&id).and_then(|transfer| transfer.path.clone()),
+                Some(folder.join(filename))
+            );
+        }
+
+        assert_eq!(
+            manager
+                .get(&approved)
+                .and_then(|transfer| transfer.path.clone()),
+            Some(PathBuf::from("approved.txt"))
+        );
+    }
+
+    #[tokio::test]
+    async fn approve_into_renames_instead_of_prompting() {
+        let mut config = Config::default();
+        config.file_transfer.on_collision = OnCollision::Prompt;
+
+        let folder = std::env::temp_dir()
+            .join(format!("halloy-approve-into-{}", std::process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("notes.txt"), "").unwrap();
+
+        let mut manager = Manager::default();
+        let id = receive(&mut manager, "notes.txt");
+
+        let approved = manager.approve_into(&[id], &folder, &config);
+        let path = manager.get(&id).and_then(|transfer| transfer.path.clone());
+
+        let _ = fs::remove_dir_all(&folder);
+
+        assert_eq!(approved, vec![id]);
+        assert_eq!(path, Some(folder.join("notes (1).txt")));
+    }
+
+    #[tokio::test]
+    async fn removing_approved_transfers_starts_waiting_ones() {
+        let mut config = Config::default();
+        config.file_transfer.max_concurrent = NonZeroUsize::new(1);
+
+        let mut manager = Manager::default();
+        let ids =
+            ["a", "b", "c"].map(|filename| receive(&mut manager, filename));
+
+        manager.approve_into(&ids, Path::new("downloads"), &config);
+
+        assert_eq!(
+            ids.map(|id| manager.waiting_position(&id)),
+            [None, Some(1), Some(2)]
+        );
+        assert_eq!(
+            manager.get(&ids[2]).map(|transfer| &transfer.status),
+            Some(&Status::Waiting)
+        );
+
+        manager.remove(&ids[0], &config);
+
+        assert_eq!(manager.waiting_position(&ids[1]), None);
+        assert_eq!(manager.waiting_position(&ids[2]), Some(1));
+        assert_eq!(
+            manager.get(&ids[1]).map(|transfer| &transfer.status),
+            Some(&Status::PendingApproval)
+        );
+
+        for id in &ids[1..] {
+            manager.remove(id, &config);
+        }
+
+        assert!(manager.is_empty());
+    }
+
+    #[tokio::test]
+    async fn transfers_beyond_max_concurrent_wait_in_order() {
+        let mut config = Config::default();
+        config.file_transfer.max_concurrent = NonZeroUsize::new(2);
+
+        let mut manager = Manager::default();
+        let ids = ["a", "b", "c", "d"]
+            .map(|filename| receive(&mut manager, filename));
+
+        for id in &ids {
+            manager.approve(id, PathBuf::from("downloads"), &config);
+        }
+
+        assert_eq!(
+            ids.map(|id| manager.waiting_position(&id)),
+            [None, None, Some(1), Some(2)]
+        );
+
+        // Only received transfers which are running take up a slot
+        let pending = receive(&mut manager, "e");
+        assert_eq!(manager.running(), 2);
+        assert!(manager.is_pending_approval(&pending));
+
+        manager.remove(&ids[1], &config);
+
+        assert_eq!(manager.running(), 2);
+        assert_eq!(manager.waiting_position(&ids[2]), None);
+        assert_eq!(manager.waiting_position(&ids[3]), Some(1));
+
+        // Removing a waiting transfer doesn't start another one
+        manager.remove(&ids[3], &config);
+
+        assert_eq!(manager.running(), 2);
+        assert!(manager.waiting.is_empty());
+    }
+
+    #[tokio::test]
+    async fn waiting_transfers_can_be_moved() {
+        let mut config = Config::default();
+        config.file_transfer.max_concurrent = NonZeroUsize::new(1);
+
+        let mut manager = Manager::default();
+        let [running, a, b, c] = ["running", "a", "b", "c"]
+            .map(|filename| receive(&mut manager, filename));
+
+        for id in [running, a, b, c] {
+            manager.approve(&id, PathBuf::from("downloads"), &config);
+        }
+
+        let order = |manager: &Manager| {
+            manager
+                .waiting
+                .iter()
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(order(&manager), vec![a, b, c]);
+
+        manager.move_waiting(&c, -1);
+        assert_eq!(order(&manager), vec![a, c, b]);
+
+        // Moves are clamped to the queue
+        manager.move_waiting(&c, -5);
+        assert_eq!(order(&manager), vec![c, a, b]);
+
+        manager.move_waiting(&c, 5);
+        assert_eq!(order(&manager), vec![a, b, c]);
+        assert_eq!(manager.waiting_position(&c), Some(3));
+
+        // Transfers which aren't waiting can't be moved
+        manager.move_waiting(&running, 1);
+        assert_eq!(order(&manager), vec![a, b, c]);
+
+        // The moved transfer is started first
+        manager.move_waiting(&b, -1);
+        manager.remove(&running, &config);
+
+        assert_eq!(manager.waiting_position(&b), None);
+        assert_eq!(order(&manager), vec![a, c]);
+    }
+}
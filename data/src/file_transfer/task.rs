@@ -11,7 +11,7 @@ use irc::proto::command;
 use irc::{BytesCodec, Connection, connection};
 use sha2::{Digest, Sha256};
 use thiserror::Error;
-use tokio::fs::File;
+use tokio::fs::{File, OpenOptions};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::task::JoinHandle;
 use tokio::time;
@@ -33,6 +33,22 @@ impl Handle {
         let _ = self.sender.try_send(Action::Approve { save_to });
     }
 
+    /// Approve, continuing the partial file at `save_to` from `position`.
+    pub fn approve_resume(&mut self, save_to: PathBuf, position: u64) {
+        let _ = self
+            .sender
+            .try_send(Action::ApproveResume { save_to, position });
+    }
+
+    /// The receiver asked to continue from `position`.
+    pub fn resume(&mut self, position: u64) {
+        let _ = self.sender.try_send(Action::Resume { position });
+    }
+
+    pub fn resume_accepted(&mut self) {
+        let _ = self.sender.try_send(Action::ResumeAccepted);
+    }
+
     pub fn confirm_reverse(&mut self, host: IpAddr, port: NonZeroU16) {
         let _ = self
             .sender
@@ -44,6 +60,19 @@ impl Handle {
     }
 }
 
+#[cfg(test)]
+impl Handle {
+    /// A handle to a task which does nothing.
+    pub fn idle() -> Self {
+        let (sender, _) = mpsc::channel(1);
+
+        Self {
+            sender,
+            task: tokio::spawn(std::future::pending()),
+        }
+    }
+}
+
 impl Drop for Handle {
     fn drop(&mut self) {
         self.task.abort();
@@ -54,6 +83,7 @@ pub enum Task {
     Receive {
         id: Id,
         dcc_send: dcc::Send,
+        secure: bool,
         server_handle: server::Handle,
         remote_user: User,
     },
@@ -63,6 +93,7 @@ pub enum Task {
         sanitized_filename: String,
         remote_user: User,
         reverse: bool,
+        secure: bool,
         server_handle: server::Handle,
     },
 }
@@ -71,12 +102,14 @@ impl Task {
     pub fn receive(
         id: Id,
         dcc_send: dcc::Send,
+        secure: bool,
         remote_user: User,
         server_handle: server::Handle,
     ) -> Self {
         Self::Receive {
             id,
             dcc_send,
+            secure,
             remote_user,
             server_handle,
         }
@@ -88,6 +121,7 @@ impl Task {
         sanitized_filename: String,
         remote_user: User,
         reverse: bool,
+        secure: bool,
         server_handle: server::Handle,
     ) -> Self {
         Self::Send {
@@ -96,6 +130,7 @@ impl Task {
             sanitized_filename,
             remote_user,
             reverse,
+            secure,
             server_handle,
         }
     }
@@ -116,12 +151,14 @@ impl Task {
                 Task::Receive {
                     id,
                     dcc_send,
+                    secure,
                     remote_user,
                     server_handle,
                 } => {
                     if let Err(error) = receive(
                         id,
                         dcc_send,
+                        secure,
                         remote_user,
                         server_handle,
                         action_receiver,
@@ -143,6 +180,7 @@ impl Task {
                     sanitized_filename,
                     remote_user,
                     reverse,
+                    secure,
                     server_handle,
                 } => {
                     if let Err(error) = send(
@@ -151,6 +189,7 @@ impl Task {
                         sanitized_filename,
                         remote_user,
                         reverse,
+                        secure,
                         server_handle,
                         action_receiver,
                         update_sender,
@@ -180,6 +219,9 @@ impl Task {
 
 pub enum Action {
     Approve { save_to: PathBuf },
+    ApproveResume { save_to: PathBuf, position: u64 },
+    Resume { position: u64 },
+    ResumeAccepted,
     ReverseConfirmed { host: IpAddr, port: NonZeroU16 },
     PortAvailable { port: NonZeroU16 },
 }
@@ -205,11 +247,43 @@ pub enum Update {
 pub struct Server {
     pub public_address: IpAddr,
     pub bind_address: IpAddr,
+    pub tls_cert_path: Option<PathBuf>,
+    pub tls_key_path: Option<PathBuf>,
+}
+
+// Peers present self-signed certificates, which can't be verified.
+fn connect_security(secure: bool) -> connection::Security<'static> {
+    if secure {
+        connection::Security::Secured {
+            accept_invalid_certs: true,
+            root_cert_path: None,
+            client_cert_path: None,
+            client_key_path: None,
+            options: connection::tls::Options::default(),
+        }
+    } else {
+        connection::Security::Unsecured
+    }
+}
+
+fn listen_security(secure: bool, server: &Server) -> connection::Security<'_> {
+    if secure {
+        connection::Security::Secured {
+            accept_invalid_certs: true,
+            root_cert_path: None,
+            client_cert_path: server.tls_cert_path.as_ref(),
+            client_key_path: server.tls_key_path.as_ref(),
+            options: connection::tls::Options::default(),
+        }
+    } else {
+        connection::Security::Unsecured
+    }
 }
 
 async fn receive(
     id: Id,
     dcc_send: dcc::Send,
+    secure: bool,
     remote_user: User,
     mut server_handle: server::Handle,
     mut action: Receiver<Action>,
@@ -219,25 +293,59 @@ async fn receive(
     proxy: Option<config::Proxy>,
 ) -> Result<(), Error> {
     // Wait for approval
-    let Some(Action::Approve { save_to }) = action.next().await else {
-        return Ok(());
+    let (save_to, position) = match action.next().await {
+        Some(Action::Approve { save_to }) => (save_to, 0),
+        Some(Action::ApproveResume { save_to, position }) => {
+            (save_to, position)
+        }
+        _ => return Ok(()),
     };
 
-    let (host, port, filename, size, reverse) = match dcc_send {
+    if position > 0 {
+        let (port, token) = match &dcc_send {
+            dcc::Send::Direct { port, .. } => (port.get(), None),
+            dcc::Send::Reverse { port, token, .. } => {
+                (port.map_or(0, NonZeroU16::get), Some(token.clone()))
+            }
+        };
+
+        let _ = server_handle
+            .send(
+                dcc::Resume {
+                    filename: dcc_send.filename().to_string(),
+                    port,
+                    position,
+                    token,
+                }
+                .encode(&remote_user.nickname(), false),
+            )
+            .await;
+
+        let Some(Action::ResumeAccepted) =
+            time::timeout(timeout, action.next())
+                .await
+                .map_err(|_| Error::TimeoutResume)?
+        else {
+            return Ok(());
+        };
+    }
+
+    let (host, port, filename, size, reverse, security) = match dcc_send {
         dcc::Send::Direct {
             host,
             port,
             filename,
             size,
             ..
-        } => (host, port, filename, size, false),
+        } => (host, port, filename, size, false, connect_security(secure)),
         dcc::Send::Reverse {
             filename,
             size,
             token,
             ..
         } => {
-            let server = server.ok_or(Error::ReverseReceiveNoServerConfig)?;
+            let server =
+                server.as_ref().ok_or(Error::ReverseReceiveNoServerConfig)?;
 
             let _ = update.send(Update::Queued(id)).await;
 
@@ -255,11 +363,18 @@ async fn receive(
                         size,
                         token,
                     }
-                    .encode(&remote_user.nickname()),
+                    .encode(&remote_user.nickname(), secure),
                 )
                 .await;
 
-            (server.bind_address, port, filename, size, true)
+            (
+                server.bind_address,
+                port,
+                filename,
+                size,
+                true,
+                listen_security(secure, server),
+            )
         }
     };
 
@@ -273,7 +388,7 @@ async fn receive(
             Connection::listen_and_accept(
                 host,
                 port.get(),
-                connection::Security::Unsecured,
+                security,
                 BytesCodec::new(),
             ),
         )
@@ -284,7 +399,7 @@ async fn receive(
             connection::Config {
                 server: &host.to_string(),
                 port: port.get(),
-                security: connection::Security::Unsecured,
+                security,
                 proxy: proxy.map(From::from),
                 websocket: None,
             },
@@ -293,10 +408,25 @@ async fn receive(
         .await?
     };
 
-    let mut file = File::create(&save_to).await?;
     let mut hasher = Sha256::new();
 
-    let mut transferred = 0;
+    let mut file = if position > 0 {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&save_to)
+            .await?;
+
+        hash_prefix(&mut file, position, &mut hasher).await?;
+        // Drop anything past the resume position
+        file.set_len(position).await?;
+
+        file
+    } else {
+        File::create(&save_to).await?
+    };
+
+    let mut transferred = position;
     let mut last_progress = started_at;
 
     let ack_timeout =
@@ -377,6 +507,7 @@ async fn send(
     sanitized_filename: String,
     remote_user: User,
     reverse: bool,
+    secure: bool,
     mut server_handle: server::Handle,
     mut action: Receiver<Action>,
     mut update: Sender<Update>,
@@ -389,6 +520,9 @@ async fn send(
 
     let _ = update.send(Update::Metadata(id, size)).await;
 
+    // Where to start sending from, if the receiver resumes
+    let mut position = 0;
+
     let mut connection = if reverse {
         // Host doesn't matter for reverse connection
         let host = IpAddr::V4([127, 0, 0, 1].into());
@@ -403,16 +537,37 @@ async fn send(
                     size,
                     token,
                 }
-                .encode(&remote_user.nickname()),
+                .encode(&remote_user.nickname(), secure),
             )
             .await;
 
-        let Some(Action::ReverseConfirmed { host, port }) =
-            time::timeout(timeout, action.next())
+        let (host, port) = loop {
+            match time::timeout(timeout, action.next())
                 .await
                 .map_err(|_| Error::TimeoutPassive)?
-        else {
-            unreachable!();
+            {
+                Some(Action::ReverseConfirmed { host, port }) => {
+                    break (host, port);
+                }
+                Some(Action::Resume {
+                    position: requested,
+                }) => {
+                    position = requested;
+
+                    let _ = server_handle
+                        .send(
+                            dcc::Resume {
+                                filename: sanitized_filename.clone(),
+                                port: 0,
+                                position,
+                                token: Some(u16::from(id).to_string()),
+                            }
+                            .encode(&remote_user.nickname(), true),
+                        )
+                        .await;
+                }
+                _ => return Ok(()),
+            }
         };
 
         let _ = update.send(Update::Ready(id)).await;
@@ -421,7 +576,7 @@ async fn send(
             connection::Config {
                 server: &host.to_string(),
                 port: port.get(),
-                security: connection::Security::Unsecured,
+                security: connect_security(secure),
                 proxy: proxy.map(From::from),
                 websocket: None,
             },
@@ -429,7 +584,8 @@ async fn send(
         )
         .await?
     } else {
-        let server = server.ok_or(Error::NonPassiveSendNoServerConfig)?;
+        let server =
+            server.as_ref().ok_or(Error::NonPassiveSendNoServerConfig)?;
 
         let _ = update.send(Update::Queued(id)).await;
 
@@ -445,23 +601,46 @@ async fn send(
                     port,
                     size,
                 }
-                .encode(&remote_user.nickname()),
+                .encode(&remote_user.nickname(), secure),
             )
             .await;
 
         let _ = update.send(Update::Ready(id)).await;
 
-        time::timeout(
+        let accept = time::timeout(
             timeout,
             Connection::listen_and_accept(
                 server.bind_address,
                 port.get(),
-                connection::Security::Unsecured,
+                listen_security(secure, server),
                 BytesCodec::new(),
             ),
-        )
-        .await
-        .map_err(|_| Error::TimeoutConnection)??
+        );
+        tokio::pin!(accept);
+
+        // The receiver may ask to resume before connecting
+        loop {
+            tokio::select! {
+                connection = &mut accept => {
+                    break connection.map_err(|_| Error::TimeoutConnection)??;
+                }
+                Some(Action::Resume { position: requested }) = action.next() => {
+                    position = requested;
+
+                    let _ = server_handle
+                        .send(
+                            dcc::Resume {
+                                filename: sanitized_filename.clone(),
+                                port: port.get(),
+                                position,
+                                token: None,
+                            }
+                            .encode(&remote_user.nickname(), true),
+                        )
+                        .await;
+                }
+            }
+        }
     };
 
     let started_at = Instant::now();
@@ -469,7 +648,10 @@ async fn send(
     let mut buffer = BytesMut::with_capacity(BUFFER_SIZE);
     let mut hasher = Sha256::new();
 
-    let mut transferred = 0;
+    // The skipped part is still hashed, so both ends agree on the checksum
+    hash_prefix(&mut file, position, &mut hasher).await?;
+
+    let mut transferred = position;
     let mut last_progress = started_at;
 
     while transferred < size {
@@ -537,6 +719,32 @@ async fn send(
     Ok(())
 }
 
+/// Hashes the first `len` bytes of `file`, leaving it positioned after them.
+async fn hash_prefix(
+    file: &mut File,
+    len: u64,
+    hasher: &mut Sha256,
+) -> Result<(), Error> {
+    let mut prefix = file.take(len);
+    let mut buffer = BytesMut::with_capacity(BUFFER_SIZE);
+    let mut read = 0;
+
+    while read < len {
+        let n = prefix.read_buf(&mut buffer).await?;
+
+        if n == 0 {
+            return Err(Error::Io(io::ErrorKind::UnexpectedEof.into()));
+        }
+
+        hasher.update(&buffer);
+        buffer.clear();
+
+        read += n as u64;
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Error)]
 enum Error {
     #[error(
@@ -555,6 +763,8 @@ enum Error {
     TimeoutConnection,
     #[error("timed out waiting for remote to confirm passive request")]
     TimeoutPassive,
+    #[error("timed out waiting for remote to accept resuming the transfer")]
+    TimeoutResume,
     #[error("connection closed before the transfer completed")]
     TruncatedTransfer,
 }
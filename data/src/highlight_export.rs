@@ -0,0 +1,120 @@
+use std::io;
+use std::process::Stdio;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time;
+
+use crate::target::Channel;
+use crate::{Message, Server, url};
+
+/// A highlight handed to the configured export command.
+#[derive(Debug, Clone, Serialize)]
+pub struct Export {
+    pub server: String,
+    pub channel: Option<String>,
+    pub sender: Option<String>,
+    pub text: String,
+    pub server_time: DateTime<Utc>,
+    pub msgid: Option<String>,
+    /// Opens the buffer the highlight was received in.
+    pub url: Option<String>,
+}
+
+impl Export {
+    pub fn new(
+        server: &Server,
+        channel: Option<&Channel>,
+        message: &Message,
+    ) -> Self {
+        Self {
+            server: server.to_string(),
+            channel: channel.map(ToString::to_string),
+            sender: message
+                .target
+                .source()
+                .user()
+                .map(|user| user.nickname().to_string()),
+            text: message.text().into_owned(),
+            server_time: message.server_time,
+            msgid: message.id.as_ref().map(|id| id.as_str().to_string()),
+            url: channel.map(|channel| {
+                url::buffer(&format!("{server:b}"), channel.as_str())
+            }),
+        }
+    }
+
+    fn envs(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("HALLOY_SERVER", Some(self.server.as_str())),
+            ("HALLOY_CHANNEL", self.channel.as_deref()),
+            ("HALLOY_SENDER", self.sender.as_deref()),
+            ("HALLOY_TEXT", Some(self.text.as_str())),
+            ("HALLOY_MSGID", self.msgid.as_deref()),
+            ("HALLOY_URL", self.url.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key, value)))
+    }
+}
+
+/// Runs `command` in a shell with the highlight as JSON on stdin, and its
+/// fields as `HALLOY_*` environment variables.
+pub async fn run(
+    command: String,
+    timeout: Duration,
+    export: Export,
+) -> Result<(), Error> {
+    let json = serde_json::to_vec(&export)?;
+
+    let mut process = if cfg!(target_os = "windows") {
+        let mut process = Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    } else {
+        let mut process = Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    };
+
+    let mut child = process
+        .envs(export.envs())
+        .env("HALLOY_SERVER_TIME", export.server_time.to_rfc3339())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The command may not read its input
+        let _ = stdin.write_all(&json).await;
+    }
+
+    let output = time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| Error::Timeout(timeout))??;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(Error::Failed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("timed out after {} seconds", .0.as_secs())]
+    Timeout(Duration),
+    #[error("command failed: {0}")]
+    Failed(String),
+}
@@ -1,5 +1,5 @@
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::time::Duration;
 use std::{fmt, io};
@@ -21,8 +21,8 @@ use crate::redaction::Redaction;
 use crate::target::{self, Target};
 use crate::user::Nick;
 use crate::{
-    Buffer, Message, Server, buffer, compression, config, environment,
-    isupport, reaction, redaction,
+    Buffer, Message, Server, buffer, compression, config, diagnostics,
+    environment, isupport, reaction, redaction,
 };
 
 pub mod filter;
@@ -133,6 +133,9 @@ impl Kind {
             Buffer::Internal(buffer::Internal::FileTransfers) => None,
             Buffer::Internal(buffer::Internal::ChannelDiscovery(_)) => None,
             Buffer::Internal(buffer::Internal::ConfigEditor) => None,
+            Buffer::Internal(buffer::Internal::Diagnostics) => None,
+            Buffer::Internal(buffer::Internal::Unread) => None,
+            Buffer::Internal(buffer::Internal::Ignores) => None,
         }
     }
 }
@@ -411,7 +414,9 @@ pub async fn dir_path() -> Result<PathBuf, Error> {
     Ok(history_dir)
 }
 
-async fn path(kind: &Kind) -> Result<PathBuf, Error> {
+/// The compressed history file backing `kind`, which may not have been
+/// written yet.
+pub async fn path(kind: &Kind) -> Result<PathBuf, Error> {
     let dir = dir_path().await?;
 
     let name = match kind {
@@ -439,12 +444,20 @@ pub enum History {
         last_updated_at: Option<Instant>,
         max_triggers_unread: Option<DateTime<Utc>>,
         max_triggers_highlight: Option<DateTime<Utc>>,
+        // Times of messages after the read marker which trigger unread /
+        // highlight, for unread counts. Only messages seen this session are
+        // counted.
+        unread_times: Vec<DateTime<Utc>>,
+        highlight_times: Vec<DateTime<Utc>>,
         read_marker: Option<ReadMarker>,
         chathistory_references: Option<MessageReferences>,
         last_seen: HashMap<Nick, DateTime<Utc>>,
         pending_reactions: HashMap<message::Id, reaction::Pending>,
         pending_redactions: HashMap<message::Id, redaction::Pending>,
         show_in_sidebar: bool,
+        // Listed by `CHATHISTORY TARGETS`, shown in the sidebar before any
+        // new message arrives.
+        discovered: bool,
         flushing_messages: Vec<(Message, Option<LabeledResponseContext>)>,
         flushing_reactions: HashMap<message::Id, reaction::Pending>,
         flushing_redactions: HashMap<message::Id, redaction::Pending>,
@@ -470,12 +483,15 @@ impl History {
             last_updated_at: None,
             max_triggers_unread: None,
             max_triggers_highlight: None,
+            unread_times: vec![],
+            highlight_times: vec![],
             read_marker: None,
             chathistory_references: None,
             last_seen: HashMap::new(),
             pending_reactions: HashMap::new(),
             pending_redactions: HashMap::new(),
             show_in_sidebar: false,
+            discovered: false,
             flushing_messages: vec![],
             flushing_reactions: HashMap::new(),
             flushing_redactions: HashMap::new(),
@@ -486,12 +502,18 @@ impl History {
         if let Self::Partial {
             max_triggers_unread,
             max_triggers_highlight,
+            unread_times,
+            highlight_times,
             read_marker,
             chathistory_references,
             ..
         } = self
         {
             *read_marker = (*read_marker).max(metadata.read_marker);
+            if let Some(read_marker) = read_marker {
+                unread_times.retain(|time| read_marker.date_time() < *time);
+                highlight_times.retain(|time| read_marker.date_time() < *time);
+            }
             *max_triggers_unread =
                 (*max_triggers_unread).max(metadata.last_triggers_unread);
             *max_triggers_highlight =
@@ -571,6 +593,61 @@ impl History {
         }
     }
 
+    fn unread_count(&self) -> usize {
+        match self {
+            History::Partial { unread_times, .. } => unread_times.len(),
+            History::Full {
+                messages,
+                display_read_marker,
+                ..
+            } => {
+                metadata::count_triggers_unread(messages, *display_read_marker)
+            }
+        }
+    }
+
+    fn unread_messages(&self) -> Vec<&Message> {
+        match self {
+            History::Partial {
+                pending_messages,
+                flushing_messages,
+                read_marker,
+                ..
+            } => pending_messages
+                .iter()
+                .chain(flushing_messages)
+                .map(|(message, _)| message)
+                .filter(|message| {
+                    message.triggers_unread()
+                        && read_marker.is_none_or(|read_marker| {
+                            read_marker.date_time() < message.server_time
+                        })
+                })
+                .collect(),
+            History::Full {
+                messages,
+                display_read_marker,
+                ..
+            } => {
+                metadata::triggers_unread_after(messages, *display_read_marker)
+                    .collect()
+            }
+        }
+    }
+
+    fn highlight_count(&self) -> usize {
+        match self {
+            History::Partial {
+                highlight_times, ..
+            } => highlight_times.len(),
+            History::Full {
+                messages,
+                read_marker,
+                ..
+            } => metadata::count_triggers_highlight(messages, *read_marker),
+        }
+    }
+
     fn add_message(
         &mut self,
         message: Message,
@@ -592,22 +669,38 @@ impl History {
         if message.triggers_unread()
             && let History::Partial {
                 max_triggers_unread,
+                unread_times,
+                read_marker,
                 ..
             } = self
         {
             *max_triggers_unread =
                 (*max_triggers_unread).max(Some(message.server_time));
+
+            if read_marker.is_none_or(|read_marker| {
+                read_marker.date_time() < message.server_time
+            }) {
+                unread_times.push(message.server_time);
+            }
         }
 
         if message.triggers_highlight()
             && !message.blocked
             && let History::Partial {
                 max_triggers_highlight,
+                highlight_times,
+                read_marker,
                 ..
             } = self
         {
             *max_triggers_highlight =
                 (*max_triggers_highlight).max(Some(message.server_time));
+
+            if read_marker.is_none_or(|read_marker| {
+                read_marker.date_time() < message.server_time
+            }) {
+                highlight_times.push(message.server_time);
+            }
         }
 
         match self {
@@ -775,7 +868,15 @@ impl History {
                             .then_some(start_index + slice_index)
                     })
                 {
-                    if messages[index].redaction.is_some() {
+                    if messages[index].redaction.is_some()
+                        || matches!(
+                            messages[index].target.source(),
+                            Source::User(_)
+                                | Source::Internal(
+                                    message::source::Internal::Output
+                                )
+                        )
+                    {
                         return vec![&mut messages[index]];
                     } else if let Some(first_index) = messages[..=index]
                         .iter()
@@ -853,7 +954,9 @@ impl History {
 
                     return Some(
                         async move {
-                            append(
+                            let started = Instant::now();
+
+                            let result = append(
                                 &kind,
                                 seed,
                                 pending_messages,
@@ -864,7 +967,11 @@ impl History {
                                 pending_reactions,
                                 pending_redactions,
                             )
-                            .await
+                            .await;
+
+                            diagnostics::history_written(started.elapsed());
+
+                            result
                         }
                         .boxed(),
                     );
@@ -904,14 +1011,19 @@ impl History {
 
                     return Some(
                         async move {
-                            overwrite(
+                            let started = Instant::now();
+
+                            let result = overwrite(
                                 &kind,
                                 &messages,
                                 read_marker,
                                 chathistory_references,
                             )
-                            .await
-                            .map(|()| vec![])
+                            .await;
+
+                            diagnostics::history_written(started.elapsed());
+
+                            result.map(|()| vec![])
                         }
                         .boxed(),
                     );
@@ -922,6 +1034,26 @@ impl History {
         }
     }
 
+    fn diagnostics(&self) -> diagnostics::Buffer {
+        match self {
+            History::Partial {
+                kind,
+                pending_messages,
+                ..
+            } => diagnostics::Buffer {
+                kind: kind.clone(),
+                messages: pending_messages.len(),
+                bytes: pending_messages.capacity()
+                    * size_of::<(Message, Option<LabeledResponseContext>)>(),
+            },
+            History::Full { kind, messages, .. } => diagnostics::Buffer {
+                kind: kind.clone(),
+                messages: messages.len(),
+                bytes: messages.capacity() * size_of::<Message>(),
+            },
+        }
+    }
+
     fn make_partial(
         &mut self,
     ) -> Option<impl Future<Output = Result<(), Error>> + use<>> {
@@ -942,6 +1074,16 @@ impl History {
                     metadata::latest_triggers_unread(messages);
                 let max_triggers_highlight =
                     metadata::latest_triggers_highlight(messages);
+                let unread_times = messages_after_read_marker(
+                    messages,
+                    read_marker,
+                    Message::triggers_unread,
+                );
+                let highlight_times = messages_after_read_marker(
+                    messages,
+                    read_marker,
+                    |message| message.triggers_highlight() && !message.blocked,
+                );
                 let chathistory_references =
                     metadata::latest_can_reference(messages)
                         .max(chathistory_references.clone());
@@ -955,11 +1097,14 @@ impl History {
                         read_marker,
                         max_triggers_unread,
                         max_triggers_highlight,
+                        unread_times,
+                        highlight_times,
                         chathistory_references: chathistory_references.clone(),
                         last_seen,
                         pending_reactions: HashMap::new(),
                         pending_redactions: HashMap::new(),
                         show_in_sidebar: true,
+                        discovered: false,
                         flushing_messages: vec![],
                         flushing_reactions: HashMap::new(),
                         flushing_redactions: HashMap::new(),
@@ -1201,8 +1346,14 @@ impl History {
         let stored = match self {
             History::Partial {
                 read_marker: stored_read_marker,
+                unread_times,
+                highlight_times,
                 ..
-            } => stored_read_marker,
+            } => {
+                unread_times.retain(|time| read_marker.date_time() < *time);
+                highlight_times.retain(|time| read_marker.date_time() < *time);
+                stored_read_marker
+            }
             History::Full {
                 display_read_marker,
                 read_marker: stored_read_marker,
@@ -1432,6 +1583,29 @@ impl History {
         }
     }
 
+    /// Distinct words and URLs from the last `count` messages sent by users,
+    /// most recent first.
+    pub fn recent_words(&self, count: usize) -> Vec<String> {
+        let History::Full { messages, .. } = self else {
+            return vec![];
+        };
+
+        let mut seen = HashSet::new();
+
+        messages
+            .iter()
+            .rev()
+            .filter(|message| message.target.source().user().is_some())
+            .take(count)
+            .flat_map(|message| {
+                completion_words(&message.text())
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|word| seen.insert(word.clone()))
+            .collect()
+    }
+
     pub fn renormalize_messages(&mut self, seed: Seed) {
         match self {
             History::Full { messages, .. } => {
@@ -1447,6 +1621,21 @@ impl History {
     }
 }
 
+/// Words in `text` worth offering for completion, stripped of surrounding
+/// punctuation.
+fn completion_words(text: &str) -> impl Iterator<Item = &str> {
+    const MIN_LENGTH: usize = 4;
+
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_start_matches(['(', '[', '<', '"', '\''])
+                .trim_end_matches([
+                    '.', ',', ';', ':', '!', '?', ')', ']', '>', '"', '\'',
+                ])
+        })
+        .filter(|word| word.chars().count() >= MIN_LENGTH)
+}
+
 /// Insert the incoming message into the provided vector, sorted
 /// on server time
 ///
@@ -1633,7 +1822,8 @@ fn has_matching_content(
                 | message::source::server::Kind::WAllOps
                 | message::source::server::Kind::Kick
                 | message::source::server::Kind::Away
-                | message::source::server::Kind::Invite => (),
+                | message::source::server::Kind::Invite
+                | message::source::server::Kind::Ctcp => (),
             }
         }
 
@@ -1685,6 +1875,23 @@ pub fn insert_reaction(
     }
 }
 
+fn messages_after_read_marker(
+    messages: &[Message],
+    read_marker: Option<ReadMarker>,
+    predicate: impl Fn(&Message) -> bool,
+) -> Vec<DateTime<Utc>> {
+    messages
+        .iter()
+        .filter(|message| {
+            predicate(message)
+                && read_marker.is_none_or(|read_marker| {
+                    read_marker.date_time() < message.server_time
+                })
+        })
+        .map(|message| message.server_time)
+        .collect()
+}
+
 pub fn update_last_seen(
     last_seen: &mut HashMap<Nick, DateTime<Utc>>,
     message: &Message,
@@ -1773,6 +1980,76 @@ pub struct View<'a> {
     pub old_messages: Vec<&'a Message>,
     pub new_messages: Vec<&'a Message>,
     pub cleared: bool,
+    /// Replayed messages folded behind the first message of each replay.
+    pub replays: HashMap<message::Hash, Folded>,
+}
+
+/// The time between losing the connection to a server and restoring it,
+/// where any messages were replayed after reconnecting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Replay {
+    pub from: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+}
+
+impl Replay {
+    pub fn contains(&self, server_time: DateTime<Utc>) -> bool {
+        self.from < server_time && server_time < self.until
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Folded {
+    /// Start of the [`Replay`] the messages are from.
+    pub from: DateTime<Utc>,
+    pub count: usize,
+    pub expanded: bool,
+}
+
+/// Keeps only the first message of each folded replay, unless it's expanded.
+pub fn fold_replays<'a>(
+    messages: Vec<&'a Message>,
+    replays: &[Replay],
+    is_expanded: impl Fn(&Replay) -> bool,
+) -> (Vec<&'a Message>, HashMap<message::Hash, Folded>) {
+    let mut first_messages = HashMap::<DateTime<Utc>, message::Hash>::new();
+    let mut folded = HashMap::<message::Hash, Folded>::new();
+
+    let messages = messages
+        .into_iter()
+        .filter(|message| {
+            let Some(replay) = replays
+                .iter()
+                .find(|replay| replay.contains(message.server_time))
+            else {
+                return true;
+            };
+
+            let expanded = is_expanded(replay);
+
+            if let Some(first) = first_messages.get(&replay.from) {
+                if let Some(folded) = folded.get_mut(first) {
+                    folded.count += 1;
+                }
+
+                expanded
+            } else {
+                first_messages.insert(replay.from, message.hash);
+                folded.insert(
+                    message.hash,
+                    Folded {
+                        from: replay.from,
+                        count: 1,
+                        expanded,
+                    },
+                );
+
+                true
+            }
+        })
+        .collect();
+
+    (messages, folded)
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -1784,3 +2061,121 @@ pub enum Error {
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{DateTime, TimeDelta, Utc};
+
+    use super::{
+        History, Kind, Message, Replay, completion_words, fold_replays,
+    };
+    use crate::message::{Content, Fragment};
+
+    fn message_at(server_time: DateTime<Utc>) -> Message {
+        Message {
+            server_time,
+            ..Message::plugin(None, server_time.to_rfc3339())
+        }
+    }
+
+    #[test]
+    fn words_for_completion() {
+        let words = completion_words(
+            "see (PROJ-1234), or https://example.com/issue?id=5. ok",
+        )
+        .collect::<Vec<_>>();
+
+        assert_eq!(words, vec!["PROJ-1234", "https://example.com/issue?id=5"]);
+    }
+
+    #[test]
+    fn replayed_messages_fold_behind_the_first() {
+        let start = Utc::now();
+        let at = |seconds| start + TimeDelta::seconds(seconds);
+
+        let messages = (0..6).map(|i| message_at(at(i))).collect::<Vec<_>>();
+        let replay = Replay {
+            from: at(0),
+            until: at(4),
+        };
+
+        let (folded, replays) =
+            fold_replays(messages.iter().collect(), &[replay], |_| false);
+
+        assert_eq!(
+            folded
+                .iter()
+                .map(|message| message.hash)
+                .collect::<Vec<_>>(),
+            [0, 1, 4, 5].map(|i| messages[i].hash)
+        );
+
+        let fold = replays[&messages[1].hash];
+        assert_eq!(fold.from, replay.from);
+        assert_eq!(fold.count, 3);
+        assert!(!fold.expanded);
+    }
+
+    #[test]
+    fn expanded_replays_keep_every_message() {
+        let start = Utc::now();
+        let at = |seconds| start + TimeDelta::seconds(seconds);
+
+        let messages = (0..6).map(|i| message_at(at(i))).collect::<Vec<_>>();
+        let replays = [
+            Replay {
+                from: at(0),
+                until: at(3),
+            },
+            Replay {
+                from: at(3),
+                until: at(6),
+            },
+        ];
+
+        let (folded, folds) =
+            fold_replays(messages.iter().collect(), &replays, |replay| {
+                replay.from == at(0)
+            });
+
+        assert_eq!(folded.len(), 5);
+        assert_eq!(folds.len(), 2);
+        assert!(folds[&messages[1].hash].expanded);
+        assert_eq!(folds[&messages[1].hash].count, 2);
+        assert!(!folds[&messages[4].hash].expanded);
+        assert_eq!(folds[&messages[4].hash].count, 2);
+    }
+
+    #[test]
+    fn blocked_highlights_are_not_counted() {
+        let start = Utc::now();
+        let highlight = |seconds, blocked| Message {
+            content: Content::Fragments(vec![Fragment::HighlightMatch(
+                "halloy".to_string(),
+            )]),
+            blocked,
+            ..message_at(start + TimeDelta::seconds(seconds))
+        };
+
+        let mut history = History::Full {
+            kind: Kind::Logs,
+            messages: vec![highlight(0, false), highlight(1, true)],
+            last_updated_at: None,
+            read_marker: None,
+            display_read_marker: None,
+            chathistory_references: None,
+            last_seen: HashMap::new(),
+            cleared: false,
+            last_flushed_at: 0,
+        };
+
+        assert_eq!(history.highlight_count(), 1);
+
+        let _ = history.make_partial();
+
+        assert!(matches!(history, History::Partial { .. }));
+        assert_eq!(history.highlight_count(), 1);
+    }
+}
@@ -18,11 +18,11 @@ use crate::redaction::Redaction;
 use crate::target::{self, Target};
 use crate::user::Nick;
 use crate::{
-    Config, Server, buffer, client, config, input, isupport, reaction,
-    redaction, server,
+    Config, Server, buffer, client, command, config, diagnostics, input,
+    isupport, reaction, redaction, server,
 };
 
-const DRAFT_SAVE_EVERY: Duration = Duration::from_secs(10);
+const INPUT_SAVE_EVERY: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Resource {
@@ -79,7 +79,7 @@ pub enum Message {
     Exited(Vec<(history::Kind, Result<(), history::Error>)>),
     SentMessageUpdated(history::Kind, history::ReadMarker),
     ResendMessage(history::Kind, message::Message),
-    DraftsSaved,
+    InputSaved,
     EchoEvents(Server, Vec<EchoEvent>),
 }
 
@@ -97,7 +97,7 @@ pub struct Manager {
     filters: Vec<Filter>,
     reroute_rules: RerouteRules,
     data: Data,
-    last_draft_changed: Option<tokio::time::Instant>,
+    last_input_changed: Option<tokio::time::Instant>,
 }
 
 impl Manager {
@@ -269,7 +269,7 @@ impl Manager {
             Message::ResendMessage(kind, message) => {
                 return Some(Event::ResendMessage(kind, message));
             }
-            Message::DraftsSaved => {}
+            Message::InputSaved => {}
             Message::EchoEvents(server, events) => {
                 return Some(Event::EchoEvents(server, events));
             }
@@ -347,6 +347,7 @@ impl Manager {
     ) -> impl Future<Output = Message> + use<> {
         let data = std::mem::take(&mut self.data);
         let drafts = data.input.clone_drafts();
+        let sent = data.input.clone_sent();
         let seeds: Vec<Option<history::Seed>> =
             data.map.keys().map(|kind| clients.get_seed(kind)).collect();
         let seeded_map = data.map.into_iter().zip(seeds);
@@ -358,27 +359,30 @@ impl Manager {
 
             let results = future::join_all(tasks).await;
             input::save_drafts(drafts).await;
+            input::save_sent(sent).await;
             Message::Exited(results)
         }
     }
 
-    pub fn maybe_save_drafts(
+    pub fn maybe_save_input(
         &mut self,
         now: tokio::time::Instant,
     ) -> Option<BoxFuture<'static, Message>> {
-        let last_changed = self.last_draft_changed?;
+        let last_changed = self.last_input_changed?;
 
-        if now.duration_since(last_changed) < DRAFT_SAVE_EVERY {
+        if now.duration_since(last_changed) < INPUT_SAVE_EVERY {
             return None;
         }
 
-        self.last_draft_changed = None;
+        self.last_input_changed = None;
         let drafts = self.data.input.clone_drafts();
+        let sent = self.data.input.clone_sent();
 
         Some(
             async move {
                 input::save_drafts(drafts).await;
-                Message::DraftsSaved
+                input::save_sent(sent).await;
+                Message::InputSaved
             }
             .boxed(),
         )
@@ -391,6 +395,14 @@ impl Manager {
         self.data.input.load_drafts_into(drafts);
     }
 
+    pub fn preload_input_history(
+        &mut self,
+        sent: HashMap<buffer::Upstream, Vec<String>>,
+        history_length: usize,
+    ) {
+        self.data.input.load_sent_into(sent, history_length);
+    }
+
     pub fn record_input_message(
         &mut self,
         message: message::Message,
@@ -429,15 +441,21 @@ impl Manager {
         &mut self,
         buffer: &buffer::Upstream,
         text: String,
+        history_length: usize,
     ) {
-        self.data.input.record(buffer, text);
+        self.data.input.record(buffer, text, history_length);
+        self.input_changed();
     }
 
     pub fn record_draft(&mut self, raw_input: input::RawInput) {
         self.data.input.store_draft(raw_input);
-        // Only set if None, so drafts save on an interval
-        if self.last_draft_changed.is_none() {
-            self.last_draft_changed = Some(tokio::time::Instant::now());
+        self.input_changed();
+    }
+
+    fn input_changed(&mut self) {
+        // Only set if None, so drafts and input history save on an interval
+        if self.last_input_changed.is_none() {
+            self.last_input_changed = Some(tokio::time::Instant::now());
         }
     }
 
@@ -587,6 +605,20 @@ impl Manager {
         self.data.remove_message(kind, server_time, hash, resend)
     }
 
+    /// Removes a message which hasn't been sent, returning its command.
+    pub fn cancel_message(
+        &mut self,
+        kind: &history::Kind,
+        server_time: DateTime<Utc>,
+        hash: message::Hash,
+    ) -> Option<command::Irc> {
+        self.data
+            .map
+            .get_mut(kind)?
+            .remove_message(server_time, hash)?
+            .command
+    }
+
     pub fn expand_message(
         &mut self,
         kind: history::Kind,
@@ -607,6 +639,21 @@ impl Manager {
         self.data.contract_message(kind, server_time, hash, config);
     }
 
+    /// Shows or folds the messages replayed after the reconnect starting at
+    /// `from`.
+    pub fn expand_replay(
+        &mut self,
+        kind: history::Kind,
+        from: DateTime<Utc>,
+        expanded: bool,
+    ) {
+        if expanded {
+            self.data.expanded_replays.insert((kind, from));
+        } else {
+            self.data.expanded_replays.remove(&(kind, from));
+        }
+    }
+
     pub fn get_reply_preview(
         &self,
         kind: history::Kind,
@@ -707,9 +754,10 @@ impl Manager {
         &self,
         kind: &history::Kind,
         limit: Option<Limit>,
+        hidden_messages: Option<&buffer::HiddenMessages>,
         config: &Config,
     ) -> Option<history::View<'_>> {
-        self.data.history_view(kind, limit, config)
+        self.data.history_view(kind, limit, hidden_messages, config)
     }
 
     pub fn get_last_seen(
@@ -725,6 +773,20 @@ impl Manager {
             .unwrap_or_default()
     }
 
+    pub fn get_recent_words(
+        &self,
+        buffer: &buffer::Upstream,
+        count: usize,
+    ) -> Vec<String> {
+        let kind = history::Kind::from_input_buffer(buffer.clone());
+
+        self.data
+            .map
+            .get(&kind)
+            .map(|history| history.recent_words(count))
+            .unwrap_or_default()
+    }
+
     pub fn get_unique_queries(&self, server: &Server) -> Vec<&target::Query> {
         self.data
             .map
@@ -738,8 +800,10 @@ impl Manager {
                     && match history {
                         History::Full { .. } => true,
                         History::Partial {
-                            show_in_sidebar, ..
-                        } => *show_in_sidebar,
+                            show_in_sidebar,
+                            discovered,
+                            ..
+                        } => *show_in_sidebar || *discovered,
                     })
                 .then_some(query),
                 _ => None,
@@ -748,6 +812,30 @@ impl Manager {
             .collect()
     }
 
+    /// Lists a query with history on the bouncer in the sidebar, before any
+    /// new message arrives. Its metadata should already be loading.
+    pub fn discover(&mut self, kind: &history::Kind) {
+        if let history::Kind::Query(..) = kind
+            && let Some(History::Partial { discovered, .. }) =
+                self.data.map.get_mut(kind)
+        {
+            *discovered = true;
+        }
+    }
+
+    /// Whether the query is only listed from `CHATHISTORY TARGETS`, without
+    /// any new messages this session.
+    pub fn is_discovered(&self, kind: &history::Kind) -> bool {
+        matches!(
+            self.data.map.get(kind),
+            Some(History::Partial {
+                show_in_sidebar: false,
+                discovered: true,
+                ..
+            })
+        )
+    }
+
     pub fn server_kinds(&self, server: Server) -> Vec<history::Kind> {
         self.data
             .map
@@ -775,6 +863,77 @@ impl Manager {
             .any(History::has_unread)
     }
 
+    pub fn server_has_highlight(&self, server: &Server) -> bool {
+        self.data
+            .map
+            .iter()
+            .filter_map(|(kind, history)| {
+                if kind.server().is_some_and(|s| *s == *server) {
+                    Some(history)
+                } else {
+                    None
+                }
+            })
+            .any(History::has_highlight)
+    }
+
+    /// Histories with unread highlights.
+    pub fn highlighted(&self) -> impl Iterator<Item = &history::Kind> {
+        self.data
+            .map
+            .iter()
+            .filter(|(_, history)| history.has_highlight())
+            .map(|(kind, _)| kind)
+    }
+
+    pub fn server_unread_count(&self, server: &Server) -> usize {
+        self.data
+            .map
+            .iter()
+            .filter(|(kind, _)| kind.server().is_some_and(|s| *s == *server))
+            .map(|(_, history)| history.unread_count())
+            .sum()
+    }
+
+    pub fn server_highlight_count(&self, server: &Server) -> usize {
+        self.data
+            .map
+            .iter()
+            .filter(|(kind, _)| kind.server().is_some_and(|s| *s == *server))
+            .map(|(_, history)| history.highlight_count())
+            .sum()
+    }
+
+    pub fn unread_count(&self, kind: &history::Kind) -> usize {
+        self.data.map.get(kind).map_or(0, History::unread_count)
+    }
+
+    /// Unread messages across all channels and queries, sorted by server
+    /// time.
+    pub fn unread_messages(&self) -> Vec<(&history::Kind, &crate::Message)> {
+        self.data
+            .map
+            .iter()
+            .filter(|(kind, _)| {
+                matches!(
+                    kind,
+                    history::Kind::Channel(..) | history::Kind::Query(..)
+                )
+            })
+            .flat_map(|(kind, history)| {
+                history
+                    .unread_messages()
+                    .into_iter()
+                    .map(move |message| (kind, message))
+            })
+            .sorted_by_key(|(_, message)| message.server_time)
+            .collect()
+    }
+
+    pub fn highlight_count(&self, kind: &history::Kind) -> usize {
+        self.data.map.get(kind).map_or(0, History::highlight_count)
+    }
+
     pub fn has_unread(&self, kind: &history::Kind) -> bool {
         self.data.map.get(kind).is_some_and(History::has_unread)
     }
@@ -828,6 +987,27 @@ impl Manager {
             })
             .cloned();
 
+        match &broadcast {
+            Broadcast::Disconnected { .. } => {
+                self.data
+                    .disconnected_at
+                    .entry(server.clone())
+                    .or_insert(server_time);
+            }
+            Broadcast::Reconnected => {
+                if let Some(from) = self.data.disconnected_at.remove(server) {
+                    self.data.replays.entry(server.clone()).or_default().push(
+                        history::Replay {
+                            from,
+                            until: server_time,
+                        },
+                    );
+                    self.data.prune_replays(server);
+                }
+            }
+            _ => (),
+        }
+
         let messages = broadcast::into_messages(
             broadcast,
             config,
@@ -1373,9 +1553,45 @@ fn with_limit<'a>(
 struct Data {
     map: HashMap<history::Kind, History>,
     input: input::Storage,
+    /// When the connection to each server was lost, until it's restored.
+    disconnected_at: HashMap<Server, DateTime<Utc>>,
+    replays: HashMap<Server, Vec<history::Replay>>,
+    expanded_replays: HashSet<(history::Kind, DateTime<Utc>)>,
 }
 
 impl Data {
+    /// Drops replays which ended before the oldest message loaded for the
+    /// server, as there is nothing left for them to fold.
+    fn prune_replays(&mut self, server: &Server) {
+        let Some(oldest) = self
+            .map
+            .iter()
+            .filter(|(kind, _)| kind.server() == Some(server))
+            .filter_map(|(_, history)| match history {
+                History::Full { messages, .. } => {
+                    messages.first().map(|message| message.server_time)
+                }
+                History::Partial { .. } => None,
+            })
+            .min()
+        else {
+            return;
+        };
+
+        let Some(replays) = self.replays.get_mut(server) else {
+            return;
+        };
+
+        replays.retain(|replay| replay.until >= oldest);
+
+        let replays = &*replays;
+
+        self.expanded_replays.retain(|(kind, from)| {
+            kind.server() != Some(server)
+                || replays.iter().any(|replay| replay.from == *from)
+        });
+    }
+
     fn load_full(
         &mut self,
         kind: history::Kind,
@@ -1547,6 +1763,7 @@ impl Data {
         &self,
         kind: &history::Kind,
         limit: Option<Limit>,
+        hidden_messages: Option<&buffer::HiddenMessages>,
         config: &Config,
     ) -> Option<history::View<'_>> {
         let History::Full {
@@ -1575,6 +1792,10 @@ impl Data {
                             .as_ref()
                             .map(std::convert::AsRef::as_ref)
                     }
+                } else if hidden_messages
+                    .is_some_and(|hidden| hidden.hides(message))
+                {
+                    None
                 } else {
                     match message.target.source() {
                         message::Source::Internal(
@@ -1600,6 +1821,18 @@ impl Data {
             })
             .collect::<Vec<_>>();
 
+        let replays = kind
+            .server()
+            .filter(|_| config.buffer.chathistory.fold_replayed)
+            .and_then(|server| self.replays.get(server))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let (processed, replays) =
+            history::fold_replays(processed, replays, |replay| {
+                self.expanded_replays.contains(&(kind.clone(), replay.from))
+            });
+
         let total = processed.len();
 
         let first_without_limit = processed.first().copied();
@@ -1643,6 +1876,7 @@ impl Data {
             old_messages: old.to_vec(),
             new_messages: new.to_vec(),
             cleared: *cleared,
+            replays,
         })
     }
 
@@ -1941,6 +2175,10 @@ impl Data {
         now: Instant,
         clients: &client::Map,
     ) -> Vec<BoxFuture<'static, Message>> {
+        diagnostics::buffers_measured(
+            self.map.values().map(History::diagnostics).collect(),
+        );
+
         self.map
             .iter_mut()
             .filter_map(|(kind, state)| {
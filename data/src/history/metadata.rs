@@ -55,7 +55,8 @@ impl ReadMarker {
                     && match message.target.source() {
                         source::Source::Internal(source) => match source {
                             source::Internal::Status(_)
-                            | source::Internal::Condensed(_) => false,
+                            | source::Internal::Condensed(_)
+                            | source::Internal::Output => false,
                             // Logs are in their own buffer and this gives us backlog support there
                             source::Internal::Logs(_) => true,
                         },
@@ -105,6 +106,46 @@ pub fn latest_triggers_highlight(
         .map(|message| message.server_time)
 }
 
+/// Number of messages after `read_marker` which trigger unread.
+pub fn count_triggers_unread(
+    messages: &[Message],
+    read_marker: Option<ReadMarker>,
+) -> usize {
+    messages_after(messages, read_marker)
+        .filter(|message| message.triggers_unread())
+        .count()
+}
+
+/// Messages after `read_marker` which trigger unread, newest first.
+pub fn triggers_unread_after(
+    messages: &[Message],
+    read_marker: Option<ReadMarker>,
+) -> impl Iterator<Item = &Message> {
+    messages_after(messages, read_marker)
+        .filter(|message| message.triggers_unread())
+}
+
+/// Number of messages after `read_marker` which trigger highlight.
+pub fn count_triggers_highlight(
+    messages: &[Message],
+    read_marker: Option<ReadMarker>,
+) -> usize {
+    messages_after(messages, read_marker)
+        .filter(|message| message.triggers_highlight())
+        .count()
+}
+
+fn messages_after(
+    messages: &[Message],
+    read_marker: Option<ReadMarker>,
+) -> impl Iterator<Item = &Message> {
+    messages.iter().rev().take_while(move |message| {
+        read_marker.is_none_or(|read_marker| {
+            read_marker.date_time() < message.server_time
+        })
+    })
+}
+
 pub fn latest_can_reference(messages: &[Message]) -> Option<MessageReferences> {
     messages
         .iter()
@@ -0,0 +1,349 @@
+//! Translations of user-facing strings.
+//!
+//! Strings are looked up by key (e.g. `"sidebar.menu.quit"`) from locale
+//! files, which are TOML documents whose (nested) tables form the keys.
+//! Locales ship with Halloy, and can be added to or overridden by placing
+//! `<language>.toml` in the `locales` directory of the config directory.
+//! Missing keys fall back to English, and then to the key itself.
+//!
+//! Dates and numbers are formatted for the locale matching the selected
+//! language, or the system locale when no language is configured.
+
+use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use pure_rust_locales::locale_match;
+
+use crate::environment;
+
+const FALLBACK_LANGUAGE: &str = "en";
+
+const BUILTIN: &[(&str, &str)] = &[
+    ("en", include_str!("../../assets/locales/en.toml")),
+    ("de", include_str!("../../assets/locales/de.toml")),
+];
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+struct Locale {
+    strings: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+    format: chrono::Locale,
+}
+
+/// Select the language used for the rest of the session.  If no language is
+/// given, the language is taken from the environment.
+///
+/// Has no effect once a string has been translated or formatted.
+pub fn init(language: Option<&str>) {
+    let _ = LOCALE.set(Locale::load(language));
+}
+
+/// Locale used to format dates and numbers.
+pub fn locale() -> chrono::Locale {
+    current().format
+}
+
+/// Translation of the string for `key`, or the key itself when it has no
+/// translation.
+pub fn tr<'a>(key: &'a str) -> &'a str {
+    let locale = current();
+
+    locale
+        .strings
+        .get(key)
+        .or_else(|| locale.fallback.get(key))
+        .map_or_else(
+            || {
+                log::debug!("missing translation for {key}");
+                key
+            },
+            String::as_str,
+        )
+}
+
+/// Translation of the string for `key`, with each `{name}` placeholder
+/// replaced by its value in `args`.
+pub fn tr_args(key: &str, args: &[(&str, &str)]) -> String {
+    substitute(tr(key), args)
+}
+
+/// Replaces placeholders in a single pass, so placeholders within the
+/// substituted values are left as they are.
+fn substitute(template: &str, args: &[(&str, &str)]) -> String {
+    let mut substituted = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        substituted.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let value = rest.find('}').and_then(|end| {
+            let name = &rest[1..end];
+
+            args.iter()
+                .find(|(arg, _)| *arg == name)
+                .map(|(_, value)| (*value, end))
+        });
+
+        if let Some((value, end)) = value {
+            substituted.push_str(value);
+            rest = &rest[end + 1..];
+        } else {
+            substituted.push('{');
+            rest = &rest[1..];
+        }
+    }
+
+    substituted.push_str(rest);
+    substituted
+}
+
+/// `value` with digits grouped by the locale's thousands separator.
+pub fn format_number(value: u64) -> String {
+    let digits = value.to_string();
+    let locale = locale();
+    let separator = locale_match!(locale => LC_NUMERIC::THOUSANDS_SEP);
+
+    digits.char_indices().fold(
+        String::new(),
+        |mut formatted, (index, digit)| {
+            if index > 0 && (digits.len() - index) % 3 == 0 {
+                formatted.push_str(separator);
+            }
+            formatted.push(digit);
+            formatted
+        },
+    )
+}
+
+/// `value` rounded to `precision` decimal places, using the locale's
+/// decimal point.
+pub fn format_decimal(value: f64, precision: usize) -> String {
+    let locale = locale();
+    let decimal_point = locale_match!(locale => LC_NUMERIC::DECIMAL_POINT);
+
+    format!("{value:.precision$}").replace('.', decimal_point)
+}
+
+/// Human readable size of `bytes`, in binary (IEC) units.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{} {}", format_decimal(value, 1), UNITS[unit])
+}
+
+fn current() -> &'static Locale {
+    LOCALE.get_or_init(|| Locale::load(None))
+}
+
+impl Locale {
+    fn load(language: Option<&str>) -> Self {
+        let fallback = strings(FALLBACK_LANGUAGE);
+        let format = format_locale(language);
+
+        let strings = language
+            .map(str::to_string)
+            .or_else(environment_language)
+            .into_iter()
+            .flat_map(|language| candidates(&language))
+            .map(|language| strings(&language))
+            .find(|strings| !strings.is_empty())
+            .unwrap_or_default();
+
+        Self {
+            strings,
+            fallback,
+            format,
+        }
+    }
+}
+
+/// Language from the POSIX locale environment variables.
+fn environment_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+}
+
+/// Formatting locale for `language`, preferring the system locale when it is
+/// a variant of the same language (e.g. `de` with a system locale of
+/// `de_AT`).
+fn format_locale(language: Option<&str>) -> chrono::Locale {
+    let system = sys_locale::get_locale();
+
+    let Some(language) = language else {
+        return system
+            .and_then(|system| parse_locale(&system))
+            .unwrap_or_default();
+    };
+
+    let tags = candidates(language);
+    let primary = tags.last().cloned().unwrap_or_default();
+
+    system
+        .filter(|system| candidates(system).last() == Some(&primary))
+        .and_then(|system| parse_locale(&system))
+        .or_else(|| tags.iter().find_map(|tag| parse_locale(tag)))
+        .or_else(|| parse_locale(&format!("{primary}-{primary}")))
+        .unwrap_or_default()
+}
+
+/// Parse a language tag (e.g. `pt-BR` or `pt_BR.UTF-8`) into a locale.
+fn parse_locale(tag: &str) -> Option<chrono::Locale> {
+    let tag = tag.split(['.', '@']).next()?.replace('-', "_");
+
+    let (language, region) = match tag.split_once('_') {
+        Some((language, region)) => (language, Some(region)),
+        None => (tag.as_str(), None),
+    };
+
+    let tag = match region {
+        Some(region) => {
+            format!("{}_{}", language.to_lowercase(), region.to_uppercase())
+        }
+        None => language.to_lowercase(),
+    };
+
+    chrono::Locale::from_str(&tag).ok()
+}
+
+/// Languages to try for `language`, most specific first (e.g. `pt_BR.UTF-8`
+/// yields `pt-br` and then `pt`).
+fn candidates(language: &str) -> Vec<String> {
+    let language = language
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-")
+        .to_lowercase();
+
+    let primary = language.split('-').next().unwrap_or_default().to_string();
+
+    if primary == language {
+        vec![language]
+    } else {
+        vec![language, primary]
+    }
+}
+
+fn strings(language: &str) -> HashMap<String, String> {
+    let mut strings = HashMap::new();
+
+    if let Some((_, content)) =
+        BUILTIN.iter().find(|(builtin, _)| *builtin == language)
+    {
+        flatten(&mut strings, None, parse(language, content));
+    }
+
+    let path = environment::config_dir()
+        .join("locales")
+        .join(format!("{language}.toml"));
+
+    if let Ok(content) = std::fs::read_to_string(&path) {
+        log::info!("loading locale from {}", path.display());
+
+        flatten(&mut strings, None, parse(language, &content));
+    }
+
+    strings
+}
+
+fn parse(language: &str, content: &str) -> toml::Table {
+    content.parse().unwrap_or_else(|error| {
+        log::warn!("invalid locale {language}: {error}");
+
+        toml::Table::new()
+    })
+}
+
+fn flatten(
+    strings: &mut HashMap<String, String>,
+    prefix: Option<&str>,
+    table: toml::Table,
+) {
+    for (key, value) in table {
+        let key = match prefix {
+            Some(prefix) => format!("{prefix}.{key}"),
+            None => key,
+        };
+
+        match value {
+            toml::Value::String(string) => {
+                strings.insert(key, string);
+            }
+            toml::Value::Table(table) => flatten(strings, Some(&key), table),
+            _ => log::warn!("ignoring non-string translation for {key}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_candidates() {
+        assert_eq!(candidates("pt_BR.UTF-8"), vec!["pt-br", "pt"]);
+        assert_eq!(candidates("de"), vec!["de"]);
+        assert_eq!(candidates("sr_RS@latin"), vec!["sr-rs", "sr"]);
+    }
+
+    #[test]
+    fn substitute_in_a_single_pass() {
+        assert_eq!(
+            substitute(
+                "{nick} joined {channel}",
+                &[("nick", "{channel}"), ("channel", "#halloy")]
+            ),
+            "{channel} joined #halloy"
+        );
+        assert_eq!(
+            substitute("{a} {missing} {", &[("a", "1")]),
+            "1 {missing} {"
+        );
+    }
+
+    #[test]
+    fn missing_key_falls_back_to_key() {
+        assert_eq!(tr("no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn parse_locales() {
+        assert_eq!(parse_locale("de-AT"), Some(chrono::Locale::de_AT));
+        assert_eq!(parse_locale("pt_br.UTF-8"), Some(chrono::Locale::pt_BR));
+        assert_eq!(parse_locale("xx"), None);
+    }
+
+    #[test]
+    fn builtin_locales_have_known_keys() {
+        let fallback = strings(FALLBACK_LANGUAGE);
+
+        for (language, _) in BUILTIN {
+            let strings = strings(language);
+
+            for key in strings.keys() {
+                assert!(
+                    fallback.contains_key(key),
+                    "{language} has unknown key {key}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use irc::proto::Command;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{User, ctcp, isupport, message};
+
+/// The kinds of message an ignore applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Messages,
+    Notices,
+    Ctcp,
+    Invites,
+}
+
+impl Kind {
+    pub const ALL: &'static [Self] =
+        &[Self::Messages, Self::Notices, Self::Ctcp, Self::Invites];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Kind::Messages => "messages",
+            Kind::Notices => "notices",
+            Kind::Ctcp => "ctcp",
+            Kind::Invites => "invites",
+        }
+    }
+
+    /// The kind of an incoming message, if it is one which can be ignored.
+    pub fn of(command: &Command) -> Option<Self> {
+        match command {
+            Command::PRIVMSG(_, text)
+                if ctcp::is_query(text) && !message::is_action(text) =>
+            {
+                Some(Kind::Ctcp)
+            }
+            Command::PRIVMSG(..) => Some(Kind::Messages),
+            Command::NOTICE(_, text) if ctcp::is_query(text) => {
+                Some(Kind::Ctcp)
+            }
+            Command::NOTICE(..) => Some(Kind::Notices),
+            Command::INVITE(..) => Some(Kind::Invites),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for Kind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or(())
+    }
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A `nick!user@host` pattern, where `*` matches any run of characters and
+/// `?` any single one, whose messages of the given kinds are hidden.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Mask {
+    pub mask: String,
+    pub types: Vec<Kind>,
+    /// Collapse messages behind a toggle rather than hiding them.
+    pub soft: bool,
+}
+
+impl Mask {
+    /// Creates a mask from a pattern, completing a bare nickname (`bob`) or
+    /// `user@host` into a full `nick!user@host` pattern.  An empty list of
+    /// kinds ignores all of them.
+    pub fn new(pattern: &str, types: Vec<Kind>) -> Self {
+        let pattern = pattern.trim();
+
+        let mask = match (pattern.contains('!'), pattern.contains('@')) {
+            (true, true) => pattern.to_string(),
+            (true, false) => format!("{pattern}@*"),
+            (false, true) => format!("*!{pattern}"),
+            (false, false) => format!("{pattern}!*@*"),
+        };
+
+        let types = if types.is_empty() {
+            Kind::ALL.to_vec()
+        } else {
+            types
+        };
+
+        Self {
+            mask,
+            types,
+            soft: false,
+        }
+    }
+
+    pub fn with_soft(self, soft: bool) -> Self {
+        Self { soft, ..self }
+    }
+
+    pub fn matches(&self, user: &User, casemapping: isupport::CaseMap) -> bool {
+        let hostmask = format!(
+            "{}!{}@{}",
+            user.nickname(),
+            user.username().unwrap_or_default(),
+            user.hostname().unwrap_or_default()
+        );
+
+        wildcard_match(&self.mask, &hostmask, casemapping)
+    }
+
+    pub fn ignores(
+        &self,
+        user: &User,
+        kind: Kind,
+        casemapping: isupport::CaseMap,
+    ) -> bool {
+        self.types.contains(&kind)
+            && !(self.soft && kind == Kind::Messages)
+            && self.matches(user, casemapping)
+    }
+
+    /// Whether messages from `user` are shown collapsed.
+    pub fn collapses(
+        &self,
+        user: &User,
+        casemapping: isupport::CaseMap,
+    ) -> bool {
+        self.soft
+            && self.types.contains(&Kind::Messages)
+            && self.matches(user, casemapping)
+    }
+
+    /// Whether this mask was entered as the same pattern as `pattern`.
+    pub fn is_pattern(&self, pattern: &str) -> bool {
+        self.mask
+            .eq_ignore_ascii_case(&Self::new(pattern, vec![]).mask)
+    }
+}
+
+impl<'de> Deserialize<'de> for Mask {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Data {
+            Mask(String),
+            WithTypes {
+                mask: String,
+                #[serde(default)]
+                types: Vec<Kind>,
+                #[serde(default)]
+                soft: bool,
+            },
+        }
+
+        Ok(match Data::deserialize(deserializer)? {
+            Data::Mask(mask) => Mask::new(&mask, vec![]),
+            Data::WithTypes { mask, types, soft } => {
+                Mask::new(&mask, types).with_soft(soft)
+            }
+        })
+    }
+}
+
+/// Whether an incoming message is from a user matched by any of `masks`,
+/// for its kind.
+pub fn is_ignored<'a>(
+    mut masks: impl Iterator<Item = &'a Mask>,
+    message: &message::Encoded,
+    casemapping: isupport::CaseMap,
+) -> bool {
+    let Some(kind) = Kind::of(&message.command) else {
+        return false;
+    };
+
+    let Some(user) = message.user(casemapping) else {
+        return false;
+    };
+
+    masks.any(|mask| mask.ignores(&user, kind, casemapping))
+}
+
+/// Whether messages from `user` are collapsed by any of `masks`.
+pub fn is_collapsed<'a>(
+    mut masks: impl Iterator<Item = &'a Mask>,
+    user: &User,
+    casemapping: isupport::CaseMap,
+) -> bool {
+    masks.any(|mask| mask.collapses(user, casemapping))
+}
+
+/// Glob matching (`*` and `?`), unlike the regex `masks` of other settings.
+/// Both sides are compared case-insensitively under the server's
+/// `casemapping`.
+fn wildcard_match(
+    pattern: &str,
+    text: &str,
+    casemapping: isupport::CaseMap,
+) -> bool {
+    let pattern = casemapping.normalize(pattern).chars().collect::<Vec<_>>();
+    let text = casemapping.normalize(text).chars().collect::<Vec<_>>();
+
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` in the pattern, and of the text it matched up
+    // to, to backtrack to on a mismatch.
+    let mut star = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Kind, Mask, wildcard_match};
+    use crate::User;
+    use crate::isupport::CaseMap;
+
+    #[test]
+    fn wildcards() {
+        let casemapping = CaseMap::default();
+
+        assert!(wildcard_match(
+            "*!*@*.example.com",
+            "bob!b@host.example.com",
+            casemapping
+        ));
+        assert!(wildcard_match("b?b!*@*", "BOB!~b@host", casemapping));
+        assert!(!wildcard_match(
+            "*!*@*.example.com",
+            "bob!b@example.org",
+            casemapping
+        ));
+        assert!(!wildcard_match("bob!*@*", "bobby!b@host", casemapping));
+        // Regex syntax has no special meaning
+        assert!(!wildcard_match("b.b!*@*", "bob!b@host", casemapping));
+        assert!(wildcard_match("[b]!*@*", "[b]!b@host", casemapping));
+    }
+
+    #[test]
+    fn wildcards_use_the_server_casemapping() {
+        assert!(wildcard_match(
+            "[bob]!*@*",
+            "{BOB}!b@host",
+            CaseMap::RFC1459
+        ));
+        assert!(!wildcard_match("[bob]!*@*", "{BOB}!b@host", CaseMap::ASCII));
+        assert!(wildcard_match("bob!*@*", "BOB!b@host", CaseMap::ASCII));
+    }
+
+    #[test]
+    fn masks() {
+        let user = User::parse("bob!~b@spam.example", CaseMap::default(), None)
+            .unwrap();
+
+        assert_eq!(Mask::new("bob", vec![]).mask, "bob!*@*");
+        assert_eq!(
+            Mask::new("*@spam.example", vec![]).mask,
+            "*!*@spam.example"
+        );
+
+        let casemapping = CaseMap::default();
+
+        assert!(Mask::new("bob", vec![]).ignores(
+            &user,
+            Kind::Invites,
+            casemapping
+        ));
+        assert!(!Mask::new("*@spam.example", vec![Kind::Ctcp]).ignores(
+            &user,
+            Kind::Messages,
+            casemapping
+        ));
+        assert!(!Mask::new("alice", vec![]).matches(&user, casemapping));
+
+        let soft = Mask::new("bob", vec![]).with_soft(true);
+
+        assert!(!soft.ignores(&user, Kind::Messages, casemapping));
+        assert!(soft.ignores(&user, Kind::Notices, casemapping));
+        assert!(soft.collapses(&user, casemapping));
+        assert!(!Mask::new("bob", vec![]).collapses(&user, casemapping));
+    }
+}
@@ -10,7 +10,7 @@ use nom::multi::{many_m_n, many0_count, many1_count};
 use nom::{Finish, IResult, Parser};
 
 use crate::capabilities::{Capabilities, MultilineBatchKind};
-use crate::config::buffer::text_input::AutoFormat;
+use crate::config::buffer::text_input::{AutoFormat, Filter, FilterAction};
 use crate::features::Features;
 use crate::history::reroute::RerouteRules;
 use crate::message::formatting;
@@ -18,11 +18,9 @@ use crate::target::Target;
 use crate::user::{ChannelUsers, NickRef};
 use crate::{
     Command, Config, Message, Server, User, buffer, command, environment,
-    isupport, message,
+    isupport, message, plugin,
 };
 
-const INPUT_HISTORY_LENGTH: usize = 100;
-
 pub fn parse(
     buffer: buffer::Upstream,
     auto_format: AutoFormat,
@@ -94,6 +92,10 @@ pub fn parse(
                     command::Internal::Reconnect
                         | command::Internal::Connect(_)
                         | command::Internal::Exec(_)
+                        | command::Internal::TestNotification(_)
+                        | command::Internal::Ignore(_)
+                        | command::Internal::Unignore(_)
+                        | command::Internal::Plugin(_, _)
                 ) {
                     return Ok(Parsed::Internal(command));
                 } else {
@@ -134,7 +136,22 @@ pub fn parse(
         }
     };
 
+    let content = content.filtered(
+        &buffer,
+        &config.buffer.text_input.filters,
+        isupport::get_casemapping_or_default(isupport),
+    )?;
+
     let is_command = matches!(content, Content::Command(..));
+    // Messages are deferred until the server is reconnected
+    let can_defer = matches!(
+        content,
+        Content::Text(_)
+            | Content::Command(
+                command::Irc::Msg(..) | command::Irc::Notice(..),
+                _
+            )
+    );
 
     let parsed = Parsed::Input(Input { buffer, content });
 
@@ -172,7 +189,9 @@ pub fn parse(
     }
 
     if !is_connected {
-        return Err(Error::Command(command::Error::Disconnected));
+        if !can_defer {
+            return Err(Error::Command(command::Error::Disconnected));
+        }
     } else if in_channel.is_some_and(|in_channel| !in_channel && !is_command) {
         return Err(Error::Command(command::Error::NotInChannel));
     }
@@ -185,16 +204,28 @@ pub enum Parsed {
     Input(Input),
     Internal(command::Internal),
     CodeFence(CodeFence),
+    /// Steps of a multi-step alias, sent one after the other.
+    Steps(Vec<Parsed>),
 }
 
 impl Parsed {
     pub fn code_fence(&self) -> Option<&CodeFence> {
         match &self {
-            Parsed::Input(_) | Parsed::Internal(_) => None,
+            Parsed::Input(_) | Parsed::Internal(_) | Parsed::Steps(_) => None,
             Parsed::CodeFence(code_fence) => Some(code_fence),
         }
     }
 
+    /// The lines to send, with the steps of an alias as separate lines.
+    pub fn into_lines(self) -> Vec<Parsed> {
+        match self {
+            Parsed::Steps(steps) => {
+                steps.into_iter().flat_map(Parsed::into_lines).collect()
+            }
+            parsed => vec![parsed],
+        }
+    }
+
     pub fn multiline_batch_kind(
         &self,
         casemapping: isupport::CaseMap,
@@ -235,7 +266,7 @@ impl Parsed {
                     _ => None,
                 },
             },
-            Parsed::Internal(_) => None,
+            Parsed::Internal(_) | Parsed::Steps(_) => None,
             // Not included in batch, but should not delimit a batch
             Parsed::CodeFence(_) => Some(("", MultilineBatchKind::PRIVMSG)),
         }
@@ -244,6 +275,7 @@ impl Parsed {
     pub fn warning(&self) -> Option<&command::Warning> {
         match &self {
             Self::Input(input) => input.warning(),
+            Self::Steps(steps) => steps.iter().find_map(Self::warning),
             Self::Internal(_) | Self::CodeFence(_) => None,
         }
     }
@@ -341,6 +373,59 @@ impl Input {
     pub fn encoded(&self) -> Option<message::Encoded> {
         self.content.proto(&self.buffer).map(message::Encoded::from)
     }
+
+    /// The first of `buffer.text_input.filters` asking for confirmation
+    /// before sending this message, if any.
+    pub fn confirmation<'a>(
+        &self,
+        filters: &'a [Filter],
+        casemapping: isupport::CaseMap,
+    ) -> Option<&'a Filter> {
+        let text = match &self.content {
+            Content::Text(text)
+            | Content::Command(
+                command::Irc::Msg(_, text)
+                | command::Irc::Notice(_, text)
+                | command::Irc::Me(_, text),
+                _,
+            ) => text,
+            Content::Command(..) => return None,
+        };
+
+        let target = self.buffer.target();
+
+        filters.iter().find(|filter| {
+            matches!(filter.action, FilterAction::Confirm)
+                && filter.is_target_included(
+                    target.as_ref().map(Target::as_target_ref),
+                    self.buffer.server(),
+                    casemapping,
+                )
+                && filter.is_match(text)
+        })
+    }
+
+    /// Runs the plugins' `on_send` hooks over a message, returning `None`
+    /// when a plugin cancelled it.
+    pub fn with_plugins(self) -> Option<Self> {
+        let Some(command::Irc::Msg(target, text)) =
+            self.content.command(&self.buffer)
+        else {
+            return Some(self);
+        };
+
+        match plugin::outgoing(self.server(), &target, &text) {
+            plugin::Outgoing::Unchanged => Some(self),
+            plugin::Outgoing::Replaced(text) => Some(Self {
+                content: Content::Command(
+                    command::Irc::Msg(target, text),
+                    self.warning().cloned(),
+                ),
+                ..self
+            }),
+            plugin::Outgoing::Cancelled => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -350,6 +435,56 @@ enum Content {
 }
 
 impl Content {
+    /// Applies `buffer.text_input.filters` to the text of a message.
+    fn filtered(
+        self,
+        buffer: &buffer::Upstream,
+        filters: &[Filter],
+        casemapping: isupport::CaseMap,
+    ) -> Result<Self, Error> {
+        if filters.is_empty() {
+            return Ok(self);
+        }
+
+        let target = buffer.target();
+        let filters = filters
+            .iter()
+            .filter(|filter| {
+                filter.is_target_included(
+                    target.as_ref().map(Target::as_target_ref),
+                    buffer.server(),
+                    casemapping,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let filter = |text: String| {
+            Filter::apply_all(filters.iter().copied(), text).map_err(|filter| {
+                Error::Blocked(filter.regex.as_str().to_string())
+            })
+        };
+
+        Ok(match self {
+            Self::Text(text) => Self::Text(filter(text)?),
+            Self::Command(command::Irc::Msg(targets, text), warning) => {
+                Self::Command(
+                    command::Irc::Msg(targets, filter(text)?),
+                    warning,
+                )
+            }
+            Self::Command(command::Irc::Notice(targets, text), warning) => {
+                Self::Command(
+                    command::Irc::Notice(targets, filter(text)?),
+                    warning,
+                )
+            }
+            Self::Command(command::Irc::Me(target, text), warning) => {
+                Self::Command(command::Irc::Me(target, filter(text)?), warning)
+            }
+            content => content,
+        })
+    }
+
     fn command(&self, buffer: &buffer::Upstream) -> Option<command::Irc> {
         match self {
             Self::Text(text) => {
@@ -406,12 +541,17 @@ impl Storage {
         }
     }
 
-    pub fn record(&mut self, buffer: &buffer::Upstream, text: String) {
+    pub fn record(
+        &mut self,
+        buffer: &buffer::Upstream,
+        text: String,
+        history_length: usize,
+    ) {
         self.draft_messages.remove(buffer);
         self.draft_reply.remove(buffer);
         let history = self.sent.entry(buffer.clone()).or_default();
         history.insert(0, text);
-        history.truncate(INPUT_HISTORY_LENGTH);
+        history.truncate(history_length);
     }
 
     pub fn store_draft(&mut self, raw_input: RawInput) {
@@ -459,12 +599,38 @@ impl Storage {
             }
         }
     }
+
+    pub fn clone_sent(&self) -> HashMap<buffer::Upstream, Vec<String>> {
+        self.sent
+            .iter()
+            .filter(|(_, history)| !history.is_empty())
+            .map(|(buffer, history)| (buffer.clone(), history.clone()))
+            .collect()
+    }
+
+    pub fn load_sent_into(
+        &mut self,
+        sent: HashMap<buffer::Upstream, Vec<String>>,
+        history_length: usize,
+    ) {
+        for (buffer, mut history) in sent {
+            history.truncate(history_length);
+
+            if !history.is_empty() {
+                self.sent.entry(buffer).or_insert(history);
+            }
+        }
+    }
 }
 
 fn draft_path() -> PathBuf {
     environment::data_dir().join("drafts.json")
 }
 
+fn sent_path() -> PathBuf {
+    environment::data_dir().join("input_history.json")
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct SavedDraft {
     pub text: String,
@@ -499,6 +665,33 @@ pub fn load_drafts_sync() -> HashMap<buffer::Upstream, SavedDraft> {
         .collect()
 }
 
+pub async fn save_sent(sent: HashMap<buffer::Upstream, Vec<String>>) {
+    if sent.is_empty() {
+        let _ = tokio::fs::remove_file(sent_path()).await;
+        return;
+    }
+    let pairs: Vec<(buffer::Upstream, Vec<String>)> =
+        sent.into_iter().collect();
+    match serde_json::to_vec(&pairs) {
+        Ok(bytes) => {
+            if let Err(e) = tokio::fs::write(sent_path(), bytes).await {
+                log::warn!("failed to save input history: {e}");
+            }
+        }
+        Err(e) => log::warn!("failed to serialize input history: {e}"),
+    }
+}
+
+pub fn load_sent_sync() -> HashMap<buffer::Upstream, Vec<String>> {
+    let Ok(bytes) = std::fs::read(sent_path()) else {
+        return HashMap::new();
+    };
+    serde_json::from_slice::<Vec<(buffer::Upstream, Vec<String>)>>(&bytes)
+        .unwrap_or_default()
+        .into_iter()
+        .collect()
+}
+
 /// Cached values for a buffers input
 #[derive(Debug, Clone, Copy)]
 pub struct Cache<'a> {
@@ -564,13 +757,15 @@ pub enum Error {
     ExceedsByteLimit { bytes: usize, bytes_limit: usize },
     #[error(transparent)]
     Command(#[from] command::Error),
+    #[error("message blocked by filter '{0}'")]
+    Blocked(String),
 }
 
 #[cfg(test)]
 mod test {
     use crate::capabilities::Capabilities;
     use crate::config::buffer::text_input::AutoFormat;
-    use crate::input::{CodeFence, Content, Input, Parsed, parse};
+    use crate::input::{CodeFence, Content, Error, Input, Parsed, parse};
     use crate::user::Nick;
     use crate::{Config, Server, buffer, command, features, isupport, target};
 
@@ -743,4 +938,94 @@ mod test {
             assert_eq!(parsed, expected);
         }
     }
+
+    #[test]
+    fn filters() {
+        let mut config = Config::default();
+        config.buffer.text_input = toml::from_str(
+            r#"
+            [[filters]]
+            regex = '''\bbrb\b'''
+            replace = "be right back"
+
+            [[filters]]
+            regex = '''\s+$'''
+            replace = ""
+
+            [[filters]]
+            regex = '''ghp_[A-Za-z0-9]{8}'''
+            block = true
+
+            [[filters]]
+            regex = '''\bsecret\b'''
+            confirm = true
+            "#,
+        )
+        .unwrap();
+
+        let isupport = &isupport::DEFAULT;
+        let casemapping = isupport::get_casemapping_or_default(isupport);
+        let nick = Nick::from_str("tester", casemapping);
+        let buffer = buffer::Upstream::Query(
+            Server {
+                name: "Libera".into(),
+                network: None,
+            },
+            target::Query::from(Nick::from_str("friend", casemapping)),
+        );
+
+        let parse = |input: &str| {
+            parse(
+                buffer.clone(),
+                AutoFormat::Disabled,
+                input,
+                None,
+                Some(nick.as_nickref()),
+                None,
+                true,
+                isupport,
+                &Capabilities::default(),
+                &features::DEFAULT,
+                None,
+                128,
+                &config,
+            )
+        };
+
+        assert_eq!(
+            parse("brb, coffee  "),
+            Ok(Parsed::Input(Input {
+                buffer: buffer.clone(),
+                content: Content::Text(String::from("be right back, coffee")),
+            }))
+        );
+        assert_eq!(
+            parse("/me brb"),
+            Ok(Parsed::Input(Input {
+                buffer: buffer.clone(),
+                content: Content::Command(
+                    command::Irc::Me(
+                        String::from("friend"),
+                        String::from("be right back")
+                    ),
+                    None,
+                ),
+            }))
+        );
+        assert_eq!(
+            parse("my token is ghp_abcd1234"),
+            Err(Error::Blocked(String::from("ghp_[A-Za-z0-9]{8}")))
+        );
+
+        let filters = &config.buffer.text_input.filters;
+        let confirmation = |input| match parse(input) {
+            Ok(Parsed::Input(input)) => {
+                input.confirmation(filters, casemapping).is_some()
+            }
+            _ => false,
+        };
+
+        assert!(confirmation("the secret plan"));
+        assert!(!confirmation("the secretary"));
+    }
 }
@@ -32,6 +32,7 @@ pub enum Kind {
     FILEHOST,
     HOSTLEN,
     ICON,
+    INVEX,
     KEYLEN,
     KICKLEN,
     KNOCK,
@@ -645,6 +646,7 @@ impl Operation {
                 "soju.im/FILEHOST" => Some(Kind::FILEHOST),
                 "HOSTLEN" => Some(Kind::HOSTLEN),
                 "draft/ICON" => Some(Kind::ICON),
+                "INVEX" => Some(Kind::INVEX),
                 "KEYLEN" => Some(Kind::KEYLEN),
                 "KICKLEN" => Some(Kind::KICKLEN),
                 "KNOCK" => Some(Kind::KNOCK),
@@ -762,6 +764,7 @@ impl Parameter {
             Parameter::FILEHOST(_) => Some(Kind::FILEHOST),
             Parameter::HOSTLEN(_) => Some(Kind::HOSTLEN),
             Parameter::ICON(_) => Some(Kind::ICON),
+            Parameter::INVEX(_) => Some(Kind::INVEX),
             Parameter::KEYLEN(_) => Some(Kind::KEYLEN),
             Parameter::KICKLEN(_) => Some(Kind::KICKLEN),
             Parameter::KNOCK => Some(Kind::KNOCK),
@@ -36,11 +36,16 @@ pub mod config;
 pub mod ctcp;
 pub mod dashboard;
 pub mod dcc;
+pub mod demo;
+pub mod diagnostics;
 pub mod environment;
 pub mod features;
 pub mod file_transfer;
 pub mod fileupload;
+pub mod highlight_export;
 pub mod history;
+pub mod i18n;
+pub mod ignore;
 pub mod image;
 pub mod input;
 pub mod isupport;
@@ -49,8 +54,12 @@ pub mod log;
 pub mod message;
 pub mod metadata;
 pub mod mode;
+pub mod moderation_queue;
+pub mod nick_history;
 pub mod notification;
+pub mod outbox;
 pub mod pane;
+pub mod plugin;
 pub mod preview;
 pub mod rate_limit;
 pub mod reaction;
@@ -62,7 +71,9 @@ pub mod shortcut;
 pub mod stream;
 pub mod target;
 pub mod time;
+pub mod timed_ban;
 pub mod url;
 pub mod user;
+pub mod user_list;
 pub mod version;
 pub mod window;
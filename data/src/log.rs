@@ -59,6 +59,30 @@ pub fn clear(number_of_logs_to_keep: usize) {
     }
 }
 
+/// A line of a log file in the JSON format.
+pub fn json(
+    timestamp: &str,
+    level: log::Level,
+    target: &str,
+    message: &str,
+) -> String {
+    #[derive(Serialize)]
+    struct Line<'a> {
+        timestamp: &'a str,
+        level: &'a str,
+        target: &'a str,
+        message: &'a str,
+    }
+
+    serde_json::to_string(&Line {
+        timestamp,
+        level: level.as_str(),
+        target,
+        message,
+    })
+    .unwrap_or_default()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Record {
     pub timestamp: DateTime<Utc>,
@@ -33,7 +33,9 @@ use crate::server::Server;
 use crate::target::join_targets;
 use crate::time::Posix;
 use crate::user::{ChannelUsers, Nick, NickRef};
-use crate::{Config, User, buffer, command, ctcp, isupport, message, target};
+use crate::{
+    Config, User, buffer, command, ctcp, ignore, isupport, message, target,
+};
 
 // References:
 // - https://datatracker.ietf.org/doc/html/rfc1738#section-5
@@ -362,7 +364,7 @@ pub struct Message {
     pub received_with_server_time: bool, // Only relevant if direction == Direction::Received
     pub blocked: bool,
     pub condensed: Option<Arc<Message>>,
-    pub expanded: bool, // Only relevant if can_condense or redaction.is_some()
+    pub expanded: bool, // Only relevant if can_condense, redaction.is_some() or soft ignored
     pub command: Option<command::Irc>, // Only relevant if direction == Direction::Sent
     pub reactions: Vec<Reaction>,
     pub rerouted_from: Option<Target>,
@@ -377,7 +379,12 @@ impl Message {
             && match self.target.source() {
                 Source::User(_) => true,
                 Source::Action(_) => true,
-                Source::Server(Some(server)) => server.kind().is_action(),
+                // CTCP requests & responses are shown as actions, but should
+                // not mark the query as unread
+                Source::Server(Some(server)) => {
+                    server.kind().is_action()
+                        && !matches!(server.kind(), Kind::Ctcp)
+                }
                 Source::Internal(source::Internal::Logs(level)) => {
                     match level {
                         Level::Warn | Level::Error => true,
@@ -453,6 +460,19 @@ impl Message {
             .then_some(self.expanded)
     }
 
+    /// Whether this message is from a soft ignored `user` and is shown
+    /// collapsed until it's expanded.
+    pub fn is_collapsed<'a>(
+        &self,
+        masks: impl Iterator<Item = &'a ignore::Mask>,
+        user: &User,
+        casemapping: isupport::CaseMap,
+    ) -> bool {
+        !self.expanded
+            && matches!(self.direction, Direction::Received)
+            && ignore::is_collapsed(masks, user, casemapping)
+    }
+
     pub fn references(&self) -> MessageReferences {
         MessageReferences {
             timestamp: self.server_time,
@@ -768,6 +788,48 @@ impl Message {
         }
     }
 
+    /// Text printed by a plugin into a buffer (or the server buffer).
+    pub fn plugin(target: Option<target::Target>, text: String) -> Message {
+        let received_at = Posix::now();
+        let server_time = Utc::now();
+        let content = plain(text);
+        let hash = Hash::new(&server_time, &content, &received_at);
+
+        let source =
+            Source::Internal(source::Internal::Status(source::Status::Success));
+
+        Message {
+            received_at,
+            server_time,
+            direction: Direction::Received,
+            target: match target {
+                Some(target::Target::Channel(channel)) => {
+                    Target::Channel { channel, source }
+                }
+                Some(target::Target::Query(query)) => {
+                    Target::Query { query, source }
+                }
+                None => Target::Server { source },
+            },
+            content,
+            id: None,
+            reply_to: None,
+            reply_preview: None,
+            hash,
+            hidden_urls: HashSet::default(),
+            is_echo: false,
+            received_with_server_time: false,
+            blocked: false,
+            condensed: None,
+            expanded: false,
+            command: None,
+            reactions: vec![],
+            rerouted_from: None,
+            deduplicate: false,
+            redaction: None,
+        }
+    }
+
     pub fn with_target(self, target: Destination) -> Self {
         let source = self.target.source();
 
@@ -861,6 +923,66 @@ impl Message {
         }
     }
 
+    /// Replies to an informational command, headed by `title` and shown
+    /// collapsed until expanded. Routed with [`Message::with_target`].
+    pub fn output(title: String, replies: Vec<Message>) -> Option<Self> {
+        let first = replies.first()?;
+
+        let received_at = Posix::now();
+        let server_time = first.server_time;
+        let target = Target::Server {
+            source: Source::Internal(source::Internal::Output),
+        };
+
+        let content = Content::Fragments(
+            iter::once(Fragment::Text(title))
+                .chain(replies.into_iter().flat_map(|reply| {
+                    iter::once(Fragment::Text("\n".to_string())).chain(
+                        match reply.content {
+                            Content::Fragments(fragments) => fragments,
+                            content => vec![Fragment::Text(
+                                content.text().into_owned(),
+                            )],
+                        },
+                    )
+                }))
+                .collect(),
+        );
+        let hash = Hash::new(&server_time, &content, &received_at);
+
+        Some(Self {
+            received_at,
+            server_time,
+            direction: Direction::Received,
+            target,
+            content,
+            id: None,
+            reply_to: None,
+            reply_preview: None,
+            hash,
+            hidden_urls: HashSet::default(),
+            is_echo: false,
+            received_with_server_time: first.received_with_server_time,
+            blocked: false,
+            condensed: None,
+            expanded: false,
+            command: None,
+            reactions: vec![],
+            rerouted_from: None,
+            deduplicate: false,
+            redaction: None,
+        })
+    }
+
+    /// The first line of the message, heading a collapsed command output.
+    pub fn title(&self) -> Option<String> {
+        matches!(
+            self.target.source(),
+            Source::Internal(source::Internal::Output)
+        )
+        .then(|| self.text().lines().next().unwrap_or_default().to_string())
+    }
+
     pub fn renormalize(&mut self, casemapping: isupport::CaseMap) {
         match self.target.source_mut() {
             Source::User(user) | Source::Action(Some(user)) => {
@@ -1017,6 +1139,23 @@ impl<'de> Deserialize<'de> for Message {
             Content::Plain(String::new())
         };
 
+        // CTCP requests & responses used to be stored without a server
+        // message kind
+        let target = match target {
+            Target::Query {
+                query,
+                source: Source::Server(None),
+            } if is_ctcp_text(&content.text()) => Target::Query {
+                query,
+                source: Source::Server(Some(source::Server::new(
+                    Kind::Ctcp,
+                    None,
+                    None,
+                ))),
+            },
+            target => target,
+        };
+
         let hash = Hash::new(&server_time, &content, &received_at);
 
         Ok(Message {
@@ -1044,6 +1183,15 @@ impl<'de> Deserialize<'de> for Message {
     }
 }
 
+const CTCP_RECEIVED: &str = "←";
+const CTCP_SENT: &str = "→";
+
+/// Text of a CTCP request or response, as shown in the query.
+fn is_ctcp_text(text: &str) -> bool {
+    text.split_once(' ')
+        .is_some_and(|(arrow, _)| arrow == CTCP_RECEIVED || arrow == CTCP_SENT)
+}
+
 pub fn condense(
     messages: &[&Message],
     condense: &config::buffer::Condensation,
@@ -2718,6 +2866,28 @@ fn target(
                 None,
             ))
         }
+        // Shown in the channel the join was forwarded to
+        Command::Numeric(ERR_LINKCHANNEL, params) => {
+            let channel = target::Channel::parse(
+                params.get(2)?,
+                chantypes,
+                statusmsg,
+                casemapping,
+            )
+            .ok()?;
+
+            Some((
+                Target::Channel {
+                    channel,
+                    source: Source::Server(Some(source::Server::new(
+                        Kind::StandardReply(StandardReply::Note),
+                        None,
+                        None,
+                    ))),
+                },
+                None,
+            ))
+        }
         Command::PRIVMSG(target, text) | Command::NOTICE(target, text) => {
             let is_notice = matches!(message.0.command, Command::NOTICE(_, _));
             let is_privmsg =
@@ -2781,7 +2951,11 @@ fn target(
                 (
                     Target::Query {
                         query: target::Query::from(user),
-                        source: Source::Server(None),
+                        source: Source::Server(Some(source::Server::new(
+                            Kind::Ctcp,
+                            None,
+                            None,
+                        ))),
                     },
                     None,
                 )
@@ -3375,9 +3549,9 @@ fn content<'a>(
                 let arrow = if target.as_normalized_str()
                     == our_nick.as_normalized_str()
                 {
-                    "←"
+                    CTCP_RECEIVED
                 } else {
-                    "→"
+                    CTCP_SENT
                 };
 
                 let command = query.command.as_ref();
@@ -5009,6 +5183,95 @@ pub mod tests {
         assert_eq!(message.text(), "*** Notice -- [snip]");
     }
 
+    #[test]
+    fn ctcp_messages_are_ctcp_server_messages() {
+        let server = Server::from(ServerName::from("test-server"));
+
+        for irc_message in [
+            ":dan!d@localhost PRIVMSG our_nick :\u{1}VERSION\u{1}\r\n",
+            ":dan!d@localhost NOTICE our_nick :\u{1}VERSION Halloy\u{1}\r\n",
+        ] {
+            let (message, _) =
+                message_with_highlight_from_irc_message(irc_message, &server);
+
+            assert!(matches!(
+                &message.target,
+                Target::Query {
+                    query,
+                    source: Source::Server(Some(source)),
+                } if query.as_str() == "dan"
+                    && source.kind() == message::source::server::Kind::Ctcp
+            ));
+            assert!(message.text().starts_with("← VERSION"));
+            assert!(!message.triggers_unread());
+        }
+    }
+
+    #[test]
+    fn ctcp_messages_from_earlier_versions_are_ctcp_server_messages() {
+        let ctcp: Message = serde_json::from_str(
+            r#"{"received_at": 1771333159986291455, "server_time": "2025-07-20T21:19:11Z", "direction": "Received", "target": {"Query": {"query": {"normalized": "dan", "raw": "dan"}, "source": {"Server": null}}}, "content": {"Plain": "← VERSION Halloy"}, "id": null}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            ctcp.target.source(),
+            Source::Server(Some(source))
+                if source.kind() == message::source::server::Kind::Ctcp
+        ));
+
+        let notice: Message = serde_json::from_str(
+            r#"{"received_at": 1771333159986291455, "server_time": "2025-07-20T21:19:11Z", "direction": "Received", "target": {"Query": {"query": {"normalized": "dan", "raw": "dan"}, "source": {"Server": null}}}, "content": {"Plain": "Not a CTCP message"}, "id": null}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(notice.target.source(), Source::Server(None)));
+    }
+
+    #[test]
+    fn soft_ignored_messages_are_collapsed() {
+        use crate::ignore::{Kind, Mask};
+        use crate::message::Direction;
+
+        let server = Server::from(ServerName::from("test-server"));
+        let casemapping = isupport::CaseMap::default();
+        let dan = User::parse("dan!d@localhost", casemapping, None).unwrap();
+        let soft = [Mask::new("dan", vec![]).with_soft(true)];
+
+        let (mut message, _) = message_with_highlight_from_irc_message(
+            ":dan!d@localhost PRIVMSG #chat :hello\r\n",
+            &server,
+        );
+
+        assert!(message.is_collapsed(soft.iter(), &dan, casemapping));
+        // Hard ignores drop messages rather than collapse them
+        assert!(!message.is_collapsed(
+            [Mask::new("dan", vec![])].iter(),
+            &dan,
+            casemapping
+        ));
+        // Soft ignores only collapse messages, not other kinds
+        assert!(!message.is_collapsed(
+            [Mask::new("dan", vec![Kind::Notices]).with_soft(true)].iter(),
+            &dan,
+            casemapping
+        ));
+        assert!(!message.is_collapsed(
+            [Mask::new("chat", vec![]).with_soft(true)].iter(),
+            &dan,
+            casemapping
+        ));
+
+        message.expanded = true;
+
+        assert!(!message.is_collapsed(soft.iter(), &dan, casemapping));
+
+        message.expanded = false;
+        message.direction = Direction::Sent;
+
+        assert!(!message.is_collapsed(soft.iter(), &dan, casemapping));
+    }
+
     #[test]
     fn wildcard_target_with_user_source_uses_server_buffer() {
         let server = Server {
@@ -5218,6 +5481,7 @@ pub mod tests {
                 BouncerNetwork {
                     id: "BouncerNetid".to_string(),
                     name: "Bouncer Name".to_string(),
+                    ..Default::default()
                 }
                 .into(),
             ),
@@ -105,6 +105,24 @@ pub fn connecting(sent_time: DateTime<Utc>) -> Vec<Message> {
     )
 }
 
+pub fn certificates_not_verified(sent_time: DateTime<Utc>) -> Vec<Message> {
+    let content = plain(
+        "Warning: the server's certificate is not verified \
+         (dangerously_accept_invalid_certs), so this connection can be \
+         intercepted"
+            .into(),
+    );
+    expand(
+        [],
+        [],
+        true,
+        Cause::Status(source::Status::Error),
+        content,
+        sent_time,
+        false,
+    )
+}
+
 pub fn connected(sent_time: DateTime<Utc>) -> Vec<Message> {
     let content = plain("Connected".into());
     expand(
@@ -328,6 +346,7 @@ pub fn kick(
 #[derive(Debug, Clone)]
 pub enum Broadcast {
     Connecting,
+    CertificatesNotVerified,
     Connected,
     ConnectionFailed {
         error: String,
@@ -382,6 +401,9 @@ pub fn into_messages(
 ) -> Vec<Message> {
     match broadcast {
         Broadcast::Connecting => connecting(server_time),
+        Broadcast::CertificatesNotVerified => {
+            certificates_not_verified(server_time)
+        }
         Broadcast::Connected => connected(server_time),
         Broadcast::ConnectionFailed { error } => {
             connection_failed(error, server_time)
@@ -1,5 +1,12 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
 use crate::{Message, User, target};
 
+/// Highlights remembered for de-duplication, dropping the oldest beyond this.
+const MAX_RECENT: usize = 100;
+
 #[derive(Debug, Clone)]
 pub struct Highlight {
     pub kind: Kind,
@@ -16,3 +23,58 @@ pub enum Kind {
         sound: Option<String>,
     },
 }
+
+/// Recent highlights, to recognize the same message relayed into several
+/// channels (e.g. by a bridge).
+#[derive(Debug, Clone, Default)]
+pub struct Recent(VecDeque<(DateTime<Utc>, String, String)>);
+
+impl Recent {
+    /// Whether the same text from the same nickname was highlighted within
+    /// `window` of `server_time`; otherwise remembers it.
+    pub fn is_duplicate(
+        &mut self,
+        nickname: &str,
+        text: &str,
+        server_time: DateTime<Utc>,
+        window: TimeDelta,
+    ) -> bool {
+        self.0
+            .retain(|(seen_at, _, _)| (server_time - *seen_at).abs() <= window);
+
+        if self.0.iter().any(|(_, seen_nickname, seen_text)| {
+            seen_nickname == nickname && seen_text == text
+        }) {
+            return true;
+        }
+
+        self.0
+            .push_back((server_time, nickname.to_string(), text.to_string()));
+
+        if self.0.len() > MAX_RECENT {
+            self.0.pop_front();
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, TimeZone, Utc};
+
+    use super::Recent;
+
+    #[test]
+    fn duplicates_within_window() {
+        let mut recent = Recent::default();
+        let window = TimeDelta::seconds(10);
+        let at = |seconds| Utc.timestamp_opt(seconds, 0).unwrap();
+
+        assert!(!recent.is_duplicate("relay", "hi halloy", at(0), window));
+        assert!(recent.is_duplicate("relay", "hi halloy", at(5), window));
+        assert!(!recent.is_duplicate("other", "hi halloy", at(5), window));
+        assert!(!recent.is_duplicate("relay", "bye halloy", at(5), window));
+        assert!(!recent.is_duplicate("relay", "hi halloy", at(30), window));
+    }
+}
@@ -28,6 +28,9 @@ pub enum Internal {
     Status(Status),
     Logs(log::Level),
     Condensed(DateTime<Utc>),
+    /// Replies to an informational command (e.g. `/whois`), gathered into a
+    /// single collapsible block.
+    Output,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -110,7 +113,8 @@ pub mod server {
                 | Kind::StandardReply(_)
                 | Kind::Away
                 | Kind::Invite
-                | Kind::RequestTopic => false,
+                | Kind::RequestTopic
+                | Kind::Ctcp => false,
             }
         }
     }
@@ -148,6 +152,7 @@ pub mod server {
         Away,
         Invite,
         RequestTopic,
+        Ctcp,
     }
 
     impl Kind {
@@ -168,7 +173,8 @@ pub mod server {
                 | Kind::ChangeMode
                 | Kind::ChangeTopic
                 | Kind::Invite
-                | Kind::RequestTopic => true,
+                | Kind::RequestTopic
+                | Kind::Ctcp => true,
             }
         }
     }
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+
+use crate::User;
+use crate::user::{AccessLevel, ChannelUsers, NickRef};
+
+/// Requests kept per channel, dropping the oldest beyond this.
+const MAX_REQUESTS: usize = 50;
+
+/// How a user asked to be let in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// `KNOCK` on an invite-only channel, granted with an `INVITE`.
+    Knock,
+    /// A message matching one of the configured patterns (e.g. `!voice`),
+    /// granted with `MODE +v`.
+    Message,
+}
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub user: User,
+    pub kind: Kind,
+    pub text: Option<String>,
+    pub received_at: DateTime<Utc>,
+}
+
+/// Users asking for voice (or an invite) in a channel we moderate.
+#[derive(Debug, Clone, Default)]
+pub struct Queue(Vec<Request>);
+
+impl Queue {
+    /// Queues the request, replacing an earlier one from the same user.
+    pub fn push(&mut self, users: &ChannelUsers, request: Request) {
+        self.0.retain(|pending| {
+            pending.user.nickname() != request.user.nickname()
+                && is_pending(pending, users)
+        });
+        self.0.push(request);
+
+        if self.0.len() > MAX_REQUESTS {
+            self.0.remove(0);
+        }
+    }
+
+    pub fn remove(&mut self, nick: NickRef) -> Option<Request> {
+        let index = self
+            .0
+            .iter()
+            .position(|request| request.user.nickname() == nick)?;
+
+        Some(self.0.remove(index))
+    }
+
+    /// Requests still waiting, skipping users who have since been voiced,
+    /// have left, or (for knocks) have joined.
+    pub fn pending<'a>(
+        &'a self,
+        users: &'a ChannelUsers,
+    ) -> impl Iterator<Item = &'a Request> {
+        self.0.iter().filter(|request| is_pending(request, users))
+    }
+}
+
+fn is_pending(request: &Request, users: &ChannelUsers) -> bool {
+    match (request.kind, users.resolve(&request.user)) {
+        (Kind::Knock, user) => user.is_none(),
+        (Kind::Message, Some(user)) => {
+            user.highest_access_level() < AccessLevel::Voice
+        }
+        (Kind::Message, None) => false,
+    }
+}
+
+/// Arguments following the first of `patterns` the text starts with,
+/// compared case-insensitively (e.g. `["#halloy"]` for `!voice #halloy`).
+pub fn parse<'a>(text: &'a str, patterns: &[String]) -> Option<Vec<&'a str>> {
+    let text = text.trim();
+
+    patterns.iter().find_map(|pattern| {
+        let rest = text
+            .get(..pattern.len())
+            .filter(|start| start.eq_ignore_ascii_case(pattern))
+            .map(|_| &text[pattern.len()..])?;
+
+        (rest.is_empty() || rest.starts_with(char::is_whitespace))
+            .then(|| rest.split_whitespace().collect())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parse_patterns() {
+        let patterns = vec!["!voice".to_string(), "!devoiced".to_string()];
+
+        let tests = [
+            ("!voice", Some(vec![])),
+            ("  !VOICE  ", Some(vec![])),
+            ("!voice #halloy", Some(vec!["#halloy"])),
+            ("!voice #halloy please", Some(vec!["#halloy", "please"])),
+            ("!voiced", None),
+            ("please !voice", None),
+            ("!devoiced #a", Some(vec!["#a"])),
+            ("", None),
+        ];
+
+        for (text, expected) in tests {
+            assert_eq!(parse(text, &patterns), expected, "{text:?}");
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::io;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::user::{NickRef, User};
+use crate::{Server, environment};
+
+// Previous nicknames remembered per user.
+const MAX_NICKS: usize = 10;
+// Users remembered per server, least recently seen changing nick are dropped
+// first.
+const MAX_USERS: usize = 2000;
+
+pub static EMPTY: LazyLock<NickHistory> = LazyLock::new(NickHistory::default);
+
+/// Observed nickname changes, keyed by account when known and by
+/// `username@hostname` otherwise.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NickHistory(IndexMap<String, Vec<String>>);
+
+impl NickHistory {
+    /// Records `old_user` changing nickname. Returns `false` if the user could
+    /// not be identified by account or hostmask.
+    pub fn record(&mut self, old_user: &User, new_nick: NickRef) -> bool {
+        let Some(key) = key(old_user.accountname(), userhost(old_user)) else {
+            return false;
+        };
+
+        let old_nick = old_user.as_str().to_string();
+
+        let mut nicks = self.0.shift_remove(&key).unwrap_or_default();
+
+        nicks.retain(|nick| *nick != old_nick);
+        nicks.push(old_nick);
+
+        if nicks.len() > MAX_NICKS {
+            nicks.drain(..nicks.len() - MAX_NICKS);
+        }
+
+        log::trace!("{key} - changed nick to {new_nick}, was: {nicks:?}");
+
+        self.0.insert(key, nicks);
+
+        if self.0.len() > MAX_USERS {
+            self.0.shift_remove_index(0);
+        }
+
+        true
+    }
+
+    /// Previous nicknames of the user, oldest first, excluding `nickname`.
+    pub fn previous<'a>(
+        &'a self,
+        account: Option<&str>,
+        userhost: Option<String>,
+        nickname: NickRef<'_>,
+    ) -> Vec<&'a str> {
+        [key(account, None), key(None, userhost)]
+            .into_iter()
+            .flatten()
+            .find_map(|key| self.0.get(&key))
+            .map(|nicks| {
+                nicks
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|nick| *nick != nickname.as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+pub fn userhost(user: &User) -> Option<String> {
+    user.username()
+        .zip(user.hostname())
+        .map(|(username, hostname)| format!("{username}@{hostname}"))
+}
+
+fn key(account: Option<&str>, userhost: Option<String>) -> Option<String> {
+    account
+        .map(|account| format!("account:{}", account.to_lowercase()))
+        .or_else(|| {
+            userhost.map(|userhost| format!("host:{}", userhost.to_lowercase()))
+        })
+}
+
+async fn path(server: &Server) -> Result<PathBuf, Error> {
+    let dir = environment::data_dir().join("nick-history");
+
+    if !dir.exists() {
+        fs::create_dir_all(&dir).await?;
+    }
+
+    let hashed_server = seahash::hash(format!("{server}").as_bytes());
+
+    Ok(dir.join(format!("{hashed_server}.json")))
+}
+
+pub async fn load(server: &Server) -> Result<NickHistory, Error> {
+    let path = path(server).await?;
+
+    match fs::read(path).await {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            Ok(NickHistory::default())
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+pub async fn save(server: Server, history: NickHistory) -> Result<(), Error> {
+    let path = path(&server).await?;
+
+    fs::write(path, serde_json::to_vec(&history)?).await?;
+
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::isupport::CaseMap;
+    use crate::user::Nick;
+
+    fn user(nickname: &str) -> User {
+        User::from(Nick::from_str(nickname, CaseMap::default()))
+            .with_username_and_hostname(
+                "foo".to_string(),
+                "example.org".to_string(),
+            )
+    }
+
+    #[test]
+    fn previous_nicks() {
+        let mut history = NickHistory::default();
+
+        for (old, new) in [("foo", "foo_"), ("foo_", "foo__"), ("foo__", "bar")]
+        {
+            let new = Nick::from_str(new, CaseMap::default());
+
+            assert!(history.record(&user(old), new.as_nickref()));
+        }
+
+        let bar = Nick::from_str("bar", CaseMap::default());
+
+        assert_eq!(
+            history.previous(
+                None,
+                Some("foo@example.org".to_string()),
+                bar.as_nickref()
+            ),
+            vec!["foo", "foo_", "foo__"]
+        );
+
+        // Unknown users can't be tracked.
+        let unknown = User::from(Nick::from_str("baz", CaseMap::default()));
+
+        assert!(!history.record(&unknown, bar.as_nickref()));
+    }
+}
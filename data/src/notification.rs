@@ -1,6 +1,10 @@
-use crate::target::Channel;
+use std::str::FromStr;
+
+use chrono::Utc;
+
+use crate::target::{Channel, Target};
 use crate::user::Nick;
-use crate::{User, isupport, reaction};
+use crate::{User, file_transfer, isupport, message, reaction};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Notification {
@@ -24,6 +28,8 @@ pub enum Notification {
         nick: Nick,
         casemapping: isupport::CaseMap,
         filename: String,
+        /// The transfer, if it is waiting to be accepted
+        awaiting_approval: Option<file_transfer::Id>,
     },
     MonitoredOnline(Vec<User>),
     MonitoredOffline(Vec<Nick>),
@@ -44,4 +50,152 @@ pub enum Notification {
         casemapping: isupport::CaseMap,
         message: String,
     },
+    UnbanReminder {
+        channel: Channel,
+        mask: String,
+    },
+    Plugin {
+        title: String,
+        body: String,
+    },
+}
+
+/// A notification type which can be sent with `/test-notification`, to check
+/// the notification config without waiting for a real event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Test {
+    Connected,
+    Disconnected,
+    Reconnected,
+    DirectMessage,
+    Highlight,
+    FileTransferRequest,
+    MonitoredOnline,
+    MonitoredOffline,
+    Channel,
+    Reaction,
+    Reply,
+    UnbanReminder,
+}
+
+impl Test {
+    pub const ALL: &[Test] = &[
+        Test::Connected,
+        Test::Disconnected,
+        Test::Reconnected,
+        Test::DirectMessage,
+        Test::Highlight,
+        Test::FileTransferRequest,
+        Test::MonitoredOnline,
+        Test::MonitoredOffline,
+        Test::Channel,
+        Test::Reaction,
+        Test::Reply,
+        Test::UnbanReminder,
+    ];
+
+    /// Name of the notification in the config.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Test::Connected => "connected",
+            Test::Disconnected => "disconnected",
+            Test::Reconnected => "reconnected",
+            Test::DirectMessage => "direct_message",
+            Test::Highlight => "highlight",
+            Test::FileTransferRequest => "file_transfer_request",
+            Test::MonitoredOnline => "monitored_online",
+            Test::MonitoredOffline => "monitored_offline",
+            Test::Channel => "channel",
+            Test::Reaction => "reaction",
+            Test::Reply => "reply",
+            Test::UnbanReminder => "unban_reminder",
+        }
+    }
+
+    /// A sample notification from the test user "halloy", in `channel`.
+    pub fn notification(
+        self,
+        channel: Channel,
+        casemapping: isupport::CaseMap,
+    ) -> Notification {
+        let nick = Nick::from_str("halloy", casemapping);
+        let user = User::from(nick.clone());
+        let message = "This is a test notification".to_string();
+
+        match self {
+            Test::Connected => Notification::Connected,
+            Test::Disconnected => Notification::Disconnected,
+            Test::Reconnected => Notification::Reconnected,
+            Test::DirectMessage => Notification::DirectMessage {
+                user,
+                casemapping,
+                message,
+            },
+            Test::Highlight => Notification::Highlight {
+                user,
+                channel,
+                casemapping,
+                message,
+                description: "highlighted you".to_string(),
+                sound: None,
+            },
+            Test::FileTransferRequest => Notification::FileTransferRequest {
+                nick,
+                casemapping,
+                filename: "test.txt".to_string(),
+                awaiting_approval: None,
+            },
+            Test::MonitoredOnline => Notification::MonitoredOnline(vec![user]),
+            Test::MonitoredOffline => {
+                Notification::MonitoredOffline(vec![nick])
+            }
+            Test::Channel => Notification::Channel {
+                user,
+                channel,
+                casemapping,
+                message,
+            },
+            Test::Reaction => Notification::Reaction {
+                casemapping,
+                reaction: reaction::Context {
+                    inner: reaction::Reaction {
+                        sender: nick,
+                        text: "👍".to_string(),
+                        unreact: false,
+                        id: None,
+                        server_time: Utc::now(),
+                    },
+                    target: Target::Channel(channel),
+                    in_reply_to: message::Id::from("test-notification"),
+                    is_echo: false,
+                    deduplicate: false,
+                },
+                message_text: message,
+            },
+            Test::Reply => Notification::Reply {
+                user,
+                channel,
+                casemapping,
+                message,
+            },
+            Test::UnbanReminder => Notification::UnbanReminder {
+                channel,
+                mask: "*!*@example.com".to_string(),
+            },
+        }
+    }
+}
+
+impl FromStr for Test {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase().replace('-', "_");
+
+        Test::ALL
+            .iter()
+            .find(|test| test.as_str() == s)
+            .copied()
+            .ok_or(())
+    }
 }
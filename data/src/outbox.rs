@@ -0,0 +1,214 @@
+//! Outgoing messages that never reached the server, either because they were
+//! held back by flood control or because the server was disconnected.
+//! They're persisted so they can be offered for resending after a reconnect
+//! or a restart.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use irc::proto;
+use serde::{Deserialize, Serialize};
+
+use crate::{Server, command, environment, message};
+
+pub type Outbox = BTreeMap<Server, Vec<Message>>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Message {
+    pub target: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub notice: bool,
+    pub queued_at: DateTime<Utc>,
+}
+
+impl Message {
+    /// Only user messages are kept; commands are not worth replaying.
+    pub fn from_encoded(encoded: &message::Encoded) -> Option<Self> {
+        let (target, text, notice) = match &encoded.command {
+            proto::Command::PRIVMSG(target, text) => (target, text, false),
+            proto::Command::NOTICE(target, text) => (target, text, true),
+            _ => return None,
+        };
+
+        Some(Self {
+            target: target.clone(),
+            text: text.clone(),
+            notice,
+            queued_at: encoded.server_time_or_now().0,
+        })
+    }
+
+    /// Whether this is the message sent by `command`.
+    pub fn is_command(&self, command: &command::Irc) -> bool {
+        match command {
+            command::Irc::Msg(target, text) => {
+                !self.notice && self.target == *target && self.text == *text
+            }
+            command::Irc::Notice(target, text) => {
+                self.notice && self.target == *target && self.text == *text
+            }
+            _ => false,
+        }
+    }
+
+    pub fn encoded(&self) -> message::Encoded {
+        let command = if self.notice {
+            proto::Command::NOTICE(self.target.clone(), self.text.clone())
+        } else {
+            proto::Command::PRIVMSG(self.target.clone(), self.text.clone())
+        };
+
+        proto::Message::from(command).into()
+    }
+}
+
+fn path() -> PathBuf {
+    environment::data_dir().join("outbox.json")
+}
+
+pub async fn save(outbox: Outbox) {
+    if outbox.is_empty() {
+        let _ = tokio::fs::remove_file(path()).await;
+        return;
+    }
+
+    match serialize(outbox) {
+        Ok(bytes) => {
+            if let Err(e) = tokio::fs::write(path(), bytes).await {
+                log::warn!("failed to save outbox: {e}");
+            }
+        }
+        Err(e) => log::warn!("failed to serialize outbox: {e}"),
+    }
+}
+
+pub fn load_sync() -> Outbox {
+    let Ok(bytes) = std::fs::read(path()) else {
+        return Outbox::new();
+    };
+
+    deserialize(&bytes)
+}
+
+fn serialize(outbox: Outbox) -> serde_json::Result<Vec<u8>> {
+    let pairs: Vec<(Server, Vec<Message>)> = outbox.into_iter().collect();
+
+    serde_json::to_vec(&pairs)
+}
+
+fn deserialize(bytes: &[u8]) -> Outbox {
+    serde_json::from_slice::<Vec<(Server, Vec<Message>)>>(bytes)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, messages)| !messages.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::server::ServerName;
+
+    fn message(target: &str, text: &str, notice: bool) -> Message {
+        Message {
+            target: target.to_string(),
+            text: text.to_string(),
+            notice,
+            queued_at: Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let outbox = Outbox::from([
+            (
+                Server::from(ServerName::from("libera")),
+                vec![
+                    message("#halloy", "hello", false),
+                    message("#halloy", "world", true),
+                ],
+            ),
+            (
+                Server::from(ServerName::from("oftc")),
+                vec![message("casper", "hi", false)],
+            ),
+        ]);
+
+        let bytes = serialize(outbox.clone()).unwrap();
+
+        assert_eq!(deserialize(&bytes), outbox);
+    }
+
+    #[test]
+    fn drops_servers_without_messages() {
+        let outbox = Outbox::from([
+            (Server::from(ServerName::from("libera")), vec![]),
+            (
+                Server::from(ServerName::from("oftc")),
+                vec![message("casper", "hi", false)],
+            ),
+        ]);
+
+        let bytes = serialize(outbox).unwrap();
+
+        assert_eq!(
+            deserialize(&bytes),
+            Outbox::from([(
+                Server::from(ServerName::from("oftc")),
+                vec![message("casper", "hi", false)],
+            )])
+        );
+    }
+
+    #[test]
+    fn invalid_file_is_empty() {
+        assert!(deserialize(b"not json").is_empty());
+    }
+
+    #[test]
+    fn only_user_messages_are_kept() {
+        let privmsg: message::Encoded = proto::Message::from(
+            proto::Command::PRIVMSG("#halloy".into(), "hello".into()),
+        )
+        .into();
+        let notice: message::Encoded = proto::Message::from(
+            proto::Command::NOTICE("#halloy".into(), "hello".into()),
+        )
+        .into();
+        let join: message::Encoded =
+            proto::Message::from(proto::Command::JOIN("#halloy".into(), None))
+                .into();
+
+        let privmsg_message = Message::from_encoded(&privmsg).unwrap();
+        let notice_message = Message::from_encoded(&notice).unwrap();
+
+        assert!(!privmsg_message.notice);
+        assert!(notice_message.notice);
+        assert!(Message::from_encoded(&join).is_none());
+
+        assert_eq!(privmsg_message.encoded().command, privmsg.command);
+        assert_eq!(notice_message.encoded().command, notice.command);
+    }
+
+    #[test]
+    fn matches_the_sending_command() {
+        let privmsg = message("#halloy", "hello", false);
+        let notice = message("#halloy", "hello", true);
+        let msg = command::Irc::Msg("#halloy".into(), "hello".into());
+        let notice_command =
+            command::Irc::Notice("#halloy".into(), "hello".into());
+
+        assert!(privmsg.is_command(&msg));
+        assert!(!privmsg.is_command(&notice_command));
+        assert!(notice.is_command(&notice_command));
+        assert!(!notice.is_command(&msg));
+        assert!(
+            !privmsg
+                .is_command(&command::Irc::Msg("#halloy".into(), "bye".into()))
+        );
+    }
+}
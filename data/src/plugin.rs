@@ -0,0 +1,678 @@
+//! Lua plugins, loaded from the `plugins` folder of the config directory.
+//!
+//! Scripts register hooks through the global `halloy` table and act on the
+//! client by queueing [`Action`]s, which the application applies.
+//!
+//! Hooks run on the UI thread, so scripts only get the standard libraries
+//! which can't touch the file system or processes, and are aborted once they
+//! run for too many instructions.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use mlua::{
+    FromLuaMulti, Function, HookTriggers, IntoLuaMulti, Lua, LuaOptions,
+    StdLib, Table, Value, VmState,
+};
+
+use crate::{Config, Server, config};
+
+/// Version of the `halloy` table, bumped on incompatible changes.
+pub const API_VERSION: u32 = 1;
+
+/// Timers fire at most this often.
+const MIN_TIMER_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Instructions run between checks of a script's budget.
+const INSTRUCTIONS_PER_CHECK: u32 = 10_000;
+/// Checks a script may pass while loading or handling a single event (10
+/// million instructions) before it's aborted.
+const MAX_CHECKS: u32 = 1_000;
+
+static PLUGINS: LazyLock<Mutex<Vec<Plugin>>> = LazyLock::new(Mutex::default);
+static ACTIONS: Mutex<Vec<Action>> = Mutex::new(Vec::new());
+
+/// Something a plugin asked the client to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Send {
+        server: String,
+        target: String,
+        text: String,
+    },
+    /// Show `text` in a buffer, without sending it. The server buffer is
+    /// used when no target is given.
+    Print {
+        server: String,
+        target: Option<String>,
+        text: String,
+    },
+    Notify {
+        server: String,
+        title: String,
+        body: String,
+    },
+}
+
+/// Outgoing message after running the `on_send` hooks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outgoing {
+    Unchanged,
+    Replaced(String),
+    Cancelled,
+}
+
+struct Plugin {
+    name: String,
+    lua: Lua,
+    hooks: Arc<Mutex<Hooks>>,
+    /// Budget checks passed since the script was last called.
+    checks: Arc<AtomicU32>,
+}
+
+#[derive(Default)]
+struct Hooks {
+    message: Vec<Function>,
+    send: Vec<Function>,
+    commands: HashMap<String, Function>,
+    /// CTCP queries, by uppercase command.
+    ctcp: HashMap<String, Function>,
+    timers: Vec<Timer>,
+}
+
+struct Timer {
+    interval: Duration,
+    next: Instant,
+    callback: Function,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// (Re)loads every `*.lua` script in the plugins folder.
+pub fn load(config: &config::Plugins) {
+    let mut plugins = lock(&PLUGINS);
+
+    plugins.clear();
+
+    if !config.enabled {
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(Config::plugins_dir()) else {
+        return;
+    };
+
+    let mut paths = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "lua"))
+        .collect::<Vec<_>>();
+
+    paths.sort();
+
+    for path in paths {
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        if config.is_excluded(name) {
+            continue;
+        }
+
+        match Plugin::load(name, &path) {
+            Ok(plugin) => {
+                log::info!("[plugin {name}] loaded");
+                plugins.push(plugin);
+            }
+            Err(error) => log::warn!("[plugin {name}] failed to load: {error}"),
+        }
+    }
+}
+
+/// Actions queued by plugins since the last call.
+pub fn take_actions() -> Vec<Action> {
+    std::mem::take(&mut *lock(&ACTIONS))
+}
+
+pub fn incoming(server: &Server, target: &str, nick: &str, text: &str) {
+    let server = server.to_string();
+
+    for plugin in lock(&PLUGINS).iter() {
+        plugin.incoming(&server, target, nick, text);
+    }
+}
+
+pub fn outgoing(server: &Server, target: &str, text: &str) -> Outgoing {
+    let server = server.to_string();
+    let mut outgoing = Outgoing::Unchanged;
+
+    for plugin in lock(&PLUGINS).iter() {
+        let current = match &outgoing {
+            Outgoing::Replaced(text) => text.as_str(),
+            _ => text,
+        };
+
+        match plugin.outgoing(&server, target, current) {
+            Outgoing::Unchanged => {}
+            Outgoing::Replaced(text) => outgoing = Outgoing::Replaced(text),
+            Outgoing::Cancelled => return Outgoing::Cancelled,
+        }
+    }
+
+    outgoing
+}
+
+pub fn has_command(name: &str) -> bool {
+    lock(&PLUGINS)
+        .iter()
+        .any(|plugin| lock(&plugin.hooks).commands.contains_key(name))
+}
+
+/// Runs the plugin command `name`, returning `false` if no plugin
+/// registered it.
+pub fn command(
+    server: &Server,
+    target: Option<&str>,
+    name: &str,
+    args: &str,
+) -> bool {
+    let server = server.to_string();
+
+    lock(&PLUGINS)
+        .iter()
+        .any(|plugin| plugin.command(&server, target, name, args))
+}
+
+/// CTCP commands plugins respond to, for `CLIENTINFO`.
+pub fn ctcp_commands() -> Vec<String> {
+    let mut commands = lock(&PLUGINS)
+        .iter()
+        .flat_map(|plugin| {
+            lock(&plugin.hooks).ctcp.keys().cloned().collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    commands.sort();
+    commands.dedup();
+
+    commands
+}
+
+/// Runs the first plugin handler for the CTCP `command` sent by `nick`,
+/// returning its response, if any.
+pub fn ctcp(
+    server: &Server,
+    nick: &str,
+    command: &str,
+    params: Option<&str>,
+) -> Option<String> {
+    let server = server.to_string();
+    let command = command.to_uppercase();
+
+    lock(&PLUGINS)
+        .iter()
+        .find_map(|plugin| plugin.ctcp(&server, nick, &command, params))
+        .flatten()
+}
+
+/// Fires timers which are due.
+pub fn tick(now: Instant) {
+    for plugin in lock(&PLUGINS).iter() {
+        plugin.tick(now);
+    }
+}
+
+impl Plugin {
+    fn load(name: &str, path: &Path) -> Result<Self, Error> {
+        let source = std::fs::read_to_string(path)?;
+
+        Self::from_source(name, &source)
+    }
+
+    fn from_source(name: &str, source: &str) -> Result<Self, Error> {
+        let lua = Lua::new_with(
+            StdLib::COROUTINE
+                | StdLib::TABLE
+                | StdLib::STRING
+                | StdLib::UTF8
+                | StdLib::MATH,
+            LuaOptions::default(),
+        )?;
+        let hooks = Arc::new(Mutex::new(Hooks::default()));
+        let checks = Arc::new(AtomicU32::new(0));
+
+        // The base library is always loaded, but shouldn't read files
+        lua.globals().set("dofile", Value::Nil)?;
+        lua.globals().set("loadfile", Value::Nil)?;
+
+        lua.set_hook(
+            HookTriggers::new().every_nth_instruction(INSTRUCTIONS_PER_CHECK),
+            {
+                let checks = checks.clone();
+
+                move |_, _| {
+                    if checks.fetch_add(1, Ordering::Relaxed) < MAX_CHECKS {
+                        Ok(VmState::Continue)
+                    } else {
+                        Err(mlua::Error::runtime("instruction limit exceeded"))
+                    }
+                }
+            },
+        )?;
+
+        lua.globals().set("halloy", api(&lua, name, &hooks)?)?;
+        lua.load(source).set_name(format!("={name}")).exec()?;
+
+        Ok(Self {
+            name: name.to_string(),
+            lua,
+            hooks,
+            checks,
+        })
+    }
+
+    /// Calls a script function with a fresh instruction budget.
+    fn call<R: FromLuaMulti>(
+        &self,
+        callback: &Function,
+        args: impl IntoLuaMulti,
+    ) -> mlua::Result<R> {
+        self.checks.store(0, Ordering::Relaxed);
+
+        callback.call(args)
+    }
+
+    fn report<T>(&self, result: mlua::Result<T>) -> Option<T> {
+        result
+            .inspect_err(|error| log::warn!("[plugin {}] {error}", self.name))
+            .ok()
+    }
+
+    fn event(&self, fields: &[(&str, Option<&str>)]) -> mlua::Result<Table> {
+        let event = self.lua.create_table()?;
+
+        for (key, value) in fields {
+            event.set(*key, *value)?;
+        }
+
+        Ok(event)
+    }
+
+    fn incoming(&self, server: &str, target: &str, nick: &str, text: &str) {
+        // Hooks may register further hooks, so don't hold the lock
+        let callbacks = lock(&self.hooks).message.clone();
+
+        for callback in callbacks {
+            self.report(
+                self.event(&[
+                    ("server", Some(server)),
+                    ("target", Some(target)),
+                    ("nick", Some(nick)),
+                    ("text", Some(text)),
+                ])
+                .and_then(|event| self.call::<()>(&callback, event)),
+            );
+        }
+    }
+
+    fn outgoing(&self, server: &str, target: &str, text: &str) -> Outgoing {
+        let callbacks = lock(&self.hooks).send.clone();
+        let mut outgoing = Outgoing::Unchanged;
+
+        for callback in callbacks {
+            let current = match &outgoing {
+                Outgoing::Replaced(text) => text.as_str(),
+                _ => text,
+            };
+
+            match self.report(
+                self.event(&[
+                    ("server", Some(server)),
+                    ("target", Some(target)),
+                    ("text", Some(current)),
+                ])
+                .and_then(|event| self.call::<Value>(&callback, event)),
+            ) {
+                Some(Value::Boolean(false)) => return Outgoing::Cancelled,
+                Some(Value::String(text)) => {
+                    outgoing = Outgoing::Replaced(text.to_string_lossy());
+                }
+                _ => {}
+            }
+        }
+
+        outgoing
+    }
+
+    fn command(
+        &self,
+        server: &str,
+        target: Option<&str>,
+        name: &str,
+        args: &str,
+    ) -> bool {
+        let Some(callback) = lock(&self.hooks).commands.get(name).cloned()
+        else {
+            return false;
+        };
+
+        self.report(
+            self.event(&[
+                ("server", Some(server)),
+                ("target", target),
+                ("args", Some(args)),
+            ])
+            .and_then(|event| self.call::<()>(&callback, event)),
+        );
+
+        true
+    }
+
+    /// `None` if the plugin doesn't handle `command`, otherwise its
+    /// response, if any.
+    fn ctcp(
+        &self,
+        server: &str,
+        nick: &str,
+        command: &str,
+        params: Option<&str>,
+    ) -> Option<Option<String>> {
+        let callback = lock(&self.hooks).ctcp.get(command).cloned()?;
+
+        let response = self.report(
+            self.event(&[
+                ("server", Some(server)),
+                ("nick", Some(nick)),
+                ("args", params),
+            ])
+            .and_then(|event| self.call::<Value>(&callback, event)),
+        );
+
+        Some(match response {
+            Some(Value::String(response)) => Some(response.to_string_lossy()),
+            _ => None,
+        })
+    }
+
+    fn tick(&self, now: Instant) {
+        let due = lock(&self.hooks)
+            .timers
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, timer)| timer.next <= now)
+            .map(|(index, timer)| {
+                timer.next = now + timer.interval;
+                (index, timer.callback.clone())
+            })
+            .collect::<Vec<_>>();
+
+        let mut stopped = vec![];
+
+        for (index, callback) in due {
+            // Returning `false` stops the timer
+            if let Some(Value::Boolean(false)) =
+                self.report(self.call::<Value>(&callback, ()))
+            {
+                stopped.push(index);
+            }
+        }
+
+        // Timers are only ever appended, so indices are still valid
+        let mut hooks = lock(&self.hooks);
+
+        for index in stopped.into_iter().rev() {
+            hooks.timers.remove(index);
+        }
+    }
+}
+
+/// The `halloy` table exposed to scripts.
+fn api(
+    lua: &Lua,
+    name: &str,
+    hooks: &Arc<Mutex<Hooks>>,
+) -> mlua::Result<Table> {
+    let halloy = lua.create_table()?;
+
+    halloy.set("api_version", API_VERSION)?;
+
+    halloy.set(
+        "send",
+        lua.create_function(
+            |_, (server, target, text): (String, String, String)| {
+                lock(&ACTIONS).push(Action::Send {
+                    server,
+                    target,
+                    text,
+                });
+                Ok(())
+            },
+        )?,
+    )?;
+
+    halloy.set(
+        "print",
+        lua.create_function(
+            |_, (server, target, text): (String, Option<String>, String)| {
+                lock(&ACTIONS).push(Action::Print {
+                    server,
+                    target,
+                    text,
+                });
+                Ok(())
+            },
+        )?,
+    )?;
+
+    halloy.set(
+        "notify",
+        lua.create_function(
+            |_, (server, title, body): (String, String, String)| {
+                lock(&ACTIONS).push(Action::Notify {
+                    server,
+                    title,
+                    body,
+                });
+                Ok(())
+            },
+        )?,
+    )?;
+
+    let plugin = name.to_string();
+    halloy.set(
+        "log",
+        lua.create_function(move |_, text: String| {
+            log::info!("[plugin {plugin}] {text}");
+            Ok(())
+        })?,
+    )?;
+
+    let message_hooks = hooks.clone();
+    halloy.set(
+        "on_message",
+        lua.create_function(move |_, callback: Function| {
+            lock(&message_hooks).message.push(callback);
+            Ok(())
+        })?,
+    )?;
+
+    let send_hooks = hooks.clone();
+    halloy.set(
+        "on_send",
+        lua.create_function(move |_, callback: Function| {
+            lock(&send_hooks).send.push(callback);
+            Ok(())
+        })?,
+    )?;
+
+    let command_hooks = hooks.clone();
+    halloy.set(
+        "command",
+        lua.create_function(move |_, (name, callback): (String, Function)| {
+            lock(&command_hooks)
+                .commands
+                .insert(name.to_lowercase(), callback);
+            Ok(())
+        })?,
+    )?;
+
+    let ctcp_hooks = hooks.clone();
+    halloy.set(
+        "ctcp",
+        lua.create_function(
+            move |_, (command, callback): (String, Function)| {
+                lock(&ctcp_hooks)
+                    .ctcp
+                    .insert(command.to_uppercase(), callback);
+                Ok(())
+            },
+        )?,
+    )?;
+
+    let timer_hooks = hooks.clone();
+    halloy.set(
+        "timer",
+        lua.create_function(move |_, (seconds, callback): (f64, Function)| {
+            let interval = Duration::try_from_secs_f64(seconds)
+                .map_err(mlua::Error::external)?
+                .max(MIN_TIMER_INTERVAL);
+
+            lock(&timer_hooks).timers.push(Timer {
+                interval,
+                next: Instant::now() + interval,
+                callback,
+            });
+            Ok(())
+        })?,
+    )?;
+
+    Ok(halloy)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Lua(#[from] mlua::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{Action, Outgoing, Plugin, lock, take_actions};
+
+    const SOURCE: &str = r#"
+        halloy.on_send(function(event)
+            if event.text == "secret" then
+                return false
+            end
+
+            return event.text:gsub(":shrug:", "¯\\_(ツ)_/¯")
+        end)
+
+        halloy.command("greet", function(event)
+            halloy.send(event.server, event.target, "hello " .. event.args)
+        end)
+
+        halloy.ctcp("finger", function(event)
+            return "no fingering " .. event.nick
+        end)
+
+        local ticks = 0
+        halloy.timer(60, function()
+            ticks = ticks + 1
+            halloy.print("libera", nil, "tick " .. ticks)
+            return ticks < 2
+        end)
+    "#;
+
+    #[test]
+    fn hooks() {
+        let plugin = Plugin::from_source("test", SOURCE).unwrap();
+
+        assert_eq!(
+            plugin.outgoing("libera", "#halloy", "secret"),
+            Outgoing::Cancelled
+        );
+        assert_eq!(
+            plugin.outgoing("libera", "#halloy", "well :shrug:"),
+            Outgoing::Replaced("well ¯\\_(ツ)_/¯".to_string())
+        );
+
+        assert!(plugin.command("libera", Some("#halloy"), "greet", "all"));
+        assert!(!plugin.command("libera", Some("#halloy"), "unknown", ""));
+
+        assert_eq!(
+            plugin.ctcp("libera", "casper", "FINGER", None),
+            Some(Some("no fingering casper".to_string()))
+        );
+        assert_eq!(plugin.ctcp("libera", "casper", "PING", None), None);
+
+        let later = Instant::now() + Duration::from_secs(61);
+        plugin.tick(later);
+        plugin.tick(later + Duration::from_secs(61));
+        plugin.tick(later + Duration::from_secs(122));
+
+        assert!(lock(&plugin.hooks).timers.is_empty());
+        assert_eq!(
+            take_actions(),
+            vec![
+                Action::Send {
+                    server: "libera".to_string(),
+                    target: "#halloy".to_string(),
+                    text: "hello all".to_string(),
+                },
+                Action::Print {
+                    server: "libera".to_string(),
+                    target: None,
+                    text: "tick 1".to_string(),
+                },
+                Action::Print {
+                    server: "libera".to_string(),
+                    target: None,
+                    text: "tick 2".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn syntax_error() {
+        assert!(Plugin::from_source("broken", "halloy.on_send(").is_err());
+    }
+
+    #[test]
+    fn sandboxed() {
+        let plugin = Plugin::from_source(
+            "sandboxed",
+            "halloy.on_send(function() while true do end end)",
+        )
+        .unwrap();
+
+        assert!(
+            plugin
+                .lua
+                .load(
+                    "return io == nil and os == nil and require == nil \
+                     and dofile == nil and loadfile == nil"
+                )
+                .eval::<bool>()
+                .unwrap()
+        );
+
+        // Runaway scripts are aborted, rather than hanging the client
+        assert_eq!(
+            plugin.outgoing("libera", "#halloy", "hello"),
+            Outgoing::Unchanged
+        );
+        assert!(Plugin::from_source("runaway", "while true do end").is_err());
+    }
+}
@@ -17,6 +17,7 @@ use tokio::time;
 use url::Url;
 
 pub use self::card::Card;
+pub use self::title::Title;
 use crate::cache::{self, Asset, CacheState, CachedAsset, FileCache};
 use crate::config::preview::{Enabled, Visibility};
 use crate::image::Image;
@@ -26,12 +27,17 @@ use crate::target::{self, TargetRef};
 use crate::{config, image, isupport};
 
 pub mod card;
+pub mod title;
 
 // Prevent us from rate limiting ourselves
 static RATE_LIMIT: OnceLock<Semaphore> = OnceLock::new();
 static META_TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r#"(?is)<meta\b[^>]*?>"#).expect("valid meta tag regex")
 });
+static TITLE_TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)<title\b[^>]*>(.*?)</title>"#)
+        .expect("valid title tag regex")
+});
 static META_ATTR_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
         r#"(?is)\b([a-zA-Z_:][-a-zA-Z0-9_:.]*)\s*=\s*("[^"]*"|'[^']*'|[^\s>]+)"#,
@@ -44,6 +50,7 @@ pub struct Previews<'a> {
     collection: &'a Collection,
     cards_are_visible: Visibility,
     images_are_visible: Visibility,
+    titles_are_visible: Visibility,
 }
 
 impl<'a> Previews<'a> {
@@ -66,6 +73,11 @@ impl<'a> Previews<'a> {
                 server,
                 casemapping,
             ),
+            titles_are_visible: config.title.visible(
+                target_ref,
+                server,
+                casemapping,
+            ),
         }
     }
 
@@ -76,6 +88,7 @@ impl<'a> Previews<'a> {
                 let visibility = match preview {
                     Preview::Card(_) => self.cards_are_visible,
                     Preview::Image(_) => self.images_are_visible,
+                    Preview::Title(_) => self.titles_are_visible,
                 };
 
                 matches!(visibility, Visibility::All | Visibility::BySource)
@@ -87,14 +100,6 @@ impl<'a> Previews<'a> {
     pub fn collection(&self) -> &'a Collection {
         self.collection
     }
-
-    pub fn cards_visibility(&self) -> Visibility {
-        self.cards_are_visible
-    }
-
-    pub fn images_visibility(&self) -> Visibility {
-        self.images_are_visible
-    }
 }
 
 pub type Collection = HashMap<Url, State>;
@@ -104,13 +109,15 @@ pub type Collection = HashMap<Url, State>;
 pub enum Preview {
     Card(Card),
     Image(Image),
+    Title(Title),
 }
 
 impl Preview {
-    pub fn image(&self) -> &Image {
+    pub fn image(&self) -> Option<&Image> {
         match self {
-            Self::Card(card) => &card.image,
-            Self::Image(image) => image,
+            Self::Card(card) => Some(&card.image),
+            Self::Image(image) => Some(image),
+            Self::Title(_) => None,
         }
     }
 
@@ -120,12 +127,11 @@ impl Preview {
         channel: Option<&target::Channel>,
         server: Option<&Server>,
         casemapping: isupport::CaseMap,
-        cards_visibility: Visibility,
-        images_visibility: Visibility,
+        previews: Previews<'_>,
         config: &config::Preview,
     ) -> bool {
         match self {
-            Self::Card(_) => match cards_visibility {
+            Self::Card(_) => match previews.cards_are_visible {
                 Visibility::All => true,
                 Visibility::BySource => config.card.visible_for_source(
                     source,
@@ -135,7 +141,7 @@ impl Preview {
                 ),
                 Visibility::None => false,
             },
-            Self::Image(_) => match images_visibility {
+            Self::Image(_) => match previews.images_are_visible {
                 Visibility::All => true,
                 Visibility::BySource => config.image.visible_for_source(
                     source,
@@ -145,6 +151,16 @@ impl Preview {
                 ),
                 Visibility::None => false,
             },
+            Self::Title(_) => match previews.titles_are_visible {
+                Visibility::All => true,
+                Visibility::BySource => config.title.visible_for_source(
+                    source,
+                    channel,
+                    server,
+                    casemapping,
+                ),
+                Visibility::None => false,
+            },
         }
     }
 }
@@ -156,6 +172,7 @@ impl CachedAsset for Preview {
                 vec![Asset(c.image.path.as_path(), &c.image.digest)]
             }
             Preview::Image(i) => vec![Asset(i.path.as_path(), &i.digest)],
+            Preview::Title(_) => vec![],
         }
     }
 }
@@ -170,6 +187,8 @@ pub enum State {
 #[derive(Debug, Clone, Copy)]
 enum Kind {
     Preview,
+    /// Only the title of a page, without fetching any images
+    Title,
     Avatar,
 }
 
@@ -180,7 +199,13 @@ pub async fn load(
     cache: Arc<FileCache>,
 ) -> Result<Preview, LoadError> {
     let is_enabled = config.is_enabled(url.as_str());
-    load_inner(url, client, &config, cache, is_enabled, Kind::Preview).await
+    let kind = if config.is_media_enabled(url.as_str()) {
+        Kind::Preview
+    } else {
+        Kind::Title
+    };
+
+    load_inner(url, client, &config, cache, is_enabled, kind).await
 }
 
 pub async fn load_avatar(
@@ -223,8 +248,9 @@ async fn load_inner(
         }
     } else {
         let loaded = match kind {
-            Kind::Preview => {
-                load_uncached(url.clone(), client, preview_config, &cache).await
+            Kind::Preview | Kind::Title => {
+                load_uncached(url.clone(), client, preview_config, &cache, kind)
+                    .await
             }
             Kind::Avatar => {
                 load_avatar_uncached(
@@ -253,9 +279,7 @@ async fn load_inner(
         }
     };
 
-    if let Ok(ref preview) = result {
-        let image = preview.image();
-
+    if let Some(image) = result.as_ref().ok().and_then(Preview::image) {
         if matches!(image.format, image::Format::Svg) {
             result
         } else if let Ok((image_width, image_height)) =
@@ -290,6 +314,20 @@ async fn load_inner(
     }
 }
 
+/// Whether the URL points at a video file, judging by its extension.
+pub fn is_video(url: &Url) -> bool {
+    const EXTENSIONS: &[&str] = &["mp4", "m4v", "mov", "webm", "mkv", "ogv"];
+
+    url.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|file_name| file_name.rsplit_once('.'))
+        .is_some_and(|(_, extension)| {
+            EXTENSIONS
+                .iter()
+                .any(|video| extension.eq_ignore_ascii_case(video))
+        })
+}
+
 fn canonical_preview_url(url: &Url) -> Url {
     let mut canonical = url.clone();
     canonical.set_fragment(None);
@@ -301,56 +339,89 @@ async fn load_uncached(
     client: Arc<reqwest::Client>,
     config: &config::Preview,
     cache: &FileCache,
+    kind: Kind,
 ) -> Result<Preview, LoadError> {
     log::trace!("Loading preview for {url}");
 
-    match fetch(url.clone(), client.clone(), config, cache).await? {
+    match fetch(url.clone(), client.clone(), config, cache, kind).await? {
         Fetched::Image(image) => Ok(Preview::Image(image)),
         Fetched::Other(bytes) => {
-            let MetaTagProperties {
-                canonical_url,
-                image_url,
-                title,
-                description,
-            } = parse_meta_tag_properties(&bytes)?;
-
-            let image_url =
-                image_url.ok_or(LoadError::MissingProperty("image"))?;
-
-            let Fetched::Image(image) =
-                fetch(image_url, client, config, cache).await?
-            else {
-                return Err(LoadError::NotImage);
-            };
-
-            let description = match &config.card.description_decode_html {
-                Enabled::Boolean(true) => description
-                    .map(|description| decode_html_string(&description)),
-                Enabled::Regex(regexes) => {
-                    if regexes.iter().any(|regex| {
-                        regex.is_match(url.as_str()).unwrap_or(false)
-                    }) {
-                        description
-                            .map(|description| decode_html_string(&description))
-                    } else {
-                        description
-                    }
-                }
-                _ => description,
-            };
+            let meta = parse_meta_tag_properties(&bytes)?;
+
+            // Pages without a card still get a title line, when enabled
+            let title = meta
+                .title
+                .clone()
+                .or_else(|| parse_title_tag(&bytes))
+                .filter(|_| config.title.is_enabled(url.as_str()))
+                .map(|title| Title {
+                    url: url.clone(),
+                    title,
+                });
+
+            if matches!(kind, Kind::Title) {
+                return title
+                    .map(Preview::Title)
+                    .ok_or(LoadError::MissingProperty("title"));
+            }
 
-            Ok(Preview::Card(Card {
-                url: url.clone(),
-                canonical_url: canonical_url
-                    .ok_or(LoadError::MissingProperty("url"))?,
-                image,
-                title: title.ok_or(LoadError::MissingProperty("title"))?,
-                description,
-            }))
+            match load_card(url, meta, client, config, cache).await {
+                Ok(card) => Ok(Preview::Card(card)),
+                Err(error) => title.map(Preview::Title).ok_or(error),
+            }
         }
     }
 }
 
+async fn load_card(
+    url: Url,
+    meta: MetaTagProperties,
+    client: Arc<reqwest::Client>,
+    config: &config::Preview,
+    cache: &FileCache,
+) -> Result<Card, LoadError> {
+    let MetaTagProperties {
+        canonical_url,
+        image_url,
+        title,
+        description,
+    } = meta;
+
+    let image_url = image_url.ok_or(LoadError::MissingProperty("image"))?;
+
+    let Fetched::Image(image) =
+        fetch(image_url, client, config, cache, Kind::Preview).await?
+    else {
+        return Err(LoadError::NotImage);
+    };
+
+    let description = match &config.card.description_decode_html {
+        Enabled::Boolean(true) => {
+            description.map(|description| decode_html_string(&description))
+        }
+        Enabled::Regex(regexes) => {
+            if regexes
+                .iter()
+                .any(|regex| regex.is_match(url.as_str()).unwrap_or(false))
+            {
+                description.map(|description| decode_html_string(&description))
+            } else {
+                description
+            }
+        }
+        _ => description,
+    };
+
+    Ok(Card {
+        url,
+        canonical_url: canonical_url
+            .ok_or(LoadError::MissingProperty("url"))?,
+        image,
+        title: title.ok_or(LoadError::MissingProperty("title"))?,
+        description,
+    })
+}
+
 async fn load_avatar_uncached(
     url: Url,
     client: Arc<reqwest::Client>,
@@ -359,7 +430,9 @@ async fn load_avatar_uncached(
 ) -> Result<Preview, LoadError> {
     log::trace!("Loading avatar for {url}");
 
-    let Fetched::Image(image) = fetch(url, client, config, cache).await? else {
+    let Fetched::Image(image) =
+        fetch(url, client, config, cache, Kind::Avatar).await?
+    else {
         return Err(LoadError::NotImage);
     };
 
@@ -376,7 +449,22 @@ async fn fetch(
     client: Arc<reqwest::Client>,
     config: &config::Preview,
     cache: &FileCache,
+    kind: Kind,
 ) -> Result<Fetched, LoadError> {
+    // The allowlist only applies to previews, not avatars
+    let is_allowed = |url: &Url| match kind {
+        Kind::Preview => config.request.is_allowed(url.as_str()),
+        Kind::Title => {
+            config.request.is_allowed(url.as_str())
+                && config.title.is_allowed(url.as_str())
+        }
+        Kind::Avatar => true,
+    };
+
+    if !is_allowed(&url) {
+        return Err(LoadError::NotAllowed);
+    }
+
     // WARN: `concurrency` changes aren't picked up until app is relaunched
     let _permit = RATE_LIMIT
         .get_or_init(|| Semaphore::new(config.request.concurrency))
@@ -411,6 +499,11 @@ async fn fetch(
 
     let mut resp = req.send().await?.error_for_status()?;
 
+    // Don't read responses redirected away from the allowlist
+    if !is_allowed(resp.url()) {
+        return Err(LoadError::NotAllowed);
+    }
+
     let Some(first_chunk) = resp.chunk().await? else {
         return Err(LoadError::EmptyBody);
     };
@@ -424,6 +517,11 @@ async fn fetch(
         .and_then(image::Format::from_mime_type))
     {
         Some(format) => {
+            // Images are never downloaded just for a title
+            if matches!(kind, Kind::Title) {
+                return Err(LoadError::NotHtml);
+            }
+
             if exceeds_image_size(
                 resp.content_length(),
                 config.request.max_image_size,
@@ -525,6 +623,19 @@ fn decode_html_string(s: &str) -> String {
     html_escape::decode_html_entities(s).to_string()
 }
 
+/// The contents of the `<title>` tag, with its whitespace collapsed.
+fn parse_title_tag(bytes: &[u8]) -> Option<String> {
+    let html = String::from_utf8_lossy(bytes);
+    let captures = TITLE_TAG_REGEX.captures(&html).ok()??;
+
+    let title = decode_html_string(captures.get(1)?.as_str())
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    (!title.is_empty()).then_some(title)
+}
+
 #[derive(Debug, Default)]
 struct MetaTagProperties {
     canonical_url: Option<Url>,
@@ -607,6 +718,8 @@ fn parse_meta_tag_properties(
 pub enum LoadError {
     #[error("loading disabled in config")]
     Disabled,
+    #[error("host is not in the preview allowlist")]
+    NotAllowed,
     #[error("cached failed attempt")]
     CachedFailed,
     #[error("url doesn't contain open graph data")]
@@ -643,9 +756,37 @@ pub enum LoadError {
 #[cfg(test)]
 mod tests {
     use super::{
-        canonical_preview_url, exceeds_image_size, parse_meta_tag_properties,
+        canonical_preview_url, exceeds_image_size, is_video,
+        parse_meta_tag_properties, parse_title_tag,
     };
 
+    #[test]
+    fn is_video_matches_file_extensions() {
+        let is_video = |url: &str| is_video(&url.parse().expect("valid URL"));
+
+        assert!(is_video("https://example.com/clip.mp4"));
+        assert!(is_video("https://example.com/a/b/Clip.WEBM?x=1#t=10"));
+        assert!(!is_video("https://example.com/image.png"));
+        assert!(!is_video("https://example.com/mp4"));
+        assert!(!is_video("https://example.com/"));
+    }
+
+    #[test]
+    fn parses_title_tag_with_collapsed_whitespace() {
+        let html = br#"
+            <html><head>
+                <TITLE lang="en">
+                    Rust &amp;
+                    Friends
+                </TITLE>
+            </head></html>
+        "#;
+
+        assert_eq!(parse_title_tag(html).as_deref(), Some("Rust & Friends"));
+        assert_eq!(parse_title_tag(b"<title>  </title>"), None);
+        assert_eq!(parse_title_tag(b"<p>no title</p>"), None);
+    }
+
     #[test]
     fn canonical_preview_url_strips_fragment_but_keeps_query() {
         let first: url::Url = "https://example.com/image.jpg?x=1#a"
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Title {
+    pub url: Url,
+    pub title: String,
+}
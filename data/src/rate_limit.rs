@@ -181,6 +181,18 @@ impl<T> TokenBucket<T> {
             )
     }
 
+    // Number of tokens waiting for a permit
+    pub fn queued(&self) -> usize {
+        self.user_tokens.len()
+            + self.high_priority_tokens.len()
+            + self.low_priority_tokens.len()
+    }
+
+    // User tokens waiting for a permit
+    pub fn user_tokens(&self) -> impl Iterator<Item = &T> {
+        self.user_tokens.iter()
+    }
+
     // Returns all tokens, regardless of permit status
     pub fn drain_tokens(&mut self) -> impl Iterator<Item = T> {
         self.user_tokens
@@ -212,4 +224,24 @@ mod tests {
 
         assert_eq!(bucket.available_permits, 3);
     }
+
+    #[test]
+    fn token_bucket_spaces_out_tokens_after_burst() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(Duration::from_secs(2), 3);
+        bucket.last = Some(start);
+
+        for token in 0..5 {
+            bucket.add_token(token, TokenPriority::User);
+        }
+
+        assert_eq!(bucket.acquire_tokens().collect::<Vec<_>>(), [0, 1, 2]);
+
+        bucket.add_permits(start + Duration::from_secs(1));
+        assert_eq!(bucket.acquire_tokens().count(), 0);
+
+        bucket.add_permits(start + Duration::from_secs(2));
+        assert_eq!(bucket.acquire_tokens().collect::<Vec<_>>(), [3]);
+        assert_eq!(bucket.queued(), 1);
+    }
 }
@@ -8,7 +8,7 @@ use irc::proto;
 use serde::{Deserialize, Serialize};
 use tokio::fs;
 
-use crate::bouncer::BouncerNetwork;
+use crate::bouncer::{self, BouncerNetwork};
 use crate::config::buffer::typing::Typing;
 use crate::config::server::{default_port, filehost, read_from_command};
 use crate::config::sidebar::{OrderBy, OrderChannelsBy};
@@ -29,8 +29,12 @@ impl Server {
         self.network.is_some()
     }
 
+    /// The id to `BOUNCER BIND` to, which ZNC networks don't have.
     pub fn bouncer_netid(&self) -> Option<&str> {
-        self.network.as_ref().map(|network| &*network.id)
+        self.network
+            .as_ref()
+            .filter(|network| network.kind == bouncer::Kind::Soju)
+            .map(|network| &*network.id)
     }
 
     pub fn parent(&self) -> Option<Server> {
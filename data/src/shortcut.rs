@@ -75,7 +75,9 @@ impl<'de> Deserialize<'de> for KeyBinds {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString,
+)]
 #[strum(serialize_all = "snake_case")]
 pub enum Command {
     MoveUp,
@@ -93,6 +95,7 @@ pub enum Command {
     ToggleNicklist,
     ToggleTopic,
     ToggleSidebar,
+    ToggleSidebarFilter,
     ToggleFullscreen,
     CommandBar,
     ReloadConfiguration,
@@ -113,6 +116,14 @@ pub enum Command {
     OpenConfigFile,
     ShowMutedBuffers,
     HideMutedBuffers,
+    ToggleAudioMute,
+    EqualizeBuffers,
+    RotateBuffers,
+    SwapPreviousBuffer,
+    MoveBufferTop,
+    MoveBufferBottom,
+    MoveBufferLeft,
+    MoveBufferRight,
 }
 
 macro_rules! default {
@@ -238,6 +249,54 @@ impl KeyBind {
             KeyBind::Unbind => String::new(),
         }
     }
+
+    /// Accelerator string for native menu items, e.g. `cmd+shift+ArrowUp`.
+    pub fn as_accelerator(&self) -> Option<String> {
+        let KeyBind::Bind {
+            key_code,
+            modifiers,
+        } = self
+        else {
+            return None;
+        };
+
+        let key = match &key_code.0 {
+            keyboard::Key::Named(key::Named::ArrowLeft) => "ArrowLeft".into(),
+            keyboard::Key::Named(key::Named::ArrowUp) => "ArrowUp".into(),
+            keyboard::Key::Named(key::Named::ArrowRight) => "ArrowRight".into(),
+            keyboard::Key::Named(key::Named::ArrowDown) => "ArrowDown".into(),
+            keyboard::Key::Named(key::Named::AudioVolumeMute) => {
+                "AudioVolumeMute".into()
+            }
+            keyboard::Key::Named(_) => key_code.to_string(),
+            keyboard::Key::Character(c) => c.to_uppercase(),
+            keyboard::Key::Unidentified => return None,
+        };
+
+        if key.is_empty() {
+            return None;
+        }
+
+        let inner = modifiers.0;
+        let mut parts = vec![];
+
+        if inner.contains(keyboard::Modifiers::LOGO) {
+            parts.push("cmd".to_string());
+        }
+        if inner.contains(keyboard::Modifiers::CTRL) {
+            parts.push("ctrl".to_string());
+        }
+        if inner.contains(keyboard::Modifiers::ALT) {
+            parts.push("alt".to_string());
+        }
+        if inner.contains(keyboard::Modifiers::SHIFT) {
+            parts.push("shift".to_string());
+        }
+
+        parts.push(key);
+
+        Some(parts.join("+"))
+    }
 }
 
 impl PartialEq for KeyBind {
@@ -345,6 +404,7 @@ impl KeyBind {
     default!(leave_buffer, "w", COMMAND | SHIFT);
     default!(toggle_nick_list, "m", COMMAND | ALT);
     default!(toggle_sidebar, "b", COMMAND | ALT);
+    default!(toggle_sidebar_filter, "f", COMMAND | SHIFT);
     default!(toggle_topic, "t", COMMAND | ALT);
     #[cfg(target_os = "macos")]
     default!(toggle_fullscreen, "f", COMMAND | CTRL);
@@ -377,6 +437,14 @@ impl KeyBind {
     default!(open_config_file);
     default!(show_muted_buffers);
     default!(hide_muted_buffers);
+    default!(toggle_audio_mute, "s", COMMAND | ALT);
+    default!(equalize_buffers);
+    default!(rotate_buffers);
+    default!(swap_previous_buffer);
+    default!(move_buffer_top);
+    default!(move_buffer_bottom);
+    default!(move_buffer_left);
+    default!(move_buffer_right);
 }
 
 impl From<(keyboard::Key, keyboard::Modifiers)> for KeyBind {
@@ -17,7 +17,7 @@ use crate::client::Client;
 use crate::config::server::IrcProtocolLogFormat;
 use crate::server::Server;
 use crate::time::Posix;
-use crate::{config, environment, message, server};
+use crate::{config, environment, message, nick_history, server};
 
 const QUIT_REQUEST_TIMEOUT: Duration = Duration::from_millis(400);
 
@@ -57,6 +57,12 @@ pub enum Update {
         sent_time: DateTime<Utc>,
         autoconnect: bool,
     },
+    /// The server presented a certificate which failed validation, and
+    /// could be pinned by its fingerprint instead.
+    UntrustedCertificate {
+        server: Server,
+        fingerprint: String,
+    },
     MessagesReceived(Server, Vec<message::Encoded>),
     Remove(Server),
     UpdateConfiguration {
@@ -66,6 +72,10 @@ pub enum Update {
     AutoconnectDisabled {
         server: Server,
     },
+    Lag {
+        server: Server,
+        lag: Duration,
+    },
 }
 
 enum State {
@@ -101,6 +111,10 @@ pub enum Control {
         error: Option<String>,
     },
     Connect(bool),
+    Reconnect(Option<String>),
+    NetworkChanged {
+        online: bool,
+    },
     DisableAutoconnect,
     End(Option<String>),
     UpdateConfiguration(Arc<config::Server>, Option<config::Proxy>),
@@ -145,8 +159,11 @@ async fn _run(
     // authentication
     let mut connection_attempt = 0;
 
+    // Reconnection attempts are paused while the network is unavailable
+    let mut offline = false;
+
     let mut state = State::Disconnected {
-        autoconnect: config.autoconnect,
+        autoconnect: config.autoconnect_on_launch(),
         retry: time::interval(config.reconnect_delay),
     };
 
@@ -156,14 +173,14 @@ async fn _run(
         is_initial,
         error: None,
         sent_time: Utc::now(),
-        autoconnect: config.autoconnect,
+        autoconnect: config.autoconnect_on_launch(),
     });
 
     loop {
         match &mut state {
             State::Disconnected { autoconnect, retry } => {
                 let selection = {
-                    if *autoconnect {
+                    if *autoconnect && !(offline && config.wait_for_network) {
                         stream::select(
                             (&mut control).boxed(),
                             retry
@@ -188,7 +205,11 @@ async fn _run(
                         default_proxy = updated_default_proxy;
 
                         state = State::Disconnected {
-                            autoconnect: config.autoconnect,
+                            autoconnect: if is_initial {
+                                config.autoconnect_on_launch()
+                            } else {
+                                config.autoconnect
+                            },
                             retry: time::interval(config.reconnect_delay),
                         };
                     }
@@ -200,6 +221,25 @@ async fn _run(
                             *autoconnect = false;
                         }
                     }
+                    Some(Control::Reconnect(_)) => {
+                        *autoconnect = true;
+                        connection_attempt = 0;
+
+                        retry.reset_immediately();
+                    }
+                    Some(Control::NetworkChanged { online }) => {
+                        offline = !online;
+
+                        // Don't wait out the retry delay once the network is
+                        // back
+                        if online && *autoconnect {
+                            log::info!("[{server}] network available");
+
+                            connection_attempt = 0;
+
+                            retry.reset_immediately();
+                        }
+                    }
                     Some(Control::DisableAutoconnect) => {
                         *autoconnect = false;
 
@@ -257,6 +297,21 @@ async fn _run(
                                 };
                             }
                             Err(e) => {
+                                if let connection::Error::Tls(
+                                    connection::tls::Error::UntrustedCertificate {
+                                        fingerprint,
+                                        ..
+                                    },
+                                ) = &e
+                                {
+                                    let _ = sender.unbounded_send(
+                                        Update::UntrustedCertificate {
+                                            server: server.clone(),
+                                            fingerprint: fingerprint.clone(),
+                                        },
+                                    );
+                                }
+
                                 let error = match e {
                                     // unwrap Tls-specific error enums to access more error info
                                     connection::Error::Tls(e) => {
@@ -356,6 +411,20 @@ async fn _run(
                             let token = token.unwrap_or_default();
                             log::trace!("[{server}] pong received: {token}");
 
+                            // Our PINGs carry the time they were sent
+                            if let Ok(sent) = token.parse::<u64>() {
+                                let lag = Duration::from_nanos(
+                                    Posix::now()
+                                        .as_nanos()
+                                        .saturating_sub(sent),
+                                );
+
+                                let _ = sender.unbounded_send(Update::Lag {
+                                    server: server.clone(),
+                                    lag,
+                                });
+                            }
+
                             *ping_timeout = None;
                         }
                         proto::Command::ERROR(error) => {
@@ -527,6 +596,9 @@ async fn _run(
                             config = updated_config;
                             default_proxy = updated_default_proxy;
                         }
+                        Control::NetworkChanged { online } => {
+                            offline = !online;
+                        }
                         Control::Connect(_) | Control::DisableAutoconnect => (),
                         Control::AuthenticationFailed { error } => {
                             let autoconnect = if connection_attempt
@@ -580,6 +652,30 @@ async fn _run(
                                 ),
                             };
                         }
+                        Control::Reconnect(reason) => {
+                            let _ = stream
+                                .connection
+                                .send(Command::QUIT(reason).into())
+                                .await;
+
+                            connection_attempt = 0;
+
+                            let _ =
+                                sender.unbounded_send(Update::Disconnected {
+                                    server: server.clone(),
+                                    is_initial,
+                                    error: None,
+                                    sent_time: Utc::now(),
+                                    autoconnect: true,
+                                });
+                            state = State::Disconnected {
+                                autoconnect: true,
+                                retry: time::interval_at(
+                                    Instant::now() + Duration::from_secs(1),
+                                    config.reconnect_delay,
+                                ),
+                            };
+                        }
                         Control::End(reason) => {
                             let _ = stream
                                 .connection
@@ -626,7 +722,14 @@ async fn connect(
 
     let (sender, receiver) = mpsc::channel(100);
 
+    let nick_history =
+        nick_history::load(&server).await.unwrap_or_else(|error| {
+            log::warn!("[{server}] failed to load nick history: {error}");
+            nick_history::NickHistory::default()
+        });
+
     let mut client = Client::new(server, config, sender);
+    client.set_nick_history(nick_history);
     if let Err(e) = client.connect() {
         log::error!("Error when connecting client: {e:?}");
     }
@@ -886,6 +989,18 @@ impl Map {
         }
     }
 
+    pub fn reconnect(&mut self, server: &Server, reason: &Option<String>) {
+        if let Some(controller) = self.0.get_mut(server) {
+            let _ = controller.try_send(Control::Reconnect(reason.clone()));
+        }
+    }
+
+    pub fn network_changed(&mut self, online: bool) {
+        for controller in self.0.values_mut() {
+            let _ = controller.try_send(Control::NetworkChanged { online });
+        }
+    }
+
     pub fn disable_autoconnect(&mut self, server: &Server) {
         if let Some(controller) = self.0.get_mut(server) {
             let _ = controller.try_send(Control::DisableAutoconnect);
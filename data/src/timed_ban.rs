@@ -0,0 +1,231 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::target::Channel;
+use crate::{Server, User};
+
+/// How long a user stays banned, before the ban is lifted again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duration {
+    FifteenMinutes,
+    OneHour,
+    OneDay,
+    OneWeek,
+}
+
+impl Duration {
+    pub const ALL: &'static [Self] = &[
+        Self::FifteenMinutes,
+        Self::OneHour,
+        Self::OneDay,
+        Self::OneWeek,
+    ];
+
+    pub fn as_time_delta(self) -> TimeDelta {
+        match self {
+            Duration::FifteenMinutes => TimeDelta::minutes(15),
+            Duration::OneHour => TimeDelta::hours(1),
+            Duration::OneDay => TimeDelta::days(1),
+            Duration::OneWeek => TimeDelta::weeks(1),
+        }
+    }
+}
+
+impl std::fmt::Display for Duration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Duration::FifteenMinutes => write!(f, "15 minutes"),
+            Duration::OneHour => write!(f, "1 hour"),
+            Duration::OneDay => write!(f, "1 day"),
+            Duration::OneWeek => write!(f, "1 week"),
+        }
+    }
+}
+
+/// A ban set by us, which Halloy lifts with `MODE -b` once it expires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimedBan {
+    pub server: Server,
+    pub channel: Channel,
+    pub nickname: String,
+    pub mask: String,
+    pub expires_at: DateTime<Utc>,
+    /// Set once the user has been reminded of an unban which could not be
+    /// sent when the ban expired.
+    #[serde(default)]
+    pub reminded: bool,
+}
+
+impl TimedBan {
+    pub fn new(
+        server: Server,
+        channel: Channel,
+        user: &User,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            server,
+            channel,
+            nickname: user.as_str().to_string(),
+            mask: mask(user),
+            expires_at: Utc::now() + duration.as_time_delta(),
+            reminded: false,
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    /// Whether `other` is the same ban, regardless of its reminder state.
+    pub fn is_same(&self, other: &TimedBan) -> bool {
+        self.server == other.server
+            && self.channel == other.channel
+            && self.mask == other.mask
+    }
+}
+
+/// Time given to the connection to (re)join the channel, e.g. after startup,
+/// before reminding the user of an unban which couldn't be sent.
+const REMIND_AFTER: TimeDelta = TimeDelta::minutes(1);
+
+/// Due actions for the timed bans at `now`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Due {
+    /// Expired bans in joined channels, removed from the schedule.
+    pub unban: Vec<TimedBan>,
+    /// Expired bans which couldn't be lifted, reminded of once.
+    pub remind: Vec<TimedBan>,
+}
+
+impl Due {
+    pub fn is_empty(&self) -> bool {
+        self.unban.is_empty() && self.remind.is_empty()
+    }
+}
+
+/// Takes the expired bans whose channel is joined off the schedule, and
+/// marks overdue bans elsewhere as reminded.
+pub fn process(
+    bans: &mut Vec<TimedBan>,
+    now: DateTime<Utc>,
+    is_joined: impl Fn(&TimedBan) -> bool,
+) -> Due {
+    let mut due = Due::default();
+
+    bans.retain_mut(|ban| {
+        if !ban.is_expired(now) {
+            return true;
+        }
+
+        if is_joined(ban) {
+            due.unban.push(ban.clone());
+
+            return false;
+        }
+
+        if !ban.reminded && now - ban.expires_at >= REMIND_AFTER {
+            ban.reminded = true;
+            due.remind.push(ban.clone());
+        }
+
+        true
+    });
+
+    due
+}
+
+/// Removes `ban` from the schedule, once it has been lifted or cancelled.
+pub fn remove(bans: &mut Vec<TimedBan>, ban: &TimedBan) {
+    bans.retain(|scheduled| !scheduled.is_same(ban));
+}
+
+/// Ban mask for the user, `*!*@host` when the hostname is known and
+/// `nick!*@*` otherwise.
+pub fn mask(user: &User) -> String {
+    match user.hostname() {
+        Some(hostname) => format!("*!*@{hostname}"),
+        None => format!("{}!*@*", user.as_str()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ban(channel: &str, expires_at: DateTime<Utc>) -> TimedBan {
+        TimedBan {
+            server: Server::from(crate::server::ServerName::from("libera")),
+            channel: Channel::from_str(
+                channel,
+                &['#'],
+                crate::isupport::CaseMap::default(),
+            ),
+            nickname: "spammer".to_string(),
+            mask: "*!*@spam.example".to_string(),
+            expires_at,
+            reminded: false,
+        }
+    }
+
+    #[test]
+    fn expired_bans_are_lifted_in_joined_channels() {
+        let now = Utc::now();
+        let pending = ban("#pending", now + TimeDelta::minutes(5));
+        let expired = ban("#joined", now);
+
+        let mut bans = vec![pending.clone(), expired.clone()];
+
+        let due =
+            process(&mut bans, now, |ban| ban.channel.as_str() == "#joined");
+
+        assert_eq!(due.unban, vec![expired]);
+        assert!(due.remind.is_empty());
+        assert_eq!(bans, vec![pending.clone()]);
+
+        // Not due until it expires
+        let due = process(&mut bans, now + TimeDelta::minutes(4), |_| true);
+        assert!(due.is_empty());
+
+        let due = process(&mut bans, now + TimeDelta::minutes(5), |_| true);
+        assert_eq!(due.unban, vec![pending]);
+        assert!(bans.is_empty());
+    }
+
+    #[test]
+    fn overdue_bans_are_reminded_once() {
+        let now = Utc::now();
+        let mut bans = vec![ban("#parted", now)];
+
+        assert!(process(&mut bans, now, |_| false).is_empty());
+
+        let later = now + REMIND_AFTER;
+        let due = process(&mut bans, later, |_| false);
+        assert_eq!(due.remind.len(), 1);
+        assert!(bans[0].reminded);
+
+        assert!(process(&mut bans, later, |_| false).is_empty());
+
+        // Lifted once the channel is joined again
+        let due = process(&mut bans, later, |_| true);
+        assert_eq!(due.unban.len(), 1);
+        assert!(bans.is_empty());
+    }
+
+    #[test]
+    fn lifted_bans_are_not_lifted_again() {
+        let now = Utc::now();
+        let lifted = ban("#halloy", now);
+
+        let mut bans = vec![lifted.clone()];
+
+        // Reminded after the pending unbans view was opened
+        process(&mut bans, now + REMIND_AFTER, |_| false);
+        assert!(bans[0].reminded);
+
+        remove(&mut bans, &lifted);
+
+        assert!(bans.is_empty());
+        assert!(process(&mut bans, now + REMIND_AFTER, |_| true).is_empty());
+    }
+}
@@ -24,6 +24,11 @@ pub enum Url {
         url: String,
         styles: theme::Styles,
     },
+    Buffer {
+        url: String,
+        server: String,
+        target: String,
+    },
     Unknown(String),
 }
 
@@ -35,6 +40,7 @@ impl std::fmt::Display for Url {
             match self {
                 Url::ServerConnect { url, .. }
                 | Url::Theme { url, .. }
+                | Url::Buffer { url, .. }
                 | Url::Unknown(url) => url,
             }
         )
@@ -45,6 +51,19 @@ pub fn theme(colors: &theme::Styles) -> String {
     format!("halloy:///theme?e={}", colors.encode_base64())
 }
 
+/// Route opening the buffer of `target` on `server`, where `server` is the
+/// machine-readable (`{:b}`) form of the server.
+pub fn buffer(server: &str, target: &str) -> String {
+    let mut url = url::Url::parse("halloy:///buffer")
+        .expect("buffer route is a valid url");
+
+    url.query_pairs_mut()
+        .append_pair("server", server)
+        .append_pair("target", target);
+
+    url.into()
+}
+
 pub fn theme_submit(colors: &theme::Styles) -> String {
     format!(
         "https://themes.halloy.chat/submit?e={}",
@@ -108,6 +127,20 @@ fn parse(url: url::Url) -> Result<Url, Error> {
                 styles,
             })
         }
+        "halloy" if url.path() == "/buffer" => {
+            let query_pair = |name: &str| {
+                url.query_pairs()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.into_owned())
+                    .ok_or(Error::MissingQueryPair)
+            };
+
+            Ok(Url::Buffer {
+                server: query_pair("server")?,
+                target: query_pair("target")?,
+                url: url.into(),
+            })
+        }
         _ => Err(Error::Unknown),
     }
 }
@@ -342,6 +375,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn buffer_route_round_trips() {
+        let url = Url::from_str(&buffer("libera", "#halloy&rust")).unwrap();
+
+        let Url::Buffer { server, target, .. } = url else {
+            panic!("expected buffer URL");
+        };
+
+        assert_eq!(server, "libera");
+        assert_eq!(target, "#halloy&rust");
+    }
+
     #[test]
     fn parse_server_config_strips_ipv6_brackets() {
         let url = url::Url::parse("irc://[2001:db8::1]/channel").unwrap();
@@ -1,4 +1,4 @@
-use std::cmp::Reverse;
+use std::cmp::{Ordering, Reverse};
 use std::collections::BTreeSet;
 use std::fmt;
 use std::hash::Hash;
@@ -103,6 +103,32 @@ impl ChannelUsers {
         self.0.contains(user)
     }
 
+    /// Replaces the users with a freshly received list, keeping what is
+    /// known about users which remain but was not part of the list.
+    pub fn replace(&mut self, users: ChannelUsers) {
+        *self = users
+            .0
+            .into_iter()
+            .map(|mut user| {
+                if let Some(known) = self.0.get(&user) {
+                    if user.username.is_none() {
+                        user.username.clone_from(&known.username);
+                    }
+                    if user.hostname.is_none() {
+                        user.hostname.clone_from(&known.hostname);
+                    }
+                    if user.accountname.is_none() {
+                        user.accountname.clone_from(&known.accountname);
+                    }
+                    user.away = known.away;
+                    user.bot = known.bot;
+                }
+
+                user
+            })
+            .collect();
+    }
+
     // Any modifications to this procedure MUST ensure that no
     // modifications are made to the user's hash.
     pub fn update_user(
@@ -307,8 +333,25 @@ impl User {
         &mut self,
         operation: mode::Operation,
         mode: mode::Channel,
+        prefix: &[isupport::PrefixMap],
     ) {
-        if let Ok(level) = AccessLevel::try_from(mode) {
+        let level = match mode {
+            // Custom prefix modes arrive either as their mode letter or,
+            // when already mapped through PREFIX, as their prefix symbol
+            mode::Channel::Unknown(c) => AccessLevel::from_prefix(
+                prefix
+                    .iter()
+                    .find(|prefix_map| prefix_map.mode == c)
+                    .map_or(c, |prefix_map| prefix_map.prefix),
+                prefix,
+            ),
+            mode => AccessLevel::try_from(mode)
+                .ok()
+                .and_then(|level| level.char())
+                .and_then(|c| AccessLevel::from_prefix(c, prefix)),
+        };
+
+        if let Some(level) = level {
             match operation {
                 mode::Operation::Add => {
                     self.access_levels.insert(level);
@@ -434,7 +477,13 @@ impl User {
 
         let access_levels = access_levels
             .chars()
-            .filter_map(|c| AccessLevel::try_from(c).ok())
+            .filter_map(|c| {
+                if let Some(prefix) = prefix {
+                    AccessLevel::from_prefix(c, prefix)
+                } else {
+                    AccessLevel::try_from(c).ok()
+                }
+            })
             .collect::<BTreeSet<_>>();
 
         let (nickname, username, hostname) =
@@ -459,11 +508,12 @@ impl User {
         account: Option<&str>,
         casemapping: isupport::CaseMap,
         bot_mode_char: Option<char>,
+        prefix: &[isupport::PrefixMap],
     ) -> Self {
         let access_levels = flags
             .chars()
             .skip(1)
-            .filter_map(|c| AccessLevel::try_from(c).ok())
+            .filter_map(|c| AccessLevel::from_prefix(c, prefix))
             .collect::<BTreeSet<_>>();
         let away = flags.starts_with('G');
         let bot =
@@ -734,7 +784,7 @@ impl PartialEq<Nick> for NickRef<'_> {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AccessLevel {
     Member,
     Voice,
@@ -742,6 +792,16 @@ pub enum AccessLevel {
     Oper,
     Protected(ProtectedPrefix),
     Founder,
+    /// A prefix the server's `PREFIX` lists besides the standard ones
+    Custom(CustomPrefix),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CustomPrefix {
+    prefix: char,
+    /// Ranked right above the closest standard access level listed after
+    /// it in `PREFIX`, which is ordered from highest to lowest
+    rank: u8,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -795,7 +855,67 @@ impl TryFrom<mode::Channel> for AccessLevel {
     }
 }
 
+impl Ord for AccessLevel {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank()
+            .cmp(&other.rank())
+            .then_with(|| self.char().cmp(&other.char()))
+    }
+}
+
+impl PartialOrd for AccessLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl AccessLevel {
+    /// The access level for one of the prefixes in the server's `PREFIX`.
+    pub fn from_prefix(
+        c: char,
+        prefix: &[isupport::PrefixMap],
+    ) -> Option<Self> {
+        let position = prefix
+            .iter()
+            .position(|prefix_map| prefix_map.prefix == c)?;
+
+        // `!` only stands in for `&` on servers which don't use both
+        let is_distinct_from_protected = c == proto::PROTECTED_PREFIX_ALT
+            && prefix.iter().any(|prefix_map| {
+                prefix_map.prefix == proto::PROTECTED_PREFIX_STD
+            });
+
+        if !is_distinct_from_protected
+            && let Ok(access_level) = AccessLevel::try_from(c)
+        {
+            return Some(access_level);
+        }
+
+        let rank = prefix[position + 1..]
+            .iter()
+            .find_map(|prefix_map| {
+                AccessLevel::try_from(prefix_map.prefix).ok()
+            })
+            .map_or(AccessLevel::Member.rank(), |access_level| {
+                access_level.rank()
+            })
+            + 1;
+
+        Some(AccessLevel::Custom(CustomPrefix { prefix: c, rank }))
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            AccessLevel::Member => 0,
+            AccessLevel::Voice => 2,
+            AccessLevel::HalfOp => 4,
+            AccessLevel::Oper => 6,
+            AccessLevel::Protected(_) => 8,
+            AccessLevel::Founder => 10,
+            AccessLevel::Custom(custom) => custom.rank,
+        }
+    }
+
     pub fn char(&self) -> Option<char> {
         match self {
             AccessLevel::Founder => Some(proto::FOUNDER_PREFIX),
@@ -808,6 +928,7 @@ impl AccessLevel {
             AccessLevel::Oper => Some(proto::OPERATOR_PREFIX),
             AccessLevel::HalfOp => Some(proto::HALF_OPERATOR_PREFIX),
             AccessLevel::Voice => Some(proto::VOICED_PREFIX),
+            AccessLevel::Custom(custom) => Some(custom.prefix),
             AccessLevel::Member => None,
         }
     }
@@ -1085,4 +1206,68 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn custom_prefixes_follow_prefix_order() {
+        let prefix = ['!', '~', '&', '@', '*', '%', '+']
+            .into_iter()
+            .zip("Yqaoxhv".chars())
+            .map(|(prefix, mode)| isupport::PrefixMap { prefix, mode })
+            .collect::<Vec<_>>();
+
+        let service = AccessLevel::from_prefix('!', &prefix).unwrap();
+        let custom = AccessLevel::from_prefix('*', &prefix).unwrap();
+
+        assert_eq!(service.char(), Some('!'));
+        assert!(service > AccessLevel::Founder);
+        assert!(custom < AccessLevel::Oper && custom > AccessLevel::HalfOp);
+        assert_eq!(
+            AccessLevel::from_prefix('&', &prefix),
+            Some(AccessLevel::Protected(ProtectedPrefix::Standard))
+        );
+        assert_eq!(AccessLevel::from_prefix('^', &prefix), None);
+
+        let user =
+            User::parse("*%nick", isupport::CaseMap::default(), Some(&prefix))
+                .unwrap();
+
+        assert_eq!(user.highest_access_level(), custom);
+        assert_eq!(user.as_str(), "nick");
+    }
+
+    #[test]
+    fn custom_prefix_modes_update_access_level() {
+        let prefix = ['~', '&', '@', '*', '%', '+']
+            .into_iter()
+            .zip("qaoxhv".chars())
+            .map(|(prefix, mode)| isupport::PrefixMap { prefix, mode })
+            .collect::<Vec<_>>();
+        let custom = AccessLevel::from_prefix('*', &prefix).unwrap();
+
+        let mut user =
+            User::parse("nick", isupport::CaseMap::default(), Some(&prefix))
+                .unwrap();
+
+        user.update_access_level(
+            mode::Operation::Add,
+            mode::Channel::Unknown('x'),
+            &prefix,
+        );
+        assert!(user.has_access_level(custom));
+        assert_eq!(user.highest_access_level(), custom);
+
+        user.update_access_level(
+            mode::Operation::Remove,
+            mode::Channel::Unknown('*'),
+            &prefix,
+        );
+        assert!(!user.has_access_level(custom));
+
+        user.update_access_level(
+            mode::Operation::Add,
+            mode::Channel::Oper,
+            &prefix,
+        );
+        assert_eq!(user.highest_access_level(), AccessLevel::Oper);
+    }
 }
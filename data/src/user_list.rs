@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::target::Channel;
+use crate::user::ChannelUsers;
+use crate::{Server, environment};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub nickname: String,
+    /// Access level prefixes (e.g. `@+`), highest first.
+    pub access_levels: String,
+    pub username: Option<String>,
+    pub hostname: Option<String>,
+    pub account: Option<String>,
+}
+
+impl Entry {
+    fn normalized_nickname(&self) -> String {
+        self.nickname.to_lowercase()
+    }
+
+    fn normalized_account(&self) -> Option<String> {
+        self.account.as_deref().map(str::to_lowercase)
+    }
+
+    pub fn hostmask(&self) -> String {
+        match (&self.username, &self.hostname) {
+            (Some(username), Some(hostname)) => {
+                format!("{}!{username}@{hostname}", self.nickname)
+            }
+            _ => self.nickname.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Export {
+    pub server: String,
+    pub channel: String,
+    pub exported_at: DateTime<Utc>,
+    pub users: Vec<Entry>,
+}
+
+impl Export {
+    pub fn new(
+        server: &Server,
+        channel: &Channel,
+        users: &ChannelUsers,
+    ) -> Self {
+        Self {
+            server: server.to_string(),
+            channel: channel.to_string(),
+            exported_at: Utc::now(),
+            users: users
+                .iter()
+                .map(|user| Entry {
+                    nickname: user.as_str().to_string(),
+                    access_levels: user
+                        .access_levels()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .into_iter()
+                        .rev()
+                        .collect(),
+                    username: user.username().map(ToString::to_string),
+                    hostname: user.hostname().map(ToString::to_string),
+                    account: user.accountname().map(ToString::to_string),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            String::from("nickname,access_levels,username,hostname,account\n");
+
+        for entry in &self.users {
+            let fields = [
+                entry.nickname.as_str(),
+                entry.access_levels.as_str(),
+                entry.username.as_deref().unwrap_or_default(),
+                entry.hostname.as_deref().unwrap_or_default(),
+                entry.account.as_deref().unwrap_or_default(),
+            ];
+
+            csv.push_str(&fields.map(escape_csv_field).join(","));
+            csv.push('\n');
+        }
+
+        csv
+    }
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => {
+                Format::Json
+            }
+            _ => Format::Csv,
+        }
+    }
+}
+
+/// Writes the export to `path`, and records it as the last export of the
+/// channel so later exports can be compared against it.
+pub async fn save(export: Export, path: PathBuf) -> Result<PathBuf, Error> {
+    let contents = match Format::from_path(&path) {
+        Format::Csv => export.to_csv(),
+        Format::Json => serde_json::to_string_pretty(&export)?,
+    };
+
+    fs::write(&path, contents).await?;
+
+    let last_path = last_path(&export.server, &export.channel).await?;
+
+    fs::write(last_path, serde_json::to_vec(&export)?).await?;
+
+    Ok(path)
+}
+
+/// The last export of the channel, if any.
+pub async fn last(
+    server: Server,
+    channel: Channel,
+) -> Result<Option<Export>, Error> {
+    let path = last_path(&server.to_string(), &channel.to_string()).await?;
+
+    match fs::read(path).await {
+        Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+async fn last_path(server: &str, channel: &str) -> Result<PathBuf, Error> {
+    let dir = environment::data_dir().join("user-lists");
+
+    if !dir.exists() {
+        fs::create_dir_all(&dir).await?;
+    }
+
+    let name = format!("{server}channel{}", channel.to_lowercase());
+    let hashed_name = seahash::hash(name.as_bytes());
+
+    Ok(dir.join(format!("{hashed_name}.json")))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff {
+    pub channel: String,
+    pub since: DateTime<Utc>,
+    pub joined: Vec<Entry>,
+    pub left: Vec<Entry>,
+    /// Users present in both, whose nickname, hostmask or access levels
+    /// changed (previous, current).
+    pub changed: Vec<(Entry, Entry)>,
+}
+
+impl Diff {
+    pub fn is_empty(&self) -> bool {
+        self.joined.is_empty()
+            && self.left.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// Users are matched by account when both exports know it, and by nickname
+/// otherwise, so account info appearing or disappearing between exports
+/// shows up as a change rather than as a join and a part.
+pub fn diff(previous: &Export, current: &Export) -> Diff {
+    let mut by_account = HashMap::<String, Vec<usize>>::new();
+    let mut by_nickname = HashMap::new();
+
+    for (index, entry) in previous.users.iter().enumerate() {
+        if let Some(account) = entry.normalized_account() {
+            by_account.entry(account).or_default().push(index);
+        }
+
+        by_nickname.insert(entry.normalized_nickname(), index);
+    }
+
+    let mut unmatched = vec![true; previous.users.len()];
+
+    let mut joined = vec![];
+    let mut changed = vec![];
+
+    for entry in &current.users {
+        let account = entry.normalized_account();
+        let nickname = entry.normalized_nickname();
+
+        // Several clients may share an account; prefer the same nickname
+        let account_match = account
+            .as_ref()
+            .and_then(|account| by_account.get(account))
+            .and_then(|indices| {
+                let candidates =
+                    || indices.iter().copied().filter(|&i| unmatched[i]);
+
+                candidates()
+                    .find(|&i| {
+                        previous.users[i].normalized_nickname() == nickname
+                    })
+                    .or_else(|| candidates().next())
+            });
+        let nickname_match = || {
+            by_nickname.get(&nickname).copied().filter(|&i| {
+                unmatched[i]
+                    && (account.is_none()
+                        || previous.users[i].account.is_none())
+            })
+        };
+
+        match account_match.or_else(nickname_match) {
+            Some(index) => {
+                unmatched[index] = false;
+
+                let previous_entry = &previous.users[index];
+
+                if previous_entry != entry {
+                    changed.push((previous_entry.clone(), entry.clone()));
+                }
+            }
+            None => joined.push(entry.clone()),
+        }
+    }
+
+    // Keep the order of the previous export.
+    let left = previous
+        .users
+        .iter()
+        .zip(unmatched)
+        .filter_map(|(entry, unmatched)| unmatched.then(|| entry.clone()))
+        .collect();
+
+    Diff {
+        channel: current.channel.clone(),
+        since: previous.exported_at,
+        joined,
+        left,
+        changed,
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(nickname: &str, account: Option<&str>) -> Entry {
+        Entry {
+            nickname: nickname.to_string(),
+            access_levels: String::new(),
+            username: Some("user".to_string()),
+            hostname: Some("example.org".to_string()),
+            account: account.map(ToString::to_string),
+        }
+    }
+
+    fn export(users: Vec<Entry>) -> Export {
+        Export {
+            server: "libera".to_string(),
+            channel: "#halloy".to_string(),
+            exported_at: Utc::now(),
+            users,
+        }
+    }
+
+    #[test]
+    fn diff_exports() {
+        let previous = export(vec![
+            entry("alice", Some("alice")),
+            entry("bob", None),
+            entry("carol", None),
+        ]);
+
+        let mut op = entry("carol", None);
+        op.access_levels = "@".to_string();
+
+        let current = export(vec![
+            entry("alice_", Some("alice")),
+            op.clone(),
+            entry("dave", None),
+        ]);
+
+        let diff = diff(&previous, &current);
+
+        assert_eq!(diff.joined, vec![entry("dave", None)]);
+        assert_eq!(diff.left, vec![entry("bob", None)]);
+        assert_eq!(
+            diff.changed,
+            vec![
+                (
+                    entry("alice", Some("alice")),
+                    entry("alice_", Some("alice"))
+                ),
+                (entry("carol", None), op),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_account_appearing_or_disappearing() {
+        let previous = export(vec![
+            entry("alice", None),
+            entry("bob", Some("bob")),
+            entry("carol", Some("carol")),
+        ]);
+        let current = export(vec![
+            entry("alice", Some("alice")),
+            entry("bob", None),
+            entry("carol", Some("mallory")),
+        ]);
+
+        let diff = diff(&previous, &current);
+
+        assert_eq!(diff.joined, vec![entry("carol", Some("mallory"))]);
+        assert_eq!(diff.left, vec![entry("carol", Some("carol"))]);
+        assert_eq!(
+            diff.changed,
+            vec![
+                (entry("alice", None), entry("alice", Some("alice"))),
+                (entry("bob", Some("bob")), entry("bob", None)),
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_escaping() {
+        let mut quoted = entry("weird", None);
+        quoted.hostname = Some("a,\"b\"".to_string());
+
+        assert_eq!(
+            export(vec![quoted]).to_csv(),
+            "nickname,access_levels,username,hostname,account\n\
+             weird,,user,\"a,\"\"b\"\"\",\n"
+        );
+    }
+}
@@ -1,8 +1,9 @@
+use std::collections::HashMap;
 use std::io;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use iced_core::{Point, Size};
+use iced_core::{Point, Rectangle, Size};
 use serde::{Deserialize, Serialize};
 use tokio::fs;
 
@@ -25,6 +26,9 @@ pub struct Window {
     #[serde(with = "serde_option_size")]
     pub fullscreen: Option<Size>,
     pub maximized: bool,
+    /// Scale factor of the window, relating its logical position to the
+    /// physical pixels displays are reported in.
+    pub scale_factor: f32,
 }
 
 impl Default for Window {
@@ -37,44 +41,117 @@ impl Default for Window {
             },
             fullscreen: None,
             maximized: false,
+            scale_factor: 1.0,
         }
     }
 }
 
 impl Window {
+    /// Loads the window state saved for the current display arrangement,
+    /// falling back to the most recently saved state.
     pub async fn load() -> Result<Window, Error> {
-        let path = path()?;
-        let bytes = fs::read(path).await?;
+        let Saved { latest, displays } = Saved::load().await?;
+
         let Window {
             position,
             size,
             fullscreen,
             maximized,
-        } = serde_json::from_slice(&bytes)?;
+            scale_factor,
+        } = Displays::current()
+            .and_then(|current| displays.get(&current.0).copied())
+            .unwrap_or(latest);
 
         let size = size.max(MIN_SIZE);
         let position = position
             .filter(|pos| pos.x.is_finite() && pos.y.is_finite())
-            .filter(|pos| is_position_valid(*pos, size));
+            .filter(|pos| is_position_valid(*pos, size, scale_factor));
 
         Ok(Window {
             position,
             size,
             fullscreen,
             maximized,
+            scale_factor,
         })
     }
 
+    /// Saves the window state, remembering it for the current display
+    /// arrangement.
     pub async fn save(self) -> Result<(), Error> {
-        let path = path()?;
+        let mut saved = Saved::load().await.unwrap_or_default();
+
+        saved.latest = self;
+
+        if let Some(current) = Displays::current() {
+            saved.displays.insert(current.0, self);
+        }
 
-        let bytes = serde_json::to_vec(&self)?;
-        fs::write(path, &bytes).await?;
+        let bytes = serde_json::to_vec(&saved)?;
+        fs::write(path()?, &bytes).await?;
 
         Ok(())
     }
 }
 
+/// Identifies the arrangement of connected displays (their sizes and
+/// positions), so docked and undocked setups each restore their own window
+/// state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Displays(String);
+
+impl Displays {
+    /// Queries the current arrangement on a blocking thread, as listing
+    /// displays can be slow.
+    pub async fn query() -> Option<Self> {
+        tokio::task::spawn_blocking(Self::current)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    pub fn current() -> Option<Self> {
+        let mut displays = display_info::DisplayInfo::all().ok()?;
+
+        if displays.is_empty() {
+            return None;
+        }
+
+        displays.sort_by_key(|display| (display.x, display.y));
+
+        Some(Self(
+            displays
+                .iter()
+                .map(|display| {
+                    format!(
+                        "{}x{}+{}+{}",
+                        display.width, display.height, display.x, display.y
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+        ))
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Saved {
+    // Flattened so window.json files written before per-arrangement state
+    // still load.
+    #[serde(flatten)]
+    latest: Window,
+    displays: HashMap<String, Window>,
+}
+
+impl Saved {
+    async fn load() -> Result<Self, Error> {
+        let bytes = fs::read(path()?).await?;
+
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
 fn path() -> Result<PathBuf, Error> {
     let parent = environment::data_dir();
 
@@ -86,11 +163,16 @@ fn path() -> Result<PathBuf, Error> {
 }
 
 /// Check if a window position is valid (within visible screen bounds)
-fn is_position_valid(position: Point, size: Size) -> bool {
+fn is_position_valid(position: Point, size: Size, scale_factor: f32) -> bool {
     display_info::DisplayInfo::all().is_ok_and(|displays| {
-        displays
-            .iter()
-            .any(|display| window_has_visible_area(position, size, display))
+        displays.iter().any(|display| {
+            let bounds = Rectangle::new(
+                Point::new(display.x as f32, display.y as f32),
+                Size::new(display.width as f32, display.height as f32),
+            );
+
+            window_has_visible_area(position, size, scale_factor, bounds)
+        })
     })
 }
 
@@ -98,17 +180,27 @@ fn is_position_valid(position: Point, size: Size) -> bool {
 fn window_has_visible_area(
     position: Point,
     size: Size,
-    display: &display_info::DisplayInfo,
+    scale_factor: f32,
+    display: Rectangle,
 ) -> bool {
     let window_left = position.x;
     let window_top = position.y;
     let window_right = position.x + size.width;
     let window_bottom = position.y + size.height;
 
-    let display_left = display.x as f32;
-    let display_top = display.y as f32;
-    let display_right = display_left + display.width as f32;
-    let display_bottom = display_top + display.height as f32;
+    // Window positions are logical, while displays are reported in physical
+    // pixels (except on macOS), so convert them with the window's scale
+    // factor, which the window position was made logical with.
+    let scale = if cfg!(target_os = "macos") || scale_factor <= 0.0 {
+        1.0
+    } else {
+        scale_factor
+    };
+
+    let display_left = display.x / scale;
+    let display_top = display.y / scale;
+    let display_right = display_left + display.width / scale;
+    let display_bottom = display_top + display.height / scale;
 
     let visible_width =
         window_right.min(display_right) - window_left.max(display_left);
@@ -240,3 +332,83 @@ mod serde_option_size {
             .serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(x: f32, y: f32, width: f32, height: f32) -> Rectangle {
+        Rectangle::new(Point::new(x, y), Size::new(width, height))
+    }
+
+    #[test]
+    fn window_json_before_display_arrangements_loads() {
+        let saved: Saved = serde_json::from_str(
+            r#"{
+                "position": { "x": 10.0, "y": 20.0 },
+                "size": { "width": 800.0, "height": 600.0 },
+                "fullscreen": null,
+                "maximized": true
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(saved.latest.position, Some(Point::new(10.0, 20.0)));
+        assert_eq!(saved.latest.size, Size::new(800.0, 600.0));
+        assert_eq!(saved.latest.fullscreen, None);
+        assert!(saved.latest.maximized);
+        assert!(saved.displays.is_empty());
+    }
+
+    #[test]
+    fn visible_area_on_display() {
+        let size = Size::new(800.0, 600.0);
+        let display = display(0.0, 0.0, 1920.0, 1080.0);
+
+        assert!(window_has_visible_area(
+            Point::new(100.0, 100.0),
+            size,
+            1.0,
+            display
+        ));
+        assert!(!window_has_visible_area(
+            Point::new(1880.0, 100.0),
+            size,
+            1.0,
+            display
+        ));
+        assert!(!window_has_visible_area(
+            Point::new(-780.0, 100.0),
+            size,
+            1.0,
+            display
+        ));
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn visible_area_with_window_scale_factor() {
+        let size = Size::new(800.0, 600.0);
+        // 1920x1080 logical pixels at a scale factor of 1.5
+        let display = display(0.0, 0.0, 2880.0, 1620.0);
+
+        assert!(window_has_visible_area(
+            Point::new(1800.0, 100.0),
+            size,
+            1.5,
+            display
+        ));
+        assert!(!window_has_visible_area(
+            Point::new(1900.0, 100.0),
+            size,
+            1.5,
+            display
+        ));
+        assert!(!window_has_visible_area(
+            Point::new(100.0, 1060.0),
+            size,
+            1.5,
+            display
+        ));
+    }
+}
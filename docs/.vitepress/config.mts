@@ -18,11 +18,13 @@ const guidesItems = [
   { text: "Connect with soju", link: "/guides/connect-with-soju" },
   { text: "Connect with ZNC", link: "/guides/connect-with-znc" },
   { text: "Custom Themes", link: "/guides/custom-themes" },
+  { text: "Demo Mode", link: "/guides/demo-mode" },
   { text: "Exec Command", link: "/guides/exec-command" },
   {
     text: "Example Server Configurations",
     link: "/guides/example-server-configurations",
   },
+  { text: "Export User Lists", link: "/guides/export-user-lists" },
   {
     text: "Inclusion/Exclusion Conditions",
     link: "/configuration/conditions",
@@ -41,6 +43,7 @@ const guidesItems = [
   { text: "File Uploads", link: "/guides/filehost" },
   { text: "Unix Signals", link: "/guides/unix-signals" },
   { text: "Text Formatting", link: "/guides/text-formatting" },
+  { text: "Timed Bans", link: "/guides/timed-bans" },
   { text: "URL Schemes", link: "/guides/url-schemes" },
 ];
 
@@ -77,9 +80,13 @@ const configurationItems = [
     text: "Highlights",
     link: "/configuration/highlights",
   },
+  { text: "Ignore", link: "/configuration/ignore" },
   { text: "Keyboard", link: "/configuration/keyboard" },
+  { text: "Language", link: "/configuration/language" },
+  { text: "Lock", link: "/configuration/lock" },
   { text: "Logs", link: "/configuration/logs" },
   { text: "Metadata", link: "/configuration/metadata" },
+  { text: "Mouse", link: "/configuration/mouse" },
   { text: "Notifications", link: "/configuration/notifications" },
   {
     text: "Pane",
@@ -89,6 +96,10 @@ const configurationItems = [
     text: "Platform Specific",
     link: "/configuration/platform-specific",
   },
+  {
+    text: "Plugins",
+    link: "/configuration/plugins",
+  },
   {
     text: "Preview",
     link: "/configuration/preview",
@@ -107,6 +118,7 @@ const configurationItems = [
     text: "Sidebar",
     link: "/configuration/sidebar",
   },
+  { text: "Status bar", link: "/configuration/status-bar" },
   {
     text: "Themes",
     link: "/configuration/themes",
@@ -557,6 +557,7 @@ pub enum Numeric {
     ERR_ALREADYREGISTERED = 462,
     ERR_PASSWDMISMATCH = 464,
     ERR_YOUREBANNEDCREEP = 465,
+    ERR_LINKCHANNEL = 470,
     ERR_CHANNELISFULL = 471,
     ERR_UNKNOWNMODE = 472,
     ERR_INVITEONLYCHAN = 473,
@@ -579,6 +580,11 @@ pub enum Numeric {
     RPL_HELPSTART = 704,
     RPL_HELPTXT = 705,
     RPL_ENDOFHELP = 706,
+    RPL_KNOCK = 710,
+    RPL_KNOCKDLVR = 711,
+    ERR_TOOMANYKNOCK = 712,
+    ERR_CHANOPEN = 713,
+    ERR_KNOCKONCHAN = 714,
     ERR_NOPRIVS = 723,
     RPL_MONONLINE = 730,
     RPL_MONOFFLINE = 731,
@@ -712,6 +718,7 @@ impl TryFrom<u16> for Numeric {
             462 => ERR_ALREADYREGISTERED,
             464 => ERR_PASSWDMISMATCH,
             465 => ERR_YOUREBANNEDCREEP,
+            470 => ERR_LINKCHANNEL,
             471 => ERR_CHANNELISFULL,
             472 => ERR_UNKNOWNMODE,
             473 => ERR_INVITEONLYCHAN,
@@ -734,6 +741,11 @@ impl TryFrom<u16> for Numeric {
             704 => RPL_HELPSTART,
             705 => RPL_HELPTXT,
             706 => RPL_ENDOFHELP,
+            710 => RPL_KNOCK,
+            711 => RPL_KNOCKDLVR,
+            712 => ERR_TOOMANYKNOCK,
+            713 => ERR_CHANOPEN,
+            714 => ERR_KNOCKONCHAN,
             723 => ERR_NOPRIVS,
             730 => RPL_MONONLINE,
             731 => RPL_MONOFFLINE,
@@ -17,7 +17,7 @@ pub use self::proxy::Proxy;
 use self::websocket::WebSocketConnection;
 
 mod proxy;
-mod tls;
+pub mod tls;
 mod websocket;
 
 pub fn prepare() {
@@ -37,6 +37,8 @@ pub enum IrcStream {
 
 pub enum Connection<Codec> {
     Tls(Framed<TlsStream<IrcStream>, Codec>),
+    /// Accepted by us, e.g. as the sender of a secure DCC transfer
+    TlsServer(Framed<tokio_rustls::server::TlsStream<IrcStream>, Codec>),
     Unsecured(Framed<IrcStream, Codec>),
     WebSocket(WebSocketConnection<Codec>),
 }
@@ -49,6 +51,7 @@ pub enum Security<'a> {
         root_cert_path: Option<&'a PathBuf>,
         client_cert_path: Option<&'a PathBuf>,
         client_key_path: Option<&'a PathBuf>,
+        options: tls::Options<'a>,
     },
 }
 
@@ -96,6 +99,7 @@ impl<Codec> Connection<Codec> {
             root_cert_path,
             client_cert_path,
             client_key_path,
+            options,
         } = config.security
         {
             let tls = tls::connect(
@@ -105,6 +109,7 @@ impl<Codec> Connection<Codec> {
                 root_cert_path,
                 client_cert_path,
                 client_key_path,
+                options,
             )
             .await?;
 
@@ -116,6 +121,9 @@ impl<Codec> Connection<Codec> {
 
     /// Binds a listener and returns a single connection
     /// once accepted. Useful for DCC flow.
+    ///
+    /// When secured, `client_cert_path` (and `client_key_path`) is used as
+    /// the certificate presented to the remote.
     pub async fn listen_and_accept(
         address: IpAddr,
         port: u16,
@@ -131,8 +139,18 @@ impl<Codec> Connection<Codec> {
             Security::Unsecured => {
                 Ok(Self::Unsecured(Framed::new(stream, codec)))
             }
-            Security::Secured { .. } => {
-                todo!();
+            Security::Secured {
+                client_cert_path,
+                client_key_path,
+                ..
+            } => {
+                let cert_path =
+                    client_cert_path.ok_or(tls::Error::MissingCertificate)?;
+
+                let tls =
+                    tls::accept(stream, cert_path, client_key_path).await?;
+
+                Ok(Self::TlsServer(Framed::new(tls, codec)))
             }
         }
     }
@@ -142,6 +160,9 @@ impl<Codec> Connection<Codec> {
             Connection::Tls(framed) => {
                 framed.into_inner().shutdown().await?;
             }
+            Connection::TlsServer(framed) => {
+                framed.into_inner().shutdown().await?;
+            }
             Connection::Unsecured(framed) => {
                 framed.into_inner().shutdown().await?;
             }
@@ -169,6 +190,7 @@ macro_rules! delegate {
     ($e:expr, $($t:tt)*) => {
         match $e {
             $crate::connection::Connection::Tls(framed) => framed.$($t)*,
+            $crate::connection::Connection::TlsServer(framed) => framed.$($t)*,
             $crate::connection::Connection::Unsecured(framed) => framed.$($t)*,
             $crate::connection::Connection::WebSocket(websocket) => websocket.$($t)*,
         }
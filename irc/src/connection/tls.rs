@@ -1,16 +1,41 @@
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use bytes::Bytes;
+use sha2::{Digest, Sha256};
 use tokio::fs;
-use tokio_rustls::TlsConnector;
 use tokio_rustls::client::TlsStream;
 use tokio_rustls::rustls::client::danger::{self, ServerCertVerifier};
+use tokio_rustls::rustls::client::{
+    VerifierBuilderError, WebPkiServerVerifier,
+};
+use tokio_rustls::rustls::crypto::{self, WebPkiSupportedAlgorithms};
 use tokio_rustls::rustls::pki_types::pem::PemObject;
 use tokio_rustls::rustls::{self, pki_types};
+use tokio_rustls::{TlsAcceptor, TlsConnector, server};
 
 use super::IrcStream;
 
+/// Minimum protocol version to negotiate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    Tls12,
+    Tls13,
+}
+
+/// Optional client settings for connecting to a server.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Options<'a> {
+    /// Trust only the certificate with this SHA-256 fingerprint.
+    pub pinned_cert_sha256: Option<&'a str>,
+    /// Name sent with SNI and validated against the certificate, instead
+    /// of the server's address.  Empty to not send SNI.
+    pub server_name: Option<&'a str>,
+    pub min_version: Option<Version>,
+    /// Protocols to offer with ALPN.
+    pub alpn: &'a [String],
+}
+
 pub async fn connect<'a>(
     stream: IrcStream,
     server: &str,
@@ -18,11 +43,31 @@ pub async fn connect<'a>(
     root_cert_path: Option<&'a PathBuf>,
     client_cert_path: Option<&'a PathBuf>,
     client_key_path: Option<&'a PathBuf>,
+    options: Options<'a>,
 ) -> Result<TlsStream<IrcStream>, Error> {
+    // Fingerprint of a certificate which failed validation, so it can be
+    // offered for pinning
+    let untrusted = Arc::new(Mutex::new(None));
+
+    let versions: &[&rustls::SupportedProtocolVersion] =
+        match options.min_version {
+            Some(Version::Tls13) => &[&rustls::version::TLS13],
+            Some(Version::Tls12) | None => rustls::DEFAULT_VERSIONS,
+        };
+    let config_builder =
+        || rustls::ClientConfig::builder_with_protocol_versions(versions);
+
     let builder = if accept_invalid_certs {
-        rustls::ClientConfig::builder()
+        config_builder()
             .dangerous()
             .with_custom_certificate_verifier(Arc::new(AcceptInvalidCerts))
+    } else if let Some(fingerprint) = options.pinned_cert_sha256 {
+        config_builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(PinnedCert {
+                fingerprint: normalize_fingerprint(fingerprint),
+                algorithms: signature_algorithms(),
+            }))
     } else {
         let mut roots = rustls::RootCertStore::empty();
 
@@ -37,10 +82,18 @@ pub async fn connect<'a>(
             roots.add_parsable_certificates(certs);
         }
 
-        rustls::ClientConfig::builder().with_root_certificates(roots)
+        let verifier =
+            WebPkiServerVerifier::builder(Arc::new(roots)).build()?;
+
+        config_builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(RecordUntrusted {
+                verifier,
+                untrusted: untrusted.clone(),
+            }))
     };
 
-    let client_config = if let Some(cert_path) = client_cert_path {
+    let mut client_config = if let Some(cert_path) = client_cert_path {
         let cert_bytes = Bytes::from(fs::read(&cert_path).await?);
 
         let key_bytes = if let Some(key_path) = client_key_path {
@@ -59,10 +112,85 @@ pub async fn connect<'a>(
         builder.with_no_client_auth()
     };
 
-    let server_name = pki_types::ServerName::try_from(server.to_string())?;
+    client_config.alpn_protocols = options
+        .alpn
+        .iter()
+        .map(|protocol| protocol.as_bytes().to_vec())
+        .collect();
+
+    let server_name = match options.server_name {
+        Some("") => {
+            client_config.enable_sni = false;
+            server
+        }
+        Some(server_name) => server_name,
+        None => server,
+    };
+    let server_name = pki_types::ServerName::try_from(server_name.to_string())?;
 
-    Ok(TlsConnector::from(Arc::new(client_config))
+    TlsConnector::from(Arc::new(client_config))
         .connect(server_name, stream)
+        .await
+        .map_err(|error| {
+            match untrusted.lock().ok().and_then(|mut fp| fp.take()) {
+                Some(fingerprint) => Error::UntrustedCertificate {
+                    fingerprint,
+                    reason: error.to_string(),
+                },
+                None => Error::Io(error),
+            }
+        })
+}
+
+/// Lowercase hex SHA-256 fingerprint of a DER encoded certificate.
+pub fn fingerprint(cert: &[u8]) -> String {
+    Sha256::digest(cert)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Accepts fingerprints as lowercase or uppercase hex, optionally separated
+/// by colons (as printed by `openssl x509 -fingerprint`).
+pub fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint
+        .chars()
+        .filter(|c| *c != ':' && !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+fn signature_algorithms() -> WebPkiSupportedAlgorithms {
+    crypto::ring::default_provider().signature_verification_algorithms
+}
+
+/// Accepts a TLS connection, presenting the certificate at `cert_path`. The
+/// private key is read from `key_path`, or from the certificate file if not
+/// set.
+pub async fn accept(
+    stream: IrcStream,
+    cert_path: &PathBuf,
+    key_path: Option<&PathBuf>,
+) -> Result<server::TlsStream<IrcStream>, Error> {
+    let cert_bytes = Bytes::from(fs::read(cert_path).await?);
+
+    let key_bytes = if let Some(key_path) = key_path {
+        Bytes::from(fs::read(key_path).await?)
+    } else {
+        cert_bytes.clone()
+    };
+
+    let certs = pki_types::CertificateDer::pem_slice_iter(&cert_bytes)
+        .collect::<Result<Vec<_>, _>>()?;
+    let key = pki_types::PrivateKeyDer::from_pem_slice(&key_bytes)
+        .map_err(|_| Error::BadPrivateKey)?;
+
+    let server_config = rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)?;
+
+    Ok(TlsAcceptor::from(Arc::new(server_config))
+        .accept(stream)
         .await?)
 }
 
@@ -118,10 +246,119 @@ impl ServerCertVerifier for AcceptInvalidCerts {
     }
 }
 
+/// Trusts only the certificate matching `fingerprint`, skipping CA and
+/// hostname validation. Handshake signatures are still verified, so the
+/// server must hold the certificate's private key.
+#[derive(Debug)]
+struct PinnedCert {
+    fingerprint: String,
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl ServerCertVerifier for PinnedCert {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::pki_types::CertificateDer<'_>,
+        _intermediates: &[rustls::pki_types::CertificateDer<'_>],
+        _server_name: &rustls::pki_types::ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: rustls::pki_types::UnixTime,
+    ) -> Result<danger::ServerCertVerified, rustls::Error> {
+        if fingerprint(end_entity) == self.fingerprint {
+            Ok(danger::ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::InvalidCertificate(
+                rustls::CertificateError::ApplicationVerificationFailure,
+            ))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<danger::HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<danger::HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
+
+/// Regular CA validation, remembering the fingerprint of a certificate that
+/// fails it.
+#[derive(Debug)]
+struct RecordUntrusted {
+    verifier: Arc<WebPkiServerVerifier>,
+    untrusted: Arc<Mutex<Option<String>>>,
+}
+
+impl ServerCertVerifier for RecordUntrusted {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::pki_types::CertificateDer<'_>,
+        intermediates: &[rustls::pki_types::CertificateDer<'_>],
+        server_name: &rustls::pki_types::ServerName<'_>,
+        ocsp_response: &[u8],
+        now: rustls::pki_types::UnixTime,
+    ) -> Result<danger::ServerCertVerified, rustls::Error> {
+        let result = self.verifier.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        );
+
+        if let Err(rustls::Error::InvalidCertificate(_)) = &result
+            && let Ok(mut untrusted) = self.untrusted.lock()
+        {
+            *untrusted = Some(fingerprint(end_entity));
+        }
+
+        result
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<danger::HandshakeSignatureValid, rustls::Error> {
+        self.verifier.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<danger::HandshakeSignatureValid, rustls::Error> {
+        self.verifier.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.verifier.supported_verify_schemes()
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("rustls error: {0}")]
     Tls(#[from] rustls::Error),
+    #[error("certificate verifier error: {0}")]
+    Verifier(#[from] VerifierBuilderError),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
     #[error("invalid DNS name: {0}")]
@@ -130,4 +367,102 @@ pub enum Error {
     Pem(#[from] pki_types::pem::Error),
     #[error("missing or invalid private key")]
     BadPrivateKey,
+    #[error("a certificate is required to accept TLS connections")]
+    MissingCertificate,
+    #[error("untrusted certificate (SHA-256 {fingerprint}): {reason}")]
+    UntrustedCertificate { fingerprint: String, reason: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Self-signed CA, only used as a trust anchor
+    const ROOT_CERT: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBijCCAS+gAwIBAgIUKV6uhvCl5SkspFbtFy7UuutEIy0wCgYIKoZIzj0EAwIw
+GTEXMBUGA1UEAwwOSGFsbG95IFRlc3QgQ0EwIBcNMjYxMDE2MTAwNTIwWhgPMjEy
+NjA5MjIxMDA1MjBaMBkxFzAVBgNVBAMMDkhhbGxveSBUZXN0IENBMFkwEwYHKoZI
+zj0CAQYIKoZIzj0DAQcDQgAEB7TDdliGlUbPqy03s3bGoblQHZPm3dTD3mNdFk4f
+kfFBzPLVW+khbraOOAj2xW1R/2Cyth1/IM4CdIDo2QYu6qNTMFEwHQYDVR0OBBYE
+FExvVG8WrSLg9lIGl3VqaK/BJkBUMB8GA1UdIwQYMBaAFExvVG8WrSLg9lIGl3Vq
+aK/BJkBUMA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSQAwRgIhALxFjn7H
+kk1wtdZKFAfkea41YtLVJPRrdCxp177DI0p4AiEA7U3UeThbXtm6od80FGAX4VfK
+9am/WhhQm01vphENlrk=
+-----END CERTIFICATE-----
+";
+
+    fn verify(
+        verifier: &dyn ServerCertVerifier,
+        cert: &[u8],
+    ) -> Result<danger::ServerCertVerified, rustls::Error> {
+        verifier.verify_server_cert(
+            &pki_types::CertificateDer::from(cert.to_vec()),
+            &[],
+            &pki_types::ServerName::try_from("irc.example.com").unwrap(),
+            &[],
+            pki_types::UnixTime::now(),
+        )
+    }
+
+    #[test]
+    fn fingerprints() {
+        assert_eq!(
+            fingerprint(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(normalize_fingerprint("AB:cd:EF:01"), "abcdef01");
+        assert_eq!(normalize_fingerprint(" ab cd\tef01\n"), "abcdef01");
+    }
+
+    #[test]
+    fn pinned_cert() {
+        let cert = b"pinned certificate";
+        let pin = fingerprint(cert)
+            .to_ascii_uppercase()
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| String::from_utf8_lossy(pair).into_owned())
+            .collect::<Vec<_>>()
+            .join(":");
+
+        let verifier = PinnedCert {
+            fingerprint: normalize_fingerprint(&pin),
+            algorithms: signature_algorithms(),
+        };
+
+        assert!(verify(&verifier, cert).is_ok());
+        assert!(matches!(
+            verify(&verifier, b"another certificate"),
+            Err(rustls::Error::InvalidCertificate(
+                rustls::CertificateError::ApplicationVerificationFailure
+            ))
+        ));
+    }
+
+    #[test]
+    fn records_untrusted_cert() {
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add_parsable_certificates(
+            pki_types::CertificateDer::pem_slice_iter(ROOT_CERT.as_bytes())
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+        );
+
+        let untrusted = Arc::new(Mutex::new(None));
+        let verifier = RecordUntrusted {
+            verifier: WebPkiServerVerifier::builder_with_provider(
+                Arc::new(roots),
+                Arc::new(crypto::ring::default_provider()),
+            )
+            .build()
+            .unwrap(),
+            untrusted: untrusted.clone(),
+        };
+
+        let cert = b"untrusted certificate";
+
+        assert!(verify(&verifier, cert).is_err());
+        assert_eq!(*untrusted.lock().unwrap(), Some(fingerprint(cert)));
+    }
 }
@@ -62,6 +62,7 @@ impl<Codec> WebSocketConnection<Codec> {
                 root_cert_path,
                 client_cert_path,
                 client_key_path,
+                options,
             } => {
                 let stream = tls::connect(
                     stream,
@@ -70,6 +71,7 @@ impl<Codec> WebSocketConnection<Codec> {
                     root_cert_path,
                     client_cert_path,
                     client_key_path,
+                    options,
                 )
                 .await?;
 
@@ -1,6 +1,7 @@
 pub use data::appearance::theme::{
-    Buffer, Button, Buttons, Formatting, General, ServerMessages, Styles, Text,
-    color_to_hex, hex_to_color,
+    Buffer, Button, Buttons, Formatting, General, MIN_CONTRAST_RATIO,
+    ServerMessages, Styles, Text, color_to_hex, contrast_ratio, fix_contrast,
+    fix_nickname_contrast, hex_to_color, nickname_contrast_ratio,
 };
 use data::config;
 use iced::widget::text::LineHeight;
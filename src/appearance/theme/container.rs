@@ -62,6 +62,16 @@ pub fn buffer_title_bar(theme: &Theme) -> Style {
     }
 }
 
+pub fn status_bar(theme: &Theme) -> Style {
+    Style {
+        background: Some(Background::Color(
+            theme.styles().buffer.background_title_bar,
+        )),
+        text_color: Some(theme.styles().text.secondary.color),
+        ..Default::default()
+    }
+}
+
 pub fn buffer_text_input(theme: &Theme) -> Style {
     let styles = theme.styles().buffer;
 
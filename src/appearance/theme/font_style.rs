@@ -20,11 +20,19 @@ pub fn action(theme: &Theme) -> Option<FontStyle> {
     theme.styles().buffer.action.font_style
 }
 
-pub fn nickname(theme: &Theme, is_user_offline: bool) -> Option<FontStyle> {
+pub fn nickname(
+    theme: &Theme,
+    is_user_away: bool,
+    is_user_offline: bool,
+) -> Option<FontStyle> {
+    let styles = theme.styles().buffer;
+
     if is_user_offline {
-        theme.styles().buffer.nickname_offline.font_style
+        styles.nickname_offline.font_style
+    } else if is_user_away && styles.nickname_away.font_style.is_some() {
+        styles.nickname_away.font_style
     } else {
-        theme.styles().buffer.nickname.font_style
+        styles.nickname.font_style
     }
 }
 
@@ -61,6 +69,7 @@ pub fn server(
             Kind::ChangeTopic => styles.change_topic.font_style,
             Kind::Away => styles.away.font_style,
             Kind::Invite => styles.invite.font_style,
+            Kind::Ctcp => None,
         })
         .or(styles.default.font_style)
 }
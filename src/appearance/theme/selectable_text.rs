@@ -28,6 +28,14 @@ pub fn default(theme: &Theme) -> Style {
     }
 }
 
+/// Message text in the color of its sender's nickname.
+pub fn sender(theme: &Theme, color: Color) -> Style {
+    Style {
+        color: Some(color),
+        selection_color: theme.styles().buffer.selection,
+    }
+}
+
 pub fn secondary(theme: &Theme) -> Style {
     Style {
         color: Some(theme.styles().text.secondary.color),
@@ -121,6 +129,7 @@ pub fn server(
             Kind::Kick => styles.kick.color,
             Kind::Away => styles.away.color,
             Kind::Invite => styles.invite.color,
+            Kind::Ctcp => None,
         })
         .or(Some(styles.default.color));
 
@@ -158,7 +167,7 @@ pub fn nickname(
     } else {
         nickname_style(
             theme,
-            &config.buffer.nickname.color,
+            &config.buffer.nickname,
             user,
             is_away,
             config.buffer.nickname.offline.is_offline(is_user_offline),
@@ -168,13 +177,13 @@ pub fn nickname(
 
 fn nickname_style(
     theme: &Theme,
-    kind: &data::buffer::Color,
+    config: &buffer::nickname::Nickname,
     user: &User,
     is_away: Option<buffer::Away>,
     is_offline: bool,
 ) -> Style {
     let color =
-        text::nickname(theme, kind, Some(user.seed()), is_away, is_offline)
+        text::nickname(theme, config, Some(user.seed()), is_away, is_offline)
             .color;
 
     Style {
@@ -137,7 +137,7 @@ pub fn url(theme: &Theme) -> Style {
 
 pub fn nickname(
     theme: &Theme,
-    kind: &data::buffer::Color,
+    config: &buffer::nickname::Nickname,
     seed: Option<&str>,
     is_away: Option<buffer::Away>,
     is_offline: bool,
@@ -148,10 +148,12 @@ pub fn nickname(
                 theme.styles().buffer.nickname_offline.color
         {
             offline_color
+        } else if is_away.is_some()
+            && let Some(away_color) = theme.styles().buffer.nickname_away.color
+        {
+            away_color
         } else {
-            let nickname = theme.styles().buffer.nickname;
-
-            nickname_color(nickname.color, kind, seed)
+            nickname_color(theme.styles(), config, seed)
         },
         is_away,
         theme.styles().buffer.background,
@@ -194,6 +196,7 @@ pub fn server(
             Kind::Kick => styles.kick.color,
             Kind::Away => styles.away.color,
             Kind::Invite => styles.invite.color,
+            Kind::Ctcp => None,
         })
         .or(Some(styles.default.color));
 
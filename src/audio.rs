@@ -5,18 +5,19 @@ use std::thread;
 use data::audio::Sound;
 use rodio::{Decoder, DeviceSinkBuilder, Player};
 
-pub fn play(sound: Sound) -> thread::JoinHandle<()> {
+pub fn play(sound: Sound, volume: f32) -> thread::JoinHandle<()> {
     thread::spawn(move || {
-        if let Err(e) = _play(sound) {
+        if let Err(e) = _play(sound, volume) {
             log::error!("Failed to play sound: {e}");
         }
     })
 }
 
-fn _play(sound: Sound) -> Result<(), PlayError> {
+fn _play(sound: Sound, volume: f32) -> Result<(), PlayError> {
     let mut sink_handle = DeviceSinkBuilder::open_default_sink()?;
     sink_handle.log_on_drop(false);
     let player = Player::connect_new(sink_handle.mixer());
+    player.set_volume(volume);
 
     let source = Decoder::new(Cursor::new(sound))?;
 
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::fmt;
 
 use chrono::{DateTime, Utc};
@@ -17,12 +18,16 @@ use iced::{Size, Task};
 pub use self::channel::Channel;
 pub use self::channel_discovery::ChannelDiscovery;
 pub use self::config_editor::ConfigEditor;
+pub use self::diagnostics::Diagnostics;
 pub use self::file_transfers::FileTransfers;
 pub use self::highlights::Highlights;
+pub use self::ignores::Ignores;
 pub use self::logs::Logs;
 pub use self::query::Query;
 pub use self::server::Server;
+pub use self::unread::Unread;
 use crate::Theme;
+use crate::screen::dashboard::modal::open_target;
 use crate::screen::dashboard::sidebar;
 use crate::widget::Element;
 use crate::window::Window;
@@ -31,9 +36,11 @@ pub mod channel;
 pub mod channel_discovery;
 pub mod config_editor;
 pub mod context_menu;
+pub mod diagnostics;
 pub mod empty;
 pub mod file_transfers;
 pub mod highlights;
+pub mod ignores;
 mod input_view;
 pub mod logs;
 mod message_view;
@@ -41,6 +48,7 @@ pub mod query;
 mod scroll_view;
 pub mod server;
 pub mod typing;
+pub mod unread;
 
 #[derive(Clone, Debug)]
 pub enum Buffer {
@@ -53,10 +61,14 @@ pub enum Buffer {
     Highlights(Highlights),
     ChannelDiscovery(ChannelDiscovery),
     ConfigEditor(ConfigEditor),
+    Diagnostics(Diagnostics),
+    Unread(Unread),
+    Ignores(Ignores),
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
+    Empty(empty::Message),
     Channel(channel::Message),
     Server(server::Message),
     Query(query::Message),
@@ -65,14 +77,25 @@ pub enum Message {
     Highlights(highlights::Message),
     ChannelList(channel_discovery::Message),
     ConfigEditor(config_editor::Message),
+    Diagnostics(diagnostics::Message),
+    Unread(unread::Message),
+    Ignores(ignores::Message),
 }
 
 pub enum Event {
     ContextMenu(context_menu::Event),
     OpenBuffers(data::Server, Vec<(Target, BufferAction)>),
     OpenInternalBuffer(buffer::Internal),
+    /// Open in place of this (empty) pane
+    OpenInPane(data::Buffer),
+    PromptOpenTarget(open_target::Kind),
     OpenServer(String),
+    OpenEmojiPicker,
     Reconnect(data::Server),
+    UpdateAutojoin(data::Server, target::Channel, target::Channel),
+    TestNotification(data::Server, data::Notification),
+    Ignore(data::ignore::Mask),
+    Unignore(String),
     LeaveBuffers(Vec<Target>, Option<String>),
     SelectedServer(data::Server),
     GoToMessage(data::Server, target::Channel, message::Hash, BufferAction),
@@ -83,8 +106,10 @@ pub enum Event {
     MarkAsRead(history::Kind),
     OpenUrl(String),
     ImagePreview(Image),
+    LoadPreview(url::Url),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
+    ExpandReplay(DateTime<Utc>, bool),
     InputSent {
         history_task: Task<history::manager::Message>,
         open_buffers: Vec<(Target, BufferAction)>,
@@ -99,6 +124,7 @@ pub enum Event {
         upload_ids: Vec<u32>,
         abort_registrations: Vec<futures::future::AbortRegistration>,
     },
+    FileTransfer(file_transfer::Id, file_transfers::Response),
 }
 
 impl Buffer {
@@ -141,6 +167,11 @@ impl Buffer {
                 buffer::Internal::ConfigEditor => {
                     Self::ConfigEditor(ConfigEditor::new())
                 }
+                buffer::Internal::Diagnostics => {
+                    Self::Diagnostics(Diagnostics::new())
+                }
+                buffer::Internal::Unread => Self::Unread(Unread::new()),
+                buffer::Internal::Ignores => Self::Ignores(Ignores::new()),
             },
         }
     }
@@ -158,7 +189,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => None,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => None,
         }
     }
 
@@ -175,6 +209,9 @@ impl Buffer {
                 Some(buffer::Internal::ChannelDiscovery(state.server.clone()))
             }
             Buffer::ConfigEditor(_) => Some(buffer::Internal::ConfigEditor),
+            Buffer::Diagnostics(_) => Some(buffer::Internal::Diagnostics),
+            Buffer::Unread(_) => Some(buffer::Internal::Unread),
+            Buffer::Ignores(_) => Some(buffer::Internal::Ignores),
         }
     }
 
@@ -205,6 +242,15 @@ impl Buffer {
             Buffer::ConfigEditor(_) => {
                 Some(data::Buffer::Internal(buffer::Internal::ConfigEditor))
             }
+            Buffer::Diagnostics(_) => {
+                Some(data::Buffer::Internal(buffer::Internal::Diagnostics))
+            }
+            Buffer::Unread(_) => {
+                Some(data::Buffer::Internal(buffer::Internal::Unread))
+            }
+            Buffer::Ignores(_) => {
+                Some(data::Buffer::Internal(buffer::Internal::Ignores))
+            }
         }
     }
 
@@ -218,7 +264,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => None,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => None,
         }
     }
 
@@ -234,7 +283,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => None,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => None,
         }
     }
 
@@ -248,7 +300,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => None,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => None,
         }
     }
 
@@ -279,7 +334,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => None,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => None,
         }
     }
 
@@ -294,6 +352,16 @@ impl Buffer {
         config: &Config,
     ) -> (Task<Message>, Option<Event>) {
         match (self, message) {
+            (Buffer::Empty, Message::Empty(message)) => {
+                let event = match message {
+                    empty::Message::Open(buffer) => Event::OpenInPane(buffer),
+                    empty::Message::Prompt(kind) => {
+                        Event::PromptOpenTarget(kind)
+                    }
+                };
+
+                (Task::none(), Some(event))
+            }
             (Buffer::Channel(state), Message::Channel(message)) => {
                 let (command, event) = state.update(
                     message,
@@ -317,9 +385,18 @@ impl Buffer {
                     channel::Event::OpenServer(server) => {
                         Event::OpenServer(server)
                     }
+                    channel::Event::OpenEmojiPicker => Event::OpenEmojiPicker,
                     channel::Event::Reconnect(server) => {
                         Event::Reconnect(server)
                     }
+                    channel::Event::UpdateAutojoin(server, from, to) => {
+                        Event::UpdateAutojoin(server, from, to)
+                    }
+                    channel::Event::TestNotification(server, notification) => {
+                        Event::TestNotification(server, notification)
+                    }
+                    channel::Event::Ignore(mask) => Event::Ignore(mask),
+                    channel::Event::Unignore(mask) => Event::Unignore(mask),
                     channel::Event::LeaveBuffers(targets, reason) => {
                         Event::LeaveBuffers(targets, reason)
                     }
@@ -336,12 +413,16 @@ impl Buffer {
                     channel::Event::ImagePreview(image) => {
                         Event::ImagePreview(image)
                     }
+                    channel::Event::LoadPreview(url) => Event::LoadPreview(url),
                     channel::Event::ExpandMessage(server_time, hash) => {
                         Event::ExpandMessage(server_time, hash)
                     }
                     channel::Event::ContractMessage(server_time, hash) => {
                         Event::ContractMessage(server_time, hash)
                     }
+                    channel::Event::ExpandReplay(from, expanded) => {
+                        Event::ExpandReplay(from, expanded)
+                    }
                     channel::Event::GoToMessage(
                         server,
                         channel,
@@ -396,9 +477,15 @@ impl Buffer {
                     server::Event::OpenServer(server) => {
                         Event::OpenServer(server)
                     }
+                    server::Event::OpenEmojiPicker => Event::OpenEmojiPicker,
                     server::Event::Reconnect(server) => {
                         Event::Reconnect(server)
                     }
+                    server::Event::TestNotification(server, notification) => {
+                        Event::TestNotification(server, notification)
+                    }
+                    server::Event::Ignore(mask) => Event::Ignore(mask),
+                    server::Event::Unignore(mask) => Event::Unignore(mask),
                     server::Event::OpenBuffers(server, targets) => {
                         Event::OpenBuffers(server, targets)
                     }
@@ -411,12 +498,16 @@ impl Buffer {
                     server::Event::ImagePreview(image) => {
                         Event::ImagePreview(image)
                     }
+                    server::Event::LoadPreview(url) => Event::LoadPreview(url),
                     server::Event::ExpandMessage(server_time, hash) => {
                         Event::ExpandMessage(server_time, hash)
                     }
                     server::Event::ContractMessage(server_time, hash) => {
                         Event::ContractMessage(server_time, hash)
                     }
+                    server::Event::ExpandReplay(from, expanded) => {
+                        Event::ExpandReplay(from, expanded)
+                    }
                     server::Event::InputSent {
                         history_task,
                         open_buffers,
@@ -466,7 +557,13 @@ impl Buffer {
                     query::Event::OpenServer(server) => {
                         Event::OpenServer(server)
                     }
+                    query::Event::OpenEmojiPicker => Event::OpenEmojiPicker,
                     query::Event::Reconnect(server) => Event::Reconnect(server),
+                    query::Event::TestNotification(server, notification) => {
+                        Event::TestNotification(server, notification)
+                    }
+                    query::Event::Ignore(mask) => Event::Ignore(mask),
+                    query::Event::Unignore(mask) => Event::Unignore(mask),
                     query::Event::LeaveBuffers(targets, reason) => {
                         Event::LeaveBuffers(targets, reason)
                     }
@@ -483,12 +580,16 @@ impl Buffer {
                     query::Event::ImagePreview(image) => {
                         Event::ImagePreview(image)
                     }
+                    query::Event::LoadPreview(url) => Event::LoadPreview(url),
                     query::Event::ExpandMessage(server_time, hash) => {
                         Event::ExpandMessage(server_time, hash)
                     }
                     query::Event::ContractMessage(server_time, hash) => {
                         Event::ContractMessage(server_time, hash)
                     }
+                    query::Event::ExpandReplay(from, expanded) => {
+                        Event::ExpandReplay(from, expanded)
+                    }
                     query::Event::InputSent {
                         history_task,
                         open_buffers,
@@ -511,6 +612,9 @@ impl Buffer {
                         upload_ids,
                         abort_registrations,
                     },
+                    query::Event::FileTransfer(id, response) => {
+                        Event::FileTransfer(id, response)
+                    }
                 });
 
                 (command.map(Message::Query), event)
@@ -560,6 +664,43 @@ impl Buffer {
 
                 (command.map(Message::ConfigEditor), event)
             }
+            (Buffer::Diagnostics(state), Message::Diagnostics(message)) => {
+                (state.update(message).map(Message::Diagnostics), None)
+            }
+            (Buffer::Unread(state), Message::Unread(message)) => {
+                let buffer_action =
+                    config.actions.buffer.click_highlight.buffer_action();
+
+                let event =
+                    state.update(message).and_then(|event| match event {
+                        unread::Event::GoTo(
+                            history::Kind::Channel(server, channel),
+                            hash,
+                        ) => Some(Event::GoToMessage(
+                            server,
+                            channel,
+                            hash,
+                            buffer_action,
+                        )),
+                        unread::Event::GoTo(
+                            history::Kind::Query(server, query),
+                            _,
+                        ) => Some(Event::OpenBuffers(
+                            server,
+                            vec![(Target::Query(query), buffer_action)],
+                        )),
+                        unread::Event::GoTo(..) => None,
+                    });
+
+                (Task::none(), event)
+            }
+            (Buffer::Ignores(state), Message::Ignores(message)) => {
+                let event = state.update(message).map(|event| match event {
+                    ignores::Event::Unignore(mask) => Event::Unignore(mask),
+                });
+
+                (Task::none(), event)
+            }
             (Buffer::Logs(state), Message::Logs(message)) => {
                 let (command, event) =
                     state.update(message, history, clients, previews, config);
@@ -576,12 +717,16 @@ impl Buffer {
                     logs::Event::ImagePreview(image) => {
                         Event::ImagePreview(image)
                     }
+                    logs::Event::LoadPreview(url) => Event::LoadPreview(url),
                     logs::Event::ExpandMessage(server_time, hash) => {
                         Event::ExpandMessage(server_time, hash)
                     }
                     logs::Event::ContractMessage(server_time, hash) => {
                         Event::ContractMessage(server_time, hash)
                     }
+                    logs::Event::ExpandReplay(from, expanded) => {
+                        Event::ExpandReplay(from, expanded)
+                    }
                 });
 
                 (command.map(Message::Logs), event)
@@ -621,12 +766,18 @@ impl Buffer {
                     highlights::Event::ImagePreview(image) => {
                         Event::ImagePreview(image)
                     }
+                    highlights::Event::LoadPreview(url) => {
+                        Event::LoadPreview(url)
+                    }
                     highlights::Event::ExpandMessage(server_time, hash) => {
                         Event::ExpandMessage(server_time, hash)
                     }
                     highlights::Event::ContractMessage(server_time, hash) => {
                         Event::ContractMessage(server_time, hash)
                     }
+                    highlights::Event::ExpandReplay(from, expanded) => {
+                        Event::ExpandReplay(from, expanded)
+                    }
                 });
 
                 (command.map(Message::Highlights), event)
@@ -643,10 +794,13 @@ impl Buffer {
         history: &'a history::Manager,
         previews: &'a preview::Collection,
         settings: Option<&'a buffer::Settings>,
+        is_archived: bool,
         config: &'a Config,
         theme: &'a Theme,
         is_focused: bool,
         sidebar: &'a sidebar::Sidebar,
+        recent_buffers: &'a VecDeque<Upstream>,
+        ignores: &'a [data::ignore::Mask],
         channel_is_focused: impl Fn(&data::Server, &target::Channel) -> bool
         + Copy
         + 'a,
@@ -655,7 +809,10 @@ impl Buffer {
         + 'a,
     ) -> Element<'a, Message> {
         match self {
-            Buffer::Empty => empty::view(config, sidebar),
+            Buffer::Empty => {
+                empty::view(config, sidebar, clients, recent_buffers)
+                    .map(Message::Empty)
+            }
             Buffer::Channel(state) => channel::view(
                 state,
                 typing_animation,
@@ -663,6 +820,8 @@ impl Buffer {
                 history,
                 previews,
                 settings,
+                is_archived,
+                ignores,
                 config,
                 theme,
                 is_focused,
@@ -686,8 +845,11 @@ impl Buffer {
                 state,
                 typing_animation,
                 clients,
+                file_transfers,
                 history,
                 previews,
+                settings,
+                ignores,
                 config,
                 theme,
                 is_focused,
@@ -732,6 +894,15 @@ impl Buffer {
                 config_editor::view(state, config, theme)
                     .map(Message::ConfigEditor)
             }
+            Buffer::Diagnostics(_) => {
+                diagnostics::view(theme).map(Message::Diagnostics)
+            }
+            Buffer::Unread(_) => {
+                unread::view(history, config, theme).map(Message::Unread)
+            }
+            Buffer::Ignores(_) => {
+                ignores::view(ignores, config, theme).map(Message::Ignores)
+            }
         }
     }
 
@@ -745,7 +916,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => false,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => false,
         }
     }
 
@@ -779,7 +953,10 @@ impl Buffer {
             Buffer::Empty
             | Buffer::FileTransfers(_)
             | Buffer::Logs(_)
-            | Buffer::Highlights(_) => widget::operate(focusable::unfocus()),
+            | Buffer::Highlights(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => widget::operate(focusable::unfocus()),
             Buffer::ConfigEditor(config_editor) => {
                 config_editor.focus().map(Message::ConfigEditor)
             }
@@ -799,7 +976,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => {}
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => {}
             Buffer::Channel(channel) => channel.reset(),
             Buffer::Server(server) => server.reset(),
             Buffer::Query(query) => query.reset(),
@@ -818,7 +998,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => (),
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => (),
             Buffer::Server(state) => state.input_view.insert_user(
                 nick,
                 state.buffer.clone(),
@@ -840,6 +1023,39 @@ impl Buffer {
         }
     }
 
+    pub fn insert_emoji_to_input(
+        &mut self,
+        emoji: &str,
+        history: &mut history::Manager,
+    ) {
+        match self {
+            Buffer::Empty
+            | Buffer::FileTransfers(_)
+            | Buffer::Logs(_)
+            | Buffer::Highlights(_)
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => (),
+            Buffer::Server(state) => state.input_view.insert_emoji(
+                emoji,
+                state.buffer.clone(),
+                history,
+            ),
+            Buffer::Channel(state) => state.input_view.insert_emoji(
+                emoji,
+                state.buffer.clone(),
+                history,
+            ),
+            Buffer::Query(state) => state.input_view.insert_emoji(
+                emoji,
+                state.buffer.clone(),
+                history,
+            ),
+        }
+    }
+
     pub fn process_input_completion_and_notice(
         &mut self,
         clients: &data::client::Map,
@@ -852,7 +1068,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => (),
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => (),
             Buffer::Server(state) => {
                 state.input_view.process_completion_and_notice(
                     &state.buffer,
@@ -884,7 +1103,10 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
-            | Buffer::ChannelDiscovery(_) => Task::none(),
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => Task::none(),
             Buffer::ConfigEditor(state) => {
                 state.scroll_up_page();
                 Task::none()
@@ -923,7 +1145,10 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
-            | Buffer::ChannelDiscovery(_) => Task::none(),
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => Task::none(),
             Buffer::ConfigEditor(state) => {
                 state.scroll_down_page();
                 Task::none()
@@ -962,7 +1187,10 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
-            | Buffer::ChannelDiscovery(_) => Task::none(),
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => Task::none(),
             Buffer::ConfigEditor(state) => {
                 state.scroll_to_start();
                 Task::none()
@@ -1002,7 +1230,10 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
-            | Buffer::ChannelDiscovery(_) => Task::none(),
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => Task::none(),
             Buffer::ConfigEditor(state) => {
                 state.scroll_to_end();
                 Task::none()
@@ -1047,7 +1278,10 @@ impl Buffer {
             Buffer::Empty
             | Buffer::FileTransfers(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => Task::none(),
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => Task::none(),
             Buffer::Channel(state) => state
                 .scroll_view
                 .scroll_to_message(
@@ -1117,7 +1351,10 @@ impl Buffer {
             Buffer::Empty
             | Buffer::FileTransfers(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => Task::none(),
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => Task::none(),
             Buffer::Channel(state) => state
                 .scroll_view
                 .scroll_to_backlog(
@@ -1174,7 +1411,10 @@ impl Buffer {
             Buffer::Empty
             | Buffer::FileTransfers(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => false,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => false,
             Buffer::Channel(state) => state.scroll_view.has_pending_scroll_to(),
             Buffer::Server(state) => state.scroll_view.has_pending_scroll_to(),
             Buffer::Query(state) => state.scroll_view.has_pending_scroll_to(),
@@ -1194,7 +1434,10 @@ impl Buffer {
             Buffer::Empty
             | Buffer::FileTransfers(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => Task::none(),
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => Task::none(),
             Buffer::Channel(state) => state
                 .scroll_view
                 .prepare_for_pending_scroll_to(
@@ -1255,7 +1498,10 @@ impl Buffer {
             Buffer::Empty
             | Buffer::FileTransfers(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => None,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => None,
             Buffer::Channel(channel) => {
                 Some(channel.scroll_view.is_scrolled_to_bottom())
             }
@@ -1279,7 +1525,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => false,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => false,
             Buffer::Server(state) => state.input_view.close_picker(),
             Buffer::Channel(state) => state.input_view.close_picker(),
             Buffer::Query(state) => state.input_view.close_picker(),
@@ -1297,7 +1546,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => false,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => false,
             Buffer::Server(state) => state.input_view.clear_draft_reply(
                 &state.buffer,
                 history,
@@ -1321,7 +1573,10 @@ impl Buffer {
             Buffer::Empty
             | Buffer::FileTransfers(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => (),
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => (),
             Buffer::Channel(channel) => {
                 channel.scroll_view.update_pane_size(pane_size, config);
             }
@@ -1347,7 +1602,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => None,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => None,
             Buffer::Server(state) => state.input_view.draft_reply(),
             Buffer::Channel(state) => state.input_view.draft_reply(),
             Buffer::Query(state) => state.input_view.draft_reply(),
@@ -1361,7 +1619,10 @@ impl Buffer {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => (),
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => (),
             Buffer::Server(state) => {
                 state.input_view.set_reply_preview(reply_preview);
             }
@@ -1387,6 +1648,9 @@ impl fmt::Display for Buffer {
             Buffer::Highlights(_) => write!(f, "Highlights"),
             Buffer::ChannelDiscovery(_) => write!(f, "Channel Discovery"),
             Buffer::ConfigEditor(_) => write!(f, "Config Editor"),
+            Buffer::Diagnostics(_) => write!(f, "Diagnostics"),
+            Buffer::Unread(_) => write!(f, "Unread"),
+            Buffer::Ignores(_) => write!(f, "Ignores"),
         }
     }
 }
@@ -2,19 +2,23 @@ use chrono::{DateTime, Utc};
 use data::dashboard::BufferAction;
 use data::history::filter::FilterChain;
 use data::preview::{self, Previews};
+use data::rate_limit::TokenPriority;
 use data::server::Server;
 use data::target::{self, Target};
 use data::user::{ChannelUsers, Nick};
-use data::{Config, Image, Preview, User, buffer, client, history, message};
-use iced::widget::{column, container, row, stack};
+use data::{
+    Config, Image, Preview, User, buffer, client, command, history, message,
+};
+use iced::widget::{button, column, container, row, stack};
 use iced::{Length, Size, Task, padding};
 
 use super::message_view::{ChannelQueryLayout, TargetInfo};
 use super::{context_menu, input_view, scroll_view, typing};
-use crate::Theme;
-use crate::widget::Element;
+use crate::widget::{Element, text};
 use crate::window::Window;
+use crate::{Theme, font, theme};
 
+mod moderation_queue;
 mod topic;
 
 #[derive(Debug, Clone)]
@@ -23,6 +27,10 @@ pub enum Message {
     InputView(input_view::Message),
     ContextMenu(context_menu::Message),
     Topic(topic::Message),
+    ModerationQueue(moderation_queue::Message),
+    Rejoin,
+    UpdateAutojoin(target::Channel),
+    DismissForward,
     FilehostUploadDone { id: u32, url: Option<String> },
     FilesDropped(Vec<std::path::PathBuf>),
 }
@@ -32,7 +40,14 @@ pub enum Event {
     OpenBuffers(Server, Vec<(Target, BufferAction)>),
     OpenInternalBuffer(buffer::Internal),
     OpenServer(String),
+    OpenEmojiPicker,
     Reconnect(Server),
+    /// Replace the first channel with the second in the server's autojoin
+    /// channels.
+    UpdateAutojoin(Server, target::Channel, target::Channel),
+    TestNotification(Server, data::Notification),
+    Ignore(data::ignore::Mask),
+    Unignore(String),
     LeaveBuffers(Vec<Target>, Option<String>),
     History(Task<history::manager::Message>),
     RequestOlderChatHistory,
@@ -41,8 +56,10 @@ pub enum Event {
     MarkAsRead(history::Kind),
     OpenUrl(String),
     ImagePreview(Image),
+    LoadPreview(url::Url),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
+    ExpandReplay(DateTime<Utc>, bool),
     GoToMessage(Server, target::Channel, message::Hash, BufferAction),
     InputSent {
         history_task: Task<history::manager::Message>,
@@ -65,6 +82,8 @@ pub fn view<'a>(
     history: &'a history::Manager,
     previews: &'a preview::Collection,
     settings: Option<&'a buffer::Settings>,
+    is_archived: bool,
+    ignores: &'a [data::ignore::Mask],
     config: &'a Config,
     theme: &'a Theme,
     is_focused: bool,
@@ -119,7 +138,9 @@ pub fn view<'a>(
         casemapping,
         prefix,
         registry,
+        nick_history: clients.get_nick_history(server),
         confirm_message_delivery,
+        deferred: clients.get_server_deferred(server),
         can_send_reactions,
         can_redact,
         can_send_replies,
@@ -134,6 +155,7 @@ pub fn view<'a>(
             our_user,
         },
         history,
+        ignores,
     };
 
     let messages = container(
@@ -148,12 +170,12 @@ pub fn view<'a>(
                     Some(channel),
                     Some(server),
                     casemapping,
-                    previews.cards_visibility(),
-                    previews.images_visibility(),
+                    previews,
                     &config.preview,
                 )
             }),
             chathistory_state,
+            settings.map(|settings| &settings.hidden_messages),
             typing::reserved_bottom_padding(
                 has_typing_text,
                 typing_style,
@@ -176,6 +198,7 @@ pub fn view<'a>(
         prefix,
         channel,
         clients.get_registry(server),
+        clients.get_nick_history(server),
         previews.collection(),
         users,
         our_user,
@@ -184,6 +207,20 @@ pub fn view<'a>(
     )
     .map(Message::ContextMenu);
 
+    let moderation_queue = config
+        .buffer
+        .channel
+        .moderation_queue
+        .enabled
+        .then(|| {
+            clients
+                .get_channel_moderation_queue(server, channel)
+                .zip(users)
+                .and_then(|(queue, users)| moderation_queue::view(queue, users))
+        })
+        .flatten()
+        .map(|view| view.map(Message::ModerationQueue));
+
     // If topic toggles from None to Some then it messes with messages' scroll state,
     // so produce a zero-height placeholder when topic is None.
     let topic = topic(
@@ -209,21 +246,29 @@ pub fn view<'a>(
 
     let filehost_url = clients.get_filehost(server);
 
-    let text_input = show_text_input.then(move || {
-        input_view::view(
-            &state.input_view,
-            our_user,
-            users,
-            &state.server,
-            registry,
-            config,
-            theme,
-            filehost_url,
-        )
-        .map(Message::InputView)
-    });
+    let text_input = if is_archived {
+        Some(archived_banner(theme))
+    } else {
+        show_text_input.then(move || {
+            input_view::view(
+                &state.input_view,
+                our_user,
+                users,
+                &state.server,
+                registry,
+                config,
+                theme,
+                filehost_url,
+            )
+            .map(Message::InputView)
+        })
+    };
 
-    let content = column![topic, messages];
+    let forwarded = clients
+        .get_channel_forwarded_from(server, channel)
+        .map(|from| forwarded_banner(from, channel, server, config, theme));
+
+    let content = column![forwarded, topic, messages];
 
     let show_nicklist = settings
         .map_or(config.buffer.channel.nicklist.enabled, |settings| {
@@ -236,6 +281,7 @@ pub fn view<'a>(
             (true, data::channel::Position::Right) => row![content, nick_list],
             (false, _) => { row![content] }.height(Length::Fill),
         }
+        .extend(moderation_queue)
         .spacing(4)
         .padding(padding::left(8).right(8));
 
@@ -266,6 +312,76 @@ pub fn view<'a>(
         .into()
 }
 
+/// Shown in place of the text input of a channel we've left.
+fn archived_banner<'a>(theme: &'a Theme) -> Element<'a, Message> {
+    container(
+        row![
+            text("You have left this channel")
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get))
+                .width(Length::Fill),
+            button(text("Rejoin"))
+                .padding([2, 6])
+                .style(|theme, status| {
+                    theme::button::secondary(theme, status, false)
+                })
+                .on_press(Message::Rejoin),
+        ]
+        .spacing(6)
+        .align_y(iced::Alignment::Center),
+    )
+    .padding(padding::top(4).bottom(4).right(8).left(8))
+    .width(Length::Fill)
+    .into()
+}
+
+/// Explains that our join was forwarded to this channel, offering to
+/// autojoin it in place of the one asked for.
+fn forwarded_banner<'a>(
+    from: &target::Channel,
+    to: &target::Channel,
+    server: &Server,
+    config: &Config,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    let in_autojoin = config.servers.get(server).is_some_and(|server| {
+        server
+            .channels
+            .iter()
+            .any(|channel| channel.eq_ignore_ascii_case(from.as_str()))
+    });
+
+    let small_button = |label: &'static str, message: Message| {
+        button(text(label))
+            .padding([2, 6])
+            .style(|theme, status| {
+                theme::button::secondary(theme, status, false)
+            })
+            .on_press(message)
+    };
+
+    container(
+        row![
+            text(format!("Joining {from} was forwarded to {to}"))
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get))
+                .width(Length::Fill),
+            in_autojoin.then(|| {
+                small_button(
+                    "Autojoin this channel instead",
+                    Message::UpdateAutojoin(from.clone()),
+                )
+            }),
+            small_button("Dismiss", Message::DismissForward),
+        ]
+        .spacing(6)
+        .align_y(iced::Alignment::Center),
+    )
+    .padding(padding::top(4).bottom(4).right(8).left(8))
+    .width(Length::Fill)
+    .into()
+}
+
 #[derive(Debug, Clone)]
 pub struct Channel {
     pub buffer: buffer::Upstream,
@@ -401,12 +517,18 @@ impl Channel {
                     scroll_view::Event::ImagePreview(image) => {
                         Some(Event::ImagePreview(image))
                     }
+                    scroll_view::Event::LoadPreview(url) => {
+                        Some(Event::LoadPreview(url))
+                    }
                     scroll_view::Event::ExpandMessage(server_time, hash) => {
                         Some(Event::ExpandMessage(server_time, hash))
                     }
                     scroll_view::Event::ContractMessage(server_time, hash) => {
                         Some(Event::ContractMessage(server_time, hash))
                     }
+                    scroll_view::Event::ExpandReplay(from, expanded) => {
+                        Some(Event::ExpandReplay(from, expanded))
+                    }
                 });
 
                 (command.map(Message::ScrollView), event)
@@ -461,9 +583,25 @@ impl Channel {
                     Some(input_view::Event::OpenServer(server)) => {
                         (command, Some(Event::OpenServer(server)))
                     }
+                    Some(input_view::Event::OpenEmojiPicker) => {
+                        (command, Some(Event::OpenEmojiPicker))
+                    }
                     Some(input_view::Event::Reconnect(server)) => {
                         (command, Some(Event::Reconnect(server)))
                     }
+                    Some(input_view::Event::TestNotification(
+                        server,
+                        notification,
+                    )) => (
+                        command,
+                        Some(Event::TestNotification(server, notification)),
+                    ),
+                    Some(input_view::Event::Ignore(mask)) => {
+                        (command, Some(Event::Ignore(mask)))
+                    }
+                    Some(input_view::Event::Unignore(mask)) => {
+                        (command, Some(Event::Unignore(mask)))
+                    }
                     Some(input_view::Event::FilehostUpload {
                         server,
                         target,
@@ -527,6 +665,77 @@ impl Channel {
                     }),
                 )
             }
+            Message::ModerationQueue(message) => {
+                let (nick, grant) = match message {
+                    moderation_queue::Message::Grant(nick) => (nick, true),
+                    moderation_queue::Message::Deny(nick) => (nick, false),
+                };
+
+                if let Some(request) = clients.remove_moderation_request(
+                    &self.server,
+                    &self.target,
+                    nick.as_nickref(),
+                ) && grant
+                {
+                    let command = match request.kind {
+                        data::moderation_queue::Kind::Knock => {
+                            command::Irc::Invite(
+                                nick.to_string(),
+                                self.target.to_string(),
+                            )
+                        }
+                        data::moderation_queue::Kind::Message => {
+                            command::Irc::Mode(
+                                self.target.to_string(),
+                                Some("+v".to_string()),
+                                Some(vec![nick.to_string()]),
+                            )
+                        }
+                    };
+
+                    let input =
+                        data::Input::from_command(self.buffer.clone(), command);
+
+                    if let Some(encoded) = input.encoded() {
+                        clients.send(
+                            &self.buffer,
+                            encoded,
+                            TokenPriority::User,
+                        );
+                    }
+                }
+
+                (Task::none(), None)
+            }
+            Message::UpdateAutojoin(from) => {
+                clients.dismiss_channel_forward(&self.server, &self.target);
+
+                (
+                    Task::none(),
+                    Some(Event::UpdateAutojoin(
+                        self.server.clone(),
+                        from,
+                        self.target.clone(),
+                    )),
+                )
+            }
+            Message::DismissForward => {
+                clients.dismiss_channel_forward(&self.server, &self.target);
+
+                (Task::none(), None)
+            }
+            Message::Rejoin => {
+                let input = data::Input::from_command(
+                    self.buffer.clone(),
+                    command::Irc::Join(self.target.to_string(), None),
+                );
+
+                if let Some(encoded) = input.encoded() {
+                    clients.send(&self.buffer, encoded, TokenPriority::User);
+                }
+
+                (Task::none(), None)
+            }
             Message::Topic(message) => (
                 Task::none(),
                 topic::update(message).map(|event| match event {
@@ -632,6 +841,7 @@ fn topic<'a>(
             config,
             theme,
             clients.get_registry(&state.server),
+            clients.get_nick_history(&state.server),
             previews,
             channel_is_focused,
             channel_is_open,
@@ -642,14 +852,16 @@ fn topic<'a>(
 
 mod nick_list {
     use context_menu::Message;
+    use data::channel::Position;
+    use data::nick_history::NickHistory;
     use data::user::ChannelUsers;
     use data::{Config, Server, User, isupport, metadata, target};
     use iced::Length;
     use iced::widget::{Scrollable, column, scrollable};
 
     use crate::buffer::context_menu;
-    use crate::widget::Element;
     use crate::widget::user_display::UserDisplay;
+    use crate::widget::{Element, tooltip};
     use crate::{Theme, theme};
 
     pub fn view<'a>(
@@ -657,6 +869,7 @@ mod nick_list {
         prefix: &'a [isupport::PrefixMap],
         channel: &'a target::Channel,
         registry: &'a dyn metadata::Registry,
+        nick_history: &'a NickHistory,
         previews: &'a data::preview::Collection,
         users: Option<&'a ChannelUsers>,
         our_user: Option<&'a User>,
@@ -701,8 +914,14 @@ mod nick_list {
             }
         };
 
+        // Shown on the side facing the messages.
+        let tooltip_position = match nicklist_config.position {
+            Position::Left => tooltip::Position::Right,
+            Position::Right => tooltip::Position::Left,
+        };
+
         let rows = user_displays.into_iter().map(|(user, user_display)| {
-            context_menu::user(
+            let entry = context_menu::user(
                 user_display.into_element(
                     user,
                     user.is_away(),
@@ -718,6 +937,7 @@ mod nick_list {
                 prefix,
                 Some(channel),
                 registry,
+                nick_history,
                 previews,
                 user,
                 Some(user),
@@ -730,7 +950,15 @@ mod nick_list {
                     .click_username
                     .as_ref()
                     .unwrap_or(&config.actions.buffer.click_username),
-            )
+            );
+
+            let account = nicklist_config
+                .show_account
+                .then(|| user.accountname())
+                .flatten()
+                .map(|accountname| format!("Logged in as {accountname}"));
+
+            tooltip(entry, account, tooltip_position, theme)
         });
 
         let content = column(rows);
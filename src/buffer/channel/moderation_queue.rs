@@ -0,0 +1,87 @@
+use data::i18n;
+use data::moderation_queue::{Kind, Queue};
+use data::user::{ChannelUsers, Nick};
+use iced::widget::{Scrollable, button, column, container, row, scrollable};
+use iced::{Length, padding};
+
+use crate::theme;
+use crate::widget::{Element, text};
+
+const WIDTH: f32 = 200.0;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Grant(Nick),
+    Deny(Nick),
+}
+
+/// Pending requests, or `None` when there are none to show.
+pub fn view<'a>(
+    queue: &'a Queue,
+    users: &'a ChannelUsers,
+) -> Option<Element<'a, Message>> {
+    let mut requests = queue.pending(users).peekable();
+
+    requests.peek()?;
+
+    let small_button = |label: &'static str, message: Message| {
+        button(text(label))
+            .padding([2, 6])
+            .style(|theme, status| {
+                theme::button::secondary(theme, status, false)
+            })
+            .on_press(message)
+    };
+
+    let rows = requests.map(|request| {
+        let nick = request.user.nickname().to_owned();
+
+        let (grant, description) = match request.kind {
+            Kind::Knock => (
+                i18n::tr("moderation_queue.invite"),
+                i18n::tr("moderation_queue.knocked"),
+            ),
+            Kind::Message => (
+                i18n::tr("moderation_queue.voice"),
+                i18n::tr("moderation_queue.voice_requested"),
+            ),
+        };
+
+        column![
+            text(nick.to_string()),
+            text(request.text.as_deref().unwrap_or(description))
+                .style(theme::text::secondary),
+            row![
+                small_button(grant, Message::Grant(nick.clone())),
+                small_button(
+                    i18n::tr("moderation_queue.deny"),
+                    Message::Deny(nick),
+                ),
+            ]
+            .spacing(4),
+        ]
+        .spacing(2)
+        .into()
+    });
+
+    let content = column![
+        text(i18n::tr("moderation_queue.requests"))
+            .style(theme::text::secondary),
+        column(rows).spacing(8)
+    ]
+    .spacing(8)
+    .padding(padding::right(4));
+
+    Some(
+        container(
+            Scrollable::new(content)
+                .direction(scrollable::Direction::Vertical(
+                    scrollable::Scrollbar::new().width(1).scroller_width(1),
+                ))
+                .style(theme::scrollable::hidden),
+        )
+        .width(Length::Fixed(WIDTH))
+        .height(Length::Fill)
+        .into(),
+    )
+}
@@ -62,6 +62,7 @@ pub fn view<'a>(
     config: &'a Config,
     theme: &'a Theme,
     registry: &'a dyn data::metadata::Registry,
+    nick_history: &'a data::nick_history::NickHistory,
     previews: &'a data::preview::Collection,
     channel_is_focused: impl Fn(&Server, &target::Channel) -> bool + Copy + 'a,
     channel_is_open: impl Fn(&Server, &target::Channel) -> bool + Copy + 'a,
@@ -92,6 +93,7 @@ pub fn view<'a>(
             prefix,
             Some(channel),
             registry,
+            nick_history,
             previews,
             user,
             user_in_channel,
@@ -175,6 +177,7 @@ pub fn view<'a>(
                             prefix,
                             channel: Some(channel),
                             registry,
+                            nick_history,
                             avatar: context_menu::user_avatar(
                                 user, registry, previews,
                             ),
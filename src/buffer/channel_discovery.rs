@@ -1,6 +1,6 @@
 use data::dashboard::BufferAction;
 use data::{
-    Config, Server, User, channel_discovery, message, metadata, target,
+    Config, Server, User, channel_discovery, i18n, message, metadata, target,
 };
 use iced::widget::{
     self, button, center, column, container, operation, pick_list, row, rule,
@@ -177,11 +177,11 @@ pub fn view<'a>(
                     container(center(unsafe_list_view(server, theme)))
                 } else {
                     let reason = if !clients.get_server_is_connected(server) {
-                        "Disconnected from server"
+                        i18n::tr("channel_discovery.disconnected")
                     } else {
                         match manager.status {
                             Some(channel_discovery::Status::Updated(_)) => {
-                                "No channels found"
+                                i18n::tr("channel_discovery.no_channels")
                             }
                             _ => "...",
                         }
@@ -307,9 +307,11 @@ fn channel_list_view<'a>(
                         .map(Message::ContextMenu)
                 },
             );
-            let user_count_text =
-                selectable_text(format!("{user_count} users"))
-                    .style(theme::selectable_text::timestamp);
+            let user_count_text = selectable_text(i18n::tr_args(
+                "channel_discovery.users",
+                &[("count", &i18n::format_number(*user_count as u64))],
+            ))
+            .style(theme::selectable_text::timestamp);
 
             let has_topic = topic_content.text().is_empty();
             let topic_text = if has_topic {
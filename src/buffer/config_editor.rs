@@ -226,7 +226,10 @@ impl ConfigEditor {
                 (
                     Task::perform(
                         async move {
-                            tokio::fs::write(Config::path(), contents)
+                            let path = Config::writable_path()
+                                .map_err(|error| error.to_string())?;
+
+                            tokio::fs::write(path, contents)
                                 .await
                                 .map_err(|error| error.to_string())
                         },
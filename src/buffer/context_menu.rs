@@ -4,10 +4,12 @@ use std::string::ToString;
 use chrono::{DateTime, Utc};
 use data::config::actions::NicknameClickAction;
 use data::dashboard::BufferAction;
+use data::nick_history::{self, NickHistory};
 use data::target::Target;
 use data::user::Nick;
 use data::{
     Config, Server, User, ctcp, isupport, message, metadata, preview, target,
+    timed_ban,
 };
 use iced::widget::{Space, button, center, column, container, row, rule, span};
 use iced::{
@@ -28,6 +30,7 @@ pub struct UserContext<'a> {
     pub prefix: &'a [isupport::PrefixMap],
     pub channel: Option<&'a target::Channel>,
     pub registry: &'a dyn metadata::Registry,
+    pub nick_history: &'a NickHistory,
     pub avatar: Option<UserAvatar<'a>>,
     pub user: &'a User,
     pub current_user: Option<&'a User>,
@@ -51,6 +54,7 @@ pub enum Context<'a> {
     Channel(ChannelContext<'a>),
     Timestamp(&'a DateTime<Utc>),
     NotSentMessage(&'a DateTime<Utc>, &'a message::Hash),
+    DeferredMessage(&'a DateTime<Utc>, &'a message::Hash),
     Message {
         message: &'a message::Message,
         selected_reactions: &'a [String],
@@ -120,6 +124,7 @@ pub enum Entry {
     Query,
     ToggleAccessLevelOp,
     ToggleAccessLevelVoice,
+    Ban(Option<timed_ban::Duration>),
     SendFile,
     UserInfo,
     UserMetadata,
@@ -141,6 +146,8 @@ pub enum Entry {
     // not sent message context
     DeleteMessage,
     ResendMessage,
+    // deferred message context
+    CancelMessage,
     // message context
     CopyMessage,
     CopyRedaction,
@@ -149,6 +156,7 @@ pub enum Entry {
     Redact,
     HideWithRedaction,
     ShowRedactedMessage,
+    ExportHighlight,
 }
 
 #[derive(Debug, Clone)]
@@ -219,6 +227,10 @@ impl Entry {
         }
     }
 
+    pub fn deferred_message_list() -> Vec<Self> {
+        vec![Entry::CancelMessage]
+    }
+
     pub fn timestamp_list() -> Vec<Self> {
         vec![Entry::Timestamp]
     }
@@ -229,6 +241,7 @@ impl Entry {
         can_send_reactions: bool,
         can_redact: bool,
         can_send_replies: bool,
+        can_export_highlight: bool,
     ) -> Vec<Self> {
         let mut entries = vec![];
 
@@ -247,6 +260,10 @@ impl Entry {
             entries.push(Entry::CopyRedaction);
         }
 
+        if can_export_highlight {
+            entries.push(Entry::ExportHighlight);
+        }
+
         if can_send_replies || can_send_reactions || can_redact {
             entries.push(Entry::HorizontalRule);
         }
@@ -366,7 +383,7 @@ impl Entry {
 
                 list
             } else if our_user.is_some_and(|u| {
-                u.has_access_level(data::user::AccessLevel::Oper)
+                u.highest_access_level() >= data::user::AccessLevel::Oper
             }) {
                 let mut list = user_info_entries;
                 list.extend([
@@ -383,6 +400,15 @@ impl Entry {
                     Entry::HorizontalRule,
                     Entry::ToggleAccessLevelOp,
                     Entry::ToggleAccessLevelVoice,
+                    Entry::HorizontalRule,
+                    Entry::Ban(None),
+                ]);
+                list.extend(
+                    timed_ban::Duration::ALL
+                        .iter()
+                        .map(|duration| Entry::Ban(Some(*duration))),
+                );
+                list.extend(vec![
                     Entry::HorizontalRule,
                     Entry::CtcpRequestVersion,
                     Entry::CtcpRequestTime,
@@ -554,6 +580,37 @@ impl Entry {
 
                 menu_button(label, message, length, theme, config)
             }
+            (
+                Entry::Ban(duration),
+                Context::User(UserContext {
+                    server,
+                    channel,
+                    user,
+                    current_user,
+                    ..
+                }),
+            ) => {
+                // The channel user is more likely to know the hostname.
+                let user = current_user
+                    .filter(|user| user.hostname().is_some())
+                    .unwrap_or(user);
+
+                let label = match duration {
+                    Some(duration) => format!("Ban for {duration}"),
+                    None => format!("Ban (+b {})", timed_ban::mask(user)),
+                };
+
+                let message = channel.map(|channel| {
+                    Message::Ban(
+                        server.clone(),
+                        channel.clone(),
+                        user.clone(),
+                        duration,
+                    )
+                });
+
+                menu_button(label, message, length, theme, config)
+            }
             (
                 Entry::SendFile,
                 Context::User(UserContext { server, user, .. }),
@@ -561,7 +618,7 @@ impl Entry {
                 let message = Message::SendFile(server.clone(), user.clone());
 
                 menu_button(
-                    "Send File".to_string(),
+                    "Send File(s)…".to_string(),
                     Some(message),
                     length,
                     theme,
@@ -571,15 +628,32 @@ impl Entry {
             (
                 Entry::UserInfo,
                 Context::User(UserContext {
-                    user, current_user, ..
+                    user,
+                    current_user,
+                    nick_history,
+                    ..
                 }),
-            ) => user_info(
-                current_user,
-                user.nickname().to_owned(),
-                length,
-                config,
-                theme,
-            ),
+            ) => {
+                // The channel user knows the account name, the message user
+                // may only know the hostmask.
+                let known_user = current_user.unwrap_or(user);
+
+                let previous_nicks = nick_history.previous(
+                    known_user.accountname(),
+                    nick_history::userhost(known_user)
+                        .or_else(|| nick_history::userhost(user)),
+                    user.nickname(),
+                );
+
+                user_info(
+                    current_user,
+                    user.nickname().to_owned(),
+                    &previous_nicks,
+                    length,
+                    config,
+                    theme,
+                )
+            }
             (
                 Entry::UserMetadata,
                 Context::User(UserContext {
@@ -812,6 +886,20 @@ impl Entry {
                     config,
                 )
             }
+            (
+                Entry::CancelMessage,
+                Context::DeferredMessage(server_time, hash),
+            ) => {
+                let message = Message::CancelMessage(*server_time, *hash);
+
+                menu_button(
+                    "Cancel Message".to_string(),
+                    Some(message),
+                    length,
+                    theme,
+                    config,
+                )
+            }
             (Entry::CopyMessage, Context::Message { message, .. }) => {
                 menu_button(
                     "Copy message".to_string(),
@@ -821,6 +909,17 @@ impl Entry {
                     config,
                 )
             }
+            (Entry::ExportHighlight, Context::Message { message, .. }) => {
+                menu_button(
+                    "Send to task manager".to_string(),
+                    Some(Message::ExportHighlight(Box::new(
+                        (*message).clone(),
+                    ))),
+                    length,
+                    theme,
+                    config,
+                )
+            }
             (
                 Entry::CopyRedaction,
                 Context::Message { message, .. }
@@ -978,6 +1077,7 @@ pub enum Message {
     Whowas(Server, Nick),
     OpenTarget(Server, Target, BufferAction),
     ToggleAccessLevel(Server, target::Channel, Nick, String),
+    Ban(Server, target::Channel, User, Option<timed_ban::Duration>),
     SendFile(Server, User),
     InsertNickname(Nick),
     CtcpRequest(ctcp::Command, Server, Nick, Option<String>),
@@ -990,6 +1090,8 @@ pub enum Message {
     DeleteMessage(DateTime<Utc>, message::Hash),
     #[allow(clippy::enum_variant_names)]
     ResendMessage(DateTime<Utc>, message::Hash),
+    #[allow(clippy::enum_variant_names)]
+    CancelMessage(DateTime<Utc>, message::Hash),
     OpenReactionModal(message::Id, Vec<String>),
     Redact(message::Id),
     Reply {
@@ -1003,6 +1105,7 @@ pub enum Message {
     ExpandMessage(DateTime<Utc>, message::Hash),
     #[allow(clippy::enum_variant_names)]
     ContractMessage(DateTime<Utc>, message::Hash),
+    ExportHighlight(Box<message::Message>),
 }
 
 #[derive(Debug, Clone)]
@@ -1011,6 +1114,7 @@ pub enum Event {
     SendWhowas(Server, Nick),
     OpenTarget(Server, Target, BufferAction),
     ToggleAccessLevel(Server, target::Channel, Nick, String),
+    Ban(Server, target::Channel, User, Option<timed_ban::Duration>),
     SendFile(Server, User),
     InsertNickname(Nick),
     CtcpRequest(ctcp::Command, Server, Nick, Option<String>),
@@ -1021,6 +1125,7 @@ pub enum Event {
     CopyTimestamp(DateTime<Utc>),
     DeleteMessage(DateTime<Utc>, message::Hash),
     ResendMessage(DateTime<Utc>, message::Hash),
+    CancelMessage(DateTime<Utc>, message::Hash),
     OpenReactionModal(message::Id, Vec<String>),
     RedactMessage(message::Id),
     Reply {
@@ -1031,6 +1136,7 @@ pub enum Event {
     LoadUserAvatar(Server, url::Url),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
+    ExportHighlight(Box<message::Message>),
 }
 
 pub fn update(message: Message) -> Option<Event> {
@@ -1043,6 +1149,9 @@ pub fn update(message: Message) -> Option<Event> {
         Message::ToggleAccessLevel(server, target, nick, mode) => {
             Some(Event::ToggleAccessLevel(server, target, nick, mode))
         }
+        Message::Ban(server, channel, user, duration) => {
+            Some(Event::Ban(server, channel, user, duration))
+        }
         Message::SendFile(server, user) => Some(Event::SendFile(server, user)),
         Message::InsertNickname(nick) => Some(Event::InsertNickname(nick)),
         Message::CtcpRequest(command, server, nick, params) => {
@@ -1065,6 +1174,9 @@ pub fn update(message: Message) -> Option<Event> {
         Message::ResendMessage(server_time, hash) => {
             Some(Event::ResendMessage(server_time, hash))
         }
+        Message::CancelMessage(server_time, hash) => {
+            Some(Event::CancelMessage(server_time, hash))
+        }
         Message::OpenReactionModal(msgid, selected_reactions) => {
             Some(Event::OpenReactionModal(msgid, selected_reactions))
         }
@@ -1089,6 +1201,9 @@ pub fn update(message: Message) -> Option<Event> {
         Message::ContractMessage(server_time, hash) => {
             Some(Event::ContractMessage(server_time, hash))
         }
+        Message::ExportHighlight(message) => {
+            Some(Event::ExportHighlight(message))
+        }
     }
 }
 
@@ -1117,6 +1232,9 @@ where
         can_send_replies
             && message.id.is_some()
             && message.rerouted_from.is_none(),
+        config.highlights.export.command.is_some()
+            && (matches!(message.target, message::Target::Highlights { .. })
+                || message.triggers_highlight()),
     );
 
     context_menu(
@@ -1197,6 +1315,7 @@ pub fn user<'a>(
     prefix: &'a [isupport::PrefixMap],
     channel: Option<&'a target::Channel>,
     registry: &'a dyn metadata::Registry,
+    nick_history: &'a NickHistory,
     previews: &'a preview::Collection,
     user: &'a User,
     current_user: Option<&'a User>,
@@ -1220,6 +1339,7 @@ pub fn user<'a>(
         prefix,
         channel,
         registry,
+        nick_history,
         previews,
         user,
         current_user,
@@ -1235,6 +1355,7 @@ pub fn rerouted_message_user<'a>(
     server: &'a Server,
     prefix: &'a [isupport::PrefixMap],
     registry: &'a dyn metadata::Registry,
+    nick_history: &'a NickHistory,
     previews: &'a preview::Collection,
     user: &'a User,
     config: &'a Config,
@@ -1247,6 +1368,7 @@ pub fn rerouted_message_user<'a>(
         prefix,
         None,
         registry,
+        nick_history,
         previews,
         user,
         None,
@@ -1263,6 +1385,7 @@ fn user_with_entries<'a>(
     prefix: &'a [isupport::PrefixMap],
     channel: Option<&'a target::Channel>,
     registry: &'a dyn metadata::Registry,
+    nick_history: &'a NickHistory,
     previews: &'a preview::Collection,
     user: &'a User,
     current_user: Option<&'a User>,
@@ -1317,6 +1440,7 @@ fn user_with_entries<'a>(
                     prefix,
                     channel,
                     registry,
+                    nick_history,
                     avatar: avatar.clone(),
                     user,
                     current_user,
@@ -1391,6 +1515,34 @@ pub fn not_sent_message<'a>(
     .into()
 }
 
+pub fn deferred_message<'a>(
+    content: impl Into<Element<'a, Message>>,
+    server_time: &'a DateTime<Utc>,
+    hash: &'a message::Hash,
+    config: &'a Config,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    let entries = Entry::deferred_message_list();
+
+    context_menu(
+        context_menu::MouseButton::Left,
+        context_menu::Anchor::Cursor,
+        context_menu::ToggleBehavior::KeepOpen,
+        Some(mouse::Interaction::Pointer),
+        content,
+        entries,
+        move |entry, length| {
+            entry.view(
+                Some(Context::DeferredMessage(server_time, hash)),
+                length,
+                config,
+                theme,
+            )
+        },
+    )
+    .into()
+}
+
 fn menu_button(
     content: String,
     message: Option<Message>,
@@ -1424,6 +1576,7 @@ fn right_justified_padding(config: &Config) -> Padding {
 fn user_info<'a>(
     current_user: Option<&User>,
     nickname: Nick,
+    previous_nicks: &[&str],
     length: Length,
     config: &Config,
     theme: &Theme,
@@ -1464,19 +1617,46 @@ fn user_info<'a>(
         .is_offline(current_user.is_none());
     let style = theme::text::nickname(
         theme,
-        &config.buffer.nickname.color,
+        &config.buffer.nickname,
         Some(nickname.seed()),
         is_user_away,
         is_user_offline,
     );
 
     let nickname = text(nickname.to_string()).style(move |_| style).font_maybe(
-        theme::font_style::nickname(theme, is_user_offline).map(font::get),
+        theme::font_style::nickname(
+            theme,
+            is_user_away.is_some(),
+            is_user_offline,
+        )
+        .map(font::get),
     );
 
+    let account = current_user.and_then(User::accountname).map(|accountname| {
+        container(
+            text(format!("account: {accountname}"))
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get))
+                .width(length),
+        )
+        .padding(right_justified_padding(config).top(0))
+    });
+
+    let previous_nicks = (!previous_nicks.is_empty()).then(|| {
+        container(
+            text(format!("was: {}", previous_nicks.join(", ")))
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get))
+                .width(length),
+        )
+        .padding(right_justified_padding(config).top(0))
+    });
+
     column![
         container(row![nickname, state].width(length).spacing(4))
-            .padding(right_justified_padding(config))
+            .padding(right_justified_padding(config)),
+        account,
+        previous_nicks,
     ]
     .into()
 }
@@ -1630,9 +1810,9 @@ pub fn user_avatar<'a>(
     previews: &'a preview::Collection,
 ) -> Option<UserAvatar<'a>> {
     avatar_url(user, registry).map(|url| match previews.get(&url) {
-        Some(preview::State::Loaded(preview)) => {
-            UserAvatar::Loaded(preview.image())
-        }
+        Some(preview::State::Loaded(preview)) => preview
+            .image()
+            .map_or(UserAvatar::Pending, UserAvatar::Loaded),
         _ => UserAvatar::Pending,
     })
 }
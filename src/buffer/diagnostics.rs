@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use data::diagnostics::{self, Snapshot};
+use data::i18n;
+use iced::widget::{
+    Scrollable, button, column, container, row, scrollable, text,
+};
+use iced::{Length, Task, clipboard};
+
+use crate::widget::Element;
+use crate::{Theme, font, theme};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Copy,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics;
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::Copy => {
+                clipboard::write(diagnostics::snapshot().to_metrics()).discard()
+            }
+        }
+    }
+}
+
+pub fn view<'a>(theme: &'a Theme) -> Element<'a, Message> {
+    let Snapshot {
+        connections,
+        history_writes,
+        mut buffers,
+    } = diagnostics::snapshot();
+
+    let section = |title: &'static str, rows: Vec<(String, String)>| {
+        column![
+            text(title)
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get)),
+        ]
+        .extend(rows.into_iter().enumerate().map(|(idx, (label, value))| {
+            container(row![text(label).width(Length::Fill), text(value)])
+                .padding([4, 8])
+                .width(Length::Fill)
+                .style(move |theme| theme::container::table(theme, idx))
+                .into()
+        }))
+        .spacing(1)
+    };
+
+    let connections = connections
+        .into_iter()
+        .flat_map(|connection| {
+            let server = connection.server.to_string();
+
+            [
+                (
+                    format!("{server}: messages per second"),
+                    format!("{:.2}", connection.messages_per_second),
+                ),
+                (
+                    format!("{server}: messages received"),
+                    connection.messages_received.to_string(),
+                ),
+                (
+                    format!("{server}: reconnects"),
+                    connection.reconnects.to_string(),
+                ),
+            ]
+        })
+        .collect();
+
+    let history_writes = vec![
+        ("Writes".to_string(), history_writes.count.to_string()),
+        ("Last".to_string(), milliseconds(history_writes.last)),
+        (
+            "Average".to_string(),
+            milliseconds(history_writes.average()),
+        ),
+        ("Max".to_string(), milliseconds(history_writes.max)),
+    ];
+
+    buffers.sort_by_key(|buffer| std::cmp::Reverse(buffer.bytes));
+
+    let buffers = buffers
+        .into_iter()
+        .map(|buffer| {
+            (
+                buffer.kind.to_string(),
+                format!(
+                    "{} messages, ~{}",
+                    buffer.messages,
+                    i18n::format_bytes(buffer.bytes as u64)
+                ),
+            )
+        })
+        .collect();
+
+    let content = column![
+        button(text("Copy as metrics"))
+            .padding([2, 6])
+            .style(|theme, status| {
+                theme::button::secondary(theme, status, false)
+            })
+            .on_press(Message::Copy),
+        section("Connections", connections),
+        section("History writes", history_writes),
+        section("Memory per buffer", buffers),
+    ]
+    .spacing(12)
+    .padding(8);
+
+    container(
+        Scrollable::new(content)
+            .direction(scrollable::Direction::Vertical(
+                scrollable::Scrollbar::new().width(1).scroller_width(1),
+            ))
+            .style(theme::scrollable::hidden),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+fn milliseconds(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
@@ -1,13 +1,29 @@
-use data::Config;
-use iced::widget::{column, container, text};
+use std::collections::VecDeque;
+
+use data::buffer::{Internal, Upstream};
+use data::{Config, client};
+use iced::widget::{button, column, container, text};
 use iced::{Length, alignment};
 
+use crate::screen::dashboard::modal::open_target;
 use crate::screen::dashboard::sidebar;
+use crate::theme;
 use crate::widget::Element;
 
-pub fn view<'a, Message: 'a>(
+/// Recently closed buffers listed in an empty pane.
+const MAX_RECENT: usize = 5;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Open(data::Buffer),
+    Prompt(open_target::Kind),
+}
+
+pub fn view<'a>(
     config: &'a Config,
     sidebar: &'a sidebar::Sidebar,
+    clients: &'a client::Map,
+    recent_buffers: &'a VecDeque<Upstream>,
 ) -> Element<'a, Message> {
     let arrow = if sidebar.hidden {
         ' '
@@ -20,12 +36,74 @@ pub fn view<'a, Message: 'a>(
         }
     };
 
-    let content = column![]
-        .push(
-            text(format!("{arrow} select buffer"))
-                .shaping(text::Shaping::Advanced),
+    let action = |label: String, message: Message| {
+        button(
+            container(text(label).shaping(text::Shaping::Advanced))
+                .align_x(alignment::Horizontal::Center)
+                .width(Length::Fill),
         )
-        .align_x(iced::Alignment::Center);
+        .padding(5)
+        .width(Length::Fixed(250.0))
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press(message)
+    };
+
+    let is_connected = clients.connected_servers().next().is_some();
+
+    let actions = column![]
+        .extend(is_connected.then(|| {
+            action(
+                "Join a channel…".to_string(),
+                Message::Prompt(open_target::Kind::Channel),
+            )
+            .into()
+        }))
+        .extend(is_connected.then(|| {
+            action(
+                "Start a query…".to_string(),
+                Message::Prompt(open_target::Kind::Query),
+            )
+            .into()
+        }))
+        .push(action(
+            "Open highlights".to_string(),
+            Message::Open(data::Buffer::Internal(Internal::Highlights)),
+        ))
+        .spacing(4);
+
+    let recent = (!recent_buffers.is_empty()).then(|| {
+        column![text("Recent").style(theme::text::secondary)]
+            .extend(recent_buffers.iter().take(MAX_RECENT).filter_map(
+                |upstream| {
+                    let target = upstream.target()?;
+
+                    Some(
+                        action(
+                            format!(
+                                "{} ({})",
+                                target.as_str(),
+                                upstream.server()
+                            ),
+                            Message::Open(data::Buffer::Upstream(
+                                upstream.clone(),
+                            )),
+                        )
+                        .into(),
+                    )
+                },
+            ))
+            .spacing(4)
+            .align_x(iced::Alignment::Center)
+            .into()
+    });
+
+    let content = column![
+        text(format!("{arrow} select buffer")).shaping(text::Shaping::Advanced),
+        actions,
+    ]
+    .extend(recent)
+    .spacing(20)
+    .align_x(iced::Alignment::Center);
 
     container(content)
         .align_x(alignment::Horizontal::Center)
@@ -1,23 +1,214 @@
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-use data::{Config, file_transfer};
+use data::{Config, file_transfer, i18n};
 use iced::widget::{
-    Scrollable, button, center, column, container, scrollable, text,
+    Scrollable, button, center, checkbox, column, container, image, row,
+    scrollable, text,
 };
-use iced::{ContentFit, Length, Task};
+use iced::{ContentFit, Length, Task, padding};
 
 use crate::widget::{Element, Text};
-use crate::{Theme, font, icon, theme};
+use crate::{Theme, font, icon, open_url, theme};
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "bmp"];
+const TEXT_EXTENSIONS: &[&str] = &[
+    "txt", "log", "md", "csv", "json", "toml", "yaml", "yml", "ini", "conf",
+    "cfg", "xml",
+];
+// Larger text files are not previewed
+const MAX_TEXT_PREVIEW_SIZE: u64 = 64 * 1024;
+const PREVIEW_HEIGHT: f32 = 300.0;
 
 #[derive(Debug, Clone)]
 pub enum Message {
     Approve(file_transfer::Id),
+    Resume(file_transfer::Id),
+    /// Move a waiting transfer towards the front (negative) or back
+    /// (positive) of the queue.
+    MoveWaiting(file_transfer::Id, isize),
     SavePathSelected(file_transfer::Id, Option<PathBuf>),
     Clear(file_transfer::Id),
+    Preview(file_transfer::Id),
+    PreviewLoaded(file_transfer::Id, Result<String, String>),
+    ClosePreview,
+    OpenContainingFolder(file_transfer::Id),
+    Select(file_transfer::Id, bool),
+    SelectAll(bool),
+    Filter(Filter),
+    AcceptSelected,
+    AcceptSelectedInto(Vec<file_transfer::Id>, Option<PathBuf>),
+    DeclineSelected,
+    ClearSelected,
+    RemoveSelected,
+}
+
+/// Transfers listed, by status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Filter {
+    #[default]
+    All,
+    Pending,
+    Active,
+    Completed,
+    Failed,
+}
+
+impl Filter {
+    const ALL: [Filter; 5] = [
+        Filter::All,
+        Filter::Pending,
+        Filter::Active,
+        Filter::Completed,
+        Filter::Failed,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Filter::All => i18n::tr("file_transfers.filter.all"),
+            Filter::Pending => i18n::tr("file_transfers.filter.pending"),
+            Filter::Active => i18n::tr("file_transfers.filter.active"),
+            Filter::Completed => i18n::tr("file_transfers.filter.completed"),
+            Filter::Failed => i18n::tr("file_transfers.filter.failed"),
+        }
+    }
+
+    fn matches(self, status: &file_transfer::Status) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Pending => matches!(
+                status,
+                file_transfer::Status::PendingApproval
+                    | file_transfer::Status::PendingReverseConfirmation
+                    | file_transfer::Status::Queued
+                    | file_transfer::Status::Waiting
+                    | file_transfer::Status::Ready
+            ),
+            Filter::Active => {
+                matches!(status, file_transfer::Status::Active { .. })
+            }
+            Filter::Completed => {
+                matches!(status, file_transfer::Status::Completed { .. })
+            }
+            Filter::Failed => {
+                matches!(status, file_transfer::Status::Failed { .. })
+            }
+        }
+    }
+}
+
+fn is_finished(transfer: &file_transfer::FileTransfer) -> bool {
+    matches!(
+        transfer.status,
+        file_transfer::Status::Completed { .. }
+            | file_transfer::Status::Failed { .. }
+    )
+}
+
+/// Response to a file transfer request, from outside of this buffer (e.g.
+/// a toast).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Response {
+    Accept,
+    Decline,
+    /// Accept, and accept later transfers from the same nickname
+    AlwaysAccept,
+}
+
+/// Where to save the received file, asking the user unless a save directory
+/// is configured (and the file can be saved there without prompting).
+pub fn select_save_path(
+    transfer: &file_transfer::FileTransfer,
+    config: &Config,
+) -> Task<Option<PathBuf>> {
+    if let Some(save_directory) = &config.file_transfer.save_directory
+        && let Some(save_path) = file_transfer::resolve_collision(
+            file_transfer::receive_save_path(
+                save_directory,
+                &transfer.filename,
+            ),
+            config.file_transfer.on_collision,
+        )
+    {
+        return Task::done(Some(save_path));
+    }
+
+    let sanitized_filename =
+        file_transfer::sanitize_filename(&transfer.filename);
+    let save_directory = config.file_transfer.save_directory.clone();
+
+    Task::future(async move {
+        let mut dialog =
+            rfd::AsyncFileDialog::new().set_file_name(sanitized_filename);
+
+        if let Some(save_directory) = save_directory {
+            dialog = dialog.set_directory(save_directory);
+        }
+
+        dialog
+            .save_file()
+            .await
+            .map(|handle| handle.path().to_path_buf())
+    })
+}
+
+/// Inline card for a transfer awaiting approval, shown in the query with the
+/// sender.
+pub fn request_card<'a, M: Clone + 'a>(
+    transfer: &'a file_transfer::FileTransfer,
+    on_response: impl Fn(Response) -> M,
+    theme: &'a Theme,
+) -> Element<'a, M> {
+    let card_button = |label: &'a str, message: M| {
+        button(text(label))
+            .padding([2, 6])
+            .style(|theme, status| {
+                theme::button::secondary(theme, status, false)
+            })
+            .on_press(message)
+    };
+
+    let file_size = i18n::format_bytes(transfer.size);
+
+    let description = column![
+        text(format!("{} ({file_size})", transfer.filename)),
+        text(i18n::tr_args(
+            "file_transfers.request",
+            &[("nick", transfer.remote_user.nickname().as_str())],
+        ))
+        .style(theme::text::secondary)
+        .font_maybe(theme::font_style::secondary(theme).map(font::get)),
+    ]
+    .width(Length::Fill)
+    .spacing(0);
+
+    container(
+        row![
+            description,
+            card_button(
+                i18n::tr("file_transfers.accept"),
+                on_response(Response::Accept)
+            ),
+            card_button(
+                i18n::tr("file_transfers.decline"),
+                on_response(Response::Decline)
+            ),
+            card_button(
+                i18n::tr("file_transfers.always_accept"),
+                on_response(Response::AlwaysAccept)
+            ),
+        ]
+        .spacing(6)
+        .align_y(iced::Alignment::Center),
+    )
+    .padding(padding::top(6).bottom(6).right(4).left(8))
+    .width(Length::Fill)
+    .style(|theme| theme::container::table(theme, 0))
+    .into()
 }
 
 pub fn view<'a>(
-    _state: &FileTransfers,
+    state: &'a FileTransfers,
     file_transfers: &'a file_transfer::Manager,
     theme: &'a Theme,
 ) -> Element<'a, Message> {
@@ -29,7 +220,7 @@ pub fn view<'a>(
                     .content_fit(ContentFit::Contain)
                     .height(theme::TEXT_SIZE + 3.0)
                     .style(theme::text::secondary),
-                text("No transfers found")
+                text(i18n::tr("file_transfers.empty"))
                     .style(theme::text::secondary)
                     .font_maybe(
                         theme::font_style::secondary(theme).map(font::get)
@@ -41,31 +232,275 @@ pub fn view<'a>(
         .into();
     }
 
-    let column =
-        column(file_transfers.list().enumerate().map(|(idx, transfer)| {
-            container(transfer_row::view(transfer, idx, theme)).into()
-        }))
-        .spacing(1)
-        .padding([0, 2]);
+    let batches = file_transfers.batches().collect::<Vec<_>>();
+    let num_batches = batches.len();
 
-    container(
-        Scrollable::new(column)
+    let column = column(
+        batches
+            .into_iter()
+            .enumerate()
+            .map(|(idx, batch)| batch_row::view(batch, idx, theme))
+            .chain(state.visible(file_transfers).enumerate().map(
+                |(idx, transfer)| {
+                    let preview = state
+                        .preview
+                        .as_ref()
+                        .filter(|preview| preview.id == transfer.id);
+
+                    column![transfer_row::view(
+                        transfer,
+                        num_batches + idx,
+                        state.selected.contains(&transfer.id),
+                        preview.is_some(),
+                        file_transfers.waiting_position(&transfer.id),
+                        theme,
+                    )]
+                    .extend(preview.map(|preview| preview_view(preview, theme)))
+                    .into()
+                },
+            )),
+    )
+    .spacing(1)
+    .padding([0, 2]);
+
+    column![
+        toolbar(state, file_transfers),
+        container(
+            Scrollable::new(column)
+                .direction(scrollable::Direction::Vertical(
+                    scrollable::Scrollbar::new().width(1).scroller_width(1),
+                ))
+                .style(theme::scrollable::hidden),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+    ]
+    .spacing(4)
+    .into()
+}
+
+/// Status filter, and actions applied to every selected transfer.
+fn toolbar<'a>(
+    state: &'a FileTransfers,
+    file_transfers: &'a file_transfer::Manager,
+) -> Element<'a, Message> {
+    let small_button = |label: &'static str, message: Option<Message>| {
+        button(text(label))
+            .padding([2, 6])
+            .style(|theme, status| {
+                theme::button::secondary(theme, status, false)
+            })
+            .on_press_maybe(message)
+    };
+
+    let filters = row(Filter::ALL.into_iter().map(|filter| {
+        let count = file_transfers
+            .list()
+            .filter(|transfer| filter.matches(&transfer.status))
+            .count();
+
+        button(text(format!("{} ({count})", filter.label())))
+            .padding([2, 6])
+            .style(move |theme, status| {
+                theme::button::secondary(theme, status, state.filter == filter)
+            })
+            .on_press(Message::Filter(filter))
+            .into()
+    }))
+    .spacing(2);
+
+    let selected = state.selected(file_transfers).collect::<Vec<_>>();
+    let all_selected = !selected.is_empty()
+        && selected.len() == state.visible(file_transfers).count();
+
+    let any_pending = selected
+        .iter()
+        .any(|transfer| file_transfers.is_pending_approval(&transfer.id));
+    let any_finished = selected.iter().any(|transfer| is_finished(transfer));
+
+    let actions = row![
+        checkbox(all_selected).on_toggle(Message::SelectAll),
+        container(
+            text(i18n::tr_args(
+                "file_transfers.selected",
+                &[("count", &i18n::format_number(selected.len() as u64))],
+            ))
+            .style(theme::text::secondary)
+        )
+        .width(Length::Fill),
+        small_button(
+            i18n::tr("file_transfers.accept"),
+            any_pending.then_some(Message::AcceptSelected)
+        ),
+        small_button(
+            i18n::tr("file_transfers.decline"),
+            any_pending.then_some(Message::DeclineSelected)
+        ),
+        small_button(
+            i18n::tr("file_transfers.clear"),
+            any_finished.then_some(Message::ClearSelected)
+        ),
+        small_button(
+            i18n::tr("file_transfers.remove"),
+            (!selected.is_empty()).then_some(Message::RemoveSelected)
+        ),
+    ]
+    .spacing(4)
+    .align_y(iced::Alignment::Center);
+
+    column![filters, actions]
+        .spacing(4)
+        .padding(padding::top(4).right(4).left(8))
+        .into()
+}
+
+fn preview_view<'a>(
+    preview: &'a Preview,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    let content: Element<'a, Message> =
+        match &preview.content {
+            PreviewContent::Image(handle) => image(handle.clone())
+                .content_fit(ContentFit::ScaleDown)
+                .width(Length::Fill)
+                .height(Length::Fixed(PREVIEW_HEIGHT))
+                .into(),
+            PreviewContent::Text(Some(contents)) => Scrollable::new(
+                container(text(contents).font_maybe(
+                    theme::font_style::primary(theme).map(font::get),
+                ))
+                .width(Length::Fill)
+                .padding(4),
+            )
             .direction(scrollable::Direction::Vertical(
                 scrollable::Scrollbar::new().width(1).scroller_width(1),
             ))
-            .style(theme::scrollable::hidden),
-    )
-    .width(Length::Fill)
-    .height(Length::Fill)
-    .into()
+            .height(Length::Shrink)
+            .into(),
+            PreviewContent::Text(None) => {
+                text(i18n::tr("file_transfers.preview_loading"))
+                    .style(theme::text::secondary)
+                    .into()
+            }
+            PreviewContent::Error(error) => text(i18n::tr_args(
+                "file_transfers.preview_failed",
+                &[("error", error.as_str())],
+            ))
+            .style(theme::text::error)
+            .into(),
+        };
+
+    container(content)
+        .padding(padding::top(4).bottom(6).right(8).left(8))
+        .width(Length::Fill)
+        .max_height(PREVIEW_HEIGHT)
+        .into()
+}
+
+#[derive(Debug, Clone)]
+struct Preview {
+    id: file_transfer::Id,
+    content: PreviewContent,
+}
+
+#[derive(Debug, Clone)]
+enum PreviewContent {
+    Image(image::Handle),
+    /// `None` while loading
+    Text(Option<String>),
+    Error(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PreviewKind {
+    Image,
+    Text,
+}
+
+/// Received files which have completed can be previewed if they are an image,
+/// or a small text file.
+fn preview_kind(transfer: &file_transfer::FileTransfer) -> Option<PreviewKind> {
+    if transfer.direction != file_transfer::Direction::Received
+        || !matches!(transfer.status, file_transfer::Status::Completed { .. })
+    {
+        return None;
+    }
+
+    let extension = transfer
+        .path
+        .as_deref()
+        .and_then(Path::extension)?
+        .to_str()?
+        .to_lowercase();
+
+    if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        Some(PreviewKind::Image)
+    } else if TEXT_EXTENSIONS.contains(&extension.as_str())
+        && transfer.size <= MAX_TEXT_PREVIEW_SIZE
+    {
+        Some(PreviewKind::Text)
+    } else {
+        None
+    }
 }
 
 #[derive(Debug, Default, Clone)]
-pub struct FileTransfers;
+pub struct FileTransfers {
+    preview: Option<Preview>,
+    filter: Filter,
+    selected: HashSet<file_transfer::Id>,
+}
 
 impl FileTransfers {
     pub fn new() -> Self {
-        FileTransfers
+        FileTransfers::default()
+    }
+
+    fn visible<'a>(
+        &'a self,
+        file_transfers: &'a file_transfer::Manager,
+    ) -> impl Iterator<Item = &'a file_transfer::FileTransfer> {
+        file_transfers
+            .list()
+            .filter(|transfer| self.filter.matches(&transfer.status))
+    }
+
+    /// Selected transfers which are still listed.
+    fn selected<'a>(
+        &'a self,
+        file_transfers: &'a file_transfer::Manager,
+    ) -> impl Iterator<Item = &'a file_transfer::FileTransfer> {
+        self.visible(file_transfers)
+            .filter(|transfer| self.selected.contains(&transfer.id))
+    }
+
+    fn selected_ids(
+        &self,
+        file_transfers: &file_transfer::Manager,
+        predicate: impl Fn(&file_transfer::FileTransfer) -> bool,
+    ) -> Vec<file_transfer::Id> {
+        self.selected(file_transfers)
+            .filter(|transfer| predicate(transfer))
+            .map(|transfer| transfer.id)
+            .collect()
+    }
+
+    fn remove(
+        &mut self,
+        id: file_transfer::Id,
+        file_transfers: &mut file_transfer::Manager,
+        config: &Config,
+    ) {
+        if self
+            .preview
+            .as_ref()
+            .is_some_and(|preview| preview.id == id)
+        {
+            self.preview = None;
+        }
+
+        self.selected.remove(&id);
+        file_transfers.remove(&id, config);
     }
 
     pub fn update(
@@ -76,48 +511,153 @@ impl FileTransfers {
     ) -> Task<Message> {
         match message {
             Message::Approve(id) => {
-                if let Some(transfer) = file_transfers.get(&id).cloned() {
-                    match &config.file_transfer.save_directory {
-                        Some(save_directory) => {
-                            let file_save_directory =
-                                file_transfer::receive_save_path(
-                                    save_directory,
-                                    &transfer.filename,
-                                );
-                            return Task::done(Message::SavePathSelected(
-                                id,
-                                Some(file_save_directory),
-                            ));
-                        }
-                        None => {
-                            let sanitized_filename =
-                                file_transfer::sanitize_filename(
-                                    &transfer.filename,
-                                );
-
-                            return Task::perform(
-                                async move {
-                                    rfd::AsyncFileDialog::new()
-                                        .set_file_name(sanitized_filename)
-                                        .save_file()
-                                        .await
-                                        .map(|handle| {
-                                            handle.path().to_path_buf()
-                                        })
-                                },
-                                move |path| Message::SavePathSelected(id, path),
-                            );
-                        }
-                    }
+                if let Some(transfer) = file_transfers.get(&id) {
+                    return select_save_path(transfer, config)
+                        .map(move |path| Message::SavePathSelected(id, path));
                 }
             }
+            Message::Resume(id) => {
+                file_transfers.resume(&id, config);
+            }
+            Message::MoveWaiting(id, offset) => {
+                file_transfers.move_waiting(&id, offset);
+            }
             Message::SavePathSelected(id, path) => {
                 if let Some(path) = path {
-                    file_transfers.approve(&id, path);
+                    file_transfers.approve(&id, path, config);
                 }
             }
             Message::Clear(id) => {
-                file_transfers.remove(&id);
+                self.remove(id, file_transfers, config);
+            }
+            Message::Preview(id) => {
+                let Some(transfer) = file_transfers.get(&id) else {
+                    return Task::none();
+                };
+                let Some(path) = transfer.path.clone() else {
+                    return Task::none();
+                };
+
+                match preview_kind(transfer) {
+                    Some(PreviewKind::Image) => {
+                        self.preview = Some(Preview {
+                            id,
+                            content: PreviewContent::Image(
+                                image::Handle::from_path(path),
+                            ),
+                        });
+                    }
+                    Some(PreviewKind::Text) => {
+                        self.preview = Some(Preview {
+                            id,
+                            content: PreviewContent::Text(None),
+                        });
+
+                        return Task::perform(
+                            async move {
+                                tokio::fs::read(path)
+                                    .await
+                                    .map(|bytes| {
+                                        String::from_utf8_lossy(&bytes)
+                                            .into_owned()
+                                    })
+                                    .map_err(|error| error.to_string())
+                            },
+                            move |result| Message::PreviewLoaded(id, result),
+                        );
+                    }
+                    None => (),
+                }
+            }
+            Message::PreviewLoaded(id, result) => {
+                if let Some(preview) =
+                    self.preview.as_mut().filter(|preview| preview.id == id)
+                {
+                    preview.content = match result {
+                        Ok(contents) => PreviewContent::Text(Some(contents)),
+                        Err(error) => PreviewContent::Error(error),
+                    };
+                }
+            }
+            Message::ClosePreview => {
+                self.preview = None;
+            }
+            Message::OpenContainingFolder(id) => {
+                if let Some(folder) = file_transfers
+                    .get(&id)
+                    .and_then(|transfer| transfer.path.as_deref())
+                    .and_then(Path::parent)
+                {
+                    let _ = open_url::open(folder);
+                }
+            }
+            Message::Select(id, selected) => {
+                if selected {
+                    self.selected.insert(id);
+                } else {
+                    self.selected.remove(&id);
+                }
+            }
+            Message::SelectAll(selected) => {
+                self.selected = if selected {
+                    self.visible(file_transfers)
+                        .map(|transfer| transfer.id)
+                        .collect()
+                } else {
+                    HashSet::new()
+                };
+            }
+            Message::Filter(filter) => {
+                self.filter = filter;
+                self.selected.clear();
+            }
+            Message::AcceptSelected => {
+                let ids = self.selected_ids(file_transfers, |transfer| {
+                    file_transfers.is_pending_approval(&transfer.id)
+                });
+
+                if let Some(save_directory) =
+                    config.file_transfer.save_directory.clone()
+                {
+                    return Task::done(Message::AcceptSelectedInto(
+                        ids,
+                        Some(save_directory),
+                    ));
+                }
+
+                // Ask once for a folder to save every file in
+                return Task::future(async move {
+                    rfd::AsyncFileDialog::new()
+                        .pick_folder()
+                        .await
+                        .map(|handle| handle.path().to_path_buf())
+                })
+                .map(move |folder| {
+                    Message::AcceptSelectedInto(ids.clone(), folder)
+                });
+            }
+            Message::AcceptSelectedInto(ids, Some(folder)) => {
+                for id in file_transfers.approve_into(&ids, &folder, config) {
+                    self.selected.remove(&id);
+                }
+            }
+            Message::AcceptSelectedInto(_, None) => {}
+            Message::DeclineSelected => {
+                for id in self.selected_ids(file_transfers, |transfer| {
+                    file_transfers.is_pending_approval(&transfer.id)
+                }) {
+                    self.remove(id, file_transfers, config);
+                }
+            }
+            Message::ClearSelected => {
+                for id in self.selected_ids(file_transfers, is_finished) {
+                    self.remove(id, file_transfers, config);
+                }
+            }
+            Message::RemoveSelected => {
+                for id in self.selected_ids(file_transfers, |_| true) {
+                    self.remove(id, file_transfers, config);
+                }
             }
         }
 
@@ -125,15 +665,78 @@ impl FileTransfers {
     }
 }
 
+mod batch_row {
+    use data::file_transfer::BatchProgress;
+    use data::i18n;
+    use iced::widget::{column, container, progress_bar, text};
+    use iced::{Length, padding};
+
+    use super::Message;
+    use crate::widget::Element;
+    use crate::{Theme, font, theme};
+
+    pub fn view<'a>(
+        batch: BatchProgress,
+        idx: usize,
+        theme: &'a Theme,
+    ) -> Element<'a, Message> {
+        let total = i18n::format_number(batch.total as u64);
+        let finished = i18n::format_number(batch.finished as u64);
+
+        let done = if batch.failed > 0 {
+            i18n::tr_args(
+                "file_transfers.batch_done_failed",
+                &[
+                    ("finished", &finished),
+                    ("total", &total),
+                    ("failed", &i18n::format_number(batch.failed as u64)),
+                ],
+            )
+        } else {
+            i18n::tr_args(
+                "file_transfers.batch_done",
+                &[("finished", &finished), ("total", &total)],
+            )
+        };
+
+        let progress_bar =
+            container(progress_bar(0.0..=1.0, batch.progress as f32))
+                .padding([4, 0])
+                .height(11);
+
+        let content = column![
+            text(i18n::tr_args(
+                "file_transfers.batch_sending",
+                &[("count", &total), ("nick", batch.to.nickname().as_str())],
+            )),
+            text(done)
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get)),
+            progress_bar,
+        ]
+        .padding(padding::top(1))
+        .width(Length::Fill)
+        .spacing(0);
+
+        container(content)
+            .padding(padding::top(6).bottom(6).right(4).left(8))
+            .width(Length::Fill)
+            .style(move |theme| theme::container::table(theme, idx))
+            .into()
+    }
+}
+
 mod transfer_row {
     use std::time::Duration;
 
-    use bytesize::ByteSize;
     use data::file_transfer::{self, FileTransfer};
-    use iced::widget::{column, container, progress_bar, row, text};
+    use data::i18n;
+    use iced::widget::{
+        button, checkbox, column, container, progress_bar, row, text,
+    };
     use iced::{Length, alignment, padding};
 
-    use super::Message;
+    use super::{Message, preview_kind};
     use crate::buffer::file_transfers::row_button;
     use crate::widget::Element;
     use crate::{Theme, font, icon, theme};
@@ -141,16 +744,21 @@ mod transfer_row {
     pub fn view<'a>(
         transfer: &FileTransfer,
         idx: usize,
+        is_selected: bool,
+        is_previewing: bool,
+        waiting_position: Option<usize>,
         theme: &'a Theme,
     ) -> Element<'a, Message> {
+        let id = transfer.id;
+        let nickname = transfer.remote_user.nickname().to_string();
         let status = match &transfer.status {
             file_transfer::Status::PendingApproval
             | file_transfer::Status::PendingReverseConfirmation => {
                 match &transfer.direction {
                     file_transfer::Direction::Sent => container(
-                        text(format!(
-                            "Transfer to {}. Waiting for them to accept.",
-                            transfer.remote_user.nickname()
+                        text(i18n::tr_args(
+                            "file_transfers.status.pending_sent",
+                            &[("nick", &nickname)],
                         ))
                         .style(theme::text::secondary)
                         .font_maybe(
@@ -158,10 +766,19 @@ mod transfer_row {
                         ),
                     ),
                     file_transfer::Direction::Received => container(
-                        text(format!(
-                            "Transfer from {}. Accept to begin.",
-                            transfer.remote_user.nickname()
-                        ))
+                        text(match &transfer.partial {
+                            Some((_, partial)) => i18n::tr_args(
+                                "file_transfers.status.pending_resumable",
+                                &[
+                                    ("nick", &nickname),
+                                    ("size", &i18n::format_bytes(*partial)),
+                                ],
+                            ),
+                            None => i18n::tr_args(
+                                "file_transfers.status.pending_received",
+                                &[("nick", &nickname)],
+                            ),
+                        })
                         .style(theme::text::secondary)
                         .font_maybe(
                             theme::font_style::secondary(theme).map(font::get),
@@ -170,39 +787,53 @@ mod transfer_row {
                 }
             }
             file_transfer::Status::Queued => {
-                let direction = match transfer.direction {
-                    file_transfer::Direction::Sent => "to",
-                    file_transfer::Direction::Received => "from",
+                let key = match transfer.direction {
+                    file_transfer::Direction::Sent => {
+                        "file_transfers.status.queued_sent"
+                    }
+                    file_transfer::Direction::Received => {
+                        "file_transfers.status.queued_received"
+                    }
                 };
 
                 container(
-                    text(format!(
-                        "Transfer {} {}. Waiting for open port.",
-                        direction,
-                        transfer.remote_user.nickname(),
-                    ))
-                    .style(theme::text::secondary)
-                    .font_maybe(
-                        theme::font_style::secondary(theme).map(font::get),
-                    ),
+                    text(i18n::tr_args(key, &[("nick", &nickname)]))
+                        .style(theme::text::secondary)
+                        .font_maybe(
+                            theme::font_style::secondary(theme).map(font::get),
+                        ),
                 )
             }
+            file_transfer::Status::Waiting => container(
+                text(i18n::tr_args(
+                    "file_transfers.status.waiting",
+                    &[
+                        ("nick", &nickname),
+                        (
+                            "position",
+                            &waiting_position.unwrap_or_default().to_string(),
+                        ),
+                    ],
+                ))
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get)),
+            ),
             file_transfer::Status::Ready => {
-                let direction = match transfer.direction {
-                    file_transfer::Direction::Sent => "to",
-                    file_transfer::Direction::Received => "from",
+                let key = match transfer.direction {
+                    file_transfer::Direction::Sent => {
+                        "file_transfers.status.ready_sent"
+                    }
+                    file_transfer::Direction::Received => {
+                        "file_transfers.status.ready_received"
+                    }
                 };
 
                 container(
-                    text(format!(
-                        "Transfer {} {}. Waiting for remote user to connect.",
-                        direction,
-                        transfer.remote_user.nickname()
-                    ))
-                    .style(theme::text::secondary)
-                    .font_maybe(
-                        theme::font_style::secondary(theme).map(font::get),
-                    ),
+                    text(i18n::tr_args(key, &[("nick", &nickname)]))
+                        .style(theme::text::secondary)
+                        .font_maybe(
+                            theme::font_style::secondary(theme).map(font::get),
+                        ),
                 )
             }
             file_transfer::Status::Active {
@@ -215,7 +846,7 @@ mod transfer_row {
                     String::default()
                 } else {
                     let bytes_per_second = *transferred / elapsed.as_secs();
-                    let transfer_speed = ByteSize::b(bytes_per_second);
+                    let transfer_speed = i18n::format_bytes(bytes_per_second);
 
                     let remaining_bytes =
                         transfer.size.saturating_sub(*transferred);
@@ -235,8 +866,8 @@ mod transfer_row {
                     format!("({transfer_speed}/s) {remaining_time}")
                 };
 
-                let transferred = ByteSize::b(*transferred);
-                let file_size = ByteSize::b(transfer.size);
+                let transferred = i18n::format_bytes(*transferred);
+                let file_size = i18n::format_bytes(transfer.size);
 
                 let progress_bar = container(progress_bar(
                     0.0..=1.0,
@@ -248,10 +879,19 @@ mod transfer_row {
                 container(
                     column![
                         text(format!(
-                            "{transferred} of {file_size} {transfer_speed_and_remaining_time}"
+                            "{} {transfer_speed_and_remaining_time}",
+                            i18n::tr_args(
+                                "file_transfers.status.progress",
+                                &[
+                                    ("transferred", &transferred),
+                                    ("size", &file_size),
+                                ],
+                            )
                         ))
                         .style(theme::text::secondary)
-                        .font_maybe(theme::font_style::secondary(theme).map(font::get)),
+                        .font_maybe(
+                            theme::font_style::secondary(theme).map(font::get)
+                        ),
                         progress_bar
                     ]
                     .spacing(0),
@@ -265,16 +905,23 @@ mod transfer_row {
                     .too_low("under a second");
                 let elapsed = formatter.convert(*elapsed);
 
-                let direction = match transfer.direction {
-                    file_transfer::Direction::Sent => "to",
-                    file_transfer::Direction::Received => "from",
+                let key = match transfer.direction {
+                    file_transfer::Direction::Sent => {
+                        "file_transfers.status.completed_sent"
+                    }
+                    file_transfer::Direction::Received => {
+                        "file_transfers.status.completed_received"
+                    }
                 };
 
                 container(
-                    text(format!(
-                        "Completed {} {} in {elapsed}. sha256: {sha256}",
-                        direction,
-                        transfer.remote_user.nickname(),
+                    text(i18n::tr_args(
+                        key,
+                        &[
+                            ("nick", &nickname),
+                            ("elapsed", &elapsed),
+                            ("sha256", sha256),
+                        ],
                     ))
                     .style(theme::text::secondary)
                     .font_maybe(
@@ -283,15 +930,28 @@ mod transfer_row {
                 )
             }
             file_transfer::Status::Failed { error } => container(
-                text(format!("Failed: {error}"))
-                    .style(theme::text::error)
-                    .font_maybe(theme::font_style::error(theme).map(font::get)),
+                text(i18n::tr_args(
+                    "file_transfers.status.failed",
+                    &[("error", error)],
+                ))
+                .style(theme::text::error)
+                .font_maybe(theme::font_style::error(theme).map(font::get)),
             ),
         };
 
-        let file_size = ByteSize::b(transfer.size);
-        let filename =
-            container(text(format!("{} ({file_size})", transfer.filename)));
+        let file_size = i18n::format_bytes(transfer.size);
+        let filename = container(
+            row![]
+                .extend(transfer.secure.then(|| {
+                    icon::lock()
+                        .size(theme::TEXT_SIZE - 2.0)
+                        .style(theme::text::secondary)
+                        .into()
+                }))
+                .push(text(format!("{} ({file_size})", transfer.filename)))
+                .spacing(4)
+                .align_y(iced::Alignment::Center),
+        );
 
         let mut buttons = row![].align_y(iced::Alignment::Center).spacing(2);
         let content = column![filename, status]
@@ -303,6 +963,16 @@ mod transfer_row {
 
         match &transfer.status {
             file_transfer::Status::PendingApproval => {
+                if transfer.partial.is_some() {
+                    buttons = buttons.push(
+                        button(text(i18n::tr("file_transfers.resume")))
+                            .padding([2, 6])
+                            .style(|theme, status| {
+                                theme::button::secondary(theme, status, false)
+                            })
+                            .on_press(Message::Resume(transfer.id)),
+                    );
+                }
                 buttons = buttons.push(row_button(
                     icon::checkmark(),
                     Message::Approve(transfer.id),
@@ -312,6 +982,27 @@ mod transfer_row {
                     Message::Clear(transfer.id),
                 ));
             }
+            file_transfer::Status::Waiting => {
+                let move_button = |label: &'static str, offset: isize| {
+                    button(text(label).shaping(text::Shaping::Advanced))
+                        .padding([2, 6])
+                        .style(|theme, status| {
+                            theme::button::secondary(theme, status, false)
+                        })
+                        .on_press_maybe(
+                            (waiting_position != Some(1) || offset > 0)
+                                .then_some(Message::MoveWaiting(id, offset)),
+                        )
+                };
+
+                buttons = buttons
+                    .push(move_button("↑", -1))
+                    .push(move_button("↓", 1))
+                    .push(row_button(
+                        icon::cancel(),
+                        Message::Clear(transfer.id),
+                    ));
+            }
             file_transfer::Status::PendingReverseConfirmation
             | file_transfer::Status::Queued
             | file_transfer::Status::Ready => {
@@ -320,8 +1011,29 @@ mod transfer_row {
                     Message::Clear(transfer.id),
                 ));
             }
-            file_transfer::Status::Active { .. }
-            | file_transfer::Status::Completed { .. } => {
+            file_transfer::Status::Active { .. } => {
+                buttons = buttons.push(row_button(
+                    icon::cancel(),
+                    Message::Clear(transfer.id),
+                ));
+            }
+            file_transfer::Status::Completed { .. } => {
+                if preview_kind(transfer).is_some() {
+                    buttons = buttons.push(if is_previewing {
+                        row_button(icon::restore(), Message::ClosePreview)
+                    } else {
+                        row_button(
+                            icon::maximize(),
+                            Message::Preview(transfer.id),
+                        )
+                    });
+                }
+                if transfer.path.is_some() {
+                    buttons = buttons.push(row_button(
+                        icon::open(),
+                        Message::OpenContainingFolder(transfer.id),
+                    ));
+                }
                 buttons = buttons.push(row_button(
                     icon::cancel(),
                     Message::Clear(transfer.id),
@@ -335,9 +1047,14 @@ mod transfer_row {
             }
         }
 
-        let row = row![content, buttons]
-            .spacing(6)
-            .align_y(iced::Alignment::Center);
+        let row = row![
+            checkbox(is_selected)
+                .on_toggle(move |selected| Message::Select(id, selected)),
+            content,
+            buttons
+        ]
+        .spacing(6)
+        .align_y(iced::Alignment::Center);
 
         container(row)
             .padding(padding::top(6).bottom(6).right(4).left(8))
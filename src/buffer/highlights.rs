@@ -31,8 +31,10 @@ pub enum Event {
     OpenUrl(String),
     MarkAsRead,
     ImagePreview(Image),
+    LoadPreview(url::Url),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
+    ExpandReplay(DateTime<Utc>, bool),
 }
 
 pub fn view<'a>(
@@ -52,6 +54,7 @@ pub fn view<'a>(
         None,
         Option::<fn(&Preview, &message::Source) -> bool>::None,
         None,
+        None,
         0.0,
         config,
         theme,
@@ -197,6 +200,7 @@ pub fn view<'a>(
                     prefix,
                     Some(channel),
                     clients.get_registry(server),
+                    clients.get_nick_history(server),
                     previews,
                     user,
                     current_user,
@@ -258,6 +262,7 @@ pub fn view<'a>(
                                 prefix,
                                 channel: Some(channel),
                                 registry: clients.get_registry(server),
+                                nick_history: clients.get_nick_history(server),
                                 avatar: context_menu::user_avatar(
                                     user,
                                     clients.get_registry(server),
@@ -439,12 +444,18 @@ impl Highlights {
                     scroll_view::Event::ImagePreview(image) => {
                         Some(Event::ImagePreview(image))
                     }
+                    scroll_view::Event::LoadPreview(url) => {
+                        Some(Event::LoadPreview(url))
+                    }
                     scroll_view::Event::ExpandMessage(server_time, hash) => {
                         Some(Event::ExpandMessage(server_time, hash))
                     }
                     scroll_view::Event::ContractMessage(server_time, hash) => {
                         Some(Event::ContractMessage(server_time, hash))
                     }
+                    scroll_view::Event::ExpandReplay(from, expanded) => {
+                        Some(Event::ExpandReplay(from, expanded))
+                    }
                 });
 
                 (command.map(Message::ScrollView), event)
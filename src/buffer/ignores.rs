@@ -0,0 +1,132 @@
+use data::Config;
+use data::ignore::Mask;
+use iced::widget::{
+    Scrollable, button, column, container, row, scrollable, text,
+};
+use iced::{Length, alignment};
+
+use crate::widget::Element;
+use crate::{Theme, font, theme};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Remove(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Unignore(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Ignores;
+
+impl Ignores {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::Remove(mask) => Some(Event::Unignore(mask)),
+        }
+    }
+}
+
+pub fn view<'a>(
+    ignores: &'a [Mask],
+    config: &'a Config,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    let entry =
+        |idx: usize, mask: &'a Mask, removable: bool| -> Element<'a, Message> {
+            let mut types = mask
+                .types
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            if mask.soft {
+                types.push_str(" (soft)");
+            }
+
+            container(
+                row![
+                    text(&mask.mask).width(Length::Fill),
+                    text(types).style(theme::text::secondary),
+                ]
+                .extend(removable.then(|| {
+                    button(text("Remove"))
+                        .padding([2, 6])
+                        .style(|theme, status| {
+                            theme::button::secondary(theme, status, false)
+                        })
+                        .on_press(Message::Remove(mask.mask.clone()))
+                        .into()
+                }))
+                .spacing(8)
+                .align_y(alignment::Vertical::Center),
+            )
+            .padding([4, 8])
+            .width(Length::Fill)
+            .style(move |theme| theme::container::table(theme, idx))
+            .into()
+        };
+
+    let section = |title: &'static str, rows: Vec<Element<'a, Message>>| {
+        column![
+            text(title)
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get)),
+        ]
+        .extend(rows)
+        .spacing(1)
+    };
+
+    let content = if ignores.is_empty() && config.ignore.masks.is_empty() {
+        column![
+            text("No one is ignored. Use /ignore <mask> to ignore a hostmask.")
+                .style(theme::text::secondary)
+        ]
+    } else {
+        column![]
+            .extend((!ignores.is_empty()).then(|| {
+                section(
+                    "Ignored with /ignore",
+                    ignores
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, mask)| entry(idx, mask, true))
+                        .collect(),
+                )
+                .into()
+            }))
+            .extend((!config.ignore.masks.is_empty()).then(|| {
+                section(
+                    "Ignored in configuration",
+                    config
+                        .ignore
+                        .masks
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, mask)| entry(idx, mask, false))
+                        .collect(),
+                )
+                .into()
+            }))
+    }
+    .spacing(12)
+    .padding(8);
+
+    container(
+        Scrollable::new(content)
+            .direction(scrollable::Direction::Vertical(
+                scrollable::Scrollbar::new().width(1).scroller_width(1),
+            ))
+            .style(theme::scrollable::hidden),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
@@ -7,16 +7,21 @@ use std::time::{Duration, Instant};
 use chrono::{DateTime, Utc};
 use data::buffer::{self, Upstream};
 use data::capabilities::{MultilineBatchKind, multiline_concat_lines};
-use data::config::buffer::text_input::{AutoFormat, Autocomplete, KeyBindings};
+use data::config::buffer::text_input::{
+    AutoFormat, Autocomplete, Filter, KeyBindings,
+};
 use data::dashboard::BufferAction;
 use data::history::filter::FilterChain;
 use data::history::{self, ReadMarker};
 use data::input::{self, CodeFence, RawInput};
 use data::rate_limit::TokenPriority;
 use data::server::Server;
-use data::target::Target;
+use data::target::{self, Target};
 use data::user::{ChannelUsers, Nick};
-use data::{Config, User, client, command, message, metadata, shortcut};
+use data::{
+    Config, Notification, User, client, command, ignore, message, metadata,
+    shortcut,
+};
 use iced::Length::Fit;
 use iced::advanced::widget::Tree;
 use iced::advanced::{Layout, Shell, mouse};
@@ -69,6 +74,9 @@ pub enum Event {
         history_task: Task<history::manager::Message>,
     },
     Reconnect(Server),
+    TestNotification(Server, Notification),
+    Ignore(ignore::Mask),
+    Unignore(String),
     FilehostUpload {
         server: Server,
         target: Option<Target>,
@@ -76,6 +84,7 @@ pub enum Event {
         upload_ids: Vec<u32>,
         abort_registrations: Vec<futures::future::AbortRegistration>,
     },
+    OpenEmojiPicker,
 }
 
 #[derive(Debug, Clone)]
@@ -90,6 +99,8 @@ pub enum Message {
     },
     SysInfoReceived(iced::system::Information),
     Send,
+    ConfirmSend,
+    CancelSend,
     Kill(text_editor_key_bindings::Kill, bool),
     SelectCompletion(usize),
     Tab(bool),
@@ -105,11 +116,16 @@ pub enum Message {
         lines: VecDeque<input::Parsed>,
     },
     Paste,
+    PasteText(String),
+    UploadPastedText,
+    InsertPastedText,
+    DiscardPastedText,
     SelectAll,
     CopyAll,
     Copy,
     Cut,
     UploadFile,
+    OpenEmojiPicker,
     FilesSelected(Vec<std::path::PathBuf>),
     FilehostUploadDone {
         id: u32,
@@ -498,14 +514,36 @@ pub fn view<'a>(
             )
         });
 
+    let maybe_emoji_button = config.buffer.emojis.picker_button.then(|| {
+        tooltip(
+            button(center(text("\u{1F642}").size(13)))
+                .padding(4)
+                .width(23)
+                .height(23)
+                .style(|theme, status| {
+                    theme::button::secondary(theme, status, false)
+                })
+                .on_press(Message::OpenEmojiPicker),
+            config.tooltips.show_for_buttons().then_some("Insert emoji"),
+            tooltip::Position::Top,
+            theme,
+        )
+    });
+
     let maybe_reply_bar = state.reply_preview.as_ref().map(|reply_preview| {
         reply_bar(reply_preview, channel_users, registry, config, theme)
     });
 
+    let maybe_paste_bar = state
+        .pending_paste
+        .as_ref()
+        .map(|pasted| paste_bar(pasted, config, theme));
+
     let input_row = container(
         row![]
             .extend(maybe_our_user(our_user, registry, config, theme))
             .push(wrapped_input)
+            .extend(maybe_emoji_button)
             .extend(maybe_upload_spinner.into_iter().chain(maybe_upload_button))
             .spacing(INPUT_ROW_SPACING)
             .height(Length::Shrink)
@@ -521,7 +559,14 @@ pub fn view<'a>(
     let styled_input =
         container(input_row).style(theme::container::buffer_text_input);
 
+    let maybe_confirmation_bar = state
+        .pending_confirmation
+        .as_ref()
+        .map(|pattern| confirmation_bar(pattern, config, theme));
+
     let input_column = column![
+        maybe_confirmation_bar,
+        maybe_paste_bar,
         if let Some(bar) = maybe_reply_bar {
             bar
         } else {
@@ -679,6 +724,87 @@ fn reply_bar<'a>(
     .into()
 }
 
+fn paste_bar<'a>(
+    pasted: &str,
+    config: &'a Config,
+    theme: &'a Theme,
+) -> crate::widget::Element<'a, Message> {
+    let font_size = config.font.size.map_or(theme::TEXT_SIZE, f32::from) * 0.85;
+
+    let choice = |label: &'a str, message: Message| {
+        button(text(label).size(font_size))
+            .on_press(message)
+            .padding([2, 6])
+            .style(|theme, status| {
+                theme::button::secondary(theme, status, false)
+            })
+    };
+
+    container(
+        row![
+            crate::icon::file_transfer().style(theme::text::primary),
+            text(format!(
+                "Pasting {} lines, more than the input allows",
+                pasted.lines().count()
+            ))
+            .style(theme::text::primary)
+            .size(font_size)
+            .width(Length::Fill),
+            choice("Upload as file", Message::UploadPastedText),
+            choice("Paste truncated", Message::InsertPastedText),
+            tooltip(
+                button(center(crate::icon::cancel()))
+                    .on_press(Message::DiscardPastedText)
+                    .width(20)
+                    .height(20)
+                    .style(|theme, status| {
+                        theme::button::secondary(theme, status, false)
+                    })
+                    .padding(5),
+                Some("Discard paste"),
+                widget::tooltip::Position::Top,
+                theme,
+            )
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center),
+    )
+    .padding([2, 8])
+    .into()
+}
+
+fn confirmation_bar<'a>(
+    pattern: &str,
+    config: &'a Config,
+    theme: &'a Theme,
+) -> crate::widget::Element<'a, Message> {
+    let font_size = config.font.size.map_or(theme::TEXT_SIZE, f32::from) * 0.85;
+
+    let choice = |label: &'a str, message: Message| {
+        button(text(label).size(font_size))
+            .on_press(message)
+            .padding([2, 6])
+            .style(|theme, status| {
+                theme::button::secondary(theme, status, false)
+            })
+    };
+
+    container(
+        row![
+            text(format!("Message matches '{pattern}'. Send anyway?"))
+                .style(theme::text::warning)
+                .size(font_size)
+                .width(Length::Fill),
+            choice("Send", Message::ConfirmSend),
+            choice("Cancel", Message::CancelSend),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center),
+    )
+    .padding([2, 8])
+    .into()
+}
+
 fn notice_view<'a, 'b, Message: 'a>(
     notice: &'a Notice,
     theme: &'a Theme,
@@ -712,6 +838,13 @@ pub struct State {
     upload_abort_handles: Vec<futures::future::AbortHandle>,
     draft_reply: Option<input::DraftReply>,
     reply_preview: Option<message::ReplyPreview>,
+    // Pasted text with more lines than the input allows, awaiting a choice
+    // between uploading it as a file or pasting it truncated
+    pending_paste: Option<String>,
+    // Pattern of a `confirm` filter matched by the input, awaiting a choice
+    // between sending it anyway or going back to editing
+    pending_confirmation: Option<String>,
+    send_confirmed: bool,
     history: History,
 }
 
@@ -732,6 +865,9 @@ impl Default for State {
             upload_abort_handles: Vec::new(),
             draft_reply: None,
             reply_preview: None,
+            pending_paste: None,
+            pending_confirmation: None,
+            send_confirmed: false,
             history: History::new(),
         }
     }
@@ -1004,11 +1140,24 @@ impl State {
                         return (Task::none(), None);
                     }
 
+                    // Ask before sending anything matching a `confirm` filter
+                    if !std::mem::take(&mut self.send_confirmed)
+                        && let Some(filter) =
+                            self.confirmation(buffer, clients, config)
+                    {
+                        self.pending_confirmation =
+                            Some(filter.regex.as_str().to_string());
+
+                        return (Task::none(), None);
+                    }
+
+                    self.pending_confirmation = None;
                     self.completion.reset();
 
                     history.record_input_history(
                         buffer,
                         self.input_content.text().clone(),
+                        config.buffer.text_input.history_length,
                     );
                     self.input_content = text_editor::Content::new();
                     self.history.clear();
@@ -1019,6 +1168,7 @@ impl State {
                         .drain(..)
                         .filter_map(Result::ok)
                         .filter(|parsed| parsed.code_fence().is_none())
+                        .flat_map(input::Parsed::into_lines)
                         .collect();
 
                     self.send_input_lines(
@@ -1028,6 +1178,23 @@ impl State {
                     (Task::none(), None)
                 }
             }
+            Message::ConfirmSend => {
+                self.send_confirmed = true;
+
+                self.update(
+                    Message::Send,
+                    buffer,
+                    clients,
+                    history,
+                    main_window,
+                    config,
+                )
+            }
+            Message::CancelSend => {
+                self.pending_confirmation = None;
+
+                (Task::none(), None)
+            }
             Message::Tab(reverse) => {
                 let cursor_position = self.input_content.cursor().position;
 
@@ -1244,6 +1411,66 @@ impl State {
 
                 Self::close_context_menu(main_window.id, vec![task])
             }
+            Message::PasteText(text) => {
+                let available_lines = config
+                    .buffer
+                    .text_input
+                    .max_lines
+                    .saturating_sub(self.input_content.line_count())
+                    + 1;
+
+                if config.filehost.paste_text()
+                    && clients.get_filehost(buffer.server()).is_some()
+                    && text.lines().count() > available_lines
+                {
+                    self.pending_paste = Some(text);
+
+                    (Task::none(), None)
+                } else {
+                    (
+                        Task::done(Message::Action(text_editor::Action::Edit(
+                            text_editor::Edit::Paste(text.into()),
+                        ))),
+                        None,
+                    )
+                }
+            }
+            Message::InsertPastedText => {
+                let task =
+                    self.pending_paste.take().map_or_else(Task::none, |text| {
+                        Task::done(Message::Action(text_editor::Action::Edit(
+                            text_editor::Edit::Paste(text.into()),
+                        )))
+                    });
+
+                (task, None)
+            }
+            Message::UploadPastedText => {
+                let Some(text) = self.pending_paste.take() else {
+                    return (Task::none(), None);
+                };
+
+                let path = std::env::temp_dir()
+                    .join(format!("halloy-paste-{}.txt", uuid::Uuid::now_v7()));
+
+                match std::fs::write(&path, text) {
+                    Ok(()) => {
+                        (Task::done(Message::FilesSelected(vec![path])), None)
+                    }
+                    Err(error) => {
+                        self.notice = Some(Notice::Error(format!(
+                            "failed to save pasted text: {error}"
+                        )));
+
+                        (Task::none(), None)
+                    }
+                }
+            }
+            Message::DiscardPastedText => {
+                self.pending_paste = None;
+
+                (Task::none(), None)
+            }
             Message::Cut => {
                 let task =
                     if let Some(selection) = self.input_content.selection() {
@@ -1280,6 +1507,9 @@ impl State {
                 Self::close_context_menu(main_window.id, vec![])
             }
             Message::CloseContextMenu(_, _) => (Task::none(), None),
+            Message::OpenEmojiPicker => {
+                (Task::none(), Some(Event::OpenEmojiPicker))
+            }
             Message::UploadFile => (
                 Task::perform(
                     async {
@@ -1511,6 +1741,7 @@ impl State {
                         let cursor = self.input_content.cursor();
 
                         self.notice = None;
+                        self.pending_confirmation = None;
                         self.selected_history = None;
 
                         if let Some(line) = self
@@ -1523,6 +1754,14 @@ impl State {
                                     .get_channel_users(buffer.server(), channel)
                             });
                             let last_seen = history.get_last_seen(buffer);
+                            let recent_words = history.get_recent_words(
+                                buffer,
+                                config
+                                    .buffer
+                                    .text_input
+                                    .autocomplete
+                                    .scrollback,
+                            );
                             let filters =
                                 FilterChain::borrow(history.get_filters());
                             let is_connected = clients
@@ -1540,6 +1779,7 @@ impl State {
                                 users,
                                 filters,
                                 &last_seen,
+                                &recent_words,
                                 clients.get_channels(buffer.server()),
                                 current_target.as_ref(),
                                 buffer.server(),
@@ -1591,6 +1831,30 @@ impl State {
         }
     }
 
+    /// The first `confirm` filter matched by any of the parsed lines.
+    fn confirmation<'a>(
+        &self,
+        buffer: &buffer::Upstream,
+        clients: &client::Map,
+        config: &'a Config,
+    ) -> Option<&'a Filter> {
+        let casemapping =
+            clients.get_server_casemapping_or_default(buffer.server());
+
+        self.parsed
+            .iter()
+            .filter_map(|parsed| parsed.as_ref().ok())
+            .cloned()
+            .flat_map(input::Parsed::into_lines)
+            .find_map(|parsed| match parsed {
+                input::Parsed::Input(input) => input.confirmation(
+                    &config.buffer.text_input.filters,
+                    casemapping,
+                ),
+                _ => None,
+            })
+    }
+
     // TODO: Create a parse_line variant that updates only a single line's
     // parsed update (and any following lines whose parsed value might change)
     fn parse_lines(
@@ -1639,21 +1903,47 @@ impl State {
                     Cow::Borrowed(line)
                 };
 
-                let parsed = input::parse(
-                    buffer.clone(),
-                    auto_format,
-                    &line,
-                    open_code_fence.as_ref(),
-                    nickname,
-                    in_channel,
-                    is_connected,
-                    isupport,
-                    capabilities,
-                    features,
-                    filehost,
-                    relay_bytes,
-                    config,
-                );
+                let parse = |line: &str, code_fence: Option<&CodeFence>| {
+                    input::parse(
+                        buffer.clone(),
+                        auto_format,
+                        line,
+                        code_fence,
+                        nickname,
+                        in_channel,
+                        is_connected,
+                        isupport,
+                        capabilities,
+                        features,
+                        filehost,
+                        relay_bytes,
+                        config,
+                    )
+                };
+
+                let steps = open_code_fence
+                    .is_none()
+                    .then(|| {
+                        command::alias::steps(
+                            &line,
+                            &command::alias::Context::new(
+                                Some(buffer),
+                                nickname,
+                            ),
+                            config,
+                        )
+                    })
+                    .flatten();
+
+                let parsed = match steps {
+                    Some(Ok(steps)) => steps
+                        .iter()
+                        .map(|step| parse(step, None))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map(input::Parsed::Steps),
+                    Some(Err(error)) => Err(input::Error::Command(error)),
+                    None => parse(&line, open_code_fence.as_ref()),
+                };
 
                 if open_code_fence.is_some() {
                     if parsed
@@ -1860,10 +2150,10 @@ impl State {
         let inputs = lines
             .into_iter()
             .filter_map(|parsed| match parsed {
-                input::Parsed::Internal(_) | input::Parsed::CodeFence(_) => {
-                    None
-                }
-                input::Parsed::Input(input) => Some(input),
+                input::Parsed::Internal(_)
+                | input::Parsed::CodeFence(_)
+                | input::Parsed::Steps(_) => None,
+                input::Parsed::Input(input) => input.with_plugins(),
             })
             .collect::<Vec<_>>();
 
@@ -2259,6 +2549,44 @@ impl State {
                             Some(Event::Reconnect(buffer.server().clone())),
                         );
                     }
+                    command::Internal::TestNotification(test) => {
+                        let casemapping = clients
+                            .get_server_casemapping_or_default(buffer.server());
+                        let channel = match buffer {
+                            Upstream::Channel(_, channel) => channel.clone(),
+                            Upstream::Server(_) | Upstream::Query(_, _) => {
+                                target::Channel::from_str(
+                                    "#halloy",
+                                    clients.get_server_chantypes_or_default(
+                                        buffer.server(),
+                                    ),
+                                    casemapping,
+                                )
+                            }
+                        };
+
+                        return (
+                            Task::none(),
+                            Some(Event::TestNotification(
+                                buffer.server().clone(),
+                                test.notification(channel, casemapping),
+                            )),
+                        );
+                    }
+                    command::Internal::Ignore(Some(mask)) => {
+                        return (Task::none(), Some(Event::Ignore(mask)));
+                    }
+                    command::Internal::Ignore(None) => {
+                        return (
+                            Task::none(),
+                            Some(Event::OpenInternalBuffer(
+                                buffer::Internal::Ignores,
+                            )),
+                        );
+                    }
+                    command::Internal::Unignore(mask) => {
+                        return (Task::none(), Some(Event::Unignore(mask)));
+                    }
                     command::Internal::Upload(_)
                         if !config.filehost.enabled =>
                     {
@@ -2299,6 +2627,18 @@ impl State {
                         };
                         return (anim, Some(event));
                     }
+                    command::Internal::Plugin(name, args) => {
+                        let target = buffer.target().map(|t| t.to_string());
+
+                        data::plugin::command(
+                            buffer.server(),
+                            target.as_deref(),
+                            &name,
+                            &args,
+                        );
+
+                        return (Task::none(), None);
+                    }
                     command::Internal::Exec(command) => {
                         if !config.buffer.commands.exec.enabled {
                             self.notice = Some(Notice::Error(
@@ -2333,8 +2673,13 @@ impl State {
                     }
                 }
             }
-            input::Parsed::Input(input) => input,
-            input::Parsed::CodeFence(_) => {
+            input::Parsed::Input(input) => match input.with_plugins() {
+                Some(input) => input,
+                // Cancelled by a plugin
+                None => return (Task::none(), None),
+            },
+            // Steps are split into lines before sending
+            input::Parsed::CodeFence(_) | input::Parsed::Steps(_) => {
                 return (Task::none(), None);
             }
         };
@@ -2394,8 +2739,23 @@ impl State {
 
         let mut history_task = Task::none();
 
-        if let Some(nick) = clients.nickname(buffer.server()) {
-            let mut user = nick.to_owned().into();
+        // Messages composed while disconnected are shown under our configured
+        // nickname until they're sent
+        let nick = clients
+            .nickname(buffer.server())
+            .map(|nick| nick.to_owned())
+            .or_else(|| {
+                config.servers.get(buffer.server()).map(|server_config| {
+                    Nick::from_str(
+                        &server_config.nickname,
+                        clients
+                            .get_server_casemapping_or_default(buffer.server()),
+                    )
+                })
+            });
+
+        if let Some(nick) = nick {
+            let mut user = nick.into();
             let mut channel_users = None;
 
             let chantypes =
@@ -2702,6 +3062,23 @@ impl State {
         });
     }
 
+    pub fn insert_emoji(
+        &mut self,
+        emoji: &str,
+        buffer: buffer::Upstream,
+        history: &mut history::Manager,
+    ) {
+        self.input_content.perform(text_editor::Action::Edit(
+            text_editor::Edit::Paste(std::sync::Arc::new(emoji.to_string())),
+        ));
+
+        history.record_draft(RawInput {
+            buffer,
+            text: self.input_content.text(),
+            reply: self.draft_reply.clone(),
+        });
+    }
+
     pub fn close_picker(&mut self) -> bool {
         self.completion.close_picker()
     }
@@ -2885,6 +3262,15 @@ impl State {
             }
             Some(Ok(parsed)) => {
                 if let Some(warning) = parsed.warning() {
+                    // Likely still typing a known command
+                    if matches!(
+                        warning,
+                        command::Warning::UnknownCommand { .. }
+                    ) && self.completion.has_command_candidates()
+                    {
+                        return;
+                    }
+
                     self.notice = Some(Notice::Warning(warning.to_string()));
                 }
             }
@@ -2900,6 +3286,7 @@ fn input_lines(text: &str) -> impl Iterator<Item = &str> {
 fn show_while_typing(error: &input::Error) -> bool {
     match error {
         input::Error::ExceedsByteLimit { .. }
+        | input::Error::Blocked(_)
         | input::Error::Command(
             command::Error::InvalidModeString
             | command::Error::ArgTooLong { .. }
@@ -2949,9 +3336,7 @@ fn handle_clipboard_content(
 ) -> Option<Task<Message>> {
     match Arc::unwrap_or_clone(content.ok()?) {
         clipboard::Content::Text(text) | clipboard::Content::Html(text) => {
-            Some(Task::done(Message::Action(text_editor::Action::Edit(
-                text_editor::Edit::Paste(text.into()),
-            ))))
+            Some(Task::done(Message::PasteText(text)))
         }
         clipboard::Content::Image(clipboard_image) => {
             let rgba_image: image::RgbaImage = image::ImageBuffer::from_raw(
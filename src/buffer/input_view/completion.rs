@@ -29,6 +29,7 @@ use crate::{emoji, font};
 
 const MAX_SHOWN_COMMAND_ENTRIES: usize = 5;
 const MAX_SHOWN_EMOJI_ENTRIES: usize = 8;
+const MAX_SHOWN_MODE_ENTRIES: usize = 8;
 const MAX_SHOWN_PATH_ENTRIES: usize = 8;
 const MAX_SHOWN_WORD_ENTRIES: usize = 8;
 
@@ -38,6 +39,7 @@ pub struct Completion {
     words: Words,
     emojis: Emojis,
     paths: Paths,
+    modes: Modes,
 }
 
 impl Completion {
@@ -55,6 +57,7 @@ impl Completion {
         users: Option<&ChannelUsers>,
         filters: FilterChain,
         last_seen: &HashMap<Nick, DateTime<Utc>>,
+        recent_words: &[String],
         channels: impl IntoIterator<Item = &'a target::Channel>,
         current_target: Option<&Target>,
         server: &Server,
@@ -86,6 +89,7 @@ impl Completion {
                 self.words = Words::default();
                 self.emojis = Emojis::default();
                 self.paths = Paths::default();
+                self.modes = Modes::default();
 
                 return;
             }
@@ -98,6 +102,7 @@ impl Completion {
             self.words = Words::default();
             self.emojis = Emojis::default();
             self.paths = Paths::default();
+            self.modes = Modes::default();
 
             return;
         }
@@ -113,6 +118,13 @@ impl Completion {
             self.emojis.process(shortcode, config);
 
             self.words = Words::default();
+            self.modes = Modes::default();
+        } else if is_command
+            && self.modes.process(input, cursor_position, isupport)
+        {
+            self.words = Words::default();
+            self.emojis = Emojis::default();
+            self.paths = Paths::default();
         } else if let Commands::Selected { command, .. } = &self.commands
             && command.title() == "UPLOAD"
         {
@@ -132,6 +144,7 @@ impl Completion {
                 users,
                 filters,
                 last_seen,
+                recent_words,
                 channels.iter().copied(),
                 current_target,
                 server,
@@ -149,10 +162,7 @@ impl Completion {
             .select()
             .map(Entry::Command)
             .or(self.emojis.select(config).map(Entry::Emoji))
-            .or(self.words.select().map(|next| Entry::Word {
-                next,
-                append_suffix: true,
-            }))
+            .or(self.words.select())
     }
 
     pub fn select_at(
@@ -165,10 +175,8 @@ impl Completion {
             .map(Entry::Command)
             .or(self.emojis.select_at(index, config).map(Entry::Emoji))
             .or(self.paths.select_at(index).map(Entry::Path))
-            .or(self.words.select_at(index).map(|next| Entry::Word {
-                next,
-                append_suffix: true,
-            }))
+            .or(self.modes.select_at(index).map(Entry::Mode))
+            .or(self.words.select_at(index))
     }
 
     pub fn complete_emoji(
@@ -188,6 +196,7 @@ impl Completion {
             .tab(reverse)
             .or_else(|| self.emojis.tab(reverse, config))
             .or_else(|| self.paths.tab(reverse))
+            .or_else(|| self.modes.tab(reverse))
             .or_else(|| self.words.tab(reverse))
     }
 
@@ -196,6 +205,7 @@ impl Completion {
             .tab_candidate_count()
             .or_else(|| self.emojis.tab_candidate_count())
             .or_else(|| self.paths.tab_candidate_count())
+            .or_else(|| self.modes.tab_candidate_count())
     }
 
     pub fn arrow(&mut self, arrow: Arrow) -> bool {
@@ -240,23 +250,39 @@ impl Completion {
         );
         let emojis_view = self.emojis.view(config, on_select_command);
         let paths_view = self.paths.view(on_select_command);
+        let modes_view = self.modes.view(on_select_command);
         let words_view = self.words.view(on_select_command);
 
         if command_view.is_some()
             || emojis_view.is_some()
             || paths_view.is_some()
+            || modes_view.is_some()
             || words_view.is_some()
         {
             Some(
-                column![emojis_view, paths_view, words_view, command_view]
-                    .spacing(4)
-                    .into(),
+                column![
+                    emojis_view,
+                    paths_view,
+                    modes_view,
+                    words_view,
+                    command_view
+                ]
+                .spacing(4)
+                .into(),
             )
         } else {
             None
         }
     }
 
+    /// Whether the command picker is listing commands matching the input.
+    pub fn has_command_candidates(&self) -> bool {
+        matches!(
+            &self.commands,
+            Commands::Selecting { filtered, .. } if !filtered.is_empty()
+        )
+    }
+
     pub fn close_picker(&mut self) -> bool {
         if matches!(self.commands, Commands::Selecting { .. }) {
             self.commands = Commands::Idle;
@@ -269,6 +295,10 @@ impl Completion {
         } else if matches!(self.words, Words::Selecting { .. }) {
             self.words = Words::Idle;
 
+            return true;
+        } else if matches!(self.modes, Modes::Selecting { .. }) {
+            self.modes = Modes::Idle;
+
             return true;
         }
 
@@ -279,8 +309,14 @@ impl Completion {
 #[derive(Debug, Clone)]
 pub enum Entry {
     Command(String),
-    Word { next: String, append_suffix: bool },
+    Word {
+        next: String,
+        append_suffix: bool,
+    },
+    /// A word or URL from recent messages in the buffer.
+    RecentWord(String),
     Path(String),
+    Mode(String),
     Emoji(String),
 }
 
@@ -326,9 +362,15 @@ impl Entry {
 
                 replace_word_with_text(input, cursor_position, next, suffix)
             }
+            Entry::RecentWord(word) => {
+                replace_word_with_text(input, cursor_position, word, Some(" "))
+            }
             Entry::Emoji(emoji) => {
                 replace_word_with_text(input, cursor_position, emoji, None)
             }
+            Entry::Mode(modestring) => {
+                replace_word_with_text(input, cursor_position, modestring, None)
+            }
             Entry::Path(path) => {
                 vec![
                     text_editor::Action::SelectAll,
@@ -1251,6 +1293,12 @@ fn connected_command_list<'a>(
         },
         // EXEC
         exec_command(),
+        // IGNORE
+        ignore_command(),
+        // SOFTIGNORE
+        soft_ignore_command(),
+        // UNIGNORE
+        unignore_command(),
         // CLEAR
         {
             Command {
@@ -1492,6 +1540,12 @@ fn disconnected_command_list(server: &Server) -> Vec<Command> {
     vec![
         // EXEC
         exec_command(),
+        // IGNORE
+        ignore_command(),
+        // SOFTIGNORE
+        soft_ignore_command(),
+        // UNIGNORE
+        unignore_command(),
         // CONNECT
         {
             Command {
@@ -1533,6 +1587,72 @@ fn exec_command() -> Command {
     }
 }
 
+fn ignore_command() -> Command {
+    Command {
+        title: "IGNORE".into(),
+        args: vec![
+            Argument {
+                text: "mask".into(),
+                kind: ArgumentKind::Optional { skipped: false },
+                tooltip: Some(
+                    "nick, user@host or nick!user@host, with * and ? wildcards\
+                   \nmay be skipped to list active ignores"
+                        .to_string(),
+                ),
+            },
+            Argument {
+                text: "types".into(),
+                kind: ArgumentKind::Optional { skipped: false },
+                tooltip: Some(
+                    "comma-separated list of messages, notices, ctcp or invites\
+                   \nmay be skipped (default: all)"
+                        .to_string(),
+                ),
+            },
+        ],
+        subcommands: None,
+    }
+}
+
+fn soft_ignore_command() -> Command {
+    Command {
+        title: "SOFTIGNORE".into(),
+        args: vec![
+            Argument {
+                text: "mask".into(),
+                kind: ArgumentKind::Required,
+                tooltip: Some(
+                    "nick, user@host or nick!user@host, with * and ? wildcards"
+                        .to_string(),
+                ),
+            },
+            Argument {
+                text: "types".into(),
+                kind: ArgumentKind::Optional { skipped: false },
+                tooltip: Some(
+                    "comma-separated list of messages, notices, ctcp or invites\
+                   \nmessages are collapsed, the others hidden\
+                   \nmay be skipped (default: all)"
+                        .to_string(),
+                ),
+            },
+        ],
+        subcommands: None,
+    }
+}
+
+fn unignore_command() -> Command {
+    Command {
+        title: "UNIGNORE".into(),
+        args: vec![Argument {
+            text: "mask".into(),
+            kind: ArgumentKind::Required,
+            tooltip: Some("a mask added with /ignore".to_string()),
+        }],
+        subcommands: None,
+    }
+}
+
 fn commands_from_aliases(aliases: &[command::Alias]) -> Vec<Command> {
     aliases
         .iter()
@@ -1685,6 +1805,11 @@ impl Command {
             "clear" => Cow::Borrowed("Clears the buffer"),
             "cleartopic" => Cow::Borrowed("Clear the topic of a channel"),
             "sysinfo" => Cow::Borrowed("Send system information"),
+            "ignore" => Cow::Borrowed("Hide messages from a hostmask"),
+            "softignore" => Cow::Borrowed(
+                "Collapse messages from a hostmask behind a toggle",
+            ),
+            "unignore" => Cow::Borrowed("Stop ignoring a hostmask"),
             "detach" => Cow::Borrowed(
                 "Hide the channel, leaving the bouncer's connection to the channel active",
             ),
@@ -1956,12 +2081,20 @@ enum Words {
         show_picker: bool,
         highlighted: Option<usize>,
         filtered: Vec<String>,
+        // Index of the first entry taken from recent messages rather than
+        // nicknames or channels
+        recent_from: usize,
     },
     Selected,
 }
 
 impl Words {
-    fn set_selecting(&mut self, prompt: String, filtered: Vec<String>) {
+    fn set_selecting(
+        &mut self,
+        prompt: String,
+        filtered: Vec<String>,
+        recent_from: usize,
+    ) {
         if filtered.is_empty() {
             *self = Self::Idle;
         } else {
@@ -1970,10 +2103,28 @@ impl Words {
                 show_picker: false,
                 highlighted: None,
                 filtered,
+                recent_from,
             };
         }
     }
 
+    fn entry(
+        filtered: &[String],
+        recent_from: usize,
+        index: usize,
+    ) -> Option<Entry> {
+        filtered.get(index).cloned().map(|next| {
+            if index < recent_from {
+                Entry::Word {
+                    next,
+                    append_suffix: true,
+                }
+            } else {
+                Entry::RecentWord(next)
+            }
+        })
+    }
+
     fn process<'a>(
         &mut self,
         input: &str,
@@ -1982,6 +2133,7 @@ impl Words {
         users: Option<&ChannelUsers>,
         filters: FilterChain,
         last_seen: &HashMap<Nick, DateTime<Utc>>,
+        recent_words: &[String],
         channels: impl IntoIterator<Item = &'a target::Channel>,
         current_target: Option<&Target>,
         server: &Server,
@@ -2005,6 +2157,7 @@ impl Words {
                 current_target.and_then(Target::as_channel),
                 server,
                 last_seen,
+                recent_words,
                 config,
             );
         }
@@ -2020,6 +2173,7 @@ impl Words {
         current_channel: Option<&target::Channel>,
         server: &Server,
         last_seen: &HashMap<Nick, DateTime<Utc>>,
+        recent_words: &[String],
         config: &Config,
     ) {
         let autocomplete = &config.buffer.text_input.autocomplete;
@@ -2031,7 +2185,7 @@ impl Words {
 
         let nick = casemapping.normalize(word);
 
-        let filtered = users
+        let mut filtered: Vec<String> = users
             .into_iter()
             .flatten()
             .filter(|user| {
@@ -2073,7 +2227,22 @@ impl Words {
             .map(|user| user.nickname().to_string())
             .collect();
 
-        self.set_selecting(word.to_string(), filtered);
+        let recent_from = filtered.len();
+        let prefix = word.to_lowercase();
+
+        let recent = recent_words
+            .iter()
+            .filter(|recent| {
+                recent.as_str() != word
+                    && recent.to_lowercase().starts_with(&prefix)
+                    && !filtered.contains(recent)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        filtered.extend(recent);
+
+        self.set_selecting(word.to_string(), filtered, recent_from);
     }
 
     fn process_channels<'a>(
@@ -2090,7 +2259,7 @@ impl Words {
         if let Some(input_channel) = get_word(input, cursor_position)
             && input_channel.starts_with(chantypes)
         {
-            let filtered = channels
+            let filtered: Vec<String> = channels
                 .into_iter()
                 .filter(|&channel| channel.as_str().starts_with(input_channel))
                 .sorted_by(|a, b: &&target::Channel| {
@@ -2130,7 +2299,13 @@ impl Words {
                 .map(ToString::to_string)
                 .collect();
 
-            self.set_selecting(input_channel.to_string(), filtered);
+            let recent_from = filtered.len();
+
+            self.set_selecting(
+                input_channel.to_string(),
+                filtered,
+                recent_from,
+            );
 
             matches!(self, Self::Selecting { .. })
         } else {
@@ -2144,6 +2319,7 @@ impl Words {
             show_picker,
             highlighted,
             filtered,
+            recent_from,
         } = self
         {
             if filtered.is_empty() {
@@ -2157,12 +2333,7 @@ impl Words {
                     if *index > 0 {
                         *index -= 1;
 
-                        return filtered.get(*index).cloned().map(|next| {
-                            Entry::Word {
-                                next,
-                                append_suffix: true,
-                            }
-                        });
+                        return Self::entry(filtered, *recent_from, *index);
                     }
 
                     *highlighted = None;
@@ -2176,12 +2347,7 @@ impl Words {
                 if *index < filtered.len() - 1 {
                     *index += 1;
 
-                    return filtered.get(*index).cloned().map(|next| {
-                        Entry::Word {
-                            next,
-                            append_suffix: true,
-                        }
-                    });
+                    return Self::entry(filtered, *recent_from, *index);
                 }
 
                 *highlighted = None;
@@ -2195,16 +2361,13 @@ impl Words {
             let index = if reverse { filtered.len() - 1 } else { 0 };
             *highlighted = Some(index);
 
-            filtered.get(index).cloned().map(|next| Entry::Word {
-                next,
-                append_suffix: true,
-            })
+            Self::entry(filtered, *recent_from, index)
         } else {
             None
         }
     }
 
-    fn select(&mut self) -> Option<String> {
+    fn select(&mut self) -> Option<Entry> {
         let index = if let Self::Selecting {
             highlighted,
             show_picker: true,
@@ -2219,17 +2382,18 @@ impl Words {
         self.select_at(index)
     }
 
-    fn select_at(&mut self, index: usize) -> Option<String> {
+    fn select_at(&mut self, index: usize) -> Option<Entry> {
         if let Self::Selecting {
             filtered,
+            recent_from,
             show_picker: true,
             ..
         } = self
-            && let Some(next) = filtered.get(index).cloned()
+            && let Some(entry) = Self::entry(filtered, *recent_from, index)
         {
             *self = Self::Selected;
 
-            return Some(next);
+            return Some(entry);
         }
 
         None
@@ -3719,6 +3883,186 @@ impl Paths {
     }
 }
 
+/// Channel modes offered while typing the modestring of `/mode <channel>`.
+#[derive(Debug, Clone, Default)]
+enum Modes {
+    #[default]
+    Idle,
+    Selecting {
+        modestring: String,
+        filtered: Vec<(char, String)>,
+        highlighted: Option<usize>,
+    },
+}
+
+impl Modes {
+    /// Returns whether the cursor is at the end of a channel modestring.
+    fn process(
+        &mut self,
+        input: &str,
+        cursor_position: usize,
+        isupport: &HashMap<isupport::Kind, isupport::Parameter>,
+    ) -> bool {
+        let chantypes = isupport::get_chantypes_or_default(isupport);
+        let args = input.split(' ').collect::<Vec<_>>();
+
+        let modestring = match args.as_slice() {
+            [command, channel, modestring]
+                if command.eq_ignore_ascii_case("/mode")
+                    && channel.starts_with(chantypes)
+                    && modestring.starts_with(['+', '-'])
+                    && cursor_position == input.len() =>
+            {
+                *modestring
+            }
+            _ => {
+                *self = Self::default();
+                return false;
+            }
+        };
+
+        let chanmodes = isupport::get_chanmodes_or_default(isupport)
+            .iter()
+            .flat_map(|chanmode| chanmode.modes.chars())
+            .map(|mode| (mode, mode::Channel::from(mode).to_string()));
+        let membership = isupport::get_prefix_or_default(isupport).iter().map(
+            |prefix_map| {
+                (
+                    prefix_map.mode,
+                    mode::Channel::from(prefix_map.prefix).to_string(),
+                )
+            },
+        );
+
+        let filtered = chanmodes
+            .chain(membership)
+            .filter(|(mode, _)| !modestring.contains(*mode))
+            .unique_by(|(mode, _)| *mode)
+            .collect::<Vec<_>>();
+
+        if filtered.is_empty() {
+            *self = Self::default();
+            return false;
+        }
+
+        *self = Self::Selecting {
+            modestring: modestring.to_string(),
+            filtered,
+            highlighted: None,
+        };
+
+        true
+    }
+
+    fn tab(&mut self, reverse: bool) -> Option<Entry> {
+        match self {
+            Self::Idle => None,
+            Self::Selecting {
+                modestring,
+                filtered,
+                highlighted,
+            } => {
+                selecting_tab(highlighted, filtered, reverse);
+
+                highlighted
+                    .and_then(|index| filtered.get(index))
+                    .map(|(mode, _)| Entry::Mode(format!("{modestring}{mode}")))
+            }
+        }
+    }
+
+    fn tab_candidate_count(&self) -> Option<usize> {
+        match self {
+            Self::Selecting { filtered, .. } => Some(filtered.len()),
+            Self::Idle => None,
+        }
+    }
+
+    fn select_at(&mut self, index: usize) -> Option<String> {
+        match self {
+            Self::Idle => None,
+            Self::Selecting {
+                modestring,
+                filtered,
+                highlighted,
+            } => {
+                let (mode, _) = filtered.get(index)?;
+
+                *highlighted = Some(index);
+
+                Some(format!("{modestring}{mode}"))
+            }
+        }
+    }
+
+    fn view<'a, Message: Clone + 'a>(
+        &'a self,
+        on_select: impl Fn(usize) -> Message + Copy + 'a,
+    ) -> Option<Element<'a, Message>> {
+        match self {
+            Self::Idle => None,
+            Self::Selecting {
+                filtered,
+                highlighted,
+                ..
+            } => {
+                let skip = {
+                    let index = highlighted.unwrap_or(0);
+                    let to = index.max(MAX_SHOWN_MODE_ENTRIES - 1);
+                    to.saturating_sub(MAX_SHOWN_MODE_ENTRIES - 1)
+                };
+
+                let entries: Vec<_> = filtered
+                    .iter()
+                    .enumerate()
+                    .skip(skip)
+                    .take(MAX_SHOWN_MODE_ENTRIES)
+                    .collect();
+
+                let content = |width| {
+                    column(entries.iter().map(
+                        |(index, (mode, description))| {
+                            let highlighted = Some(*index) == *highlighted;
+
+                            Element::from(
+                                button(
+                                    row![
+                                        text(mode.to_string()),
+                                        text(description.as_str())
+                                            .style(theme::text::secondary),
+                                    ]
+                                    .spacing(8),
+                                )
+                                .width(width)
+                                .padding(6)
+                                .style(move |theme, status| {
+                                    theme::button::picker(
+                                        theme,
+                                        status,
+                                        highlighted,
+                                    )
+                                })
+                                .on_press(on_select(*index)),
+                            )
+                        },
+                    ))
+                };
+
+                Some(
+                    container(double_pass(
+                        content(Length::Shrink),
+                        content(Length::Fill),
+                    ))
+                    .padding(4)
+                    .style(theme::container::tooltip)
+                    .width(Length::Shrink)
+                    .into(),
+                )
+            }
+        }
+    }
+}
+
 fn pick_emoji(shortcode: &str, skin_tone: SkinTone) -> Option<&'static str> {
     emoji::get_by_shortcode(shortcode).map(|emoji| {
         if let Some(emoji_with_skin_tone) =
@@ -3941,3 +4285,34 @@ enum Formatting {
     Format,
     Plain,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::Modes;
+
+    #[test]
+    fn modes_at_end_of_modestring() {
+        let isupport = HashMap::new();
+        let mut modes = Modes::default();
+
+        let input = "/mode #halloy +n";
+        assert!(modes.process(input, input.len(), &isupport));
+        let Modes::Selecting { filtered, .. } = &modes else {
+            panic!("expected mode candidates");
+        };
+        assert!(filtered.iter().all(|(mode, _)| *mode != 'n'));
+
+        assert!(!modes.process(input, input.len() - 1, &isupport));
+        assert!(matches!(modes, Modes::Idle));
+
+        // Cursor positions are byte offsets
+        let input = "/mode #café +";
+        assert!(modes.process(input, input.len(), &isupport));
+        assert!(!modes.process(input, input.chars().count(), &isupport));
+
+        let input = "/mode halloy +";
+        assert!(!modes.process(input, input.len(), &isupport));
+    }
+}
@@ -21,8 +21,10 @@ pub enum Event {
     MarkAsRead,
     OpenUrl(String),
     ImagePreview(Image),
+    LoadPreview(url::Url),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
+    ExpandReplay(DateTime<Utc>, bool),
 }
 
 pub fn view<'a>(
@@ -41,6 +43,7 @@ pub fn view<'a>(
             None,
             Option::<fn(&Preview, &message::Source) -> bool>::None,
             None,
+            None,
             0.0,
             config,
             theme,
@@ -170,12 +173,18 @@ impl Logs {
                     scroll_view::Event::ImagePreview(image) => {
                         Some(Event::ImagePreview(image))
                     }
+                    scroll_view::Event::LoadPreview(url) => {
+                        Some(Event::LoadPreview(url))
+                    }
                     scroll_view::Event::ExpandMessage(server_time, hash) => {
                         Some(Event::ExpandMessage(server_time, hash))
                     }
                     scroll_view::Event::ContractMessage(server_time, hash) => {
                         Some(Event::ContractMessage(server_time, hash))
                     }
+                    scroll_view::Event::ExpandReplay(from, expanded) => {
+                        Some(Event::ExpandReplay(from, expanded))
+                    }
                 });
 
                 (command.map(Message::ScrollView), event)
@@ -1,16 +1,18 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 use chrono::{DateTime, TimeDelta, Utc};
+use data::appearance::theme::nickname_color;
 use data::buffer::RightAlignmentWidths;
 use data::config::buffer::nickname::ShownStatus;
 use data::config::buffer::{CondensationIcon, Dimmed};
-use data::config::preview::HideUrlCondition;
+use data::config::preview::{Fetch, HideUrlCondition};
 use data::isupport::{CaseMap, PrefixMap};
+use data::nick_history::NickHistory;
 use data::preview::{self, Previews};
 use data::redaction::Redaction;
 use data::server::Server;
 use data::user::{ChannelUsers, NickRef};
-use data::{Config, Preview, User, history, message, metadata, target};
+use data::{Config, Preview, User, history, message, metadata, outbox, target};
 use iced::Length::Fit;
 use iced::widget::text::LineHeight;
 use iced::widget::{
@@ -88,7 +90,9 @@ pub struct ChannelQueryLayout<'a> {
     pub casemapping: CaseMap,
     pub prefix: &'a [PrefixMap],
     pub registry: &'a dyn metadata::Registry,
+    pub nick_history: &'a NickHistory,
     pub confirm_message_delivery: bool,
+    pub deferred: &'a [outbox::Message],
     pub can_send_replies: bool,
     pub can_send_reactions: bool,
     pub can_redact: bool,
@@ -99,6 +103,7 @@ pub struct ChannelQueryLayout<'a> {
     pub previews: Previews<'a>,
     pub target: TargetInfo<'a>,
     pub history: &'a history::Manager,
+    pub ignores: &'a [data::ignore::Mask],
 }
 
 impl<'a> ChannelQueryLayout<'a> {
@@ -285,9 +290,19 @@ impl<'a> ChannelQueryLayout<'a> {
         })
     }
 
+    fn deferred(&self, message: &data::Message) -> bool {
+        matches!(message.direction, message::Direction::Sent)
+            && message.command.as_ref().is_some_and(|command| {
+                self.deferred
+                    .iter()
+                    .any(|deferred| deferred.is_command(command))
+            })
+    }
+
     fn not_sent(&self, message: &data::Message) -> bool {
         self.confirm_message_delivery
             && message.command.is_some()
+            && !self.deferred(message)
             && matches!(message.direction, message::Direction::Sent)
             && Utc::now().signed_duration_since(message.server_time)
                 > TimeDelta::seconds(10)
@@ -297,16 +312,44 @@ impl<'a> ChannelQueryLayout<'a> {
         &self,
         message: &'a data::Message,
     ) -> Option<Element<'a, Message>> {
-        if !self.not_sent(message) {
-            return None;
-        }
-
         let font_size =
             0.85 * self.config.font.size.map_or(theme::TEXT_SIZE, f32::from);
         let icon_size = theme::line_height(&self.config.font)
             .to_absolute(font_size.into())
             .0;
 
+        if self.deferred(message) {
+            return Some(
+                context_menu::deferred_message(
+                    button(
+                        row![
+                            icon::connecting()
+                                .style(theme::text::secondary)
+                                .height(icon_size)
+                                .width(Length::Shrink)
+                                .content_fit(ContentFit::Contain),
+                            text(" Sending once reconnected")
+                                .line_height(LineHeight::Relative(1.0))
+                                .style(theme::text::secondary)
+                                .size(font_size)
+                        ]
+                        .align_y(alignment::Vertical::Center),
+                    )
+                    .style(theme::button::bare)
+                    .padding(padding::top(self.config.buffer.line_spacing)),
+                    &message.server_time,
+                    &message.hash,
+                    self.config,
+                    self.theme,
+                )
+                .map(Message::ContextMenu),
+            );
+        }
+
+        if !self.not_sent(message) {
+            return None;
+        }
+
         Some(
             context_menu::not_sent_message(
                 button(
@@ -459,6 +502,15 @@ impl<'a> ChannelQueryLayout<'a> {
                         .style(theme::button::bare),
                 )
             }
+            data::Preview::Title(_) => keyed(
+                keyed::Key::Preview(message.hash, index),
+                button(preview_content(preview, self.config, self.theme))
+                    .on_press(Message::Link(message::Link::Url(
+                        url.to_string(),
+                    )))
+                    .padding(0)
+                    .style(theme::button::bare),
+            ),
         };
 
         let content = context_menu::preview(
@@ -514,6 +566,64 @@ impl<'a> ChannelQueryLayout<'a> {
             .into()
     }
 
+    /// A link to a video file, or a button to fetch the preview when
+    /// previews are only fetched on demand.
+    fn preview_placeholder(
+        &self,
+        url: &'a url::Url,
+    ) -> Option<Element<'a, Message>> {
+        let config = &self.config.preview;
+
+        if !config.is_enabled(url.as_str()) {
+            return None;
+        }
+
+        let (icon, label, on_press) = if preview::is_video(url) {
+            if !config.video.enabled {
+                return None;
+            }
+
+            let file_name = url
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .unwrap_or(url.as_str());
+
+            (
+                icon::open(),
+                file_name,
+                Message::Link(message::Link::Url(url.to_string())),
+            )
+        } else if matches!(config.fetch, Fetch::OnDemand)
+            && self.previews.collection().get(url).is_none()
+        {
+            (
+                icon::plus(),
+                "Load preview",
+                Message::LoadPreview(url.clone()),
+            )
+        } else {
+            return None;
+        };
+
+        Some(
+            container(
+                button(
+                    row![
+                        icon.style(theme::text::secondary),
+                        text(label).style(theme::text::secondary),
+                    ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+                )
+                .padding([4, 8])
+                .style(theme::button::preview_card)
+                .on_press(on_press),
+            )
+            .padding(padding::top(4).bottom(4))
+            .into(),
+        )
+    }
+
     fn format_user_message(
         &self,
         message: &'a data::Message,
@@ -531,7 +641,16 @@ impl<'a> ChannelQueryLayout<'a> {
     ) {
         let not_sent_row = self.not_sent_row(message);
 
-        let dimmed = (not_sent_row.is_some() || message.redaction.is_some())
+        // Soft ignored, shown once expanded
+        let collapsed = message.is_collapsed(
+            self.config.ignore.masks.iter().chain(self.ignores),
+            user,
+            self.casemapping,
+        );
+
+        let dimmed = (not_sent_row.is_some()
+            || message.redaction.is_some()
+            || collapsed)
             .then_some(Dimmed::new(None));
         let dimmed_background_tuple = dimmed
             .map(|dimmed| (dimmed, self.theme.styles().buffer.background));
@@ -604,6 +723,7 @@ impl<'a> ChannelQueryLayout<'a> {
                     self.server,
                     self.prefix,
                     self.registry,
+                    self.nick_history,
                     self.previews.collection(),
                     user,
                     self.config,
@@ -618,6 +738,7 @@ impl<'a> ChannelQueryLayout<'a> {
                     self.prefix,
                     self.target.channel(),
                     self.registry,
+                    self.nick_history,
                     self.previews.collection(),
                     user,
                     user_in_channel,
@@ -632,10 +753,21 @@ impl<'a> ChannelQueryLayout<'a> {
 
         let formatter = *self;
 
+        let sender_color =
+            self.config.buffer.nickname.color_message.then(|| {
+                nickname_color(
+                    self.theme.styles(),
+                    &self.config.buffer.nickname,
+                    Some(user.seed()),
+                )
+            });
+
         let message_style = move |message_theme: &Theme| {
             theme::selectable_text::dimmed(
                 if rerouted_message {
                     theme::selectable_text::tertiary(message_theme)
+                } else if let Some(color) = sender_color {
+                    theme::selectable_text::sender(message_theme, color)
                 } else {
                     theme::selectable_text::default(message_theme)
                 },
@@ -656,81 +788,102 @@ impl<'a> ChannelQueryLayout<'a> {
         let redaction_message =
             message.redaction.as_ref().map(Redaction::message);
 
-        let (message_content, after_content) =
-            if self.config.buffer.redaction.display.is_redacted()
-                && !message.expanded
-                && let Some(redaction_message) = redaction_message
-            {
-                (
-                    button(
-                        selectable_text(redaction_message)
-                            .font_maybe(
-                                theme::font_style::primary(self.theme)
-                                    .map(font::get),
-                            )
-                            .style(message_style),
+        let (message_content, after_content) = if collapsed {
+            (
+                button(
+                    selectable_text(format!(
+                        "Show message from {}",
+                        user.nickname()
+                    ))
+                    .font_maybe(
+                        theme::font_style::primary(self.theme).map(font::get),
                     )
-                    .style(theme::button::bare)
-                    .padding(0)
-                    .on_press(Message::Link(message::Link::ExpandMessage(
-                        message.server_time,
-                        message.hash,
-                        None,
-                    )))
-                    .into(),
-                    vec![],
+                    .style(message_style),
                 )
-            } else {
-                (
-                    tooltip(
-                        message_content::with_context(
-                            &message.content,
-                            hidden_fragments,
-                            self.server,
-                            self.registry,
-                            self.chantypes,
-                            self.casemapping,
-                            self.theme,
-                            Message::Link,
-                            None,
-                            message_style,
-                            theme::font_style::primary,
-                            color_transformation,
-                            move |link| {
-                                formatter.link_entries(
-                                    message,
-                                    link,
-                                    channel_is_focused,
-                                    channel_is_open,
-                                )
-                            },
-                            move |link, entry, length| {
-                                entry
-                                    .view(
-                                        formatter.link_context(
-                                            message,
-                                            link,
-                                            channel_is_open,
-                                        ),
-                                        length,
-                                        formatter.config,
-                                        formatter.theme,
-                                    )
-                                    .map(Message::ContextMenu)
-                            },
-                            nick_prefix_to_strip,
-                            self.config,
-                        ),
-                        redaction_message,
-                        tooltip::Position::Top,
+                .style(theme::button::bare)
+                .padding(0)
+                .on_press(Message::Link(message::Link::ExpandMessage(
+                    message.server_time,
+                    message.hash,
+                    None,
+                )))
+                .into(),
+                vec![],
+            )
+        } else if self.config.buffer.redaction.display.is_redacted()
+            && !message.expanded
+            && let Some(redaction_message) = redaction_message
+        {
+            (
+                button(
+                    selectable_text(redaction_message)
+                        .font_maybe(
+                            theme::font_style::primary(self.theme)
+                                .map(font::get),
+                        )
+                        .style(message_style),
+                )
+                .style(theme::button::bare)
+                .padding(0)
+                .on_press(Message::Link(message::Link::ExpandMessage(
+                    message.server_time,
+                    message.hash,
+                    None,
+                )))
+                .into(),
+                vec![],
+            )
+        } else {
+            (
+                tooltip(
+                    message_content::with_context(
+                        &message.content,
+                        hidden_fragments,
+                        self.server,
+                        self.registry,
+                        self.chantypes,
+                        self.casemapping,
                         self.theme,
+                        Message::Link,
+                        None,
+                        message_style,
+                        theme::font_style::primary,
+                        color_transformation,
+                        move |link| {
+                            formatter.link_entries(
+                                message,
+                                link,
+                                channel_is_focused,
+                                channel_is_open,
+                            )
+                        },
+                        move |link, entry, length| {
+                            entry
+                                .view(
+                                    formatter.link_context(
+                                        message,
+                                        link,
+                                        channel_is_open,
+                                    ),
+                                    length,
+                                    formatter.config,
+                                    formatter.theme,
+                                )
+                                .map(Message::ContextMenu)
+                        },
+                        nick_prefix_to_strip,
+                        self.config,
                     ),
-                    self.reaction_row(message)
-                        .into_iter()
-                        .chain(not_sent_row)
-                        .collect(),
-                )
-            };
+                    redaction_message,
+                    tooltip::Position::Top,
+                    self.theme,
+                ),
+                self.reaction_row(message)
+                    .into_iter()
+                    .chain(not_sent_row)
+                    .collect(),
+            )
+        };
 
         (Some(nick_element), message_content, after_content)
     }
@@ -1066,6 +1219,7 @@ impl<'a> ChannelQueryLayout<'a> {
                     prefix: self.prefix,
                     channel: self.target.channel(),
                     registry: self.registry,
+                    nick_history: self.nick_history,
                     avatar: context_menu::user_avatar(
                         user,
                         self.registry,
@@ -1366,6 +1520,81 @@ impl<'a> LayoutMessage<'a> for ChannelQueryLayout<'a> {
             message::Source::Internal(message::source::Internal::Logs(_)) => {
                 None
             }
+            message::Source::Internal(message::source::Internal::Output) => {
+                let message_style = move |message_theme: &Theme| {
+                    theme::selectable_text::server(message_theme, None)
+                };
+                let message_font_style = move |message_theme: &Theme| {
+                    theme::font_style::server(message_theme, None)
+                };
+
+                let link = if message.expanded {
+                    message::Link::ContractMessage(
+                        message.server_time,
+                        message.hash,
+                        None,
+                    )
+                } else {
+                    message::Link::ExpandMessage(
+                        message.server_time,
+                        message.hash,
+                        None,
+                    )
+                };
+
+                let marker = message_marker(
+                    if message.expanded {
+                        Marker::Contract
+                    } else {
+                        Marker::Expand
+                    },
+                    right_alignment_middle_width,
+                    self.config,
+                    theme::selectable_text::condensed_marker,
+                    Some(Message::Link(link.clone())),
+                );
+
+                let content = if message.expanded {
+                    message_content(
+                        &message.content,
+                        &[],
+                        self.server,
+                        self.registry,
+                        self.chantypes,
+                        self.casemapping,
+                        self.theme,
+                        Message::Link,
+                        None,
+                        message_style,
+                        message_font_style,
+                        Option::<fn(Color) -> Color>::None,
+                        None,
+                        self.config,
+                    )
+                } else {
+                    let lines =
+                        message.text().lines().count().saturating_sub(1);
+
+                    button(
+                        selectable_text(format!(
+                            "{} ({lines} {})",
+                            message.title().unwrap_or_default(),
+                            if lines == 1 { "line" } else { "lines" }
+                        ))
+                        .font_maybe(
+                            theme::font_style::server(self.theme, None)
+                                .map(font::get),
+                        )
+                        .style(message_style),
+                    )
+                    .style(theme::button::bare)
+                    .padding(0)
+                    .on_press(Message::Link(link))
+                    .into()
+                };
+
+                Some((Some(marker), content, vec![]))
+            }
             message::Source::Internal(
                 message::source::Internal::Condensed(_),
             ) => (!message.text().is_empty()).then_some(
@@ -1441,6 +1670,9 @@ impl<'a> LayoutMessage<'a> for ChannelQueryLayout<'a> {
                     column = column.push(self.preview_row(
                         message, preview, url, *index, is_hovered,
                     ));
+                } else if let Some(placeholder) = self.preview_placeholder(url)
+                {
+                    column = column.push(placeholder);
                 }
             }
 
@@ -1954,6 +2186,7 @@ impl<'a> ChannelQueryLayout<'a> {
                     data::Preview::Image(..) => {
                         container(el).height(Fit.max(200)).into()
                     }
+                    data::Preview::Title(..) => el,
                 };
                 content_col = content_col.push(el);
             }
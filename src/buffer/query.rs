@@ -5,13 +5,14 @@ use data::preview::{self, Previews};
 use data::target::{self, Target};
 use data::user::Nick;
 use data::{
-    Config, Image, Preview, Server, User, buffer, client, history, message,
+    Config, Image, Preview, Server, User, buffer, client, file_transfer,
+    history, message,
 };
 use iced::widget::{column, container, stack};
 use iced::{Length, Size, Task, padding};
 
 use super::message_view::{ChannelQueryLayout, TargetInfo};
-use super::{context_menu, input_view, scroll_view, typing};
+use super::{context_menu, file_transfers, input_view, scroll_view, typing};
 use crate::Theme;
 use crate::widget::Element;
 use crate::window::Window;
@@ -22,6 +23,7 @@ pub enum Message {
     InputView(input_view::Message),
     FilehostUploadDone { id: u32, url: Option<String> },
     FilesDropped(Vec<std::path::PathBuf>),
+    FileTransfer(file_transfer::Id, file_transfers::Response),
 }
 
 pub enum Event {
@@ -29,7 +31,11 @@ pub enum Event {
     OpenBuffers(Server, Vec<(Target, BufferAction)>),
     OpenInternalBuffer(buffer::Internal),
     OpenServer(String),
+    OpenEmojiPicker,
     Reconnect(Server),
+    TestNotification(Server, data::Notification),
+    Ignore(data::ignore::Mask),
+    Unignore(String),
     LeaveBuffers(Vec<Target>, Option<String>),
     History(Task<history::manager::Message>),
     RequestOlderChatHistory,
@@ -38,8 +44,10 @@ pub enum Event {
     MarkAsRead(history::Kind),
     OpenUrl(String),
     ImagePreview(Image),
+    LoadPreview(url::Url),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
+    ExpandReplay(DateTime<Utc>, bool),
     InputSent {
         history_task: Task<history::manager::Message>,
         open_buffers: Vec<(Target, BufferAction)>,
@@ -52,14 +60,18 @@ pub enum Event {
         upload_ids: Vec<u32>,
         abort_registrations: Vec<futures::future::AbortRegistration>,
     },
+    FileTransfer(file_transfer::Id, file_transfers::Response),
 }
 
 pub fn view<'a>(
     state: &'a Query,
     typing_animation: Option<&'a typing::Animation>,
     clients: &'a data::client::Map,
+    file_transfers: &'a file_transfer::Manager,
     history: &'a history::Manager,
     previews: &'a preview::Collection,
+    settings: Option<&'a buffer::Settings>,
+    ignores: &'a [data::ignore::Mask],
     config: &'a Config,
     theme: &'a Theme,
     is_focused: bool,
@@ -106,7 +118,9 @@ pub fn view<'a>(
         casemapping,
         prefix,
         registry,
+        nick_history: clients.get_nick_history(server),
         confirm_message_delivery,
+        deferred: clients.get_server_deferred(server),
         can_send_reactions,
         can_redact,
         can_send_replies,
@@ -117,6 +131,7 @@ pub fn view<'a>(
         previews,
         target: TargetInfo::Query { query },
         history,
+        ignores,
     };
 
     let messages = container(
@@ -127,6 +142,7 @@ pub fn view<'a>(
             Some(previews),
             Option::<fn(&Preview, &message::Source) -> bool>::None,
             chathistory_state,
+            settings.map(|settings| &settings.hidden_messages),
             typing::reserved_bottom_padding(
                 has_typing_text,
                 typing_style,
@@ -166,7 +182,27 @@ pub fn view<'a>(
         .map(Message::InputView)
     });
 
-    let content = column![messages];
+    let file_transfer_requests = column(
+        file_transfers
+            .list()
+            .filter(|transfer| {
+                transfer.server == *server
+                    && target::Query::from(&transfer.remote_user) == *query
+                    && file_transfers.is_pending_approval(&transfer.id)
+            })
+            .map(|transfer| {
+                let id = transfer.id;
+
+                file_transfers::request_card(
+                    transfer,
+                    move |response| Message::FileTransfer(id, response),
+                    theme,
+                )
+            }),
+    )
+    .spacing(1);
+
+    let content = column![file_transfer_requests, messages];
 
     let body: Element<'a, Message> =
         if typing::show_row(show_typing, typing_style, has_typing_text) {
@@ -320,12 +356,18 @@ impl Query {
                     scroll_view::Event::ImagePreview(image) => {
                         Some(Event::ImagePreview(image))
                     }
+                    scroll_view::Event::LoadPreview(url) => {
+                        Some(Event::LoadPreview(url))
+                    }
                     scroll_view::Event::ExpandMessage(server_time, hash) => {
                         Some(Event::ExpandMessage(server_time, hash))
                     }
                     scroll_view::Event::ContractMessage(server_time, hash) => {
                         Some(Event::ContractMessage(server_time, hash))
                     }
+                    scroll_view::Event::ExpandReplay(from, expanded) => {
+                        Some(Event::ExpandReplay(from, expanded))
+                    }
                 });
 
                 (command.map(Message::ScrollView), event)
@@ -380,9 +422,25 @@ impl Query {
                     Some(input_view::Event::OpenServer(server)) => {
                         (command, Some(Event::OpenServer(server)))
                     }
+                    Some(input_view::Event::OpenEmojiPicker) => {
+                        (command, Some(Event::OpenEmojiPicker))
+                    }
                     Some(input_view::Event::Reconnect(server)) => {
                         (command, Some(Event::Reconnect(server)))
                     }
+                    Some(input_view::Event::TestNotification(
+                        server,
+                        notification,
+                    )) => (
+                        command,
+                        Some(Event::TestNotification(server, notification)),
+                    ),
+                    Some(input_view::Event::Ignore(mask)) => {
+                        (command, Some(Event::Ignore(mask)))
+                    }
+                    Some(input_view::Event::Unignore(mask)) => {
+                        (command, Some(Event::Unignore(mask)))
+                    }
                     Some(input_view::Event::FilehostUpload {
                         server,
                         target,
@@ -413,6 +471,9 @@ impl Query {
                 );
                 (task.map(Message::InputView), None)
             }
+            Message::FileTransfer(id, response) => {
+                (Task::none(), Some(Event::FileTransfer(id, response)))
+            }
             Message::FilesDropped(paths) => {
                 let (task, event) = self.input_view.update(
                     input_view::Message::FilesSelected(paths),
@@ -15,7 +15,7 @@ use data::rate_limit::TokenPriority;
 use data::reaction::Reaction;
 use data::server::Server;
 use data::target::{self, Target};
-use data::{Config, Image, Preview, client, history, metadata, reaction};
+use data::{Config, Image, Preview, client, history, i18n, metadata, reaction};
 use iced::widget::{
     self, Scrollable, button, column, container, row, rule, scrollable, space,
     text,
@@ -65,10 +65,12 @@ pub enum Message {
     PreviewHovered(message::Hash, usize),
     PreviewUnhovered(message::Hash, usize),
     HidePreview(message::Hash, url::Url),
+    LoadPreview(url::Url),
     MarkAsRead,
     ContentResized(Size),
     PendingScrollTo,
     FadeHighlight(message::Hash, u64),
+    ExpandReplay(DateTime<Utc>, bool),
     HeightsCollected(Vec<(keyed::Key, f32)>),
     Reacted {
         msgid: message::Id,
@@ -97,8 +99,10 @@ pub enum Event {
     MarkAsRead,
     OpenUrl(String),
     ImagePreview(Image),
+    LoadPreview(url::Url),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
+    ExpandReplay(DateTime<Utc>, bool),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -236,6 +240,33 @@ fn has_visible_preview(
     false
 }
 
+/// Shows the first message of a replay with a button to expand or fold the
+/// rest of the messages replayed after reconnecting.
+fn replay_fold<'a>(
+    folded: history::Folded,
+    element: Element<'a, Message>,
+    font_size: f32,
+) -> Element<'a, Message> {
+    let count = i18n::format_number(folded.count as u64);
+    let content = if folded.expanded {
+        i18n::tr_args("buffer.replay.hide", &[("count", &count)])
+    } else {
+        i18n::tr_args("buffer.replay.folded", &[("count", &count)])
+    };
+
+    let toggle = button(text(content).size(font_size))
+        .padding([3, 5])
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press(Message::ExpandReplay(folded.from, !folded.expanded));
+
+    let toggle_row = row![space::horizontal(), toggle, space::horizontal()]
+        .padding(padding::top(2).bottom(2))
+        .width(Length::Fill)
+        .align_y(iced::Alignment::Center);
+
+    column![toggle_row, element].into()
+}
+
 fn is_consecutive_user_message(
     message: &data::Message,
     prev_message: Option<&data::Message>,
@@ -269,6 +300,7 @@ pub fn view<'a>(
     previews: Option<Previews<'a>>,
     visible_for_source: Option<impl Fn(&Preview, &message::Source) -> bool>,
     chathistory_state: Option<ChatHistoryState>,
+    hidden_messages: Option<&data::buffer::HiddenMessages>,
     reserved_bottom_padding: f32,
     config: &'a Config,
     theme: &'a Theme,
@@ -286,8 +318,14 @@ pub fn view<'a>(
         old_messages,
         new_messages,
         cleared,
+        replays,
         ..
-    }) = history.get_messages(&kind.into(), Some(state.limit), config)
+    }) = history.get_messages(
+        &kind.into(),
+        Some(state.limit),
+        hidden_messages,
+        config,
+    )
     else {
         return column![].into();
     };
@@ -509,7 +547,18 @@ pub fn view<'a>(
                             channel_is_focused,
                             channel_is_open,
                         )
-                        .map(|element| (message, element)),
+                        .map(|element| {
+                            let element = match replays.get(&message.hash) {
+                                Some(folded) => replay_fold(
+                                    *folded,
+                                    element,
+                                    divider_font_size,
+                                ),
+                                None => element,
+                            };
+
+                            (message, element)
+                        }),
                 )
             })
             .flatten()
@@ -959,6 +1008,7 @@ impl State {
                             }) = history.get_messages(
                                 &kind.into(),
                                 Some(self.limit),
+                                None,
                                 config,
                             ) && let Some(oldest) =
                                 old_messages.iter().chain(&new_messages).next()
@@ -1229,6 +1279,12 @@ impl State {
                     Some(Event::ContractMessage(server_time, hash)),
                 );
             }
+            Message::ExpandReplay(from, expanded) => {
+                return (
+                    Task::none(),
+                    Some(Event::ExpandReplay(from, expanded)),
+                );
+            }
             Message::RequestOlderChatHistory => {
                 if let Some(server) = kind.server() {
                     self.status = Status::Unlocked;
@@ -1308,6 +1364,9 @@ impl State {
             Message::ImagePreview(image) => {
                 return (Task::none(), Some(Event::ImagePreview(image)));
             }
+            Message::LoadPreview(url) => {
+                return (Task::none(), Some(Event::LoadPreview(url)));
+            }
             Message::PendingScrollTo => {
                 if let Some(key) = &self.pending_scroll_to {
                     let scroll_to = keyed::find(self.scrollable.clone(), *key)
@@ -1492,7 +1551,7 @@ impl State {
             old_messages,
             new_messages,
             ..
-        }) = history.get_messages(&kind.into(), None, config)
+        }) = history.get_messages(&kind.into(), None, None, config)
         else {
             // We're still loading history, which will trigger scroll_to_backlog
             // after loading. If this is set, we will scroll_to_message
@@ -1536,7 +1595,7 @@ impl State {
             old_messages,
             new_messages,
             ..
-        }) = history.get_messages(&kind.into(), None, config)
+        }) = history.get_messages(&kind.into(), None, None, config)
         else {
             return Task::none();
         };
@@ -1582,7 +1641,7 @@ impl State {
             old_messages,
             new_messages,
             ..
-        }) = history.get_messages(&kind.into(), None, config)
+        }) = history.get_messages(&kind.into(), None, None, config)
         else {
             return Task::none();
         };
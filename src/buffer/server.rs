@@ -7,7 +7,7 @@ use data::{
     Config, Image, Preview, User, buffer, client, history, message, preview,
 };
 use iced::advanced::text;
-use iced::widget::{Space, column, container, row, space};
+use iced::widget::{Space, button, column, container, row, space};
 use iced::{Color, Length, Size, Task, padding};
 
 use super::{context_menu, input_view, scroll_view};
@@ -51,14 +51,20 @@ pub enum Event {
     OpenBuffers(data::server::Server, Vec<(Target, BufferAction)>),
     OpenInternalBuffer(buffer::Internal),
     OpenServer(String),
+    OpenEmojiPicker,
     Reconnect(data::server::Server),
+    TestNotification(data::server::Server, data::Notification),
+    Ignore(data::ignore::Mask),
+    Unignore(String),
     LeaveBuffers(Vec<Target>, Option<String>),
     History(Task<history::manager::Message>),
     MarkAsRead(history::Kind),
     OpenUrl(String),
     ImagePreview(Image),
+    LoadPreview(url::Url),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
+    ExpandReplay(DateTime<Utc>, bool),
     InputSent {
         history_task: Task<history::manager::Message>,
         open_buffers: Vec<(Target, BufferAction)>,
@@ -91,6 +97,7 @@ pub fn view<'a>(
         clients.nickname(&state.server);
     let our_user = our_nick.map(|our_nick| User::from(Nick::from(our_nick)));
     let registry = clients.get_registry(&state.server);
+    let nick_history = clients.get_nick_history(&state.server);
 
     let messages = container(
         scroll_view::view(
@@ -100,6 +107,7 @@ pub fn view<'a>(
             None,
             Option::<fn(&Preview, &message::Source) -> bool>::None,
             None,
+            None,
             0.0,
             config,
             theme,
@@ -191,6 +199,77 @@ pub fn view<'a>(
 
                         Some(row_with_timestamp(timestamp, content))
                     }
+                    message::Source::Internal(
+                        message::source::Internal::Output,
+                    ) => {
+                        let link = if message.expanded {
+                            message::Link::ContractMessage(
+                                message.server_time,
+                                message.hash,
+                                None,
+                            )
+                        } else {
+                            message::Link::ExpandMessage(
+                                message.server_time,
+                                message.hash,
+                                None,
+                            )
+                        };
+
+                        let content = if message.expanded {
+                            message_content(
+                                &message.content,
+                                &[],
+                                &state.server,
+                                registry,
+                                chantypes,
+                                casemapping,
+                                theme,
+                                scroll_view::Message::Link,
+                                None,
+                                move |theme| {
+                                    theme::selectable_text::server(theme, None)
+                                },
+                                move |theme| {
+                                    theme::font_style::server(theme, None)
+                                },
+                                Option::<fn(Color) -> Color>::None,
+                                None,
+                                config,
+                            )
+                        } else {
+                            let lines = message
+                                .text()
+                                .lines()
+                                .count()
+                                .saturating_sub(1);
+
+                            button(
+                                selectable_text(format!(
+                                    "{} ({lines} {})",
+                                    message.title().unwrap_or_default(),
+                                    if lines == 1 { "line" } else { "lines" }
+                                ))
+                                .font_maybe(
+                                    theme::font_style::server(theme, None)
+                                        .map(font::get),
+                                )
+                                .style(
+                                    |theme| {
+                                        theme::selectable_text::server(
+                                            theme, None,
+                                        )
+                                    },
+                                ),
+                            )
+                            .style(theme::button::bare)
+                            .padding(0)
+                            .on_press(scroll_view::Message::Link(link))
+                            .into()
+                        };
+
+                        Some(row_with_timestamp(timestamp, content))
+                    }
                     message::Source::User(user) => {
                         let user_display = UserDisplay::new(
                             user,
@@ -244,6 +323,7 @@ pub fn view<'a>(
                                 prefix,
                                 None,
                                 registry,
+                                nick_history,
                                 previews,
                                 user,
                                 None,
@@ -420,12 +500,18 @@ impl Server {
                     scroll_view::Event::ImagePreview(image) => {
                         Some(Event::ImagePreview(image))
                     }
+                    scroll_view::Event::LoadPreview(url) => {
+                        Some(Event::LoadPreview(url))
+                    }
                     scroll_view::Event::ExpandMessage(server_time, hash) => {
                         Some(Event::ExpandMessage(server_time, hash))
                     }
                     scroll_view::Event::ContractMessage(server_time, hash) => {
                         Some(Event::ContractMessage(server_time, hash))
                     }
+                    scroll_view::Event::ExpandReplay(from, expanded) => {
+                        Some(Event::ExpandReplay(from, expanded))
+                    }
                 });
 
                 (command.map(Message::ScrollView), event)
@@ -476,9 +562,25 @@ impl Server {
                     Some(input_view::Event::OpenServer(server)) => {
                         (command, Some(Event::OpenServer(server)))
                     }
+                    Some(input_view::Event::OpenEmojiPicker) => {
+                        (command, Some(Event::OpenEmojiPicker))
+                    }
                     Some(input_view::Event::Reconnect(server)) => {
                         (command, Some(Event::Reconnect(server)))
                     }
+                    Some(input_view::Event::TestNotification(
+                        server,
+                        notification,
+                    )) => (
+                        command,
+                        Some(Event::TestNotification(server, notification)),
+                    ),
+                    Some(input_view::Event::Ignore(mask)) => {
+                        (command, Some(Event::Ignore(mask)))
+                    }
+                    Some(input_view::Event::Unignore(mask)) => {
+                        (command, Some(Event::Unignore(mask)))
+                    }
                     Some(input_view::Event::FilehostUpload {
                         server,
                         target,
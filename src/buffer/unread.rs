@@ -0,0 +1,128 @@
+use data::{Config, history, message};
+use iced::widget::{
+    Scrollable, button, center, column, container, row, scrollable, text,
+};
+use iced::{Length, alignment};
+
+use crate::widget::Element;
+use crate::{Theme, font, icon, theme};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    GoTo(history::Kind, message::Hash),
+}
+
+pub enum Event {
+    GoTo(history::Kind, message::Hash),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Unread;
+
+impl Unread {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::GoTo(kind, hash) => Some(Event::GoTo(kind, hash)),
+        }
+    }
+}
+
+pub fn view<'a>(
+    history: &'a history::Manager,
+    config: &'a Config,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    let messages = history.unread_messages();
+
+    if messages.is_empty() {
+        return center(container(
+            column![
+                icon::checkmark()
+                    .width(Length::Shrink)
+                    .height(theme::TEXT_SIZE + 3.0)
+                    .style(theme::text::secondary),
+                text("No unread messages")
+                    .style(theme::text::secondary)
+                    .font_maybe(
+                        theme::font_style::secondary(theme).map(font::get)
+                    ),
+            ]
+            .spacing(8)
+            .align_x(iced::Alignment::Center),
+        ))
+        .into();
+    }
+
+    let rows =
+        messages
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (kind, message))| {
+                unread_row(idx, kind, message, config, theme)
+            });
+
+    container(
+        Scrollable::new(column(rows).spacing(1).padding(8))
+            .direction(scrollable::Direction::Vertical(
+                scrollable::Scrollbar::new().width(1).scroller_width(1),
+            ))
+            .style(theme::scrollable::hidden),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+fn unread_row<'a>(
+    idx: usize,
+    kind: &'a history::Kind,
+    message: &'a data::Message,
+    config: &'a Config,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    let timestamp =
+        config
+            .buffer
+            .format_timestamp(&message.server_time)
+            .map(|timestamp| {
+                text(timestamp).style(theme::text::timestamp).font_maybe(
+                    theme::font_style::timestamp(theme).map(font::get),
+                )
+            });
+
+    let source = button(
+        text(kind.to_string())
+            .style(theme::text::buffer_title_bar)
+            .font_maybe(theme.styles().buffer.url.font_style.map(font::get)),
+    )
+    .padding(0)
+    .style(theme::button::bare)
+    .on_press(Message::GoTo(kind.clone(), message.hash));
+
+    let nick = message.target.source().user().map(|user| {
+        text(format!("<{}>", user.nickname()))
+            .style(theme::text::secondary)
+            .font_maybe(
+                theme::font_style::nickname(theme, false, false).map(font::get),
+            )
+    });
+
+    container(
+        row![
+            timestamp,
+            source,
+            nick,
+            text(message.text()).width(Length::Fill),
+        ]
+        .spacing(8)
+        .align_y(alignment::Vertical::Top),
+    )
+    .padding([4, 8])
+    .width(Length::Fill)
+    .style(move |theme| theme::container::table(theme, idx))
+    .into()
+}
@@ -2,6 +2,8 @@ use iced::{Subscription, event, keyboard, mouse, window};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Event {
+    /// Any other key press, click or scroll, tracked for idle detection.
+    Activity,
     Copy,
     Escape,
     LeftClick,
@@ -38,6 +40,11 @@ fn filtered_events(
         )) if cfg!(target_os = "linux") && ignored(status) => {
             Some(Event::UpdatePrimaryClipboard)
         }
+        iced::Event::Keyboard(keyboard::Event::KeyPressed { .. })
+        | iced::Event::Mouse(
+            mouse::Event::ButtonPressed { .. }
+            | mouse::Event::WheelScrolled { .. },
+        ) => Some(Event::Activity),
         _ => None,
     };
 
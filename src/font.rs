@@ -204,3 +204,41 @@ pub fn get(font_style: FontStyle) -> Font {
         FontStyle::ItalicBold => MONO_BOLD_ITALICS.clone(),
     }
 }
+
+/// A font family installed on the system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Family {
+    pub name: String,
+    pub monospaced: bool,
+}
+
+impl std::fmt::Display for Family {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.name.fmt(f)
+    }
+}
+
+/// Font families known to the text renderer, sorted by name.
+pub fn families() -> Vec<Family> {
+    use std::collections::BTreeMap;
+
+    use iced::advanced::graphics::text::font_system;
+
+    let mut font_system = font_system()
+        .write()
+        .expect("font system lock is not poisoned");
+
+    let mut families = BTreeMap::<String, bool>::new();
+
+    for face in font_system.raw().db().faces() {
+        for (name, _) in &face.families {
+            *families.entry(name.clone()).or_default() |= face.monospaced;
+        }
+    }
+
+    families
+        .into_iter()
+        .filter(|(name, _)| name != "halloy-icons")
+        .map(|(name, monospaced)| Family { name, monospaced })
+        .collect()
+}
@@ -298,6 +298,20 @@ pub fn hide<'a>() -> TextColorSvg<'a, Theme> {
     text_color_svg(svg::Handle::from_memory(fontawesome_eye_off))
 }
 
+pub fn expanded<'a>() -> TextColorSvg<'a, Theme> {
+    let entypo_down_open =
+        include_bytes!("../assets/fontello/entypo-down-open.svg").to_vec();
+
+    text_color_svg(svg::Handle::from_memory(entypo_down_open))
+}
+
+pub fn collapsed<'a>() -> TextColorSvg<'a, Theme> {
+    let entypo_right_open =
+        include_bytes!("../assets/fontello/entypo-right-open.svg").to_vec();
+
+    text_color_svg(svg::Handle::from_memory(entypo_right_open))
+}
+
 pub fn spinner<'a>(angle: f32) -> TextColorSvg<'a, Theme> {
     let bytes = include_bytes!("../assets/spinner.svg").to_vec();
 
@@ -0,0 +1,135 @@
+use std::time::{Duration, Instant};
+
+use data::{Config, i18n};
+use iced::widget::{button, column, container, operation, text_input};
+use iced::{Length, Task, alignment};
+
+use crate::theme;
+use crate::widget::{Element, text};
+
+/// Hides every window behind a passphrase prompt once Halloy has gone
+/// without input for `lock.timeout` seconds, see `config::Lock`.
+#[derive(Debug)]
+pub struct Lock {
+    last_activity: Instant,
+    locked: bool,
+    passphrase_id: iced::widget::Id,
+    passphrase: String,
+    failed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    PassphraseChanged(String),
+    Unlock,
+}
+
+impl Default for Lock {
+    fn default() -> Self {
+        Self {
+            last_activity: Instant::now(),
+            locked: false,
+            passphrase_id: iced::widget::Id::unique(),
+            passphrase: String::new(),
+            failed: false,
+        }
+    }
+}
+
+impl Lock {
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Input in any window, restarting the idle period.
+    pub fn record_activity(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    pub fn tick(&mut self, now: Instant, config: &Config) -> Task<Message> {
+        if self.locked
+            || !config.lock.is_enabled()
+            || now.saturating_duration_since(self.last_activity)
+                < Duration::from_secs(config.lock.timeout)
+        {
+            return Task::none();
+        }
+
+        self.lock()
+    }
+
+    pub fn lock(&mut self) -> Task<Message> {
+        self.locked = true;
+        self.passphrase.clear();
+        self.failed = false;
+
+        operation::focus(self.passphrase_id.clone())
+    }
+
+    pub fn update(&mut self, message: Message, config: &Config) {
+        match message {
+            Message::PassphraseChanged(passphrase) => {
+                self.passphrase = passphrase;
+                self.failed = false;
+            }
+            Message::Unlock => {
+                if config.lock.verify(&self.passphrase) {
+                    self.locked = false;
+                    self.record_activity();
+                } else {
+                    self.failed = true;
+                }
+
+                self.passphrase.clear();
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let content = column![
+            text(i18n::tr("lock.title")),
+            text_input(i18n::tr("lock.passphrase"), &self.passphrase)
+                .id(self.passphrase_id.clone())
+                .secure(true)
+                .on_input(Message::PassphraseChanged)
+                .on_submit(Message::Unlock)
+                .padding(8)
+                .width(Length::Fill),
+        ]
+        .extend(self.failed.then(|| {
+            text(i18n::tr("lock.incorrect"))
+                .style(theme::text::error)
+                .into()
+        }))
+        .push(
+            button(
+                container(text(i18n::tr("lock.unlock")))
+                    .align_x(alignment::Horizontal::Center)
+                    .width(Length::Fill),
+            )
+            .padding(5)
+            .width(Length::Fixed(250.0))
+            .style(|theme, status| {
+                theme::button::secondary(theme, status, false)
+            })
+            .on_press_maybe(
+                (!self.passphrase.is_empty()).then_some(Message::Unlock),
+            ),
+        )
+        .spacing(12)
+        .align_x(iced::Alignment::Center);
+
+        container(
+            container(content)
+                .width(Length::Fixed(380.0))
+                .padding(25)
+                .style(theme::container::tooltip),
+        )
+        .align_x(alignment::Horizontal::Center)
+        .align_y(alignment::Vertical::Center)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container::root)
+        .into()
+    }
+}
@@ -48,8 +48,37 @@ fn file_dispatch(
                 env.to_level_filter()
             });
 
-        fern::Dispatch::new()
+        let modules = config.modules.clone();
+
+        let dispatch = fern::Dispatch::new()
             .format(move |out, message, record| {
+                if config.file_format == config::logs::FileFormat::Json {
+                    let timestamp = match config.file_timestamp {
+                        config::logs::Timestamp::Local => chrono::Local::now()
+                            .to_rfc3339_opts(
+                                chrono::SecondsFormat::Millis,
+                                false,
+                            ),
+                        config::logs::Timestamp::Utc => Utc::now()
+                            .to_rfc3339_opts(
+                                chrono::SecondsFormat::Millis,
+                                true,
+                            ),
+                    };
+
+                    out.finish(format_args!(
+                        "{}",
+                        data::log::json(
+                            &timestamp,
+                            record.level(),
+                            record.target(),
+                            &message.to_string(),
+                        )
+                    ));
+
+                    return;
+                }
+
                 let timestamp_format = "%H:%M:%S%.3f";
                 let timestamp = match config.file_timestamp {
                     config::logs::Timestamp::Local => {
@@ -112,7 +141,13 @@ fn file_dispatch(
             .level_for("iced_wgpu", log::LevelFilter::Info)
             .level_for("data", file_level_filter)
             .level_for("ipc", file_level_filter)
-            .level_for("halloy", file_level_filter)
+            .level_for("halloy", file_level_filter);
+
+        modules
+            .into_iter()
+            .fold(dispatch, |dispatch, (module, level)| {
+                dispatch.level_for(module, log::LevelFilter::from(level))
+            })
     })
 }
 
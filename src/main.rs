@@ -9,7 +9,9 @@ mod event;
 mod filehost;
 mod font;
 mod icon;
+mod lock;
 mod logger;
+mod menu;
 mod modal;
 mod notification;
 mod open_url;
@@ -17,6 +19,7 @@ mod platform_specific;
 mod screen;
 mod stream;
 mod system;
+mod taskbar;
 mod unix_signal;
 mod url;
 mod widget;
@@ -37,13 +40,15 @@ use data::history::filter::FilterChain;
 use data::history::manager::{EchoEvent, ReactionToEcho, ReplyToEcho};
 use data::history::reroute::RerouteRules;
 use data::message::{self, Broadcast};
+use data::outbox::{self, Outbox};
 use data::reaction::Reaction;
 use data::redaction::Redaction;
 use data::target::{self, Target};
 use data::user::Nick;
 use data::version::Version;
 use data::{
-    Notification, Server, Url, User, environment, history, server, version,
+    Notification, Server, Url, User, diagnostics, environment, history, plugin,
+    server, shortcut, version,
 };
 use iced::widget::{column, container};
 use iced::{Length, Subscription, Task, padding};
@@ -68,6 +73,11 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    // Demo mode must be enabled before any config or data is loaded.
+    if env::args().any(|arg| arg == "--demo") {
+        data::demo::start()?;
+    }
+
     // Prepare crypto provider before any TLS config is built.
     irc::connection::prepare();
 
@@ -90,8 +100,16 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
     });
 
+    let metrics_port = logs_config.metrics_port;
+
     let log_stream = logger::setup(logs_config).expect("setup logging");
     log::info!("halloy {} has started", environment::formatted_version());
+
+    if let Some(port) = metrics_port
+        && let Err(error) = diagnostics::serve(port)
+    {
+        log::error!("[metrics] failed to serve on port {port}: {error}");
+    }
     log::info!("config dir: {:?}", environment::config_dir());
     log::info!("data dir: {:?}", environment::data_dir());
 
@@ -112,6 +130,14 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     // before we do any iced related stuff w/ it
     font::set(config_load.as_ref().ok());
 
+    // Strings are resolved once; changing `language` requires a restart
+    data::i18n::init(
+        config_load
+            .as_ref()
+            .ok()
+            .and_then(|config| config.language.as_deref()),
+    );
+
     let destination = data::Url::find_in(std::env::args());
     if let Some(loc) = &destination
         && ipc::connect_and_send(loc.to_string())
@@ -152,6 +178,8 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     .run()
     .inspect_err(|err| log::error!("{err}"))?;
 
+    data::demo::cleanup();
+
     Ok(())
 }
 
@@ -173,7 +201,7 @@ fn settings(config_load: &Result<Config, config::Error>) -> iced::Settings {
         power_preference: power_preference_from_config(
             runtime.power_preference,
         ),
-        id: None,
+        id: Some(environment::APPLICATION_ID.to_string()),
         antialiasing: runtime.antialiasing,
         fonts: font::load(),
         vsync: runtime.vsync,
@@ -234,10 +262,24 @@ struct Halloy {
     controllers: stream::Map,
     modal: Option<Modal>,
     main_window: Window,
+    /// Display arrangement the main window state is remembered for.
+    displays: Option<data::window::Displays>,
     focused_window: Option<window::Id>,
     pending_logs: Vec<data::log::Record>,
     notifications: Notifications,
     power: system::State,
+    offline: bool,
+    /// Unsent messages waiting for the user to resend or discard them.
+    unsent: Outbox,
+    saved_outbox: Outbox,
+    /// Certificate fingerprints the user declined to pin this session.
+    declined_certificates: HashSet<String>,
+    /// Native menu bar, kept alive while it is shown (macOS only).
+    menu: Option<menu::Menu>,
+    taskbar: taskbar::Taskbar,
+    lock: lock::Lock,
+    /// Zoom applied on top of the configured scale factor by pinching.
+    zoom: f32,
 }
 
 impl Halloy {
@@ -273,6 +315,8 @@ impl Halloy {
                 let mut servers: server::Map = config.servers.clone().into();
                 servers.set_order(config.sidebar.order_by);
                 let (mut screen, mut commands) = load_dashboard(&config);
+                commands =
+                    commands.chain(screen.focus_on_launch(&servers, &config));
                 screen.init_filters(&servers, &data::client::Map::default());
                 screen
                     .set_reroute_rules(&servers, &data::client::Map::default());
@@ -300,11 +344,20 @@ impl Halloy {
             ),
         };
 
-        let (notifications, stream) = Notifications::new(&config);
+        let (mut notifications, stream) = Notifications::new(&config);
+
+        plugin::load(&config.plugins);
+
+        if let Screen::Dashboard(dashboard) = &screen {
+            notifications.set_sounds_muted(dashboard.audio_muted());
+            notifications.set_muted(dashboard.muted_buffers());
+        }
 
         let commands =
             Task::batch(vec![stream.map(Message::Notification), commands]);
 
+        let unsent = outbox::load_sync();
+
         (
             Halloy {
                 version: Version::new(),
@@ -317,10 +370,19 @@ impl Halloy {
                 config,
                 modal: None,
                 main_window,
+                displays: data::window::Displays::current(),
                 focused_window: None,
                 pending_logs: vec![],
                 notifications,
                 power: system::State::default(),
+                offline: false,
+                saved_outbox: unsent.clone(),
+                unsent,
+                declined_certificates: HashSet::new(),
+                menu: None,
+                taskbar: taskbar::Taskbar::default(),
+                lock: lock::Lock::default(),
+                zoom: 1.0,
             },
             commands,
         )
@@ -351,6 +413,8 @@ pub enum Message {
     AppearanceChange(appearance::Mode),
     Window(window::Id, window::Event),
     WindowSettingsSaved(Result<(), window::Error>),
+    DisplaysChecked(Instant),
+    DisplaysQueried(Option<data::window::Displays>),
     WindowMaximizeChecked(bool),
     Logging(Vec<logger::Record>),
     OnConnect(Server, client::on_connect::Event),
@@ -360,25 +424,47 @@ pub enum Message {
     SystemInformation(iced::system::Information),
     Notification(notification::Event),
     System(system::Event),
+    Menu(shortcut::Command),
+    Lock(lock::Message),
 }
 
 impl Halloy {
     fn save_main_window_settings(&self) -> Task<Message> {
         let main_window = self.main_window;
-
-        // In multi-monitor layouts with a display above or offset from the
-        // primary, `Moved` events can be missed. Query the current position
-        // before saving.
-        iced::window::position(main_window.id).then(move |position| {
-            let mut main_window = main_window;
-            if let Some(position) = position {
-                main_window.position = Some(position);
+        let displays = self.displays.clone();
+
+        Task::future(data::window::Displays::query()).then(move |current| {
+            // Skip saving while the display arrangement changes, so the
+            // position the OS picks for the window isn't remembered for the
+            // arrangement.
+            if current != displays {
+                return Task::none();
             }
 
-            Task::perform(
-                data::Window::from(main_window).save(),
-                Message::WindowSettingsSaved,
-            )
+            // In multi-monitor layouts with a display above or offset from
+            // the primary, `Moved` events can be missed. Query the current
+            // position before saving.
+            iced::window::position(main_window.id).then(move |position| {
+                let mut main_window = main_window;
+                if let Some(position) = position {
+                    main_window.position = Some(position);
+                }
+
+                // Saved along with the position, to check it against displays
+                // in physical pixels when restoring
+                iced::window::scale_factor(main_window.id).then(
+                    move |scale_factor| {
+                        Task::perform(
+                            data::Window {
+                                scale_factor,
+                                ..data::Window::from(main_window)
+                            }
+                            .save(),
+                            Message::WindowSettingsSaved,
+                        )
+                    },
+                )
+            })
         })
     }
 
@@ -394,6 +480,7 @@ impl Halloy {
             position,
             fullscreen,
             maximized,
+            ..
         } = window_load.unwrap_or_default();
 
         let default_config = Config::default();
@@ -481,6 +568,35 @@ impl Halloy {
                         .map(Message::Dashboard);
                 }
             }
+            data::Url::Buffer { server, target, .. } => {
+                if let Screen::Dashboard(dashboard) = &mut self.screen
+                    && let Some(server) = self
+                        .servers
+                        .keys()
+                        .find(|known| format!("{known:b}") == server)
+                        .cloned()
+                {
+                    let target = Target::parse(
+                        &target,
+                        self.clients.get_server_chantypes_or_default(&server),
+                        self.clients.get_server_statusmsg_or_default(&server),
+                        self.clients.get_server_casemapping_or_default(&server),
+                    );
+
+                    return window::gain_focus(self.main_window.id).chain(
+                        dashboard
+                            .open_target(
+                                server,
+                                target,
+                                &mut self.clients,
+                                self.config.actions.sidebar.buffer,
+                                &self.config,
+                                false,
+                            )
+                            .map(Message::Dashboard),
+                    );
+                }
+            }
             data::Url::Unknown(url) => {
                 log::warn!("Received unknown url: {url}");
             }
@@ -593,7 +709,16 @@ impl Halloy {
                 // Retrack after dashboard state changes
                 let track = dashboard.track(Some(&self.clients));
 
+                self.notifications.set_sounds_muted(dashboard.audio_muted());
+                self.notifications
+                    .set_snoozed(dashboard.snoozed_notifications());
+                self.notifications.set_muted(dashboard.muted_buffers());
+
                 let event_task = match event {
+                    Some(dashboard::Event::Lock) => {
+                        self.notifications.set_locked(true);
+                        self.lock.lock().map(Message::Lock)
+                    }
                     Some(dashboard::Event::ToggleFullscreen) => {
                         self.main_window.toggle_fullscreen();
                         self.save_main_window_settings()
@@ -601,6 +726,36 @@ impl Halloy {
                     Some(dashboard::Event::ConfigReloaded(config)) => {
                         self.config_file_reloaded(config)
                     }
+                    Some(dashboard::Event::ThemeSwitched(theme)) => {
+                        let dark = self.current_mode == appearance::Mode::Dark;
+
+                        // Keep the switched theme when the OS appearance
+                        // changes
+                        match &mut self.config.appearance.selected {
+                            data::appearance::Selected::Static(selected) => {
+                                *selected = theme.clone();
+                            }
+                            data::appearance::Selected::Dynamic {
+                                light,
+                                dark: dark_theme,
+                            } => {
+                                if dark {
+                                    *dark_theme = theme.clone();
+                                } else {
+                                    *light = theme.clone();
+                                }
+                            }
+                        }
+
+                        Task::future(async move {
+                            if let Err(error) =
+                                Config::save_theme(theme.name, dark).await
+                            {
+                                log::error!("failed to save theme: {error}");
+                            }
+                        })
+                        .discard()
+                    }
                     Some(dashboard::Event::ReloadThemes) => {
                         Task::future(Config::load()).then(|config| match config
                         {
@@ -709,6 +864,21 @@ impl Halloy {
                         });
                         Task::none()
                     }
+                    Some(dashboard::Event::ConfirmClose {
+                        close,
+                        confirmation,
+                        name,
+                        window,
+                    }) => {
+                        self.modal = Some(Modal::ConfirmClose {
+                            close,
+                            confirmation,
+                            name,
+                            dont_ask_again: false,
+                            window,
+                        });
+                        Task::none()
+                    }
                     Some(dashboard::Event::EchoEvents(server, events)) => {
                         let casemapping = self
                             .clients
@@ -756,6 +926,23 @@ impl Halloy {
 
                         Task::none()
                     }
+                    Some(dashboard::Event::TestNotification(
+                        server,
+                        notification,
+                    )) => {
+                        self.notifications.notify(
+                            &self.config,
+                            &notification,
+                            &server,
+                        );
+
+                        Task::none()
+                    }
+                    Some(dashboard::Event::Zoom(scale)) => {
+                        self.zoom = (self.zoom * scale).clamp(0.5, 2.0);
+
+                        Task::none()
+                    }
                     None => Task::none(),
                 };
 
@@ -763,6 +950,7 @@ impl Halloy {
                     event_task,
                     command.map(Message::Dashboard),
                     track.map(Message::Dashboard),
+                    self.apply_plugin_actions(),
                 ])
             }
             Message::Version(remote) => {
@@ -807,6 +995,20 @@ impl Halloy {
             Message::System(system::Event::Resumed) => {
                 log::info!("system resumed");
                 self.power = system::State::Awake;
+                // Reconnect right away rather than waiting out retry delays
+                self.controllers.network_changed(!self.offline);
+                Task::none()
+            }
+            Message::System(system::Event::NetworkOffline) => {
+                log::info!("network offline");
+                self.offline = true;
+                self.controllers.network_changed(false);
+                Task::none()
+            }
+            Message::System(system::Event::NetworkOnline) => {
+                log::info!("network online");
+                self.offline = false;
+                self.controllers.network_changed(true);
                 Task::none()
             }
             Message::Stream(update) => match update {
@@ -817,10 +1019,17 @@ impl Halloy {
                     sent_time,
                     autoconnect,
                 } => {
+                    let save_nick_history = self
+                        .clients
+                        .save_nick_history(&server, None)
+                        .map_or_else(Task::none, |save| {
+                            Task::future(save).discard()
+                        });
+
                     self.clients.disconnected(server.clone(), autoconnect);
 
                     let Screen::Dashboard(dashboard) = &mut self.screen else {
-                        return Task::none();
+                        return save_nick_history;
                     };
 
                     dashboard.process_server_inputs_completion_and_notice(
@@ -830,7 +1039,7 @@ impl Halloy {
                     );
 
                     if is_initial || self.power.suppresses_connection_events() {
-                        Task::none()
+                        save_nick_history
                     } else {
                         if !self.main_window.focused {
                             self.notifications.notify(
@@ -841,6 +1050,7 @@ impl Halloy {
                         }
 
                         Task::batch(vec![
+                            save_nick_history,
                             dashboard
                                 .broadcast(
                                     &server,
@@ -868,18 +1078,34 @@ impl Halloy {
                         return Task::none();
                     };
 
+                    let casemapping =
+                        self.clients.get_server_casemapping_or_default(&server);
+
                     // Initial is sent when first trying to connect
-                    dashboard
-                        .broadcast(
+                    let mut tasks = vec![dashboard.broadcast(
+                        &server,
+                        casemapping,
+                        &self.config,
+                        sent_time,
+                        false,
+                        Broadcast::Connecting,
+                    )];
+
+                    if self.servers.get(&server).is_some_and(|config| {
+                        config.use_tls
+                            && config.dangerously_accept_invalid_certs
+                    }) {
+                        tasks.push(dashboard.broadcast(
                             &server,
-                            self.clients
-                                .get_server_casemapping_or_default(&server),
+                            casemapping,
                             &self.config,
                             sent_time,
                             false,
-                            Broadcast::Connecting,
-                        )
-                        .map(Message::Dashboard)
+                            Broadcast::CertificatesNotVerified,
+                        ));
+                    }
+
+                    Task::batch(tasks).map(Message::Dashboard)
                 }
                 stream::Update::Connected {
                     server,
@@ -888,6 +1114,9 @@ impl Halloy {
                     sent_time,
                 } => {
                     self.clients.ready(server.clone(), connection);
+                    diagnostics::connected(&server);
+
+                    self.prompt_resend_unsent();
 
                     let Screen::Dashboard(dashboard) = &mut self.screen else {
                         return Task::none();
@@ -962,13 +1191,38 @@ impl Halloy {
                         )
                         .map(Message::Dashboard)
                 }
+                stream::Update::UntrustedCertificate {
+                    server,
+                    fingerprint,
+                } => {
+                    if self.modal.is_none()
+                        && !self.declined_certificates.contains(&fingerprint)
+                    {
+                        self.modal = Some(Modal::PinCertificate {
+                            server,
+                            fingerprint,
+                        });
+                    }
+
+                    Task::none()
+                }
                 stream::Update::MessagesReceived(server, messages) => {
-                    self.handle_messages_received(server, messages)
+                    diagnostics::messages_received(&server, messages.len());
+                    let task = self.handle_messages_received(server, messages);
+
+                    Task::batch(vec![task, self.apply_plugin_actions()])
+                }
+                stream::Update::Remove(server) => {
+                    diagnostics::removed(&server);
+                    self.remove(server)
                 }
-                stream::Update::Remove(server) => self.remove(server),
                 stream::Update::Controller { server, controller } => {
                     self.controllers.insert(server, controller);
 
+                    if self.offline {
+                        self.controllers.network_changed(false);
+                    }
+
                     Task::none()
                 }
                 stream::Update::UpdateConfiguration {
@@ -990,7 +1244,9 @@ impl Halloy {
                             bouncer_network.clone(),
                             self.clients.update_config(
                                 bouncer_network,
-                                updated_config.bouncer_config().into(),
+                                updated_config
+                                    .bouncer_network_config(bouncer_network)
+                                    .into(),
                                 false,
                             ),
                         ));
@@ -1041,10 +1297,22 @@ impl Halloy {
                 stream::Update::AutoconnectDisabled { server } => {
                     self.clients.autoconnect_disabled(&server);
 
+                    Task::none()
+                }
+                stream::Update::Lag { server, lag } => {
+                    self.clients.update_lag(&server, lag);
+
                     Task::none()
                 }
             },
             Message::Event(window, event) => {
+                if self.lock.is_locked() {
+                    return Task::none();
+                }
+
+                self.lock.record_activity();
+                self.notifications.record_activity();
+
                 if let Screen::Dashboard(dashboard) = &mut self.screen {
                     return dashboard
                         .handle_event(
@@ -1066,12 +1334,59 @@ impl Halloy {
                     handle_irc_error(e);
                 }
 
+                self.prompt_resend_unsent();
+                self.notifications.tick(&self.config);
+                let save_outbox = self.save_outbox();
+                let save_nick_histories = Task::batch(
+                    self.clients
+                        .save_nick_histories(Some(now))
+                        .into_iter()
+                        .map(|save| Task::future(save).discard()),
+                );
+
+                plugin::tick(now);
+                let plugin_actions = self.apply_plugin_actions();
+                let lock = self.lock.tick(now, &self.config).map(Message::Lock);
+                self.notifications.set_locked(self.lock.is_locked());
+
                 if let Screen::Dashboard(dashboard) = &mut self.screen {
-                    dashboard
-                        .tick(now, &self.clients, &self.config)
-                        .map(Message::Dashboard)
+                    let taskbar = if cfg!(target_os = "windows") {
+                        self.taskbar.update(
+                            self.main_window.id,
+                            taskbar::State {
+                                progress: dashboard.file_transfer_progress(),
+                                highlight: dashboard.has_unread_highlight(),
+                                recent: dashboard.recent_buffers(),
+                            },
+                        )
+                    } else {
+                        Task::none()
+                    };
+
+                    Task::batch(vec![
+                        dashboard
+                            .tick(
+                                now,
+                                &mut self.clients,
+                                &mut self.controllers,
+                                &self.servers,
+                                &self.config,
+                                &mut self.notifications,
+                            )
+                            .map(Message::Dashboard),
+                        save_outbox,
+                        save_nick_histories,
+                        taskbar,
+                        plugin_actions,
+                        lock,
+                    ])
                 } else {
-                    Task::none()
+                    Task::batch(vec![
+                        save_outbox,
+                        save_nick_histories,
+                        plugin_actions,
+                        lock,
+                    ])
                 }
             }
             Message::AnimationTick(now) => {
@@ -1097,7 +1412,15 @@ impl Halloy {
                                 self.modal,
                                 Some(Modal::ConfirmFileUpload { .. })
                             );
-                            self.modal = None;
+
+                            if let Some(Modal::PinCertificate {
+                                fingerprint,
+                                ..
+                            }) = self.modal.take()
+                            {
+                                self.declined_certificates.insert(fingerprint);
+                            }
+
                             if cancel_upload
                                 && let Screen::Dashboard(_) = &self.screen
                             {
@@ -1109,6 +1432,63 @@ impl Halloy {
                                     ]);
                             }
                         }
+                        modal::Event::PinCertificate => {
+                            if let Some(Modal::PinCertificate {
+                                server,
+                                fingerprint,
+                            }) = self.modal.take()
+                            {
+                                // Saved to the config file, which is then
+                                // reloaded to reconnect with the pin
+                                return Task::batch(vec![
+                                    command.map(Message::Modal),
+                                    Task::future(Config::save_pinned_cert(
+                                        server.name.to_string(),
+                                        fingerprint,
+                                    ))
+                                    .then(|result| match result {
+                                        Ok(()) => Task::future(Config::load())
+                                            .map(Message::ConfigReloaded),
+                                        Err(error) => {
+                                            log::error!(
+                                                "failed to pin certificate: {error}"
+                                            );
+                                            Task::none()
+                                        }
+                                    }),
+                                ]);
+                            }
+                        }
+                        modal::Event::ResendUnsent => {
+                            if let Some(Modal::ResendUnsent {
+                                server,
+                                messages,
+                            }) = self.modal.take()
+                            {
+                                self.clients.resend(&server, messages);
+                            }
+                        }
+                        modal::Event::ConfirmClose => {
+                            if let Some(Modal::ConfirmClose {
+                                close,
+                                confirmation,
+                                dont_ask_again,
+                                ..
+                            }) = self.modal.take()
+                                && let Screen::Dashboard(_) = &self.screen
+                            {
+                                return Task::batch(vec![
+                                    command.map(Message::Modal),
+                                    Task::done(Message::Dashboard(
+                                        dashboard::Message::ConfirmedClose(
+                                            close,
+                                            dont_ask_again
+                                                .then_some(confirmation),
+                                        ),
+                                    )),
+                                ]);
+                            }
+                        }
                         modal::Event::ConfirmFileUpload => {
                             self.modal = None;
                             if let Screen::Dashboard(_) = &self.screen {
@@ -1186,6 +1566,8 @@ impl Halloy {
 
                 match &event {
                     window::Event::Focused => {
+                        self.notifications.record_activity();
+
                         if self.focused_window != Some(id) {
                             tasks.push(iced::window::request_user_attention(
                                 id, None,
@@ -1195,6 +1577,8 @@ impl Halloy {
                         self.focused_window = Some(id);
                     }
                     window::Event::Unfocused => {
+                        self.notifications.record_activity();
+
                         if self.focused_window == Some(id) {
                             self.focused_window = None;
                         }
@@ -1224,6 +1608,11 @@ impl Halloy {
                         }
                         window::Event::Opened { position, size } => {
                             self.main_window.opened(position, size);
+
+                            // Installed once the application has launched so
+                            // it replaces the default menu bar.
+                            self.menu =
+                                menu::Menu::install(&self.config.keyboard);
                         }
                         window::Event::CloseRequested => {
                             let save = self.save_main_window_settings();
@@ -1260,11 +1649,12 @@ impl Halloy {
                             {
                                 dashboard.filehost.file_being_hovered = false;
 
-                                if self.config.filehost.file_drop() {
-                                    return dashboard
-                                        .handle_file_drop(path.clone())
-                                        .map(Message::Dashboard);
-                                }
+                                return dashboard
+                                    .handle_file_drop(
+                                        path.clone(),
+                                        &self.config,
+                                    )
+                                    .map(Message::Dashboard);
                             }
                         }
                     }
@@ -1303,6 +1693,28 @@ impl Halloy {
                 self.main_window.update_maximize(is_maximized);
                 self.save_main_window_settings()
             }
+            Message::DisplaysChecked(_) => Task::perform(
+                data::window::Displays::query(),
+                Message::DisplaysQueried,
+            ),
+            Message::DisplaysQueried(displays) => {
+                if displays == self.displays {
+                    return Task::none();
+                }
+
+                // Docked or undocked; restore the window state remembered for
+                // the new arrangement, if any.
+                self.displays = displays;
+
+                let id = self.main_window.id;
+
+                Task::future(data::Window::load()).then(move |window| {
+                    match window {
+                        Ok(window) => window::restore(id, window),
+                        Err(_) => Task::none(),
+                    }
+                })
+            }
             Message::WindowSettingsSaved(result) => {
                 if let Err(err) = result {
                     log::error!("window settings failed to save: {err:?}");
@@ -1415,6 +1827,27 @@ impl Halloy {
                 }
                 _ => Task::none(),
             },
+            Message::Menu(command) => {
+                if self.lock.is_locked()
+                    && command != shortcut::Command::QuitApplication
+                {
+                    Task::none()
+                } else if matches!(self.screen, Screen::Dashboard(_)) {
+                    self.update(Message::Dashboard(
+                        dashboard::Message::Shortcut(command),
+                    ))
+                } else if command == shortcut::Command::QuitApplication {
+                    iced::exit()
+                } else {
+                    Task::none()
+                }
+            }
+            Message::Lock(message) => {
+                self.lock.update(message, &self.config);
+                self.notifications.set_locked(self.lock.is_locked());
+
+                Task::none()
+            }
             Message::Notification(event) => {
                 if let Screen::Dashboard(dashboard) = &mut self.screen {
                     dashboard
@@ -1435,6 +1868,13 @@ impl Halloy {
         let platform_specific_padding =
             platform_specific::content_padding(&self.config);
 
+        // Nothing is shown (nor interactive) while locked.
+        if self.lock.is_locked() {
+            return container(self.lock.view().map(Message::Lock))
+                .padding(padding::top(platform_specific_padding))
+                .into();
+        }
+
         // Main window.
         if id == self.main_window.id {
             let screen = match &self.screen {
@@ -1455,10 +1895,13 @@ impl Halloy {
             };
 
             let content = container(
-                container(screen)
-                    .width(Length::Fill)
-                    .height(Length::Fill)
-                    .style(theme::container::root),
+                container(widget::mouse_wheel::scroll_speed(
+                    screen,
+                    self.config.mouse.scroll_speed,
+                ))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(theme::container::root),
             )
             .padding(padding::top(platform_specific_padding));
 
@@ -1480,7 +1923,7 @@ impl Halloy {
             }
         // Popped out window.
         } else if let Screen::Dashboard(dashboard) = &self.screen {
-            let content = container(
+            let content = container(widget::mouse_wheel::scroll_speed(
                 dashboard
                     .view_window(
                         id,
@@ -1491,7 +1934,8 @@ impl Halloy {
                         &self.theme,
                     )
                     .map(Message::Dashboard),
-            )
+                self.config.mouse.scroll_speed,
+            ))
             .padding(padding::top(platform_specific_padding));
 
             // Modals might have a id representing which window to be presented on.
@@ -1515,7 +1959,9 @@ impl Halloy {
     }
 
     fn scale_factor(&self, _window: window::Id) -> f32 {
-        f32::from(self.config.scale_factor)
+        f32::from(data::config::ScaleFactor::from(
+            f32::from(self.config.scale_factor) * self.zoom,
+        ))
     }
 
     fn subscription(&self) -> Subscription<Message> {
@@ -1534,6 +1980,9 @@ impl Halloy {
             window::events()
                 .map(|(window, event)| Message::Window(window, event)),
             system::events().map(Message::System),
+            menu::events().map(Message::Menu),
+            iced::time::every(Duration::from_secs(5))
+                .map(Message::DisplaysChecked),
             tick,
             streams,
         ];
@@ -1598,11 +2047,12 @@ impl Halloy {
                             .collect::<Vec<_>>();
 
                         for bouncer_network in bouncer_networks {
-                            if let Some(bouncer_network) =
+                            if let Some(network_config) =
                                 self.servers.get_mut(&bouncer_network)
                             {
-                                *bouncer_network =
-                                    config.bouncer_config().into();
+                                *network_config = config
+                                    .bouncer_network_config(&bouncer_network)
+                                    .into();
                             }
                         }
 
@@ -1626,8 +2076,14 @@ impl Halloy {
                 // Load new notification sounds.
                 self.notifications.update(&updated);
 
+                plugin::load(&updated.plugins);
+
                 self.config = updated;
 
+                if self.menu.is_some() {
+                    self.menu = menu::Menu::install(&self.config.keyboard);
+                }
+
                 for (server, _) in removed_servers {
                     self.controllers.end(
                         &server,
@@ -1714,6 +2170,84 @@ impl Halloy {
         )
     }
 
+    /// Applies the actions queued by plugins.
+    fn apply_plugin_actions(&mut self) -> Task<Message> {
+        let actions = plugin::take_actions();
+
+        let Screen::Dashboard(dashboard) = &mut self.screen else {
+            return Task::none();
+        };
+
+        let mut tasks = vec![];
+
+        for action in actions {
+            let server_name = match &action {
+                plugin::Action::Send { server, .. }
+                | plugin::Action::Print { server, .. }
+                | plugin::Action::Notify { server, .. } => server,
+            };
+
+            let Some(server) = self
+                .servers
+                .keys()
+                .find(|server| server.to_string() == *server_name)
+                .cloned()
+            else {
+                log::warn!("[plugin] unknown server {server_name}");
+                continue;
+            };
+
+            match action {
+                plugin::Action::Send { target, text, .. } => {
+                    tasks.push(
+                        dashboard
+                            .send_plugin_message(
+                                &mut self.clients,
+                                &server,
+                                &target,
+                                text,
+                                &self.config,
+                            )
+                            .map(Message::Dashboard),
+                    );
+                }
+                plugin::Action::Print { target, text, .. } => {
+                    let target = target.map(|target| {
+                        Target::parse(
+                            &target,
+                            self.clients
+                                .get_server_chantypes_or_default(&server),
+                            self.clients
+                                .get_server_statusmsg_or_default(&server),
+                            self.clients
+                                .get_server_casemapping_or_default(&server),
+                        )
+                    });
+
+                    tasks.push(
+                        dashboard
+                            .record_message(
+                                &server,
+                                data::Message::plugin(target, text),
+                                None,
+                                &self.config.buffer,
+                            )
+                            .map(Message::Dashboard),
+                    );
+                }
+                plugin::Action::Notify { title, body, .. } => {
+                    self.notifications.notify(
+                        &self.config,
+                        &Notification::Plugin { title, body },
+                        &server,
+                    );
+                }
+            }
+        }
+
+        Task::batch(tasks)
+    }
+
     fn remove(&mut self, server: Server) -> Task<Message> {
         match &mut self.screen {
             Screen::Dashboard(_) => {
@@ -1723,6 +2257,8 @@ impl Halloy {
                     .cloned()
                     .collect();
 
+                let mut save_nick_histories = vec![];
+
                 for bouncer_network in bouncer_networks {
                     self.controllers.end(
                         &bouncer_network,
@@ -1731,7 +2267,11 @@ impl Halloy {
 
                     self.servers.remove(&bouncer_network);
 
-                    self.clients.remove(&bouncer_network);
+                    save_nick_histories.extend(
+                        self.clients.remove(&bouncer_network).and_then(
+                            |mut client| client.save_nick_history(None),
+                        ),
+                    );
                 }
 
                 self.controllers.end(
@@ -1741,9 +2281,19 @@ impl Halloy {
 
                 self.servers.remove(&server);
 
-                self.clients.remove(&server);
+                save_nick_histories.extend(
+                    self.clients
+                        .remove(&server)
+                        .and_then(|mut client| client.save_nick_history(None)),
+                );
 
-                Task::none()
+                self.unsent.remove(&server);
+
+                Task::batch(
+                    save_nick_histories
+                        .into_iter()
+                        .map(|save| Task::future(save).discard()),
+                )
             }
             Screen::Exit { pending_exit } => {
                 pending_exit.remove(&server);
@@ -1757,6 +2307,52 @@ impl Halloy {
             _ => Task::none(),
         }
     }
+
+    /// Offers to resend unsent messages once their server is connected.
+    fn prompt_resend_unsent(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+
+        let Some(server) = self
+            .unsent
+            .keys()
+            .find(|server| self.clients.status(server).connected())
+            .cloned()
+        else {
+            return;
+        };
+
+        if let Some(messages) = self.unsent.remove(&server) {
+            self.modal = Some(Modal::ResendUnsent { server, messages });
+        }
+    }
+
+    /// Persists messages which haven't been sent yet, so they survive a
+    /// crash or restart.
+    fn save_outbox(&mut self) -> Task<Message> {
+        let mut outbox = self.clients.outbox();
+
+        for (server, messages) in self.unsent.iter().chain(match &self.modal {
+            Some(Modal::ResendUnsent { server, messages }) => {
+                Some((server, messages))
+            }
+            _ => None,
+        }) {
+            outbox
+                .entry(server.clone())
+                .or_default()
+                .extend(messages.iter().cloned());
+        }
+
+        if outbox == self.saved_outbox {
+            return Task::none();
+        }
+
+        self.saved_outbox = outbox.clone();
+
+        Task::future(outbox::save(outbox)).discard()
+    }
 }
 
 fn handle_client_events(
@@ -1778,6 +2374,12 @@ fn handle_client_events(
     let mut reactions = vec![];
 
     for event in events {
+        if event.encoded().is_some_and(|encoded| {
+            dashboard.is_ignored(encoded, casemapping, config)
+        }) {
+            continue;
+        }
+
         match event {
             Event::Single {
                 message: encoded,
@@ -1837,6 +2439,24 @@ fn handle_client_events(
                     main_window,
                 );
             }
+            Event::CommandOutput {
+                title,
+                messages,
+                our_nick,
+                target,
+            } => {
+                handle_command_output(
+                    server,
+                    title,
+                    messages,
+                    our_nick,
+                    target,
+                    dashboard,
+                    &mut commands,
+                    clients,
+                    config,
+                );
+            }
             Event::Broadcast(broadcast) => {
                 handle_broadcast(
                     server,
@@ -1858,6 +2478,9 @@ fn handle_client_events(
                     commands.push(command.map(Message::Dashboard));
                 }
             }
+            Event::FileTransferResume(request) => {
+                dashboard.resume_file_transfer(request, config);
+            }
             Event::UpdateReadMarker(target, read_marker) => {
                 commands.push(
                     dashboard
@@ -1869,6 +2492,11 @@ fn handle_client_events(
                 );
             }
             Event::JoinedChannel(channel, server_time) => {
+                dashboard.unarchive(&data::buffer::Upstream::Channel(
+                    server.clone(),
+                    channel.clone(),
+                ));
+
                 commands.push(
                     dashboard
                         .load_metadata_and_request_newer_chathistory(
@@ -1904,12 +2532,14 @@ fn handle_client_events(
                         .load_metadata_and_request_newer_chathistory(
                             clients,
                             server.clone(),
-                            target,
+                            target.clone(),
                             server_time,
                             true,
                         )
                         .map(Message::Dashboard),
                 );
+
+                dashboard.discover_chathistory_target(server.clone(), target);
             }
             Event::ChatHistoryTargetsReceived(server_time) => {
                 if let Some(command) = dashboard
@@ -2213,6 +2843,50 @@ fn handle_with_target_event(
     );
 }
 
+fn handle_command_output(
+    server: &Server,
+    title: String,
+    messages: Vec<message::Encoded>,
+    our_nick: data::user::Nick,
+    target: Destination,
+    dashboard: &mut screen::Dashboard,
+    commands: &mut Vec<Task<Message>>,
+    clients: &data::client::Map,
+    config: &Config,
+) {
+    let replies = messages
+        .into_iter()
+        .filter_map(|encoded| {
+            create_message(
+                server,
+                encoded,
+                our_nick.clone(),
+                false,
+                config,
+                clients,
+                dashboard.get_reroute_rules(),
+                dashboard.focused_upstream_buffer(),
+            )
+        })
+        .collect();
+
+    let Some(message) = data::Message::output(title, replies) else {
+        return;
+    };
+
+    commands.push(
+        dashboard
+            .block_and_record_message(
+                server,
+                clients.get_server_casemapping_or_default(server),
+                message.with_target(target),
+                None,
+                &config.buffer,
+            )
+            .map(Message::Dashboard),
+    );
+}
+
 fn handle_priv_or_notice(
     server: &Server,
     encoded: message::Encoded,
@@ -2353,6 +3027,11 @@ fn handle_highlight(
     notifications: &mut Notifications,
     main_window: &Window,
 ) {
+    // Relayed into several channels, only the first is kept
+    if dashboard.is_duplicate_highlight(&highlight, &config.highlights) {
+        return;
+    }
+
     let message::Highlight {
         kind: highlight_kind,
         channel: highlight_channel,
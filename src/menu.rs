@@ -0,0 +1,298 @@
+#[cfg(target_os = "macos")]
+mod menu_bar {
+    use data::config::Keyboard;
+    use data::environment;
+    use data::shortcut::{Command, KeyBinds};
+    use futures::channel::mpsc;
+    use futures::{SinkExt, StreamExt};
+    use iced::Subscription;
+    use muda::accelerator::Accelerator;
+    use muda::{
+        AboutMetadata, MenuEvent, MenuItem, PredefinedMenuItem, Submenu,
+    };
+
+    /// Native menu bar of the application.
+    ///
+    /// Must be kept alive for as long as the menu bar is shown.
+    pub struct Menu(muda::Menu);
+
+    impl Menu {
+        /// Builds the menu bar and installs it for the application, using the
+        /// primary key bind of each command as its accelerator.
+        pub fn install(keyboard: &Keyboard) -> Option<Self> {
+            let menu = muda::Menu::new();
+
+            if let Err(error) = build(&menu, keyboard) {
+                log::error!("failed to build menu bar: {error}");
+                return None;
+            }
+
+            menu.init_for_nsapp();
+
+            Some(Self(menu))
+        }
+    }
+
+    fn build(menu: &muda::Menu, keyboard: &Keyboard) -> muda::Result<()> {
+        let item = |text: &str, command: Command, key_binds: &KeyBinds| {
+            let accelerator = key_binds
+                .primary()
+                .and_then(|key_bind| key_bind.as_accelerator())
+                .and_then(|accelerator| {
+                    accelerator.parse::<Accelerator>().ok()
+                });
+
+            MenuItem::with_id(command, text, true, accelerator)
+        };
+
+        let app = Submenu::with_items(
+            "Halloy",
+            true,
+            &[
+                &PredefinedMenuItem::about(
+                    None,
+                    Some(AboutMetadata {
+                        name: Some("Halloy".to_string()),
+                        version: Some(environment::VERSION.to_string()),
+                        ..AboutMetadata::default()
+                    }),
+                ),
+                &PredefinedMenuItem::separator(),
+                &item(
+                    "Settings…",
+                    Command::OpenConfigEditor,
+                    &keyboard.open_config_editor,
+                ),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::services(None),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::hide(None),
+                &PredefinedMenuItem::hide_others(None),
+                &PredefinedMenuItem::show_all(None),
+                &PredefinedMenuItem::separator(),
+                // Quit through Halloy so connections are closed gracefully,
+                // keeping the standard ⌘Q unless it has been rebound.
+                &MenuItem::with_id(
+                    Command::QuitApplication,
+                    "Quit Halloy",
+                    true,
+                    keyboard
+                        .quit_application
+                        .primary()
+                        .and_then(|key_bind| key_bind.as_accelerator())
+                        .or_else(|| Some("cmd+q".to_string()))
+                        .and_then(|accelerator| {
+                            accelerator.parse::<Accelerator>().ok()
+                        }),
+                ),
+            ],
+        )?;
+
+        let file = Submenu::with_items(
+            "File",
+            true,
+            &[
+                &item(
+                    "Command Bar",
+                    Command::CommandBar,
+                    &keyboard.command_bar,
+                ),
+                &PredefinedMenuItem::separator(),
+                &item(
+                    "Open Config File",
+                    Command::OpenConfigFile,
+                    &keyboard.open_config_file,
+                ),
+                &item(
+                    "Reload Configuration",
+                    Command::ReloadConfiguration,
+                    &keyboard.reload_configuration,
+                ),
+                &PredefinedMenuItem::separator(),
+                &item(
+                    "Close Buffer",
+                    Command::CloseBuffer,
+                    &keyboard.close_buffer,
+                ),
+                &item(
+                    "Leave Buffer",
+                    Command::LeaveBuffer,
+                    &keyboard.leave_buffer,
+                ),
+            ],
+        )?;
+
+        let edit = Submenu::with_items(
+            "Edit",
+            true,
+            &[
+                &PredefinedMenuItem::undo(None),
+                &PredefinedMenuItem::redo(None),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::cut(None),
+                &PredefinedMenuItem::copy(None),
+                &PredefinedMenuItem::paste(None),
+                &PredefinedMenuItem::select_all(None),
+                &PredefinedMenuItem::separator(),
+                &item(
+                    "Mark as Read",
+                    Command::MarkAsRead,
+                    &keyboard.mark_as_read,
+                ),
+            ],
+        )?;
+
+        let view = Submenu::with_items(
+            "View",
+            true,
+            &[
+                &item(
+                    "Toggle Sidebar",
+                    Command::ToggleSidebar,
+                    &keyboard.toggle_sidebar,
+                ),
+                &item(
+                    "Toggle Nicklist",
+                    Command::ToggleNicklist,
+                    &keyboard.toggle_nick_list,
+                ),
+                &item(
+                    "Toggle Topic",
+                    Command::ToggleTopic,
+                    &keyboard.toggle_topic,
+                ),
+                &PredefinedMenuItem::separator(),
+                &item(
+                    "File Transfers",
+                    Command::FileTransfers,
+                    &keyboard.file_transfers,
+                ),
+                &item("Highlights", Command::Highlights, &keyboard.highlights),
+                &item("Logs", Command::Logs, &keyboard.logs),
+                &item(
+                    "Theme Editor",
+                    Command::ThemeEditor,
+                    &keyboard.theme_editor,
+                ),
+                &PredefinedMenuItem::separator(),
+                &item(
+                    "Toggle Full Screen",
+                    Command::ToggleFullscreen,
+                    &keyboard.toggle_fullscreen,
+                ),
+            ],
+        )?;
+
+        let window = Submenu::with_items(
+            "Window",
+            true,
+            &[
+                &PredefinedMenuItem::minimize(None),
+                &PredefinedMenuItem::maximize(None),
+                &PredefinedMenuItem::separator(),
+                &item(
+                    "New Horizontal Buffer",
+                    Command::NewHorizontalBuffer,
+                    &keyboard.new_horizontal_buffer,
+                ),
+                &item(
+                    "New Vertical Buffer",
+                    Command::NewVerticalBuffer,
+                    &keyboard.new_vertical_buffer,
+                ),
+                &item(
+                    "Maximize Buffer",
+                    Command::MaximizeBuffer,
+                    &keyboard.maximize_buffer,
+                ),
+                &item(
+                    "Restore Buffer",
+                    Command::RestoreBuffer,
+                    &keyboard.restore_buffer,
+                ),
+                &item(
+                    "Equalize Buffers",
+                    Command::EqualizeBuffers,
+                    &keyboard.equalize_buffers,
+                ),
+                &item(
+                    "Rotate Buffers",
+                    Command::RotateBuffers,
+                    &keyboard.rotate_buffers,
+                ),
+                &PredefinedMenuItem::separator(),
+                &item(
+                    "Next Buffer",
+                    Command::CycleNextBuffer,
+                    &keyboard.cycle_next_buffer,
+                ),
+                &item(
+                    "Previous Buffer",
+                    Command::CyclePreviousBuffer,
+                    &keyboard.cycle_previous_buffer,
+                ),
+                &item(
+                    "Next Unread Buffer",
+                    Command::CycleNextUnreadBuffer,
+                    &keyboard.cycle_next_unread_buffer,
+                ),
+                &item(
+                    "Previous Unread Buffer",
+                    Command::CyclePreviousUnreadBuffer,
+                    &keyboard.cycle_previous_unread_buffer,
+                ),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::bring_all_to_front(None),
+            ],
+        )?;
+
+        menu.append_items(&[&app, &file, &edit, &view, &window])?;
+
+        window.set_as_windows_menu_for_nsapp();
+
+        Ok(())
+    }
+
+    pub fn events() -> Subscription<Command> {
+        Subscription::run(|| {
+            iced::stream::channel(10, async |mut output| {
+                let (sender, mut receiver) = mpsc::unbounded();
+
+                MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+                    if let Ok(command) = event.id.as_ref().parse::<Command>() {
+                        let _ = sender.unbounded_send(command);
+                    }
+                }));
+
+                while let Some(command) = receiver.next().await {
+                    if output.send(command).await.is_err() {
+                        break;
+                    }
+                }
+
+                MenuEvent::set_event_handler(None::<fn(MenuEvent)>);
+            })
+        })
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod menu_bar {
+    use data::config::Keyboard;
+    use data::shortcut::Command;
+    use iced::Subscription;
+
+    pub struct Menu;
+
+    impl Menu {
+        pub fn install(_keyboard: &Keyboard) -> Option<Self> {
+            None
+        }
+    }
+
+    pub fn events() -> Subscription<Command> {
+        Subscription::none()
+    }
+}
+
+pub use menu_bar::{Menu, events};
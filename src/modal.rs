@@ -1,18 +1,23 @@
 use std::path::PathBuf;
 use std::time::Instant;
 
-use data::{Image, Server, config};
+use data::config::buffer::CloseConfirmation;
+use data::{Image, Server, config, outbox};
 use iced::Task;
 
+use crate::screen::dashboard::PendingClose;
 use crate::widget::Element;
 use crate::{Theme, open_url, window};
 
 pub mod about;
+pub mod confirm_close;
 pub mod confirm_file_upload;
 pub mod connect_to_server;
 pub mod image_preview;
+pub mod pin_certificate;
 pub mod prompt_before_open_url;
 pub mod reload_configuration_error;
+pub mod resend_unsent;
 
 #[derive(Debug)]
 pub enum Modal {
@@ -37,6 +42,21 @@ pub enum Modal {
         has_credentials: bool,
         window: window::Id,
     },
+    ResendUnsent {
+        server: Server,
+        messages: Vec<outbox::Message>,
+    },
+    PinCertificate {
+        server: Server,
+        fingerprint: String,
+    },
+    ConfirmClose {
+        close: PendingClose,
+        confirmation: CloseConfirmation,
+        name: String,
+        dont_ask_again: bool,
+        window: window::Id,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +64,10 @@ pub enum Message {
     Cancel,
     OpenURL(String),
     ConfirmFileUpload,
+    ResendUnsent,
+    PinCertificate,
+    ConfirmClose,
+    DontAskAgain(bool),
     // Modal specific messages
     ServerConnect(ServerConnect),
     About(about::Action),
@@ -66,6 +90,9 @@ pub enum Event {
     CloseModal,
     AcceptNewServer,
     ConfirmFileUpload,
+    ResendUnsent,
+    PinCertificate,
+    ConfirmClose,
 }
 
 impl Modal {
@@ -81,6 +108,9 @@ impl Modal {
                 window,
             } => Some(*window),
             Modal::ConfirmFileUpload { window, .. } => Some(*window),
+            Modal::ResendUnsent { .. } => None,
+            Modal::PinCertificate { .. } => None,
+            Modal::ConfirmClose { window, .. } => Some(*window),
         }
     }
 
@@ -93,6 +123,18 @@ impl Modal {
             Message::ConfirmFileUpload => {
                 (Task::none(), Some(Event::ConfirmFileUpload))
             }
+            Message::ResendUnsent => (Task::none(), Some(Event::ResendUnsent)),
+            Message::PinCertificate => {
+                (Task::none(), Some(Event::PinCertificate))
+            }
+            Message::ConfirmClose => (Task::none(), Some(Event::ConfirmClose)),
+            Message::DontAskAgain(toggle) => {
+                if let Modal::ConfirmClose { dont_ask_again, .. } = self {
+                    *dont_ask_again = toggle;
+                }
+
+                (Task::none(), None)
+            }
             Message::About(action) => {
                 if let Modal::About(about) = self {
                     (about.update(action), None)
@@ -185,6 +227,19 @@ impl Modal {
                 timer,
                 window: _,
             } => image_preview::view(image, timer, theme),
+            Modal::ResendUnsent { server, messages } => {
+                resend_unsent::view(server, messages)
+            }
+            Modal::PinCertificate {
+                server,
+                fingerprint,
+            } => pin_certificate::view(server, fingerprint),
+            Modal::ConfirmClose {
+                confirmation,
+                name,
+                dont_ask_again,
+                ..
+            } => confirm_close::view(*confirmation, name, *dont_ask_again),
         }
     }
 }
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use data::config::Runtime;
+use data::i18n;
 use iced::widget::{
     Space, button, center, column, container, image, row, rule, text,
 };
@@ -99,25 +100,25 @@ impl About {
             .as_ref()
             .map(|info| info.graphics_backend.trim())
             .filter(|backend| !backend.is_empty())
-            .unwrap_or("Unknown");
+            .unwrap_or(i18n::tr("modal.about.unknown"));
 
         let gpu_adapter = self
             .system_information
             .as_ref()
             .map(|info| info.graphics_adapter.trim())
             .filter(|adapter| !adapter.is_empty())
-            .unwrap_or("Unknown");
+            .unwrap_or(i18n::tr("modal.about.unknown"));
 
         let vsync = if self.runtime.vsync {
-            "Enabled"
+            i18n::tr("modal.about.enabled")
         } else {
-            "Disabled"
+            i18n::tr("modal.about.disabled")
         };
 
         let antialiasing = if self.runtime.antialiasing {
-            "Enabled"
+            i18n::tr("modal.about.enabled")
         } else {
-            "Disabled"
+            i18n::tr("modal.about.disabled")
         };
 
         let item = |label: &'a str, value: &'a str, field: Field| {
@@ -176,7 +177,7 @@ impl About {
         let copy_all_content = if self.copied == Some(Field::All) {
             container(
                 row![
-                    text("Copied"),
+                    text(i18n::tr("modal.about.copied")),
                     Space::new().width(6),
                     icon::checkmark().style(theme::text::success)
                 ]
@@ -185,7 +186,7 @@ impl About {
             .align_x(alignment::Horizontal::Center)
             .width(Length::Fill)
         } else {
-            container(text("Copy all"))
+            container(text(i18n::tr("modal.about.copy_all")))
                 .align_x(alignment::Horizontal::Center)
                 .width(Length::Fill)
         };
@@ -202,7 +203,7 @@ impl About {
             )));
 
         let close = button(
-            container(text("Close"))
+            container(text(i18n::tr("modal.close")))
                 .align_x(alignment::Horizontal::Center)
                 .width(Length::Fill),
         )
@@ -215,16 +216,40 @@ impl About {
             column![
                 logo,
                 column![
-                    item("Version", &self.version, Field::Version),
-                    item("Commit", &self.commit, Field::Commit),
-                    item("GPU Backend", gpu_backend, Field::GpuBackend),
-                    item("GPU Adapter", gpu_adapter, Field::GpuAdapter),
-                    item("VSync", vsync, Field::Vsync),
-                    item("Antialiasing", antialiasing, Field::Antialiasing),
+                    item(
+                        i18n::tr("modal.about.version"),
+                        &self.version,
+                        Field::Version
+                    ),
+                    item(
+                        i18n::tr("modal.about.commit"),
+                        &self.commit,
+                        Field::Commit
+                    ),
+                    item(
+                        i18n::tr("modal.about.gpu_backend"),
+                        gpu_backend,
+                        Field::GpuBackend
+                    ),
+                    item(
+                        i18n::tr("modal.about.gpu_adapter"),
+                        gpu_adapter,
+                        Field::GpuAdapter
+                    ),
+                    item(i18n::tr("modal.about.vsync"), vsync, Field::Vsync),
+                    item(
+                        i18n::tr("modal.about.antialiasing"),
+                        antialiasing,
+                        Field::Antialiasing
+                    ),
                     container(rule::horizontal(1))
                         .padding([6, 0])
                         .width(Length::Fill),
-                    item("Contact", data::environment::EMAIL, Field::Mail),
+                    item(
+                        i18n::tr("modal.about.contact"),
+                        data::environment::EMAIL,
+                        Field::Mail
+                    ),
                 ],
                 column![copy_all, close]
                     .spacing(8)
@@ -0,0 +1,65 @@
+use data::config::buffer::CloseConfirmation;
+use data::i18n;
+use iced::Length::Fit;
+use iced::widget::{button, checkbox, column, container, row, text};
+use iced::{Length, alignment};
+
+use super::Message;
+use crate::theme;
+use crate::widget::Element;
+
+pub fn view<'a>(
+    confirmation: CloseConfirmation,
+    name: &'a str,
+    dont_ask_again: bool,
+) -> Element<'a, Message> {
+    let (title, confirm) = match confirmation {
+        CloseConfirmation::LeaveChannel => (
+            "modal.confirm_close.leave_channel",
+            "modal.confirm_close.leave",
+        ),
+        CloseConfirmation::QueryWithUnread => (
+            "modal.confirm_close.query_with_unread",
+            "modal.confirm_close.close",
+        ),
+        CloseConfirmation::PaneWithDraft => (
+            "modal.confirm_close.pane_with_draft",
+            "modal.confirm_close.close",
+        ),
+    };
+
+    let action = |label: &'static str, message: Message| {
+        button(
+            container(text(label))
+                .align_x(alignment::Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .padding(5)
+        .width(Length::Fixed(250.0))
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press(message)
+    };
+
+    container(
+        column![
+            text(i18n::tr_args(title, &[("buffer", name)])),
+            row![
+                checkbox(dont_ask_again).on_toggle(Message::DontAskAgain),
+                text(i18n::tr("modal.confirm_close.dont_ask_again")),
+            ]
+            .spacing(4),
+            column![
+                action(i18n::tr(confirm), Message::ConfirmClose),
+                action(i18n::tr("modal.close"), Message::Cancel),
+            ]
+            .spacing(4),
+        ]
+        .spacing(20)
+        .align_x(iced::Alignment::Center),
+    )
+    .width(Fit.max(400))
+    .width(Length::Shrink)
+    .style(theme::container::tooltip)
+    .padding(25)
+    .into()
+}
@@ -1,3 +1,4 @@
+use data::i18n;
 use iced::Length::{self, Fit};
 use iced::widget::{button, column, container, row, span, text};
 
@@ -14,15 +15,18 @@ pub fn view<'a>(
     let error_color = theme.styles().text.error.color;
 
     let mut spans = vec![
-        span("You are about to upload to the filehost "),
+        span(i18n::tr("modal.confirm_file_upload.prompt")),
         span(url).color(url_color).link(()),
-        span(" for the first time."),
+        span(i18n::tr("modal.confirm_file_upload.first_time")),
     ];
 
     if has_credentials {
         spans.push(
-            span("\n\nYour server credentials will be sent with this request!")
-                .color(error_color),
+            span(format!(
+                "\n\n{}",
+                i18n::tr("modal.confirm_file_upload.credentials")
+            ))
+            .color(error_color),
         );
     }
 
@@ -32,13 +36,13 @@ pub fn view<'a>(
                 .on_link(|()| Message::OpenURL(url.to_string()))
                 .align_x(iced::Alignment::Center),
             row![
-                button(text("Upload"))
+                button(text(i18n::tr("modal.confirm_file_upload.upload")))
                     .padding(5)
                     .style(|theme, status| theme::button::secondary(
                         theme, status, false
                     ))
                     .on_press(Message::ConfirmFileUpload),
-                button(text("Close"))
+                button(text(i18n::tr("modal.close")))
                     .padding(5)
                     .style(|theme, status| theme::button::secondary(
                         theme, status, false
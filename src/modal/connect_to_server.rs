@@ -1,4 +1,4 @@
-use data::config;
+use data::{config, i18n};
 use iced::widget::{button, checkbox, column, container, row, text};
 use iced::{Length, alignment};
 
@@ -13,7 +13,7 @@ pub fn view<'a>(
 ) -> Element<'a, Message> {
     container(
         column![
-            text("Connect to server?"),
+            text(i18n::tr("modal.connect_to_server.title")),
             text(raw)
                 .style(theme::text::tertiary)
                 .font_maybe(theme::font_style::tertiary(theme).map(font::get)),
@@ -29,14 +29,14 @@ pub fn view<'a>(
                         )
                     }
                 ),
-                text("Accept invalid certificates")
+                text(i18n::tr("modal.connect_to_server.accept_invalid_certs"))
             ]
             .spacing(4),
         )
         .push(
             column![
                 button(
-                    container(text("Accept"))
+                    container(text(i18n::tr("modal.connect_to_server.accept")))
                         .align_x(alignment::Horizontal::Center)
                         .width(Length::Fill),
                 )
@@ -49,7 +49,7 @@ pub fn view<'a>(
                     super::ServerConnect::AcceptNewServer
                 )),
                 button(
-                    container(text("Close"))
+                    container(text(i18n::tr("modal.close")))
                         .align_x(alignment::Horizontal::Center)
                         .width(Length::Fill),
                 )
@@ -1,6 +1,6 @@
 use std::time::{Duration, Instant};
 
-use data::Image;
+use data::{Image, i18n};
 use iced::widget::{button, center, column, container, row, space};
 use iced::{ContentFit, Length};
 
@@ -49,7 +49,7 @@ pub fn view<'a>(
                                     )
                                 }
                             ),
-                            Some("Save image"),
+                            Some(i18n::tr("modal.image_preview.save_image")),
                             tooltip::Position::Bottom,
                             theme,
                         ),
@@ -66,7 +66,9 @@ pub fn view<'a>(
                                         theme, status, false,
                                     )
                                 }),
-                            Some("Open in browser"),
+                            Some(i18n::tr(
+                                "modal.image_preview.open_in_browser"
+                            )),
                             tooltip::Position::Bottom,
                             theme,
                         ),
@@ -81,7 +83,7 @@ pub fn view<'a>(
                                         theme, status, false,
                                     )
                                 }),
-                            Some("Close"),
+                            Some(i18n::tr("modal.close")),
                             tooltip::Position::Bottom,
                             theme,
                         )
@@ -0,0 +1,61 @@
+use data::{Server, i18n};
+use iced::Length::Fit;
+use iced::widget::{button, column, container, space, text};
+use iced::{Length, alignment};
+
+use super::Message;
+use crate::theme;
+use crate::widget::Element;
+
+pub fn view<'a>(
+    server: &'a Server,
+    fingerprint: &'a str,
+) -> Element<'a, Message> {
+    let server = server.to_string();
+
+    let action = |label: &'static str, message: Message| {
+        button(
+            container(text(label))
+                .align_x(alignment::Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .padding(5)
+        .width(Length::Fixed(250.0))
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press(message)
+    };
+
+    container(
+        column![
+            column![
+                text(i18n::tr_args(
+                    "modal.pin_certificate.title",
+                    &[("server", &server)]
+                )),
+                text(fingerprint)
+                    .style(theme::text::secondary)
+                    .wrapping(text::Wrapping::Glyph)
+                    .width(Length::Shrink),
+                space::vertical().height(8),
+                text(i18n::tr("modal.pin_certificate.confirm")),
+            ]
+            .align_x(iced::Alignment::Center)
+            .spacing(2),
+            column![
+                action(
+                    i18n::tr("modal.pin_certificate.pin"),
+                    Message::PinCertificate
+                ),
+                action(i18n::tr("modal.close"), Message::Cancel),
+            ]
+            .spacing(4),
+        ]
+        .spacing(20)
+        .align_x(iced::Alignment::Center),
+    )
+    .width(Fit.max(400))
+    .width(Length::Shrink)
+    .style(theme::container::tooltip)
+    .padding(25)
+    .into()
+}
@@ -1,3 +1,4 @@
+use data::i18n;
 use iced::Length::Fit;
 use iced::widget::{button, column, container, space, text};
 use iced::{Length, alignment};
@@ -10,22 +11,24 @@ pub fn view<'a>(payload: &'a str, theme: &'a Theme) -> Element<'a, Message> {
     container(
         column![
             column![
-                text("This hyperlink will take you to"),
+                text(i18n::tr("modal.prompt_before_open_url.title")),
                 text(payload)
                     .style(theme::text::url)
                     .font_maybe(theme::font_style::url(theme).map(font::get))
                     .wrapping(text::Wrapping::Glyph)
                     .width(Length::Shrink),
                 space::vertical().height(8),
-                text("Are you sure you want to go there?"),
+                text(i18n::tr("modal.prompt_before_open_url.confirm")),
             ]
             .align_x(iced::Alignment::Center)
             .spacing(2),
             column![
                 button(
-                    container(text("Open URL"))
-                        .align_x(alignment::Horizontal::Center)
-                        .width(Length::Fill),
+                    container(text(i18n::tr(
+                        "modal.prompt_before_open_url.open"
+                    )))
+                    .align_x(alignment::Horizontal::Center)
+                    .width(Length::Fill),
                 )
                 .padding(5)
                 .width(Length::Fixed(250.0))
@@ -34,7 +37,7 @@ pub fn view<'a>(payload: &'a str, theme: &'a Theme) -> Element<'a, Message> {
                 ))
                 .on_press(Message::OpenURL(payload.to_string())),
                 button(
-                    container(text("Close"))
+                    container(text(i18n::tr("modal.close")))
                         .align_x(alignment::Horizontal::Center)
                         .width(Length::Fill),
                 )
@@ -1,4 +1,4 @@
-use data::config;
+use data::{config, i18n};
 use iced::widget::{button, column, container, text};
 use iced::{Length, alignment};
 
@@ -9,12 +9,12 @@ use crate::{Theme, font, theme};
 pub fn view<'a>(error: &config::Error, theme: &Theme) -> Element<'a, Message> {
     container(
         column![
-            text("Error reloading configuration file"),
+            text(i18n::tr("modal.reload_configuration_error.title")),
             text(error.to_string())
                 .style(theme::text::error)
                 .font_maybe(theme::font_style::error(theme).map(font::get)),
             button(
-                container(text("Close"))
+                container(text(i18n::tr("modal.close")))
                     .align_x(alignment::Horizontal::Center)
                     .width(Length::Fill),
             )
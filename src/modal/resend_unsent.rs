@@ -0,0 +1,66 @@
+use data::{Server, i18n, outbox};
+use iced::Length::Fit;
+use iced::widget::{button, column, container, row, scrollable, text};
+use iced::{Length, alignment};
+
+use super::Message;
+use crate::theme;
+use crate::widget::Element;
+
+pub fn view<'a>(
+    server: &'a Server,
+    messages: &'a [outbox::Message],
+) -> Element<'a, Message> {
+    let server = server.to_string();
+
+    let messages = column(messages.iter().map(|message| {
+        row![
+            text(message.target.as_str()).style(theme::text::secondary),
+            text(message.text.as_str()).wrapping(text::Wrapping::WordOrGlyph),
+        ]
+        .spacing(8)
+        .into()
+    }))
+    .spacing(4);
+
+    let action = |label: &'static str, message: Message| {
+        button(
+            container(text(label))
+                .align_x(alignment::Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .padding(5)
+        .width(Length::Fixed(250.0))
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press(message)
+    };
+
+    container(
+        column![
+            text(i18n::tr_args(
+                "modal.resend_unsent.title",
+                &[("server", &server)]
+            )),
+            scrollable(messages).height(Length::Shrink),
+            column![
+                action(
+                    i18n::tr("modal.resend_unsent.resend"),
+                    Message::ResendUnsent
+                ),
+                action(
+                    i18n::tr("modal.resend_unsent.discard"),
+                    Message::Cancel
+                ),
+            ]
+            .spacing(4),
+        ]
+        .spacing(20)
+        .align_x(iced::Alignment::Center),
+    )
+    .width(Fit.max(400))
+    .width(Length::Shrink)
+    .max_height(480)
+    .style(theme::container::tooltip)
+    .padding(25)
+    .into()
+}
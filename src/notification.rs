@@ -1,5 +1,6 @@
-use std::collections::HashMap;
-use std::thread;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::{mem, thread};
 
 use chrono::{DateTime, TimeDelta, Utc};
 use data::audio::Sound;
@@ -8,15 +9,18 @@ use data::config::actions::NotificationAction;
 use data::config::notification;
 use data::target::join_targets;
 use data::user::Nick;
-use data::{Config, Notification, Server, User};
+use data::{Config, Notification, Server, User, file_transfer, i18n};
 use iced::Task;
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 
+use self::backend::Backend;
+pub use self::backend::prepare;
 use self::toast::Toast;
-pub use self::toast::prepare;
 use crate::audio;
+use crate::buffer::file_transfers;
 
+pub mod backend;
 pub mod toast;
 
 #[derive(Debug)]
@@ -28,6 +32,10 @@ pub enum Event {
         action: toast::Action,
         buffer: Option<Buffer>,
     },
+    FileTransferResponse {
+        id: file_transfer::Id,
+        response: file_transfers::Response,
+    },
 }
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -43,6 +51,8 @@ enum NotificationDelayKey {
     Channel(Box<str>),
     Reaction,
     Reply(Box<str>),
+    UnbanReminder(Box<str>),
+    Plugin(Box<str>),
 }
 
 impl From<&Notification> for NotificationDelayKey {
@@ -81,6 +91,14 @@ impl From<&Notification> for NotificationDelayKey {
             Notification::Reply { channel, .. } => {
                 NotificationDelayKey::Reply(channel.as_normalized_str().into())
             }
+            Notification::UnbanReminder { channel, mask } => {
+                NotificationDelayKey::UnbanReminder(
+                    format!("{}{mask}", channel.as_normalized_str()).into(),
+                )
+            }
+            Notification::Plugin { title, .. } => {
+                NotificationDelayKey::Plugin(title.as_str().into())
+            }
         }
     }
 }
@@ -89,7 +107,25 @@ pub struct Notifications {
     recent_notifications: HashMap<NotificationDelayKey, DateTime<Utc>>,
     sounds: HashMap<String, Sound>,
     sender: mpsc::Sender<Event>,
+    backend: Arc<dyn Backend>,
     audio: Option<thread::JoinHandle<()>>,
+    sounds_muted: bool,
+    snoozed: HashMap<Buffer, DateTime<Utc>>,
+    muted: HashSet<buffer::Upstream>,
+    last_activity: DateTime<Utc>,
+    /// Halloy is locked, see `lock`.
+    locked: bool,
+    /// Held back while Halloy is in use, see `notifications.idle`.
+    suppressed: Vec<Suppressed>,
+}
+
+struct Suppressed {
+    title: String,
+    show_toast: bool,
+    request_attention: bool,
+    sound: Option<String>,
+    volume: f32,
+    buffer: Option<Buffer>,
 }
 
 impl Notifications {
@@ -103,7 +139,14 @@ impl Notifications {
                 recent_notifications: HashMap::new(),
                 sounds,
                 sender,
+                backend: backend::from_config(&config.notifications.backend),
                 audio: None,
+                sounds_muted: false,
+                snoozed: HashMap::new(),
+                muted: HashSet::new(),
+                last_activity: Utc::now(),
+                locked: false,
+                suppressed: vec![],
             },
             Task::stream(ReceiverStream::new(receiver)),
         )
@@ -112,6 +155,123 @@ impl Notifications {
     pub fn update(&mut self, config: &Config) {
         // Load sounds from different sources.
         self.sounds = Notifications::load_sounds(config);
+        self.backend = backend::from_config(&config.notifications.backend);
+    }
+
+    pub fn set_sounds_muted(&mut self, sounds_muted: bool) {
+        self.sounds_muted = sounds_muted;
+    }
+
+    pub fn set_snoozed(&mut self, snoozed: &HashMap<Buffer, DateTime<Utc>>) {
+        if self.snoozed != *snoozed {
+            self.snoozed.clone_from(snoozed);
+        }
+    }
+
+    pub fn set_muted(&mut self, muted: &HashSet<buffer::Upstream>) {
+        if self.muted != *muted {
+            self.muted.clone_from(muted);
+        }
+    }
+
+    pub fn set_locked(&mut self, locked: bool) {
+        self.locked = locked;
+    }
+
+    /// Input in (or focus changes of) any window, restarting the idle
+    /// timeout.
+    pub fn record_activity(&mut self) {
+        self.last_activity = Utc::now();
+    }
+
+    fn is_idle(&self, config: &notification::Idle) -> bool {
+        Utc::now() - self.last_activity
+            >= TimeDelta::seconds(
+                i64::try_from(config.timeout).unwrap_or(i64::MAX),
+            )
+    }
+
+    /// Delivers notifications held back while Halloy was in use, batched
+    /// into a single toast, once it has become idle.
+    pub fn tick(&mut self, config: &Config) {
+        if self.suppressed.is_empty()
+            || (config.notifications.idle.enabled
+                && !self.is_idle(&config.notifications.idle))
+        {
+            return;
+        }
+
+        let suppressed = mem::take(&mut self.suppressed);
+
+        let first_buffer = suppressed.first().and_then(|s| s.buffer.clone());
+        let buffer = suppressed
+            .iter()
+            .all(|s| s.buffer == first_buffer)
+            .then_some(first_buffer)
+            .flatten();
+
+        if suppressed.iter().any(|s| s.request_attention) {
+            let sender = self.sender.clone();
+            let buffer = buffer.clone();
+
+            tokio::task::spawn(async move {
+                let _ = sender.send(Event::RequestAttention { buffer }).await;
+            });
+        }
+
+        if suppressed.iter().any(|s| s.show_toast) {
+            let mut titles = Vec::<&str>::new();
+
+            for s in &suppressed {
+                if !titles.contains(&s.title.as_str()) {
+                    titles.push(&s.title);
+                }
+            }
+
+            let title = i18n::tr_args(
+                "notification.while_active",
+                &[("count", &suppressed.len().to_string())],
+            );
+
+            self.show_toast(
+                Toast::new(
+                    &title,
+                    None,
+                    &titles.join("\n"),
+                    buffer.is_some(),
+                    config.actions.notification.default,
+                ),
+                None,
+                buffer,
+            );
+        }
+
+        if let Some((sound, volume)) = suppressed
+            .iter()
+            .find_map(|s| s.sound.clone().map(|sound| (sound, s.volume)))
+        {
+            self.play_sound(&sound, volume);
+        }
+    }
+
+    fn is_muted(&self, buffer: Option<&Buffer>) -> bool {
+        buffer
+            .and_then(Buffer::upstream)
+            .is_some_and(|buffer| self.muted.contains(buffer))
+    }
+
+    /// A notification is snoozed if its buffer, or the server buffer it
+    /// belongs to, has been snoozed and the snooze has not yet expired.
+    fn is_snoozed(&self, buffer: Option<&Buffer>, server: &Server) -> bool {
+        let now = Utc::now();
+        let server_buffer =
+            Buffer::Upstream(buffer::Upstream::Server(server.clone()));
+
+        buffer
+            .into_iter()
+            .chain(Some(&server_buffer))
+            .filter_map(|buffer| self.snoozed.get(buffer))
+            .any(|until| *until > now)
     }
 
     fn load_sounds(config: &Config) -> HashMap<String, Sound> {
@@ -135,7 +295,7 @@ impl Notifications {
             match notification {
                 Notification::Connected => (
                     &config.notifications.connected,
-                    "Connected".to_string(),
+                    i18n::tr("notification.connected").to_string(),
                     None,
                     server.to_string(),
                     None,
@@ -143,7 +303,7 @@ impl Notifications {
                 ),
                 Notification::Disconnected => (
                     &config.notifications.disconnected,
-                    "Disconnected".to_string(),
+                    i18n::tr("notification.disconnected").to_string(),
                     None,
                     server.to_string(),
                     None,
@@ -151,7 +311,7 @@ impl Notifications {
                 ),
                 Notification::Reconnected => (
                     &config.notifications.reconnected,
-                    "Reconnected".to_string(),
+                    i18n::tr("notification.reconnected").to_string(),
                     None,
                     server.to_string(),
                     None,
@@ -160,9 +320,9 @@ impl Notifications {
                 Notification::MonitoredOnline(targets) => (
                     &config.notifications.monitored_online,
                     if targets.len() == 1 {
-                        "Monitored user is online"
+                        i18n::tr("notification.monitored_online")
                     } else {
-                        "Monitored users are online"
+                        i18n::tr("notification.monitored_online_many")
                     }
                     .to_string(),
                     None,
@@ -173,9 +333,9 @@ impl Notifications {
                 Notification::MonitoredOffline(targets) => (
                     &config.notifications.monitored_offline,
                     if targets.len() == 1 {
-                        "Monitored user is offline"
+                        i18n::tr("notification.monitored_offline")
                     } else {
-                        "Monitored users are offline"
+                        i18n::tr("notification.monitored_offline_many")
                     }
                     .to_string(),
                     None,
@@ -187,6 +347,7 @@ impl Notifications {
                     nick,
                     casemapping,
                     filename,
+                    ..
                 } => {
                     if config.notifications.file_transfer_request.should_notify(
                         &User::from(nick.clone()),
@@ -206,13 +367,22 @@ impl Notifications {
                             (
                                 nick.as_str().to_owned(),
                                 Some(format!("{server}")),
-                                format!("Sent you a file: {filename}"),
+                                i18n::tr_args(
+                                    "notification.file_transfer_request",
+                                    &[("filename", filename)],
+                                ),
                             )
                         } else {
                             (
-                                format!("File transfer from {nick}"),
+                                i18n::tr_args(
+                                    "notification.file_transfer_from",
+                                    &[("nick", nick.as_str())],
+                                ),
                                 None,
-                                format!("Sent you a file on {server}"),
+                                i18n::tr_args(
+                                    "notification.file_transfer_request_on",
+                                    &[("server", &server.to_string())],
+                                ),
                             )
                         };
 
@@ -256,8 +426,9 @@ impl Notifications {
                             (
                                 user.nickname().as_str().to_owned(),
                                 None,
-                                format!(
-                                    "Sent you a direct message on {server}"
+                                i18n::tr_args(
+                                    "notification.direct_message",
+                                    &[("server", &server.to_string())],
                                 ),
                             )
                         };
@@ -317,8 +488,13 @@ impl Notifications {
                                 &config.notifications.highlight,
                                 user.nickname().to_string(),
                                 None,
-                                format!(
-                                    "{description} in {channel} ({server})"
+                                i18n::tr_args(
+                                    "notification.highlight_in",
+                                    &[
+                                        ("description", description),
+                                        ("channel", channel.as_str()),
+                                        ("server", &server.to_string()),
+                                    ],
                                 ),
                                 sound.to_owned(),
                                 Some(buffer),
@@ -366,8 +542,12 @@ impl Notifications {
                                 channel_notifications_config,
                                 user.nickname().to_string(),
                                 None,
-                                format!(
-                                    "Sent a message in {channel} ({server})"
+                                i18n::tr_args(
+                                    "notification.channel_message",
+                                    &[
+                                        ("channel", channel.as_str()),
+                                        ("server", &server.to_string()),
+                                    ],
                                 ),
                                 None,
                                 Some(buffer),
@@ -434,16 +614,22 @@ impl Notifications {
                             (
                                 user.nickname().to_string(),
                                 Some(react_sent_in.to_string()),
-                                format!(
-                                    "Reacted {} to your message: {message_text}",
-                                    reaction.inner.text
+                                i18n::tr_args(
+                                    "notification.reacted_with",
+                                    &[
+                                        (
+                                            "reaction",
+                                            reaction.inner.text.as_str(),
+                                        ),
+                                        ("message", message_text.as_str()),
+                                    ],
                                 ),
                             )
                         } else {
                             (
                                 user.nickname().to_string(),
                                 Some(react_sent_in.to_string()),
-                                "Reacted to your message".to_string(),
+                                i18n::tr("notification.reacted").to_string(),
                             )
                         };
 
@@ -480,7 +666,10 @@ impl Notifications {
                         if config.notifications.highlight.show_content {
                             (
                                 &config.notifications.highlight,
-                                format!("{} replied to you", user.nickname()),
+                                i18n::tr_args(
+                                    "notification.replied",
+                                    &[("nick", user.nickname().as_str())],
+                                ),
                                 Some(if cfg!(target_os = "macos") {
                                     format!("{channel} ({server})")
                                 } else {
@@ -495,8 +684,12 @@ impl Notifications {
                                 &config.notifications.highlight,
                                 user.nickname().to_string(),
                                 None,
-                                format!(
-                                    "replied to you in {channel} ({server})"
+                                i18n::tr_args(
+                                    "notification.replied_in",
+                                    &[
+                                        ("channel", channel.as_str()),
+                                        ("server", &server.to_string()),
+                                    ],
                                 ),
                                 None,
                                 Some(buffer),
@@ -506,8 +699,57 @@ impl Notifications {
                         return;
                     }
                 }
+                Notification::UnbanReminder { channel, mask } => (
+                    &config.notifications.unban_reminder,
+                    i18n::tr("notification.unban_reminder").to_string(),
+                    None,
+                    i18n::tr_args(
+                        "notification.unban_reminder_body",
+                        &[
+                            ("mask", mask),
+                            ("channel", channel.as_str()),
+                            ("server", &server.to_string()),
+                        ],
+                    ),
+                    None,
+                    Some(Buffer::Upstream(buffer::Upstream::Channel(
+                        server.clone(),
+                        channel.clone(),
+                    ))),
+                ),
+                Notification::Plugin { title, body } => (
+                    &config.notifications.plugin,
+                    title.clone(),
+                    Some(server.to_string()),
+                    body.clone(),
+                    None,
+                    Some(Buffer::Upstream(buffer::Upstream::Server(
+                        server.clone(),
+                    ))),
+                ),
             };
 
+        if self.is_snoozed(buffer.as_ref(), server)
+            || self.is_muted(buffer.as_ref())
+        {
+            return;
+        }
+
+        if config.notifications.idle.enabled
+            && !self.is_idle(&config.notifications.idle)
+        {
+            self.suppressed.push(Suppressed {
+                title,
+                show_toast: notification_config.show_toast,
+                request_attention: notification_config.request_attention,
+                sound: sound_name.or(notification_config.sound.clone()),
+                volume: config.notifications.volume(notification_config),
+                buffer,
+            });
+
+            return;
+        }
+
         if notification_config.request_attention {
             let sender = self.sender.clone();
             let buffer = buffer.clone();
@@ -517,8 +759,16 @@ impl Notifications {
             });
         }
 
+        // Don't reveal any content in toasts while locked
+        let (subtitle, body) = if self.locked {
+            (None, String::new())
+        } else {
+            (subtitle, body)
+        };
+
         self.execute(
             notification_config,
+            config.notifications.volume(notification_config),
             config.actions.notification.default,
             notification,
             &title,
@@ -532,6 +782,7 @@ impl Notifications {
     fn execute(
         &mut self,
         config: &notification::Notification,
+        volume: f32,
         default_notification_action: NotificationAction,
         notification: &Notification,
         title: &str,
@@ -557,7 +808,7 @@ impl Notifications {
         self.recent_notifications.insert(delay_key, now);
 
         if config.show_toast {
-            let toast = Toast::new(
+            let mut toast = Toast::new(
                 title,
                 subtitle,
                 body,
@@ -565,29 +816,75 @@ impl Notifications {
                 default_notification_action,
             );
 
-            let sender = self.sender.clone();
+            let awaiting_approval = match notification {
+                Notification::FileTransferRequest {
+                    awaiting_approval, ..
+                } => *awaiting_approval,
+                _ => None,
+            };
 
-            tokio::task::spawn(async move {
-                if let Some(action) = toast
-                    .show_and_wait_for_response(default_notification_action)
-                    .await
-                {
-                    let _ = sender
-                        .send(Event::NotificationResponse { action, buffer })
-                        .await;
-                }
-            });
+            if awaiting_approval.is_some() {
+                toast = toast.with_file_transfer_actions();
+            }
+
+            // Nothing to reply to while locked, as the content is hidden
+            if !self.locked
+                && matches!(
+                    buffer,
+                    Some(Buffer::Upstream(
+                        buffer::Upstream::Channel(..)
+                            | buffer::Upstream::Query(..)
+                    ))
+                )
+            {
+                toast = toast.with_reply();
+            }
+
+            self.show_toast(toast, awaiting_approval, buffer);
         }
 
-        if let Some(sound) = sound_name
-            .or(config.sound.as_deref())
-            .and_then(|sound_name| self.sounds.get(sound_name))
+        if let Some(sound_name) = sound_name.or(config.sound.as_deref()) {
+            self.play_sound(sound_name, volume);
+        }
+    }
+
+    fn show_toast(
+        &self,
+        toast: Toast,
+        awaiting_approval: Option<file_transfer::Id>,
+        buffer: Option<Buffer>,
+    ) {
+        let sender = self.sender.clone();
+        let response = self.backend.show(toast);
+
+        tokio::task::spawn(async move {
+            let event = match response.await {
+                Some(toast::Action::FileTransfer(response)) => {
+                    awaiting_approval
+                        .map(|id| Event::FileTransferResponse { id, response })
+                }
+                Some(action) => {
+                    Some(Event::NotificationResponse { action, buffer })
+                }
+                None => None,
+            };
+
+            if let Some(event) = event {
+                let _ = sender.send(event).await;
+            }
+        });
+    }
+
+    fn play_sound(&mut self, sound_name: &str, volume: f32) {
+        if !self.sounds_muted
+            && volume > 0.0
+            && let Some(sound) = self.sounds.get(sound_name)
             && self
                 .audio
                 .as_ref()
                 .is_none_or(thread::JoinHandle::is_finished)
         {
-            self.audio = Some(audio::play(sound.clone()));
+            self.audio = Some(audio::play(sound.clone(), volume));
         }
     }
 }
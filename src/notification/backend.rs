@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use data::config::notification;
+use futures::future::BoxFuture;
+
+use super::toast::{Action, Toast};
+
+mod command;
+mod native;
+mod terminal_notifier;
+
+pub use self::native::prepare;
+
+/// Shows toasts, and waits for the user to respond to them.
+pub trait Backend: Send + Sync {
+    /// Resolves once the toast has been responded to, or right away when
+    /// responses can't be received.
+    fn show(&self, toast: Toast) -> BoxFuture<'static, Option<Action>>;
+}
+
+pub fn from_config(config: &notification::Backend) -> Arc<dyn Backend> {
+    match config {
+        notification::Backend::Native => Arc::new(native::Native),
+        notification::Backend::TerminalNotifier => {
+            Arc::new(terminal_notifier::TerminalNotifier)
+        }
+        notification::Backend::Command(command) => {
+            Arc::new(command::Command(command.clone()))
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::process::Stdio;
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+
+use super::Backend;
+use crate::notification::toast::{Action, Toast};
+
+// https://learn.microsoft.com/en-us/windows/win32/procthread/process-creation-flags
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Notifications through a user provided shell command, which is given the
+/// toast in environment variables and may print a response.
+pub struct Command(pub String);
+
+impl Backend for Command {
+    fn show(&self, toast: Toast) -> BoxFuture<'static, Option<Action>> {
+        show_and_wait_for_response(self.0.clone(), toast).boxed()
+    }
+}
+
+async fn show_and_wait_for_response(
+    command: String,
+    toast: Toast,
+) -> Option<Action> {
+    let mut process = if cfg!(target_os = "windows") {
+        let mut process = tokio::process::Command::new("cmd");
+        #[cfg(target_os = "windows")]
+        process.creation_flags(CREATE_NO_WINDOW);
+        process.arg("/C").arg(command);
+        process
+    } else {
+        let mut process = tokio::process::Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    };
+
+    let actions = toast
+        .actions
+        .iter()
+        .map(|(id, _)| *id)
+        .chain(toast.reply.then_some("reply"))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let output = match process
+        .env("HALLOY_NOTIFICATION_TITLE", &toast.title)
+        .env(
+            "HALLOY_NOTIFICATION_SUBTITLE",
+            toast.subtitle.as_deref().unwrap_or_default(),
+        )
+        .env("HALLOY_NOTIFICATION_BODY", &toast.body)
+        .env("HALLOY_NOTIFICATION_ACTIONS", actions)
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output()
+        .await
+    {
+        Ok(output) => output,
+        Err(error) => {
+            log::error!("failed to run notification command: {error}");
+            return None;
+        }
+    };
+
+    if !output.status.success() {
+        log::warn!(
+            "notification command exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let response = String::from_utf8_lossy(&output.stdout);
+
+    parse_response(&toast, response.lines().next().unwrap_or_default())
+}
+
+/// The first line printed by the command names the action to take, or
+/// `reply <text>` to reply to the buffer.
+fn parse_response(toast: &Toast, response: &str) -> Option<Action> {
+    let response = response.trim();
+
+    if response.is_empty() {
+        return None;
+    }
+
+    match response.split_once(' ') {
+        Some(("reply", text)) if toast.reply && !text.trim().is_empty() => {
+            Some(Action::Reply(text.trim().to_string()))
+        }
+        _ => Some(toast.action(response)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data::config::actions::NotificationAction;
+
+    use super::parse_response;
+    use crate::buffer::file_transfers::Response;
+    use crate::notification::toast::{Action, Toast};
+
+    fn toast() -> Toast {
+        Toast::new(
+            "halloy",
+            None,
+            "hello",
+            true,
+            NotificationAction::OpenBuffer,
+        )
+    }
+
+    #[test]
+    fn parses_actions() {
+        let toast = toast().with_file_transfer_actions();
+
+        assert_eq!(parse_response(&toast, " "), None);
+        assert_eq!(parse_response(&toast, "default"), Some(Action::OpenBuffer));
+        assert_eq!(
+            parse_response(&toast, "decline_file_transfer"),
+            Some(Action::FileTransfer(Response::Decline))
+        );
+        assert_eq!(parse_response(&toast, "unknown"), Some(Action::Dismiss));
+    }
+
+    #[test]
+    fn parses_replies_when_allowed() {
+        assert_eq!(
+            parse_response(&toast().with_reply(), "reply  on my way "),
+            Some(Action::Reply("on my way".to_string()))
+        );
+        assert_eq!(
+            parse_response(&toast().with_reply(), "reply  "),
+            Some(Action::Dismiss)
+        );
+        assert_eq!(
+            parse_response(&toast(), "reply on my way"),
+            Some(Action::Dismiss)
+        );
+    }
+}
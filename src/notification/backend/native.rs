@@ -0,0 +1,144 @@
+use futures::FutureExt;
+use futures::future::BoxFuture;
+#[cfg(target_os = "linux")]
+use image::EncodableLayout;
+use notify_rust::{Notification, NotificationResponse};
+
+use super::Backend;
+use crate::notification::toast::{Action, Toast};
+
+#[cfg(target_os = "macos")]
+pub fn prepare() {
+    match notify_rust::set_application(data::environment::APPLICATION_ID) {
+        Ok(()) => {}
+        Err(error) => {
+            log::error!("{error}");
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn prepare() {}
+
+/// Notifications through the platform's notification service, over D-Bus on
+/// Linux and the BSDs.
+pub struct Native;
+
+impl Backend for Native {
+    fn show(&self, toast: Toast) -> BoxFuture<'static, Option<Action>> {
+        show_and_wait_for_response(toast).boxed()
+    }
+}
+
+fn notification(toast: &Toast) -> Notification {
+    let mut notification = notify_rust::Notification::new();
+
+    notification.body(&toast.body);
+
+    #[cfg(target_os = "macos")]
+    {
+        notification.summary(&toast.title);
+        if let Some(subtitle) = &toast.subtitle {
+            notification.subtitle(subtitle);
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        if let Some(subtitle) = &toast.subtitle {
+            notification.summary(&format!("{} ({subtitle})", toast.title));
+        } else {
+            notification.summary(&toast.title);
+        }
+        notification.appname("Halloy");
+        notification.icon(data::environment::APPLICATION_ID);
+    }
+    #[cfg(target_os = "linux")]
+    {
+        // For GNOME 46+ setting the icon is not sufficient to show the icon
+        // in the body area of the notification; setting image_data or
+        // image_path is needed.
+        if let Some(logo) = image::load_from_memory_with_format(
+            include_bytes!("../../../assets/logo.png"),
+            image::ImageFormat::Png,
+        )
+        .ok()
+        .and_then(|image| {
+            image.as_rgba8().and_then(|image| {
+                notify_rust::Image::from_rgba(
+                    image.width().try_into().unwrap_or_default(),
+                    image.height().try_into().unwrap_or_default(),
+                    image.as_bytes().to_vec(),
+                )
+                .ok()
+            })
+        }) {
+            notification.image_data(logo);
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        notification.app_id(data::environment::APPLICATION_ID);
+    }
+
+    for (id, label) in &toast.actions {
+        notification.action(id, label);
+    }
+
+    notification.finalize()
+}
+
+#[cfg(target_os = "linux")]
+async fn show_and_wait_for_response(toast: Toast) -> Option<Action> {
+    let notification = notification(&toast);
+
+    // When image_data is set, Notification::show/Notification::show_async
+    // will attempt to start a tokio runtime and panic.  This is a
+    // workaround for that behavior.
+    let mut action = None;
+
+    if let Ok(handle) = tokio::task::spawn_blocking(move || {
+        futures::executor::block_on(async { notification.show_async().await })
+    })
+    .await
+    .ok()?
+    {
+        handle
+            .wait_for_action_async(|response: &NotificationResponse| {
+                action = Some(handle_response(&toast, response));
+            })
+            .await;
+    }
+
+    action
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn show_and_wait_for_response(toast: Toast) -> Option<Action> {
+    let mut action = None;
+
+    // Notification::show_async and
+    // NotificationHandle::wait_for_action_async are not available on
+    // macOS/Windows.
+    notification(&toast)
+        .show()
+        .ok()?
+        .wait_for_response(|response: &NotificationResponse| {
+            action = Some(handle_response(&toast, response));
+        })
+        .ok()?;
+
+    action
+}
+
+fn handle_response(toast: &Toast, response: &NotificationResponse) -> Action {
+    match response {
+        NotificationResponse::Default => toast.action("default"),
+        NotificationResponse::Action(id) => toast.action(id),
+        NotificationResponse::Reply(text) if toast.reply => {
+            Action::Reply(text.clone())
+        }
+        NotificationResponse::Reply(_) | NotificationResponse::Closed(_) => {
+            Action::Dismiss
+        }
+    }
+}
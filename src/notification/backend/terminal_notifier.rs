@@ -0,0 +1,149 @@
+use std::process::Stdio;
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use tokio::process::Command;
+
+use super::Backend;
+use crate::notification::toast::{Action, Toast};
+
+/// Notifications through [terminal-notifier](https://github.com/julienXX/terminal-notifier)
+/// on macOS, which supports replying from the notification.
+pub struct TerminalNotifier;
+
+impl Backend for TerminalNotifier {
+    fn show(&self, toast: Toast) -> BoxFuture<'static, Option<Action>> {
+        show_and_wait_for_response(toast).boxed()
+    }
+}
+
+async fn show_and_wait_for_response(toast: Toast) -> Option<Action> {
+    let mut process = Command::new("terminal-notifier");
+
+    process
+        .arg("-title")
+        .arg(&toast.title)
+        .arg("-message")
+        .arg(&toast.body)
+        .arg("-sender")
+        .arg(data::environment::APPLICATION_ID);
+
+    if let Some(subtitle) = &toast.subtitle {
+        process.arg("-subtitle").arg(subtitle);
+    }
+
+    // Replies and buttons can't be combined, and terminal-notifier only
+    // waits for a response when one of them is requested
+    let buttons = buttons(&toast);
+
+    if toast.reply {
+        process.arg("-reply").arg("Reply");
+    } else if !buttons.is_empty() {
+        process.arg("-actions").arg(
+            buttons
+                .iter()
+                .map(|(_, label)| *label)
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+
+    let output = match process
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+    {
+        Ok(output) => output,
+        Err(error) => {
+            log::error!("failed to run terminal-notifier: {error}");
+            return None;
+        }
+    };
+
+    let response = String::from_utf8_lossy(&output.stdout);
+
+    parse_response(&toast, &response)
+}
+
+fn buttons(toast: &Toast) -> Vec<&(&'static str, &'static str)> {
+    toast
+        .actions
+        .iter()
+        .filter(|(id, _)| *id != "default")
+        .collect()
+}
+
+/// terminal-notifier prints the label of the clicked button, the reply, or
+/// an event such as `@CONTENTCLICKED`.
+fn parse_response(toast: &Toast, response: &str) -> Option<Action> {
+    match response.trim() {
+        "" => None,
+        "@CONTENTCLICKED" => Some(toast.action("default")),
+        "@CLOSED" | "@TIMEOUT" => Some(Action::Dismiss),
+        reply if toast.reply => Some(Action::Reply(reply.to_string())),
+        label => Some(
+            buttons(toast)
+                .iter()
+                .find(|(_, button)| *button == label)
+                .map_or(Action::Dismiss, |(id, _)| toast.action(id)),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data::config::actions::NotificationAction;
+
+    use super::parse_response;
+    use crate::buffer::file_transfers::Response;
+    use crate::notification::toast::{Action, Toast};
+
+    fn toast() -> Toast {
+        Toast::new(
+            "halloy",
+            None,
+            "hello",
+            true,
+            NotificationAction::ActivateApplication,
+        )
+    }
+
+    #[test]
+    fn parses_events() {
+        let toast = toast();
+
+        assert_eq!(parse_response(&toast, ""), None);
+        assert_eq!(
+            parse_response(&toast, "@CONTENTCLICKED\n"),
+            Some(Action::ActivateApplication)
+        );
+        assert_eq!(parse_response(&toast, "@TIMEOUT"), Some(Action::Dismiss));
+    }
+
+    #[test]
+    fn parses_buttons_by_label() {
+        let toast = toast().with_file_transfer_actions();
+
+        assert_eq!(
+            parse_response(&toast, "Always Accept"),
+            Some(Action::FileTransfer(Response::AlwaysAccept))
+        );
+        assert_eq!(
+            parse_response(&toast, "Open Buffer"),
+            Some(Action::OpenBuffer)
+        );
+        assert_eq!(parse_response(&toast, "Unknown"), Some(Action::Dismiss));
+    }
+
+    #[test]
+    fn parses_replies() {
+        let toast = toast().with_reply();
+
+        assert_eq!(
+            parse_response(&toast, "see you there\n"),
+            Some(Action::Reply("see you there".to_string()))
+        );
+    }
+}
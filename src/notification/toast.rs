@@ -1,23 +1,21 @@
 use data::config::actions::NotificationAction;
-#[cfg(target_os = "linux")]
-use image::EncodableLayout;
-use notify_rust::{Notification, NotificationResponse};
 
-#[cfg(target_os = "macos")]
-pub fn prepare() {
-    match notify_rust::set_application(data::environment::APPLICATION_ID) {
-        Ok(()) => {}
-        Err(error) => {
-            log::error!("{error}");
-        }
-    }
+use crate::buffer::file_transfers::Response;
+
+/// A notification to show through a [`Backend`](super::backend::Backend).
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub body: String,
+    /// Identifiers and labels of the buttons to show, where `"default"` is
+    /// the action taken when the notification itself is clicked.
+    pub actions: Vec<(&'static str, &'static str)>,
+    /// Whether a reply can be typed into the notification.
+    pub reply: bool,
+    default_action: NotificationAction,
 }
 
-#[cfg(not(target_os = "macos"))]
-pub fn prepare() {}
-
-pub struct Toast(Notification);
-
 impl Toast {
     pub fn new(
         title: &str,
@@ -26,154 +24,77 @@ impl Toast {
         has_buffer_context: bool,
         notification_action: NotificationAction,
     ) -> Self {
-        let mut notification = notify_rust::Notification::new();
-
-        notification.body(body);
-
-        #[cfg(target_os = "macos")]
-        {
-            notification.summary(title);
-            if let Some(subtitle) = subtitle {
-                notification.subtitle(subtitle);
-            }
-        }
-        #[cfg(not(target_os = "macos"))]
-        {
-            if let Some(subtitle) = subtitle {
-                notification.summary(&format!("{title} ({subtitle})"));
-            } else {
-                notification.summary(title);
-            }
-            notification.appname("Halloy");
-            notification.icon(data::environment::APPLICATION_ID);
-        }
-        #[cfg(target_os = "linux")]
-        {
-            // For GNOME 46+ setting the icon is not sufficient to show the icon
-            // in the body area of the notification; setting image_data or
-            // image_path is needed.
-            if let Some(logo) = image::load_from_memory_with_format(
-                include_bytes!("../../assets/logo.png"),
-                image::ImageFormat::Png,
-            )
-            .ok()
-            .and_then(|image| {
-                image.as_rgba8().and_then(|image| {
-                    notify_rust::Image::from_rgba(
-                        image.width().try_into().unwrap_or_default(),
-                        image.height().try_into().unwrap_or_default(),
-                        image.as_bytes().to_vec(),
-                    )
-                    .ok()
-                })
-            }) {
-                notification.image_data(logo);
-            }
-        }
-        #[cfg(target_os = "windows")]
-        {
-            notification.app_id(data::environment::APPLICATION_ID);
-        }
-
-        match notification_action {
+        let actions = match notification_action {
             NotificationAction::ActivateApplication => {
-                notification.action("default", "Open Halloy");
+                let mut actions = vec![("default", "Open Halloy")];
                 if has_buffer_context {
-                    notification.action("open_buffer", "Open Buffer");
+                    actions.push(("open_buffer", "Open Buffer"));
                 }
+                actions
             }
             NotificationAction::OpenBuffer => {
                 if has_buffer_context {
-                    notification.action("default", "Open Buffer");
+                    vec![("default", "Open Buffer")]
+                } else {
+                    vec![]
                 }
             }
+        };
+
+        Self {
+            title: title.to_string(),
+            subtitle: subtitle.map(ToString::to_string),
+            body: body.to_string(),
+            actions,
+            reply: false,
+            default_action: notification_action,
         }
-
-        Self(notification.finalize())
     }
 
-    #[cfg(target_os = "linux")]
-    pub async fn show_and_wait_for_response(
-        self,
-        default_action: NotificationAction,
-    ) -> Option<Action> {
-        // When image_data is set, Notification::show/Notification::show_async
-        // will attempt to start a tokio runtime and panic.  This is a
-        // workaround for that behavior.
-        let mut action = None;
+    /// Adds actions to accept or decline a file transfer request.
+    pub fn with_file_transfer_actions(mut self) -> Self {
+        self.actions.extend([
+            ("accept_file_transfer", "Accept"),
+            ("decline_file_transfer", "Decline"),
+            ("always_accept_file_transfer", "Always Accept"),
+        ]);
 
-        if let Ok(handle) = tokio::task::spawn_blocking(move || {
-            futures::executor::block_on(async { self.0.show_async().await })
-        })
-        .await
-        .ok()?
-        {
-            handle
-                .wait_for_action_async(|response: &NotificationResponse| {
-                    Toast::handle_response(
-                        response,
-                        default_action,
-                        &mut action,
-                    );
-                })
-                .await;
-        }
-
-        action
+        self
     }
 
-    #[cfg(not(target_os = "linux"))]
-    pub async fn show_and_wait_for_response(
-        self,
-        default_action: NotificationAction,
-    ) -> Option<Action> {
-        let mut action = None;
-
-        // Notification::show_async and
-        // NotificationHandle::wait_for_action_async are not available on
-        // macOS/Windows.
-        self.0
-            .show()
-            .ok()?
-            .wait_for_response(|response: &NotificationResponse| {
-                Toast::handle_response(response, default_action, &mut action);
-            })
-            .ok()?;
+    /// Allows replying to the buffer the notification is from.
+    pub fn with_reply(mut self) -> Self {
+        self.reply = true;
 
-        action
+        self
     }
 
-    fn handle_response(
-        response: &NotificationResponse,
-        default_action: NotificationAction,
-        action: &mut Option<Action>,
-    ) {
-        match response {
-            NotificationResponse::Default => match default_action {
+    /// The action for a button identifier, or for a click on the
+    /// notification itself with `"default"`.
+    pub fn action(&self, id: &str) -> Action {
+        match id {
+            "default" => match self.default_action {
                 NotificationAction::ActivateApplication => {
-                    *action = Some(Action::ActivateApplication);
-                }
-                NotificationAction::OpenBuffer => {
-                    *action = Some(Action::OpenBuffer);
+                    Action::ActivateApplication
                 }
+                NotificationAction::OpenBuffer => Action::OpenBuffer,
             },
-            NotificationResponse::Action(response_action)
-                if response_action == "open_buffer" =>
-            {
-                *action = Some(Action::OpenBuffer);
-            }
-            NotificationResponse::Action(_)
-            | NotificationResponse::Reply(_)
-            | NotificationResponse::Closed(_) => {
-                *action = Some(Action::Dismiss);
+            "open_buffer" => Action::OpenBuffer,
+            "accept_file_transfer" => Action::FileTransfer(Response::Accept),
+            "decline_file_transfer" => Action::FileTransfer(Response::Decline),
+            "always_accept_file_transfer" => {
+                Action::FileTransfer(Response::AlwaysAccept)
             }
+            _ => Action::Dismiss,
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
     ActivateApplication,
     OpenBuffer,
+    FileTransfer(Response),
+    Reply(String),
     Dismiss,
 }
@@ -5,11 +5,12 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::{convert, slice};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 use data::capabilities::{
     LabeledResponseContext, MultilineBatchKind, multiline_concat_lines,
 };
-use data::config::buffer::{ScrollPosition, UsernameFormat};
+use data::config::actions::UnjoinedChannelAction;
+use data::config::buffer::{CloseConfirmation, ScrollPosition, UsernameFormat};
 use data::dashboard::{self, BufferAction};
 use data::environment::{RELEASE_WEBSITE, WIKI_WEBSITE};
 use data::history::ReadMarker;
@@ -23,24 +24,32 @@ use data::target::{self, Target};
 use data::user::Nick;
 use data::{
     Config, Image, Notification, Server, User, Version, cache, client, command,
-    config, environment, file_transfer, history, preview, reaction, redaction,
-    server, server_icon, stream,
+    config, environment, file_transfer, highlight_export, history, ignore,
+    preview, reaction, redaction, server, server_icon, stream, timed_ban,
+    user_list,
 };
+use futures::future;
 use iced::widget::pane_grid::{self, PaneGrid};
 use iced::widget::{Space, center, column, container, row, stack, text};
 use iced::{Length, Size, Task, Vector, clipboard, padding};
 use irc::proto;
 
 use self::command_bar::CommandBar;
-use self::modal::{reaction as reaction_modal, redaction as redaction_modal};
+use self::modal::{
+    channel_settings as channel_settings_modal,
+    emoji_picker as emoji_picker_modal, join_channel as join_channel_modal,
+    open_target as open_target_modal, reaction as reaction_modal,
+    redaction as redaction_modal, send_file as send_file_modal,
+    timed_bans as timed_bans_modal, user_list as user_list_modal,
+};
 use self::pane::Pane;
 use self::sidebar::Sidebar;
 use self::theme_editor::ThemeEditor;
 use crate::buffer::{self, Buffer};
 use crate::notification::{self, Notifications, toast};
 use crate::widget::{
-    Column, Element, Row, anchored_overlay, context_menu, selectable_text,
-    shortcut,
+    Column, Element, Row, anchored_overlay, context_menu, gesture, gestures,
+    mouse_wheel, selectable_text, shortcut,
 };
 use crate::window::Window;
 use crate::{
@@ -51,10 +60,14 @@ mod command_bar;
 pub mod modal;
 pub mod pane;
 pub mod sidebar;
+mod status_bar;
 mod theme_editor;
 
 const FOCUS_HISTORY_LEN: usize = 8;
 const SAVE_AFTER: Duration = Duration::from_secs(3);
+const NOTIFICATION_SNOOZE: TimeDelta = TimeDelta::hours(1);
+const CLOSED_BUFFERS_LEN: usize = 10;
+const RECENT_EMOJIS_LEN: usize = 24;
 
 pub struct Dashboard {
     panes: Panes,
@@ -68,11 +81,33 @@ pub struct Dashboard {
     file_transfers: file_transfer::Manager,
     theme_editor: Option<ThemeEditor>,
     previews: preview::Collection,
+    /// Previews asked for when they're only fetched on demand.
+    requested_previews: HashSet<url::Url>,
     previews_cache: Arc<cache::FileCache>,
     server_icons: server_icon::Manager,
     typing_animation: Option<buffer::typing::Animation>,
     http_client: Option<Arc<reqwest::Client>>,
+    preview_http_client: Option<Arc<reqwest::Client>>,
     buffer_settings: dashboard::BufferSettings,
+    audio_muted: bool,
+    timed_bans: Vec<timed_ban::TimedBan>,
+    snoozed_notifications: HashMap<data::Buffer, DateTime<Utc>>,
+    focused_buffer: Option<FocusedBuffer>,
+    connected_on_demand: HashSet<Server>,
+    collapsed_servers: HashSet<Server>,
+    pinned_buffers: HashSet<buffer::Upstream>,
+    muted_buffers: HashSet<buffer::Upstream>,
+    archived_buffers: HashSet<buffer::Upstream>,
+    collapsed_channel_groups: HashSet<(Server, String)>,
+    sidebar_width: Option<f32>,
+    recent_emojis: Vec<String>,
+    /// Hostmasks ignored with `/ignore`, alongside those in `ignore.masks`.
+    ignores: Vec<ignore::Mask>,
+    /// `buffer.close.confirm` rules the user asked not to be prompted for.
+    dismissed_close_confirmations: Vec<CloseConfirmation>,
+    recent_highlights: message::highlight::Recent,
+    /// Channels and queries whose panes were closed, most recent first.
+    closed_buffers: VecDeque<buffer::Upstream>,
     pub filehost: filehost::Manager,
 }
 
@@ -86,7 +121,18 @@ pub enum Message {
     Task(command_bar::Message),
     Shortcut(shortcut::Command),
     FileTransfer(file_transfer::task::Update),
-    SendFileSelected(Server, User, Option<PathBuf>),
+    SendFilesSelected(Server, User, Option<Vec<PathBuf>>),
+    FileTransferSavePathSelected(file_transfer::Id, Option<PathBuf>),
+    UserListExportSelected(Server, target::Channel, Option<PathBuf>),
+    UserListExported(Result<PathBuf, user_list::Error>),
+    HighlightExported(Result<(), highlight_export::Error>),
+    UserListCompared(
+        window::Id,
+        pane_grid::Pane,
+        Server,
+        target::Channel,
+        Result<Option<user_list::Export>, user_list::Error>,
+    ),
     CloseContextMenu(window::Id, bool),
     ThemeEditor(theme_editor::Message),
     ConfigReloaded(Result<Config, config::Error>),
@@ -98,12 +144,17 @@ pub enum Message {
     Filehost(filehost::Message),
     ProceedWithFilehostUpload,
     CancelFilehostUpload,
+    Gesture(gesture::Gesture),
+    /// A close confirmed by the user, along with the rule to stop asking
+    /// about when "don't ask again" was checked.
+    ConfirmedClose(PendingClose, Option<CloseConfirmation>),
 }
 
 #[derive(Debug)]
 pub enum Event {
     ConfigReloaded(Result<Config, config::Error>),
     ReloadThemes,
+    ThemeSwitched(data::Theme),
     QuitServer(Server, Option<String>),
     IrcError(anyhow::Error),
     Exit,
@@ -116,6 +167,7 @@ pub enum Event {
     OpenServer(String),
     ImagePreview(Image),
     ToggleFullscreen,
+    Lock,
     Remove(Server),
     PromptBeforeFileUpload {
         upload_url: String,
@@ -123,6 +175,21 @@ pub enum Event {
         window: window::Id,
     },
     EchoEvents(Server, Vec<EchoEvent>),
+    TestNotification(Server, Notification),
+    Zoom(f32),
+    ConfirmClose {
+        close: PendingClose,
+        confirmation: CloseConfirmation,
+        name: String,
+        window: window::Id,
+    },
+}
+
+/// A pane close or buffer leave waiting on the user to confirm it.
+#[derive(Debug, Clone)]
+pub enum PendingClose {
+    Pane(window::Id, pane_grid::Pane),
+    Leave(buffer::Upstream),
 }
 
 impl Dashboard {
@@ -154,11 +221,30 @@ impl Dashboard {
             file_transfers: file_transfer::Manager::default(),
             theme_editor: None,
             previews: preview::Collection::default(),
+            requested_previews: HashSet::new(),
             previews_cache: Arc::new(preview_cache(&config.preview)),
             server_icons: server_icon::Manager::default(),
             typing_animation: None,
             http_client: http_client_from_config(config).map(Arc::new),
+            preview_http_client: preview_http_client_from_config(config)
+                .map(Arc::new),
             buffer_settings: dashboard::BufferSettings::default(),
+            audio_muted: false,
+            timed_bans: vec![],
+            snoozed_notifications: HashMap::new(),
+            focused_buffer: None,
+            connected_on_demand: HashSet::new(),
+            collapsed_servers: HashSet::new(),
+            pinned_buffers: HashSet::new(),
+            muted_buffers: HashSet::new(),
+            archived_buffers: HashSet::new(),
+            collapsed_channel_groups: HashSet::new(),
+            sidebar_width: None,
+            recent_emojis: vec![],
+            ignores: vec![],
+            dismissed_close_confirmations: vec![],
+            recent_highlights: message::highlight::Recent::default(),
+            closed_buffers: VecDeque::new(),
             filehost: filehost::Manager::new(),
         };
 
@@ -166,6 +252,10 @@ impl Dashboard {
             dashboard
                 .history
                 .preload_drafts(data::input::load_drafts_sync());
+            dashboard.history.preload_input_history(
+                data::input::load_sent_sync(),
+                config.buffer.text_input.history_length,
+            );
         }
 
         let command = dashboard.track(None);
@@ -187,6 +277,58 @@ impl Dashboard {
         (dashboard, tasks)
     }
 
+    /// Focuses the buffer configured by `pane.focus_on_launch`.
+    pub fn focus_on_launch(
+        &mut self,
+        servers: &server::Map,
+        config: &Config,
+    ) -> Task<Message> {
+        match &config.pane.focus_on_launch {
+            config::pane::FocusOnLaunch::Last => Task::none(),
+            config::pane::FocusOnLaunch::Empty => {
+                if let Some((window, pane)) =
+                    self.panes.iter().find_map(|(window, pane, state)| {
+                        matches!(state.buffer, Buffer::Empty)
+                            .then_some((window, pane))
+                    })
+                {
+                    self.focus_pane(window, pane)
+                } else {
+                    self.new_pane(pane_grid::Axis::Vertical)
+                }
+            }
+            config::pane::FocusOnLaunch::Buffer { server, channel } => {
+                let Some(server) =
+                    servers.entries().map(|entry| entry.server).find(
+                        |candidate| candidate.name.as_ref() == server.as_str(),
+                    )
+                else {
+                    log::warn!("pane.focus_on_launch: unknown server {server}");
+                    return Task::none();
+                };
+
+                let upstream = match channel {
+                    Some(channel) => buffer::Upstream::Channel(
+                        server,
+                        target::Channel::from_str(
+                            channel,
+                            isupport::DEFAULT_CHANTYPES,
+                            isupport::CaseMap::default(),
+                        ),
+                    ),
+                    None => buffer::Upstream::Server(server),
+                };
+
+                self.open_buffer(
+                    data::Buffer::Upstream(upstream),
+                    BufferAction::NewPane,
+                    &mut client::Map::default(),
+                    config,
+                )
+            }
+        }
+    }
+
     pub fn init_filters(
         &mut self,
         servers: &server::Map,
@@ -229,6 +371,8 @@ impl Dashboard {
 
     pub fn refresh_cache_limits(&mut self, config: &Config) {
         self.previews_cache = Arc::new(preview_cache(&config.preview));
+        self.preview_http_client =
+            preview_http_client_from_config(config).map(Arc::new);
     }
 
     pub fn set_reroute_rules(
@@ -358,14 +502,13 @@ impl Dashboard {
                     }
                     pane::Message::PaneDragged(_) => {}
                     pane::Message::ClosePane => {
-                        return (
-                            self.close_pane(
-                                clients,
-                                config,
+                        return self.request_close(
+                            clients,
+                            config,
+                            PendingClose::Pane(
                                 self.focus.window,
                                 self.focus.pane,
                             ),
-                            None,
                         );
                     }
                     pane::Message::SplitPane(axis) => {
@@ -441,6 +584,20 @@ impl Dashboard {
                             return (Task::none(), None);
                         }
                     }
+                    pane::Message::ToggleHiddenMessages(class) => {
+                        if let Some((_, _, pane)) = self.get_focused_mut() {
+                            if let Some(buffer) = pane.buffer.data() {
+                                let settings = self.buffer_settings.entry(
+                                    &buffer,
+                                    Some(config.buffer.clone().into()),
+                                );
+                                settings.hidden_messages.toggle(class);
+                            }
+
+                            self.last_changed = Some(Instant::now());
+                            return (Task::none(), None);
+                        }
+                    }
                     pane::Message::MaximizePane => self.maximize_pane(),
                     pane::Message::Popout => {
                         return (self.popout_pane(clients, config), None);
@@ -563,6 +720,23 @@ impl Dashboard {
 
                                     (task, event)
                                 }
+                                modal::Event::InsertEmoji(emoji) => {
+                                    pane.close_buffer_modal();
+                                    pane.buffer.insert_emoji_to_input(
+                                        emoji,
+                                        &mut self.history,
+                                    );
+
+                                    self.recent_emojis
+                                        .retain(|recent| recent != emoji);
+                                    self.recent_emojis
+                                        .insert(0, emoji.to_string());
+                                    self.recent_emojis
+                                        .truncate(RECENT_EMOJIS_LEN);
+                                    self.last_changed = Some(Instant::now());
+
+                                    (self.refocus_pane(), None)
+                                }
                                 modal::Event::RedactReason {
                                     msgid,
                                     reason,
@@ -597,6 +771,152 @@ impl Dashboard {
                                         }
                                     }
 
+                                    (self.refocus_pane(), None)
+                                }
+                                modal::Event::Unban(ban) => {
+                                    // The ban can only be lifted from within
+                                    // the channel, otherwise it stays pending.
+                                    if clients
+                                        .get_channel_users(
+                                            &ban.server,
+                                            &ban.channel,
+                                        )
+                                        .is_some()
+                                    {
+                                        send_unban(clients, &ban);
+
+                                        timed_ban::remove(
+                                            &mut self.timed_bans,
+                                            &ban,
+                                        );
+                                        self.last_changed =
+                                            Some(Instant::now());
+                                    }
+
+                                    pane.modal =
+                                        Some(modal::Modal::PendingUnbans(
+                                            timed_bans_modal::State::new(
+                                                ban.channel.to_string(),
+                                                pending_unbans(
+                                                    &self.timed_bans,
+                                                    &ban.server,
+                                                    &ban.channel,
+                                                ),
+                                            ),
+                                        ));
+
+                                    (Task::none(), None)
+                                }
+                                modal::Event::ChannelCommand(
+                                    server,
+                                    channel,
+                                    command,
+                                ) => {
+                                    send_channel_command(
+                                        clients, &server, &channel, command,
+                                    );
+                                    request_channel_settings(
+                                        clients, &server, &channel,
+                                    );
+
+                                    (Task::none(), None)
+                                }
+                                modal::Event::CancelUnban(ban) => {
+                                    timed_ban::remove(
+                                        &mut self.timed_bans,
+                                        &ban,
+                                    );
+                                    self.last_changed = Some(Instant::now());
+
+                                    (Task::none(), None)
+                                }
+                                modal::Event::JoinChannel(
+                                    server,
+                                    channel,
+                                    buffer_action,
+                                ) => {
+                                    pane.close_buffer_modal();
+
+                                    let refocus = self.refocus_pane();
+
+                                    (
+                                        refocus.chain(self.open_target(
+                                            server,
+                                            Target::Channel(channel),
+                                            clients,
+                                            buffer_action,
+                                            config,
+                                            true,
+                                        )),
+                                        None,
+                                    )
+                                }
+                                modal::Event::OpenTarget(
+                                    kind,
+                                    server,
+                                    name,
+                                ) => {
+                                    pane.close_buffer_modal();
+
+                                    let target = match kind {
+                                        open_target_modal::Kind::Channel => {
+                                            Target::Channel(
+                                                target::Channel::from_str(
+                                                    &name,
+                                                    clients.get_server_chantypes_or_default(&server),
+                                                    clients.get_server_casemapping_or_default(&server),
+                                                ),
+                                            )
+                                        }
+                                        open_target_modal::Kind::Query => {
+                                            Target::Query(target::Query::from(
+                                                Nick::from_str(
+                                                    &name,
+                                                    clients.get_server_casemapping_or_default(&server),
+                                                ),
+                                            ))
+                                        }
+                                    };
+
+                                    let refocus = self.refocus_pane();
+
+                                    (
+                                        refocus.chain(self.open_target(
+                                            server,
+                                            target,
+                                            clients,
+                                            BufferAction::ReplacePane,
+                                            config,
+                                            true,
+                                        )),
+                                        None,
+                                    )
+                                }
+                                modal::Event::SendFile(server, nick, path) => {
+                                    pane.close_buffer_modal();
+
+                                    let to = User::from(Nick::from_str(
+                                        &nick,
+                                        clients
+                                            .get_server_casemapping_or_default(
+                                                &server,
+                                            ),
+                                    ));
+
+                                    (
+                                        self.refocus_pane().chain(Task::done(
+                                            Message::SendFilesSelected(
+                                                server,
+                                                to,
+                                                Some(vec![path]),
+                                            ),
+                                        )),
+                                        None,
+                                    )
+                                }
+                                modal::Event::Close => {
+                                    pane.close_buffer_modal();
+
                                     (self.refocus_pane(), None)
                                 }
                             }
@@ -676,9 +996,11 @@ impl Dashboard {
                         ),
                         None,
                     ),
-                    sidebar::Event::Close(window, pane) => {
-                        (self.close_pane(clients, config, window, pane), None)
-                    }
+                    sidebar::Event::Close(window, pane) => self.request_close(
+                        clients,
+                        config,
+                        PendingClose::Pane(window, pane),
+                    ),
                     sidebar::Event::Swap(window, pane) => {
                         (self.swap_pane_with_focus(window, pane), None)
                     }
@@ -700,8 +1022,221 @@ impl Dashboard {
                             (Task::none(), None)
                         }
                     }
-                    sidebar::Event::Leave(buffer) => {
-                        self.leave_buffer(clients, config, buffer)
+                    sidebar::Event::Leave(buffer) => self.request_close(
+                        clients,
+                        config,
+                        PendingClose::Leave(buffer),
+                    ),
+                    sidebar::Event::RefreshUserList(server, channel) => {
+                        clients.refresh_users(&server, &channel);
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::ExportUserList(server, channel) => {
+                        // Refresh accounts and hostmasks while the user picks
+                        // where to save the export.
+                        clients.refresh_who_poll(&server, &channel);
+
+                        let file_name = format!("{channel}.csv");
+
+                        (
+                            Task::perform(
+                                async move {
+                                    rfd::AsyncFileDialog::new()
+                                        .set_file_name(file_name)
+                                        .add_filter("CSV", &["csv"])
+                                        .add_filter("JSON", &["json"])
+                                        .save_file()
+                                        .await
+                                        .map(|handle| {
+                                            handle.path().to_path_buf()
+                                        })
+                                },
+                                move |path| {
+                                    Message::UserListExportSelected(
+                                        server.clone(),
+                                        channel.clone(),
+                                        path,
+                                    )
+                                },
+                            ),
+                            None,
+                        )
+                    }
+                    sidebar::Event::CompareUserList(
+                        window,
+                        pane,
+                        server,
+                        channel,
+                    ) => (
+                        Task::perform(
+                            user_list::last(server.clone(), channel.clone()),
+                            move |result| {
+                                Message::UserListCompared(
+                                    window,
+                                    pane,
+                                    server.clone(),
+                                    channel.clone(),
+                                    result,
+                                )
+                            },
+                        ),
+                        None,
+                    ),
+                    sidebar::Event::PendingUnbans(
+                        window,
+                        pane,
+                        server,
+                        channel,
+                    ) => {
+                        let bans =
+                            pending_unbans(&self.timed_bans, &server, &channel);
+
+                        if let Some(state) = self.panes.get_mut(window, pane) {
+                            (
+                                state
+                                    .open_modal(
+                                        pane,
+                                        modal::Modal::PendingUnbans(
+                                            timed_bans_modal::State::new(
+                                                channel.to_string(),
+                                                bans,
+                                            ),
+                                        ),
+                                    )
+                                    .map(move |message| {
+                                        Message::Pane(window, message)
+                                    }),
+                                None,
+                            )
+                        } else {
+                            (Task::none(), None)
+                        }
+                    }
+                    sidebar::Event::ChannelSettings(
+                        window,
+                        pane,
+                        server,
+                        channel,
+                    ) => {
+                        request_channel_settings(clients, &server, &channel);
+
+                        if let Some(state) = self.panes.get_mut(window, pane) {
+                            (
+                                state
+                                    .open_modal(
+                                        pane,
+                                        modal::Modal::ChannelSettings(
+                                            channel_settings_modal::State::new(
+                                                server, channel,
+                                            ),
+                                        ),
+                                    )
+                                    .map(move |message| {
+                                        Message::Pane(window, message)
+                                    }),
+                                None,
+                            )
+                        } else {
+                            (Task::none(), None)
+                        }
+                    }
+                    sidebar::Event::ToggleServerCollapsed(server) => {
+                        if !self.collapsed_servers.remove(&server) {
+                            self.collapsed_servers.insert(server);
+                        }
+
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::ToggleChannelGroupCollapsed(
+                        server,
+                        prefix,
+                    ) => {
+                        let group = (server, prefix);
+
+                        if !self.collapsed_channel_groups.remove(&group) {
+                            self.collapsed_channel_groups.insert(group);
+                        }
+
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::Pin(buffer) => {
+                        self.pinned_buffers.insert(buffer);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::Unpin(buffer) => {
+                        self.pinned_buffers.remove(&buffer);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::Mute(buffer) => {
+                        self.muted_buffers.insert(buffer);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::Unmute(buffer) => {
+                        self.muted_buffers.remove(&buffer);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::Rejoin(buffer) => {
+                        // Unarchived once joined
+                        if let buffer::Upstream::Channel(server, channel) =
+                            &buffer
+                        {
+                            send_channel_command(
+                                clients,
+                                server,
+                                channel,
+                                command::Irc::Join(channel.to_string(), None),
+                            );
+                        }
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::Unarchive(buffer) => {
+                        let open = self.panes.iter().find_map(
+                            |(window, pane, state)| {
+                                (state.buffer.upstream() == Some(&buffer))
+                                    .then_some((window, pane))
+                            },
+                        );
+
+                        self.unarchive(&buffer);
+
+                        (
+                            open.map_or_else(Task::none, |(window, pane)| {
+                                self.close_pane(clients, config, window, pane)
+                            }),
+                            None,
+                        )
+                    }
+                    sidebar::Event::Resize(width) => {
+                        self.sidebar_width = width;
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::CycleBuffer(step) => {
+                        let command = match step {
+                            mouse_wheel::Step::Up => {
+                                shortcut::Command::CyclePreviousBuffer
+                            }
+                            mouse_wheel::Step::Down => {
+                                shortcut::Command::CycleNextBuffer
+                            }
+                        };
+
+                        (Task::done(Message::Shortcut(command)), None)
                     }
                     sidebar::Event::ToggleCommandBar => (
                         self.toggle_command_bar(
@@ -775,6 +1310,38 @@ impl Dashboard {
                         self.buffer_settings.show_muted = show_muted_buffers;
                         (Task::none(), None)
                     }
+                    sidebar::Event::ToggleAudioMute => {
+                        self.toggle_audio_mute();
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::SnoozeNotifications(buffer) => {
+                        self.snoozed_notifications
+                            .insert(buffer, Utc::now() + NOTIFICATION_SNOOZE);
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::UnsnoozeNotifications(buffer) => {
+                        self.snoozed_notifications.remove(&buffer);
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::OpenLogFile(buffer) => (
+                        history::Kind::from_buffer(buffer).map_or_else(
+                            Task::none,
+                            |kind| {
+                                Task::future(open_history_file(kind)).discard()
+                            },
+                        ),
+                        None,
+                    ),
+                    sidebar::Event::OpenDataFolder(buffer) => (
+                        history::Kind::from_buffer(buffer).map_or_else(
+                            Task::none,
+                            |kind| {
+                                Task::future(open_history_folder(kind))
+                                    .discard()
+                            },
+                        ),
+                        None,
+                    ),
                 };
 
                 let window = main_window.id;
@@ -1053,9 +1620,10 @@ impl Dashboard {
                     }
                     CloseBuffer => {
                         let Focus { window, pane } = self.focus;
-                        return (
-                            self.close_pane(clients, config, window, pane),
-                            None,
+                        return self.request_close(
+                            clients,
+                            config,
+                            PendingClose::Pane(window, pane),
                         );
                     }
                     MaximizeBuffer => {
@@ -1119,7 +1687,11 @@ impl Dashboard {
                             && let Some(buffer) =
                                 state.buffer.upstream().cloned()
                         {
-                            return self.leave_buffer(clients, config, buffer);
+                            return self.request_close(
+                                clients,
+                                config,
+                                PendingClose::Leave(buffer),
+                            );
                         }
                     }
                     ToggleNicklist => {
@@ -1154,7 +1726,15 @@ impl Dashboard {
                         }
                     }
                     ToggleSidebar => {
-                        self.side_menu.toggle_visibility();
+                        self.side_menu.toggle_visibility(config);
+                    }
+                    ToggleSidebarFilter => {
+                        return (
+                            self.side_menu
+                                .toggle_filter()
+                                .map(Message::Sidebar),
+                            None,
+                        );
                     }
                     CommandBar => {
                         return (
@@ -1440,21 +2020,63 @@ impl Dashboard {
                     HideMutedBuffers => {
                         self.buffer_settings.show_muted = false;
                     }
-                }
-            }
-            Message::FileTransfer(update) => {
-                self.file_transfers.update(update, config);
-            }
-            Message::SendFileSelected(server, to, path) => {
-                if let Some(server_handle) = clients.get_server_handle(&server)
-                {
+                    ToggleAudioMute => {
+                        self.toggle_audio_mute();
+                    }
+                    EqualizeBuffers => {
+                        self.equalize_panes();
+                    }
+                    RotateBuffers => {
+                        return (self.rotate_panes(), None);
+                    }
+                    SwapPreviousBuffer => {
+                        self.swap_with_previous_pane();
+                    }
+                    MoveBufferTop => {
+                        self.move_pane_to_edge(pane_grid::Edge::Top);
+                    }
+                    MoveBufferBottom => {
+                        self.move_pane_to_edge(pane_grid::Edge::Bottom);
+                    }
+                    MoveBufferLeft => {
+                        self.move_pane_to_edge(pane_grid::Edge::Left);
+                    }
+                    MoveBufferRight => {
+                        self.move_pane_to_edge(pane_grid::Edge::Right);
+                    }
+                }
+            }
+            Message::FileTransfer(update) => {
+                // The next file of a batch
+                if let Some(event) = self.file_transfers.update(update, config)
+                {
+                    let file_transfer::manager::Event::NewTransfer(transfer, _) =
+                        &event;
+
+                    let server = transfer.server.clone();
+                    let query = target::Query::from(&transfer.remote_user);
+
+                    return (
+                        self.handle_file_transfer_event(
+                            &server,
+                            &query,
+                            event,
+                            &config.buffer,
+                        ),
+                        None,
+                    );
+                }
+            }
+            Message::SendFilesSelected(server, to, paths) => {
+                if let Some(server_handle) = clients.get_server_handle(&server)
+                {
                     let query = target::Query::from(&to);
 
-                    if let Some(path) = path
-                        && let Some(event) = self.file_transfers.send(
-                            file_transfer::SendRequest {
+                    if let Some(paths) = paths
+                        && let Some(event) = self.file_transfers.send_batch(
+                            file_transfer::SendBatchRequest {
                                 to,
-                                path,
+                                paths,
                                 server: server.clone(),
                                 server_handle: server_handle.clone(),
                             },
@@ -1473,6 +2095,76 @@ impl Dashboard {
                     }
                 }
             }
+            Message::FileTransferSavePathSelected(id, path) => {
+                if let Some(path) = path {
+                    self.file_transfers.approve(&id, path, config);
+                }
+            }
+            Message::UserListExportSelected(server, channel, path) => {
+                if let Some(path) = path
+                    && let Some(users) =
+                        clients.get_channel_users(&server, &channel)
+                {
+                    let export =
+                        user_list::Export::new(&server, &channel, users);
+
+                    return (
+                        Task::perform(
+                            user_list::save(export, path),
+                            Message::UserListExported,
+                        ),
+                        None,
+                    );
+                }
+            }
+            Message::UserListExported(Ok(path)) => {
+                log::info!("user list exported to {}", path.display());
+            }
+            Message::UserListExported(Err(error)) => {
+                log::warn!("error exporting user list: {error}");
+            }
+            Message::HighlightExported(Ok(())) => {
+                log::info!("highlight sent to export command");
+            }
+            Message::HighlightExported(Err(error)) => {
+                log::warn!("error exporting highlight: {error}");
+            }
+            Message::UserListCompared(window, id, server, channel, result) => {
+                let previous = match result {
+                    Ok(previous) => previous,
+                    Err(error) => {
+                        log::warn!(
+                            "error loading last user list export: {error}"
+                        );
+                        return (Task::none(), None);
+                    }
+                };
+
+                let diff = previous.and_then(|previous| {
+                    clients.get_channel_users(&server, &channel).map(|users| {
+                        user_list::diff(
+                            &previous,
+                            &user_list::Export::new(&server, &channel, users),
+                        )
+                    })
+                });
+
+                if let Some(pane) = self.panes.get_mut(window, id) {
+                    return (
+                        pane.open_modal(
+                            id,
+                            modal::Modal::UserListDiff(
+                                user_list_modal::State::new(
+                                    channel.to_string(),
+                                    diff,
+                                ),
+                            ),
+                        )
+                        .map(move |message| Message::Pane(window, message)),
+                        None,
+                    );
+                }
+            }
             Message::CloseContextMenu(window, any_closed) => {
                 if !any_closed {
                     if let Some((_, _, state, history)) =
@@ -1516,6 +2208,12 @@ impl Dashboard {
                         theme_editor::Event::ReloadThemes => {
                             event = Some(Event::ReloadThemes);
                         }
+                        theme_editor::Event::ReloadConfig => {
+                            tasks.push(Task::perform(
+                                Config::load(),
+                                Message::ConfigReloaded,
+                            ));
+                        }
                     }
                 }
 
@@ -1651,6 +2349,35 @@ impl Dashboard {
                 let task = self.filehost.cancel().map(Message::Filehost);
                 return (task, None);
             }
+            Message::ConfirmedClose(close, dismissed) => {
+                if let Some(confirmation) = dismissed
+                    && !self
+                        .dismissed_close_confirmations
+                        .contains(&confirmation)
+                {
+                    self.dismissed_close_confirmations.push(confirmation);
+                    self.last_changed = Some(Instant::now());
+                }
+
+                return self.perform_close(clients, config, close);
+            }
+            Message::Gesture(gesture) => match gesture {
+                gesture::Gesture::Swipe(direction) => {
+                    let command = match direction {
+                        gesture::Direction::Left => {
+                            shortcut::Command::CycleNextBuffer
+                        }
+                        gesture::Direction::Right => {
+                            shortcut::Command::CyclePreviousBuffer
+                        }
+                    };
+
+                    return (Task::done(Message::Shortcut(command)), None);
+                }
+                gesture::Gesture::Pinch(scale) => {
+                    return (Task::none(), Some(Event::Zoom(scale)));
+                }
+            },
         }
 
         (Task::none(), None)
@@ -1678,6 +2405,10 @@ impl Dashboard {
                     let settings = buffer
                         .as_ref()
                         .and_then(|b| self.buffer_settings.get(b));
+                    let is_archived =
+                        pane.buffer.upstream().is_some_and(|buffer| {
+                            self.is_archived(clients, buffer)
+                        });
 
                     pane.view(
                         id,
@@ -1690,9 +2421,12 @@ impl Dashboard {
                         &self.history,
                         &self.previews,
                         &self.side_menu,
+                        &self.closed_buffers,
+                        &self.ignores,
                         config,
                         theme,
                         settings,
+                        is_archived,
                         window != self.main_window(),
                         |server: &Server, channel: &target::Channel| -> bool {
                             self.has_focused_pane_channel(server, channel)
@@ -1709,8 +2443,12 @@ impl Dashboard {
             .height(Length::Fill)
             .padding(padding);
 
-            let base = Element::new(content)
-                .map(move |message| Message::Pane(window, message));
+            let base = gestures(
+                Element::new(content)
+                    .map(move |message| Message::Pane(window, message)),
+                config.pane.gestures,
+                Message::Gesture,
+            );
             let base = self.with_command_bar_overlay(
                 base, window, servers, clients, version, config,
             );
@@ -1744,6 +2482,10 @@ impl Dashboard {
                 let buffer = pane.buffer.data();
                 let settings =
                     buffer.as_ref().and_then(|b| self.buffer_settings.get(b));
+                let is_archived = pane
+                    .buffer
+                    .upstream()
+                    .is_some_and(|buffer| self.is_archived(clients, buffer));
 
                 pane.view(
                     id,
@@ -1756,9 +2498,12 @@ impl Dashboard {
                     &self.history,
                     &self.previews,
                     &self.side_menu,
+                    &self.closed_buffers,
+                    &self.ignores,
                     config,
                     theme,
                     settings,
+                    is_archived,
                     false,
                     |server: &Server, channel: &target::Channel| -> bool {
                         self.has_focused_pane_channel(server, channel)
@@ -1790,13 +2535,16 @@ impl Dashboard {
             }
         };
 
-        let pane_grid =
+        let pane_grid = gestures(
             container(pane_grid.map(move |message| {
                 Message::Pane(self.main_window(), message)
             }))
             .width(Length::Fill)
             .height(Length::Fill)
-            .padding(pane_padding);
+            .padding(pane_padding),
+            config.pane.gestures,
+            Message::Gesture,
+        );
 
         let side_menu = self
             .side_menu
@@ -1812,6 +2560,14 @@ impl Dashboard {
                 version,
                 theme,
                 self.buffer_settings.show_muted,
+                self.audio_muted,
+                &self.snoozed_notifications,
+                &self.collapsed_servers,
+                &self.pinned_buffers,
+                &self.muted_buffers,
+                &self.archived_buffers,
+                &self.collapsed_channel_groups,
+                self.sidebar_width,
             )
             .map(|e| e.map(Message::Sidebar));
 
@@ -1845,6 +2601,30 @@ impl Dashboard {
                 .into()
         };
 
+        let focused_server = self
+            .panes
+            .get(self.focus.window, self.focus.pane)
+            .and_then(|pane| pane.buffer.server());
+
+        let base = if let Some(status_bar) = status_bar::view(
+            focused_server.as_ref(),
+            clients,
+            &self.file_transfers,
+            config,
+            theme,
+        ) {
+            match config.status_bar.position {
+                data::config::status_bar::Position::Top => {
+                    column![status_bar, base].into()
+                }
+                data::config::status_bar::Position::Bottom => {
+                    column![base, status_bar].into()
+                }
+            }
+        } else {
+            base
+        };
+
         let base = self.with_command_bar_overlay(
             base,
             self.main_window(),
@@ -2109,6 +2889,43 @@ impl Dashboard {
 
                         None
                     }
+                    buffer::context_menu::Event::Ban(
+                        server,
+                        channel,
+                        user,
+                        duration,
+                    ) => {
+                        let buffer = buffer::Upstream::Channel(
+                            server.clone(),
+                            channel.clone(),
+                        );
+
+                        let mask = timed_ban::mask(&user);
+
+                        let command = command::Irc::Mode(
+                            channel.to_string(),
+                            Some("+b".to_string()),
+                            Some(vec![mask]),
+                        );
+                        let input = data::Input::from_command(buffer, command);
+
+                        if let Some(encoded) = input.encoded() {
+                            clients.send(
+                                &input.buffer,
+                                encoded,
+                                TokenPriority::User,
+                            );
+
+                            if let Some(duration) = duration {
+                                self.timed_bans.push(timed_ban::TimedBan::new(
+                                    server, channel, &user, duration,
+                                ));
+                                self.last_changed = Some(Instant::now());
+                            }
+                        }
+
+                        None
+                    }
                     buffer::context_menu::Event::SendWhois(server, nick) => {
                         let buffer =
                             pane.buffer.upstream().cloned().unwrap_or_else(
@@ -2193,15 +3010,22 @@ impl Dashboard {
                         tasks.push(Task::perform(
                             async move {
                                 rfd::AsyncFileDialog::new()
-                                    .pick_file()
+                                    .pick_files()
                                     .await
-                                    .map(|handle| handle.path().to_path_buf())
+                                    .map(|handles| {
+                                        handles
+                                            .iter()
+                                            .map(|handle| {
+                                                handle.path().to_path_buf()
+                                            })
+                                            .collect()
+                                    })
                             },
-                            move |file| {
-                                Message::SendFileSelected(
+                            move |files| {
+                                Message::SendFilesSelected(
                                     server.clone(),
                                     nick.clone(),
-                                    file,
+                                    files,
                                 )
                             },
                         ));
@@ -2290,6 +3114,24 @@ impl Dashboard {
 
                         None
                     }
+                    buffer::context_menu::Event::CancelMessage(
+                        server_time,
+                        hash,
+                    ) => {
+                        if let Some(buffer) = pane.buffer.upstream()
+                            && let Some(command) = self.history.cancel_message(
+                                &history::Kind::from_input_buffer(
+                                    buffer.clone(),
+                                ),
+                                server_time,
+                                hash,
+                            )
+                        {
+                            clients.cancel_deferred(buffer.server(), &command);
+                        }
+
+                        None
+                    }
                     buffer::context_menu::Event::OpenReactionModal(
                         msgid,
                         selected_reactions,
@@ -2380,6 +3222,44 @@ impl Dashboard {
                             );
                         }
 
+                        None
+                    }
+                    buffer::context_menu::Event::ExportHighlight(message) => {
+                        let (server, channel) = match &message.target {
+                            message::Target::Highlights {
+                                server,
+                                channel,
+                                ..
+                            } => (Some(server.clone()), Some(channel.clone())),
+                            _ => (
+                                pane.buffer.server(),
+                                pane.buffer
+                                    .upstream()
+                                    .and_then(buffer::Upstream::channel)
+                                    .cloned(),
+                            ),
+                        };
+
+                        if let Some(command) =
+                            config.highlights.export.command.clone()
+                            && let Some(server) = server
+                        {
+                            tasks.push(Task::perform(
+                                highlight_export::run(
+                                    command,
+                                    Duration::from_secs(
+                                        config.highlights.export.timeout,
+                                    ),
+                                    highlight_export::Export::new(
+                                        &server,
+                                        channel.as_ref(),
+                                        &message,
+                                    ),
+                                ),
+                                Message::HighlightExported,
+                            ));
+                        }
+
                         None
                     }
                 };
@@ -2387,17 +3267,73 @@ impl Dashboard {
                 return (Task::batch(tasks), event);
             }
             buffer::Event::OpenBuffers(server, targets) => {
+                // Listed channels are opened to be joined
+                let unjoined_action =
+                    if matches!(pane.buffer, Buffer::ChannelDiscovery(_)) {
+                        UnjoinedChannelAction::Join
+                    } else {
+                        config.actions.buffer.click_unjoined_channel
+                    };
+
                 let mut tasks = vec![];
 
                 for (target, buffer_action) in targets {
-                    tasks.push(self.open_target(
-                        server.clone(),
-                        target,
-                        clients,
-                        buffer_action,
-                        config,
-                        true,
-                    ));
+                    let unjoined_channel = match &target {
+                        Target::Channel(channel)
+                            if !clients.contains_channel(&server, channel) =>
+                        {
+                            Some(channel.clone())
+                        }
+                        _ => None,
+                    };
+
+                    match (unjoined_channel, unjoined_action) {
+                        (Some(channel), UnjoinedChannelAction::Prompt) => {
+                            let Some(pane) = self.panes.get_mut(window, id)
+                            else {
+                                continue;
+                            };
+
+                            let modal = modal::Modal::JoinChannel(
+                                join_channel_modal::State::new(
+                                    server.clone(),
+                                    channel,
+                                    buffer_action,
+                                ),
+                            );
+
+                            tasks.push(pane.open_modal(id, modal).map(
+                                move |message| Message::Pane(window, message),
+                            ));
+                        }
+                        (Some(channel), UnjoinedChannelAction::Preview) => {
+                            send_channel_command(
+                                clients,
+                                &server,
+                                &channel,
+                                command::Irc::Topic(channel.to_string(), None),
+                            );
+
+                            tasks.push(self.open_target(
+                                server.clone(),
+                                target,
+                                clients,
+                                buffer_action,
+                                config,
+                                false,
+                            ));
+                        }
+                        _ => {
+                            tasks.push(self.open_target(
+                                server.clone(),
+                                target,
+                                clients,
+                                buffer_action,
+                                config,
+                                true,
+                            ));
+                        }
+                    }
                 }
 
                 return (Task::batch(tasks), None);
@@ -2520,6 +3456,18 @@ impl Dashboard {
             buffer::Event::HidePreview(kind, hash, url) => {
                 self.history.hide_preview(kind, hash, url);
             }
+            buffer::Event::LoadPreview(url) => {
+                self.requested_previews.insert(url);
+
+                return self.handle_buffer_event(
+                    window,
+                    id,
+                    buffer::Event::PreviewChanged,
+                    clients,
+                    controllers,
+                    config,
+                );
+            }
             buffer::Event::MarkAsRead(kind) => {
                 mark_as_read(
                     kind,
@@ -2564,6 +3512,13 @@ impl Dashboard {
                     );
                 }
             }
+            buffer::Event::ExpandReplay(from, expanded) => {
+                if let Some(kind) =
+                    pane.buffer.data().and_then(history::Kind::from_buffer)
+                {
+                    self.history.expand_replay(kind, from, expanded);
+                }
+            }
             buffer::Event::InputSent {
                 history_task,
                 open_buffers,
@@ -2617,12 +3572,92 @@ impl Dashboard {
                     None,
                 );
             }
+            buffer::Event::OpenInPane(buffer) => {
+                return (
+                    self.open_buffer(
+                        buffer,
+                        BufferAction::ReplacePane,
+                        clients,
+                        config,
+                    ),
+                    None,
+                );
+            }
+            buffer::Event::PromptOpenTarget(kind) => {
+                let servers = clients.connected_servers().cloned().collect();
+
+                return (
+                    pane.open_modal(
+                        id,
+                        modal::Modal::OpenTarget(
+                            open_target_modal::State::new(kind, servers),
+                        ),
+                    )
+                    .map(move |message| Message::Pane(window, message)),
+                    None,
+                );
+            }
             buffer::Event::OpenServer(server) => {
                 return (Task::none(), Some(Event::OpenServer(server)));
             }
+            buffer::Event::OpenEmojiPicker => {
+                return (
+                    pane.open_modal(
+                        id,
+                        modal::Modal::InsertEmoji(
+                            emoji_picker_modal::State::new(
+                                &self.recent_emojis,
+                                config.buffer.emojis.skin_tone,
+                            ),
+                        ),
+                    )
+                    .map(move |message| Message::Pane(window, message)),
+                    None,
+                );
+            }
             buffer::Event::Reconnect(server) => {
                 controllers.connect(&server);
             }
+            buffer::Event::UpdateAutojoin(server, from, to) => {
+                // Saved to the config file, which is then reloaded
+                return (
+                    Task::future(Config::save_autojoin_channel(
+                        server.name.to_string(),
+                        from.to_string(),
+                        to.to_string(),
+                    ))
+                    .then(|result| match result {
+                        Ok(()) => Task::perform(
+                            Config::load(),
+                            Message::ConfigReloaded,
+                        ),
+                        Err(error) => {
+                            log::error!("failed to update autojoin: {error}");
+                            Task::none()
+                        }
+                    }),
+                    None,
+                );
+            }
+            buffer::Event::Ignore(mask) => {
+                self.ignores.retain(|ignore| ignore.mask != mask.mask);
+                self.ignores.push(mask);
+            }
+            buffer::Event::Unignore(pattern) => {
+                self.ignores.retain(|ignore| !ignore.is_pattern(&pattern));
+            }
+            buffer::Event::TestNotification(server, notification) => {
+                return (
+                    Task::none(),
+                    Some(Event::TestNotification(server, notification)),
+                );
+            }
+            buffer::Event::FileTransfer(id, response) => {
+                return (
+                    self.respond_to_file_transfer(id, response, config),
+                    None,
+                );
+            }
             buffer::Event::FilehostUpload {
                 server,
                 target,
@@ -2835,6 +3870,7 @@ impl Dashboard {
                 )
             }),
             LeftClick => self.refocus_pane(),
+            Activity => Task::none(),
             UpdatePrimaryClipboard => {
                 selectable_text::selected(|selected_text| {
                     Message::SelectedText(
@@ -2876,7 +3912,11 @@ impl Dashboard {
                 }
                 command_bar::Buffer::Close => {
                     let Focus { window, pane } = self.focus;
-                    (self.close_pane(clients, config, window, pane), None)
+                    self.request_close(
+                        clients,
+                        config,
+                        PendingClose::Pane(window, pane),
+                    )
                 }
                 command_bar::Buffer::Replace(buffer) => (
                     self.open_buffer(
@@ -2897,6 +3937,19 @@ impl Dashboard {
                     self.buffer_settings.show_muted = show_muted_buffers;
                     (Task::none(), None)
                 }
+                command_bar::Buffer::Equalize => {
+                    self.equalize_panes();
+                    (Task::none(), None)
+                }
+                command_bar::Buffer::Rotate => (self.rotate_panes(), None),
+                command_bar::Buffer::SwapPrevious => {
+                    self.swap_with_previous_pane();
+                    (Task::none(), None)
+                }
+                command_bar::Buffer::MoveToEdge(edge) => {
+                    self.move_pane_to_edge(edge);
+                    (Task::none(), None)
+                }
             },
             command_bar::Command::Configuration(command) => match command {
                 command_bar::Configuration::OpenConfigDirectory => {
@@ -2926,8 +3979,8 @@ impl Dashboard {
             },
             command_bar::Command::Theme(command) => match command {
                 command_bar::Theme::Switch(new) => {
-                    *theme = Theme::from(new);
-                    (Task::none(), None)
+                    *theme = Theme::from(new.clone());
+                    (Task::none(), Some(Event::ThemeSwitched(new)))
                 }
                 command_bar::Theme::OpenEditor => {
                     if let Some(editor) = &self.theme_editor {
@@ -2954,8 +4007,11 @@ impl Dashboard {
                 command_bar::Application::ToggleFullscreen => {
                     (window::toggle_fullscreen(), Some(Event::ToggleFullscreen))
                 }
+                command_bar::Application::Lock => {
+                    (Task::none(), Some(Event::Lock))
+                }
                 command_bar::Application::ToggleSidebarVisibility => {
-                    self.side_menu.toggle_visibility();
+                    self.side_menu.toggle_visibility(config);
                     (Task::none(), None)
                 }
             },
@@ -2976,6 +4032,26 @@ impl Dashboard {
                         .chain(self.request_server_icon(clients, &server)),
                     None,
                 ),
+                command_bar::Server::ReconnectAll => {
+                    for server in servers.keys() {
+                        controllers.reconnect(
+                            server,
+                            &config.buffer.commands.quit.default_reason,
+                        );
+                    }
+
+                    (Task::none(), None)
+                }
+                command_bar::Server::DisconnectAll => {
+                    for server in servers.keys() {
+                        clients.quit(
+                            server,
+                            config.buffer.commands.quit.default_reason.clone(),
+                        );
+                    }
+
+                    (Task::none(), None)
+                }
             },
         }
     }
@@ -3078,6 +4154,10 @@ impl Dashboard {
         // TODO(pounce) reduce clones
         let panes = self.panes.clone();
 
+        if let Some(upstream) = buffer.upstream() {
+            self.closed_buffers.retain(|closed| closed != upstream);
+        }
+
         self.last_changed = Some(Instant::now());
 
         match buffer.upstream() {
@@ -3115,6 +4195,10 @@ impl Dashboard {
                         clients,
                         config,
                     );
+                    remember_closed_buffer(
+                        &state.buffer,
+                        &mut self.closed_buffers,
+                    );
 
                     if let Some(buffer::Upstream::Channel(server, channel)) =
                         state.buffer.upstream()
@@ -3298,6 +4382,112 @@ impl Dashboard {
         Task::batch(tasks)
     }
 
+    /// Closes a pane or leaves a buffer, unless a `buffer.close.confirm` rule
+    /// asks the user about it first.
+    fn request_close(
+        &mut self,
+        clients: &mut data::client::Map,
+        config: &Config,
+        close: PendingClose,
+    ) -> (Task<Message>, Option<Event>) {
+        let Some(confirmation) = self.close_confirmation(config, &close) else {
+            return self.perform_close(clients, config, close);
+        };
+
+        let (buffer, window) = match &close {
+            PendingClose::Pane(window, pane) => (
+                self.panes
+                    .get(*window, *pane)
+                    .and_then(|state| state.buffer.upstream().cloned()),
+                *window,
+            ),
+            PendingClose::Leave(buffer) => {
+                (Some(buffer.clone()), self.focus.window)
+            }
+        };
+
+        let name = buffer.map_or_else(String::new, |buffer| {
+            buffer.target().map_or_else(
+                || buffer.server().to_string(),
+                |target| target.as_str().to_string(),
+            )
+        });
+
+        (
+            Task::none(),
+            Some(Event::ConfirmClose {
+                close,
+                confirmation,
+                name,
+                window,
+            }),
+        )
+    }
+
+    fn close_confirmation(
+        &self,
+        config: &Config,
+        close: &PendingClose,
+    ) -> Option<CloseConfirmation> {
+        let has_unread_query = |buffer: &buffer::Upstream| {
+            if let buffer::Upstream::Query(server, query) = buffer {
+                self.history.has_unread(&history::Kind::Query(
+                    server.clone(),
+                    query.clone(),
+                ))
+            } else {
+                false
+            }
+        };
+
+        let confirmation = match close {
+            PendingClose::Pane(window, pane) => {
+                let buffer =
+                    self.panes.get(*window, *pane)?.buffer.upstream()?;
+
+                if !self.history.input(buffer).draft_message.is_empty() {
+                    Some(CloseConfirmation::PaneWithDraft)
+                } else if config.buffer.close.query.close()
+                    && has_unread_query(buffer)
+                {
+                    Some(CloseConfirmation::QueryWithUnread)
+                } else {
+                    None
+                }
+            }
+            PendingClose::Leave(buffer::Upstream::Channel(..)) => {
+                Some(CloseConfirmation::LeaveChannel)
+            }
+            PendingClose::Leave(buffer) => has_unread_query(buffer)
+                .then_some(CloseConfirmation::QueryWithUnread),
+        }?;
+
+        (config.buffer.close.confirm.asks(confirmation)
+            && !self.dismissed_close_confirmations.contains(&confirmation))
+        .then_some(confirmation)
+    }
+
+    fn perform_close(
+        &mut self,
+        clients: &mut data::client::Map,
+        config: &Config,
+        close: PendingClose,
+    ) -> (Task<Message>, Option<Event>) {
+        match close {
+            PendingClose::Pane(window, pane) => {
+                // The pane may have closed while the user was asked
+                if self.panes.get(window, pane).is_none() {
+                    return (Task::none(), None);
+                }
+
+                (self.close_pane(clients, config, window, pane), None)
+            }
+            PendingClose::Leave(buffer) => {
+                self.leave_buffer(clients, config, buffer)
+            }
+        }
+    }
+
     pub fn leave_buffer(
         &mut self,
         clients: &mut data::client::Map,
@@ -3307,11 +4497,15 @@ impl Dashboard {
         let open = self.panes.iter().find_map(|(window, pane, state)| {
             (state.buffer.upstream() == Some(&buffer)).then_some((window, pane))
         });
+        let archive = matches!(buffer, buffer::Upstream::Channel(_, _))
+            && config.buffer.channel.archive_on_leave;
 
         let mut tasks = vec![];
 
-        // Close pane
-        if let Some((window, pane)) = open {
+        // Close pane, unless the channel is kept archived
+        if let Some((window, pane)) = open
+            && !archive
+        {
             tasks.push(self.close_pane(clients, config, window, pane));
         }
 
@@ -3335,13 +4529,23 @@ impl Dashboard {
                     clients.send(&buffer, encoded, TokenPriority::High);
                 }
 
-                tasks.push(
-                    self.history
-                        .close(history::Kind::Channel(server, channel), clients)
-                        .map_or_else(Task::none, |task| {
-                            Task::perform(task, Message::History)
-                        }),
-                );
+                if archive {
+                    self.archive(buffer);
+                }
+
+                // History of an archived channel stays open in its pane
+                if !archive || open.is_none() {
+                    tasks.push(
+                        self.history
+                            .close(
+                                history::Kind::Channel(server, channel),
+                                clients,
+                            )
+                            .map_or_else(Task::none, |task| {
+                                Task::perform(task, Message::History)
+                            }),
+                    );
+                }
 
                 (Task::batch(tasks), None)
             }
@@ -3380,11 +4584,15 @@ impl Dashboard {
                 && state.buffer.target() == Some(target.clone()))
             .then_some((window, pane))
         });
+        let archive = matches!(target, Target::Channel(_))
+            && config.buffer.channel.archive_on_leave;
 
         let mut tasks = vec![];
 
-        // Close pane
-        if let Some((window, pane)) = open {
+        // Close pane, unless the channel is kept archived
+        if let Some((window, pane)) = open
+            && !archive
+        {
             tasks.push(self.close_pane(clients, config, window, pane));
         }
 
@@ -3403,13 +4611,23 @@ impl Dashboard {
                     clients.send(&buffer, encoded, TokenPriority::User);
                 }
 
-                tasks.push(
-                    self.history
-                        .close(history::Kind::Channel(server, channel), clients)
-                        .map_or_else(Task::none, |task| {
-                            Task::perform(task, Message::History)
-                        }),
-                );
+                if archive {
+                    self.archive(buffer);
+                }
+
+                // History of an archived channel stays open in its pane
+                if !archive || open.is_none() {
+                    tasks.push(
+                        self.history
+                            .close(
+                                history::Kind::Channel(server, channel),
+                                clients,
+                            )
+                            .map_or_else(Task::none, |task| {
+                                Task::perform(task, Message::History)
+                            }),
+                    );
+                }
 
                 Task::batch(tasks)
             }
@@ -3428,6 +4646,32 @@ impl Dashboard {
         }
     }
 
+    fn archive(&mut self, buffer: buffer::Upstream) {
+        self.archived_buffers.insert(buffer);
+        self.last_changed = Some(Instant::now());
+    }
+
+    /// Stops showing a channel as archived, e.g. once it is joined again.
+    pub fn unarchive(&mut self, buffer: &buffer::Upstream) {
+        if self.archived_buffers.remove(buffer) {
+            self.last_changed = Some(Instant::now());
+        }
+    }
+
+    /// An archived channel we haven't joined since.
+    pub fn is_archived(
+        &self,
+        clients: &data::client::Map,
+        buffer: &buffer::Upstream,
+    ) -> bool {
+        self.archived_buffers.contains(buffer)
+            && buffer.channel().is_none_or(|channel| {
+                !clients
+                    .get_channels(buffer.server())
+                    .any(|joined| joined == channel)
+            })
+    }
+
     pub fn record_message(
         &mut self,
         server: &Server,
@@ -3568,6 +4812,91 @@ impl Dashboard {
         Task::none()
     }
 
+    /// Sends a message on behalf of a plugin, recording it like one typed
+    /// into the target's buffer.
+    pub fn send_plugin_message(
+        &mut self,
+        clients: &mut client::Map,
+        server: &Server,
+        target: &str,
+        text: String,
+        config: &Config,
+    ) -> Task<Message> {
+        let Some(user) = clients
+            .nickname(server)
+            .map(|nick| User::from(nick.to_owned()))
+        else {
+            return Task::none();
+        };
+
+        let chantypes = clients.get_server_chantypes_or_default(server);
+        let statusmsg = clients.get_server_statusmsg_or_default(server);
+        let casemapping = clients.get_server_casemapping_or_default(server);
+        let supports_echoes = clients.get_server_supports_echoes(server);
+
+        let buffer =
+            match Target::parse(target, chantypes, statusmsg, casemapping) {
+                Target::Channel(channel) => {
+                    buffer::Upstream::Channel(server.clone(), channel)
+                }
+                Target::Query(query) => {
+                    buffer::Upstream::Query(server.clone(), query)
+                }
+            };
+
+        let input = data::Input::from_command(
+            buffer.clone(),
+            command::Irc::Msg(target.to_string(), text),
+        );
+
+        let Some(encoded) = input.encoded() else {
+            return Task::none();
+        };
+
+        let labeled_response_context =
+            clients.send(&buffer, encoded, TokenPriority::User);
+
+        let (user, channel_users) =
+            if let buffer::Upstream::Channel(server, channel) = &buffer {
+                (
+                    clients
+                        .resolve_user_attributes(server, channel, &user)
+                        .cloned()
+                        .unwrap_or(user),
+                    clients.get_channel_users(server, channel),
+                )
+            } else {
+                (user, None)
+            };
+
+        let Some(messages) = input.messages(
+            user,
+            channel_users,
+            server,
+            chantypes,
+            statusmsg,
+            casemapping,
+            supports_echoes,
+            self.history.get_reroute_rules(),
+        ) else {
+            return Task::none();
+        };
+
+        let mut tasks = vec![];
+
+        for message in messages {
+            tasks.extend(self.history.record_input_message(
+                message,
+                labeled_response_context.clone(),
+                server,
+                casemapping,
+                config,
+            ));
+        }
+
+        Task::batch(tasks.into_iter().map(Task::future)).map(Message::History)
+    }
+
     pub fn record_reaction(
         &mut self,
         server: &Server,
@@ -3691,6 +5020,24 @@ impl Dashboard {
         }
     }
 
+    /// Whether the same highlight was just relayed into another channel.
+    pub fn is_duplicate_highlight(
+        &mut self,
+        highlight: &message::Highlight,
+        config: &config::Highlights,
+    ) -> bool {
+        config.deduplicate.enabled
+            && self.recent_highlights.is_duplicate(
+                highlight.user.nickname().as_normalized_str(),
+                &highlight.message.text(),
+                highlight.message.server_time,
+                TimeDelta::seconds(
+                    i64::try_from(config.deduplicate.window)
+                        .unwrap_or(i64::MAX),
+                ),
+            )
+    }
+
     pub fn record_highlight(
         &mut self,
         message: data::Message,
@@ -3937,6 +5284,16 @@ impl Dashboard {
         }
     }
 
+    /// Lists a query returned by `CHATHISTORY TARGETS` in the sidebar.
+    pub fn discover_chathistory_target(
+        &mut self,
+        server: Server,
+        target: Target,
+    ) {
+        self.history
+            .discover(&history::Kind::from_target(server, target));
+    }
+
     pub fn load_chathistory_targets_timestamp(
         &self,
         clients: &data::client::Map,
@@ -4040,48 +5397,297 @@ impl Dashboard {
 
                 self.focus_history.truncate(FOCUS_HISTORY_LEN);
 
-                if self.is_pane_maximized() {
-                    self.panes.main.restore();
-                    self.panes.main.maximize(pane);
+                if self.is_pane_maximized() {
+                    self.panes.main.restore();
+                    self.panes.main.maximize(pane);
+                }
+            }
+        }
+
+        self.refocus_pane()
+    }
+
+    fn mark_as_read_on_focus(
+        &mut self,
+        now: Instant,
+        clients: &mut data::client::Map,
+        config: &Config,
+    ) {
+        let Some((kind, is_scrolled_to_bottom)) =
+            self.get_focused().and_then(|(_, _, state)| {
+                state
+                    .buffer
+                    .data()
+                    .and_then(history::Kind::from_buffer)
+                    .map(|kind| (kind, state.buffer.is_scrolled_to_bottom()))
+            })
+        else {
+            self.focused_buffer = None;
+            return;
+        };
+
+        if self
+            .focused_buffer
+            .as_ref()
+            .is_none_or(|focused_buffer| focused_buffer.kind != kind)
+        {
+            self.focused_buffer = Some(FocusedBuffer {
+                kind: kind.clone(),
+                since: now,
+                marked_as_read: false,
+            });
+        }
+
+        let Some(focused_buffer) = self.focused_buffer.as_mut() else {
+            return;
+        };
+
+        // Only mark as read once per focus, so messages arriving while the
+        // buffer stays focused are left to `on_message`
+        if focused_buffer.marked_as_read
+            || !config.buffer.mark_as_read.on_focus.mark_as_read(
+                now.duration_since(focused_buffer.since),
+                is_scrolled_to_bottom,
+            )
+        {
+            return;
+        }
+
+        focused_buffer.marked_as_read = true;
+
+        if self.history.can_mark_as_read(&kind) {
+            mark_as_read(kind, &mut self.history, clients, TokenPriority::High);
+        }
+    }
+
+    fn connect_on_demand(
+        &mut self,
+        clients: &data::client::Map,
+        controllers: &mut stream::Map,
+        servers: &server::Map,
+    ) {
+        let Some(server) = self
+            .get_focused()
+            .and_then(|(_, _, state)| state.buffer.server())
+        else {
+            return;
+        };
+
+        // Only connect on the first focus, so a server the user has since
+        // disconnected from isn't reconnected just by looking at it
+        if self.connected_on_demand.contains(&server)
+            || !servers
+                .get(&server)
+                .is_some_and(|config| config.connect_on_demand)
+            || !matches!(
+                clients.state(&server),
+                Some(data::client::State::Disconnected {
+                    connecting: false,
+                    ..
+                })
+            )
+        {
+            return;
+        }
+
+        self.connected_on_demand.insert(server.clone());
+
+        connect_server(server, controllers, servers);
+    }
+
+    fn focus_first_pane(&mut self, window: window::Id) -> Task<Message> {
+        let pane = self
+            .panes
+            .iter()
+            .find_map(|(w, pane, _)| (w == window).then_some(pane));
+
+        pane.map_or(Task::none(), |pane| self.focus_pane(window, pane))
+    }
+
+    pub fn focus_window_pane(&mut self, window: window::Id) -> Task<Message> {
+        if self.focus.window == window {
+            Task::none()
+        } else if let Some(pane) = self.focus_history.front()
+            && window == self.main_window()
+        {
+            self.focus_pane(window, *pane)
+        } else {
+            self.focus_first_pane(window)
+        }
+    }
+
+    pub fn focus_window(&mut self, window: window::Id) -> Task<Message> {
+        let task = self.focus_window_pane(window);
+
+        window::gain_focus(window).chain(task)
+    }
+
+    fn maximize_pane(&mut self) {
+        if self.is_pane_maximized() {
+            self.panes.main.restore();
+        } else if self.focus.window == self.main_window() {
+            self.panes.main.maximize(self.focus.pane);
+        }
+    }
+
+    /// Resizes every split of the main window so that panes share the space
+    /// along each axis evenly.
+    fn equalize_panes(&mut self) {
+        use pane_grid::Node;
+
+        // Number of panes side by side along `axis`
+        fn weight(node: &Node, axis: pane_grid::Axis) -> usize {
+            match node {
+                Node::Split {
+                    axis: split_axis,
+                    a,
+                    b,
+                    ..
+                } => {
+                    if *split_axis == axis {
+                        weight(a, axis) + weight(b, axis)
+                    } else {
+                        weight(a, axis).max(weight(b, axis))
+                    }
+                }
+                Node::Pane(_) => 1,
+            }
+        }
+
+        fn ratios(node: &Node, splits: &mut Vec<(pane_grid::Split, f32)>) {
+            if let Node::Split { id, axis, a, b, .. } = node {
+                let a_weight = weight(a, *axis);
+                let b_weight = weight(b, *axis);
+
+                splits.push((
+                    *id,
+                    a_weight as f32 / (a_weight + b_weight) as f32,
+                ));
+
+                ratios(a, splits);
+                ratios(b, splits);
+            }
+        }
+
+        let mut splits = vec![];
+        ratios(self.panes.main.layout(), &mut splits);
+
+        for (split, ratio) in splits {
+            self.panes.main.resize(split, ratio);
+        }
+
+        self.last_changed = Some(Instant::now());
+    }
+
+    /// Rotates the layout of the main window by 90°, turning horizontal splits
+    /// into vertical ones and vice versa.
+    fn rotate_panes(&mut self) -> Task<Message> {
+        use pane_grid::{Axis, Configuration, Node};
+
+        fn rotate(
+            node: &Node,
+            panes: &pane_grid::State<Pane>,
+            order: &mut Vec<pane_grid::Pane>,
+        ) -> Configuration<Pane> {
+            match node {
+                Node::Split {
+                    axis, ratio, a, b, ..
+                } => Configuration::Split {
+                    axis: match axis {
+                        Axis::Horizontal => Axis::Vertical,
+                        Axis::Vertical => Axis::Horizontal,
+                    },
+                    ratio: *ratio,
+                    a: Box::new(rotate(a, panes, order)),
+                    b: Box::new(rotate(b, panes, order)),
+                },
+                Node::Pane(pane) => {
+                    order.push(*pane);
+
+                    Configuration::Pane(
+                        panes
+                            .get(*pane)
+                            .cloned()
+                            .unwrap_or_else(|| Pane::new(Buffer::empty())),
+                    )
+                }
+            }
+        }
+
+        fn leaves(node: &Node, order: &mut Vec<pane_grid::Pane>) {
+            match node {
+                Node::Split { a, b, .. } => {
+                    leaves(a, order);
+                    leaves(b, order);
                 }
+                Node::Pane(pane) => order.push(*pane),
             }
         }
 
-        self.refocus_pane()
-    }
+        if self.panes.main.len() < 2 {
+            return Task::none();
+        }
 
-    fn focus_first_pane(&mut self, window: window::Id) -> Task<Message> {
-        let pane = self
-            .panes
+        let mut old_order = vec![];
+        let configuration =
+            rotate(self.panes.main.layout(), &self.panes.main, &mut old_order);
+
+        let rotated = pane_grid::State::with_configuration(configuration);
+
+        // Panes get new ids when the grid is rebuilt, and are laid out in the
+        // same order as before
+        let mut new_order = vec![];
+        leaves(rotated.layout(), &mut new_order);
+
+        let ids: HashMap<_, _> = old_order.into_iter().zip(new_order).collect();
+
+        self.panes.main = rotated;
+        self.focus_history = self
+            .focus_history
             .iter()
-            .find_map(|(w, pane, _)| (w == window).then_some(pane));
+            .filter_map(|pane| ids.get(pane).copied())
+            .collect();
 
-        pane.map_or(Task::none(), |pane| self.focus_pane(window, pane))
-    }
+        self.last_changed = Some(Instant::now());
 
-    pub fn focus_window_pane(&mut self, window: window::Id) -> Task<Message> {
-        if self.focus.window == window {
-            Task::none()
-        } else if let Some(pane) = self.focus_history.front()
-            && window == self.main_window()
+        let Focus { window, pane } = self.focus;
+
+        if window == self.main_window()
+            && let Some(pane) = ids.get(&pane).copied()
         {
-            self.focus_pane(window, *pane)
+            self.focus = Focus { window, pane };
+            self.refocus_pane()
         } else {
-            self.focus_first_pane(window)
+            Task::none()
         }
     }
 
-    pub fn focus_window(&mut self, window: window::Id) -> Task<Message> {
-        let task = self.focus_window_pane(window);
+    /// Swaps the focused pane of the main window with the one focused before
+    /// it.
+    fn swap_with_previous_pane(&mut self) {
+        let Focus { window, pane } = self.focus;
 
-        window::gain_focus(window).chain(task)
+        if window != self.main_window() {
+            return;
+        }
+
+        if let Some(previous) = self
+            .focus_history
+            .iter()
+            .copied()
+            .find(|p| *p != pane && self.panes.main.get(*p).is_some())
+        {
+            self.panes.main.swap(pane, previous);
+            self.last_changed = Some(Instant::now());
+        }
     }
 
-    fn maximize_pane(&mut self) {
-        if self.is_pane_maximized() {
-            self.panes.main.restore();
-        } else if self.focus.window == self.main_window() {
-            self.panes.main.maximize(self.focus.pane);
+    fn move_pane_to_edge(&mut self, edge: pane_grid::Edge) {
+        let Focus { window, pane } = self.focus;
+
+        if window == self.main_window() {
+            self.panes.main.move_to_edge(pane, edge);
+            self.last_changed = Some(Instant::now());
         }
     }
 
@@ -4162,6 +5768,7 @@ impl Dashboard {
                 clients,
                 config,
             );
+            remember_closed_buffer(&state.buffer, &mut self.closed_buffers);
 
             if let Some(buffer::Upstream::Channel(server, channel)) =
                 state.buffer.upstream()
@@ -4321,13 +5928,24 @@ impl Dashboard {
     pub fn tick(
         &mut self,
         now: Instant,
-        clients: &data::client::Map,
+        clients: &mut data::client::Map,
+        controllers: &mut stream::Map,
+        servers: &server::Map,
         config: &Config,
+        notifications: &mut Notifications,
     ) -> Task<Message> {
+        self.mark_as_read_on_focus(now, clients, config);
+        self.connect_on_demand(clients, controllers, servers);
+        self.process_timed_bans(clients, config, notifications);
+
         if !self.has_typing_activity(clients) {
             self.typing_animation = None;
         }
 
+        let utc_now = Utc::now();
+        self.snoozed_notifications
+            .retain(|_, until| *until > utc_now);
+
         let history_ticks = Task::batch(
             self.history
                 .tick(now.into(), clients)
@@ -4342,7 +5960,7 @@ impl Dashboard {
             .persist
             .then(|| {
                 self.history
-                    .maybe_save_drafts(now.into())
+                    .maybe_save_input(now.into())
                     .map(|fut| Task::perform(fut, Message::History))
             })
             .flatten();
@@ -4373,6 +5991,40 @@ impl Dashboard {
         )
     }
 
+    /// Lifts expired timed bans, or reminds the user of them when the unban
+    /// can't be sent (e.g. the server is disconnected).
+    fn process_timed_bans(
+        &mut self,
+        clients: &mut data::client::Map,
+        config: &Config,
+        notifications: &mut Notifications,
+    ) {
+        let due = timed_ban::process(&mut self.timed_bans, Utc::now(), |ban| {
+            clients
+                .get_channel_users(&ban.server, &ban.channel)
+                .is_some()
+        });
+
+        for ban in &due.unban {
+            send_unban(clients, ban);
+        }
+
+        for ban in &due.remind {
+            notifications.notify(
+                config,
+                &Notification::UnbanReminder {
+                    channel: ban.channel.clone(),
+                    mask: ban.mask.clone(),
+                },
+                &ban.server,
+            );
+        }
+
+        if !due.is_empty() {
+            self.last_changed = Some(Instant::now());
+        }
+    }
+
     pub fn animation_tick(
         &mut self,
         now: Instant,
@@ -4480,17 +6132,18 @@ impl Dashboard {
 
         let event = self.file_transfers.receive(request.clone(), config)?;
 
+        let file_transfer::manager::Event::NewTransfer(ref transfer, _) = event;
+
         notifications.notify(
             config,
             &Notification::FileTransferRequest {
                 nick: request.from.nickname().to_owned(),
                 casemapping,
-                filename: match event {
-                    file_transfer::manager::Event::NewTransfer(
-                        ref transfer,
-                        _,
-                    ) => transfer.filename.clone(),
-                },
+                filename: transfer.filename.clone(),
+                awaiting_approval: self
+                    .file_transfers
+                    .is_pending_approval(&transfer.id)
+                    .then_some(transfer.id),
             },
             server,
         );
@@ -4507,6 +6160,57 @@ impl Dashboard {
         Some(task)
     }
 
+    pub fn resume_file_transfer(
+        &mut self,
+        request: file_transfer::ResumeRequest,
+        config: &Config,
+    ) {
+        if config.file_transfer.enabled {
+            self.file_transfers.handle_resume(request);
+        }
+    }
+
+    fn respond_to_file_transfer(
+        &mut self,
+        id: file_transfer::Id,
+        response: buffer::file_transfers::Response,
+        config: &Config,
+    ) -> Task<Message> {
+        use buffer::file_transfers::Response;
+
+        if !self.file_transfers.is_pending_approval(&id) {
+            return Task::none();
+        }
+
+        let Some(transfer) = self.file_transfers.get(&id) else {
+            return Task::none();
+        };
+
+        match response {
+            Response::Decline => {
+                self.file_transfers.remove(&id, config);
+
+                Task::none()
+            }
+            Response::Accept | Response::AlwaysAccept => {
+                let task =
+                    buffer::file_transfers::select_save_path(transfer, config)
+                        .map(move |path| {
+                            Message::FileTransferSavePathSelected(id, path)
+                        });
+
+                if response == Response::AlwaysAccept {
+                    let nickname = transfer.remote_user.nickname().to_owned();
+
+                    self.file_transfers.trust(nickname.as_nickref());
+                    self.last_changed = Some(Instant::now());
+                }
+
+                task
+            }
+        }
+    }
+
     pub fn handle_file_transfer_event(
         &mut self,
         server: &Server,
@@ -4603,6 +6307,10 @@ impl Dashboard {
         let mut history = history::Manager::default();
         if config.buffer.text_input.persist {
             history.preload_drafts(data::input::load_drafts_sync());
+            history.preload_input_history(
+                data::input::load_sent_sync(),
+                config.buffer.text_input.history_length,
+            );
         }
 
         let panes = Panes {
@@ -4647,14 +6355,40 @@ impl Dashboard {
             file_transfers: file_transfer::Manager::default(),
             theme_editor: None,
             previews: preview::Collection::default(),
+            requested_previews: HashSet::new(),
             previews_cache: Arc::new(preview_cache(&config.preview)),
             server_icons: server_icon::Manager::default(),
             typing_animation: None,
             http_client: http_client_from_config(config).map(Arc::new),
+            preview_http_client: preview_http_client_from_config(config)
+                .map(Arc::new),
             buffer_settings: data.buffer_settings.clone(),
+            audio_muted: data.audio_muted,
+            timed_bans: data.timed_bans,
+            snoozed_notifications: HashMap::new(),
+            focused_buffer: None,
+            connected_on_demand: HashSet::new(),
+            collapsed_servers: data.collapsed_servers.into_iter().collect(),
+            pinned_buffers: data.pinned_buffers.into_iter().collect(),
+            muted_buffers: data.muted_buffers.into_iter().collect(),
+            archived_buffers: data.archived_buffers.into_iter().collect(),
+            collapsed_channel_groups: data
+                .collapsed_channel_groups
+                .into_iter()
+                .collect(),
+            sidebar_width: data.sidebar_width,
+            recent_emojis: data.recent_emojis,
+            ignores: data.ignores,
+            dismissed_close_confirmations: data.dismissed_close_confirmations,
+            recent_highlights: message::highlight::Recent::default(),
+            closed_buffers: VecDeque::new(),
             filehost: filehost::Manager::new(),
         };
 
+        dashboard
+            .file_transfers
+            .set_trusted(data.trusted_file_transfer_nicks);
+
         let mut tasks = vec![sidebar_task.map(Message::Sidebar)];
 
         for pane in data.popout_panes {
@@ -4688,17 +6422,142 @@ impl Dashboard {
         &self.history
     }
 
+    pub fn audio_muted(&self) -> bool {
+        self.audio_muted
+    }
+
+    pub fn muted_buffers(&self) -> &HashSet<buffer::Upstream> {
+        &self.muted_buffers
+    }
+
+    /// Channels and queries shown in the main window, most recently focused
+    /// first.
+    pub fn recent_buffers(&self) -> Vec<buffer::Upstream> {
+        let main_window = self.main_window();
+        let mut recent: Vec<buffer::Upstream> = vec![];
+
+        for state in self
+            .focus_history
+            .iter()
+            .filter_map(|pane| self.panes.get(main_window, *pane))
+            .chain(self.panes.iter().map(|(_, _, state)| state))
+        {
+            if let Some(upstream) = state.buffer.upstream()
+                && upstream.target().is_some()
+                && !recent.contains(upstream)
+            {
+                recent.push(upstream.clone());
+            }
+        }
+
+        recent
+    }
+
+    /// Combined progress of the running file transfers, if any.
+    pub fn file_transfer_progress(&self) -> Option<f64> {
+        let (transferred, size) = self
+            .file_transfers
+            .list()
+            .filter_map(|file_transfer| match file_transfer.status {
+                file_transfer::Status::Active { transferred, .. } => {
+                    Some((transferred, file_transfer.size))
+                }
+                _ => None,
+            })
+            .reduce(|(a, a_size), (b, b_size)| (a + b, a_size + b_size))?;
+
+        Some(if size == 0 {
+            0.0
+        } else {
+            transferred as f64 / size as f64
+        })
+    }
+
+    pub fn has_unread_highlight(&self) -> bool {
+        self.history.highlighted().any(|kind| {
+            let upstream = match kind {
+                history::Kind::Channel(server, channel) => {
+                    buffer::Upstream::Channel(server.clone(), channel.clone())
+                }
+                history::Kind::Query(server, query) => {
+                    buffer::Upstream::Query(server.clone(), query.clone())
+                }
+                _ => return false,
+            };
+
+            !self.muted_buffers.contains(&upstream)
+        })
+    }
+
+    pub fn snoozed_notifications(
+        &self,
+    ) -> &HashMap<data::Buffer, DateTime<Utc>> {
+        &self.snoozed_notifications
+    }
+
+    fn toggle_audio_mute(&mut self) {
+        self.audio_muted = !self.audio_muted;
+        self.last_changed = Some(Instant::now());
+    }
+
     pub fn get_filters(&mut self) -> &mut Vec<Filter> {
         self.history.get_filters()
     }
 
-    pub fn handle_file_drop(&mut self, path: PathBuf) -> Task<Message> {
+    /// Whether a message from the server is from a user ignored with
+    /// `/ignore` or in `ignore.masks`.
+    pub fn is_ignored(
+        &self,
+        encoded: &message::Encoded,
+        casemapping: isupport::CaseMap,
+        config: &Config,
+    ) -> bool {
+        ignore::is_ignored(
+            config.ignore.masks.iter().chain(&self.ignores),
+            encoded,
+            casemapping,
+        )
+    }
+
+    pub fn handle_file_drop(
+        &mut self,
+        path: PathBuf,
+        config: &Config,
+    ) -> Task<Message> {
         let Focus { window, pane } = self.focus;
 
-        let Some(pane_state) = self.panes.get(window, pane) else {
+        let Some(pane_state) = self.panes.get_mut(window, pane) else {
             return Task::none();
         };
 
+        if !config.filehost.file_drop() {
+            if !config.file_transfer.enabled || !config.file_transfer.file_drop
+            {
+                return Task::none();
+            }
+
+            return match &pane_state.buffer {
+                Buffer::Query(state) => Task::done(Message::SendFilesSelected(
+                    state.server.clone(),
+                    User::from(Nick::from(&state.target)),
+                    Some(vec![path]),
+                )),
+                Buffer::Channel(state) => {
+                    let modal =
+                        modal::Modal::SendFile(send_file_modal::State::new(
+                            state.server.clone(),
+                            state.target.clone(),
+                            path,
+                        ));
+
+                    pane_state
+                        .open_modal(pane, modal)
+                        .map(move |message| Message::Pane(window, message))
+                }
+                _ => Task::none(),
+            };
+        }
+
         let msg = match &pane_state.buffer {
             Buffer::Channel(_) => Some(buffer::Message::Channel(
                 buffer::channel::Message::FilesDropped(vec![path]),
@@ -4737,10 +6596,10 @@ impl Dashboard {
             notification::Event::NotificationResponse { action, buffer } => {
                 // When an notification action is performed in Wayland the
                 // application is not automatically brought forward.  Since
-                // there is currently no interface to ensure the application is
-                // activated with non-dismissal notification interactions,
-                // request attention in order to do so (should be a noop in
-                // other environments).
+                // the activation token from the notification isn't passed on
+                // to the window, request attention in order to do so, then
+                // ask for focus (which raises the window on X11 and Windows,
+                // and is typically declined by Wayland compositors).
 
                 let window_id = if let Some(buffer) = &buffer
                     && let Some((window, _, _)) =
@@ -4751,13 +6610,32 @@ impl Dashboard {
                     self.focus.window
                 };
 
-                let activate_application = iced::window::request_user_attention(
-                    window_id,
-                    Some(iced::window::UserAttention::Informational),
-                );
+                let activate_application =
+                    iced::window::request_user_attention(
+                        window_id,
+                        Some(iced::window::UserAttention::Informational),
+                    )
+                    .chain(window::gain_focus(window_id));
 
                 match action {
-                    toast::Action::Dismiss => Task::none(),
+                    toast::Action::Dismiss | toast::Action::FileTransfer(_) => {
+                        Task::none()
+                    }
+                    toast::Action::Reply(text) => {
+                        if let Some(data::Buffer::Upstream(buffer)) = &buffer
+                            && let Some(target) = buffer.target()
+                        {
+                            self.send_plugin_message(
+                                clients,
+                                buffer.server(),
+                                target.as_str(),
+                                text,
+                                config,
+                            )
+                        } else {
+                            Task::none()
+                        }
+                    }
                     toast::Action::ActivateApplication => activate_application,
                     toast::Action::OpenBuffer => {
                         if let Some(buffer) = buffer {
@@ -4773,6 +6651,9 @@ impl Dashboard {
                     }
                 }
             }
+            notification::Event::FileTransferResponse { id, response } => {
+                self.respond_to_file_transfer(id, response, config)
+            }
             notification::Event::RequestAttention { buffer } => {
                 let window_id = if let Some(buffer) = buffer
                     && let Some((window, _, _)) =
@@ -4824,9 +6705,8 @@ impl Dashboard {
                 }
                 window::Event::FileDropped(path) => {
                     self.filehost.file_being_hovered = false;
-                    if config.filehost.file_drop() {
-                        return self.handle_file_drop(path);
-                    }
+
+                    return self.handle_file_drop(path, config);
                 }
             }
         } else if self.theme_editor.as_ref().is_some_and(|e| e.window == id) {
@@ -4898,11 +6778,14 @@ impl Dashboard {
         });
 
         let history = self.history.exit(clients);
+        let nick_histories = clients.save_nick_histories(None);
         let last_changed = self.last_changed.take();
         let dashboard = data::Dashboard::from(&*self);
 
         Task::perform(
             async move {
+                future::join_all(nick_histories).await;
+
                 if last_changed.is_some() {
                     match dashboard.save().await {
                         Ok(()) => {
@@ -5049,6 +6932,12 @@ impl Dashboard {
                 self.http_client.clone()
             };
 
+            // The preview proxy is only used where previews are fetched at
+            // all, so previews stay disabled behind Tor
+            let preview_client = preview_client.map(|preview_client| {
+                self.preview_http_client.clone().unwrap_or(preview_client)
+            });
+
             let Some(preview_client) = preview_client else {
                 return vec![];
             };
@@ -5065,6 +6954,9 @@ impl Dashboard {
                 ) && matches!(
                     config.image.visible(target_ref, server, casemapping),
                     config::preview::Visibility::None
+                ) && matches!(
+                    config.title.visible(target_ref, server, casemapping),
+                    config::preview::Visibility::None
                 ) {
                     return vec![];
                 }
@@ -5072,6 +6964,18 @@ impl Dashboard {
 
             pane.visible_urls()
                 .into_iter()
+                // Video files are linked to, but never downloaded
+                .filter(|url| !data::preview::is_video(url))
+                .filter(|url| {
+                    matches!(config.fetch, config::preview::Fetch::Automatic)
+                        || self.requested_previews.contains(url)
+                })
+                .take(
+                    config
+                        .request
+                        .max_per_buffer
+                        .map_or(usize::MAX, |max| max as usize),
+                )
                 .map(move |url| (url.clone(), preview_client.clone()))
                 .collect()
         };
@@ -5215,12 +7119,32 @@ fn mark_as_read_on_buffer_close(
     }
 }
 
+fn remember_closed_buffer(
+    buffer: &Buffer,
+    closed_buffers: &mut VecDeque<buffer::Upstream>,
+) {
+    if let Some(upstream) = buffer.upstream()
+        && upstream.target().is_some()
+    {
+        closed_buffers.retain(|closed| closed != upstream);
+        closed_buffers.push_front(upstream.clone());
+        closed_buffers.truncate(CLOSED_BUFFERS_LEN);
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Focus {
     pub window: window::Id,
     pub pane: pane_grid::Pane,
 }
 
+#[derive(Debug, Clone)]
+struct FocusedBuffer {
+    kind: history::Kind,
+    since: Instant,
+    marked_as_read: bool,
+}
+
 impl<'a> From<&'a Dashboard> for data::Dashboard {
     fn from(dashboard: &'a Dashboard) -> Self {
         use pane_grid::Node;
@@ -5267,6 +7191,36 @@ impl<'a> From<&'a Dashboard> for data::Dashboard {
                     .then_some(state.buffer.data())
                     .flatten()
             }),
+            audio_muted: dashboard.audio_muted,
+            timed_bans: dashboard.timed_bans.clone(),
+            trusted_file_transfer_nicks: dashboard
+                .file_transfers
+                .trusted()
+                .map(String::from)
+                .collect(),
+            collapsed_servers: dashboard
+                .collapsed_servers
+                .iter()
+                .cloned()
+                .collect(),
+            pinned_buffers: dashboard.pinned_buffers.iter().cloned().collect(),
+            muted_buffers: dashboard.muted_buffers.iter().cloned().collect(),
+            archived_buffers: dashboard
+                .archived_buffers
+                .iter()
+                .cloned()
+                .collect(),
+            collapsed_channel_groups: dashboard
+                .collapsed_channel_groups
+                .iter()
+                .cloned()
+                .collect(),
+            sidebar_width: dashboard.sidebar_width,
+            recent_emojis: dashboard.recent_emojis.clone(),
+            ignores: dashboard.ignores.clone(),
+            dismissed_close_confirmations: dashboard
+                .dismissed_close_confirmations
+                .clone(),
         }
     }
 }
@@ -5608,6 +7562,66 @@ fn cycle_previous_unread_buffer(
         .cloned()
 }
 
+fn pending_unbans(
+    timed_bans: &[timed_ban::TimedBan],
+    server: &Server,
+    channel: &target::Channel,
+) -> Vec<timed_ban::TimedBan> {
+    timed_bans
+        .iter()
+        .filter(|ban| ban.server == *server && ban.channel == *channel)
+        .cloned()
+        .collect()
+}
+
+fn send_unban(clients: &mut data::client::Map, ban: &timed_ban::TimedBan) {
+    send_channel_command(
+        clients,
+        &ban.server,
+        &ban.channel,
+        command::Irc::Mode(
+            ban.channel.to_string(),
+            Some("-b".to_string()),
+            Some(vec![ban.mask.clone()]),
+        ),
+    );
+}
+
+fn send_channel_command(
+    clients: &mut data::client::Map,
+    server: &Server,
+    channel: &target::Channel,
+    command: command::Irc,
+) {
+    let buffer = buffer::Upstream::Channel(server.clone(), channel.clone());
+    let input = data::Input::from_command(buffer, command);
+
+    if let Some(encoded) = input.encoded() {
+        clients.send(&input.buffer, encoded, TokenPriority::User);
+    }
+}
+
+/// Requests the modes, bans, and invite exceptions shown in the channel
+/// settings modal.
+fn request_channel_settings(
+    clients: &mut data::client::Map,
+    server: &Server,
+    channel: &target::Channel,
+) {
+    let lists = std::iter::once('b')
+        .chain(clients.get_server_invite_exception_mode(server))
+        .map(|mode| Some(format!("+{mode}")));
+
+    for modes in std::iter::once(None).chain(lists).collect::<Vec<_>>() {
+        send_channel_command(
+            clients,
+            server,
+            channel,
+            command::Irc::Mode(channel.to_string(), modes, None),
+        );
+    }
+}
+
 fn connect_server(
     server: Server,
     controllers: &mut stream::Map,
@@ -5639,6 +7653,21 @@ fn http_client_from_config(config: &Config) -> Option<reqwest::Client> {
     }
 }
 
+fn preview_http_client_from_config(config: &Config) -> Option<reqwest::Client> {
+    let proxy = config.preview.request.proxy.as_ref()?;
+
+    match config::proxy::build_client(Some(proxy), None) {
+        Ok(http_client) => Some(http_client),
+        Err(error) => {
+            log::warn!(
+                "Unable to build preview HTTP client, falling back to the default: {error}"
+            );
+
+            None
+        }
+    }
+}
+
 fn preview_cache(config: &config::Preview) -> cache::FileCache {
     let root = environment::cache_dir().join("previews");
 
@@ -5648,3 +7677,27 @@ fn preview_cache(config: &config::Preview) -> cache::FileCache {
         config.request.image_cache.trim_interval,
     )
 }
+
+async fn open_history_file(kind: history::Kind) {
+    match history::path(&kind).await {
+        Ok(path) if path.exists() => {
+            let _ = open_url::open(path);
+        }
+        // Not written until the buffer is first flushed
+        Ok(_) => open_history_folder(kind).await,
+        Err(error) => {
+            log::warn!("error opening log file for {kind}: {error}");
+        }
+    }
+}
+
+async fn open_history_folder(kind: history::Kind) {
+    match history::dir_path().await {
+        Ok(dir) => {
+            let _ = open_url::open(dir);
+        }
+        Err(error) => {
+            log::warn!("error opening data folder for {kind}: {error}");
+        }
+    }
+}
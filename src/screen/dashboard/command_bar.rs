@@ -1,6 +1,6 @@
 use data::{Config, buffer, client, server};
 use iced::Length;
-use iced::widget::{column, container, text};
+use iced::widget::{column, container, pane_grid, text};
 
 use super::Focus;
 use crate::widget::{Element, combo_box, double_pass, key_press};
@@ -151,6 +151,7 @@ pub enum Command {
 #[derive(Debug, Clone)]
 pub enum Application {
     Quit,
+    Lock,
     ToggleFullscreen,
     ToggleSidebarVisibility,
 }
@@ -165,6 +166,8 @@ pub enum Server {
     Connect(data::Server),
     Disconnect(data::Server),
     ReloadIcon(data::Server),
+    ReconnectAll,
+    DisconnectAll,
 }
 
 #[derive(Debug, Clone)]
@@ -177,6 +180,10 @@ pub enum Buffer {
     Popout,
     Merge,
     ShowMutedBuffers(bool),
+    Equalize,
+    Rotate,
+    SwapPrevious,
+    MoveToEdge(pane_grid::Edge),
 }
 
 #[derive(Debug, Clone)]
@@ -230,8 +237,9 @@ impl Command {
 
         let version = Version::list(version).into_iter().map(Command::Version);
 
-        let application =
-            Application::list().into_iter().map(Command::Application);
+        let application = Application::list(config)
+            .into_iter()
+            .map(Command::Application);
 
         version
             .chain(application)
@@ -278,6 +286,17 @@ impl Server {
             }
         }
 
+        if servers.keys().next().is_some() {
+            list.push(Server::ReconnectAll);
+        }
+
+        if servers
+            .keys()
+            .any(|server| clients.get_server_is_connected(server))
+        {
+            list.push(Server::DisconnectAll);
+        }
+
         list
     }
 }
@@ -290,6 +309,8 @@ impl std::fmt::Display for Server {
             Server::ReloadIcon(server) => {
                 write!(f, "Reload {server} server icon")
             }
+            Server::ReconnectAll => write!(f, "Reconnect all servers"),
+            Server::DisconnectAll => write!(f, "Disconnect from all servers"),
         }
     }
 }
@@ -320,6 +341,15 @@ impl Buffer {
 
         if focus.window == main_window {
             list.push(Buffer::Popout);
+            list.extend([
+                Buffer::Equalize,
+                Buffer::Rotate,
+                Buffer::SwapPrevious,
+                Buffer::MoveToEdge(pane_grid::Edge::Top),
+                Buffer::MoveToEdge(pane_grid::Edge::Bottom),
+                Buffer::MoveToEdge(pane_grid::Edge::Left),
+                Buffer::MoveToEdge(pane_grid::Edge::Right),
+            ]);
         } else {
             list.push(Buffer::Merge);
         }
@@ -338,12 +368,19 @@ impl Buffer {
 }
 
 impl Application {
-    fn list() -> Vec<Self> {
-        vec![
-            Application::Quit,
+    fn list(config: &Config) -> Vec<Self> {
+        let mut list = vec![Application::Quit];
+
+        if config.lock.is_enabled() {
+            list.push(Application::Lock);
+        }
+
+        list.extend([
             Application::ToggleFullscreen,
             Application::ToggleSidebarVisibility,
-        ]
+        ]);
+
+        list
     }
 }
 
@@ -380,6 +417,7 @@ impl std::fmt::Display for Application {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Application::Quit => write!(f, "Quit Halloy"),
+            Application::Lock => write!(f, "Lock Halloy"),
             Application::ToggleFullscreen => write!(f, "Fullscreen"),
             Application::ToggleSidebarVisibility => write!(f, "Toggle Sidebar"),
         }
@@ -436,6 +474,11 @@ impl std::fmt::Display for Buffer {
                     buffer::Internal::ConfigEditor => {
                         write!(f, "Open config editor")
                     }
+                    buffer::Internal::Diagnostics => {
+                        write!(f, "Open diagnostics")
+                    }
+                    buffer::Internal::Unread => write!(f, "Open unread"),
+                    buffer::Internal::Ignores => write!(f, "Open ignores"),
                 },
                 data::Buffer::Upstream(upstream) => match upstream {
                     buffer::Upstream::Server(server) => {
@@ -458,6 +501,21 @@ impl std::fmt::Display for Buffer {
                     "Hide muted buffers"
                 }
             ),
+            Buffer::Equalize => write!(f, "Equalize buffer sizes"),
+            Buffer::Rotate => write!(f, "Rotate buffer layout"),
+            Buffer::SwapPrevious => {
+                write!(f, "Swap with previously focused buffer")
+            }
+            Buffer::MoveToEdge(edge) => write!(
+                f,
+                "Move buffer to {}",
+                match edge {
+                    pane_grid::Edge::Top => "top",
+                    pane_grid::Edge::Bottom => "bottom",
+                    pane_grid::Edge::Left => "left",
+                    pane_grid::Edge::Right => "right",
+                }
+            ),
         }
     }
 }
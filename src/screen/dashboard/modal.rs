@@ -1,9 +1,20 @@
+pub mod channel_settings;
+pub mod emoji_picker;
+pub mod join_channel;
+pub mod open_target;
 pub mod reaction;
 pub mod redaction;
+pub mod send_file;
+pub mod timed_bans;
+pub mod user_list;
 
 use std::borrow::Cow;
+use std::path::PathBuf;
 
-use data::{Config, message};
+use data::command;
+use data::dashboard::BufferAction;
+use data::timed_ban::TimedBan;
+use data::{Config, Server, message, target};
 use iced::Task;
 
 use crate::widget::Element;
@@ -11,13 +22,27 @@ use crate::widget::Element;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Modal {
     AddReaction(reaction::State),
+    InsertEmoji(emoji_picker::State),
     RedactReason(redaction::State),
+    UserListDiff(user_list::State),
+    PendingUnbans(timed_bans::State),
+    ChannelSettings(channel_settings::State),
+    JoinChannel(join_channel::State),
+    OpenTarget(open_target::State),
+    SendFile(send_file::State),
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     Reaction(reaction::Message),
+    EmojiPicker(emoji_picker::Message),
     Redaction(redaction::Message),
+    UserList(user_list::Message),
+    TimedBans(timed_bans::Message),
+    ChannelSettings(channel_settings::Message),
+    JoinChannel(join_channel::Message),
+    OpenTarget(open_target::Message),
+    SendFile(send_file::Message),
 }
 
 #[derive(Debug, Clone)]
@@ -27,10 +52,18 @@ pub enum Event {
         text: Cow<'static, str>,
         unreact: bool,
     },
+    InsertEmoji(&'static str),
     RedactReason {
         msgid: message::Id,
         reason: String,
     },
+    Unban(TimedBan),
+    CancelUnban(TimedBan),
+    ChannelCommand(Server, target::Channel, command::Irc),
+    JoinChannel(Server, target::Channel, BufferAction),
+    OpenTarget(open_target::Kind, Server, String),
+    SendFile(Server, String, PathBuf),
+    Close,
 }
 
 impl Modal {
@@ -49,30 +82,144 @@ impl Modal {
                     },
                 )
             }
+            (Modal::InsertEmoji(state), Message::EmojiPicker(message)) => state
+                .update(message)
+                .map(|emoji_picker::Event::Insert(emoji)| {
+                    Event::InsertEmoji(emoji)
+                }),
             (Modal::RedactReason(state), Message::Redaction(reason)) => state
                 .update(reason)
                 .map(|redaction::Event::RedactReason { msgid, reason }| {
                     Event::RedactReason { msgid, reason }
                 }),
+            (Modal::UserListDiff(state), Message::UserList(message)) => state
+                .update(message)
+                .map(|user_list::Event::Close| Event::Close),
+            (Modal::PendingUnbans(state), Message::TimedBans(message)) => {
+                state.update(message).map(|event| match event {
+                    timed_bans::Event::Unban(ban) => Event::Unban(ban),
+                    timed_bans::Event::Cancel(ban) => Event::CancelUnban(ban),
+                    timed_bans::Event::Close => Event::Close,
+                })
+            }
+            (
+                Modal::ChannelSettings(state),
+                Message::ChannelSettings(message),
+            ) => {
+                let server = state.server().clone();
+                let channel = state.channel().clone();
+
+                state.update(message).map(|event| match event {
+                    channel_settings::Event::SetTopic(topic) => {
+                        Event::ChannelCommand(
+                            server,
+                            channel.clone(),
+                            command::Irc::Topic(
+                                channel.to_string(),
+                                Some(topic),
+                            ),
+                        )
+                    }
+                    channel_settings::Event::SetModes(modes, args) => {
+                        Event::ChannelCommand(
+                            server,
+                            channel.clone(),
+                            command::Irc::Mode(
+                                channel.to_string(),
+                                Some(modes),
+                                (!args.is_empty()).then_some(args),
+                            ),
+                        )
+                    }
+                    channel_settings::Event::Close => Event::Close,
+                })
+            }
+            (Modal::JoinChannel(state), Message::JoinChannel(message)) => {
+                state.update(message).map(|event| match event {
+                    join_channel::Event::Join(
+                        server,
+                        channel,
+                        buffer_action,
+                    ) => Event::JoinChannel(server, channel, buffer_action),
+                    join_channel::Event::Close => Event::Close,
+                })
+            }
+            (Modal::OpenTarget(state), Message::OpenTarget(message)) => {
+                state.update(message).map(|event| match event {
+                    open_target::Event::Open(kind, server, name) => {
+                        Event::OpenTarget(kind, server, name)
+                    }
+                    open_target::Event::Close => Event::Close,
+                })
+            }
+            (Modal::SendFile(state), Message::SendFile(message)) => {
+                state.update(message).map(|event| match event {
+                    send_file::Event::Send(server, nick, path) => {
+                        Event::SendFile(server, nick, path)
+                    }
+                    send_file::Event::Close => Event::Close,
+                })
+            }
             _ => None,
         }
     }
 
-    pub fn view<'a>(&'a self, config: &'a Config) -> Element<'a, Message> {
+    pub fn view<'a>(
+        &'a self,
+        clients: &'a data::client::Map,
+        config: &'a Config,
+    ) -> Element<'a, Message> {
         match self {
             Modal::AddReaction(state) => {
                 reaction::view(state, config).map(Message::Reaction)
             }
+            Modal::InsertEmoji(state) => {
+                emoji_picker::view(state, config).map(Message::EmojiPicker)
+            }
             Modal::RedactReason(state) => {
                 redaction::view(state, config).map(Message::Redaction)
             }
+            Modal::UserListDiff(state) => {
+                user_list::view(state, config).map(Message::UserList)
+            }
+            Modal::PendingUnbans(state) => {
+                timed_bans::view(state, config).map(Message::TimedBans)
+            }
+            Modal::ChannelSettings(state) => {
+                channel_settings::view(state, clients, config)
+                    .map(Message::ChannelSettings)
+            }
+            Modal::JoinChannel(state) => {
+                join_channel::view(state, config).map(Message::JoinChannel)
+            }
+            Modal::OpenTarget(state) => {
+                open_target::view(state, config).map(Message::OpenTarget)
+            }
+            Modal::SendFile(state) => {
+                send_file::view(state, config).map(Message::SendFile)
+            }
         }
     }
 
     pub fn focus(&self) -> Task<Message> {
         match self {
             Modal::AddReaction(state) => state.focus().map(Message::Reaction),
+            Modal::InsertEmoji(state) => {
+                state.focus().map(Message::EmojiPicker)
+            }
             Modal::RedactReason(state) => state.focus().map(Message::Redaction),
+            Modal::UserListDiff(state) => state.focus().map(Message::UserList),
+            Modal::PendingUnbans(state) => {
+                state.focus().map(Message::TimedBans)
+            }
+            Modal::ChannelSettings(state) => {
+                state.focus().map(Message::ChannelSettings)
+            }
+            Modal::JoinChannel(state) => {
+                state.focus().map(Message::JoinChannel)
+            }
+            Modal::OpenTarget(state) => state.focus().map(Message::OpenTarget),
+            Modal::SendFile(state) => state.focus().map(Message::SendFile),
         }
     }
 }
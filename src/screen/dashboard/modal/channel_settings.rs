@@ -0,0 +1,365 @@
+use data::client::MaskList;
+use data::user::{AccessLevel, Nick, User};
+use data::{Config, Server, i18n, target};
+use iced::widget::{button, column, container, row, scrollable, text_input};
+use iced::{Length, Task, alignment, padding};
+
+use crate::theme;
+use crate::widget::{Element, text};
+
+const MODAL_WIDTH: f32 = 480.0;
+const MODAL_MAX_HEIGHT: f32 = 520.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    server: Server,
+    channel: target::Channel,
+    /// Edited topic, until submitted.
+    topic: Option<String>,
+    modes: String,
+    ban: String,
+    invite_exception: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    TopicChanged(String),
+    SetTopic,
+    ModesChanged(String),
+    SetModes,
+    BanChanged(String),
+    AddBan,
+    RemoveBan(String),
+    InviteExceptionChanged(String),
+    AddInviteException(char),
+    RemoveInviteException(char, String),
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    SetTopic(String),
+    SetModes(String, Vec<String>),
+    Close,
+}
+
+impl State {
+    pub fn new(server: Server, channel: target::Channel) -> Self {
+        Self {
+            server,
+            channel,
+            topic: None,
+            modes: String::new(),
+            ban: String::new(),
+            invite_exception: String::new(),
+        }
+    }
+
+    pub fn server(&self) -> &Server {
+        &self.server
+    }
+
+    pub fn channel(&self) -> &target::Channel {
+        &self.channel
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::TopicChanged(topic) => {
+                self.topic = Some(topic);
+                None
+            }
+            Message::SetTopic => self.topic.take().map(Event::SetTopic),
+            Message::ModesChanged(modes) => {
+                self.modes = modes;
+                None
+            }
+            Message::SetModes => {
+                let modes = std::mem::take(&mut self.modes);
+                let mut parts = modes.split_whitespace().map(String::from);
+
+                parts
+                    .next()
+                    .map(|mode| Event::SetModes(mode, parts.collect()))
+            }
+            Message::BanChanged(ban) => {
+                self.ban = ban;
+                None
+            }
+            Message::AddBan => {
+                let mask = std::mem::take(&mut self.ban);
+
+                (!mask.trim().is_empty()).then(|| {
+                    Event::SetModes("+b".to_string(), vec![mask.trim().into()])
+                })
+            }
+            Message::RemoveBan(mask) => {
+                Some(Event::SetModes("-b".to_string(), vec![mask]))
+            }
+            Message::InviteExceptionChanged(invite_exception) => {
+                self.invite_exception = invite_exception;
+                None
+            }
+            Message::AddInviteException(mode) => {
+                let mask = std::mem::take(&mut self.invite_exception);
+
+                (!mask.trim().is_empty()).then(|| {
+                    Event::SetModes(
+                        format!("+{mode}"),
+                        vec![mask.trim().into()],
+                    )
+                })
+            }
+            Message::RemoveInviteException(mode, mask) => {
+                Some(Event::SetModes(format!("-{mode}"), vec![mask]))
+            }
+            Message::Close => Some(Event::Close),
+        }
+    }
+
+    pub fn focus(&self) -> Task<Message> {
+        Task::none()
+    }
+}
+
+pub fn view<'a>(
+    state: &'a State,
+    clients: &'a data::client::Map,
+    _config: &'a Config,
+) -> Element<'a, Message> {
+    let server = &state.server;
+    let channel = &state.channel;
+
+    // Half-ops and above may edit the channel settings; the server has the
+    // final say.
+    let is_op = clients
+        .nickname(server)
+        .map(|nick| User::from(Nick::from(nick)))
+        .and_then(|user| {
+            clients.resolve_user_attributes(server, channel, &user)
+        })
+        .is_some_and(|user| {
+            user.access_levels()
+                .any(|level| *level >= AccessLevel::HalfOp)
+        });
+
+    let small_button = |label: &'static str, message: Option<Message>| {
+        button(text(label))
+            .padding([2, 6])
+            .style(|theme, status| {
+                theme::button::secondary(theme, status, false)
+            })
+            .on_press_maybe(message)
+    };
+
+    let heading = |title: &'static str| {
+        text(title)
+            .style(theme::text::secondary)
+            .width(Length::Fill)
+    };
+
+    let current_topic = clients
+        .get_channel_topic(server, channel)
+        .and_then(|topic| topic.content.as_ref())
+        .map(|content| content.text().into_owned())
+        .unwrap_or_default();
+
+    let topic: Element<'a, Message> = if is_op {
+        row![
+            text_input(
+                i18n::tr("modal.channel_settings.no_topic"),
+                state.topic.as_deref().unwrap_or(&current_topic),
+            )
+            .on_input(Message::TopicChanged)
+            .on_submit(Message::SetTopic)
+            .padding(6)
+            .width(Length::Fill),
+            small_button(
+                i18n::tr("modal.channel_settings.set"),
+                state.topic.is_some().then_some(Message::SetTopic)
+            ),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center)
+        .into()
+    } else if current_topic.is_empty() {
+        text(i18n::tr("modal.channel_settings.no_topic"))
+            .style(theme::text::secondary)
+            .into()
+    } else {
+        text(current_topic).into()
+    };
+
+    let current_modes = clients
+        .get_channel_mode(server, channel)
+        .cloned()
+        .unwrap_or_default();
+
+    let modes: Element<'a, Message> = if is_op {
+        column![
+            text(current_modes),
+            row![
+                text_input(
+                    i18n::tr("modal.channel_settings.modes_example"),
+                    &state.modes
+                )
+                .on_input(Message::ModesChanged)
+                .on_submit(Message::SetModes)
+                .padding(6)
+                .width(Length::Fill),
+                small_button(
+                    i18n::tr("modal.channel_settings.apply"),
+                    (!state.modes.trim().is_empty())
+                        .then_some(Message::SetModes)
+                ),
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center),
+        ]
+        .spacing(4)
+        .into()
+    } else {
+        text(current_modes).into()
+    };
+
+    let mask_list = |list: Option<&'a MaskList>,
+                     on_remove: &dyn Fn(String) -> Message|
+     -> Element<'a, Message> {
+        let Some(entries) = list.and_then(|list| list.entries.as_ref()) else {
+            return text(i18n::tr("modal.channel_settings.loading"))
+                .style(theme::text::secondary)
+                .into();
+        };
+
+        if entries.is_empty() {
+            return text(i18n::tr("modal.channel_settings.none"))
+                .style(theme::text::secondary)
+                .into();
+        }
+
+        column(entries.iter().map(|entry| {
+            let set_by = match (&entry.set_by, entry.set_at) {
+                (Some(who), Some(at)) => {
+                    let date = at
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string();
+
+                    Some(i18n::tr_args(
+                        "modal.channel_settings.set_by_at",
+                        &[("nick", who.as_str()), ("date", date.as_str())],
+                    ))
+                }
+                (Some(who), None) => Some(i18n::tr_args(
+                    "modal.channel_settings.set_by",
+                    &[("nick", who.as_str())],
+                )),
+                _ => None,
+            };
+
+            row![
+                column![text(entry.mask.clone())]
+                    .extend(set_by.map(|set_by| {
+                        text(set_by).style(theme::text::secondary).into()
+                    }))
+                    .spacing(2)
+                    .width(Length::Fill),
+            ]
+            .extend(is_op.then(|| {
+                small_button(
+                    i18n::tr("modal.channel_settings.remove"),
+                    Some(on_remove(entry.mask.clone())),
+                )
+                .into()
+            }))
+            .spacing(8)
+            .align_y(iced::Alignment::Center)
+            .into()
+        }))
+        .spacing(6)
+        .into()
+    };
+
+    let add_mask = |value: &'a str, on_input: fn(String) -> Message, on_add| {
+        is_op.then(|| {
+            row![
+                text_input("nick!user@host", value)
+                    .on_input(on_input)
+                    .on_submit(on_add)
+                    .padding(6)
+                    .width(Length::Fill),
+                small_button(
+                    i18n::tr("modal.channel_settings.add"),
+                    (!value.trim().is_empty()).then_some(on_add)
+                ),
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center)
+        })
+    };
+
+    let mut body = column![
+        heading(i18n::tr("modal.channel_settings.topic")),
+        topic,
+        heading(i18n::tr("modal.channel_settings.modes")),
+        modes,
+        heading(i18n::tr("modal.channel_settings.bans")),
+        mask_list(
+            clients.get_channel_bans(server, channel),
+            &Message::RemoveBan
+        ),
+    ]
+    .extend(
+        add_mask(&state.ban, Message::BanChanged, Message::AddBan)
+            .map(Into::into),
+    )
+    .spacing(8);
+
+    if let Some(mode) = clients.get_server_invite_exception_mode(server) {
+        body = body
+            .push(heading(i18n::tr(
+                "modal.channel_settings.invite_exceptions",
+            )))
+            .push(mask_list(
+                clients.get_channel_invite_exceptions(server, channel),
+                &move |mask| Message::RemoveInviteException(mode, mask),
+            ))
+            .extend(
+                add_mask(
+                    &state.invite_exception,
+                    Message::InviteExceptionChanged,
+                    Message::AddInviteException(mode),
+                )
+                .map(Into::into),
+            );
+    }
+
+    let content = column![
+        text(i18n::tr_args(
+            "modal.channel_settings.title",
+            &[("channel", channel.as_str())],
+        )),
+        // Leave room for the scrollbar
+        scrollable(body.padding(padding::right(10)))
+            .height(Length::Shrink)
+            .width(Length::Fill),
+        button(
+            container(text(i18n::tr("modal.close")))
+                .align_x(alignment::Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .padding(5)
+        .width(Length::Fixed(250.0))
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press(Message::Close),
+    ]
+    .spacing(20)
+    .align_x(iced::Alignment::Center);
+
+    container(content)
+        .width(Length::Fixed(MODAL_WIDTH))
+        .max_height(MODAL_MAX_HEIGHT)
+        .padding(25)
+        .style(theme::container::tooltip)
+        .into()
+}
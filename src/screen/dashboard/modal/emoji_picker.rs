@@ -0,0 +1,234 @@
+use data::Config;
+use data::buffer::SkinTone;
+use iced::widget::{
+    Scrollable, button, column, container, operation, scrollable, text_input,
+};
+use iced::{Length, Task};
+
+use crate::widget::{Element, Row, key_press, text};
+use crate::{emoji, theme, widget};
+
+const MODAL_WIDTH: f32 = 380.0;
+const MODAL_HEIGHT: f32 = 300.0;
+const EMOJI_BUTTON_WIDTH: f32 = 32.0;
+const EMOJI_BUTTON_HEIGHT: f32 = 32.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    recent: Vec<&'static str>,
+    skin_tone: SkinTone,
+    search_query_id: iced::widget::Id,
+    search_query: String,
+    selection: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SearchChanged(String),
+    Tab(bool),
+    SearchSelect,
+    SelectEmoji(&'static str),
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Insert(&'static str),
+}
+
+impl State {
+    pub fn new(recent: &[String], skin_tone: SkinTone) -> Self {
+        Self {
+            recent: recent
+                .iter()
+                .filter_map(|emoji| emojis::get(emoji))
+                .map(emojis::Emoji::as_str)
+                .collect(),
+            skin_tone,
+            search_query_id: iced::widget::Id::unique(),
+            search_query: String::new(),
+            selection: None,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::SearchChanged(search_query) => {
+                self.search_query = search_query;
+                self.selection = (!self.entries().is_empty()).then_some(0);
+
+                None
+            }
+            Message::Tab(shift) => {
+                let count = self.entries().len();
+
+                self.selection = if count == 0 {
+                    None
+                } else if let Some(selection) = self.selection {
+                    Some(if shift {
+                        selection.checked_sub(1).unwrap_or(count - 1)
+                    } else {
+                        (selection + 1) % count
+                    })
+                } else if shift {
+                    Some(count - 1)
+                } else {
+                    Some(0)
+                };
+
+                None
+            }
+            Message::SearchSelect => self.selection.and_then(|selection| {
+                self.entries()
+                    .get(selection)
+                    .map(|emoji| Event::Insert(emoji.as_str()))
+            }),
+            Message::SelectEmoji(emoji) => Some(Event::Insert(emoji)),
+        }
+    }
+
+    pub fn focus(&self) -> Task<Message> {
+        let search_query_id = self.search_query_id.clone();
+
+        operation::is_focused(search_query_id.clone()).then(move |is_focused| {
+            if is_focused {
+                Task::none()
+            } else {
+                operation::focus(search_query_id.clone())
+            }
+        })
+    }
+
+    fn is_searching(&self) -> bool {
+        !normalized_query(&self.search_query).is_empty()
+    }
+
+    /// Emojis in the order they are shown, recently used ones first unless
+    /// searching.
+    fn entries(&self) -> Vec<&'static emojis::Emoji> {
+        let matching =
+            emoji::matching_emojis(&normalized_query(&self.search_query));
+
+        let entries = if self.is_searching() {
+            matching
+        } else {
+            self.recent
+                .iter()
+                .filter_map(|emoji| emojis::get(emoji))
+                .chain(matching)
+                .collect()
+        };
+
+        entries
+            .into_iter()
+            .map(|emoji| {
+                emoji.with_skin_tone(self.skin_tone.into()).unwrap_or(emoji)
+            })
+            .collect()
+    }
+}
+
+pub fn view<'a>(state: &'a State, config: &'a Config) -> Element<'a, Message> {
+    let entries = state.entries();
+
+    let body: Element<'a, Message> = if entries.is_empty() {
+        container(text("No emoji matches"))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(iced::alignment::Horizontal::Center)
+            .align_y(iced::alignment::Vertical::Center)
+            .into()
+    } else {
+        let sections = if state.is_searching() || state.recent.is_empty() {
+            column![emoji_grid(&entries, 0, state.selection)]
+        } else {
+            let (recent, all) = entries.split_at(state.recent.len());
+
+            column![
+                text("Recently used").style(theme::text::secondary),
+                emoji_grid(recent, 0, state.selection),
+                text("All").style(theme::text::secondary),
+                emoji_grid(all, recent.len(), state.selection),
+            ]
+            .spacing(8)
+        };
+
+        Scrollable::new(container(sections).width(Length::Fill))
+            .direction(scrollable::Direction::Vertical(
+                scrollable::Scrollbar::default()
+                    .width(config.pane.scrollbar.width)
+                    .scroller_width(config.pane.scrollbar.scroller_width),
+            ))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    };
+
+    let content = column![
+        key_press(
+            key_press(
+                text_input("Search...", &state.search_query)
+                    .id(state.search_query_id.clone())
+                    .on_input(Message::SearchChanged)
+                    .on_submit(Message::SearchSelect)
+                    .padding(8),
+                key_press::Key::Named(key_press::Named::Tab),
+                key_press::Modifiers::SHIFT,
+                Message::Tab(true),
+            ),
+            key_press::Key::Named(key_press::Named::Tab),
+            key_press::Modifiers::default(),
+            Message::Tab(false),
+        ),
+        body
+    ]
+    .spacing(12)
+    .height(Length::Fill);
+
+    container(content)
+        .width(Length::Fixed(MODAL_WIDTH))
+        .height(Length::Fixed(MODAL_HEIGHT))
+        .padding(8)
+        .style(theme::container::tooltip)
+        .into()
+}
+
+fn emoji_grid<'a>(
+    emojis: &[&'static emojis::Emoji],
+    offset: usize,
+    selection: Option<usize>,
+) -> Element<'a, Message> {
+    emojis
+        .iter()
+        .enumerate()
+        .fold(Row::new().spacing(4), |row, (index, emoji)| {
+            row.push(emoji_button(
+                emoji,
+                selection.is_some_and(|selection| selection == offset + index),
+            ))
+        })
+        .wrap()
+        .into()
+}
+
+fn emoji_button<'a>(
+    emoji: &'static emojis::Emoji,
+    selection: bool,
+) -> widget::Button<'a, Message> {
+    button(
+        container(text(emoji.as_str()).size(16))
+            .width(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill),
+    )
+    .padding(4)
+    .width(Length::Fixed(EMOJI_BUTTON_WIDTH))
+    .height(Length::Fixed(EMOJI_BUTTON_HEIGHT))
+    .style(move |theme, status| {
+        theme::button::reaction(theme, status, false, selection)
+    })
+    .on_press(Message::SelectEmoji(emoji.as_str()))
+}
+
+fn normalized_query(query: &str) -> String {
+    query.trim().trim_matches(':').to_ascii_lowercase()
+}
@@ -0,0 +1,92 @@
+use data::dashboard::BufferAction;
+use data::{Config, Server, i18n, target};
+use iced::widget::{button, column, container};
+use iced::{Length, Task, alignment};
+
+use crate::theme;
+use crate::widget::{Element, text};
+
+const MODAL_WIDTH: f32 = 380.0;
+
+/// Asks before joining a channel clicked on while not in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    server: Server,
+    channel: target::Channel,
+    buffer_action: BufferAction,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Join,
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Join(Server, target::Channel, BufferAction),
+    Close,
+}
+
+impl State {
+    pub fn new(
+        server: Server,
+        channel: target::Channel,
+        buffer_action: BufferAction,
+    ) -> Self {
+        Self {
+            server,
+            channel,
+            buffer_action,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::Join => Some(Event::Join(
+                self.server.clone(),
+                self.channel.clone(),
+                self.buffer_action,
+            )),
+            Message::Close => Some(Event::Close),
+        }
+    }
+
+    pub fn focus(&self) -> Task<Message> {
+        Task::none()
+    }
+}
+
+pub fn view<'a>(state: &'a State, _config: &'a Config) -> Element<'a, Message> {
+    let action = |label: &'static str, message: Message| {
+        button(
+            container(text(label))
+                .align_x(alignment::Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .padding(5)
+        .width(Length::Fixed(250.0))
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press(message)
+    };
+
+    let content = column![
+        text(i18n::tr_args(
+            "modal.join_channel.title",
+            &[("channel", state.channel.as_str())]
+        )),
+        column![
+            action(i18n::tr("modal.join_channel.join"), Message::Join),
+            action(i18n::tr("modal.close"), Message::Close),
+        ]
+        .spacing(4),
+    ]
+    .spacing(20)
+    .align_x(iced::Alignment::Center);
+
+    container(content)
+        .width(Length::Fixed(MODAL_WIDTH))
+        .padding(25)
+        .style(theme::container::tooltip)
+        .into()
+}
@@ -0,0 +1,155 @@
+use data::{Config, Server, i18n};
+use iced::widget::{
+    button, column, container, operation, pick_list, text_input,
+};
+use iced::{Length, Task, alignment};
+
+use crate::theme;
+use crate::widget::{Element, text};
+
+const MODAL_WIDTH: f32 = 380.0;
+
+/// Whether the entered name is joined as a channel or opened as a query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Channel,
+    Query,
+}
+
+/// Asks for a channel to join, or a nickname to start a query with, from an
+/// empty pane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    kind: Kind,
+    servers: Vec<Server>,
+    server: Option<Server>,
+    name_id: iced::widget::Id,
+    name: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SelectServer(Server),
+    NameChanged(String),
+    Submit,
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Open(Kind, Server, String),
+    Close,
+}
+
+impl State {
+    pub fn new(kind: Kind, servers: Vec<Server>) -> Self {
+        Self {
+            kind,
+            server: servers.first().cloned(),
+            servers,
+            name_id: iced::widget::Id::unique(),
+            name: String::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::SelectServer(server) => {
+                self.server = Some(server);
+                None
+            }
+            Message::NameChanged(name) => {
+                self.name = name;
+                None
+            }
+            Message::Submit => {
+                let name = self.name.trim();
+
+                if name.is_empty() {
+                    return None;
+                }
+
+                self.server.clone().map(|server| {
+                    Event::Open(self.kind, server, name.to_string())
+                })
+            }
+            Message::Close => Some(Event::Close),
+        }
+    }
+
+    pub fn focus(&self) -> Task<Message> {
+        let name_id = self.name_id.clone();
+
+        operation::is_focused(name_id.clone()).then(move |is_focused| {
+            if is_focused {
+                Task::none()
+            } else {
+                operation::focus(name_id.clone())
+            }
+        })
+    }
+}
+
+pub fn view<'a>(state: &'a State, _config: &'a Config) -> Element<'a, Message> {
+    let (title, placeholder, submit) = match state.kind {
+        Kind::Channel => (
+            i18n::tr("modal.open_target.join_channel"),
+            i18n::tr("modal.open_target.channel"),
+            i18n::tr("modal.join_channel.join"),
+        ),
+        Kind::Query => (
+            i18n::tr("modal.open_target.start_query"),
+            i18n::tr("modal.open_target.nickname"),
+            i18n::tr("modal.open_target.open"),
+        ),
+    };
+
+    let action = |label: &'static str, message: Option<Message>| {
+        button(
+            container(text(label))
+                .align_x(alignment::Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .padding(5)
+        .width(Length::Fixed(250.0))
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press_maybe(message)
+    };
+
+    let can_submit = state.server.is_some() && !state.name.trim().is_empty();
+
+    let content = column![text(title)]
+        .extend((state.servers.len() > 1).then(|| {
+            pick_list(
+                state.server.as_ref(),
+                state.servers.as_slice(),
+                Server::to_string,
+            )
+            .on_select(Message::SelectServer)
+            .width(Length::Fill)
+            .into()
+        }))
+        .push(
+            text_input(placeholder, &state.name)
+                .id(state.name_id.clone())
+                .on_input(Message::NameChanged)
+                .on_submit(Message::Submit)
+                .padding(8)
+                .width(Length::Fill),
+        )
+        .push(
+            column![
+                action(submit, can_submit.then_some(Message::Submit)),
+                action(i18n::tr("modal.close"), Some(Message::Close)),
+            ]
+            .spacing(4),
+        )
+        .spacing(20)
+        .align_x(iced::Alignment::Center);
+
+    container(content)
+        .width(Length::Fixed(MODAL_WIDTH))
+        .padding(25)
+        .style(theme::container::tooltip)
+        .into()
+}
@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+
+use data::{Config, Server, i18n, target};
+use iced::widget::{button, column, container, operation, text_input};
+use iced::{Length, Task, alignment};
+
+use crate::theme;
+use crate::widget::{Element, text};
+
+const MODAL_WIDTH: f32 = 380.0;
+
+/// Asks who to send a file dropped onto a channel pane to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    server: Server,
+    channel: target::Channel,
+    path: PathBuf,
+    nick_id: iced::widget::Id,
+    nick: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    NickChanged(String),
+    Submit,
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Send(Server, String, PathBuf),
+    Close,
+}
+
+impl State {
+    pub fn new(
+        server: Server,
+        channel: target::Channel,
+        path: PathBuf,
+    ) -> Self {
+        Self {
+            server,
+            channel,
+            path,
+            nick_id: iced::widget::Id::unique(),
+            nick: String::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::NickChanged(nick) => {
+                self.nick = nick;
+                None
+            }
+            Message::Submit => {
+                let nick = self.nick.trim();
+
+                (!nick.is_empty()).then(|| {
+                    Event::Send(
+                        self.server.clone(),
+                        nick.to_string(),
+                        self.path.clone(),
+                    )
+                })
+            }
+            Message::Close => Some(Event::Close),
+        }
+    }
+
+    pub fn focus(&self) -> Task<Message> {
+        let nick_id = self.nick_id.clone();
+
+        operation::is_focused(nick_id.clone()).then(move |is_focused| {
+            if is_focused {
+                Task::none()
+            } else {
+                operation::focus(nick_id.clone())
+            }
+        })
+    }
+}
+
+pub fn view<'a>(state: &'a State, _config: &'a Config) -> Element<'a, Message> {
+    let file_name = state
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+
+    let action = |label: &'static str, message: Option<Message>| {
+        button(
+            container(text(label))
+                .align_x(alignment::Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .padding(5)
+        .width(Length::Fixed(250.0))
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press_maybe(message)
+    };
+
+    let can_submit = !state.nick.trim().is_empty();
+
+    let content = column![
+        text(i18n::tr_args(
+            "modal.send_file.title",
+            &[("file", &file_name), ("channel", state.channel.as_str())]
+        )),
+        text_input(i18n::tr("modal.open_target.nickname"), &state.nick)
+            .id(state.nick_id.clone())
+            .on_input(Message::NickChanged)
+            .on_submit(Message::Submit)
+            .padding(8)
+            .width(Length::Fill),
+        column![
+            action(
+                i18n::tr("modal.send_file.send"),
+                can_submit.then_some(Message::Submit)
+            ),
+            action(i18n::tr("modal.close"), Some(Message::Close)),
+        ]
+        .spacing(4),
+    ]
+    .spacing(20)
+    .align_x(iced::Alignment::Center);
+
+    container(content)
+        .width(Length::Fixed(MODAL_WIDTH))
+        .padding(25)
+        .style(theme::container::tooltip)
+        .into()
+}
@@ -0,0 +1,150 @@
+use chrono::Utc;
+use data::timed_ban::TimedBan;
+use data::{Config, i18n};
+use iced::widget::{button, column, container, row, scrollable};
+use iced::{Length, Task, alignment};
+
+use crate::theme;
+use crate::widget::{Element, text};
+
+const MODAL_WIDTH: f32 = 480.0;
+const MODAL_MAX_HEIGHT: f32 = 420.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    channel: String,
+    bans: Vec<TimedBan>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Unban(usize),
+    Cancel(usize),
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Unban(TimedBan),
+    Cancel(TimedBan),
+    Close,
+}
+
+impl State {
+    pub fn new(channel: String, bans: Vec<TimedBan>) -> Self {
+        Self { channel, bans }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::Unban(index) => (index < self.bans.len())
+                .then(|| Event::Unban(self.bans.remove(index))),
+            Message::Cancel(index) => (index < self.bans.len())
+                .then(|| Event::Cancel(self.bans.remove(index))),
+            Message::Close => Some(Event::Close),
+        }
+    }
+
+    pub fn focus(&self) -> Task<Message> {
+        Task::none()
+    }
+}
+
+pub fn view<'a>(state: &'a State, _config: &'a Config) -> Element<'a, Message> {
+    let now = Utc::now();
+
+    let body: Element<'a, Message> = if state.bans.is_empty() {
+        text(i18n::tr_args(
+            "modal.timed_bans.no_pending",
+            &[("channel", &state.channel)],
+        ))
+        .style(theme::text::secondary)
+        .into()
+    } else {
+        let small_button = |label: &'static str, message: Message| {
+            button(text(label))
+                .padding([2, 6])
+                .style(|theme, status| {
+                    theme::button::secondary(theme, status, false)
+                })
+                .on_press(message)
+        };
+
+        scrollable(
+            column(state.bans.iter().enumerate().map(|(index, ban)| {
+                let expires_at = ban
+                    .expires_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string();
+
+                let expiry = if ban.is_expired(now) {
+                    text(i18n::tr_args(
+                        "modal.timed_bans.overdue",
+                        &[("date", &expires_at)],
+                    ))
+                    .style(theme::text::error)
+                } else {
+                    text(i18n::tr_args(
+                        "modal.timed_bans.expires",
+                        &[("date", &expires_at)],
+                    ))
+                    .style(theme::text::secondary)
+                };
+
+                row![
+                    column![
+                        text(i18n::tr_args(
+                            "modal.timed_bans.entry",
+                            &[("mask", &ban.mask), ("nick", &ban.nickname)],
+                        )),
+                        expiry,
+                    ]
+                    .spacing(2)
+                    .width(Length::Fill),
+                    small_button(
+                        i18n::tr("modal.timed_bans.unban_now"),
+                        Message::Unban(index),
+                    ),
+                    small_button(
+                        i18n::tr("modal.timed_bans.cancel"),
+                        Message::Cancel(index),
+                    ),
+                ]
+                .spacing(8)
+                .align_y(iced::Alignment::Center)
+                .into()
+            }))
+            .spacing(8)
+            .width(Length::Fill),
+        )
+        .height(Length::Shrink)
+        .into()
+    };
+
+    let content = column![
+        text(i18n::tr_args(
+            "modal.timed_bans.title",
+            &[("channel", &state.channel)],
+        )),
+        body,
+        button(
+            container(text(i18n::tr("modal.close")))
+                .align_x(alignment::Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .padding(5)
+        .width(Length::Fixed(250.0))
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press(Message::Close),
+    ]
+    .spacing(20)
+    .align_x(iced::Alignment::Center);
+
+    container(content)
+        .width(Length::Fixed(MODAL_WIDTH))
+        .max_height(MODAL_MAX_HEIGHT)
+        .padding(25)
+        .style(theme::container::tooltip)
+        .into()
+}
@@ -0,0 +1,147 @@
+use data::user_list::{Diff, Entry};
+use data::{Config, i18n};
+use iced::widget::{button, column, container, scrollable};
+use iced::{Length, Task, alignment};
+
+use crate::theme;
+use crate::widget::{Element, text};
+
+const MODAL_WIDTH: f32 = 420.0;
+const MODAL_MAX_HEIGHT: f32 = 420.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    channel: String,
+    diff: Option<Diff>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Close,
+}
+
+impl State {
+    /// `diff` is `None` when the channel has not been exported before.
+    pub fn new(channel: String, diff: Option<Diff>) -> Self {
+        Self { channel, diff }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::Close => Some(Event::Close),
+        }
+    }
+
+    pub fn focus(&self) -> Task<Message> {
+        Task::none()
+    }
+}
+
+pub fn view<'a>(state: &'a State, _config: &'a Config) -> Element<'a, Message> {
+    let body: Element<'a, Message> = match &state.diff {
+        None => text(i18n::tr_args(
+            "modal.user_list.not_exported",
+            &[("channel", &state.channel)],
+        ))
+        .style(theme::text::secondary)
+        .into(),
+        Some(diff) if diff.is_empty() => {
+            text(i18n::tr("modal.user_list.no_changes"))
+                .style(theme::text::secondary)
+                .into()
+        }
+        Some(diff) => {
+            let section = |title: &str, lines: Vec<String>| {
+                (!lines.is_empty()).then(|| {
+                    column![text(i18n::tr_args(
+                        title,
+                        &[("count", &i18n::format_number(lines.len() as u64))],
+                    ))]
+                    .extend(lines.into_iter().map(|line| {
+                        text(line).style(theme::text::secondary).into()
+                    }))
+                    .spacing(2)
+                })
+            };
+
+            scrollable(
+                column![
+                    section(
+                        "modal.user_list.joined",
+                        diff.joined.iter().map(Entry::hostmask).collect()
+                    ),
+                    section(
+                        "modal.user_list.left",
+                        diff.left.iter().map(Entry::hostmask).collect()
+                    ),
+                    section(
+                        "modal.user_list.changed",
+                        diff.changed
+                            .iter()
+                            .map(|(previous, current)| format!(
+                                "{}{} → {}{}",
+                                previous.access_levels,
+                                previous.hostmask(),
+                                current.access_levels,
+                                current.hostmask()
+                            ))
+                            .collect()
+                    ),
+                ]
+                .spacing(12)
+                .width(Length::Fill),
+            )
+            .height(Length::Shrink)
+            .into()
+        }
+    };
+
+    let title = match &state.diff {
+        Some(diff) => i18n::tr_args(
+            "modal.user_list.title_since",
+            &[
+                ("channel", &state.channel),
+                (
+                    "date",
+                    &diff
+                        .since
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string(),
+                ),
+            ],
+        ),
+        None => i18n::tr_args(
+            "modal.user_list.title",
+            &[("channel", &state.channel)],
+        ),
+    };
+
+    let content = column![
+        text(title),
+        body,
+        button(
+            container(text(i18n::tr("modal.close")))
+                .align_x(alignment::Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .padding(5)
+        .width(Length::Fixed(250.0))
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press(Message::Close),
+    ]
+    .spacing(20)
+    .align_x(iced::Alignment::Center);
+
+    container(content)
+        .width(Length::Fixed(MODAL_WIDTH))
+        .max_height(MODAL_MAX_HEIGHT)
+        .padding(25)
+        .style(theme::container::tooltip)
+        .into()
+}
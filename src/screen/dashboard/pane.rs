@@ -1,12 +1,15 @@
+use std::collections::VecDeque;
+
+use data::buffer::MessageClass;
 use data::user::{ChannelUsers, User};
-use data::{Config, Server, file_transfer, history, preview, target};
+use data::{Config, Server, file_transfer, history, i18n, preview, target};
 use iced::widget::text::Wrapping;
 use iced::widget::{button, center, column, container, pane_grid, row, text};
-use iced::{Length, Padding, Size, Task, padding};
+use iced::{Length, Padding, Size, Task, mouse, padding};
 
 use super::sidebar;
 use crate::buffer::{self, Buffer};
-use crate::widget::{Element, on_resize, tooltip};
+use crate::widget::{Element, context_menu, on_resize, tooltip};
 use crate::{Theme, font, icon, theme, widget};
 
 #[derive(Debug, Clone)]
@@ -20,6 +23,7 @@ pub enum Message {
     MaximizePane,
     ToggleShowUserList,
     ToggleShowTopic,
+    ToggleHiddenMessages(MessageClass),
     Popout,
     Merge,
     ScrollToBottom,
@@ -63,9 +67,12 @@ impl Pane {
         history: &'a history::Manager,
         previews: &'a preview::Collection,
         sidebar: &'a sidebar::Sidebar,
+        recent_buffers: &'a VecDeque<buffer::Upstream>,
+        ignores: &'a [data::ignore::Mask],
         config: &'a Config,
         theme: &'a Theme,
         settings: Option<&'a buffer::Settings>,
+        is_archived: bool,
         is_popout: bool,
         channel_is_focused: impl Fn(&Server, &target::Channel) -> bool + Copy + 'a,
         channel_is_open: impl Fn(&Server, &target::Channel) -> bool + Copy + 'a,
@@ -127,12 +134,12 @@ impl Pane {
                 config,
                 theme,
             ),
-            Buffer::FileTransfers(_) => text("File Transfers")
+            Buffer::FileTransfers(_) => text(i18n::tr("buffer.file_transfers"))
                 .wrapping(Wrapping::None)
                 .ellipsis(text::Ellipsis::End)
                 .into(),
             Buffer::ChannelDiscovery(state) => {
-                let base = "Channel Discovery";
+                let base = i18n::tr("buffer.channel_discovery");
                 if let Some(server) = state.server.as_ref() {
                     let base = format!("{base} @ {server}");
                     let channel_count = clients
@@ -140,7 +147,15 @@ impl Pane {
                         .map(data::channel_discovery::Manager::amount_of_channels)
                         .unwrap_or_default();
                     if channel_count > 0 {
-                        text(format!("{base} - {channel_count} channels"))
+                        let channels = i18n::tr_args(
+                            "channel_discovery.channels",
+                            &[(
+                                "count",
+                                &i18n::format_number(channel_count as u64),
+                            )],
+                        );
+
+                        text(format!("{base} - {channels}"))
                             .wrapping(Wrapping::None)
                             .ellipsis(text::Ellipsis::End)
                             .into()
@@ -157,15 +172,27 @@ impl Pane {
                         .into()
                 }
             }
-            Buffer::Logs(_) => text("Logs")
+            Buffer::Logs(_) => text(i18n::tr("buffer.logs"))
+                .wrapping(Wrapping::None)
+                .ellipsis(text::Ellipsis::End)
+                .into(),
+            Buffer::Highlights(_) => text(i18n::tr("buffer.highlights"))
+                .wrapping(Wrapping::None)
+                .ellipsis(text::Ellipsis::End)
+                .into(),
+            Buffer::ConfigEditor(_) => text(i18n::tr("buffer.config_editor"))
+                .wrapping(Wrapping::None)
+                .ellipsis(text::Ellipsis::End)
+                .into(),
+            Buffer::Diagnostics(_) => text(i18n::tr("buffer.diagnostics"))
                 .wrapping(Wrapping::None)
                 .ellipsis(text::Ellipsis::End)
                 .into(),
-            Buffer::Highlights(_) => text("Highlights")
+            Buffer::Unread(_) => text(i18n::tr("buffer.unread"))
                 .wrapping(Wrapping::None)
                 .ellipsis(text::Ellipsis::End)
                 .into(),
-            Buffer::ConfigEditor(_) => text("Config Editor")
+            Buffer::Ignores(_) => text(i18n::tr("buffer.ignores"))
                 .wrapping(Wrapping::None)
                 .ellipsis(text::Ellipsis::End)
                 .into(),
@@ -197,10 +224,13 @@ impl Pane {
                 history,
                 previews,
                 settings,
+                is_archived,
                 config,
                 theme,
                 is_focused,
                 sidebar,
+                recent_buffers,
+                ignores,
                 channel_is_focused,
                 channel_is_open,
             )
@@ -213,7 +243,7 @@ impl Pane {
             Some(modal) => widget::modal(
                 content,
                 modal
-                    .view(config)
+                    .view(clients, config)
                     .map(move |message| Message::Modal(id, message)),
                 move || Message::CloseBufferModal(id),
                 0.2,
@@ -266,7 +296,10 @@ impl Pane {
             Buffer::Highlights(_) => Some(history::Resource::highlights()),
             Buffer::ChannelDiscovery(_)
             | Buffer::FileTransfers(_)
-            | Buffer::ConfigEditor(_) => None,
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => None,
         }
     }
 
@@ -282,7 +315,10 @@ impl Pane {
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
             | Buffer::ChannelDiscovery(_)
-            | Buffer::ConfigEditor(_) => vec![],
+            | Buffer::ConfigEditor(_)
+            | Buffer::Diagnostics(_)
+            | Buffer::Unread(_)
+            | Buffer::Ignores(_) => vec![],
         }
     }
 }
@@ -547,6 +583,83 @@ impl TitleBar {
                     None
                 }
             },
+            {
+                let classes = match &buffer {
+                    Buffer::Channel(_) => Some(MessageClass::CHANNEL),
+                    Buffer::Query(_) => Some(MessageClass::QUERY),
+                    _ => None,
+                };
+
+                classes.map(|classes| {
+                    let hidden_messages =
+                        settings.map(|settings| &settings.hidden_messages);
+                    let has_hidden_messages =
+                        hidden_messages.is_some_and(|hidden_messages| {
+                            !hidden_messages.is_empty()
+                        });
+
+                    // No `on_press`, so the click opens the context menu instead
+                    let hidden_messages_button = button(center(icon::hide()))
+                        .padding(5)
+                        .width(22)
+                        .height(22)
+                        .style(move |theme, status| {
+                            let status = match status {
+                                button::Status::Disabled => {
+                                    button::Status::Active
+                                }
+                                status => status,
+                            };
+
+                            theme::button::secondary(
+                                theme,
+                                status,
+                                has_hidden_messages,
+                            )
+                        });
+
+                    let hidden_messages_menu = context_menu(
+                        context_menu::MouseButton::Left,
+                        context_menu::Anchor::Widget,
+                        context_menu::ToggleBehavior::KeepOpen,
+                        Some(mouse::Interaction::Pointer),
+                        hidden_messages_button,
+                        classes.to_vec(),
+                        move |class, length| {
+                            let hidden = hidden_messages.is_some_and(
+                                |hidden_messages| {
+                                    hidden_messages.contains(class)
+                                },
+                            );
+
+                            button(
+                                row![
+                                    container(hidden.then(icon::checkmark))
+                                        .width(12),
+                                    text(message_class_label(class)),
+                                ]
+                                .spacing(8)
+                                .align_y(iced::Alignment::Center),
+                            )
+                            .width(length)
+                            .padding(config.context_menu.padding.entry)
+                            .on_press(Message::ToggleHiddenMessages(class))
+                            .into()
+                        },
+                    );
+
+                    tooltip(
+                        hidden_messages_menu,
+                        show_tooltips.then_some(if has_hidden_messages {
+                            i18n::tr("buffer.hidden_messages.some_hidden")
+                        } else {
+                            i18n::tr("buffer.hidden_messages.hide")
+                        }),
+                        tooltip::Position::Bottom,
+                        theme,
+                    )
+                })
+            },
             if let Buffer::Channel(state) = &buffer {
                 if let Some(topic) =
                     clients.get_channel_topic(&state.server, &state.target)
@@ -851,14 +964,19 @@ fn query_title<'a>(
         .style(move |_| {
             theme::text::nickname(
                 theme,
-                &config.buffer.nickname.color,
+                &config.buffer.nickname,
                 Some(user.seed()),
                 is_user_away,
                 is_user_offline,
             )
         })
         .font_maybe(
-            theme::font_style::nickname(theme, is_user_offline).map(font::get),
+            theme::font_style::nickname(
+                theme,
+                is_user_away.is_some(),
+                is_user_offline,
+            )
+            .map(font::get),
         )
         .shaping(text::Shaping::Advanced)
         .wrapping(Wrapping::None)
@@ -875,7 +993,7 @@ fn query_title<'a>(
                             .style(move |_| {
                                 theme::text::nickname(
                                     theme,
-                                    &config.buffer.nickname.color,
+                                    &config.buffer.nickname,
                                     Some(resolved_query.as_str()),
                                     is_user_away,
                                     is_user_offline,
@@ -949,8 +1067,35 @@ impl From<Pane> for data::Pane {
             Buffer::ConfigEditor(_) => {
                 data::Buffer::Internal(buffer::Internal::ConfigEditor)
             }
+            Buffer::Diagnostics(_) => {
+                data::Buffer::Internal(buffer::Internal::Diagnostics)
+            }
+            Buffer::Unread(_) => {
+                data::Buffer::Internal(buffer::Internal::Unread)
+            }
+            Buffer::Ignores(_) => {
+                data::Buffer::Internal(buffer::Internal::Ignores)
+            }
         };
 
         data::Pane::Buffer { buffer }
     }
 }
+
+fn message_class_label(class: MessageClass) -> &'static str {
+    match class {
+        MessageClass::Join => i18n::tr("buffer.hidden_messages.join"),
+        MessageClass::Part => i18n::tr("buffer.hidden_messages.part"),
+        MessageClass::Quit => i18n::tr("buffer.hidden_messages.quit"),
+        MessageClass::ChangeNick => {
+            i18n::tr("buffer.hidden_messages.change_nick")
+        }
+        MessageClass::ChangeMode => {
+            i18n::tr("buffer.hidden_messages.change_mode")
+        }
+        MessageClass::ChangeTopic => {
+            i18n::tr("buffer.hidden_messages.change_topic")
+        }
+        MessageClass::Ctcp => i18n::tr("buffer.hidden_messages.ctcp"),
+    }
+}
@@ -1,32 +1,44 @@
+use std::collections::{HashMap, HashSet};
 use std::iter;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
+use data::config::actions::SidebarClickAction;
 use data::config::{self, Config, sidebar};
 use data::dashboard::{BufferAction, BufferFocusedAction};
 use data::{
-    Image, Version, buffer, file_transfer, history, isupport, server,
-    server_icon, target,
+    Image, Version, buffer, channel, file_transfer, history, i18n, isupport,
+    server, server_icon, target,
 };
 use iced::Length::Shrink;
 use iced::widget::text::{Ellipsis, LineHeight, Shaping, Wrapping};
 use iced::widget::{
-    Column, Row, Scrollable, Space, button, column, container, pane_grid, row,
-    rule, scrollable, space, stack,
+    Column, Row, Scrollable, Space, button, column, container, mouse_area,
+    operation, pane_grid, row, rule, scrollable, space, stack, text_input,
 };
 use iced::{
     Alignment, Border, ContentFit, Length, Padding, Task, mouse, padding,
 };
 use itertools::Either;
+use nucleo_matcher::pattern::{AtomKind, CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Matcher, Utf32Str};
 use tokio::time;
 
 use super::{Focus, Panes, Server};
+use crate::widget::double_click::double_click_over;
 use crate::widget::text_color_svg::TextColorSvg;
 use crate::widget::{
-    Element, Text, TextExt, context_menu, double_pass, image, text,
+    Element, Text, TextExt, context_menu, double_pass, image, key_press,
+    mouse_wheel, resizable, text,
 };
 use crate::{Theme, font, icon, platform_specific, theme, window};
 
 const CONFIG_RELOAD_DELAY: Duration = Duration::from_secs(1);
+const MIN_WIDTH: f32 = 120.0;
+/// Thickness of the collapsed sidebar when `auto_hide` is enabled.
+const AUTO_HIDE_STRIP: f32 = 10.0;
+/// Grace period before collapsing once the cursor leaves the sidebar.
+const AUTO_HIDE_DELAY: Duration = Duration::from_millis(300);
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -38,7 +50,27 @@ pub enum Message {
     Swap(window::Id, pane_grid::Pane),
     Detach(buffer::Upstream),
     Leave(buffer::Upstream),
+    ExportUserList(Server, target::Channel),
+    RefreshUserList(Server, target::Channel),
+    CompareUserList(window::Id, pane_grid::Pane, Server, target::Channel),
+    PendingUnbans(window::Id, pane_grid::Pane, Server, target::Channel),
+    ChannelSettings(window::Id, pane_grid::Pane, Server, target::Channel),
     CloseAllQueries(Server, Vec<target::Query>),
+    ToggleServerCollapsed(Server),
+    ToggleChannelGroupCollapsed(Server, String),
+    Pin(buffer::Upstream),
+    Unpin(buffer::Upstream),
+    Mute(buffer::Upstream),
+    Unmute(buffer::Upstream),
+    Rejoin(buffer::Upstream),
+    Unarchive(buffer::Upstream),
+    FilterChanged(String),
+    CloseFilter,
+    Expand,
+    Exited,
+    Collapse,
+    Resize(Option<f32>),
+    CycleBuffer(mouse_wheel::Step),
     ToggleCommandBar,
     ToggleThemeEditor,
     ReloadConfigFile,
@@ -59,6 +91,11 @@ pub enum Message {
     Remove(Server),
     SystemInformation(iced::system::Information),
     ShowMutedBuffers(bool),
+    ToggleAudioMute,
+    SnoozeNotifications(data::Buffer),
+    UnsnoozeNotifications(data::Buffer),
+    OpenLogFile(data::Buffer),
+    OpenDataFolder(data::Buffer),
 }
 
 #[derive(Debug, Clone)]
@@ -71,7 +108,22 @@ pub enum Event {
     Swap(window::Id, pane_grid::Pane),
     Detach(buffer::Upstream),
     Leave(buffer::Upstream),
+    ExportUserList(Server, target::Channel),
+    RefreshUserList(Server, target::Channel),
+    CompareUserList(window::Id, pane_grid::Pane, Server, target::Channel),
+    PendingUnbans(window::Id, pane_grid::Pane, Server, target::Channel),
+    ChannelSettings(window::Id, pane_grid::Pane, Server, target::Channel),
     CloseAllQueries(Server, Vec<target::Query>),
+    ToggleServerCollapsed(Server),
+    ToggleChannelGroupCollapsed(Server, String),
+    Pin(buffer::Upstream),
+    Unpin(buffer::Upstream),
+    Mute(buffer::Upstream),
+    Unmute(buffer::Upstream),
+    Rejoin(buffer::Upstream),
+    Unarchive(buffer::Upstream),
+    Resize(Option<f32>),
+    CycleBuffer(mouse_wheel::Step),
     ToggleCommandBar,
     ToggleThemeEditor,
     OpenReleaseWebsite,
@@ -89,13 +141,23 @@ pub enum Event {
     DisableAutoconnect(Server),
     Remove(Server),
     ShowMutedBuffers(bool),
+    ToggleAudioMute,
+    SnoozeNotifications(data::Buffer),
+    UnsnoozeNotifications(data::Buffer),
+    OpenLogFile(data::Buffer),
+    OpenDataFolder(data::Buffer),
 }
 
 #[derive(Clone)]
 pub struct Sidebar {
     pub hidden: bool,
+    /// Whether the auto-hidden sidebar is expanded.
+    expanded: bool,
+    hovered: bool,
     reloading_config: bool,
     system_information: Option<iced::system::Information>,
+    filter: Option<String>,
+    filter_id: iced::widget::Id,
 }
 
 impl Sidebar {
@@ -103,15 +165,40 @@ impl Sidebar {
         (
             Self {
                 hidden: false,
+                expanded: false,
+                hovered: false,
                 reloading_config: false,
                 system_information: None,
+                filter: None,
+                filter_id: iced::widget::Id::unique(),
             },
             iced::system::information().map(Message::SystemInformation),
         )
     }
 
-    pub fn toggle_visibility(&mut self) {
-        self.hidden = !self.hidden;
+    /// Hides or shows the sidebar, or expands and collapses it when
+    /// `auto_hide` is enabled.
+    pub fn toggle_visibility(&mut self, config: &Config) {
+        if config.sidebar.auto_hide && !self.hidden {
+            self.expanded = !self.expanded;
+        } else {
+            self.hidden = !self.hidden;
+        }
+    }
+
+    /// Shows the filter input (revealing the sidebar if needed) and focuses
+    /// it, or hides it and clears the filter if it's already shown.
+    pub fn toggle_filter(&mut self) -> Task<Message> {
+        if self.filter.is_some() && !self.hidden {
+            self.filter = None;
+
+            Task::none()
+        } else {
+            self.hidden = false;
+            self.filter.get_or_insert_default();
+
+            operation::focus(self.filter_id.clone())
+        }
     }
 
     pub fn update(
@@ -151,6 +238,80 @@ impl Sidebar {
             Message::Leave(buffer) => {
                 (Task::none(), Some(Event::Leave(buffer)))
             }
+            Message::ExportUserList(server, channel) => {
+                (Task::none(), Some(Event::ExportUserList(server, channel)))
+            }
+            Message::RefreshUserList(server, channel) => {
+                (Task::none(), Some(Event::RefreshUserList(server, channel)))
+            }
+            Message::CompareUserList(window, pane, server, channel) => (
+                Task::none(),
+                Some(Event::CompareUserList(window, pane, server, channel)),
+            ),
+            Message::PendingUnbans(window, pane, server, channel) => (
+                Task::none(),
+                Some(Event::PendingUnbans(window, pane, server, channel)),
+            ),
+            Message::ChannelSettings(window, pane, server, channel) => (
+                Task::none(),
+                Some(Event::ChannelSettings(window, pane, server, channel)),
+            ),
+            Message::ToggleServerCollapsed(server) => {
+                (Task::none(), Some(Event::ToggleServerCollapsed(server)))
+            }
+            Message::ToggleChannelGroupCollapsed(server, prefix) => (
+                Task::none(),
+                Some(Event::ToggleChannelGroupCollapsed(server, prefix)),
+            ),
+            Message::Pin(buffer) => (Task::none(), Some(Event::Pin(buffer))),
+            Message::Unpin(buffer) => {
+                (Task::none(), Some(Event::Unpin(buffer)))
+            }
+            Message::Mute(buffer) => (Task::none(), Some(Event::Mute(buffer))),
+            Message::Unmute(buffer) => {
+                (Task::none(), Some(Event::Unmute(buffer)))
+            }
+            Message::Rejoin(buffer) => {
+                (Task::none(), Some(Event::Rejoin(buffer)))
+            }
+            Message::Unarchive(buffer) => {
+                (Task::none(), Some(Event::Unarchive(buffer)))
+            }
+            Message::Resize(width) => (
+                Task::none(),
+                Some(Event::Resize(width.map(|width| width.max(MIN_WIDTH)))),
+            ),
+            Message::CycleBuffer(step) => {
+                (Task::none(), Some(Event::CycleBuffer(step)))
+            }
+            Message::FilterChanged(filter) => {
+                self.filter = Some(filter);
+                (Task::none(), None)
+            }
+            Message::CloseFilter => {
+                self.filter = None;
+                (Task::none(), None)
+            }
+            Message::Expand => {
+                self.expanded = true;
+                self.hovered = true;
+                (Task::none(), None)
+            }
+            Message::Exited => {
+                self.hovered = false;
+                (
+                    Task::perform(time::sleep(AUTO_HIDE_DELAY), |()| {
+                        Message::Collapse
+                    }),
+                    None,
+                )
+            }
+            Message::Collapse => {
+                if !self.hovered {
+                    self.expanded = false;
+                }
+                (Task::none(), None)
+            }
             Message::ToggleCommandBar => {
                 (Task::none(), Some(Event::ToggleCommandBar))
             }
@@ -208,6 +369,21 @@ impl Sidebar {
                 Task::none(),
                 Some(Event::ShowMutedBuffers(show_muted_buffers)),
             ),
+            Message::ToggleAudioMute => {
+                (Task::none(), Some(Event::ToggleAudioMute))
+            }
+            Message::SnoozeNotifications(buffer) => {
+                (Task::none(), Some(Event::SnoozeNotifications(buffer)))
+            }
+            Message::UnsnoozeNotifications(buffer) => {
+                (Task::none(), Some(Event::UnsnoozeNotifications(buffer)))
+            }
+            Message::OpenLogFile(buffer) => {
+                (Task::none(), Some(Event::OpenLogFile(buffer)))
+            }
+            Message::OpenDataFolder(buffer) => {
+                (Task::none(), Some(Event::OpenDataFolder(buffer)))
+            }
         }
     }
 
@@ -219,6 +395,7 @@ impl Sidebar {
         version: &'a Version,
         theme: &'a Theme,
         show_muted_buffers: bool,
+        audio_muted: bool,
     ) -> Element<'a, Message> {
         let keyboard = &config.keyboard;
 
@@ -250,6 +427,11 @@ impl Sidebar {
                 icon::circle().style(theme::text::tertiary),
                 dimensions.unread_indicator_size,
             ))
+        } else if audio_muted {
+            Some((
+                icon::speaker().style(theme::text::error),
+                dimensions.icon_badge_size,
+            ))
         } else {
             None
         };
@@ -272,6 +454,7 @@ impl Sidebar {
             config.file_transfer.enabled,
             &config.sidebar.internal_buffers.buffers,
             show_muted_buffers,
+            audio_muted,
         );
 
         if menu.is_empty() {
@@ -332,7 +515,7 @@ impl Sidebar {
 
                     match menu {
                         Menu::QuitApplication => context_button(
-                            text("Quit Halloy"),
+                            text(i18n::tr("sidebar.menu.quit")),
                             Some(&keyboard.quit_application),
                             icon::quit(),
                             Message::QuitApplication,
@@ -340,9 +523,9 @@ impl Sidebar {
                         Menu::ShowMutedBuffers(show_muted_buffers) => {
                             context_button(
                                 text(if show_muted_buffers {
-                                    "Show muted buffers"
+                                    i18n::tr("sidebar.menu.show_muted_buffers")
                                 } else {
-                                    "Hide muted buffers"
+                                    i18n::tr("sidebar.menu.hide_muted_buffers")
                                 }),
                                 Some(if show_muted_buffers {
                                     &keyboard.show_muted_buffers
@@ -357,20 +540,41 @@ impl Sidebar {
                                 Message::ShowMutedBuffers(show_muted_buffers),
                             )
                         }
+                        Menu::ToggleAudioMute(audio_muted) => context_button(
+                            text(if audio_muted {
+                                i18n::tr("sidebar.menu.unmute_sounds")
+                            } else {
+                                i18n::tr("sidebar.menu.mute_sounds")
+                            })
+                            .style(
+                                if audio_muted {
+                                    theme::text::error
+                                } else {
+                                    theme::text::primary
+                                },
+                            ),
+                            Some(&keyboard.toggle_audio_mute),
+                            icon::speaker().style(if audio_muted {
+                                theme::text::error
+                            } else {
+                                theme::text::primary
+                            }),
+                            Message::ToggleAudioMute,
+                        ),
                         Menu::RefreshConfig => context_button(
-                            text("Reload config file"),
+                            text(i18n::tr("sidebar.menu.reload_config")),
                             Some(&keyboard.reload_configuration),
                             icon::refresh(),
                             Message::ReloadConfigFile,
                         ),
                         Menu::CommandBar => context_button(
-                            text("Command Bar"),
+                            text(i18n::tr("sidebar.menu.command_bar")),
                             Some(&keyboard.command_bar),
                             icon::search(),
                             Message::ToggleCommandBar,
                         ),
                         Menu::FileTransfers => context_button(
-                            text("File Transfers")
+                            text(i18n::tr("buffer.file_transfers"))
                                 .style(if file_transfers.is_empty() {
                                     theme::text::primary
                                 } else {
@@ -396,7 +600,7 @@ impl Sidebar {
                             ),
                         ),
                         Menu::Highlights => context_button(
-                            text("Highlights"),
+                            text(i18n::tr("buffer.highlights")),
                             Some(&keyboard.highlights),
                             icon::highlights(),
                             Message::Replace(
@@ -404,7 +608,7 @@ impl Sidebar {
                             ),
                         ),
                         Menu::ChannelDiscovery => context_button(
-                            text("Channel Discovery"),
+                            text(i18n::tr("buffer.channel_discovery")),
                             None,
                             icon::channel_discovery(),
                             Message::Replace(
@@ -412,7 +616,7 @@ impl Sidebar {
                             ),
                         ),
                         Menu::Logs => context_button(
-                            text("Logs")
+                            text(i18n::tr("buffer.logs"))
                                 .style(if logs_has_unread {
                                     theme::text::tertiary
                                 } else {
@@ -433,8 +637,28 @@ impl Sidebar {
                             }),
                             Message::Replace(buffer::Internal::Logs.into()),
                         ),
+                        Menu::Diagnostics => context_button(
+                            text(i18n::tr("buffer.diagnostics")),
+                            None,
+                            icon::lightbulb(),
+                            Message::Replace(
+                                buffer::Internal::Diagnostics.into(),
+                            ),
+                        ),
+                        Menu::Unread => context_button(
+                            text(i18n::tr("buffer.unread")),
+                            None,
+                            icon::circle(),
+                            Message::Replace(buffer::Internal::Unread.into()),
+                        ),
+                        Menu::Ignores => context_button(
+                            text(i18n::tr("buffer.ignores")),
+                            None,
+                            icon::hide(),
+                            Message::Replace(buffer::Internal::Ignores.into()),
+                        ),
                         Menu::ThemeEditor => context_button(
-                            text("Theme Editor"),
+                            text(i18n::tr("sidebar.menu.theme_editor")),
                             Some(&keyboard.theme_editor),
                             icon::theme_editor(),
                             Message::ToggleThemeEditor,
@@ -446,7 +670,7 @@ impl Sidebar {
                             _ => Space::new().width(length).height(1).into(),
                         },
                         Menu::Update => context_button(
-                            text("New version available")
+                            text(i18n::tr("sidebar.menu.new_version"))
                                 .style(theme::text::tertiary)
                                 .font_maybe(
                                     theme::font_style::tertiary(theme)
@@ -457,7 +681,7 @@ impl Sidebar {
                             Message::OpenReleaseWebsite,
                         ),
                         Menu::Version => context_button(
-                            text("About Halloy"),
+                            text(i18n::tr("sidebar.menu.about")),
                             None,
                             icon::about(),
                             Message::OpenAbout {
@@ -471,13 +695,13 @@ impl Sidebar {
                             },
                         ),
                         Menu::Documentation => context_button(
-                            text("Documentation"),
+                            text(i18n::tr("sidebar.menu.documentation")),
                             None,
                             icon::documentation(),
                             Message::OpenDocumentation,
                         ),
                         Menu::ConfigEditor => context_button(
-                            text("Config Editor"),
+                            text(i18n::tr("buffer.config_editor")),
                             Some(&keyboard.open_config_editor),
                             icon::config(),
                             Message::Replace(
@@ -504,11 +728,27 @@ impl Sidebar {
         version: &'a Version,
         theme: &'a Theme,
         show_muted_buffers: bool,
+        audio_muted: bool,
+        snoozed_notifications: &HashMap<data::Buffer, DateTime<Utc>>,
+        collapsed_servers: &HashSet<Server>,
+        pinned_buffers: &HashSet<buffer::Upstream>,
+        muted_buffers: &HashSet<buffer::Upstream>,
+        archived_buffers: &HashSet<buffer::Upstream>,
+        collapsed_channel_groups: &HashSet<(Server, String)>,
+        width: Option<f32>,
     ) -> Option<Element<'a, Message>> {
         if self.hidden {
             return None;
         }
 
+        let auto_hide = config.sidebar.auto_hide && self.filter.is_none();
+
+        if auto_hide && !self.expanded {
+            return Some(auto_hide_strip(servers, history, config));
+        }
+
+        let now = Utc::now();
+
         let content = |width| {
             let user_menu_button =
                 config.sidebar.user_menu.enabled.then(|| {
@@ -519,6 +759,7 @@ impl Sidebar {
                         version,
                         theme,
                         show_muted_buffers,
+                        audio_muted,
                     )
                 });
 
@@ -529,19 +770,78 @@ impl Sidebar {
             }
 
             let mut upstream_buffers = vec![];
+            let mut pinned_upstream_buffers = vec![];
             let mut client_enumeration = 0;
 
+            let mut filter = self.filter.as_deref().and_then(Filter::new);
+            let is_filtering = filter.is_some();
+
             for server in servers.keys() {
+                // A server matching the filter shows all of its buffers.
+                let server_matches = filter
+                    .as_mut()
+                    .is_none_or(|filter| filter.matches(&server.to_string()));
+                let mut is_visible = |name: &str| {
+                    server_matches
+                        || filter
+                            .as_mut()
+                            .is_none_or(|filter| filter.matches(name))
+                };
+
                 let server_has_unread = history.server_has_unread(server);
                 let supports_detach =
                     clients.get_server_supports_detach(server);
                 let casemapping =
                     clients.get_server_casemapping_or_default(server);
+                // Filtering takes precedence so that matches are reachable.
+                let is_collapsed =
+                    collapsed_servers.contains(server) && !is_filtering;
+                let nickname = config
+                    .sidebar
+                    .show_nickname
+                    .then(|| clients.nickname(server))
+                    .flatten()
+                    .map(|nickname| {
+                        (
+                            nickname.to_string(),
+                            clients.get_server_is_away(server),
+                        )
+                    });
 
                 let button =
                     |buffer: buffer::Upstream,
                      kind: history::Kind,
-                     connection_status: ConnectionStatus| {
+                     connection_status: ConnectionStatus,
+                     collapsed: Option<bool>,
+                     pinned: Option<bool>| {
+                        let is_snoozed = snoozed_notifications
+                            .get(&data::Buffer::Upstream(buffer.clone()))
+                            .is_some_and(|until| *until > now);
+                        let is_muted = muted_buffers.contains(&buffer);
+                        // Archived channels are shown as if disconnected
+                        let is_archived = archived_buffers.contains(&buffer)
+                            && buffer.channel().is_some_and(|channel| {
+                                !clients
+                                    .get_channels(server)
+                                    .any(|joined| joined == channel)
+                            });
+                        // Queries only known from the bouncer are shown the
+                        // same way, until a new message arrives.
+                        let is_discovered = history.is_discovered(&kind);
+                        let connection_status = if is_archived || is_discovered
+                        {
+                            ConnectionStatus::Disconnected {
+                                autoconnect: false,
+                                connecting: false,
+                            }
+                        } else {
+                            connection_status
+                        };
+                        let nickname =
+                            matches!(buffer, buffer::Upstream::Server(_))
+                                .then(|| nickname.clone())
+                                .flatten();
+
                         upstream_buffer_button(
                             config,
                             panes,
@@ -556,6 +856,12 @@ impl Sidebar {
                             history,
                             width,
                             theme,
+                            is_snoozed,
+                            is_muted,
+                            is_archived,
+                            collapsed,
+                            pinned,
+                            nickname,
                         )
                     };
 
@@ -566,7 +872,12 @@ impl Sidebar {
                         data::client::State::Disconnected {
                             autoconnect,
                             connecting,
+                            ..
                         } => {
+                            if !server_matches {
+                                continue;
+                            }
+
                             // Disconnected server.
                             upstream_buffers.push(button(
                                 buffer::Upstream::Server(server.clone()),
@@ -575,12 +886,56 @@ impl Sidebar {
                                     autoconnect: *autoconnect,
                                     connecting: *connecting,
                                 },
+                                None,
+                                None,
                             ));
                         }
                         data::client::State::Ready(connection) => {
                             let registration_complete =
                                 connection.registration_complete();
 
+                            // Archived channels follow the joined ones
+                            let mut archived = archived_buffers
+                                .iter()
+                                .filter_map(|buffer| match buffer {
+                                    buffer::Upstream::Channel(
+                                        archived_server,
+                                        channel,
+                                    ) if archived_server == server
+                                        && !connection.channels().any(
+                                            |joined| joined == channel,
+                                        ) =>
+                                    {
+                                        Some(channel)
+                                    }
+                                    _ => None,
+                                })
+                                .collect::<Vec<_>>();
+                            archived.sort();
+
+                            let channels = connection
+                                .channels()
+                                .chain(archived)
+                                .filter(|channel| is_visible(channel.as_str()))
+                                .collect::<Vec<_>>();
+                            let queries = history
+                                .get_unique_queries(server)
+                                .into_iter()
+                                .map(|query| {
+                                    clients
+                                        .resolve_query(server, query)
+                                        .unwrap_or(query)
+                                })
+                                .filter(|query| is_visible(query.as_str()))
+                                .collect::<Vec<_>>();
+
+                            if !server_matches
+                                && channels.is_empty()
+                                && queries.is_empty()
+                            {
+                                continue;
+                            }
+
                             // Connected server.
                             upstream_buffers.push(button(
                                 buffer::Upstream::Server(server.clone()),
@@ -588,45 +943,155 @@ impl Sidebar {
                                 ConnectionStatus::Connected {
                                     registration_complete,
                                 },
+                                Some(is_collapsed),
+                                None,
                             ));
 
-                            // Channels from the connected server.
-                            for channel in connection.channels() {
-                                upstream_buffers.push(button(
-                                    buffer::Upstream::Channel(
-                                        server.clone(),
-                                        channel.clone(),
-                                    ),
-                                    history::Kind::Channel(
-                                        server.clone(),
-                                        channel.clone(),
-                                    ),
-                                    ConnectionStatus::Connected {
-                                        registration_complete,
-                                    },
-                                ));
+                            // Channels and queries from the connected server.
+                            let buffers = channels
+                                .into_iter()
+                                .map(|channel| {
+                                    (
+                                        buffer::Upstream::Channel(
+                                            server.clone(),
+                                            channel.clone(),
+                                        ),
+                                        history::Kind::Channel(
+                                            server.clone(),
+                                            channel.clone(),
+                                        ),
+                                    )
+                                })
+                                .chain(queries.into_iter().map(|query| {
+                                    (
+                                        buffer::Upstream::Query(
+                                            server.clone(),
+                                            query.clone(),
+                                        ),
+                                        history::Kind::Query(
+                                            server.clone(),
+                                            query.clone(),
+                                        ),
+                                    )
+                                }));
+
+                            let connection_status =
+                                ConnectionStatus::Connected {
+                                    registration_complete,
+                                };
+                            let mut unpinned_buffers = vec![];
+
+                            for (buffer, kind) in buffers {
+                                // Pinned buffers are shown at the top of the
+                                // sidebar, even while the server is
+                                // collapsed.
+                                if pinned_buffers.contains(&buffer) {
+                                    pinned_upstream_buffers.push(button(
+                                        buffer,
+                                        kind,
+                                        connection_status,
+                                        None,
+                                        Some(true),
+                                    ));
+                                } else if !is_collapsed {
+                                    unpinned_buffers.push((buffer, kind));
+                                }
                             }
 
-                            // Queries from the connected server.
-                            let queries = history.get_unique_queries(server);
-                            for query in queries {
-                                let query = clients
-                                    .resolve_query(server, query)
-                                    .unwrap_or(query);
-
-                                upstream_buffers.push(button(
-                                    buffer::Upstream::Query(
-                                        server.clone(),
-                                        query.clone(),
-                                    ),
-                                    history::Kind::Query(
-                                        server.clone(),
-                                        query.clone(),
-                                    ),
-                                    ConnectionStatus::Connected {
-                                        registration_complete,
-                                    },
-                                ));
+                            let grouped = match config.sidebar.grouping {
+                                sidebar::Grouping::None => unpinned_buffers
+                                    .into_iter()
+                                    .map(channel::Grouped::Single)
+                                    .collect(),
+                                sidebar::Grouping::Prefix => {
+                                    channel::group_by_prefix(
+                                        unpinned_buffers,
+                                        |(buffer, _)| match buffer {
+                                            buffer::Upstream::Channel(
+                                                _,
+                                                channel,
+                                            ) => Some(channel.as_str()),
+                                            _ => None,
+                                        },
+                                    )
+                                }
+                            };
+
+                            for grouped in grouped {
+                                match grouped {
+                                    channel::Grouped::Single((
+                                        buffer,
+                                        kind,
+                                    )) => {
+                                        upstream_buffers.push(button(
+                                            buffer,
+                                            kind,
+                                            connection_status,
+                                            None,
+                                            Some(false),
+                                        ));
+                                    }
+                                    channel::Grouped::Group {
+                                        prefix,
+                                        channels,
+                                    } => {
+                                        let is_group_collapsed =
+                                            collapsed_channel_groups.contains(
+                                                &(
+                                                    server.clone(),
+                                                    prefix.clone(),
+                                                ),
+                                            ) && !is_filtering;
+                                        let has_unread = is_group_collapsed
+                                            && channels.iter().any(
+                                                |(_, kind)| {
+                                                    history.has_unread(kind)
+                                                },
+                                            );
+
+                                        upstream_buffers.push(
+                                            channel_group_button(
+                                                config,
+                                                server,
+                                                prefix,
+                                                is_group_collapsed,
+                                                has_unread,
+                                                width,
+                                            ),
+                                        );
+
+                                        if is_group_collapsed {
+                                            continue;
+                                        }
+
+                                        for (buffer, kind) in channels {
+                                            let channel_button = button(
+                                                buffer,
+                                                kind,
+                                                connection_status,
+                                                None,
+                                                Some(false),
+                                            );
+
+                                            // Indent grouped channels.
+                                            upstream_buffers.push(
+                                                if config
+                                                    .sidebar
+                                                    .position
+                                                    .is_horizontal()
+                                                {
+                                                    channel_button
+                                                } else {
+                                                    container(channel_button)
+                                                        .padding(padding::left(
+                                                            12,
+                                                        ))
+                                                        .into()
+                                                },
+                                            );
+                                        }
+                                    }
+                                }
                             }
 
                             // Separator between servers.
@@ -654,6 +1119,25 @@ impl Sidebar {
                 }
             }
 
+            if !pinned_upstream_buffers.is_empty() {
+                if !upstream_buffers.is_empty() {
+                    pinned_upstream_buffers.push(
+                        if config.sidebar.position.is_horizontal() {
+                            space::horizontal()
+                                .width(config.sidebar.spacing.server)
+                                .into()
+                        } else {
+                            space::vertical()
+                                .height(config.sidebar.spacing.server)
+                                .into()
+                        },
+                    );
+                }
+
+                pinned_upstream_buffers.extend(upstream_buffers);
+                upstream_buffers = pinned_upstream_buffers;
+            }
+
             let internal_buffers: Vec<_> = config
                 .sidebar
                 .internal_buffers
@@ -663,28 +1147,44 @@ impl Sidebar {
                     let (buffer, title) = match internal_buffer {
                         config::sidebar::InternalBuffer::ConfigEditor => (
                             buffer::Internal::ConfigEditor,
-                            "Config Editor",
+                            i18n::tr("buffer.config_editor"),
                         ),
                         data::config::sidebar::InternalBuffer::FileTransfers => {
                             config.file_transfer.enabled.then_some((
                                 buffer::Internal::FileTransfers,
-                                "File Transfers",
+                                i18n::tr("buffer.file_transfers"),
                             ))?
                         }
                         data::config::sidebar::InternalBuffer::ChannelDiscovery => (
                             buffer::Internal::ChannelDiscovery(None),
-                            "Channel Discovery",
+                            i18n::tr("buffer.channel_discovery"),
                         ),
                         data::config::sidebar::InternalBuffer::Highlights => (
                             buffer::Internal::Highlights,
-                            "Highlights",
+                            i18n::tr("buffer.highlights"),
                         ),
                         data::config::sidebar::InternalBuffer::Logs => (
                             buffer::Internal::Logs,
-                            "Logs",
+                            i18n::tr("buffer.logs"),
+                        ),
+                        data::config::sidebar::InternalBuffer::Diagnostics => (
+                            buffer::Internal::Diagnostics,
+                            i18n::tr("buffer.diagnostics"),
+                        ),
+                        data::config::sidebar::InternalBuffer::Unread => (
+                            buffer::Internal::Unread,
+                            i18n::tr("buffer.unread"),
+                        ),
+                        data::config::sidebar::InternalBuffer::Ignores => (
+                            buffer::Internal::Ignores,
+                            i18n::tr("buffer.ignores"),
                         ),
                     };
 
+                    if filter.as_mut().is_some_and(|filter| !filter.matches(title)) {
+                        return None;
+                    }
+
                     if show_muted_buffers || should_show_internal_buffer(buffer.clone(), config, history) {
                         Some(internal_buffer_button(config, panes, focus, buffer, title, history, width, theme))
                     } else {
@@ -716,6 +1216,34 @@ impl Sidebar {
             }
             buffers.extend(right);
 
+            let filter_input = |width, padding| {
+                self.filter.as_ref().map(|filter| {
+                    container(key_press(
+                        text_input(i18n::tr("sidebar.filter"), filter)
+                            .id(self.filter_id.clone())
+                            .on_input(Message::FilterChanged)
+                            .padding([2, 4])
+                            .width(width),
+                        key_press::Key::Named(key_press::Named::Escape),
+                        key_press::Modifiers::default(),
+                        Message::CloseFilter,
+                    ))
+                    .padding(padding)
+                })
+            };
+
+            let wheel = |buffers: Scrollable<'a, Message, Theme>| {
+                if config.mouse.sidebar.wheel_cycles_buffers {
+                    mouse_wheel::steps(buffers, Message::CycleBuffer)
+                } else if config.sidebar.position.is_horizontal()
+                    && !config.mouse.sidebar.scroll_requires_shift
+                {
+                    mouse_wheel::horizontal(buffers)
+                } else {
+                    buffers.into()
+                }
+            };
+
             match config.sidebar.position {
                 sidebar::Position::Left | sidebar::Position::Right => {
                     let column_padding = if matches!(
@@ -728,7 +1256,7 @@ impl Sidebar {
                     };
 
                     // Add buffers to a column.
-                    let buffers = column![
+                    let buffers = column![wheel(
                         Scrollable::new(
                             Column::with_children(buffers)
                                 .spacing(1)
@@ -744,10 +1272,11 @@ impl Sidebar {
                                     .spacing(4)
                             )
                         )
-                    ];
+                    )];
 
                     // Wrap buffers in a column with user_menu_button
                     let content = column![
+                        filter_input(width, padding::bottom(4)),
                         container(buffers).height(Length::Fill),
                         user_menu_button,
                     ];
@@ -756,7 +1285,7 @@ impl Sidebar {
                 }
                 sidebar::Position::Top | sidebar::Position::Bottom => {
                     // Add buffers to a row.
-                    let buffers = row![
+                    let buffers = row![wheel(
                         Scrollable::new(
                             Row::with_children(buffers)
                                 .spacing(2)
@@ -772,10 +1301,11 @@ impl Sidebar {
                                     .spacing(4)
                             )
                         )
-                    ];
+                    )];
 
                     // Wrap buffers in a row with user_menu_button
                     let content = row![
+                        filter_input(Length::Fixed(160.0), padding::right(4)),
                         container(buffers).width(Length::Fill),
                         user_menu_button,
                     ]
@@ -809,25 +1339,116 @@ impl Sidebar {
                 .top(platform_specific_padding),
         };
 
-        let content = if config.sidebar.position.is_horizontal() {
-            container(
+        let content: Element<'a, Message> = match config.sidebar.position {
+            sidebar::Position::Top | sidebar::Position::Bottom => container(
                 content(Length::Shrink).width(Length::Fill).padding(padding),
             )
-        } else {
-            let first_pass = content(Length::Shrink);
-            let second_pass = content(Length::Fill);
+            .into(),
+            sidebar::Position::Left | sidebar::Position::Right => {
+                // A width set by dragging the inner edge takes precedence
+                // over `max_width`
+                let content = if let Some(width) = width {
+                    container(content(Length::Fill)).width(width)
+                } else {
+                    let first_pass = content(Length::Shrink);
+                    let second_pass = content(Length::Fill);
+
+                    container(double_pass(first_pass, second_pass)).width(
+                        Shrink.max(
+                            config
+                                .sidebar
+                                .max_width
+                                .map_or(f32::INFINITY, f32::from),
+                        ),
+                    )
+                };
 
-            container(double_pass(first_pass, second_pass))
-                .width(Shrink.max(
-                    config.sidebar.max_width.map_or(f32::INFINITY, f32::from),
-                ))
-                .padding(padding)
+                let edge = match config.sidebar.position {
+                    sidebar::Position::Right => resizable::Edge::Left,
+                    _ => resizable::Edge::Right,
+                };
+
+                resizable(content.padding(padding), edge, Message::Resize)
+            }
         };
 
-        Some(content.into())
+        if auto_hide {
+            Some(
+                mouse_area(content)
+                    .on_enter(Message::Expand)
+                    .on_exit(Message::Exited)
+                    .into(),
+            )
+        } else {
+            Some(content)
+        }
     }
 }
 
+/// The collapsed auto-hide sidebar, showing which servers have unread
+/// messages or highlights.  Hovering it expands the sidebar.
+fn auto_hide_strip<'a>(
+    servers: &server::Map,
+    history: &history::Manager,
+    config: &Config,
+) -> Element<'a, Message> {
+    let dimensions = Dimensions::from(&config.sidebar);
+
+    let indicators = servers.keys().filter_map(|server| {
+        let (icon, size) = if history.server_has_highlight(server) {
+            (
+                icon::from_icon(config.sidebar.highlight_indicator.icon)
+                    .unwrap_or_else(icon::circle)
+                    .style(theme::text::highlight_indicator),
+                dimensions.highlight_indicator_size,
+            )
+        } else if history.server_has_unread(server) {
+            (
+                icon::from_icon(config.sidebar.unread_indicator.icon)
+                    .unwrap_or_else(icon::circle)
+                    .style(theme::text::unread_indicator),
+                dimensions.unread_indicator_size,
+            )
+        } else {
+            return None;
+        };
+
+        let size = size.clamp(4, AUTO_HIDE_STRIP as u32 - 2);
+
+        Some(icon.width(size).height(size).into())
+    });
+
+    let content: Element<'a, Message> =
+        if config.sidebar.position.is_horizontal() {
+            container(
+                Row::with_children(indicators)
+                    .spacing(4)
+                    .align_y(Alignment::Center),
+            )
+            .padding([0, 6])
+            .width(Length::Fill)
+            .height(AUTO_HIDE_STRIP)
+            .align_y(Alignment::Center)
+            .into()
+        } else {
+            container(
+                Column::with_children(indicators)
+                    .spacing(4)
+                    .align_x(Alignment::Center),
+            )
+            .padding(
+                padding::top(6 + platform_specific::sidebar_padding(config))
+                    .bottom(6),
+            )
+            .width(AUTO_HIDE_STRIP)
+            .height(Length::Fill)
+            .align_x(Alignment::Center)
+            .into()
+        };
+
+    mouse_area(content).on_enter(Message::Expand).into()
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Menu {
     RefreshConfig,
@@ -837,6 +1458,9 @@ enum Menu {
     Highlights,
     ChannelDiscovery,
     Logs,
+    Diagnostics,
+    Unread,
+    Ignores,
     FileTransfers,
     Version,
     Update,
@@ -844,6 +1468,7 @@ enum Menu {
     Documentation,
     QuitApplication,
     ShowMutedBuffers(bool),
+    ToggleAudioMute(bool),
 }
 
 impl Menu {
@@ -852,6 +1477,7 @@ impl Menu {
         file_transfer_enabled: bool,
         internal_buffers_in_sidebar: &[config::sidebar::InternalBuffer],
         show_muted_buffers: bool,
+        audio_muted: bool,
     ) -> Vec<Self> {
         let mut list = vec![Self::Version];
 
@@ -884,17 +1510,36 @@ impl Menu {
             list.push(Self::Highlights);
         }
 
+        if !internal_buffers_in_sidebar
+            .contains(&config::sidebar::InternalBuffer::Unread)
+        {
+            list.push(Self::Unread);
+        }
+
         if !internal_buffers_in_sidebar
             .contains(&config::sidebar::InternalBuffer::Logs)
         {
             list.push(Self::Logs);
         }
 
+        if !internal_buffers_in_sidebar
+            .contains(&config::sidebar::InternalBuffer::Diagnostics)
+        {
+            list.push(Self::Diagnostics);
+        }
+
+        if !internal_buffers_in_sidebar
+            .contains(&config::sidebar::InternalBuffer::Ignores)
+        {
+            list.push(Self::Ignores);
+        }
+
         list.extend([
             Self::ConfigEditor,
             Self::RefreshConfig,
             Self::ThemeEditor,
             Self::ShowMutedBuffers(!show_muted_buffers),
+            Self::ToggleAudioMute(audio_muted),
             Self::QuitApplication,
         ]);
 
@@ -910,13 +1555,28 @@ enum Entry {
     DisableAutoconnect,
     MarkAsRead,
     MarkServerAsRead,
+    SnoozeNotifications,
+    UnsnoozeNotifications,
+    Pin,
+    Unpin,
+    Mute,
+    Unmute,
     Close(window::Id, pane_grid::Pane),
     CloseAllQueries,
     NewPane,
     Popout,
     Replace,
     Swap(window::Id, pane_grid::Pane),
+    ExportUserList,
+    RefreshUserList,
+    CompareUserList(window::Id, pane_grid::Pane),
+    PendingUnbans(window::Id, pane_grid::Pane),
+    ChannelSettings(window::Id, pane_grid::Pane),
+    OpenLogFile,
+    OpenDataFolder,
     Detach,
+    Rejoin,
+    Unarchive,
     Leave,
     Remove,
 }
@@ -930,6 +1590,10 @@ impl Entry {
         connection_status: Option<ConnectionStatus>,
         supports_detach: bool,
         has_history: bool,
+        snoozed: Option<bool>,
+        pinned: Option<bool>,
+        muted: Option<bool>,
+        archived: bool,
     ) -> Vec<Self> {
         use Entry::*;
 
@@ -958,7 +1622,25 @@ impl Entry {
         }
 
         if has_history {
-            entries.push(MarkAsRead);
+            entries.extend([MarkAsRead, OpenLogFile, OpenDataFolder]);
+        }
+
+        match snoozed {
+            Some(true) => entries.push(UnsnoozeNotifications),
+            Some(false) => entries.push(SnoozeNotifications),
+            None => (),
+        }
+
+        match pinned {
+            Some(true) => entries.push(Unpin),
+            Some(false) => entries.push(Pin),
+            None => (),
+        }
+
+        match muted {
+            Some(true) => entries.push(Unmute),
+            Some(false) => entries.push(Mute),
+            None => (),
         }
 
         match open {
@@ -972,12 +1654,33 @@ impl Entry {
                 if (Focus { window, pane }) != focus {
                     entries.push(Swap(window, pane));
                 }
+                // Available while disconnected, to review bans which could
+                // not be lifted.
+                if matches!(
+                    buffer,
+                    buffer::Buffer::Upstream(buffer::Upstream::Channel(_, _))
+                ) {
+                    entries.push(PendingUnbans(window, pane));
+                }
             }
         }
 
         if connection_status.is_some_and(|connection_status| {
             matches!(connection_status, ConnectionStatus::Connected { .. })
         }) {
+            if matches!(
+                buffer,
+                buffer::Buffer::Upstream(buffer::Upstream::Channel(_, _))
+            ) {
+                entries.extend([ExportUserList, RefreshUserList]);
+
+                if let Some((window, pane)) = open {
+                    entries.extend([
+                        CompareUserList(window, pane),
+                        ChannelSettings(window, pane),
+                    ]);
+                }
+            }
             if matches!(
                 buffer,
                 buffer::Buffer::Upstream(buffer::Upstream::Channel(_, _))
@@ -988,11 +1691,62 @@ impl Entry {
             entries.push(Leave);
         }
 
+        if archived {
+            entries.extend([Rejoin, Unarchive]);
+        }
+
         entries.sort();
         entries
     }
 }
 
+fn channel_group_button<'a>(
+    config: &'a Config,
+    server: &Server,
+    prefix: String,
+    collapsed: bool,
+    has_unread: bool,
+    width: Length,
+) -> Element<'a, Message> {
+    let chevron = if collapsed {
+        icon::collapsed()
+    } else {
+        icon::expanded()
+    };
+
+    let font_size = config
+        .sidebar
+        .secondary_font_size
+        .or(config.font.size)
+        .map(f32::from);
+
+    // A collapsed group indicates unread messages in its hidden channels
+    let title_style = if has_unread {
+        theme::text::unread_indicator
+    } else {
+        theme::text::secondary
+    };
+
+    let content = row![
+        chevron.style(theme::text::secondary).width(10).height(10),
+        text(prefix.clone())
+            .line_height(LineHeight::Relative(1.0))
+            .size_maybe(font_size)
+            .style(title_style)
+            .shaping(Shaping::Advanced)
+            .wrapping(Wrapping::None)
+            .ellipsis(Ellipsis::End),
+    ]
+    .spacing(6)
+    .align_y(Alignment::Center);
+
+    button(content.width(width))
+        .style(theme::button::bare)
+        .padding(config.sidebar.padding.buffer)
+        .on_press(Message::ToggleChannelGroupCollapsed(server.clone(), prefix))
+        .into()
+}
+
 fn upstream_buffer_button<'a>(
     config: &'a Config,
     panes: &'a Panes,
@@ -1007,7 +1761,20 @@ fn upstream_buffer_button<'a>(
     history: &'a history::Manager,
     width: Length,
     theme: &'a Theme,
+    is_snoozed: bool,
+    // Whether notifications and unread indicators are suppressed
+    is_muted: bool,
+    // A channel we've left, kept read-only
+    is_archived: bool,
+    // Whether a server's channels and queries are collapsed, if they can be
+    collapsed: Option<bool>,
+    // Whether the buffer is pinned, if it can be
+    pinned: Option<bool>,
+    // Our nickname on a server, and whether we're away
+    nickname: Option<(String, bool)>,
 ) -> Element<'a, Message> {
+    let is_collapsed = collapsed.unwrap_or_default();
+
     let open = panes.iter().find_map(|(window_id, pane, state)| {
         (state.buffer.upstream() == Some(&buffer)).then_some((window_id, pane))
     });
@@ -1017,7 +1784,11 @@ fn upstream_buffer_button<'a>(
 
     let can_mark_as_read = history.can_mark_as_read(&kind);
 
-    let has_unread = if config.sidebar.unread_indicator.show_on_open_buffers
+    // A collapsed server indicates unread messages and highlights of its
+    // hidden channels and queries
+    let has_unread = if is_collapsed {
+        server_has_unread
+    } else if config.sidebar.unread_indicator.show_on_open_buffers
         || !is_visible
     {
         history.has_unread(&kind)
@@ -1025,14 +1796,19 @@ fn upstream_buffer_button<'a>(
         false
     };
 
-    let has_highlight =
-        if config.sidebar.highlight_indicator.show_on_open_buffers
-            || !is_visible
-        {
-            history.has_highlight(&kind)
-        } else {
-            false
-        };
+    let has_highlight = if is_collapsed {
+        history.server_has_highlight(buffer.server())
+    } else if config.sidebar.highlight_indicator.show_on_open_buffers
+        || !is_visible
+    {
+        history.has_highlight(&kind)
+    } else {
+        false
+    };
+
+    // Muted buffers never indicate unread messages or highlights
+    let has_unread = has_unread && !is_muted;
+    let has_highlight = has_highlight && !is_muted;
 
     let is_focused = panes.iter().find_map(|(window_id, pane, state)| {
         (Focus {
@@ -1070,6 +1846,9 @@ fn upstream_buffer_button<'a>(
     let show_unread_title = has_unread
         && config.sidebar.unread_indicator.title
         && should_indicate_unread;
+    let show_unread_count = has_unread
+        && config.sidebar.unread_indicator.count
+        && should_indicate_unread;
     let show_highlight_title = has_highlight
         && config.sidebar.highlight_indicator.title
         && should_indicate_highlight;
@@ -1087,6 +1866,8 @@ fn upstream_buffer_button<'a>(
         } else {
             theme::text::secondary
         }
+    } else if is_muted {
+        theme::text::secondary
     } else {
         theme::text::primary
     };
@@ -1214,6 +1995,31 @@ fn upstream_buffer_button<'a>(
                         .ellipsis(Ellipsis::End),
                 );
             }
+
+            if let Some((nickname, is_away)) = nickname {
+                let font_size = config
+                    .sidebar
+                    .secondary_font_size
+                    .or(config.font.size)
+                    .map(f32::from);
+
+                let nickname = if is_away {
+                    format!("{nickname} ({})", i18n::tr("sidebar.away"))
+                } else {
+                    nickname
+                };
+
+                content = content.push(Space::new().width(6));
+                content = content.push(
+                    text(nickname)
+                        .line_height(LineHeight::Relative(1.0))
+                        .size_maybe(font_size)
+                        .style(theme::text::secondary)
+                        .shaping(Shaping::Advanced)
+                        .wrapping(Wrapping::None)
+                        .ellipsis(Ellipsis::End),
+                );
+            }
         }
         buffer::Upstream::Channel(_, channel) => {
             let font_size = config
@@ -1260,6 +2066,70 @@ fn upstream_buffer_button<'a>(
         }
     }
 
+    if show_unread_count {
+        let (unread_count, highlight_count) = if is_collapsed {
+            (
+                history.server_unread_count(buffer.server()),
+                history.server_highlight_count(buffer.server()),
+            )
+        } else {
+            (history.unread_count(&kind), history.highlight_count(&kind))
+        };
+
+        let font_size = config
+            .sidebar
+            .secondary_font_size
+            .or(config.font.size)
+            .map(f32::from);
+        let count_text = |count: String| {
+            text(count)
+                .line_height(LineHeight::Relative(1.0))
+                .size_maybe(font_size)
+                .wrapping(Wrapping::None)
+        };
+
+        content = content.push(Space::new().width(6));
+        // Messages from before this session are not counted
+        content = content.push(
+            count_text(if unread_count > 0 {
+                unread_count.to_string()
+            } else {
+                "•".to_string()
+            })
+            .style(theme::text::unread_indicator),
+        );
+
+        if has_highlight && should_indicate_highlight && highlight_count > 0 {
+            content = content.push(Space::new().width(4));
+            content = content.push(
+                count_text(highlight_count.to_string())
+                    .style(theme::text::highlight_indicator),
+            );
+        }
+    }
+
+    if let Some(collapsed) = collapsed
+        && let buffer::Upstream::Server(server) = &buffer
+    {
+        let chevron = if collapsed {
+            icon::collapsed()
+        } else {
+            icon::expanded()
+        };
+
+        content = if config.sidebar.position.is_horizontal() {
+            content.push(Space::new().width(4))
+        } else {
+            content.push(space::horizontal())
+        };
+        content = content.push(
+            button(chevron.style(theme::text::secondary).width(10).height(10))
+                .padding(2)
+                .style(theme::button::bare)
+                .on_press(Message::ToggleServerCollapsed(server.clone())),
+        );
+    }
+
     let base =
         button(content.width(width).padding(Padding::default().bottom(1)))
             .style(move |theme, status| {
@@ -1322,6 +2192,39 @@ fn upstream_buffer_button<'a>(
                 }
             });
 
+    let click_action = |action| match action {
+        SidebarClickAction::NewPane => {
+            Some(Message::New(buffer.clone().into()))
+        }
+        SidebarClickAction::ReplacePane => {
+            Some(Message::Replace(buffer.clone().into()))
+        }
+        SidebarClickAction::NewWindow => {
+            Some(Message::Popout(buffer.clone().into()))
+        }
+        SidebarClickAction::ClosePane => {
+            open.map(|(window, pane)| Message::Close(window, pane))
+        }
+        SidebarClickAction::Leave => Some(Message::Leave(buffer.clone())),
+        SidebarClickAction::MarkAsRead => {
+            Some(Message::MarkAsRead(buffer.clone().into()))
+        }
+    };
+
+    let mut base: Element<'a, Message> = base.into();
+
+    if let Some(message) =
+        config.actions.sidebar.middle_click.and_then(click_action)
+    {
+        base = mouse_area(base).on_middle_press(message).into();
+    }
+
+    if let Some(message) =
+        config.actions.sidebar.double_click.and_then(click_action)
+    {
+        base = double_click_over(base, message);
+    }
+
     let entries = Entry::list(
         &buffer.clone().into(),
         panes.len(),
@@ -1330,6 +2233,10 @@ fn upstream_buffer_button<'a>(
         Some(connection_status),
         supports_detach,
         true,
+        Some(is_snoozed),
+        pinned,
+        Some(is_muted),
+        is_archived,
     );
 
     if entries.is_empty() {
@@ -1352,7 +2259,7 @@ fn upstream_buffer_button<'a>(
                             .collect::<Vec<_>>();
 
                         (
-                            "Close all queries",
+                            i18n::tr("sidebar.buffer.close_all_queries"),
                             if queries.is_empty() {
                                 None
                             } else {
@@ -1364,7 +2271,7 @@ fn upstream_buffer_button<'a>(
                         )
                     }
                     Entry::MarkServerAsRead => (
-                        "Mark entire server as read",
+                        i18n::tr("sidebar.buffer.mark_server_as_read"),
                         if server_has_unread {
                             Some(Message::MarkServerAsRead(
                                 buffer.server().clone(),
@@ -1375,9 +2282,11 @@ fn upstream_buffer_button<'a>(
                     ),
                     Entry::MarkAsRead => (
                         if matches!(&buffer, buffer::Upstream::Server(_)) {
-                            "Mark server buffer as read"
+                            i18n::tr(
+                                "sidebar.buffer.mark_server_buffer_as_read",
+                            )
                         } else {
-                            "Mark as read"
+                            i18n::tr("sidebar.buffer.mark_as_read")
                         },
                         if can_mark_as_read {
                             Some(Message::MarkAsRead(buffer.clone().into()))
@@ -1385,51 +2294,171 @@ fn upstream_buffer_button<'a>(
                             None
                         },
                     ),
+                    Entry::SnoozeNotifications => (
+                        i18n::tr("sidebar.buffer.snooze_notifications"),
+                        Some(Message::SnoozeNotifications(
+                            buffer.clone().into(),
+                        )),
+                    ),
+                    Entry::UnsnoozeNotifications => (
+                        i18n::tr("sidebar.buffer.resume_notifications"),
+                        Some(Message::UnsnoozeNotifications(
+                            buffer.clone().into(),
+                        )),
+                    ),
+                    Entry::Pin => (
+                        i18n::tr("sidebar.buffer.pin"),
+                        Some(Message::Pin(buffer.clone())),
+                    ),
+                    Entry::Unpin => (
+                        i18n::tr("sidebar.buffer.unpin"),
+                        Some(Message::Unpin(buffer.clone())),
+                    ),
+                    Entry::Mute => (
+                        i18n::tr("sidebar.buffer.mute"),
+                        Some(Message::Mute(buffer.clone())),
+                    ),
+                    Entry::Unmute => (
+                        i18n::tr("sidebar.buffer.unmute"),
+                        Some(Message::Unmute(buffer.clone())),
+                    ),
                     Entry::NewPane => (
-                        "Open in new pane",
+                        i18n::tr("sidebar.buffer.open_in_new_pane"),
                         Some(Message::New(buffer.clone().into())),
                     ),
                     Entry::Popout => (
-                        "Open in new window",
+                        i18n::tr("sidebar.buffer.open_in_new_window"),
                         Some(Message::Popout(buffer.clone().into())),
                     ),
                     Entry::Replace => (
-                        "Replace current pane",
+                        i18n::tr("sidebar.buffer.replace_pane"),
                         Some(Message::Replace(buffer.clone().into())),
                     ),
-                    Entry::Close(window, pane) => {
-                        ("Close pane", Some(Message::Close(window, pane)))
-                    }
+                    Entry::Close(window, pane) => (
+                        i18n::tr("sidebar.buffer.close_pane"),
+                        Some(Message::Close(window, pane)),
+                    ),
                     Entry::Swap(window, pane) => (
-                        "Swap with current pane",
+                        i18n::tr("sidebar.buffer.swap_pane"),
                         Some(Message::Swap(window, pane)),
                     ),
+                    Entry::ExportUserList => (
+                        i18n::tr("sidebar.buffer.export_user_list"),
+                        if let buffer::Upstream::Channel(server, channel) =
+                            &buffer
+                        {
+                            Some(Message::ExportUserList(
+                                server.clone(),
+                                channel.clone(),
+                            ))
+                        } else {
+                            None
+                        },
+                    ),
+                    Entry::RefreshUserList => (
+                        i18n::tr("sidebar.buffer.refresh_user_list"),
+                        if let buffer::Upstream::Channel(server, channel) =
+                            &buffer
+                        {
+                            Some(Message::RefreshUserList(
+                                server.clone(),
+                                channel.clone(),
+                            ))
+                        } else {
+                            None
+                        },
+                    ),
+                    Entry::CompareUserList(window, pane) => (
+                        i18n::tr("sidebar.buffer.compare_user_list"),
+                        if let buffer::Upstream::Channel(server, channel) =
+                            &buffer
+                        {
+                            Some(Message::CompareUserList(
+                                window,
+                                pane,
+                                server.clone(),
+                                channel.clone(),
+                            ))
+                        } else {
+                            None
+                        },
+                    ),
+                    Entry::PendingUnbans(window, pane) => (
+                        i18n::tr("sidebar.buffer.pending_unbans"),
+                        if let buffer::Upstream::Channel(server, channel) =
+                            &buffer
+                        {
+                            Some(Message::PendingUnbans(
+                                window,
+                                pane,
+                                server.clone(),
+                                channel.clone(),
+                            ))
+                        } else {
+                            None
+                        },
+                    ),
+                    Entry::ChannelSettings(window, pane) => (
+                        i18n::tr("sidebar.buffer.channel_settings"),
+                        if let buffer::Upstream::Channel(server, channel) =
+                            &buffer
+                        {
+                            Some(Message::ChannelSettings(
+                                window,
+                                pane,
+                                server.clone(),
+                                channel.clone(),
+                            ))
+                        } else {
+                            None
+                        },
+                    ),
+                    Entry::OpenLogFile => (
+                        i18n::tr("sidebar.buffer.open_log_file"),
+                        Some(Message::OpenLogFile(buffer.clone().into())),
+                    ),
+                    Entry::OpenDataFolder => (
+                        i18n::tr("sidebar.buffer.open_data_folder"),
+                        Some(Message::OpenDataFolder(buffer.clone().into())),
+                    ),
                     Entry::Detach => (
-                        "Detach from channel",
+                        i18n::tr("sidebar.buffer.detach"),
                         Some(Message::Detach(buffer.clone())),
                     ),
+                    Entry::Rejoin => (
+                        i18n::tr("sidebar.buffer.rejoin_channel"),
+                        Some(Message::Rejoin(buffer.clone())),
+                    ),
+                    Entry::Unarchive => (
+                        i18n::tr("sidebar.buffer.remove_archived_channel"),
+                        Some(Message::Unarchive(buffer.clone())),
+                    ),
                     Entry::Leave => (
                         match &buffer {
                             buffer::Upstream::Server(_) => {
-                                "Disconnect from server"
+                                i18n::tr("sidebar.buffer.disconnect")
+                            }
+                            buffer::Upstream::Channel(_, _) => {
+                                i18n::tr("sidebar.buffer.leave_channel")
+                            }
+                            buffer::Upstream::Query(_, _) => {
+                                i18n::tr("sidebar.buffer.close_query")
                             }
-                            buffer::Upstream::Channel(_, _) => "Leave channel",
-                            buffer::Upstream::Query(_, _) => "Close query",
                         },
                         Some(Message::Leave(buffer.clone())),
                     ),
                     Entry::Connect => (
-                        "Connect to server",
+                        i18n::tr("sidebar.buffer.connect"),
                         Some(Message::Connect(buffer.server().clone())),
                     ),
                     Entry::DisableAutoconnect => (
-                        "Disable autoconnect",
+                        i18n::tr("sidebar.buffer.disable_autoconnect"),
                         Some(Message::DisableAutoconnect(
                             buffer.server().clone(),
                         )),
                     ),
                     Entry::Remove => (
-                        "Remove server from sidebar",
+                        i18n::tr("sidebar.buffer.remove"),
                         Some(Message::Remove(buffer.server().clone())),
                     ),
                     Entry::Context => {
@@ -1511,6 +2540,36 @@ fn upstream_buffer_button<'a>(
     }
 }
 
+/// Fuzzy matches buffer names against the sidebar filter.
+struct Filter {
+    pattern: Pattern,
+    matcher: Matcher,
+    buffer: Vec<char>,
+}
+
+impl Filter {
+    fn new(query: &str) -> Option<Self> {
+        let query = query.trim();
+
+        (!query.is_empty()).then(|| Self {
+            pattern: Pattern::new(
+                query,
+                CaseMatching::Ignore,
+                Normalization::Smart,
+                AtomKind::Fuzzy,
+            ),
+            matcher: Matcher::new(nucleo_matcher::Config::DEFAULT),
+            buffer: Vec::new(),
+        })
+    }
+
+    fn matches(&mut self, name: &str) -> bool {
+        self.pattern
+            .score(Utf32Str::new(name, &mut self.buffer), &mut self.matcher)
+            .is_some()
+    }
+}
+
 fn should_show_internal_buffer(
     buffer: buffer::Internal,
     config: &Config,
@@ -1587,6 +2646,11 @@ fn internal_buffer_button<'a>(
         buffer::Internal::ConfigEditor => {
             (show_icon.then_some(icon::config()), None)
         }
+        buffer::Internal::Diagnostics => {
+            (show_icon.then_some(icon::lightbulb()), None)
+        }
+        buffer::Internal::Unread => (show_icon.then_some(icon::circle()), None),
+        buffer::Internal::Ignores => (show_icon.then_some(icon::hide()), None),
         buffer::Internal::FileTransfers => {
             (show_icon.then_some(icon::file_transfer()), None)
         }
@@ -1704,6 +2768,10 @@ fn internal_buffer_button<'a>(
         None,
         false,
         has_history,
+        None,
+        None,
+        None,
+        false,
     );
 
     if entries.is_empty() {
@@ -1719,7 +2787,7 @@ fn internal_buffer_button<'a>(
             move |entry, length| {
                 let (content, message) = match entry {
                     Entry::MarkAsRead => (
-                        "Mark as read",
+                        i18n::tr("sidebar.buffer.mark_as_read"),
                         if can_mark_as_read {
                             Some(Message::MarkAsRead(buffer.clone().into()))
                         } else {
@@ -1727,24 +2795,33 @@ fn internal_buffer_button<'a>(
                         },
                     ),
                     Entry::NewPane => (
-                        "Open in new pane",
+                        i18n::tr("sidebar.buffer.open_in_new_pane"),
                         Some(Message::New(buffer.clone().into())),
                     ),
                     Entry::Popout => (
-                        "Open in new window",
+                        i18n::tr("sidebar.buffer.open_in_new_window"),
                         Some(Message::Popout(buffer.clone().into())),
                     ),
                     Entry::Replace => (
-                        "Replace current pane",
+                        i18n::tr("sidebar.buffer.replace_pane"),
                         Some(Message::Replace(buffer.clone().into())),
                     ),
-                    Entry::Close(window, pane) => {
-                        ("Close pane", Some(Message::Close(window, pane)))
-                    }
+                    Entry::Close(window, pane) => (
+                        i18n::tr("sidebar.buffer.close_pane"),
+                        Some(Message::Close(window, pane)),
+                    ),
                     Entry::Swap(window, pane) => (
-                        "Swap with current pane",
+                        i18n::tr("sidebar.buffer.swap_pane"),
                         Some(Message::Swap(window, pane)),
                     ),
+                    Entry::OpenLogFile => (
+                        i18n::tr("sidebar.buffer.open_log_file"),
+                        Some(Message::OpenLogFile(buffer.clone().into())),
+                    ),
+                    Entry::OpenDataFolder => (
+                        i18n::tr("sidebar.buffer.open_data_folder"),
+                        Some(Message::OpenDataFolder(buffer.clone().into())),
+                    ),
                     Entry::Context => {
                         return container(
                             text(title)
@@ -1966,6 +3043,7 @@ impl Dimensions {
     }
 }
 
+#[derive(Clone, Copy)]
 enum ConnectionStatus {
     Connected { registration_complete: bool },
     Disconnected { autoconnect: bool, connecting: bool },
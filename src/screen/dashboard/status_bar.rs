@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use data::config::status_bar::Segment;
+use data::{Config, Server, client, file_transfer, i18n};
+use iced::widget::{Row, container, text};
+use iced::{Length, alignment};
+
+use crate::widget::Element;
+use crate::{Theme, font, theme};
+
+pub fn view<'a, Message: 'a>(
+    server: Option<&Server>,
+    clients: &client::Map,
+    file_transfers: &file_transfer::Manager,
+    config: &Config,
+    theme: &Theme,
+) -> Option<Element<'a, Message>> {
+    if !config.status_bar.enabled {
+        return None;
+    }
+
+    let segment = |content: String| -> Element<'a, Message> {
+        text(content)
+            .style(theme::text::secondary)
+            .font_maybe(theme::font_style::secondary(theme).map(font::get))
+            .size(theme::TEXT_SIZE - 1.0)
+            .into()
+    };
+
+    let segments = config.status_bar.segments.iter().filter_map(|kind| {
+        match kind {
+            Segment::Server => Some(match server {
+                Some(server) if clients.status(server).connected() => {
+                    server.to_string()
+                }
+                Some(server) => i18n::tr_args(
+                    "status_bar.disconnected",
+                    &[("server", &server.to_string())],
+                ),
+                None => i18n::tr("status_bar.no_server").to_string(),
+            }),
+            Segment::Lag => server
+                .and_then(|server| clients.get_server_lag(server))
+                .map(|lag| {
+                    i18n::tr_args(
+                        "status_bar.lag",
+                        &[("lag", &format_lag(lag))],
+                    )
+                }),
+            Segment::Away => server
+                .is_some_and(|server| clients.get_server_is_away(server))
+                .then(|| i18n::tr("status_bar.away").to_string()),
+            Segment::Queue => server
+                .map(|server| clients.get_server_queued_messages(server))
+                .filter(|queued| *queued > 0)
+                .map(|queued| {
+                    i18n::tr_args(
+                        "status_bar.queued",
+                        &[("count", &i18n::format_number(queued as u64))],
+                    )
+                }),
+            Segment::FileTransfers => {
+                let active = file_transfers
+                    .list()
+                    .filter(|file_transfer| {
+                        matches!(
+                            file_transfer.status,
+                            file_transfer::Status::Active { .. }
+                        )
+                    })
+                    .count();
+
+                (active > 0).then(|| {
+                    i18n::tr_args(
+                        "status_bar.file_transfers",
+                        &[("count", &i18n::format_number(active as u64))],
+                    )
+                })
+            }
+        }
+        .map(segment)
+    });
+
+    Some(
+        container(
+            Row::with_children(segments)
+                .spacing(16)
+                .align_y(alignment::Vertical::Center),
+        )
+        .padding([2, 8])
+        .width(Length::Fill)
+        .style(theme::container::status_bar)
+        .into(),
+    )
+}
+
+fn format_lag(lag: Duration) -> String {
+    if lag < Duration::from_secs(1) {
+        format!("{} ms", lag.as_millis())
+    } else {
+        format!("{} s", i18n::format_decimal(lag.as_secs_f64(), 1))
+    }
+}
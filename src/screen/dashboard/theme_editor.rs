@@ -1,14 +1,21 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use data::appearance::community;
 use data::appearance::theme::FontStyle;
-use data::{Config, url};
+use data::{Config, environment, i18n, url};
 use futures::TryFutureExt;
 use iced::Length::*;
 use iced::alignment::Vertical;
 use iced::widget::text::LineHeight;
-use iced::widget::{button, center, column, container, row, text_input};
-use iced::{Color, Length, Padding, Task, Vector, alignment, clipboard};
+use iced::widget::{
+    button, center, checkbox, column, container, row, scrollable, text,
+    text_input,
+};
+use iced::{
+    Border, Color, Length, Padding, Task, Vector, alignment, clipboard, padding,
+};
 use strum::IntoEnumIterator;
 use tokio::time;
 
@@ -17,12 +24,13 @@ use crate::widget::{
     Element, color_picker, combo_box, font_style_pick_list, tooltip,
 };
 use crate::window::{self, Window};
-use crate::{icon, open_url, platform_specific, widget};
+use crate::{font, icon, open_url, platform_specific, widget};
 
 #[derive(Debug, Clone)]
 pub enum Event {
     Close,
     ReloadThemes,
+    ReloadConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -42,6 +50,26 @@ pub enum Message {
     Saved(Result<(), String>),
     ClearSaveResult,
     ClearCopy,
+    FontFamily(font::Family),
+    DefaultFontFamily,
+    MonospacedOnly(bool),
+    SaveFontFamily,
+    FontFamilySaved(Result<(), String>),
+    ClearFontFamilySaveResult,
+    BrowseThemes,
+    CloseBrowser,
+    OpenThemeWebsite,
+    ThemeLinkName(String),
+    ThemeLink(String),
+    AddThemeLink,
+    PreviewCommunityTheme(usize),
+    InstallCommunityTheme(usize),
+    CommunityThemeInstalled(usize, Result<String, String>),
+    CheckContrast,
+    CloseContrast,
+    EditComponent(Component),
+    FixContrast(Component),
+    FixAllContrast,
 }
 
 #[derive(Debug, Clone)]
@@ -52,6 +80,24 @@ pub struct ThemeEditor {
     hex_input: Option<String>,
     save_result: Option<bool>,
     copied: bool,
+    font_families: Vec<font::Family>,
+    font_combo_box: combo_box::State<font::Family>,
+    font_family: Option<font::Family>,
+    monospaced_only: bool,
+    font_family_save_result: Option<bool>,
+    restart_required: bool,
+    browser: Option<Browser>,
+    nickname_color: data::buffer::Color,
+    contrast: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Browser {
+    name: String,
+    link: String,
+    error: Option<String>,
+    themes: Vec<community::Theme>,
+    installed: HashMap<usize, Result<String, String>>,
 }
 
 impl ThemeEditor {
@@ -61,7 +107,7 @@ impl ThemeEditor {
     ) -> (Self, Task<window::Id>) {
         let (window, task) = window::open(window::Settings {
             // Just big enough to show all components in combobox
-            size: iced::Size::new(555.0, 300.0),
+            size: iced::Size::new(555.0, 400.0),
             resizable: false,
             position: main_window
                 .position
@@ -73,6 +119,18 @@ impl ThemeEditor {
             ..window::settings(config)
         });
 
+        let font_families = font::families();
+        let font_family = config.font.family.as_ref().map(|name| {
+            font_families
+                .iter()
+                .find(|family| &family.name == name)
+                .cloned()
+                .unwrap_or_else(|| font::Family {
+                    name: name.clone(),
+                    monospaced: true,
+                })
+        });
+
         (
             Self {
                 window,
@@ -83,6 +141,15 @@ impl ThemeEditor {
                 hex_input: None,
                 save_result: None,
                 copied: false,
+                font_combo_box: font_combo_box(&font_families, true),
+                font_families,
+                font_family,
+                monospaced_only: true,
+                font_family_save_result: None,
+                restart_required: false,
+                browser: None,
+                nickname_color: config.buffer.nickname.color.clone(),
+                contrast: false,
             },
             task,
         )
@@ -251,16 +318,226 @@ impl ThemeEditor {
             Message::ClearCopy => {
                 self.copied = false;
             }
+            Message::FontFamily(family) => {
+                self.font_family = Some(family);
+            }
+            Message::DefaultFontFamily => {
+                self.font_family = None;
+            }
+            Message::MonospacedOnly(monospaced_only) => {
+                self.monospaced_only = monospaced_only;
+                self.font_combo_box =
+                    font_combo_box(&self.font_families, monospaced_only);
+            }
+            Message::SaveFontFamily => {
+                let family =
+                    self.font_family.as_ref().map(|family| family.name.clone());
+
+                return (
+                    Task::perform(
+                        Config::save_font_family(family)
+                            .map_err(|e| e.to_string()),
+                        Message::FontFamilySaved,
+                    ),
+                    None,
+                );
+            }
+            Message::FontFamilySaved(Err(err)) => {
+                log::error!("Failed to save font: {err}");
+                self.font_family_save_result = Some(false);
+
+                return (
+                    Task::perform(time::sleep(Duration::from_secs(2)), |()| {
+                        Message::ClearFontFamilySaveResult
+                    }),
+                    None,
+                );
+            }
+            Message::FontFamilySaved(Ok(())) => {
+                log::debug!("Font saved");
+                self.font_family_save_result = Some(true);
+                self.restart_required = true;
+
+                return (
+                    Task::perform(time::sleep(Duration::from_secs(2)), |()| {
+                        Message::ClearFontFamilySaveResult
+                    }),
+                    Some(Event::ReloadConfig),
+                );
+            }
+            Message::ClearFontFamilySaveResult => {
+                self.font_family_save_result = None;
+            }
+            Message::BrowseThemes => {
+                self.browser = Some(Browser::default());
+            }
+            Message::CloseBrowser => {
+                self.browser = None;
+            }
+            Message::OpenThemeWebsite => {
+                let _ = open_url::open(environment::THEME_WEBSITE);
+            }
+            Message::ThemeLinkName(name) => {
+                if let Some(browser) = &mut self.browser {
+                    browser.name = name;
+                }
+            }
+            Message::ThemeLink(link) => {
+                if let Some(browser) = &mut self.browser {
+                    browser.link = link;
+                    browser.error = None;
+                }
+            }
+            Message::AddThemeLink => {
+                if let Some(browser) = &mut self.browser {
+                    match community::Theme::from_link(
+                        &browser.name,
+                        &browser.link,
+                    ) {
+                        Ok(community_theme) => {
+                            browser.themes.push(community_theme);
+                            browser.name.clear();
+                            browser.link.clear();
+                        }
+                        Err(err) => {
+                            browser.error = Some(err.to_string());
+                        }
+                    }
+                }
+            }
+            Message::PreviewCommunityTheme(idx) => {
+                if let Some(browser) = &self.browser
+                    && let Some(community_theme) = browser.themes.get(idx)
+                {
+                    self.hex_input = None;
+
+                    *theme = theme.preview(data::Theme::new(
+                        community_theme.name.clone(),
+                        community_theme.styles,
+                    ));
+                }
+            }
+            Message::InstallCommunityTheme(idx) => {
+                if let Some(browser) = &self.browser
+                    && let Some(community_theme) = browser.themes.get(idx)
+                {
+                    return (
+                        Task::perform(
+                            community::install(community_theme.clone())
+                                .map_err(|e| e.to_string()),
+                            move |result| {
+                                Message::CommunityThemeInstalled(idx, result)
+                            },
+                        ),
+                        None,
+                    );
+                }
+            }
+            Message::CommunityThemeInstalled(idx, result) => {
+                let is_success = result.is_ok();
+
+                match &result {
+                    Ok(name) => log::debug!("Installed theme {name}"),
+                    Err(err) => log::error!("Failed to install theme: {err}"),
+                }
+
+                if let Some(browser) = &mut self.browser {
+                    browser.installed.insert(idx, result);
+                }
+
+                if is_success {
+                    return (Task::none(), Some(Event::ReloadThemes));
+                }
+            }
+            Message::CheckContrast => {
+                self.contrast = true;
+            }
+            Message::CloseContrast => {
+                self.contrast = false;
+            }
+            Message::EditComponent(component) => {
+                self.hex_input = None;
+                self.contrast = false;
+
+                self.component = component;
+            }
+            Message::FixContrast(component) => {
+                self.hex_input = None;
+
+                let mut styles = *theme.styles();
+
+                self.fix_contrast(component, &mut styles);
+
+                *theme = theme
+                    .preview(data::Theme::new("Custom Theme".into(), styles));
+            }
+            Message::FixAllContrast => {
+                self.hex_input = None;
+
+                let mut styles = *theme.styles();
+
+                for issue in contrast_issues(&styles, &self.nickname_color) {
+                    self.fix_contrast(issue.component, &mut styles);
+                }
+
+                *theme = theme
+                    .preview(data::Theme::new("Custom Theme".into(), styles));
+            }
         }
 
         (Task::none(), None)
     }
 
+    fn fix_contrast(&self, component: Component, styles: &mut Styles) {
+        let (Some(color), Some(background)) =
+            (component.color(styles), component.text_background(styles))
+        else {
+            return;
+        };
+
+        let fixed = if self.has_unique_nickname_colors(component) {
+            theme::fix_nickname_contrast(color, background)
+        } else {
+            theme::fix_contrast(color, background)
+        };
+        let font_style = component.font_style(styles).flatten();
+
+        component.update(styles, Some(fixed), font_style);
+    }
+
+    fn has_unique_nickname_colors(&self, component: Component) -> bool {
+        component == Component::Buffer(Buffer::Nickname)
+            && matches!(self.nickname_color, data::buffer::Color::Unique)
+    }
+
     pub fn view<'a>(
         &'a self,
         config: &'a Config,
         theme: &'a Theme,
     ) -> Element<'a, Message> {
+        let default_padding: u16 = 8;
+        let top_padding = platform_specific::popped_out_window_padding(config)
+            + u32::from(default_padding);
+        let padding =
+            Padding::new(default_padding.into()).top(top_padding as f32);
+
+        let overlay = if let Some(browser) = &self.browser {
+            Some(browser_view(browser, theme))
+        } else if self.contrast {
+            Some(contrast_view(theme.styles(), &self.nickname_color, theme))
+        } else {
+            None
+        };
+
+        if let Some(overlay) = overlay {
+            return container(overlay)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .padding(padding)
+                .style(theme::container::general)
+                .into();
+        }
+
         let color = self
             .component
             .color(theme.styles())
@@ -313,6 +590,8 @@ impl ThemeEditor {
         };
         let apply =
             secondary_button("Apply Colors & Font Styles", Message::Apply);
+        let check_contrast =
+            secondary_button("Check Contrast", Message::CheckContrast);
 
         let copy = if self.copied {
             success_icon()
@@ -327,6 +606,13 @@ impl ThemeEditor {
             theme,
         );
 
+        let browse = icon(
+            icon::open(),
+            "Install theme from link",
+            Message::BrowseThemes,
+            theme,
+        );
+
         let color_picker = color_picker(color, Message::Color);
 
         let font_style_pick_list = font_style.map(|font_style| {
@@ -335,11 +621,51 @@ impl ThemeEditor {
             })
         });
 
-        let default_padding: u16 = 8;
-        let top_padding = platform_specific::popped_out_window_padding(config)
-            + u32::from(default_padding);
-        let padding =
-            Padding::new(default_padding.into()).top(top_padding as f32);
+        let font_family = combo_box(
+            &self.font_combo_box,
+            "Default font",
+            self.font_family.as_ref(),
+            Message::FontFamily,
+        );
+
+        let monospaced_only = row![
+            checkbox(self.monospaced_only).on_toggle(Message::MonospacedOnly),
+            text("Monospaced"),
+        ]
+        .align_y(Vertical::Center)
+        .spacing(4);
+
+        let default_font_family = icon(
+            icon::undo(),
+            "Use default font",
+            Message::DefaultFontFamily,
+            theme,
+        );
+
+        let save_font_family = match self.font_family_save_result {
+            Some(is_success) => status_button(is_success),
+            None => secondary_button("Use Font", Message::SaveFontFamily),
+        };
+
+        let preview_font = self.font_family.as_ref().map_or_else(
+            || font::MONO.clone().into(),
+            |family| iced::Font::with_family(family.name.as_str()),
+        );
+
+        let font_preview = container(
+            text(FONT_PREVIEW)
+                .font(preview_font)
+                .size(config.font.size.map_or(theme::TEXT_SIZE, f32::from))
+                .line_height(font::line_height()),
+        )
+        .padding(8)
+        .width(Fill)
+        .style(theme::container::buffer_title_bar);
+
+        let restart_required = self.restart_required.then(|| {
+            text("Restart Halloy to use the new font")
+                .style(theme::text::secondary)
+        });
 
         let content = column![
             row![
@@ -349,11 +675,22 @@ impl ThemeEditor {
                 undo,
                 copy,
                 share,
+                browse,
             ]
             .align_y(Vertical::Center)
             .spacing(4),
             color_picker,
-            row![apply, save].spacing(4),
+            row![apply, save, check_contrast].spacing(4),
+            row![
+                container(font_family).width(Fill),
+                monospaced_only,
+                default_font_family,
+                container(save_font_family).width(100),
+            ]
+            .align_y(Vertical::Center)
+            .spacing(4),
+            font_preview,
+            restart_required,
         ]
         .spacing(8);
 
@@ -366,6 +703,333 @@ impl ThemeEditor {
     }
 }
 
+fn browser_view<'a>(
+    browser: &'a Browser,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    let header = row![
+        browser_button("Back to Editor", Some(Message::CloseBrowser)),
+        container(text("Install Theme from Link")).center_x(Fill),
+        browser_button("Open Website", Some(Message::OpenThemeWebsite)),
+    ]
+    .align_y(Vertical::Center)
+    .spacing(4);
+
+    let add = row![
+        text_input("Theme name", &browser.name)
+            .on_input(Message::ThemeLinkName)
+            .on_submit(Message::AddThemeLink)
+            .padding(5)
+            .width(FillPortion(1)),
+        text_input("halloy:///theme?e=…", &browser.link)
+            .on_input(Message::ThemeLink)
+            .on_submit(Message::AddThemeLink)
+            .padding(5)
+            .width(FillPortion(2)),
+        browser_button(
+            "Add",
+            (!browser.link.trim().is_empty()).then_some(Message::AddThemeLink)
+        ),
+    ]
+    .align_y(Vertical::Center)
+    .spacing(4);
+
+    let status = match &browser.error {
+        Some(error) => text(format!("Unable to add theme: {error}"))
+            .style(theme::text::error),
+        None => text(
+            "Copy a theme link from the theme website to preview and \
+             install it",
+        )
+        .style(theme::text::secondary),
+    };
+
+    let themes = scrollable(
+        column(browser.themes.iter().enumerate().map(
+            |(idx, community_theme)| {
+                community_theme_view(
+                    idx,
+                    community_theme,
+                    browser.installed.get(&idx),
+                    theme,
+                )
+            },
+        ))
+        .spacing(8)
+        .padding(padding::right(12)),
+    )
+    .height(Fill);
+
+    column![header, add, status, themes].spacing(8).into()
+}
+
+fn browser_button(
+    label: &str,
+    message: Option<Message>,
+) -> Element<'_, Message> {
+    button(text(label))
+        .padding(5)
+        .style(|theme, status| theme::button::secondary(theme, status, false))
+        .on_press_maybe(message)
+        .into()
+}
+
+fn community_theme_view<'a>(
+    idx: usize,
+    community_theme: &'a community::Theme,
+    installed: Option<&'a Result<String, String>>,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    let styles = community_theme.styles;
+    let buffer = styles.buffer;
+
+    let install: Element<'a, Message> = match installed {
+        Some(Ok(name)) => tooltip(
+            success_icon(),
+            Some(name.as_str()),
+            tooltip::Position::Bottom,
+            theme,
+        ),
+        Some(Err(error)) => tooltip(
+            status_button(false),
+            Some(error.as_str()),
+            tooltip::Position::Bottom,
+            theme,
+        ),
+        None => button(text("Install"))
+            .padding(5)
+            .style(|theme, status| {
+                theme::button::secondary(theme, status, false)
+            })
+            .on_press(Message::InstallCommunityTheme(idx))
+            .into(),
+    };
+
+    let header = row![
+        text(community_theme.name.as_str())
+            .color(styles.text.primary.color)
+            .width(Fill),
+        button(text("Preview"))
+            .padding(5)
+            .style(|theme, status| theme::button::secondary(
+                theme, status, false
+            ))
+            .on_press(Message::PreviewCommunityTheme(idx)),
+        container(install).center_x(70),
+    ]
+    .align_y(Vertical::Center)
+    .spacing(4);
+
+    let timestamp = |time: &'static str| {
+        text(format!("{time} ")).color(buffer.timestamp.color)
+    };
+    let nickname = |nick: &'static str| {
+        text(format!("<{nick}> ")).color(buffer.nickname.color)
+    };
+
+    let sample = column![
+        row![
+            timestamp("12:01"),
+            nickname("casey"),
+            text("has anyone tried the new theme?")
+                .color(styles.text.primary.color),
+        ],
+        row![
+            timestamp("12:02"),
+            text("∙ robin has joined the channel").color(
+                buffer
+                    .server_messages
+                    .join
+                    .color
+                    .unwrap_or(styles.text.secondary.color)
+            ),
+        ],
+        row![
+            timestamp("12:03"),
+            nickname("robin"),
+            text("casey: ").color(buffer.nickname.color),
+            text("https://themes.halloy.chat").color(buffer.url.color),
+        ],
+    ]
+    .spacing(2);
+
+    container(column![header, sample].spacing(8))
+        .padding(8)
+        .width(Fill)
+        .style(move |_| container::Style {
+            background: Some(buffer.background.into()),
+            border: Border {
+                color: buffer.border,
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..container::Style::default()
+        })
+        .into()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ContrastIssue {
+    component: Component,
+    ratio: f32,
+}
+
+/// Text components whose color doesn't reach the minimum contrast ratio
+/// against their background.
+fn contrast_issues(
+    styles: &Styles,
+    nickname_color: &data::buffer::Color,
+) -> Vec<ContrastIssue> {
+    components()
+        .filter_map(|component| {
+            let color = component.color(styles)?;
+            let background = component.text_background(styles)?;
+
+            let ratio = if component == Component::Buffer(Buffer::Nickname)
+                && matches!(nickname_color, data::buffer::Color::Unique)
+            {
+                theme::nickname_contrast_ratio(color, background)
+            } else {
+                theme::contrast_ratio(color, background)
+            };
+
+            (ratio < theme::MIN_CONTRAST_RATIO)
+                .then_some(ContrastIssue { component, ratio })
+        })
+        .collect()
+}
+
+fn contrast_view<'a>(
+    styles: &Styles,
+    nickname_color: &'a data::buffer::Color,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    let issues = contrast_issues(styles, nickname_color);
+
+    // Palette colors come from the config rather than the theme, so they can
+    // only be reported
+    let palette_issues = match nickname_color {
+        data::buffer::Color::Palette(colors) => colors
+            .iter()
+            .map(|color| {
+                (
+                    *color,
+                    theme::contrast_ratio(*color, styles.buffer.background),
+                )
+            })
+            .filter(|(_, ratio)| *ratio < theme::MIN_CONTRAST_RATIO)
+            .collect(),
+        _ => vec![],
+    };
+
+    let ratio_text = |ratio: f32| {
+        text(format!("{}:1", i18n::format_decimal(f64::from(ratio), 1)))
+            .style(theme::text::error)
+            .width(50)
+    };
+
+    let header = row![
+        button(text("Back to Editor"))
+            .padding(5)
+            .style(|theme, status| theme::button::secondary(
+                theme, status, false
+            ))
+            .on_press(Message::CloseContrast),
+        container(text(format!(
+            "Minimum contrast {}:1",
+            i18n::format_decimal(f64::from(theme::MIN_CONTRAST_RATIO), 1)
+        )))
+        .center_x(Fill),
+        button(text("Auto-fix Lightness"))
+            .padding(5)
+            .style(|theme, status| theme::button::secondary(
+                theme, status, false
+            ))
+            .on_press_maybe(
+                (!issues.is_empty()).then_some(Message::FixAllContrast)
+            ),
+    ]
+    .align_y(Vertical::Center)
+    .spacing(4);
+
+    if issues.is_empty() && palette_issues.is_empty() {
+        return column![
+            header,
+            center(
+                text("All text colors meet the minimum contrast")
+                    .style(theme::text::success)
+            ),
+        ]
+        .spacing(8)
+        .into();
+    }
+
+    let rows = issues.into_iter().map(|issue| -> Element<'a, Message> {
+        row![
+            button(text(issue.component.to_string()))
+                .padding(0)
+                .style(theme::button::bare)
+                .on_press(Message::EditComponent(issue.component))
+                .width(Fill),
+            ratio_text(issue.ratio),
+            button(text("Fix"))
+                .padding([2, 8])
+                .style(|theme, status| theme::button::secondary(
+                    theme, status, false
+                ))
+                .on_press(Message::FixContrast(issue.component)),
+        ]
+        .align_y(Vertical::Center)
+        .spacing(4)
+        .into()
+    });
+
+    let palette_rows = palette_issues.into_iter().map(
+        |(color, ratio)| -> Element<'a, Message> {
+            row![
+                text(format!(
+                    "nickname palette {} (set in config)",
+                    theme::color_to_hex(color)
+                ))
+                .color(color)
+                .width(Fill),
+                ratio_text(ratio),
+            ]
+            .align_y(Vertical::Center)
+            .spacing(4)
+            .into()
+        },
+    );
+
+    column![
+        header,
+        scrollable(
+            column(rows.chain(palette_rows))
+                .spacing(4)
+                .padding(padding::right(12))
+        )
+        .height(Fill),
+    ]
+    .spacing(8)
+    .into()
+}
+
+const FONT_PREVIEW: &str = "The quick brown fox jumps over the lazy dog\n\
+    0123456789 <nick> ~!@#$%^&*()[]{}";
+
+fn font_combo_box(
+    families: &[font::Family],
+    monospaced_only: bool,
+) -> combo_box::State<font::Family> {
+    combo_box::State::new(
+        families
+            .iter()
+            .filter(|family| !monospaced_only || family.monospaced)
+            .cloned()
+            .collect(),
+    )
+}
+
 fn icon<'a>(
     icon: widget::Text<'a>,
     tip: &'a str,
@@ -483,6 +1147,23 @@ impl Component {
         }
     }
 
+    /// Background the component's text is drawn on, if it is text.
+    fn text_background(&self, styles: &Styles) -> Option<Color> {
+        let is_text = match self {
+            Component::General(general) => {
+                *general == General::HorizontalRuleText
+            }
+            Component::Text(_) => true,
+            Component::Buffer(buffer) => {
+                matches!(buffer, Buffer::BacklogRuleText | Buffer::DateRuleText)
+                    || buffer.font_style(styles).is_some()
+            }
+            Component::Buttons(_) | Component::Formatting(_) => false,
+        };
+
+        is_text.then_some(styles.buffer.background)
+    }
+
     fn update(
         &self,
         styles: &mut Styles,
@@ -691,6 +1372,7 @@ pub enum Buffer {
     Highlight,
     Nickname,
     NicknameOffline,
+    NicknameAway,
     Selection,
     #[strum(to_string = "server-message-{0}")]
     ServerMessages(ServerMessages),
@@ -720,6 +1402,7 @@ impl Buffer {
             Buffer::Highlight => Some(styles.buffer.highlight),
             Buffer::Nickname => Some(styles.buffer.nickname.color),
             Buffer::NicknameOffline => styles.buffer.nickname_offline.color,
+            Buffer::NicknameAway => styles.buffer.nickname_away.color,
             Buffer::Selection => Some(styles.buffer.selection),
             Buffer::ServerMessages(server_messages) => {
                 server_messages.color(&styles.buffer.server_messages)
@@ -770,6 +1453,9 @@ impl Buffer {
             Buffer::NicknameOffline => {
                 Some(styles.buffer.nickname_offline.font_style)
             }
+            Buffer::NicknameAway => {
+                Some(styles.buffer.nickname_away.font_style)
+            }
             Buffer::Selection => None,
             Buffer::ServerMessages(server_messages) => {
                 Some(server_messages.font_style(&styles.buffer.server_messages))
@@ -843,6 +1529,10 @@ impl Buffer {
                 styles.nickname_offline.color = color;
                 styles.nickname_offline.font_style = font_style;
             }
+            Buffer::NicknameAway => {
+                styles.nickname_away.color = color;
+                styles.nickname_away.font_style = font_style;
+            }
             Buffer::Selection => {
                 if let Some(color) = color {
                     styles.selection = color;
@@ -2,11 +2,14 @@ use iced::Subscription;
 
 #[cfg(target_os = "macos")]
 mod macos;
+mod network;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Event {
     Suspending,
     Resumed,
+    NetworkOffline,
+    NetworkOnline,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -24,10 +27,10 @@ impl State {
 
 #[cfg(target_os = "macos")]
 pub fn events() -> Subscription<Event> {
-    macos::events()
+    Subscription::batch([macos::events(), network::events()])
 }
 
 #[cfg(not(target_os = "macos"))]
 pub fn events() -> Subscription<Event> {
-    Subscription::none()
+    network::events()
 }
@@ -0,0 +1,63 @@
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::time::Duration;
+
+use futures::SinkExt;
+use iced::Subscription;
+
+use super::Event;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+pub fn events() -> Subscription<Event> {
+    Subscription::run(|| {
+        iced::stream::channel(10, async |mut output| {
+            let mut online = true;
+
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+
+                if has_route() == online {
+                    continue;
+                }
+
+                online = !online;
+
+                let event = if online {
+                    Event::NetworkOnline
+                } else {
+                    Event::NetworkOffline
+                };
+
+                if output.send(event).await.is_err() {
+                    break;
+                }
+            }
+        })
+    })
+}
+
+/// Whether the OS has a route to the outside world.  Connecting a UDP socket
+/// only performs a routing table lookup, so no traffic is generated.
+fn has_route() -> bool {
+    [
+        (
+            SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+            // TEST-NET-1 (RFC 5737)
+            SocketAddr::from((Ipv4Addr::new(192, 0, 2, 1), 9)),
+        ),
+        (
+            SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+            // Documentation prefix (RFC 3849)
+            SocketAddr::from((
+                Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+                9,
+            )),
+        ),
+    ]
+    .into_iter()
+    .any(|(local, remote)| {
+        UdpSocket::bind(local)
+            .and_then(|socket| socket.connect(remote))
+            .is_ok()
+    })
+}
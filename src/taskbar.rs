@@ -0,0 +1,324 @@
+use data::buffer;
+use iced::Task;
+
+use crate::window;
+
+/// How many recent buffers are offered in the jump list.
+const JUMP_LIST_LEN: usize = 8;
+
+/// What the taskbar button of the main window reflects.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct State {
+    /// Combined progress of the running file transfers, if any.
+    pub progress: Option<f64>,
+    /// Whether any buffer has unread highlights.
+    pub highlight: bool,
+    /// Most recently focused channels and queries.
+    pub recent: Vec<buffer::Upstream>,
+}
+
+/// Keeps the taskbar in sync with [`State`], only touching the parts that
+/// changed since the last update (Windows only).
+#[derive(Debug, Default)]
+pub struct Taskbar {
+    applied: State,
+}
+
+impl Taskbar {
+    pub fn update<Message: Send + 'static>(
+        &mut self,
+        window: window::Id,
+        mut state: State,
+    ) -> Task<Message> {
+        state.recent.truncate(JUMP_LIST_LEN);
+
+        let mut tasks = vec![];
+
+        // Progress is reported in whole percents to avoid redundant updates
+        let percent = |progress: Option<f64>| {
+            progress.map(|progress| (progress * 100.0).round() as u64)
+        };
+
+        if percent(state.progress) != percent(self.applied.progress) {
+            tasks.push(platform::set_progress(window, percent(state.progress)));
+        }
+
+        if state.highlight != self.applied.highlight {
+            tasks.push(platform::set_overlay(window, state.highlight));
+        }
+
+        if state.recent != self.applied.recent {
+            tasks.push(platform::set_jump_list(state.recent.clone()));
+        }
+
+        self.applied = state;
+
+        Task::batch(tasks)
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use data::{buffer, i18n};
+    use iced::Task;
+    use iced::window::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::StructuredStorage::{
+        PROPVARIANT, PVCHF_DEFAULT, PropVariantChangeType,
+    };
+    use windows::Win32::System::Com::{
+        CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED, CoCreateInstance,
+        CoInitializeEx, CoUninitialize,
+    };
+    use windows::Win32::System::Variant::VT_LPWSTR;
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList,
+        IShellLinkW, ITaskbarList3, ShellLink, TBPF_NOPROGRESS, TBPF_NORMAL,
+        TaskbarList,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateIcon, DestroyIcon, HICON,
+    };
+    use windows::core::{HSTRING, Interface, PCWSTR};
+
+    use crate::window;
+
+    /// Size of the overlay badge, which Windows draws at 16x16.
+    const BADGE_SIZE: i32 = 16;
+
+    pub fn set_progress<Message: Send + 'static>(
+        window: window::Id,
+        percent: Option<u64>,
+    ) -> Task<Message> {
+        with_taskbar(window, move |taskbar, hwnd| unsafe {
+            match percent {
+                Some(percent) => {
+                    taskbar.SetProgressState(hwnd, TBPF_NORMAL)?;
+                    taskbar.SetProgressValue(hwnd, percent, 100)
+                }
+                None => taskbar.SetProgressState(hwnd, TBPF_NOPROGRESS),
+            }
+        })
+    }
+
+    pub fn set_overlay<Message: Send + 'static>(
+        window: window::Id,
+        highlight: bool,
+    ) -> Task<Message> {
+        with_taskbar(window, move |taskbar, hwnd| unsafe {
+            if highlight {
+                let badge = badge()?;
+                let result = taskbar.SetOverlayIcon(
+                    hwnd,
+                    badge,
+                    &HSTRING::from(i18n::tr("taskbar.unread_highlights")),
+                );
+                // The taskbar keeps its own copy of the icon
+                let _ = DestroyIcon(badge);
+                result
+            } else {
+                taskbar.SetOverlayIcon(hwnd, HICON::default(), PCWSTR::null())
+            }
+        })
+    }
+
+    pub fn set_jump_list<Message: Send + 'static>(
+        buffers: Vec<buffer::Upstream>,
+    ) -> Task<Message> {
+        let items = buffers.iter().map(JumpListItem::from).collect::<Vec<_>>();
+
+        Task::future(tokio::task::spawn_blocking(move || {
+            // SAFETY: COM is initialized for this blocking thread only, and
+            // uninitialized once the list has been committed.
+            unsafe {
+                if CoInitializeEx(None, COINIT_APARTMENTTHREADED).is_err() {
+                    return;
+                }
+
+                if let Err(error) = commit_jump_list(&items) {
+                    log::warn!("failed to update jump list: {error}");
+                }
+
+                CoUninitialize();
+            }
+        }))
+        .discard()
+    }
+
+    struct JumpListItem {
+        title: String,
+        url: String,
+    }
+
+    impl From<&buffer::Upstream> for JumpListItem {
+        fn from(buffer: &buffer::Upstream) -> Self {
+            let server = buffer.server();
+            let target = buffer
+                .target()
+                .map(|target| target.as_str().to_string())
+                .unwrap_or_default();
+
+            Self {
+                title: format!("{target} ({server})"),
+                url: data::url::buffer(&format!("{server:b}"), &target),
+            }
+        }
+    }
+
+    unsafe fn commit_jump_list(
+        items: &[JumpListItem],
+    ) -> windows::core::Result<()> {
+        let exe = HSTRING::from(std::env::current_exe()?.as_path());
+
+        unsafe {
+            let list: ICustomDestinationList =
+                CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+            let mut slots = 0;
+            let _removed: IObjectArray = list.BeginList(&mut slots)?;
+
+            let collection: IObjectCollection = CoCreateInstance(
+                &EnumerableObjectCollection,
+                None,
+                CLSCTX_INPROC_SERVER,
+            )?;
+
+            for item in items.iter().take(slots as usize) {
+                let link: IShellLinkW =
+                    CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+                link.SetPath(&exe)?;
+                link.SetArguments(&HSTRING::from(item.url.as_str()))?;
+                link.SetIconLocation(&exe, 0)?;
+
+                // Jump list titles must be VT_LPWSTR
+                let mut title = PROPVARIANT::default();
+                PropVariantChangeType(
+                    &mut title,
+                    &PROPVARIANT::from(item.title.as_str()),
+                    PVCHF_DEFAULT,
+                    VT_LPWSTR,
+                )?;
+
+                let store: IPropertyStore = link.cast()?;
+                store.SetValue(&PKEY_Title, &title)?;
+                store.Commit()?;
+
+                collection.AddObject(&link)?;
+            }
+
+            let collection: IObjectArray = collection.cast()?;
+            list.AppendCategory(
+                &HSTRING::from(i18n::tr("taskbar.recent")),
+                &collection,
+            )?;
+            list.CommitList()
+        }
+    }
+
+    /// Runs `f` with the taskbar and the native handle of `window`, on the
+    /// thread owning the window.
+    fn with_taskbar<Message: Send + 'static>(
+        window: window::Id,
+        f: impl FnOnce(&ITaskbarList3, HWND) -> windows::core::Result<()>
+        + Send
+        + 'static,
+    ) -> Task<Message> {
+        iced::window::run(window, move |handle| {
+            let Ok(handle) = handle.window_handle() else {
+                return;
+            };
+            let RawWindowHandle::Win32(handle) = handle.as_raw() else {
+                return;
+            };
+            let hwnd = HWND(handle.hwnd.get() as *mut _);
+
+            // SAFETY: COM is initialized by the windowing library on the
+            // thread owning the window.
+            let result = unsafe {
+                CoCreateInstance::<_, ITaskbarList3>(
+                    &TaskbarList,
+                    None,
+                    CLSCTX_INPROC_SERVER,
+                )
+                .and_then(|taskbar| {
+                    taskbar.HrInit()?;
+                    f(&taskbar, hwnd)
+                })
+            };
+
+            if let Err(error) = result {
+                log::warn!("failed to update taskbar: {error}");
+            }
+        })
+        .discard()
+    }
+
+    /// Draws the overlay badge: a filled circle in the highlight color.
+    unsafe fn badge() -> windows::core::Result<HICON> {
+        let size = BADGE_SIZE as usize;
+        let radius = BADGE_SIZE as f32 / 2.0;
+
+        // Fully opaque AND mask; transparency comes from the alpha channel
+        let and_mask = vec![0u8; size * size / 8];
+        let mut bgra = vec![0u8; size * size * 4];
+
+        for y in 0..size {
+            for x in 0..size {
+                let dx = x as f32 + 0.5 - radius;
+                let dy = y as f32 + 0.5 - radius;
+                let coverage =
+                    (radius - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+
+                let pixel = &mut bgra[(y * size + x) * 4..][..4];
+                pixel.copy_from_slice(&[
+                    0x4a,
+                    0x4a,
+                    0xe0,
+                    (coverage * 255.0).round() as u8,
+                ]);
+            }
+        }
+
+        unsafe {
+            CreateIcon(
+                None,
+                BADGE_SIZE,
+                BADGE_SIZE,
+                1,
+                32,
+                and_mask.as_ptr(),
+                bgra.as_ptr(),
+            )
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use data::buffer;
+    use iced::Task;
+
+    use crate::window;
+
+    pub fn set_progress<Message>(
+        _window: window::Id,
+        _percent: Option<u64>,
+    ) -> Task<Message> {
+        Task::none()
+    }
+
+    pub fn set_overlay<Message>(
+        _window: window::Id,
+        _highlight: bool,
+    ) -> Task<Message> {
+        Task::none()
+    }
+
+    pub fn set_jump_list<Message>(
+        _buffers: Vec<buffer::Upstream>,
+    ) -> Task<Message> {
+        Task::none()
+    }
+}
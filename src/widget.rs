@@ -11,6 +11,7 @@ pub use self::context_menu::context_menu;
 pub use self::decorate::decorate;
 pub use self::double_pass::double_pass;
 pub use self::font_style_pick_list::font_style_pick_list;
+pub use self::gesture::gestures;
 pub use self::key_press::key_press;
 pub use self::message_content::message_content;
 pub use self::modal::modal;
@@ -18,6 +19,7 @@ pub use self::notify_visibility::notify_visibility;
 pub use self::on_resize::on_resize;
 pub use self::preview::preview_content;
 pub use self::reply::reply_preview_content;
+pub use self::resizable::resizable;
 pub use self::selectable_rich_text::selectable_rich_text;
 pub use self::selectable_text::selectable_text;
 pub use self::shortcut::shortcut;
@@ -34,15 +36,18 @@ pub mod double_click;
 pub mod double_pass;
 pub mod editor_history;
 pub mod font_style_pick_list;
+pub mod gesture;
 pub mod key_press;
 pub mod message_content;
 pub mod modal;
+pub mod mouse_wheel;
 pub mod notify_visibility;
 pub mod on_resize;
 pub mod pick_list;
 pub mod preview;
 pub mod reaction_row;
 pub mod reply;
+pub mod resizable;
 pub mod selectable_rich_text;
 pub mod selectable_text;
 pub mod shortcut;
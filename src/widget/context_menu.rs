@@ -1,4 +1,5 @@
 use std::slice;
+use std::time::{Duration, Instant};
 
 use iced::advanced::widget::{Operation, operation, tree};
 use iced::advanced::{
@@ -7,11 +8,17 @@ use iced::advanced::{
 pub use iced::widget::container::{Style, StyleFn};
 use iced::widget::{column, container};
 use iced::{
-    Element, Event, Length, Point, Rectangle, Size, Task, Vector, mouse,
+    Element, Event, Length, Point, Rectangle, Size, Task, Vector, mouse, touch,
+    window,
 };
 
 use super::double_pass;
 
+/// How long a finger has to be held down to open a context menu.
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+/// How far a finger may move before a long press is cancelled.
+const LONG_PRESS_TOLERANCE: f32 = 10.0;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub enum MouseButton {
     Left,
@@ -75,6 +82,7 @@ pub struct ContextMenu<'a, T, Message, Theme, Renderer> {
 pub struct State {
     pub status: Status,
     menu_tree: widget::Tree,
+    long_press: Option<LongPress>,
 }
 
 impl State {
@@ -82,10 +90,22 @@ impl State {
         State {
             status: Status::Closed,
             menu_tree: widget::Tree::empty(),
+            long_press: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+struct LongPress {
+    finger: touch::Finger,
+    // Where the finger went down, in the coordinates of touch events
+    origin: Point,
+    // Where the finger went down, relative to the widget
+    position: Point,
+    started: Instant,
+    opened: bool,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Status {
     Closed,
@@ -129,6 +149,104 @@ impl<'a, T, Message, Theme, Renderer>
         self.on_open = Some(Box::new(message));
         self
     }
+
+    /// Tracks a long press, opening the menu once the finger has been held
+    /// down long enough.
+    ///
+    /// Returns whether `event` belongs to a long press that opened the menu.
+    fn long_press(
+        &self,
+        tree: &mut widget::Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        shell: &mut Shell<'_, Message>,
+    ) -> bool {
+        let state = tree.state.downcast_mut::<State>();
+
+        match event {
+            Event::Touch(touch::Event::FingerPressed { id, position }) => {
+                state.long_press =
+                    cursor.position_over(layout.bounds()).map(|cursor| {
+                        LongPress {
+                            finger: *id,
+                            origin: *position,
+                            position: cursor,
+                            started: Instant::now(),
+                            opened: false,
+                        }
+                    });
+
+                if state.long_press.is_some() {
+                    shell.request_redraw_at(
+                        Instant::now() + LONG_PRESS_DURATION,
+                    );
+                }
+            }
+            Event::Touch(touch::Event::FingerMoved { id, position }) => {
+                if let Some(press) = state.long_press
+                    && press.finger == *id
+                {
+                    if press.opened {
+                        return true;
+                    }
+
+                    if press.origin.distance(*position) > LONG_PRESS_TOLERANCE {
+                        state.long_press = None;
+                    }
+                }
+            }
+            Event::Touch(
+                touch::Event::FingerLifted { id, .. }
+                | touch::Event::FingerLost { id, .. },
+            ) => {
+                if let Some(press) =
+                    state.long_press.take_if(|press| press.finger == *id)
+                {
+                    return press.opened;
+                }
+            }
+            Event::Window(window::Event::RedrawRequested(now)) => {
+                if let Some(press) = &mut state.long_press
+                    && !press.opened
+                    && now.duration_since(press.started) >= LONG_PRESS_DURATION
+                {
+                    press.opened = true;
+
+                    let bounds = layout.bounds();
+                    let position = match self.anchor {
+                        Anchor::Widget => Point::new(
+                            bounds.x + bounds.width,
+                            bounds.y + bounds.height,
+                        ),
+                        Anchor::Cursor => Point::new(
+                            press.position.x + 5.0,
+                            press.position.y + 5.0,
+                        ),
+                    };
+
+                    state.status = Status::Open {
+                        position,
+                        keep_open_bounds: match self.toggle_behavior {
+                            ToggleBehavior::KeepOpen => None,
+                            ToggleBehavior::Close => Some((
+                                bounds.position() - position,
+                                bounds.size(),
+                            )),
+                        },
+                    };
+                    shell.request_redraw();
+
+                    if let Some(message) = self.on_open.as_ref().map(|f| f()) {
+                        shell.publish(message);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        false
+    }
 }
 
 impl<'a, T, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
@@ -184,10 +302,7 @@ where
     }
 
     fn state(&self) -> tree::State {
-        tree::State::new(State {
-            status: Status::Closed,
-            menu_tree: widget::Tree::empty(),
-        })
+        tree::State::new(State::new())
     }
 
     fn diff(&mut self, tree: &mut widget::Tree) {
@@ -223,6 +338,35 @@ where
         shell: &mut Shell<'_, Message>,
         viewport: &Rectangle,
     ) {
+        // Context menus open with a long press on touch screens
+        if matches!(self.activation_button, mouse::Button::Right)
+            && self.long_press(tree, event, layout, cursor, shell)
+        {
+            if let Event::Touch(
+                touch::Event::FingerLifted { id, position }
+                | touch::Event::FingerLost { id, position },
+            ) = event
+            {
+                // The finger opened the menu, so the content shouldn't
+                // treat lifting it as a tap
+                self.base.as_widget_mut().update(
+                    &mut tree.children[0],
+                    &Event::Touch(touch::Event::FingerLost {
+                        id: *id,
+                        position: *position,
+                    }),
+                    layout,
+                    cursor,
+                    renderer,
+                    shell,
+                    viewport,
+                );
+            }
+
+            shell.capture_event();
+            return;
+        }
+
         self.base.as_widget_mut().update(
             &mut tree.children[0],
             event,
@@ -238,8 +382,11 @@ where
         }
 
         // is this a mouse event we are waiting for?
-        let is_mouse_event =
-            matches!(event, Event::Mouse(mouse::Event::ButtonPressed { .. }));
+        let is_mouse_event = matches!(
+            event,
+            Event::Mouse(mouse::Event::ButtonPressed { .. })
+                | Event::Touch(touch::Event::FingerPressed { .. })
+        );
 
         if is_mouse_event {
             let state = tree.state.downcast_mut::<State>();
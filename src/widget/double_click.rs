@@ -62,6 +62,52 @@ where
         .into()
 }
 
+/// Like [`double_click`], but handles the second click before `content` does,
+/// so it also works on top of widgets that capture clicks (e.g. buttons).
+pub fn double_click_over<'a, Message>(
+    content: impl Into<Element<'a, Message>>,
+    message: Message,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    decorate(content)
+        .update(
+            move |state: &mut Internal,
+                  inner: &mut Element<'a, Message>,
+                  tree: &mut Tree,
+                  event: &iced::Event,
+                  layout: Layout<'_>,
+                  cursor: mouse::Cursor,
+                  renderer: &Renderer,
+                  shell: &mut Shell<'_, Message>,
+                  viewport: &iced::Rectangle| {
+                if cursor.is_over(layout.bounds())
+                    && let event::Event::Mouse(mouse::Event::ButtonPressed {
+                        button: mouse::Button::Left,
+                        ..
+                    }) = event
+                {
+                    let now = time::Instant::now();
+                    let timeout = time::Duration::from_millis(TIMEOUT_MILLIS);
+
+                    if now - state.instant <= timeout {
+                        shell.publish(message.clone());
+                        shell.capture_event();
+                        return;
+                    }
+
+                    state.instant = now;
+                }
+
+                inner.as_widget_mut().update(
+                    tree, event, layout, cursor, renderer, shell, viewport,
+                );
+            },
+        )
+        .into()
+}
+
 #[derive(Clone, Debug)]
 struct Internal {
     instant: time::Instant,
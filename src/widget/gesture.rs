@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use data::config::pane::Gestures;
+use iced::advanced::widget::Tree;
+use iced::advanced::{Layout, Shell};
+use iced::{Point, Vector, keyboard, mouse, touch};
+
+use super::{Element, Renderer, decorate};
+
+/// Horizontal distance a swipe has to travel before it switches buffers.
+const SWIPE_DISTANCE: f32 = 120.0;
+/// Pause after which accumulated trackpad scrolling starts a new swipe.
+const SWIPE_TIMEOUT: Duration = Duration::from_millis(300);
+/// Change in distance between two fingers before they are pinching.
+const PINCH_DISTANCE: f32 = 24.0;
+/// Zoom applied per pixel scrolled while holding ctrl.
+const ZOOM_PER_PIXEL: f32 = 0.005;
+/// Zoom applied per line scrolled while holding ctrl.
+const ZOOM_PER_LINE: f32 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// Two fingers swiped horizontally in the given direction.
+    Swipe(Direction),
+    /// Two fingers pinched, scaling by the given factor.
+    Pinch(f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn from_delta(delta: f32) -> Self {
+        if delta < 0.0 { Self::Left } else { Self::Right }
+    }
+}
+
+/// Recognizes two-finger swipes and pinches over `content`, from both touch
+/// screens and trackpads.
+///
+/// Trackpads report swipes as horizontal scrolling, and pinches as scrolling
+/// with ctrl held.
+pub fn gestures<'a, Message>(
+    content: impl Into<Element<'a, Message>>,
+    settings: Gestures,
+    on_gesture: impl Fn(Gesture) -> Message + 'a,
+) -> Element<'a, Message>
+where
+    Message: 'a,
+{
+    #[derive(Default)]
+    struct State {
+        modifiers: keyboard::Modifiers,
+        // Horizontal scroll accumulated since the last swipe
+        scrolled: f32,
+        last_scroll: Option<Instant>,
+        // Swipe already recognized for the ongoing scroll
+        swiped: bool,
+        fingers: HashMap<touch::Finger, Point>,
+        two_fingers: Option<TwoFingers>,
+    }
+
+    struct TwoFingers {
+        start: (Point, Point),
+        current: (Point, Point),
+    }
+
+    impl TwoFingers {
+        fn centroid_delta(&self) -> Vector {
+            midpoint(self.current) - midpoint(self.start)
+        }
+
+        fn distance_delta(&self) -> f32 {
+            distance(self.current) - distance(self.start)
+        }
+
+        fn scale(&self) -> f32 {
+            let start = distance(self.start);
+
+            if start > 0.0 {
+                distance(self.current) / start
+            } else {
+                1.0
+            }
+        }
+    }
+
+    fn midpoint((a, b): (Point, Point)) -> Point {
+        Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)
+    }
+
+    fn distance((a, b): (Point, Point)) -> f32 {
+        a.distance(b)
+    }
+
+    decorate(content)
+        .update(
+            move |state: &mut State,
+                  inner: &mut Element<'a, Message>,
+                  tree: &mut Tree,
+                  event: &iced::Event,
+                  layout: Layout<'_>,
+                  cursor: mouse::Cursor,
+                  renderer: &Renderer,
+                  shell: &mut Shell<'_, Message>,
+                  viewport: &iced::Rectangle| {
+                match event {
+                    iced::Event::Keyboard(
+                        keyboard::Event::ModifiersChanged(modifiers),
+                    ) => {
+                        state.modifiers = *modifiers;
+                    }
+                    iced::Event::Mouse(mouse::Event::WheelScrolled {
+                        delta,
+                    }) if cursor.is_over(layout.bounds()) => {
+                        if settings.pinch_to_zoom && state.modifiers.control() {
+                            let zoom = match delta {
+                                mouse::ScrollDelta::Lines { y, .. } => {
+                                    y * ZOOM_PER_LINE
+                                }
+                                mouse::ScrollDelta::Pixels { y, .. } => {
+                                    y * ZOOM_PER_PIXEL
+                                }
+                            };
+
+                            shell.publish(on_gesture(Gesture::Pinch(
+                                (1.0 + zoom).max(0.1),
+                            )));
+                            shell.capture_event();
+                            return;
+                        }
+
+                        // Only trackpads scroll by pixels
+                        if let mouse::ScrollDelta::Pixels { x, y } = delta
+                            && settings.swipe_buffers
+                            && x.abs() > y.abs()
+                        {
+                            let now = Instant::now();
+
+                            if state.last_scroll.is_none_or(|last| {
+                                now.duration_since(last) > SWIPE_TIMEOUT
+                            }) {
+                                state.scrolled = 0.0;
+                                state.swiped = false;
+                            }
+
+                            state.last_scroll = Some(now);
+                            state.scrolled += x;
+
+                            // Ignore the momentum of a recognized swipe
+                            if !state.swiped
+                                && state.scrolled.abs() >= SWIPE_DISTANCE
+                            {
+                                state.swiped = true;
+
+                                shell.publish(on_gesture(Gesture::Swipe(
+                                    Direction::from_delta(state.scrolled),
+                                )));
+                            }
+
+                            shell.capture_event();
+                            return;
+                        }
+                    }
+                    iced::Event::Touch(touch::Event::FingerPressed {
+                        id,
+                        position,
+                    }) => {
+                        if layout.bounds().contains(*position) {
+                            state.fingers.insert(*id, *position);
+                        }
+
+                        state.two_fingers = if state.fingers.len() == 2 {
+                            let mut fingers = state.fingers.values();
+
+                            fingers.next().zip(fingers.next()).map(|(a, b)| {
+                                TwoFingers {
+                                    start: (*a, *b),
+                                    current: (*a, *b),
+                                }
+                            })
+                        } else {
+                            None
+                        };
+                    }
+                    iced::Event::Touch(touch::Event::FingerMoved {
+                        id,
+                        position,
+                    }) => {
+                        if let Some(finger) = state.fingers.get_mut(id) {
+                            *finger = *position;
+                        }
+
+                        if let Some(two_fingers) = &mut state.two_fingers {
+                            let mut fingers = state.fingers.values();
+
+                            if let Some((a, b)) =
+                                fingers.next().zip(fingers.next())
+                            {
+                                two_fingers.current = (*a, *b);
+                            }
+
+                            // Keep the content from scrolling or selecting
+                            // while two fingers are down
+                            shell.capture_event();
+                            return;
+                        }
+                    }
+                    iced::Event::Touch(
+                        touch::Event::FingerLifted { id, .. }
+                        | touch::Event::FingerLost { id, .. },
+                    ) => {
+                        state.fingers.remove(id);
+
+                        // Recognized once the first finger is lifted, so
+                        // zooming doesn't move the fingers under the
+                        // gesture while it is ongoing
+                        if let Some(two_fingers) = state.two_fingers.take() {
+                            let centroid = two_fingers.centroid_delta();
+                            let pinched = two_fingers.distance_delta().abs();
+
+                            if settings.pinch_to_zoom
+                                && pinched >= PINCH_DISTANCE
+                            {
+                                shell.publish(on_gesture(Gesture::Pinch(
+                                    two_fingers.scale(),
+                                )));
+                            } else if settings.swipe_buffers
+                                && centroid.x.abs() >= SWIPE_DISTANCE
+                                && centroid.x.abs() > centroid.y.abs() * 2.0
+                            {
+                                shell.publish(on_gesture(Gesture::Swipe(
+                                    Direction::from_delta(centroid.x),
+                                )));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+
+                inner.as_widget_mut().update(
+                    tree, event, layout, cursor, renderer, shell, viewport,
+                );
+            },
+        )
+        .into()
+}
@@ -112,8 +112,8 @@ fn message_content_impl<'a, T: Copy + 'a, M: 'a + std::clone::Clone>(
             .and_then(|registry| registry.color(&Query::from(user)))
             .map_or(
                 nickname_color(
-                    theme.styles().buffer.nickname.color,
-                    &config.buffer.nickname.color,
+                    theme.styles(),
+                    &config.buffer.nickname,
                     Some(user.seed()),
                 ),
                 |color| {
@@ -142,10 +142,12 @@ fn message_content_impl<'a, T: Copy + 'a, M: 'a + std::clone::Clone>(
                     .font_maybe(font_style(theme).map(font::get))
                     .size(f32::from(only_emojis_size))
                     .style(style)
+                    .gestures(config.buffer.selection)
             } else {
                 selectable_text(display_text)
                     .font_maybe(font_style(theme).map(font::get))
                     .style(style)
+                    .gestures(config.buffer.selection)
             };
 
             if let Some((default_link, _)) = default_link {
@@ -390,7 +392,8 @@ fn message_content_impl<'a, T: Copy + 'a, M: 'a + std::clone::Clone>(
             )
             .on_link(on_link)
             .font_maybe(font_style(theme).map(font::get))
-            .style(style);
+            .style(style)
+            .gestures(config.buffer.selection);
 
             if let Some((link_entries, view)) = context_menu {
                 text = text.context_menu(link_entries, view);
@@ -406,6 +409,7 @@ fn message_content_impl<'a, T: Copy + 'a, M: 'a + std::clone::Clone>(
 
             selectable_rich_text::<M, message::Link, T, Theme, Renderer>(spans)
                 .style(style)
+                .gestures(config.buffer.selection)
                 .into()
         }
     }
@@ -0,0 +1,205 @@
+use iced::advanced::widget::Tree;
+use iced::advanced::{Layout, Shell};
+use iced::{keyboard, mouse};
+
+use super::{Element, Renderer, decorate};
+
+/// Pixels a trackpad has to scroll to count as one notch of a wheel.
+const PIXELS_PER_STEP: f32 = 50.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Up,
+    Down,
+}
+
+/// Multiplies the distance scrolled by the mouse wheel within `content` by
+/// `speed`.
+pub fn scroll_speed<'a, Message>(
+    content: impl Into<Element<'a, Message>>,
+    speed: f32,
+) -> Element<'a, Message>
+where
+    Message: 'a,
+{
+    decorate(content)
+        .update(
+            move |_state: &mut (),
+                  inner: &mut Element<'a, Message>,
+                  tree: &mut Tree,
+                  event: &iced::Event,
+                  layout: Layout<'_>,
+                  cursor: mouse::Cursor,
+                  renderer: &Renderer,
+                  shell: &mut Shell<'_, Message>,
+                  viewport: &iced::Rectangle| {
+                let scaled;
+
+                let event = match event {
+                    iced::Event::Mouse(mouse::Event::WheelScrolled {
+                        delta,
+                    }) if speed != 1.0 => {
+                        let delta = match *delta {
+                            mouse::ScrollDelta::Lines { x, y } => {
+                                mouse::ScrollDelta::Lines {
+                                    x: x * speed,
+                                    y: y * speed,
+                                }
+                            }
+                            mouse::ScrollDelta::Pixels { x, y } => {
+                                mouse::ScrollDelta::Pixels {
+                                    x: x * speed,
+                                    y: y * speed,
+                                }
+                            }
+                        };
+
+                        scaled =
+                            iced::Event::Mouse(mouse::Event::WheelScrolled {
+                                delta,
+                            });
+                        &scaled
+                    }
+                    _ => event,
+                };
+
+                inner.as_widget_mut().update(
+                    tree, event, layout, cursor, renderer, shell, viewport,
+                );
+            },
+        )
+        .into()
+}
+
+/// Publishes a [`Step`] for every notch the wheel is scrolled over `content`,
+/// instead of scrolling it. Scrolling with shift held is left to `content`.
+pub fn steps<'a, Message>(
+    content: impl Into<Element<'a, Message>>,
+    on_step: impl Fn(Step) -> Message + 'a,
+) -> Element<'a, Message>
+where
+    Message: 'a,
+{
+    #[derive(Default)]
+    struct State {
+        modifiers: keyboard::Modifiers,
+        // Scrolling not yet published as a step, in notches
+        scrolled: f32,
+    }
+
+    decorate(content)
+        .update(
+            move |state: &mut State,
+                  inner: &mut Element<'a, Message>,
+                  tree: &mut Tree,
+                  event: &iced::Event,
+                  layout: Layout<'_>,
+                  cursor: mouse::Cursor,
+                  renderer: &Renderer,
+                  shell: &mut Shell<'_, Message>,
+                  viewport: &iced::Rectangle| {
+                match event {
+                    iced::Event::Keyboard(
+                        keyboard::Event::ModifiersChanged(modifiers),
+                    ) => {
+                        state.modifiers = *modifiers;
+                    }
+                    iced::Event::Mouse(mouse::Event::WheelScrolled {
+                        delta,
+                    }) if !state.modifiers.shift()
+                        && cursor.is_over(layout.bounds()) =>
+                    {
+                        state.scrolled += match *delta {
+                            mouse::ScrollDelta::Lines { y, .. } => y,
+                            mouse::ScrollDelta::Pixels { y, .. } => {
+                                y / PIXELS_PER_STEP
+                            }
+                        };
+
+                        let steps = state.scrolled.trunc();
+                        state.scrolled -= steps;
+                        let steps = steps as i32;
+
+                        // Positive deltas scroll up
+                        let step =
+                            if steps > 0 { Step::Up } else { Step::Down };
+
+                        for _ in 0..steps.unsigned_abs() {
+                            shell.publish(on_step(step));
+                        }
+
+                        shell.capture_event();
+                        return;
+                    }
+                    _ => {}
+                }
+
+                inner.as_widget_mut().update(
+                    tree, event, layout, cursor, renderer, shell, viewport,
+                );
+            },
+        )
+        .into()
+}
+
+/// Lets the vertical wheel scroll a horizontal scrollable within `content`
+/// without holding shift.
+pub fn horizontal<'a, Message>(
+    content: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message>
+where
+    Message: 'a,
+{
+    #[derive(Default)]
+    struct State {
+        modifiers: keyboard::Modifiers,
+    }
+
+    decorate(content)
+        .update(
+            move |state: &mut State,
+                  inner: &mut Element<'a, Message>,
+                  tree: &mut Tree,
+                  event: &iced::Event,
+                  layout: Layout<'_>,
+                  cursor: mouse::Cursor,
+                  renderer: &Renderer,
+                  shell: &mut Shell<'_, Message>,
+                  viewport: &iced::Rectangle| {
+                let rotated;
+
+                let event = match event {
+                    iced::Event::Keyboard(
+                        keyboard::Event::ModifiersChanged(modifiers),
+                    ) => {
+                        state.modifiers = *modifiers;
+                        event
+                    }
+                    iced::Event::Mouse(mouse::Event::WheelScrolled {
+                        delta,
+                    }) if !state.modifiers.shift() => {
+                        let delta = match *delta {
+                            mouse::ScrollDelta::Lines { x, y } => {
+                                mouse::ScrollDelta::Lines { x: x + y, y: 0.0 }
+                            }
+                            mouse::ScrollDelta::Pixels { x, y } => {
+                                mouse::ScrollDelta::Pixels { x: x + y, y: 0.0 }
+                            }
+                        };
+
+                        rotated =
+                            iced::Event::Mouse(mouse::Event::WheelScrolled {
+                                delta,
+                            });
+                        &rotated
+                    }
+                    _ => event,
+                };
+
+                inner.as_widget_mut().update(
+                    tree, event, layout, cursor, renderer, shell, viewport,
+                );
+            },
+        )
+        .into()
+}
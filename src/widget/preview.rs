@@ -83,5 +83,12 @@ pub fn preview_content<'a, M: 'a>(
         .width(Fit.max(config.preview.image.max_width))
         .height(Fit.max(config.preview.image.max_height))
         .into(),
+
+        Preview::Title(preview) => text(&preview.title)
+            .shaping(text::Shaping::Advanced)
+            .wrapping(text::Wrapping::WordOrGlyph)
+            .style(theme::text::secondary)
+            .font_maybe(theme::font_style::secondary(theme).map(font::get))
+            .into(),
     }
 }
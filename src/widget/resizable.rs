@@ -0,0 +1,142 @@
+use iced::advanced::widget::Tree;
+use iced::advanced::{Layout, Shell};
+use iced::{Point, Rectangle, mouse};
+
+use super::{Element, Renderer, decorate};
+
+/// Width of the grab area along the draggable edge.
+const GRAB_WIDTH: f32 = 6.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    Left,
+    Right,
+}
+
+/// Makes `content` resizable by dragging one of its vertical edges.
+///
+/// `on_resize` receives the new width while dragging, and `None` when the
+/// edge is double-clicked to reset the width.
+pub fn resizable<'a, Message>(
+    content: impl Into<Element<'a, Message>>,
+    edge: Edge,
+    on_resize: impl Fn(Option<f32>) -> Message + 'a,
+) -> Element<'a, Message>
+where
+    Message: 'a,
+{
+    #[derive(Default)]
+    struct State {
+        // Cursor x and content width when the drag started
+        dragging: Option<(f32, f32)>,
+        last_click: Option<mouse::Click>,
+    }
+
+    let grab_area = move |bounds: Rectangle| match edge {
+        Edge::Left => Rectangle {
+            width: GRAB_WIDTH,
+            ..bounds
+        },
+        Edge::Right => Rectangle {
+            x: bounds.x + bounds.width - GRAB_WIDTH,
+            width: GRAB_WIDTH,
+            ..bounds
+        },
+    };
+
+    decorate(content)
+        .update(
+            move |state: &mut State,
+                  inner: &mut Element<'a, Message>,
+                  tree: &mut Tree,
+                  event: &iced::Event,
+                  layout: Layout<'_>,
+                  cursor: mouse::Cursor,
+                  renderer: &Renderer,
+                  shell: &mut Shell<'_, Message>,
+                  viewport: &iced::Rectangle| {
+                let bounds = layout.bounds();
+
+                match event {
+                    iced::Event::Mouse(mouse::Event::ButtonPressed {
+                        button: mouse::Button::Left,
+                        ..
+                    }) => {
+                        if let Some(position) =
+                            cursor.position_over(grab_area(bounds))
+                        {
+                            let click = mouse::Click::new(
+                                position,
+                                mouse::Button::Left,
+                                state.last_click,
+                            );
+                            state.last_click = Some(click);
+
+                            if matches!(
+                                click.kind(),
+                                mouse::click::Kind::Double
+                            ) {
+                                state.dragging = None;
+                                shell.publish(on_resize(None));
+                            } else {
+                                state.dragging =
+                                    Some((position.x, bounds.width));
+                            }
+
+                            shell.capture_event();
+                            return;
+                        }
+                    }
+                    iced::Event::Mouse(mouse::Event::CursorMoved {
+                        position: Point { x, .. },
+                    }) => {
+                        if let Some((start_x, start_width)) = state.dragging {
+                            let delta = match edge {
+                                Edge::Left => start_x - x,
+                                Edge::Right => x - start_x,
+                            };
+
+                            shell.publish(on_resize(Some(
+                                (start_width + delta).max(0.0),
+                            )));
+                            shell.capture_event();
+                            return;
+                        }
+                    }
+                    iced::Event::Mouse(mouse::Event::ButtonReleased(
+                        mouse::Button::Left,
+                    )) => {
+                        if state.dragging.take().is_some() {
+                            shell.capture_event();
+                            return;
+                        }
+                    }
+                    _ => {}
+                }
+
+                inner.as_widget_mut().update(
+                    tree, event, layout, cursor, renderer, shell, viewport,
+                );
+            },
+        )
+        .mouse_interaction(
+            move |state: &State,
+                  inner: &Element<'a, Message>,
+                  tree: &Tree,
+                  layout: Layout<'_>,
+                  cursor: mouse::Cursor,
+                  viewport: &iced::Rectangle,
+                  renderer: &Renderer| {
+                if state.dragging.is_some()
+                    || cursor.is_over(grab_area(layout.bounds()))
+                {
+                    mouse::Interaction::ResizingHorizontally
+                } else {
+                    inner.as_widget().mouse_interaction(
+                        tree, layout, cursor, viewport, renderer,
+                    )
+                }
+            },
+        )
+        .into()
+}
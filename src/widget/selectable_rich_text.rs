@@ -56,6 +56,7 @@ pub struct Rich<
     align_y: alignment::Vertical,
     class: Theme::Class<'a>,
     on_link: Option<Box<dyn Fn(Link) -> Message + 'a>>,
+    gestures: selection::Gestures,
 
     #[allow(clippy::type_complexity)]
     context_menu: Option<(
@@ -93,6 +94,7 @@ where
             align_y: alignment::Vertical::Top,
             class: Theme::default(),
             on_link: None,
+            gestures: selection::Gestures::default(),
 
             context_menu: None,
             cached_entries: vec![],
@@ -207,6 +209,16 @@ where
         self
     }
 
+    /// Sets which multi-click gestures expand the selection of the [`Rich`]
+    /// text.
+    pub fn gestures(
+        mut self,
+        gestures: impl Into<selection::Gestures>,
+    ) -> Self {
+        self.gestures = gestures.into();
+        self
+    }
+
     pub fn context_menu(
         self,
         link_entries: impl Fn(&Link) -> Vec<Entry> + 'a,
@@ -248,6 +260,7 @@ struct State<Link, P: Paragraph> {
     link_hovered: bool,
     spoiler_hovered: bool,
     interaction: Interaction,
+    last_click: Option<mouse::Click>,
     shown_spoilers: HashMap<usize, (Color, Highlight)>,
 
     context_menu_link: Option<Link>,
@@ -311,6 +324,7 @@ where
             span_pressed: None,
             paragraph: Renderer::Paragraph::default(),
             interaction: Interaction::default(),
+            last_click: None,
             shown_spoilers: HashMap::new(),
             context_menu_link: None,
             context_menu: context_menu::State::new(),
@@ -427,7 +441,33 @@ where
                     shell.capture_event();
                 }
 
-                if let Some(cursor) = cursor.position() {
+                let expanded =
+                    cursor.position_over(bounds).and_then(|position| {
+                        let click = mouse::Click::new(
+                            position,
+                            mouse::Button::Left,
+                            state.last_click,
+                        );
+                        state.last_click = Some(click);
+
+                        selection::expand(
+                            click,
+                            self.gestures,
+                            bounds,
+                            &state.paragraph,
+                            &Value::new(
+                                &self
+                                    .spans
+                                    .iter()
+                                    .map(|s| s.text.as_ref())
+                                    .join(""),
+                            ),
+                        )
+                    });
+
+                if let Some(raw) = expanded {
+                    state.interaction = Interaction::Selected(raw);
+                } else if let Some(cursor) = cursor.position() {
                     state.interaction =
                         Interaction::Selecting(selection::Raw {
                             start: cursor,
@@ -540,10 +580,12 @@ where
                     }
                 }
 
-                if let Interaction::Selecting(raw) = state.interaction {
-                    state.interaction = Interaction::Selected(raw);
-                } else {
-                    state.interaction = Interaction::Idle;
+                match state.interaction {
+                    Interaction::Selecting(raw) => {
+                        state.interaction = Interaction::Selected(raw);
+                    }
+                    // A double or triple click selection is kept as is
+                    Interaction::Selected(_) | Interaction::Idle => {}
                 }
             }
             iced::Event::Mouse(mouse::Event::CursorMoved { .. })
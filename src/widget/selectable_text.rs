@@ -34,6 +34,7 @@ where
     fragment: Fragment<'a>,
     format: Format<Renderer::Font>,
     class: Theme::Class<'a>,
+    gestures: selection::Gestures,
 }
 
 impl<'a, Theme, Renderer> Text<'a, Theme, Renderer>
@@ -54,6 +55,7 @@ where
                 ..Format::default()
             },
             class: Theme::default(),
+            gestures: selection::Gestures::default(),
         }
     }
 
@@ -127,6 +129,14 @@ where
         self.class = class.into();
         self
     }
+
+    pub fn gestures(
+        mut self,
+        gestures: impl Into<selection::Gestures>,
+    ) -> Self {
+        self.gestures = gestures.into();
+        self
+    }
 }
 
 impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
@@ -208,7 +218,27 @@ where
                 ..
             })
             | iced::Event::Touch(touch::Event::FingerPressed { .. }) => {
-                if let Some(cursor) = cursor.position() {
+                let expanded =
+                    cursor.position_over(bounds).and_then(|position| {
+                        let click = mouse::Click::new(
+                            position,
+                            mouse::Button::Left,
+                            state.last_click,
+                        );
+                        state.last_click = Some(click);
+
+                        selection::expand(
+                            click,
+                            self.gestures,
+                            bounds,
+                            state.paragraph.raw(),
+                            &Value::new(&self.fragment),
+                        )
+                    });
+
+                if let Some(raw) = expanded {
+                    state.interaction = Interaction::Selected(raw);
+                } else if let Some(cursor) = cursor.position() {
                     state.interaction =
                         Interaction::Selecting(selection::Raw {
                             start: cursor,
@@ -223,10 +253,12 @@ where
             ))
             | iced::Event::Touch(touch::Event::FingerLifted { .. })
             | iced::Event::Touch(touch::Event::FingerLost { .. }) => {
-                if let Interaction::Selecting(raw) = state.interaction {
-                    state.interaction = Interaction::Selected(raw);
-                } else {
-                    state.interaction = Interaction::Idle;
+                match state.interaction {
+                    Interaction::Selecting(raw) => {
+                        state.interaction = Interaction::Selected(raw);
+                    }
+                    // A double or triple click selection is kept as is
+                    Interaction::Selected(_) | Interaction::Idle => {}
                 }
             }
             iced::Event::Mouse(mouse::Event::CursorMoved { .. })
@@ -472,6 +504,7 @@ pub struct State<P: Paragraph> {
     paragraph: paragraph::Plain<P>,
     interaction: Interaction,
     hovered: bool,
+    last_click: Option<mouse::Click>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
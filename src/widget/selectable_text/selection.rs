@@ -1,4 +1,4 @@
-use iced::advanced::text;
+use iced::advanced::{mouse, text};
 use iced::{Point, Rectangle, Vector};
 
 use super::Value;
@@ -55,6 +55,33 @@ pub struct Resolved {
     pub end: Point,
 }
 
+/// Which multi-click gestures expand the selection.
+#[derive(Debug, Clone, Copy)]
+pub struct Gestures {
+    /// Double-click selects the word under the cursor
+    pub double_click: bool,
+    /// Triple-click selects the whole text
+    pub triple_click: bool,
+}
+
+impl Default for Gestures {
+    fn default() -> Self {
+        Self {
+            double_click: true,
+            triple_click: true,
+        }
+    }
+}
+
+impl From<data::config::buffer::Selection> for Gestures {
+    fn from(selection: data::config::buffer::Selection) -> Self {
+        Self {
+            double_click: selection.double_click,
+            triple_click: selection.triple_click,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Selection {
     pub start: usize,
@@ -100,6 +127,89 @@ pub fn find_cursor_position<P: text::Paragraph>(
     )
 }
 
+/// Expands a multi-click at `position` into a selection of the word under
+/// the cursor (double-click) or of the whole text (triple-click).
+pub fn expand<P: text::Paragraph>(
+    click: mouse::Click,
+    gestures: Gestures,
+    bounds: Rectangle,
+    paragraph: &P,
+    value: &Value,
+) -> Option<Raw> {
+    let position = click.position();
+
+    match click.kind() {
+        mouse::click::Kind::Single => None,
+        mouse::click::Kind::Double => {
+            if !gestures.double_click {
+                return None;
+            }
+
+            let cursor = find_cursor_position(
+                paragraph,
+                value,
+                relative(position, bounds),
+            )?;
+
+            let start = value.previous_start_of_word(cursor);
+            let end = value.next_end_of_word(cursor);
+
+            let start_x =
+                boundary_x(paragraph, value, bounds, position.y, start)?;
+            let end_x = boundary_x(paragraph, value, bounds, position.y, end)
+                .unwrap_or(bounds.x + bounds.width);
+
+            Some(Raw {
+                start: Point::new(start_x, position.y),
+                end: Point::new(end_x, position.y),
+            })
+        }
+        // Keep the end point inside the last row so it still hit-tests
+        mouse::click::Kind::Triple => gestures.triple_click.then(|| Raw {
+            start: bounds.position(),
+            end: bounds.position()
+                + Vector::new(bounds.width, (bounds.height - 1.0).max(0.0)),
+        }),
+    }
+}
+
+/// Finds the leftmost x coordinate on the row at `y` whose cursor position is
+/// at or past `index`.
+///
+/// Selections are stored as points, so word boundaries have to be mapped back
+/// to a position that hit-tests to the same grapheme.
+fn boundary_x<P: text::Paragraph>(
+    paragraph: &P,
+    value: &Value,
+    bounds: Rectangle,
+    y: f32,
+    index: usize,
+) -> Option<f32> {
+    let cursor_at = |x: f32| {
+        find_cursor_position(paragraph, value, Point::new(x, y - bounds.y))
+    };
+
+    let mut low = 0.0;
+    let mut high = bounds.width;
+
+    if cursor_at(high)? < index {
+        return None;
+    }
+
+    // Hit-testing is monotonic along a row
+    for _ in 0..16 {
+        let mid = (low + high) / 2.0;
+
+        if cursor_at(mid).is_some_and(|cursor| cursor >= index) {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+
+    Some(bounds.x + high)
+}
+
 fn relative(point: Point, bounds: Rectangle) -> Point {
     point - Vector::new(bounds.x, bounds.y)
 }
@@ -321,20 +321,29 @@ impl UserDisplayData {
             dimmed,
         );
 
-        self.render(style, is_offline, size, selectable, theme, line_height)
+        self.render(
+            style,
+            is_away,
+            is_offline,
+            size,
+            selectable,
+            theme,
+            line_height,
+        )
     }
 
     fn render<'a, M: 'a>(
         self,
         style: crate::widget::selectable_text::Style,
+        is_away: bool,
         is_offline: bool,
         size: Option<f32>,
         selectable: bool,
         theme: &'a Theme,
         line_height: iced::widget::text::LineHeight,
     ) -> Element<'a, M> {
-        let font =
-            theme::font_style::nickname(theme, is_offline).map(font::get);
+        let font = theme::font_style::nickname(theme, is_away, is_offline)
+            .map(font::get);
 
         // selectable_text carries selection state and handles copy interactions;
         // plain text is used where selection would be undesirable (e.g. input bar)
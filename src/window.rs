@@ -73,6 +73,7 @@ impl From<Window> for data::Window {
             size,
             fullscreen: window.fullscreen,
             maximized: window.maximized,
+            ..data::Window::default()
         }
     }
 }
@@ -97,6 +98,23 @@ pub fn toggle_fullscreen<Message: 'static + Send>() -> Task<Message> {
     })
 }
 
+/// Moves and resizes the window to a saved state.
+pub fn restore<Message: 'static + Send>(
+    id: Id,
+    window: data::Window,
+) -> Task<Message> {
+    if window.maximized {
+        return maximize(id, true);
+    }
+
+    let resize = iced::window::resize(id, window.size);
+
+    match window.position {
+        Some(position) => resize.chain(iced::window::move_to(id, position)),
+        None => resize,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Event {
     Moved(Point),